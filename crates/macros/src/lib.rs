@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
@@ -80,11 +82,72 @@ fn pascal_case(filename: &str) -> String {
         .collect()
 }
 
+/// The name of the optional metadata file in the icons directory.
+const METADATA_FILE: &str = "icons.json";
+
+/// The tags and categories of an icon.
+#[derive(Debug, Default, PartialEq)]
+struct IconMetadata {
+    tags: Vec<String>,
+    categories: Vec<String>,
+}
+
+/// Parse the icons metadata, keyed by the icon name (the file name without `.svg`).
+///
+/// ```json
+/// {
+///   "arrow-right": { "tags": ["forward", "next"], "categories": ["arrows"] }
+/// }
+/// ```
+fn parse_metadata(content: &str) -> Result<HashMap<String, IconMetadata>, String> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let Some(icons) = value.as_object() else {
+        return Err("expected an object".to_string());
+    };
+
+    let strings = |value: &serde_json::Value, key: &str| -> Vec<String> {
+        value
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    Ok(icons
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.clone(),
+                IconMetadata {
+                    tags: strings(value, "tags"),
+                    categories: strings(value, "categories"),
+                },
+            )
+        })
+        .collect())
+}
+
 /// Generate a custom icon enum and its `IconNamed` impl by scanning a directory of SVG files.
 ///
 /// Accepts an enum name, a path, and optionally a list of additional derive traits.
 /// Each `.svg` file becomes an enum variant using PascalCase conversion.
 ///
+/// Besides the `IconNamed` impl, the enum gets `all()`, `name()`, `from_name()`, `tags()`,
+/// `categories()` and `matches()` methods for building icon pickers and serializing the
+/// icon choices. The tags and categories are read from an optional `icons.json` in the icons
+/// directory, keyed by the icon name (the file name without `.svg`):
+///
+/// ```json
+/// {
+///   "arrow-right": { "tags": ["forward", "next"], "categories": ["arrows"] }
+/// }
+/// ```
+///
 /// The path may be either:
 ///
 /// - **A literal path** (the common case), resolved relative to the calling crate's
@@ -140,7 +203,27 @@ pub fn icon_named(input: TokenStream) -> TokenStream {
         std::path::Path::new(&manifest_dir).join(&raw_path)
     };
 
-    let mut entries: Vec<(String, String)> = Vec::new();
+    let metadata_path = icons_dir.join(METADATA_FILE);
+    let mut metadata = if metadata_path.is_file() {
+        let content = std::fs::read_to_string(&metadata_path).unwrap_or_else(|e| {
+            panic!(
+                "icon_named: failed to read '{}': {}",
+                metadata_path.display(),
+                e
+            )
+        });
+        parse_metadata(&content).unwrap_or_else(|e| {
+            panic!(
+                "icon_named: failed to parse '{}': {}",
+                metadata_path.display(),
+                e
+            )
+        })
+    } else {
+        HashMap::new()
+    };
+
+    let mut entries: Vec<(String, String, String)> = Vec::new();
 
     let dir = std::fs::read_dir(&icons_dir).unwrap_or_else(|e| {
         panic!(
@@ -155,8 +238,9 @@ pub fn icon_named(input: TokenStream) -> TokenStream {
         let filename = entry.file_name().to_string_lossy().to_string();
         if filename.ends_with(".svg") {
             let variant_name = pascal_case(&filename);
+            let name = filename.trim_end_matches(".svg").to_string();
             let path = format!("icons/{}", filename);
-            entries.push((variant_name, path));
+            entries.push((variant_name, name, path));
         }
     }
 
@@ -164,9 +248,17 @@ pub fn icon_named(input: TokenStream) -> TokenStream {
 
     let variants: Vec<proc_macro2::Ident> = entries
         .iter()
-        .map(|(name, _)| proc_macro2::Ident::new(name, proc_macro2::Span::call_site()))
+        .map(|(variant, _, _)| proc_macro2::Ident::new(variant, proc_macro2::Span::call_site()))
         .collect();
-    let paths: Vec<&str> = entries.iter().map(|(_, p)| p.as_str()).collect();
+    let names: Vec<&str> = entries.iter().map(|(_, name, _)| name.as_str()).collect();
+    let paths: Vec<&str> = entries.iter().map(|(_, _, path)| path.as_str()).collect();
+    let (tags, categories): (Vec<Vec<String>>, Vec<Vec<String>>) = entries
+        .iter()
+        .map(|(_, name, _)| {
+            let meta = metadata.remove(name).unwrap_or_default();
+            (meta.tags, meta.categories)
+        })
+        .unzip();
 
     // Build derive list: always include IntoElement and Clone, then add custom derives
     let derive_attrs = if let Some((_, custom_derives)) = derives {
@@ -195,6 +287,54 @@ pub fn icon_named(input: TokenStream) -> TokenStream {
                 .into()
             }
         }
+
+        impl #enum_name {
+            /// Returns all the icons, sorted by the variant name.
+            pub fn all() -> &'static [Self] {
+                &[#(Self::#variants,)*]
+            }
+
+            /// Returns the name of the icon, which is the SVG file name without `.svg`,
+            /// e.g. `arrow-right`.
+            ///
+            /// Use this to serialize the icon, and [`Self::from_name`] to deserialize it.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+
+            /// Returns the icon with the given name, see [`Self::name`].
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #(#names => Some(Self::#variants),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the search tags of the icon.
+            pub fn tags(&self) -> &'static [&'static str] {
+                match self {
+                    #(Self::#variants => &[#(#tags),*],)*
+                }
+            }
+
+            /// Returns the categories of the icon.
+            pub fn categories(&self) -> &'static [&'static str] {
+                match self {
+                    #(Self::#variants => &[#(#categories),*],)*
+                }
+            }
+
+            /// Returns true if the name or one of the tags of the icon contains the query,
+            /// case-insensitive. An empty query matches all icons.
+            pub fn matches(&self, query: &str) -> bool {
+                let query = query.trim().to_lowercase();
+                query.is_empty()
+                    || self.name().to_lowercase().contains(&query)
+                    || self.tags().iter().any(|tag| tag.to_lowercase().contains(&query))
+            }
+        }
     };
 
     TokenStream::from(expanded)
@@ -204,6 +344,31 @@ pub fn icon_named(input: TokenStream) -> TokenStream {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_metadata() {
+        let metadata = parse_metadata(
+            r#"{
+                "arrow-right": { "tags": ["forward", "next"], "categories": ["arrows"] },
+                "bell": { "tags": ["alarm"] }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(
+            metadata["arrow-right"],
+            IconMetadata {
+                tags: vec!["forward".into(), "next".into()],
+                categories: vec!["arrows".into()],
+            }
+        );
+        assert_eq!(metadata["bell"].tags, vec!["alarm".to_string()]);
+        assert!(metadata["bell"].categories.is_empty());
+
+        assert!(parse_metadata("[]").is_err());
+        assert!(parse_metadata("{").is_err());
+    }
+
     #[test]
     fn test_pascal_case_basic() {
         assert_eq!(pascal_case("arrow-right.svg"), "ArrowRight");
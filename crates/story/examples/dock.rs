@@ -26,8 +26,18 @@ pub struct AddPanel(DockPlacement);
 #[action(namespace = story, no_json)]
 pub struct TogglePanelVisible(SharedString);
 
+#[derive(Action, Clone, PartialEq, Eq, Deserialize)]
+#[action(namespace = story, no_json)]
+pub struct SaveLayoutPreset(SharedString);
+
+#[derive(Action, Clone, PartialEq, Eq, Deserialize)]
+#[action(namespace = story, no_json)]
+pub struct ApplyLayoutPreset(SharedString);
+
 actions!(story, [ToggleDockToggleButton]);
 
+const LAYOUT_PRESETS: [&str; 3] = ["Coding", "Debugging", "Review"];
+
 const MAIN_DOCK_AREA: DockAreaTab = DockAreaTab {
     id: "main-dock",
     version: 5,
@@ -65,8 +75,12 @@ struct DockAreaTab {
 
 impl StoryWorkspace {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let dock_area =
-            cx.new(|cx| DockArea::new(MAIN_DOCK_AREA.id, Some(MAIN_DOCK_AREA.version), window, cx));
+        let dock_area = cx.new(|cx| {
+            let mut dock_area =
+                DockArea::new(MAIN_DOCK_AREA.id, Some(MAIN_DOCK_AREA.version), window, cx);
+            dock_area.set_tear_out(true, window, cx);
+            dock_area
+        });
         let weak_dock_area = dock_area.downgrade();
 
         match Self::load_layout(dock_area.clone(), window, cx) {
@@ -103,6 +117,7 @@ impl StoryWorkspace {
 
         let title_bar = cx.new(|cx| {
             AppTitleBar::new("Examples", window, cx).child({
+                let weak_dock_area = dock_area.downgrade();
                 move |_, cx| {
                     Button::new("add-panel")
                         .icon(IconName::LayoutDashboard)
@@ -110,8 +125,20 @@ impl StoryWorkspace {
                         .ghost()
                         .dropdown_menu({
                             let invisible_panels = AppState::global(cx).invisible_panels.clone();
+                            let weak_dock_area = weak_dock_area.clone();
 
                             move |menu, _, cx| {
+                                let (saved_layouts, active_layout) = weak_dock_area
+                                    .upgrade()
+                                    .map(|dock_area| {
+                                        let dock_area = dock_area.read(cx);
+                                        (
+                                            dock_area.layouts().cloned().collect::<Vec<_>>(),
+                                            dock_area.active_layout().cloned(),
+                                        )
+                                    })
+                                    .unwrap_or_default();
+
                                 menu.menu(
                                     "Add Panel to Center",
                                     Box::new(AddPanel(DockPlacement::Center)),
@@ -160,6 +187,25 @@ impl StoryWorkspace {
                                         .contains(&SharedString::from("List")),
                                     Box::new(TogglePanelVisible(SharedString::from("List"))),
                                 )
+                                .separator()
+                                .map(|menu| {
+                                    LAYOUT_PRESETS.iter().fold(menu, |menu, name| {
+                                        menu.menu(
+                                            format!("Save Layout as {}", name),
+                                            Box::new(SaveLayoutPreset(SharedString::from(*name))),
+                                        )
+                                    })
+                                })
+                                .when(!saved_layouts.is_empty(), |menu| menu.separator())
+                                .map(|menu| {
+                                    saved_layouts.into_iter().fold(menu, |menu, name| {
+                                        menu.menu_with_check(
+                                            format!("{} Layout", name),
+                                            active_layout.as_ref() == Some(&name),
+                                            Box::new(ApplyLayoutPreset(name)),
+                                        )
+                                    })
+                                })
                             }
                         })
                         .anchor(Anchor::TopRight)
@@ -254,6 +300,15 @@ impl StoryWorkspace {
                 window,
                 cx,
             );
+            dock_area.set_dock_tool_strip(
+                Edges {
+                    left: true,
+                    right: true,
+                    ..Default::default()
+                },
+                window,
+                cx,
+            );
 
             Ok::<(), anyhow::Error>(())
         })
@@ -338,32 +393,36 @@ impl StoryWorkspace {
         cx: &mut App,
     ) -> DockItem {
         DockItem::v_split(
-            vec![DockItem::tabs(
-                vec![
-                    Arc::new(StoryContainer::panel::<ButtonStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<InputStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<SelectStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<LabelStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<DialogStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<PopoverStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<SwitchStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<ProgressStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<DataTableStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<ImageStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<IconStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<TooltipStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<CalendarStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<ResizableStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<ScrollbarStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<AccordionStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<SidebarStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<FormStory>(window, cx)),
-                    Arc::new(StoryContainer::panel::<NotificationStory>(window, cx)),
-                ],
-                &dock_area,
-                window,
-                cx,
-            )],
+            vec![
+                DockItem::tabs(
+                    vec![
+                        Arc::new(StoryContainer::panel::<ButtonStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<InputStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<SelectStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<LabelStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<DialogStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<PopoverStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<SwitchStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<ProgressStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<DataTableStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<ImageStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<IconStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<TooltipStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<CalendarStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<ResizableStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<ScrollbarStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<AccordionStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<SidebarStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<FormStory>(window, cx)),
+                        Arc::new(StoryContainer::panel::<NotificationStory>(window, cx)),
+                    ],
+                    &dock_area,
+                    window,
+                    cx,
+                )
+                .lazy(cx)
+                .keep_alive(true, cx),
+            ],
             &dock_area,
             window,
             cx,
@@ -470,6 +529,30 @@ impl StoryWorkspace {
         cx.notify();
     }
 
+    fn on_action_save_layout_preset(
+        &mut self,
+        action: &SaveLayoutPreset,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.dock_area.update(cx, |dock_area, cx| {
+            dock_area.save_layout(action.0.clone(), cx);
+        });
+    }
+
+    fn on_action_apply_layout_preset(
+        &mut self,
+        action: &ApplyLayoutPreset,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.dock_area.update(cx, |dock_area, cx| {
+            if let Err(err) = dock_area.apply_layout(&action.0, window, cx) {
+                eprintln!("apply layout error: {:?}", err);
+            }
+        });
+    }
+
     fn on_action_toggle_dock_toggle_button(
         &mut self,
         _: &ToggleDockToggleButton,
@@ -509,6 +592,8 @@ impl Render for StoryWorkspace {
             .on_action(cx.listener(Self::on_action_add_panel))
             .on_action(cx.listener(Self::on_action_toggle_panel_visible))
             .on_action(cx.listener(Self::on_action_toggle_dock_toggle_button))
+            .on_action(cx.listener(Self::on_action_save_layout_preset))
+            .on_action(cx.listener(Self::on_action_apply_layout_preset))
             .relative()
             .size_full()
             .flex()
@@ -518,7 +603,9 @@ impl Render for StoryWorkspace {
             .child(
                 StatusBar::new()
                     .left(
-                        Button::new("toggle-left-dock").ghost().xsmall()
+                        Button::new("toggle-left-dock")
+                            .ghost()
+                            .xsmall()
                             .icon(IconName::PanelLeft)
                             .tooltip("Toggle Left Dock")
                             .on_click(cx.listener(|this, _, window, cx| {
@@ -528,7 +615,9 @@ impl Render for StoryWorkspace {
                             })),
                     )
                     .left(
-                        Button::new("toggle-bottom-dock").ghost().xsmall()
+                        Button::new("toggle-bottom-dock")
+                            .ghost()
+                            .xsmall()
                             .icon(IconName::PanelBottom)
                             .tooltip("Toggle Bottom Dock")
                             .on_click(cx.listener(|this, _, window, cx| {
@@ -538,7 +627,9 @@ impl Render for StoryWorkspace {
                             })),
                     )
                     .child(
-                        Button::new("toggle-right-dock").ghost().xsmall()
+                        Button::new("toggle-right-dock")
+                            .ghost()
+                            .xsmall()
                             .icon(IconName::PanelRight)
                             .tooltip("Toggle Right Dock")
                             .on_click(cx.listener(|this, _, window, cx| {
@@ -15,8 +15,11 @@ use gpui_component::{
     h_flex,
     highlighter::{Diagnostic, DiagnosticSeverity, Language, LanguageConfig, LanguageRegistry},
     input::{
-        self, CodeActionProvider, CompletionProvider, DefinitionProvider, DocumentColorProvider,
-        HoverProvider, Input, InputEvent, InputState, Position, Rope, RopeExt, TabSize,
+        self, CodeActionProvider, CompletionProvider, DefinitionProvider, DiagnosticsPanel,
+        DiagnosticsPanelState, DocumentColorProvider, FormattingProvider, GutterMark,
+        HoverProvider, InlayHintProvider, Input, InputEvent, InputState, LanguageSettings,
+        LanguageSettingsTable, LineNumberMode, Position, RenameProvider, Rope, RopeExt,
+        SignatureHelpProvider,
     },
     list::ListItem,
     resizable::{h_resizable, resizable_panel},
@@ -28,8 +31,8 @@ use gpui_component_assets::Assets;
 use gpui_component_story::Open;
 use lsp_types::{
     CodeAction, CodeActionKind, CompletionContext, CompletionItem, CompletionResponse,
-    CompletionTextEdit, InlineCompletionContext, InlineCompletionItem, InlineCompletionResponse,
-    InsertReplaceEdit, InsertTextFormat, TextEdit, WorkspaceEdit,
+    CompletionTextEdit, FormattingOptions, InlineCompletionContext, InlineCompletionItem,
+    InlineCompletionResponse, InsertReplaceEdit, InsertTextFormat, TextEdit, WorkspaceEdit,
 };
 
 enum Lang {
@@ -69,6 +72,8 @@ fn init() {
 
 pub struct Example {
     editor: Entity<InputState>,
+    /// The text of the opened file, to show the changed lines in the gutter.
+    base_text: SharedString,
     tree_state: Entity<TreeState>,
     go_to_line_state: Entity<InputState>,
     language: Lang,
@@ -76,10 +81,18 @@ pub struct Example {
     indent_guides: bool,
     soft_wrap: bool,
     show_whitespaces: bool,
+    show_line_endings: bool,
+    relative_line_numbers: bool,
+    minimap: bool,
+    sticky_scroll: bool,
+    vim_mode: bool,
     folding: bool,
     disabled: bool,
     scroll_beyond_last_line: Option<usize>,
     cursor_surrounding_lines: Option<usize>,
+    diagnostics_panel: Entity<DiagnosticsPanelState>,
+    show_problems: bool,
+    split_editor: Option<Entity<InputState>>,
     lsp_store: ExampleLspStore,
     _subscriptions: Vec<Subscription>,
     _lint_task: Task<()>,
@@ -156,6 +169,19 @@ fn completion_item(
     }
 }
 
+fn snippet_item(
+    replace_range: &lsp_types::Range,
+    label: &str,
+    snippet: &str,
+    documentation: &str,
+) -> CompletionItem {
+    CompletionItem {
+        kind: Some(lsp_types::CompletionItemKind::SNIPPET),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        ..completion_item(replace_range, label, snippet, documentation)
+    }
+}
+
 impl CompletionProvider for ExampleLspStore {
     fn completions(
         &self,
@@ -196,6 +222,18 @@ impl CompletionProvider for ExampleLspStore {
                     completion_item(&replace_range, "/smile", "😊", "Insert 😊"),
                     completion_item(&replace_range, "/sad", "😢", "Insert 😢"),
                     completion_item(&replace_range, "/launch", "🚀", "Insert 🚀"),
+                    snippet_item(
+                        &replace_range,
+                        "/fn",
+                        "fn ${1:name}(${2:args}) {\n    $0\n}",
+                        "Insert a function",
+                    ),
+                    snippet_item(
+                        &replace_range,
+                        "/let",
+                        "let ${1:value} = ${2:expr};\nprintln!(\"{}\", $1);$0",
+                        "Insert a let binding",
+                    ),
                 ];
                 return Ok(CompletionResponse::Array(items));
             }
@@ -656,6 +694,265 @@ impl DocumentColorProvider for ExampleLspStore {
     }
 }
 
+impl InlayHintProvider for ExampleLspStore {
+    fn inlay_hints(
+        &self,
+        text: &Rope,
+        range: Range<usize>,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<gpui::Result<Vec<lsp_types::InlayHint>>> {
+        // A naive type hint for `let name = <literal>;`, just for demo.
+        let start_row = text.offset_to_point(range.start).row;
+        let end_row = text.offset_to_point(range.end).row;
+
+        let mut hints = vec![];
+        for row in start_row..=end_row {
+            let line = text.slice_line(row).to_string();
+            let Some(rest) = line.trim_start().strip_prefix("let ") else {
+                continue;
+            };
+            let rest = rest.strip_prefix("mut ").unwrap_or(rest);
+            let Some((name, value)) = rest.split_once('=') else {
+                continue;
+            };
+            let name = name.trim();
+            if name.is_empty()
+                || name.contains(':')
+                || !name.chars().all(|c| c.is_alphanumeric() || c == '_')
+            {
+                continue;
+            }
+
+            let value = value.trim().trim_end_matches(';');
+            let ty = if value.parse::<i64>().is_ok() {
+                "i32"
+            } else if value.parse::<f64>().is_ok() {
+                "f64"
+            } else if value == "true" || value == "false" {
+                "bool"
+            } else if value.starts_with('"') {
+                "&str"
+            } else {
+                continue;
+            };
+
+            let name_end = line.len() - rest.len() + rest.find(name).unwrap_or(0) + name.len();
+            let column = line[..name_end].encode_utf16().count() as u32;
+            hints.push(lsp_types::InlayHint {
+                position: lsp_types::Position::new(row as u32, column),
+                label: lsp_types::InlayHintLabel::String(format!(": {}", ty)),
+                kind: Some(lsp_types::InlayHintKind::TYPE),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: None,
+                data: None,
+            });
+        }
+
+        Task::ready(Ok(hints))
+    }
+}
+
+/// The signatures for the signature help demo, multiple labels are the overloads.
+const EXAMPLE_SIGNATURES: &[(&str, &[&str])] = &[
+    (
+        "max",
+        &[
+            "fn max(a: i32, b: i32) -> i32",
+            "fn max(a: f64, b: f64) -> f64",
+        ],
+    ),
+    ("clamp", &["fn clamp(self, min: Self, max: Self) -> Self"]),
+    (
+        "insert",
+        &["fn insert(&mut self, index: usize, element: T)"],
+    ),
+    (
+        "with_capacity",
+        &["fn with_capacity(capacity: usize) -> Vec<T>"],
+    ),
+];
+
+impl SignatureHelpProvider for ExampleLspStore {
+    fn signature_help(
+        &self,
+        text: &Rope,
+        offset: usize,
+        _context: lsp_types::SignatureHelpContext,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Result<Option<lsp_types::SignatureHelp>>> {
+        // Find the unclosed `(` before the cursor in the current line, just for demo.
+        let line_start = text.line_start_offset(text.offset_to_point(offset).row);
+        let before = text.slice(line_start..offset).to_string();
+        let mut depth = 0;
+        let mut active_parameter = 0;
+        let mut open_ix = None;
+        for (ix, c) in before.char_indices().rev() {
+            match c {
+                ')' => depth += 1,
+                '(' if depth > 0 => depth -= 1,
+                '(' => {
+                    open_ix = Some(ix);
+                    break;
+                }
+                ',' if depth == 0 => active_parameter += 1,
+                _ => {}
+            }
+        }
+        let Some(open_ix) = open_ix else {
+            return Task::ready(Ok(None));
+        };
+
+        let name = before[..open_ix]
+            .rsplit(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or_default();
+        let Some((_, labels)) = EXAMPLE_SIGNATURES.iter().find(|(n, _)| *n == name) else {
+            return Task::ready(Ok(None));
+        };
+
+        let signatures = labels
+            .iter()
+            .map(|label| {
+                let params = label
+                    .split_once('(')
+                    .and_then(|(_, rest)| rest.split_once(')'))
+                    .map(|(params, _)| params)
+                    .unwrap_or_default();
+                lsp_types::SignatureInformation {
+                    label: label.to_string(),
+                    documentation: None,
+                    parameters: Some(
+                        params
+                            .split(", ")
+                            .map(|param| lsp_types::ParameterInformation {
+                                label: lsp_types::ParameterLabel::Simple(param.to_string()),
+                                documentation: None,
+                            })
+                            .collect(),
+                    ),
+                    active_parameter: None,
+                }
+            })
+            .collect();
+
+        Task::ready(Ok(Some(lsp_types::SignatureHelp {
+            signatures,
+            active_signature: Some(0),
+            active_parameter: Some(active_parameter),
+        })))
+    }
+}
+
+impl RenameProvider for ExampleLspStore {
+    fn rename(
+        &self,
+        text: &Rope,
+        offset: usize,
+        new_name: &str,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Result<Vec<TextEdit>>> {
+        // Rename all the whole word occurrences, just for demo.
+        let word = text.word_at(offset);
+        if word.is_empty() {
+            return Task::ready(Ok(vec![]));
+        }
+
+        let content = text.to_string();
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+        let edits = content
+            .match_indices(&word)
+            .filter(|(ix, _)| {
+                let before = content[..*ix].chars().next_back();
+                let after = content[ix + word.len()..].chars().next();
+                !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+            })
+            .map(|(ix, _)| TextEdit {
+                range: lsp_types::Range::new(
+                    text.offset_to_position(ix),
+                    text.offset_to_position(ix + word.len()),
+                ),
+                new_text: new_name.to_string(),
+            })
+            .collect();
+
+        Task::ready(Ok(edits))
+    }
+}
+
+/// Format the lines in the rows, just for demo: re-indent the leading whitespace by the
+/// options and trim the trailing whitespace.
+fn format_lines(text: &Rope, rows: Range<usize>, options: &FormattingOptions) -> Vec<TextEdit> {
+    let mut edits = vec![];
+    for row in rows {
+        let line = text.slice_line(row).to_string();
+        let line = line.trim_end_matches(['\r', '\n']);
+        let content = line.trim();
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let width = indent
+            .chars()
+            .map(|c| {
+                if c == '\t' {
+                    options.tab_size as usize
+                } else {
+                    1
+                }
+            })
+            .sum::<usize>();
+        let level = width / options.tab_size.max(1) as usize;
+        let new_indent = if options.insert_spaces {
+            " ".repeat(level * options.tab_size as usize)
+        } else {
+            "\t".repeat(level)
+        };
+
+        let new_line = if content.is_empty() {
+            String::new()
+        } else {
+            format!("{}{}", new_indent, content)
+        };
+        if new_line != line {
+            edits.push(TextEdit {
+                range: lsp_types::Range::new(
+                    lsp_types::Position::new(row as u32, 0),
+                    lsp_types::Position::new(row as u32, line.encode_utf16().count() as u32),
+                ),
+                new_text: new_line,
+            });
+        }
+    }
+    edits
+}
+
+impl FormattingProvider for ExampleLspStore {
+    fn format(
+        &self,
+        text: &Rope,
+        options: FormattingOptions,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Result<Vec<TextEdit>>> {
+        Task::ready(Ok(format_lines(text, 0..text.lines_len(), &options)))
+    }
+
+    fn format_range(
+        &self,
+        text: &Rope,
+        range: Range<usize>,
+        options: FormattingOptions,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Result<Vec<TextEdit>>> {
+        let start_row = text.offset_to_point(range.start).row;
+        let end_row = text.offset_to_point(range.end).row;
+        Task::ready(Ok(format_lines(text, start_row..end_row + 1, &options)))
+    }
+}
+
 fn build_file_items(ignorer: &Ignorer, root: &PathBuf, path: &PathBuf) -> Vec<TreeItem> {
     let mut items = Vec::new();
 
@@ -700,11 +997,27 @@ impl Example {
                 .code_editor(default_language.name().to_string())
                 .line_number(true)
                 .indent_guides(true)
-                .tab_size(TabSize {
-                    tab_size: 4,
-                    hard_tabs: false,
-                })
+                .language_settings(
+                    LanguageSettingsTable::new()
+                        .default_settings(
+                            LanguageSettings::new()
+                                .tab_size(4)
+                                .rulers([100])
+                                .trim_trailing_whitespace(true),
+                        )
+                        .language("python", LanguageSettings::new().rulers([79]))
+                        .language("go", LanguageSettings::new().hard_tabs(true))
+                        .language(
+                            "markdown",
+                            LanguageSettings::new()
+                                .tab_size(2)
+                                .rulers(Vec::new())
+                                .trim_trailing_whitespace(false),
+                        ),
+                )
                 .soft_wrap(false)
+                .minimap(true)
+                .sticky_scroll(true)
                 .default_value(include_str!("./fixtures/test.rs"))
                 .placeholder("Enter your code here...");
 
@@ -713,7 +1026,12 @@ impl Example {
             editor.lsp.code_action_providers = vec![lsp_store.clone(), Rc::new(TextConvertor)];
             editor.lsp.hover_provider = Some(lsp_store.clone());
             editor.lsp.definition_provider = Some(lsp_store.clone());
+            editor.lsp.document_uri = Some(lsp_types::Uri::from_str("file://example").unwrap());
             editor.lsp.document_color_provider = Some(lsp_store.clone());
+            editor.lsp.inlay_hint_provider = Some(lsp_store.clone());
+            editor.lsp.signature_help_provider = Some(lsp_store.clone());
+            editor.lsp.rename_provider = Some(lsp_store.clone());
+            editor.lsp.formatting_provider = Some(lsp_store.clone());
 
             editor
         });
@@ -726,16 +1044,29 @@ impl Example {
         });
 
         let go_to_line_state = cx.new(|cx| InputState::new(window, cx));
+        let diagnostics_panel = cx.new(|cx| {
+            let mut panel = DiagnosticsPanelState::new(window, cx);
+            panel.add_input("test.rs", &editor, cx);
+            panel
+        });
 
         let tree_state = cx.new(|cx| TreeState::new(cx));
         Self::load_files(tree_state.clone(), PathBuf::from("./"), cx);
 
-        let _subscriptions = vec![cx.subscribe(&editor, |this, _editor, _: &InputEvent, cx| {
-            this.lint_document(cx);
-        })];
+        let _subscriptions = vec![cx.subscribe_in(
+            &editor,
+            window,
+            |this, _editor, event: &InputEvent, window, cx| {
+                this.lint_document(cx);
+                if let InputEvent::Change = event {
+                    this.update_gutter_marks(window, cx);
+                }
+            },
+        )];
 
         Self {
             editor,
+            base_text: include_str!("./fixtures/test.rs").into(),
             tree_state,
             go_to_line_state,
             language: default_language,
@@ -743,10 +1074,18 @@ impl Example {
             indent_guides: true,
             soft_wrap: false,
             show_whitespaces: false,
+            show_line_endings: false,
+            relative_line_numbers: false,
+            minimap: true,
+            sticky_scroll: true,
+            vim_mode: false,
             folding: true,
             disabled: false,
             scroll_beyond_last_line: None,
             cursor_surrounding_lines: None,
+            diagnostics_panel,
+            show_problems: false,
+            split_editor: None,
             lsp_store,
             _subscriptions,
             _lint_task: Task::ready(()),
@@ -811,6 +1150,57 @@ impl Example {
         });
     }
 
+    /// Update the gutter marks by a simple line diff against the opened text.
+    fn update_gutter_marks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.editor.read(cx).value();
+        let base_lines = self.base_text.lines().collect::<Vec<_>>();
+        let lines = text.lines().collect::<Vec<_>>();
+
+        let prefix = base_lines
+            .iter()
+            .zip(lines.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let suffix = base_lines[prefix..]
+            .iter()
+            .rev()
+            .zip(lines[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let old_rows = prefix..base_lines.len() - suffix;
+        let new_rows = prefix..lines.len() - suffix;
+
+        let mark = if old_rows.is_empty() && new_rows.is_empty() {
+            None
+        } else if old_rows.is_empty() {
+            Some(GutterMark::added(new_rows))
+        } else if new_rows.is_empty() {
+            Some(GutterMark::deleted(prefix))
+        } else {
+            Some(GutterMark::modified(new_rows))
+        };
+
+        let marks = mark
+            .map(|mut mark| {
+                if !old_rows.is_empty() {
+                    mark = mark.preview(base_lines[old_rows.clone()].join("\n"));
+                }
+
+                let editor = self.editor.clone();
+                mark.on_click(move |_, window, cx| {
+                    editor.update(cx, |state, cx| {
+                        state.set_cursor_position(Position::new(prefix as u32, 0), window, cx);
+                    });
+                })
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        self.editor.update(cx, |state, cx| {
+            state.set_gutter_marks(marks, window, cx);
+        });
+    }
+
     fn lint_document(&mut self, cx: &mut Context<Self>) {
         let language = self.language.name().to_string();
         let lsp_store = self.lsp_store.clone();
@@ -903,14 +1293,25 @@ impl Example {
             .unwrap_or_default();
         let language = Lang::from_str(&language);
         let content = std::fs::read_to_string(&path)?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
 
         window
             .spawn(cx, async move |window| {
                 _ = view.update_in(window, |this, window, cx| {
+                    this.base_text = content.clone().into();
+                    // The split editor is highlighted with the previous language.
+                    this.split_editor = None;
                     _ = this.editor.update(cx, |this, cx| {
                         this.set_highlighter(language.name().to_string(), cx);
                         this.set_value(content, window, cx);
                     });
+                    this.update_gutter_marks(window, cx);
+                    this.diagnostics_panel.update(cx, |panel, cx| {
+                        panel.add_input(file_name, &this.editor, cx);
+                    });
 
                     this.language = language;
                     cx.notify();
@@ -1016,6 +1417,134 @@ impl Example {
             }))
     }
 
+    fn render_show_line_endings_button(&self, _: &mut Window, cx: &mut Context<Self>) -> Button {
+        Button::new("show-line-endings")
+            .ghost()
+            .xsmall()
+            .when(self.show_line_endings, |this| this.icon(IconName::Check))
+            .label("Show Line Endings")
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.show_line_endings = !this.show_line_endings;
+                this.editor.update(cx, |state, cx| {
+                    state.set_show_line_endings(this.show_line_endings, window, cx);
+                });
+                cx.notify();
+            }))
+    }
+
+    fn render_relative_line_numbers_button(
+        &self,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Button {
+        Button::new("relative-line-numbers")
+            .ghost()
+            .xsmall()
+            .when(self.relative_line_numbers, |this| {
+                this.icon(IconName::Check)
+            })
+            .label("Relative Line Numbers")
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.relative_line_numbers = !this.relative_line_numbers;
+                let mode = if this.relative_line_numbers {
+                    LineNumberMode::Relative
+                } else {
+                    LineNumberMode::Absolute
+                };
+                this.editor.update(cx, |state, cx| {
+                    state.set_line_number_mode(mode, window, cx);
+                });
+                cx.notify();
+            }))
+    }
+
+    fn render_minimap_button(&self, _: &mut Window, cx: &mut Context<Self>) -> Button {
+        Button::new("minimap")
+            .ghost()
+            .xsmall()
+            .when(self.minimap, |this| this.icon(IconName::Check))
+            .label("Minimap")
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.minimap = !this.minimap;
+                this.editor.update(cx, |state, cx| {
+                    state.set_minimap(this.minimap, window, cx);
+                });
+                cx.notify();
+            }))
+    }
+
+    fn render_split_button(&self, _: &mut Window, cx: &mut Context<Self>) -> Button {
+        Button::new("split")
+            .ghost()
+            .xsmall()
+            .when(self.split_editor.is_some(), |this| {
+                this.icon(IconName::Check)
+            })
+            .label("Split")
+            .on_click(cx.listener(|this, _, window, cx| {
+                if this.split_editor.take().is_none() {
+                    let language = this.language.name().to_string();
+                    let editor = this.editor.clone();
+                    this.split_editor = Some(cx.new(|cx| {
+                        InputState::new(window, cx)
+                            .code_editor(language)
+                            .line_number(true)
+                            .share_buffer(&editor, window, cx)
+                    }));
+                }
+                cx.notify();
+            }))
+    }
+
+    fn render_problems_button(&self, _: &mut Window, cx: &mut Context<Self>) -> Button {
+        let count = self.diagnostics_panel.read(cx).len(cx);
+
+        Button::new("problems")
+            .ghost()
+            .xsmall()
+            .when(self.show_problems, |this| this.icon(IconName::Check))
+            .label(format!("Problems ({})", count))
+            .on_click(cx.listener(|this, _, _, cx| {
+                this.show_problems = !this.show_problems;
+                cx.notify();
+            }))
+    }
+
+    fn render_sticky_scroll_button(&self, _: &mut Window, cx: &mut Context<Self>) -> Button {
+        Button::new("sticky-scroll")
+            .ghost()
+            .xsmall()
+            .when(self.sticky_scroll, |this| this.icon(IconName::Check))
+            .label("Sticky Scroll")
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.sticky_scroll = !this.sticky_scroll;
+                this.editor.update(cx, |state, cx| {
+                    state.set_sticky_scroll(this.sticky_scroll, window, cx);
+                });
+                cx.notify();
+            }))
+    }
+
+    fn render_vim_mode_button(&self, _: &mut Window, cx: &mut Context<Self>) -> Button {
+        let label = match self.editor.read(cx).current_vim_mode() {
+            Some(mode) => format!("Vim: {}", mode.as_str().to_uppercase()),
+            None => "Vim".to_string(),
+        };
+
+        Button::new("vim-mode")
+            .ghost()
+            .xsmall()
+            .when(self.vim_mode, |this| this.icon(IconName::Check))
+            .label(label)
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.vim_mode = !this.vim_mode;
+                this.editor.update(cx, |state, cx| {
+                    state.set_vim_mode(this.vim_mode, window, cx);
+                });
+                cx.notify();
+            }))
+    }
+
     fn render_indent_guides_button(&self, _: &mut Window, cx: &mut Context<Self>) -> Button {
         Button::new("indent-guides")
             .ghost()
@@ -1175,18 +1704,47 @@ impl Render for Example {
                                     .text_size(cx.theme().mono_font_size)
                                     .focus_bordered(false)
                                     .into_any_element(),
-                            ),
+                            )
+                            .when_some(self.split_editor.clone(), |this, split_editor| {
+                                this.child(
+                                    Input::new(&split_editor)
+                                        .disabled(self.disabled)
+                                        .bordered(false)
+                                        .p_0()
+                                        .h_full()
+                                        .font_family(cx.theme().mono_font_family.clone())
+                                        .text_size(cx.theme().mono_font_size)
+                                        .focus_bordered(false)
+                                        .into_any_element(),
+                                )
+                            }),
                     )
+                    .when(self.show_problems, |this| {
+                        this.child(
+                            DiagnosticsPanel::new(&self.diagnostics_panel)
+                                .h(px(160.))
+                                .flex_shrink_0()
+                                .border_t_1()
+                                .border_color(cx.theme().border),
+                        )
+                    })
                     .child(
                         StatusBar::new()
                             .left(self.render_line_number_button(window, cx))
                             .left(self.render_soft_wrap_button(window, cx))
                             .left(self.render_show_whitespaces_button(window, cx))
+                            .left(self.render_show_line_endings_button(window, cx))
+                            .left(self.render_relative_line_numbers_button(window, cx))
+                            .left(self.render_minimap_button(window, cx))
+                            .left(self.render_sticky_scroll_button(window, cx))
+                            .left(self.render_vim_mode_button(window, cx))
                             .left(self.render_indent_guides_button(window, cx))
                             .left(self.render_folding_button(window, cx))
                             .left(self.render_disabled_button(window, cx))
                             .left(self.render_scroll_beyond_last_line_button(window, cx))
                             .left(self.render_cursor_surrounding_lines_button(window, cx))
+                            .right(self.render_split_button(window, cx))
+                            .right(self.render_problems_button(window, cx))
                             .right(self.render_go_to_line_button(window, cx)),
                     ),
             )
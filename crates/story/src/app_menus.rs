@@ -104,7 +104,13 @@ fn build_menus(title: impl Into<SharedString>, cx: &App) -> Vec<Menu> {
         },
         Menu {
             name: "Window".into(),
-            items: vec![MenuItem::action("Toggle Search", ToggleSearch)],
+            items: vec![
+                MenuItem::action("Toggle Search", ToggleSearch),
+                MenuItem::separator(),
+                MenuItem::action("Zoom In", gpui_component::ZoomIn),
+                MenuItem::action("Zoom Out", gpui_component::ZoomOut),
+                MenuItem::action("Reset Zoom", gpui_component::ResetZoom),
+            ],
             disabled: false,
         },
         Menu {
@@ -0,0 +1,312 @@
+use std::ops::RangeInclusive;
+
+use gpui::{
+    AppContext as _, Context, Entity, Hsla, IntoElement, ParentElement as _, Render, SharedString,
+    Styled as _, Subscription, Window, prelude::FluentBuilder as _, px,
+};
+use gpui_component::{
+    ActiveTheme as _, Sizable as _,
+    color_picker::{ColorPicker, ColorPickerEvent, ColorPickerState},
+    h_flex,
+    label::Label,
+    radio::RadioGroup,
+    slider::{Slider, SliderEvent, SliderState},
+    switch::Switch,
+    v_flex,
+};
+
+enum ControlKind {
+    Bool(bool),
+    Enum {
+        options: Vec<SharedString>,
+        selected: usize,
+    },
+    Number {
+        value: f32,
+        range: RangeInclusive<f32>,
+        step: f32,
+        state: Option<Entity<SliderState>>,
+    },
+    Color {
+        value: Hsla,
+        state: Option<Entity<ColorPickerState>>,
+    },
+}
+
+struct Control {
+    key: SharedString,
+    label: SharedString,
+    kind: ControlKind,
+}
+
+/// The knobs of a story, rendered in the controls sidebar of the [`crate::StoryContainer`].
+///
+/// The story creates the controls, returns it in [`crate::Story::controls`], and reads
+/// the values by the key to set the builder options of its component:
+///
+/// ```ignore
+/// let controls = cx.new(|_| {
+///     StoryControls::new()
+///         .add_bool("disabled", "Disabled", false)
+///         .add_enum("size", "Size", ["xs", "sm", "md", "lg"], 2)
+/// });
+/// cx.observe(&controls, |_, _, cx| cx.notify()).detach();
+///
+/// Button::new("button").disabled(controls.read(cx).bool("disabled"))
+/// ```
+pub struct StoryControls {
+    controls: Vec<Control>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl StoryControls {
+    pub fn new() -> Self {
+        Self {
+            controls: vec![],
+            _subscriptions: vec![],
+        }
+    }
+
+    fn add(mut self, key: &str, label: &str, kind: ControlKind) -> Self {
+        self.controls.push(Control {
+            key: SharedString::from(key.to_string()),
+            label: SharedString::from(label.to_string()),
+            kind,
+        });
+        self
+    }
+
+    /// Add a bool control, rendered as a switch.
+    pub fn add_bool(self, key: &str, label: &str, value: bool) -> Self {
+        self.add(key, label, ControlKind::Bool(value))
+    }
+
+    /// Add an enum control, rendered as a radio group.
+    pub fn add_enum(
+        self,
+        key: &str,
+        label: &str,
+        options: impl IntoIterator<Item = impl Into<SharedString>>,
+        selected: usize,
+    ) -> Self {
+        let options = options.into_iter().map(Into::into).collect();
+        self.add(key, label, ControlKind::Enum { options, selected })
+    }
+
+    /// Add a number control, rendered as a slider.
+    pub fn add_number(
+        self,
+        key: &str,
+        label: &str,
+        value: f32,
+        range: RangeInclusive<f32>,
+        step: f32,
+    ) -> Self {
+        self.add(
+            key,
+            label,
+            ControlKind::Number {
+                value,
+                range,
+                step,
+                state: None,
+            },
+        )
+    }
+
+    /// Add a color control, rendered as a color picker.
+    pub fn add_color(self, key: &str, label: &str, value: Hsla) -> Self {
+        self.add(key, label, ControlKind::Color { value, state: None })
+    }
+
+    /// Returns true if there is no control.
+    pub fn is_empty(&self) -> bool {
+        self.controls.is_empty()
+    }
+
+    fn kind(&self, key: &str) -> Option<&ControlKind> {
+        self.controls
+            .iter()
+            .find(|control| control.key == key)
+            .map(|control| &control.kind)
+    }
+
+    /// Returns the value of the bool control, false if not found.
+    pub fn bool(&self, key: &str) -> bool {
+        match self.kind(key) {
+            Some(ControlKind::Bool(value)) => *value,
+            _ => false,
+        }
+    }
+
+    /// Returns the selected index of the enum control, 0 if not found.
+    pub fn enum_ix(&self, key: &str) -> usize {
+        match self.kind(key) {
+            Some(ControlKind::Enum { selected, .. }) => *selected,
+            _ => 0,
+        }
+    }
+
+    /// Returns the selected option of the enum control, empty if not found.
+    pub fn enum_value(&self, key: &str) -> SharedString {
+        match self.kind(key) {
+            Some(ControlKind::Enum { options, selected }) => {
+                options.get(*selected).cloned().unwrap_or_default()
+            }
+            _ => SharedString::default(),
+        }
+    }
+
+    /// Returns the value of the number control, 0 if not found.
+    pub fn number(&self, key: &str) -> f32 {
+        match self.kind(key) {
+            Some(ControlKind::Number { value, .. }) => *value,
+            _ => 0.,
+        }
+    }
+
+    /// Returns the value of the color control, transparent if not found.
+    pub fn color(&self, key: &str) -> Hsla {
+        match self.kind(key) {
+            Some(ControlKind::Color { value, .. }) => *value,
+            _ => Hsla::default(),
+        }
+    }
+
+    fn set(&mut self, ix: usize, f: impl FnOnce(&mut ControlKind), cx: &mut Context<Self>) {
+        if let Some(control) = self.controls.get_mut(ix) {
+            f(&mut control.kind);
+            cx.notify();
+        }
+    }
+
+    /// Create the states of the slider and color picker controls at the first render.
+    fn prepare_states(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for ix in 0..self.controls.len() {
+            match &mut self.controls[ix].kind {
+                ControlKind::Number {
+                    value,
+                    range,
+                    step,
+                    state: state @ None,
+                } => {
+                    let slider = cx.new(|_| {
+                        SliderState::new()
+                            .min(*range.start())
+                            .max(*range.end())
+                            .step(*step)
+                            .default_value(*value)
+                    });
+                    self._subscriptions.push(cx.subscribe(
+                        &slider,
+                        move |this, _, event: &SliderEvent, cx| {
+                            let SliderEvent::Change(new_value) = event else {
+                                return;
+                            };
+                            let new_value = new_value.start();
+                            this.set(
+                                ix,
+                                |kind| {
+                                    if let ControlKind::Number { value, .. } = kind {
+                                        *value = new_value;
+                                    }
+                                },
+                                cx,
+                            );
+                        },
+                    ));
+                    *state = Some(slider);
+                }
+                ControlKind::Color {
+                    value,
+                    state: state @ None,
+                } => {
+                    let value = *value;
+                    let picker =
+                        cx.new(|cx| ColorPickerState::new(window, cx).default_value(value));
+                    self._subscriptions.push(cx.subscribe_in(
+                        &picker,
+                        window,
+                        move |this, _, event: &ColorPickerEvent, _, cx| {
+                            let ColorPickerEvent::Change(Some(new_value)) = event else {
+                                return;
+                            };
+                            let new_value = *new_value;
+                            this.set(
+                                ix,
+                                |kind| {
+                                    if let ControlKind::Color { value, .. } = kind {
+                                        *value = new_value;
+                                    }
+                                },
+                                cx,
+                            );
+                        },
+                    ));
+                    *state = Some(picker);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Render for StoryControls {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.prepare_states(window, cx);
+
+        v_flex()
+            .gap_4()
+            .children(self.controls.iter().enumerate().map(|(ix, control)| {
+                let label = Label::new(control.label.clone())
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground);
+
+                let field = match &control.kind {
+                    ControlKind::Bool(value) => h_flex().justify_between().child(label).child(
+                        Switch::new(("control-bool", ix))
+                            .checked(*value)
+                            .small()
+                            .on_click(cx.listener(move |this, checked: &bool, _, cx| {
+                                let checked = *checked;
+                                this.set(ix, |kind| *kind = ControlKind::Bool(checked), cx);
+                            })),
+                    ),
+                    ControlKind::Enum { options, selected } => v_flex().gap_2().child(label).child(
+                        RadioGroup::horizontal(("control-enum", ix))
+                            .children(options.iter().cloned())
+                            .selected_index(Some(*selected))
+                            .on_click(cx.listener(move |this, new_ix: &usize, _, cx| {
+                                let new_ix = *new_ix;
+                                this.set(
+                                    ix,
+                                    |kind| {
+                                        if let ControlKind::Enum { selected, .. } = kind {
+                                            *selected = new_ix;
+                                        }
+                                    },
+                                    cx,
+                                );
+                            })),
+                    ),
+                    ControlKind::Number { value, state, .. } => v_flex()
+                        .gap_2()
+                        .child(
+                            h_flex()
+                                .justify_between()
+                                .child(label)
+                                .child(Label::new(format!("{}", value)).text_sm()),
+                        )
+                        .when_some(state.as_ref(), |this, state| this.child(Slider::new(state))),
+                    ControlKind::Color { state, .. } => h_flex()
+                        .justify_between()
+                        .child(label)
+                        .when_some(state.as_ref(), |this, state| {
+                            this.child(ColorPicker::new(state).small())
+                        }),
+                };
+
+                field.w_full().min_h(px(24.))
+            }))
+    }
+}
@@ -61,6 +61,8 @@ impl Gallery {
                     StoryContainer::panel::<DropdownButtonStory>(window, cx),
                     StoryContainer::panel::<EditorStory>(window, cx),
                     StoryContainer::panel::<FormStory>(window, cx),
+                    StoryContainer::panel::<GanttStory>(window, cx),
+                    StoryContainer::panel::<GraphStory>(window, cx),
                     StoryContainer::panel::<GroupBoxStory>(window, cx),
                     StoryContainer::panel::<HoverCardStory>(window, cx),
                     StoryContainer::panel::<IconStory>(window, cx),
@@ -93,6 +95,7 @@ impl Gallery {
                     StoryContainer::panel::<StepperStory>(window, cx),
                     StoryContainer::panel::<SwitchStory>(window, cx),
                     StoryContainer::panel::<DataTableStory>(window, cx),
+                    StoryContainer::panel::<TreeTableStory>(window, cx),
                     StoryContainer::panel::<TableStory>(window, cx),
                     StoryContainer::panel::<TabsStory>(window, cx),
                     StoryContainer::panel::<TagStory>(window, cx),
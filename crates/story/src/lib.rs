@@ -6,25 +6,28 @@ use gpui::{
     size,
 };
 use gpui_component::{
-    ActiveTheme, IconName, Root, TitleBar, WindowExt,
-    button::Button,
+    ActiveTheme, IconName, Root, Selectable as _, Sizable as _, TitleBar, WindowExt,
+    button::{Button, ButtonVariants as _},
+    clipboard::Clipboard,
     dock::{Panel, PanelControl, PanelEvent, PanelInfo, PanelState, TitleStyle, register_panel},
     group_box::{GroupBox, GroupBoxVariants as _},
     h_flex,
     menu::PopupMenu,
     notification::Notification,
     scroll::{ScrollableElement as _, ScrollbarShow},
-    text::markdown,
+    text::{TextView, markdown},
     v_flex,
 };
 use serde::{Deserialize, Serialize};
 
 mod app_menus;
+mod controls;
 mod embedded_themes;
 mod gallery;
 mod stories;
 mod themes;
 mod title_bar;
+pub use crate::controls::StoryControls;
 pub use crate::title_bar::AppTitleBar;
 pub use gallery::Gallery;
 pub use stories::*;
@@ -184,6 +187,7 @@ pub fn init(cx: &mut App) {
 
     rust_i18n::extend!(gpui_component);
     gpui_component::init(cx);
+    gpui_component::Theme::bind_zoom_keys(cx);
     AppState::init(cx);
     themes::init(cx);
     stories::init(cx);
@@ -251,11 +255,13 @@ pub fn init(cx: &mut App) {
         };
 
         let view = cx.new(|cx| {
-            let (title, description, closable, zoomable, story, on_active) =
+            let (title, description, closable, zoomable, story, on_active, controls) =
                 story_state.to_story(window, cx);
+            let controls = controls(story.clone(), cx);
             let mut container = StoryContainer::new(window, cx)
                 .story(story, story_state.story_klass)
                 .on_active(on_active);
+            container.controls = controls;
 
             cx.on_focus_in(
                 &container.focus_handle,
@@ -278,11 +284,64 @@ pub fn init(cx: &mut App) {
     cx.activate(true);
 }
 
+/// Include a region of a story source file as `&'static str`, to attach to [`StorySection::source`].
+///
+/// The region is marked in the file (relative to `src/stories`) by comments:
+///
+/// ```ignore
+/// // #region basic
+/// Button::new("ok").label("Ok")
+/// // #endregion
+/// ```
+///
+/// ```ignore
+/// section("Basic").source(story_source!("button_story.rs", "basic"))
+/// ```
+#[macro_export]
+macro_rules! story_source {
+    ($file:literal, $region:literal) => {
+        $crate::source_region(
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/stories/", $file)),
+            $region,
+        )
+    };
+}
+
+/// Returns the dedented lines between `// #region {name}` and `// #endregion` in the source.
+///
+/// Returns an empty string if the region is not found.
+pub fn source_region(source: &str, name: &str) -> String {
+    let mut lines = source
+        .lines()
+        .skip_while(|line| {
+            line.trim()
+                .strip_prefix("// #region")
+                .map_or(true, |region| region.trim() != name)
+        })
+        .skip(1)
+        .take_while(|line| line.trim() != "// #endregion")
+        .collect::<Vec<_>>();
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    for line in lines.iter_mut() {
+        *line = line.get(indent..).unwrap_or("");
+    }
+
+    lines.join("\n")
+}
+
 #[derive(IntoElement)]
 struct StorySection {
     base: Div,
     title: SharedString,
     sub_title: Vec<AnyElement>,
+    source: Option<SharedString>,
     children: Vec<AnyElement>,
 }
 
@@ -292,6 +351,17 @@ impl StorySection {
         self
     }
 
+    /// Attach the Rust source of this example, see [`story_source!`].
+    ///
+    /// The source is shown in a collapsible code pane with a copy button.
+    pub fn source(mut self, source: impl Into<SharedString>) -> Self {
+        let source = source.into();
+        if !source.is_empty() {
+            self.source = Some(source);
+        }
+        self
+    }
+
     #[allow(unused)]
     fn max_w_md(mut self) -> Self {
         self.base = self.base.max_w(rems(48.));
@@ -330,7 +400,14 @@ impl Styled for StorySection {
 }
 
 impl RenderOnce for StorySection {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let source_open = window.use_keyed_state(
+            SharedString::from(format!("{}-source-open", self.title)),
+            cx,
+            |_, _| false,
+        );
+        let is_source_open = *source_open.read(cx);
+
         GroupBox::new()
             .id(self.title.clone())
             .outline()
@@ -339,8 +416,41 @@ impl RenderOnce for StorySection {
                     .justify_between()
                     .w_full()
                     .gap_4()
-                    .child(self.title)
-                    .children(self.sub_title),
+                    .child(self.title.clone())
+                    .children(self.sub_title)
+                    .when_some(self.source.clone(), |this, source| {
+                        this.child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Clipboard::new(SharedString::from(format!(
+                                        "{}-source-copy",
+                                        self.title
+                                    )))
+                                    .tooltip("Copy Code")
+                                    .value(source),
+                                )
+                                .child(
+                                    Button::new(SharedString::from(format!(
+                                        "{}-source-toggle",
+                                        self.title
+                                    )))
+                                    .ghost()
+                                    .xsmall()
+                                    .icon(IconName::SquareTerminal)
+                                    .label("Code")
+                                    .selected(is_source_open)
+                                    .on_click(
+                                        move |_, _, cx| {
+                                            source_open.update(cx, |open, cx| {
+                                                *open = !*open;
+                                                cx.notify();
+                                            })
+                                        },
+                                    ),
+                                ),
+                        )
+                    }),
             )
             .content_style(
                 StyleRefinement::default()
@@ -350,6 +460,18 @@ impl RenderOnce for StorySection {
                     .justify_center(),
             )
             .child(self.base.children(self.children))
+            .when_some(self.source.filter(|_| is_source_open), |this, source| {
+                this.child(
+                    div()
+                        .w_full()
+                        .border_t_1()
+                        .border_color(cx.theme().border)
+                        .child(TextView::markdown(
+                            SharedString::from(format!("{}-source", self.title)),
+                            format!("```rust\n{}\n```", source),
+                        )),
+                )
+            })
     }
 }
 
@@ -357,6 +479,7 @@ pub(crate) fn section(title: impl Into<SharedString>) -> StorySection {
     StorySection {
         title: title.into(),
         sub_title: vec![],
+        source: None,
         base: h_flex()
             .w_full()
             .flex_wrap()
@@ -370,6 +493,8 @@ pub(crate) fn section(title: impl Into<SharedString>) -> StorySection {
 pub struct StoryContainer {
     focus_handle: gpui::FocusHandle,
     pub name: SharedString,
+    /// The title renamed by double-clicking the dock tab.
+    renamed_title: Option<SharedString>,
     pub title_bg: Option<Hsla>,
     pub description: SharedString,
     width: Option<gpui::Pixels>,
@@ -380,6 +505,9 @@ pub struct StoryContainer {
     zoomable: Option<PanelControl>,
     paddings: Pixels,
     on_active: Option<fn(AnyView, bool, &mut Window, &mut App)>,
+    /// The controls of the story, see [`Story::controls`].
+    controls: Option<Entity<StoryControls>>,
+    show_controls: bool,
 }
 
 #[derive(Debug)]
@@ -396,6 +524,7 @@ impl StoryContainer {
         Self {
             focus_handle,
             name: "".into(),
+            renamed_title: None,
             title_bg: None,
             description: "".into(),
             width: None,
@@ -406,6 +535,8 @@ impl StoryContainer {
             zoomable: Some(PanelControl::default()),
             paddings: px(16.),
             on_active: None,
+            controls: None,
+            show_controls: true,
         }
     }
 
@@ -414,6 +545,7 @@ impl StoryContainer {
         let description = S::description();
         let story = S::new_view(window, cx);
         let story_klass = S::klass();
+        let controls = S::controls_any(story.clone().into(), cx);
 
         let view = cx.new(|cx| {
             let mut story = Self::new(window, cx)
@@ -426,6 +558,7 @@ impl StoryContainer {
             story.description = description.into();
             story.title_bg = S::title_bg();
             story.paddings = S::paddings();
+            story.controls = controls;
             story
         });
 
@@ -481,6 +614,7 @@ impl StoryState {
         Option<PanelControl>,
         AnyView,
         fn(AnyView, bool, &mut Window, &mut App),
+        fn(AnyView, &App) -> Option<Entity<StoryControls>>,
     ) {
         macro_rules! story {
             ($klass:tt) => {
@@ -491,6 +625,7 @@ impl StoryState {
                     $klass::zoomable(),
                     $klass::view(window, cx).into(),
                     $klass::on_active_any,
+                    $klass::controls_any,
                 )
             };
         }
@@ -512,6 +647,7 @@ impl StoryState {
             "ScrollbarStory" => story!(ScrollbarStory),
             "SwitchStory" => story!(SwitchStory),
             "DataTableStory" => story!(DataTableStory),
+            "TreeTableStory" => story!(TreeTableStory),
             "TableStory" => story!(TableStory),
             "LabelStory" => story!(LabelStory),
             "TooltipStory" => story!(TooltipStory),
@@ -533,7 +669,24 @@ impl Panel for StoryContainer {
     }
 
     fn title(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        self.name.clone().into_any_element()
+        self.renamed_title
+            .clone()
+            .unwrap_or_else(|| self.name.clone())
+            .into_any_element()
+    }
+
+    fn editable_title(&self, _cx: &App) -> Option<SharedString> {
+        Some(
+            self.renamed_title
+                .clone()
+                .unwrap_or_else(|| self.name.clone()),
+        )
+    }
+
+    fn rename(&mut self, title: SharedString, _window: &mut Window, cx: &mut Context<Self>) {
+        println!("panel: {} renamed to: {}", self.name, title);
+        self.renamed_title = Some(title);
+        cx.notify();
     }
 
     fn title_style(&self, cx: &App) -> Option<TitleStyle> {
@@ -587,9 +740,26 @@ impl Panel for StoryContainer {
     fn toolbar_buttons(
         &mut self,
         _window: &mut Window,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) -> Option<Vec<Button>> {
-        Some(vec![
+        let mut buttons = vec![];
+        if self
+            .controls
+            .as_ref()
+            .is_some_and(|controls| !controls.read(cx).is_empty())
+        {
+            buttons.push(
+                Button::new("controls")
+                    .icon(IconName::Settings2)
+                    .selected(self.show_controls)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.show_controls = !this.show_controls;
+                        cx.notify();
+                    })),
+            );
+        }
+
+        buttons.extend([
             Button::new("info")
                 .icon(IconName::Info)
                 .on_click(|_, window, cx| {
@@ -600,7 +770,9 @@ impl Panel for StoryContainer {
                 .on_click(|_, window, cx| {
                     window.push_notification("You have clicked search button", cx);
                 }),
-        ])
+        ]);
+
+        Some(buttons)
     }
 
     fn dump(&self, _cx: &App) -> PanelState {
@@ -620,14 +792,39 @@ impl Focusable for StoryContainer {
     }
 }
 impl Render for StoryContainer {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
-        div()
-            .id("story-container")
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let controls = self
+            .controls
+            .clone()
+            .filter(|controls| self.show_controls && !controls.read(cx).is_empty());
+
+        h_flex()
             .size_full()
-            .overflow_y_scrollbar()
+            .items_start()
             .track_focus(&self.focus_handle)
-            .when_some(self.story.clone(), |this, story| {
-                this.child(div().size_full().p(self.paddings).child(story))
+            .child(
+                div()
+                    .id("story-container")
+                    .flex_1()
+                    .h_full()
+                    .overflow_y_scrollbar()
+                    .when_some(self.story.clone(), |this, story| {
+                        this.child(div().size_full().p(self.paddings).child(story))
+                    }),
+            )
+            .when_some(controls, |this, controls| {
+                this.child(
+                    div()
+                        .id("story-controls")
+                        .w(px(260.))
+                        .h_full()
+                        .flex_shrink_0()
+                        .p_4()
+                        .border_l_1()
+                        .border_color(cx.theme().border)
+                        .overflow_y_scrollbar()
+                        .child(controls),
+                )
             })
     }
 }
@@ -722,6 +919,20 @@ impl Render for StoryRoot {
 
 #[cfg(test)]
 mod tests {
+    use super::source_region;
+
+    #[test]
+    fn test_source_region() {
+        let source = "fn render() {\n    v_flex()\n        // #region basic\n        .child(\n            Button::new(\"ok\"),\n\n        )\n        // #endregion\n        .child(\"other\")\n}";
+
+        assert_eq!(
+            source_region(source, "basic"),
+            ".child(\n    Button::new(\"ok\"),\n\n)"
+        );
+        assert_eq!(source_region(source, "unknown"), "");
+        assert_eq!(source_region(source, "basi"), "");
+    }
+
     #[test]
     fn extends_component_translations_with_story_locales() {
         rust_i18n::extend!(gpui_component);
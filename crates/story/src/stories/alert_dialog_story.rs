@@ -10,7 +10,7 @@ use gpui_component::{
         AlertDialog, DialogAction, DialogButtonProps, DialogClose, DialogDescription, DialogFooter,
         DialogHeader, DialogTitle,
     },
-    v_flex,
+    h_flex, v_flex,
 };
 
 use crate::section;
@@ -301,6 +301,57 @@ impl Render for AlertDialogStory {
                         },
                     )),
                 ))
+                .child(section("Presets").child(
+                    h_flex()
+                        .gap_2()
+                        .child(Button::new("info-preset").outline().label("Info").on_click(
+                            cx.listener(|_, _, window, cx| {
+                                window.open_alert_dialog(cx, |alert, _, _| {
+                                    alert
+                                        .info()
+                                        .title("Backup Completed")
+                                        .description("All of your files have been backed up.")
+                                });
+                            }),
+                        ))
+                        .child(
+                            Button::new("destructive-preset")
+                                .outline()
+                                .label("Destructive")
+                                .on_click(cx.listener(|_, _, window, cx| {
+                                    window.open_alert_dialog(cx, |alert, _, _| {
+                                        alert
+                                            .button_props(
+                                                DialogButtonProps::default().ok_text("Discard"),
+                                            )
+                                            .destructive()
+                                            .title("Discard Changes?")
+                                            .description(
+                                                "The Cancel button is focused, \
+                                                press Enter will not discard the changes.",
+                                            )
+                                    });
+                                })),
+                        )
+                        .child(
+                            Button::new("typed-confirmation-preset")
+                                .outline()
+                                .label("Typed Confirmation")
+                                .on_click(cx.listener(|_, _, window, cx| {
+                                    window.open_alert_dialog(cx, |alert, _, _| {
+                                        alert
+                                            .button_props(
+                                                DialogButtonProps::default().ok_text("Delete"),
+                                            )
+                                            .typed_confirmation("DELETE")
+                                            .title("Delete Project")
+                                            .description(
+                                                "This will permanently delete the project.",
+                                            )
+                                    });
+                                })),
+                        ),
+                ))
                 .child(section("Overlay Closable").child(
                     Button::new("overlay-closable").outline().label("Overlay Closable").on_click(cx.listener(
                         |_, _, window, cx| {
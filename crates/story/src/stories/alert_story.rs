@@ -5,7 +5,7 @@ use gpui::{
 use gpui_component::{
     IconName, Selectable as _, Sizable as _, Size,
     alert::Alert,
-    button::{Button, ButtonGroup},
+    button::{Button, ButtonGroup, ButtonVariants as _},
     dock::PanelControl,
     text::markdown,
     v_flex,
@@ -21,7 +21,11 @@ pub struct AlertStory {
 
 impl AlertStory {
     fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
-        Self { size: Size::default(), banner_visible: true, focus_handle: cx.focus_handle() }
+        Self {
+            size: Size::default(),
+            banner_visible: true,
+            focus_handle: cx.focus_handle(),
+        }
     }
 
     pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
@@ -67,13 +71,25 @@ impl Render for AlertStory {
                     .outline()
                     .compact()
                     .child(
-                        Button::new("xsmall").label("XSmall").selected(self.size == Size::XSmall),
+                        Button::new("xsmall")
+                            .label("XSmall")
+                            .selected(self.size == Size::XSmall),
                     )
-                    .child(Button::new("small").label("Small").selected(self.size == Size::Small))
                     .child(
-                        Button::new("medium").label("Medium").selected(self.size == Size::Medium),
+                        Button::new("small")
+                            .label("Small")
+                            .selected(self.size == Size::Small),
+                    )
+                    .child(
+                        Button::new("medium")
+                            .label("Medium")
+                            .selected(self.size == Size::Medium),
+                    )
+                    .child(
+                        Button::new("large")
+                            .label("Large")
+                            .selected(self.size == Size::Large),
                     )
-                    .child(Button::new("large").label("Large").selected(self.size == Size::Large))
                     .on_click(cx.listener(|this, selecteds: &Vec<usize>, window, cx| {
                         let size = match selecteds[0] {
                             0 => Size::XSmall,
@@ -201,6 +217,28 @@ impl Render for AlertStory {
                         ),
                 ),
             )
+            .child(
+                section("With Actions").w_2_3().child(
+                    v_flex()
+                        .w_full()
+                        .gap_2()
+                        .child(
+                            Alert::error("actions-1", "Unable to sync your changes.")
+                                .title("Sync failed")
+                                .with_size(self.size)
+                                .action(Button::new("retry").xsmall().outline().label("Retry"))
+                                .action(Button::new("details").xsmall().ghost().label("Details"))
+                                .dismissible(true),
+                        )
+                        .child(
+                            Alert::warning("actions-2", "Your trial ends in 3 days.")
+                                .banner()
+                                .with_size(self.size)
+                                .action(Button::new("upgrade").xsmall().primary().label("Upgrade"))
+                                .on_dismiss(|_, _| println!("Trial banner dismissed")),
+                        ),
+                ),
+            )
             .child(
                 section("Custom Icon").w_2_3().child(
                     Alert::new(
@@ -1,17 +1,20 @@
 use gpui::{
     App, AppContext, Context, Entity, FocusHandle, Focusable, Hsla, IntoElement, ParentElement,
-    Render, Rgba, SharedString, Styled, Window, div, linear_color_stop, linear_gradient,
-    prelude::FluentBuilder, px,
+    Render, Rgba, SharedString, Styled, Subscription, Window, div, linear_color_stop,
+    linear_gradient, prelude::FluentBuilder, px,
 };
 use gpui_component::{
     ActiveTheme, StyledExt,
     chart::{
         AreaChart, BarChart, CandlestickChart, LineChart, PieChart, RadarChart, SankeyChart,
-        SankeyLabel,
+        SankeyLabel, Sparkline, Waveform,
     },
     dock::PanelControl,
     h_flex,
-    plot::shape::{BarAlignment, SankeyAlign, SankeyLink, SankeyValueScale},
+    plot::{
+        brush::{Brush, BrushEvent, BrushState},
+        shape::{BarAlignment, SankeyAlign, SankeyLink, SankeyValueScale},
+    },
     separator::Separator,
     v_flex,
 };
@@ -105,6 +108,11 @@ pub struct ChartStory {
     radar_devices: Vec<RadarDevice>,
     stock_prices: Vec<StockPrice>,
     tsla_statements: Vec<(SharedString, Vec<TslaNode>, Vec<SankeyLink>)>,
+    waveform_samples: Vec<f32>,
+    brush: Entity<BrushState>,
+    /// The range of the `stock_prices` selected by the brush.
+    brushed_prices: Option<std::ops::Range<usize>>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl ChartStory {
@@ -169,16 +177,90 @@ impl ChartStory {
             })
             .collect();
 
+        // A decaying tone with beats, long enough to be downsampled.
+        let waveform_samples = (0..8000)
+            .map(|ix| {
+                let t = ix as f32 / 8000.;
+                let envelope = (1. - (t * 8.).fract()) * (0.4 + 0.6 * (t * 3.).sin().abs());
+                envelope * (ix as f32 * 0.3).sin()
+            })
+            .collect();
+
+        let brush = cx.new(|_| BrushState::new());
+        let _subscriptions = vec![cx.subscribe(&brush, |this, brush, event: &BrushEvent, cx| {
+            let BrushEvent::BrushChanged(selection) = event;
+            let width = brush.read(cx).bounds().size.width.as_f32();
+            let len = this.stock_prices.len();
+            this.brushed_prices = selection.as_ref().filter(|_| width > 0.).map(|selection| {
+                let ix = |x: f32| ((x / width * len as f32) as usize).min(len);
+                ix(selection.x.start)..ix(selection.x.end).max(ix(selection.x.start) + 1).min(len)
+            });
+            cx.notify();
+        })];
+
         Self {
             daily_devices,
             monthly_devices,
             radar_devices,
             stock_prices,
             tsla_statements,
+            waveform_samples,
+            brush,
+            brushed_prices: None,
+            _subscriptions,
             focus_handle: cx.focus_handle(),
         }
     }
 
+    fn render_brush_chart(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected = self
+            .brushed_prices
+            .clone()
+            .and_then(|range| {
+                let first = self.stock_prices.get(range.start)?;
+                let last = self.stock_prices.get(range.end.saturating_sub(1))?;
+                Some(format!(
+                    "Selected {} ~ {}, {} days",
+                    first.date,
+                    last.date,
+                    range.len()
+                ))
+            })
+            .unwrap_or_else(|| "Drag on the chart to select a range".to_string());
+
+        h_flex()
+            .gap_4()
+            .child(chart_container(
+                "Line Chart - Brush",
+                div()
+                    .relative()
+                    .size_full()
+                    .child(
+                        LineChart::new(self.stock_prices.clone())
+                            .x(|d| d.date.clone())
+                            .y(|d| d.close)
+                            .tick_margin(10),
+                    )
+                    .child(Brush::new(&self.brush).absolute().inset_0()),
+                false,
+                cx,
+            ))
+            .child(v_flex().flex_1().gap_2().child(selected).when_some(
+                self.brushed_prices.clone(),
+                |this, range| {
+                    let prices = &self.stock_prices[range];
+                    let high = prices.iter().map(|d| d.high).fold(f64::MIN, f64::max);
+                    let low = prices.iter().map(|d| d.low).fold(f64::MAX, f64::min);
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("High {:.2}, Low {:.2}", high, low)),
+                    )
+                },
+            ))
+    }
+
     pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
         cx.new(|cx| Self::new(window, cx))
     }
@@ -784,6 +866,8 @@ impl Render for ChartStory {
                     )),
             )
             .child(Separator::horizontal())
+            .child(self.render_brush_chart(cx))
+            .child(Separator::horizontal())
             .child(
                 h_flex().flex_wrap().gap_4().children(
                     self.tsla_statements
@@ -835,5 +919,59 @@ impl Render for ChartStory {
                         .collect::<Vec<_>>(),
                 ),
             )
+            .child(Separator::horizontal())
+            .child(self.render_micro_charts(cx))
+    }
+}
+
+impl ChartStory {
+    fn render_micro_charts(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let desktop: Vec<f32> = self
+            .daily_devices
+            .iter()
+            .map(|d| d.desktop as f32)
+            .collect();
+        let mobile: Vec<f32> = self.daily_devices.iter().map(|d| d.mobile as f32).collect();
+        let stocks: Vec<f32> = self.stock_prices.iter().map(|d| d.close as f32).collect();
+
+        let row = |label: &'static str, chart: gpui::AnyElement| {
+            h_flex()
+                .gap_4()
+                .py_1()
+                .child(div().w(px(120.)).text_sm().child(label))
+                .child(chart)
+        };
+
+        v_flex()
+            .gap_2()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius_lg)
+            .p_4()
+            .child(div().font_semibold().child("Sparkline & Waveform"))
+            .child(row("Desktop", Sparkline::new(desktop).into_any_element()))
+            .child(row(
+                "Mobile",
+                Sparkline::new(mobile)
+                    .bar()
+                    .color(cx.theme().chart_2)
+                    .w(px(160.))
+                    .into_any_element(),
+            ))
+            .child(row(
+                "Stock",
+                Sparkline::new(stocks)
+                    .color(cx.theme().chart_3)
+                    .w(px(160.))
+                    .h(px(24.))
+                    .into_any_element(),
+            ))
+            .child(row(
+                "Waveform",
+                Waveform::new(self.waveform_samples.clone())
+                    .position(0.35)
+                    .w(px(320.))
+                    .into_any_element(),
+            ))
     }
 }
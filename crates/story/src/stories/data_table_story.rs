@@ -21,8 +21,8 @@ use gpui_component::{
     menu::{DropdownMenu, PopupMenu},
     spinner::Spinner,
     table::{
-        Column, ColumnFixed, ColumnGroup, ColumnSort, DataTable, TableDelegate, TableEvent,
-        TableState,
+        Column, ColumnFilter, ColumnFixed, ColumnGroup, ColumnSort, CsvExportOptions, DataTable,
+        FilterDescriptor, NumberFormat, TableDelegate, TableEvent, TableState,
     },
     v_flex,
 };
@@ -187,6 +187,8 @@ fn random_stocks(size: usize) -> Vec<Stock> {
 
 struct StockTableDelegate {
     stocks: Vec<Stock>,
+    /// All the stocks before filtering, `None` if no filters.
+    unfiltered_stocks: Option<Vec<Stock>>,
     columns: Vec<Column>,
     /// Number of extra "Column N" columns appended after the built-in columns.
     extra_columns_count: usize,
@@ -208,6 +210,7 @@ impl StockTableDelegate {
         Self {
             size: Size::default(),
             stocks: random_stocks(size),
+            unfiltered_stocks: None,
             lazy_load: false,
             clicked_row: None,
             columns: vec![
@@ -219,25 +222,34 @@ impl StockTableDelegate {
                     .max_width(100.)
                     .text_center(),
                 Column::new("market", "Market")
-                    .width(60.)
+                    .width(80.)
                     .fixed(ColumnFixed::Left)
                     .resizable(true)
-                    .min_width(50.),
+                    .min_width(50.)
+                    .filter(ColumnFilter::Enum(vec!["HK".into(), "US".into()])),
                 Column::new("name", "Name")
                     .width(180.)
                     .fixed(ColumnFixed::Left)
-                    .max_width(300.),
+                    .max_width(300.)
+                    .auto_fit()
+                    .filter(ColumnFilter::Text),
                 Column::new("symbol", "Symbol")
                     .width(100.)
                     .fixed(ColumnFixed::Left)
                     .sortable(),
-                Column::new("price", "Price").sortable().text_right().p_0(),
+                Column::new("price", "Price")
+                    .sortable()
+                    .text_right()
+                    .p_0()
+                    .filter(ColumnFilter::Number),
                 Column::new("change", "Chg").sortable().text_right().p_0(),
                 Column::new("change_percent", "Chg%")
                     .sortable()
                     .text_right()
                     .p_0(),
-                Column::new("volume", "Volume").p_0(),
+                Column::new("volume", "Volume")
+                    .number_format(NumberFormat::new().precision(0).thousands_separator(true))
+                    .p_0(),
                 Column::new("turnover", "Turnover").p_0(),
                 Column::new("market_cap", "Market Cap").p_0(),
                 Column::new("ttm", "TTM").p_0(),
@@ -280,7 +292,7 @@ impl StockTableDelegate {
                 Column::new("day_10_ranking", "10d Ranking"),
                 Column::new("day_30_ranking", "30d Ranking"),
                 Column::new("day_120_ranking", "120d Ranking"),
-                Column::new("day_250_ranking", "250d Ranking"),
+                Column::new("day_250_ranking", "250d Ranking").fixed(ColumnFixed::Right),
             ],
             extra_columns_count: 0,
             loading: false,
@@ -301,11 +313,34 @@ impl StockTableDelegate {
         }
 
         self.stocks = random_stocks(size);
+        self.unfiltered_stocks = None;
         self.eof = size <= 50;
         self.loading = false;
         self.full_loading = false;
     }
 
+    fn filter_stocks(&mut self, filters: &[FilterDescriptor]) {
+        let stocks = self
+            .unfiltered_stocks
+            .take()
+            .unwrap_or_else(|| std::mem::take(&mut self.stocks));
+        if filters.is_empty() {
+            self.stocks = stocks;
+            return;
+        }
+
+        self.stocks = stocks
+            .iter()
+            .filter(|stock| {
+                filters
+                    .iter()
+                    .all(|filter| filter.value.matches(&stock_text(stock, &filter.key)))
+            })
+            .cloned()
+            .collect();
+        self.unfiltered_stocks = Some(stocks);
+    }
+
     fn render_percent(&self, col: &Column, val: f64, cx: &mut App) -> AnyElement {
         let right_num = ((val - val.floor()) * 1000.).floor() as i32;
 
@@ -476,6 +511,30 @@ impl TableDelegate for StockTableDelegate {
     /// This is means render the full table cells takes 232.745µs. Then 232.745µs / 8.82ms = 2.6% of the frame duration.
     ///
     /// If we improve the td rendering, we can reduce the time to render the full table cells.
+    fn render_footer_cell(
+        &mut self,
+        col_ix: usize,
+        _: &mut Window,
+        _: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        let Some(col) = self.columns.get(col_ix) else {
+            return div();
+        };
+
+        match col.key.as_ref() {
+            "name" => div().child(format!("{} stocks", self.stocks.len())),
+            "volume" => {
+                let total = self.stocks.iter().map(|stock| stock.volume).sum::<f64>();
+                div().child(format!("{:.3}", total))
+            }
+            "turnover" => {
+                let total = self.stocks.iter().map(|stock| stock.turnover).sum::<f64>();
+                div().child(format!("{:.3}", total))
+            }
+            _ => div(),
+        }
+    }
+
     fn render_td(
         &mut self,
         row_ix: usize,
@@ -590,6 +649,17 @@ impl TableDelegate for StockTableDelegate {
         self.columns.insert(to_ix, col);
     }
 
+    fn move_row(
+        &mut self,
+        row_ix: usize,
+        to_ix: usize,
+        _: &mut Window,
+        _: &mut Context<TableState<Self>>,
+    ) {
+        let stock = self.stocks.remove(row_ix);
+        self.stocks.insert(to_ix, stock);
+    }
+
     fn perform_sort(
         &mut self,
         col_ix: usize,
@@ -677,6 +747,45 @@ impl TableDelegate for StockTableDelegate {
         self.visible_cols = visible_range;
     }
 
+    fn render_expanded_row(
+        &mut self,
+        row_ix: usize,
+        _: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        let Some(stock) = self.stocks.get(row_ix) else {
+            return div();
+        };
+
+        let item = |label: &'static str, value: String| {
+            v_flex()
+                .gap_1()
+                .child(Label::new(label).text_color(cx.theme().muted_foreground))
+                .child(value)
+        };
+
+        div().size_full().p_3().child(
+            h_flex()
+                .gap_8()
+                .child(item("Name", stock.counter.name.to_string()))
+                .child(item("Open", format!("{:.3}", stock.open)))
+                .child(item("High", format!("{:.3}", stock.high)))
+                .child(item("Low", format!("{:.3}", stock.low)))
+                .child(item("Prev Close", format!("{:.3}", stock.prev_close)))
+                .child(item("Market Cap", format!("{:.3}", stock.market_cap))),
+        )
+    }
+
+    fn expanded_row_height(&self, _: usize, _: &App) -> gpui::Pixels {
+        px(72.)
+    }
+
+    fn is_cell_editable(&self, _: usize, col_ix: usize, _: &App) -> bool {
+        self.columns
+            .get(col_ix)
+            .map_or(false, |col| col.key.as_ref() == "name")
+    }
+
     fn cell_text(&self, row_ix: usize, col_ix: usize, _cx: &App) -> String {
         let Some(stock) = self.stocks.get(row_ix) else {
             return String::new();
@@ -685,54 +794,59 @@ impl TableDelegate for StockTableDelegate {
             return String::new();
         };
 
-        match col.key.as_ref() {
-            "id" => stock.id.to_string(),
-            "market" => stock.counter.market.to_string(),
-            "symbol" => stock.counter.symbol_code().to_string(),
-            "name" => stock.counter.name.to_string(),
-            "price" => format!("{:.3}", stock.price),
-            "change" => format!("{:.3}", stock.change),
-            "change_percent" => format!("{:.2}%", stock.change_percent * 100.),
-            "volume" => format!("{:.3}", stock.volume),
-            "turnover" => format!("{:.3}", stock.turnover),
-            "market_cap" => format!("{:.3}", stock.market_cap),
-            "ttm" => format!("{:.3}", stock.ttm),
-            "five_mins_ranking" => format!("{:.3}", stock.five_mins_ranking),
-            "th60_days_ranking" => stock.th60_days_ranking.floor().to_string(),
-            "year_change_percent" => format!("{:.2}%", stock.year_change_percent * 100.),
-            "bid" => format!("{:.3}", stock.bid),
-            "bid_volume" => format!("{:.3}", stock.bid_volume),
-            "ask" => format!("{:.3}", stock.ask),
-            "ask_volume" => format!("{:.3}", stock.ask_volume),
-            "open" => format!("{:.3}", stock.open),
-            "prev_close" => format!("{:.3}", stock.prev_close),
-            "high" => format!("{:.3}", stock.high),
-            "low" => format!("{:.3}", stock.low),
-            "turnover_rate" => format!("{:.0}", stock.turnover_rate * 100.),
-            "rise_rate" => format!("{:.0}", stock.rise_rate * 100.),
-            "amplitude" => format!("{:.0}", stock.amplitude * 100.),
-            "pe_status" => stock.pe_status.floor().to_string(),
-            "pb_status" => stock.pb_status.floor().to_string(),
-            "volume_ratio" => format!("{:.3}", stock.volume_ratio),
-            "bid_ask_ratio" => format!("{:.3}", stock.bid_ask_ratio),
-            "latest_pre_close" => stock.latest_pre_close.floor().to_string(),
-            "latest_post_close" => stock.latest_post_close.floor().to_string(),
-            "pre_market_cap" => stock.pre_market_cap.floor().to_string(),
-            "pre_market_percent" => format!("{:.2}%", stock.pre_market_percent * 100.),
-            "pre_market_change" => stock.pre_market_change.floor().to_string(),
-            "post_market_cap" => stock.post_market_cap.floor().to_string(),
-            "post_market_percent" => format!("{:.2}%", stock.post_market_percent * 100.),
-            "post_market_change" => stock.post_market_change.floor().to_string(),
-            "float_cap" => stock.float_cap.floor().to_string(),
-            "shares" => stock.shares.to_string(),
-            "shares_float" => stock.shares_float.to_string(),
-            "day_5_ranking" => stock.day_5_ranking.floor().to_string(),
-            "day_10_ranking" => stock.day_10_ranking.floor().to_string(),
-            "day_30_ranking" => stock.day_30_ranking.floor().to_string(),
-            "day_120_ranking" => stock.day_120_ranking.floor().to_string(),
-            "day_250_ranking" => stock.day_250_ranking.floor().to_string(),
-            _ => String::new(),
-        }
+        stock_text(stock, &col.key)
+    }
+}
+
+/// Returns the text of the stock by the column key.
+fn stock_text(stock: &Stock, key: &str) -> String {
+    match key {
+        "id" => stock.id.to_string(),
+        "market" => stock.counter.market.to_string(),
+        "symbol" => stock.counter.symbol_code().to_string(),
+        "name" => stock.counter.name.to_string(),
+        "price" => format!("{:.3}", stock.price),
+        "change" => format!("{:.3}", stock.change),
+        "change_percent" => format!("{:.2}%", stock.change_percent * 100.),
+        "volume" => format!("{:.3}", stock.volume),
+        "turnover" => format!("{:.3}", stock.turnover),
+        "market_cap" => format!("{:.3}", stock.market_cap),
+        "ttm" => format!("{:.3}", stock.ttm),
+        "five_mins_ranking" => format!("{:.3}", stock.five_mins_ranking),
+        "th60_days_ranking" => stock.th60_days_ranking.floor().to_string(),
+        "year_change_percent" => format!("{:.2}%", stock.year_change_percent * 100.),
+        "bid" => format!("{:.3}", stock.bid),
+        "bid_volume" => format!("{:.3}", stock.bid_volume),
+        "ask" => format!("{:.3}", stock.ask),
+        "ask_volume" => format!("{:.3}", stock.ask_volume),
+        "open" => format!("{:.3}", stock.open),
+        "prev_close" => format!("{:.3}", stock.prev_close),
+        "high" => format!("{:.3}", stock.high),
+        "low" => format!("{:.3}", stock.low),
+        "turnover_rate" => format!("{:.0}", stock.turnover_rate * 100.),
+        "rise_rate" => format!("{:.0}", stock.rise_rate * 100.),
+        "amplitude" => format!("{:.0}", stock.amplitude * 100.),
+        "pe_status" => stock.pe_status.floor().to_string(),
+        "pb_status" => stock.pb_status.floor().to_string(),
+        "volume_ratio" => format!("{:.3}", stock.volume_ratio),
+        "bid_ask_ratio" => format!("{:.3}", stock.bid_ask_ratio),
+        "latest_pre_close" => stock.latest_pre_close.floor().to_string(),
+        "latest_post_close" => stock.latest_post_close.floor().to_string(),
+        "pre_market_cap" => stock.pre_market_cap.floor().to_string(),
+        "pre_market_percent" => format!("{:.2}%", stock.pre_market_percent * 100.),
+        "pre_market_change" => stock.pre_market_change.floor().to_string(),
+        "post_market_cap" => stock.post_market_cap.floor().to_string(),
+        "post_market_percent" => format!("{:.2}%", stock.post_market_percent * 100.),
+        "post_market_change" => stock.post_market_change.floor().to_string(),
+        "float_cap" => stock.float_cap.floor().to_string(),
+        "shares" => stock.shares.to_string(),
+        "shares_float" => stock.shares_float.to_string(),
+        "day_5_ranking" => stock.day_5_ranking.floor().to_string(),
+        "day_10_ranking" => stock.day_10_ranking.floor().to_string(),
+        "day_30_ranking" => stock.day_30_ranking.floor().to_string(),
+        "day_120_ranking" => stock.day_120_ranking.floor().to_string(),
+        "day_250_ranking" => stock.day_250_ranking.floor().to_string(),
+        _ => String::new(),
     }
 }
 
@@ -795,7 +909,11 @@ impl DataTableStory {
         });
 
         let delegate = StockTableDelegate::new(5000);
-        let table = cx.new(|cx| TableState::new(delegate, window, cx));
+        let table = cx.new(|cx| {
+            TableState::new(delegate, window, cx)
+                .column_picker(true)
+                .footer(true)
+        });
 
         let _subscriptions = vec![
             cx.subscribe_in(&table, window, Self::on_table_event),
@@ -864,8 +982,9 @@ impl DataTableStory {
                         return;
                     }
 
-                    self.table.update(cx, |table, _| {
+                    self.table.update(cx, |table, cx| {
                         table.delegate_mut().update_stocks(total_count);
+                        table.clear_filters(cx);
                     });
                     cx.notify();
                 }
@@ -917,6 +1036,13 @@ impl DataTableStory {
         });
     }
 
+    fn toggle_row_order(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.table.update(cx, |table, cx| {
+            table.row_movable = *checked;
+            cx.notify();
+        });
+    }
+
     fn toggle_col_sort(&mut self, checked: &bool, _: &mut Window, cx: &mut Context<Self>) {
         self.table.update(cx, |table, cx| {
             table.sortable = *checked;
@@ -986,7 +1112,7 @@ impl DataTableStory {
         _: &Entity<TableState<StockTableDelegate>>,
         event: &TableEvent,
         _window: &mut Window,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) {
         match event {
             TableEvent::ColumnWidthsChanged(col_widths) => {
@@ -999,6 +1125,18 @@ impl DataTableStory {
             TableEvent::DoubleClickedCell(row_ix, col_ix) => {
                 println!("Double clicked cell: row={}, col={}", row_ix, col_ix)
             }
+            TableEvent::CellEdited(row_ix, col_ix, value) => {
+                println!(
+                    "Edited cell: row={}, col={}, value={}",
+                    row_ix, col_ix, value
+                );
+                self.table.update(cx, |table, cx| {
+                    if let Some(stock) = table.delegate_mut().stocks.get_mut(*row_ix) {
+                        stock.counter.name = value.clone();
+                    }
+                    cx.notify();
+                });
+            }
             TableEvent::DoubleClickedRow(ix) => println!("Double clicked row: {}", ix),
             TableEvent::SelectRow(ix) => println!("Select row: {}", ix),
             TableEvent::MoveColumn(origin_idx, target_idx) => {
@@ -1008,6 +1146,28 @@ impl DataTableStory {
             TableEvent::RightClickedCell(row_ix, col_ix) => {
                 println!("Right clicked cell: row={}, col={}", row_ix, col_ix)
             }
+            TableEvent::ExpandRow(row_ix, expanded) => {
+                println!("Expand row: {}, expanded: {}", row_ix, expanded)
+            }
+            TableEvent::ExpandChildren(row_ix, expanded) => {
+                println!("Expand children: {}, expanded: {}", row_ix, expanded)
+            }
+            TableEvent::SelectionChanged(rows) => {
+                println!("Selected rows: {:?}", rows)
+            }
+            TableEvent::ExpandGroup(key, expanded) => {
+                println!("Expand group: {}, expanded: {}", key, expanded)
+            }
+            TableEvent::ColumnVisibilityChanged(col_ix, visible) => {
+                println!("Column visibility: {}, visible: {}", col_ix, visible)
+            }
+            TableEvent::FilterChanged(filters) => {
+                println!("Filters: {:?}", filters);
+                self.table.update(cx, |table, cx| {
+                    table.delegate_mut().filter_stocks(filters);
+                    cx.notify();
+                });
+            }
             TableEvent::ClearSelection => {
                 println!("Selection cleared");
             }
@@ -1015,55 +1175,14 @@ impl DataTableStory {
     }
 
     fn dump_csv(&mut self, _: &ClickEvent, window: &mut Window, cx: &mut Context<Self>) {
-        match self.write_csv(cx) {
-            Ok(csv_content) => {
-                let Some(path) = dirs::download_dir() else {
-                    eprintln!("Failed to get download directory");
-                    return;
-                };
-                let receiver = cx.prompt_for_new_path(&path, Some("export.csv"));
-                cx.spawn_in(window, async move |_, _| {
-                    if let Some(path) = receiver.await.ok().into_iter().flatten().flatten().next() {
-                        match std::fs::write(&path, csv_content) {
-                            Ok(_) => {
-                                println!("CSV exported successfully to: {:?}", path);
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to save CSV file: {}", e);
-                            }
-                        }
-                    } else {
-                        println!("CSV export cancelled by user");
-                    };
-                })
-                .detach();
-            }
-            Err(e) => {
-                eprintln!("Failed to export CSV: {}", e);
-            }
-        }
-    }
-
-    fn write_csv(&mut self, cx: &mut Context<Self>) -> anyhow::Result<String> {
-        let (headers, rows) = self.table.update(cx, |table, cx| table.dump(cx));
-
-        // Convert to CSV format using rust-csv
-        let mut wtr = csv::Writer::from_writer(vec![]);
-
-        // Write header
-        wtr.write_record(&headers)?;
-
-        // Write data rows
-        for row in rows {
-            wtr.write_record(&row)?;
-        }
-
-        // Flush and get the CSV data
-        wtr.flush()?;
-        let data = wtr.into_inner().map_err(csv::IntoInnerError::into_error)?;
-        let csv_content = String::from_utf8(data)?;
+        let Some(path) = dirs::download_dir() else {
+            eprintln!("Failed to get download directory");
+            return;
+        };
 
-        Ok(csv_content)
+        self.table.update(cx, |table, cx| {
+            table.prompt_export_csv(&path, &CsvExportOptions::new(), window, cx);
+        });
     }
 }
 
@@ -1102,6 +1221,12 @@ impl Render for DataTableStory {
                             .selected(table.col_movable)
                             .on_click(cx.listener(Self::toggle_col_order)),
                     )
+                    .child(
+                        Checkbox::new("row-order")
+                            .label("Row Order")
+                            .selected(table.row_movable)
+                            .on_click(cx.listener(Self::toggle_row_order)),
+                    )
                     .child(
                         Checkbox::new("col-sort")
                             .label("Sortable")
@@ -1132,12 +1257,47 @@ impl Render for DataTableStory {
                             .selected(table.row_header)
                             .on_click(cx.listener(Self::toggle_row_header)),
                     )
+                    .child(
+                        Checkbox::new("multi-selectable")
+                            .label("Multi Selection")
+                            .selected(table.multi_selectable)
+                            .on_click(cx.listener(|this, check: &bool, _, cx| {
+                                this.table.update(cx, |this, cx| {
+                                    this.multi_selectable = *check;
+                                    if !*check {
+                                        this.set_selected_rows([], cx);
+                                    }
+                                    cx.notify();
+                                })
+                            })),
+                    )
+                    .child(
+                        Checkbox::new("expandable")
+                            .label("Expandable")
+                            .selected(table.expandable)
+                            .on_click(cx.listener(|this, check: &bool, _, cx| {
+                                this.table.update(cx, |this, cx| {
+                                    this.expandable = *check;
+                                    cx.notify();
+                                })
+                            })),
+                    )
                     .child(
                         Checkbox::new("fixed")
                             .label("Column Fixed")
                             .selected(table.col_fixed)
                             .on_click(cx.listener(Self::toggle_col_fixed)),
                     )
+                    .child(
+                        Checkbox::new("group-by-market")
+                            .label("Group by Market")
+                            .selected(table.grouped_col().is_some())
+                            .on_click(cx.listener(|this, check: &bool, _, cx| {
+                                this.table.update(cx, |this, cx| {
+                                    this.set_group_by(check.then_some(1), cx);
+                                })
+                            })),
+                    )
                     .child(
                         Checkbox::new("stripe")
                             .label("Stripe")
@@ -1155,6 +1315,17 @@ impl Render for DataTableStory {
                                 })
                             })),
                     )
+                    .child(
+                        Checkbox::new("loading-skeleton")
+                            .label("Loading Skeleton")
+                            .selected(table.loading_skeleton)
+                            .on_click(cx.listener(|this, check: &bool, _, cx| {
+                                this.table.update(cx, |this, cx| {
+                                    this.loading_skeleton = *check;
+                                    cx.notify();
+                                })
+                            })),
+                    )
                     .child(
                         Checkbox::new("refresh-data")
                             .label("Refresh Data")
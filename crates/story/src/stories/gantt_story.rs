@@ -0,0 +1,158 @@
+use chrono::{Duration, Local};
+use gpui::{
+    App, AppContext, Context, Entity, IntoElement, ParentElement, Render, SharedString, Styled,
+    Subscription, Window, div, px,
+};
+
+use gpui_component::{
+    ActiveTheme as _,
+    button::{Button, ButtonVariants as _},
+    gantt::{GanttChart, GanttEvent, GanttState, GanttTask},
+    h_flex, v_flex,
+};
+
+use crate::{Story, section};
+
+const PHASES: [&str; 5] = ["Design", "Build", "Review", "Test", "Release"];
+
+pub struct GanttStory {
+    state: Entity<GanttState>,
+    message: SharedString,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl Story for GanttStory {
+    fn title() -> &'static str {
+        "GanttChart"
+    }
+
+    fn description() -> &'static str {
+        "A timeline of tasks with draggable bars and dependencies."
+    }
+
+    fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render> {
+        Self::view(window, cx)
+    }
+}
+
+impl GanttStory {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        let today = Local::now().naive_local().date();
+        let colors = [
+            cx.theme().chart_1,
+            cx.theme().chart_2,
+            cx.theme().chart_3,
+            cx.theme().chart_4,
+            cx.theme().chart_5,
+        ];
+
+        // 60 projects of 5 phases, each phase depends on the previous one.
+        let mut tasks = vec![];
+        for project in 0..60 {
+            let mut start = today + Duration::days(project * 3 - 20);
+            for (ix, phase) in PHASES.iter().enumerate() {
+                let id = format!("{}-{}", project, ix);
+                let days = 2 + ((project as usize + ix * 3) % 6) as i64;
+                let mut task = if ix == PHASES.len() - 1 {
+                    GanttTask::new(id, format!("#{} {}", project + 1, phase), start, start)
+                } else {
+                    GanttTask::new(
+                        id,
+                        format!("#{} {}", project + 1, phase),
+                        start,
+                        start + Duration::days(days),
+                    )
+                    .progress(((40 - project * 3 - ix as i64 * 4) as f32 / 10.).clamp(0., 1.))
+                    .color(colors[ix])
+                };
+                if ix > 0 {
+                    task = task.depends_on(format!("{}-{}", project, ix - 1));
+                }
+
+                start = task.end;
+                tasks.push(task);
+            }
+        }
+
+        let state = cx.new(|cx| GanttState::new(cx).tasks(tasks));
+        let _subscriptions = vec![cx.subscribe(&state, |this, _, event, cx| {
+            this.message = match event {
+                GanttEvent::Change { id, start, end } => {
+                    format!("Changed {}: {} - {}", id, start, end).into()
+                }
+                GanttEvent::Select(id) => format!("Selected {}", id).into(),
+            };
+            cx.notify();
+        })];
+
+        Self {
+            state,
+            message: SharedString::default(),
+            _subscriptions,
+        }
+    }
+}
+
+impl Render for GanttStory {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("zoom-out")
+                            .outline()
+                            .label("Zoom Out")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.state.update(cx, |state, cx| state.zoom_out(cx));
+                            })),
+                    )
+                    .child(
+                        Button::new("zoom-in")
+                            .outline()
+                            .label("Zoom In")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.state.update(cx, |state, cx| state.zoom_in(cx));
+                            })),
+                    )
+                    .child(
+                        Button::new("today")
+                            .outline()
+                            .label("Today")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.state.update(cx, |state, cx| state.scroll_to_today(cx));
+                            })),
+                    )
+                    .child(
+                        Button::new("last-task")
+                            .ghost()
+                            .label("Go to #60 Release")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.state.update(cx, |state, cx| {
+                                    state.scroll_to_task(&"59-4".into(), cx)
+                                });
+                            })),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(self.message.clone()),
+                    ),
+            )
+            .child(
+                section("Project schedule")
+                    .sub_title(
+                        "Drag the bars to move, drag the edges to resize, scroll horizontally to pan.",
+                    )
+                    .w_full()
+                    .child(GanttChart::new(&self.state).label_width(px(180.)).h(px(560.))),
+            )
+    }
+}
@@ -0,0 +1,190 @@
+use gpui::{
+    App, AppContext, Context, Entity, IntoElement, ParentElement, Render, SharedString, Styled,
+    Subscription, Window, div, px,
+};
+
+use gpui_component::{
+    ActiveTheme as _, StyledExt as _,
+    button::{Button, ButtonVariants as _},
+    graph::{Graph, GraphDelegate, GraphDirection, GraphEvent, GraphNodeState, GraphState},
+    h_flex, v_flex,
+};
+
+use crate::{Story, section};
+
+const TITLES: [&str; 5] = ["CEO", "VP", "Director", "Manager", "Engineer"];
+const NAMES: [&str; 12] = [
+    "Alice", "Bob", "Carol", "David", "Erin", "Frank", "Grace", "Heidi", "Ivan", "Judy", "Mallory",
+    "Oscar",
+];
+
+/// An org chart of the generated employees, the id is the path of the indices from the CEO,
+/// e.g. `0.2.1`. The reports are only generated when a node is expanded.
+struct OrgChartDelegate;
+
+impl OrgChartDelegate {
+    fn depth(id: &SharedString) -> usize {
+        id.matches('.').count()
+    }
+
+    fn name(id: &SharedString) -> &'static str {
+        let hash = id.bytes().fold(7usize, |hash, b| {
+            hash.wrapping_mul(31).wrapping_add(b as usize)
+        });
+        NAMES[hash % NAMES.len()]
+    }
+}
+
+impl GraphDelegate for OrgChartDelegate {
+    fn roots(&self, _: &App) -> Vec<SharedString> {
+        vec!["0".into()]
+    }
+
+    fn children(&self, id: &SharedString, _: &App) -> Vec<SharedString> {
+        let depth = Self::depth(id);
+        if depth + 1 >= TITLES.len() {
+            return vec![];
+        }
+
+        let count = 2 + (id.len() + depth) % 3;
+        (0..count)
+            .map(|ix| format!("{}.{}", id, ix).into())
+            .collect()
+    }
+
+    fn has_children(&self, id: &SharedString, _: &App) -> bool {
+        Self::depth(id) + 1 < TITLES.len()
+    }
+
+    fn links(&self, _: &App) -> Vec<(SharedString, SharedString)> {
+        // A dotted-line report.
+        vec![("0.1".into(), "0.0.1".into())]
+    }
+
+    fn expanded_by_default(&self, _: &SharedString, depth: usize, _: &App) -> bool {
+        depth < 2
+    }
+
+    fn render_node(
+        &mut self,
+        id: &SharedString,
+        node: GraphNodeState,
+        _: &mut Window,
+        cx: &mut Context<GraphState<Self>>,
+    ) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .justify_center()
+            .px_3()
+            .child(div().text_sm().font_semibold().child(Self::name(id)))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!("{} · {}", TITLES[node.depth], id)),
+            )
+    }
+}
+
+pub struct GraphStory {
+    state: Entity<GraphState<OrgChartDelegate>>,
+    message: SharedString,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl Story for GraphStory {
+    fn title() -> &'static str {
+        "Graph"
+    }
+
+    fn description() -> &'static str {
+        "A node-link diagram with tree layout, pan, zoom and lazy expansion."
+    }
+
+    fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render> {
+        Self::view(window, cx)
+    }
+}
+
+impl GraphStory {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let state = cx.new(|cx| GraphState::new(OrgChartDelegate, window, cx));
+        let _subscriptions = vec![cx.subscribe(&state, |this, _, event, cx| {
+            this.message = match event {
+                GraphEvent::Click(id) => format!("Clicked {}", id).into(),
+                GraphEvent::Hover(Some(id)) => format!("Hovered {}", id).into(),
+                GraphEvent::Hover(None) => return,
+                GraphEvent::Expand(id) => format!("Expanded {}", id).into(),
+                GraphEvent::Collapse(id) => format!("Collapsed {}", id).into(),
+            };
+            cx.notify();
+        })];
+
+        Self {
+            state,
+            message: SharedString::default(),
+            _subscriptions,
+        }
+    }
+}
+
+impl Render for GraphStory {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .gap_3()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("top-down")
+                            .outline()
+                            .label("Top Down")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.state.update(cx, |state, cx| {
+                                    state.set_direction(GraphDirection::TopDown, cx)
+                                });
+                            })),
+                    )
+                    .child(
+                        Button::new("left-right")
+                            .outline()
+                            .label("Left Right")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.state.update(cx, |state, cx| {
+                                    state.set_direction(GraphDirection::LeftRight, cx)
+                                });
+                            })),
+                    )
+                    .child(
+                        Button::new("find")
+                            .ghost()
+                            .label("Go to 0.1")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.state.update(cx, |state, cx| {
+                                    state.set_selected(Some("0.1".into()), cx);
+                                    state.scroll_to_node(&"0.1".into(), cx);
+                                });
+                            })),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(self.message.clone()),
+                    ),
+            )
+            .child(
+                section("Org chart")
+                    .sub_title(
+                        "Drag to pan, scroll with cmd/ctrl to zoom, click the buttons under the nodes to expand.",
+                    )
+                    .w_full()
+                    .child(Graph::new(&self.state).h(px(560.))),
+            )
+    }
+}
@@ -1,23 +1,38 @@
 use gpui::{
     App, AppContext, Context, Entity, FocusHandle, Focusable, IntoElement, ParentElement, Render,
-    Styled, Window,
+    Styled, Subscription, Window,
 };
 use gpui_component::{
+    ActiveTheme as _, Icon, IconName, Sizable,
     button::{Button, ButtonVariant, ButtonVariants},
     dock::PanelControl,
-    h_flex, neutral_500, v_flex, ActiveTheme as _, Icon, IconName, Sizable,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    neutral_500, v_flex,
 };
 
 use crate::section;
 
 pub struct IconStory {
     focus_handle: gpui::FocusHandle,
+    search_input: Entity<InputState>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl IconStory {
-    fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let search_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Search icons by name or tag"));
+        let _subscriptions = vec![cx.subscribe(&search_input, |_, _, event, cx| {
+            if let InputEvent::Change = event {
+                cx.notify();
+            }
+        })];
+
         Self {
             focus_handle: cx.focus_handle(),
+            search_input,
+            _subscriptions,
         }
     }
 
@@ -52,6 +67,8 @@ impl Focusable for IconStory {
 
 impl Render for IconStory {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let query = self.search_input.read(cx).value();
+
         v_flex()
             .gap_4()
             .child(
@@ -111,6 +128,30 @@ impl Render for IconStory {
                         ),
                 ),
             )
+            .child(
+                section("Search Icons").child(
+                    v_flex()
+                        .w_full()
+                        .gap_3()
+                        .child(Input::new(&self.search_input).cleanable(true))
+                        .child(
+                            h_flex().flex_wrap().gap_1().children(
+                                IconName::all()
+                                    .iter()
+                                    .filter(|icon| icon.matches(&query))
+                                    .map(|icon| {
+                                        let name = icon.name();
+                                        let tooltip =
+                                            format!("{} ({})", name, icon.categories().join(", "));
+                                        Button::new(name)
+                                            .ghost()
+                                            .icon(icon.clone())
+                                            .tooltip(tooltip)
+                                    }),
+                            ),
+                        ),
+                ),
+            )
             .child(
                 section("Button with size").child(
                     Button::new("button-with-size")
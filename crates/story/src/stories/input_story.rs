@@ -1,10 +1,12 @@
 use gpui::{
     App, AppContext as _, ClickEvent, Context, Entity, InteractiveElement, IntoElement,
-    ParentElement as _, Render, Role, Styled, Subscription, Window, div,
+    ParentElement as _, Render, Role, SharedString, Styled, Subscription, Window, div,
 };
 
+use std::{rc::Rc, time::Duration};
+
 use crate::section;
-use gpui_component::{button::*, input::*, label::Label, *};
+use gpui_component::{button::*, input::*, label::Label, storage::MemoryStorage, *};
 
 const CODE_EXAMPLE: &str = r#"{"single_line":"code editor"}"#;
 
@@ -14,6 +16,8 @@ pub struct InputStory {
     input1: Entity<InputState>,
     input2: Entity<InputState>,
     input_esc: Entity<InputState>,
+    max_length_input: Entity<InputState>,
+    history_input: Entity<InputState>,
     input_text_centered: Entity<InputState>,
     input_text_right: Entity<InputState>,
     mask_input: Entity<InputState>,
@@ -27,16 +31,60 @@ pub struct InputStory {
     small_input: Entity<InputState>,
     phone_input: Entity<InputState>,
     mask_input2: Entity<InputState>,
+    card_input: Entity<InputState>,
     currency_input: Entity<InputState>,
     custom_input: Entity<InputState>,
     custom_menu_input: Entity<InputState>,
     code_input: Entity<InputState>,
     color_input: Entity<InputState>,
+    search_input: Entity<SearchInputState>,
+    search_message: SharedString,
     content_type_inputs: Vec<ContentTypeInput>,
 
     _subscriptions: Vec<Subscription>,
 }
 
+/// Credit card number mask, American Express is grouped by 4-6-5, others by 4-4-4-4.
+struct CreditCardMask;
+
+impl CreditCardMask {
+    fn groups(digits: &str) -> &'static [usize] {
+        if digits.starts_with("34") || digits.starts_with("37") {
+            &[4, 6, 5]
+        } else {
+            &[4, 4, 4, 4]
+        }
+    }
+}
+
+impl MaskProvider for CreditCardMask {
+    fn mask(&self, text: &str) -> SharedString {
+        let digits = self.unmask(text);
+        let mut chars = digits.chars();
+        Self::groups(&digits)
+            .iter()
+            .map(|len| chars.by_ref().take(*len).collect::<String>())
+            .filter(|group| !group.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .into()
+    }
+
+    fn unmask(&self, mask_text: &str) -> String {
+        mask_text.chars().filter(|ch| *ch != ' ').collect()
+    }
+
+    fn is_valid(&self, text: &str) -> bool {
+        let digits = self.unmask(text);
+        digits.chars().all(|ch| ch.is_ascii_digit())
+            && digits.len() <= Self::groups(&digits).iter().sum()
+    }
+
+    fn placeholder(&self) -> Option<String> {
+        Some("Card number".into())
+    }
+}
+
 struct ContentTypeInput {
     label: &'static str,
     content_type: InputContentType,
@@ -75,6 +123,16 @@ impl InputStory {
                 .placeholder("Enter text and clear it by pressing ESC")
                 .clean_on_escape()
         });
+        let max_length_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Up to 20 characters")
+                .max_length(20)
+        });
+        let history_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Press Enter to commit, Up/Down to recall")
+                .value_history("input-story.history", Rc::new(MemoryStorage::new()))
+        });
 
         let mask_input = cx.new(|cx| {
             InputState::new(window, cx)
@@ -106,6 +164,9 @@ impl InputStory {
 
         let phone_input = cx.new(|cx| InputState::new(window, cx).mask_pattern("(999)-999-9999"));
         let mask_input2 = cx.new(|cx| InputState::new(window, cx).mask_pattern("AAA-###-AAA"));
+        let card_input = cx.new(|cx| {
+            InputState::new(window, cx).mask_pattern(MaskPattern::custom(CreditCardMask))
+        });
         let currency_input = cx.new(|cx| {
             InputState::new(window, cx).mask_pattern(MaskPattern::Number {
                 separator: Some(','),
@@ -258,16 +319,30 @@ impl InputStory {
             ),
         ];
 
+        let search_input = cx.new(|cx| {
+            SearchInputState::new(window, cx)
+                .placeholder("Search files...", window, cx)
+                .filters(vec![
+                    SearchFilter::new("files", "Files").selected(true),
+                    SearchFilter::new("symbols", "Symbols"),
+                ])
+                .history_storage("input-story.search", Rc::new(MemoryStorage::new()))
+        });
+
         let _subscriptions = vec![
+            cx.subscribe_in(&search_input, window, Self::on_search_event),
             cx.subscribe_in(&input1, window, Self::on_input_event),
             cx.subscribe_in(&input2, window, Self::on_input_event),
             cx.subscribe_in(&phone_input, window, Self::on_input_event),
+            cx.subscribe_in(&max_length_input, window, Self::on_input_event),
         ];
 
         Self {
             input1,
             input2,
             input_esc,
+            max_length_input,
+            history_input,
             mask_input,
             disabled_input: cx
                 .new(|cx| InputState::new(window, cx).default_value("This is disabled input")),
@@ -284,11 +359,14 @@ impl InputStory {
             complete_disabled_input,
             phone_input,
             mask_input2,
+            card_input,
             currency_input,
             custom_input,
             custom_menu_input,
             code_input,
             color_input,
+            search_input,
+            search_message: SharedString::default(),
             input_text_centered,
             input_text_right,
             content_type_inputs,
@@ -296,6 +374,40 @@ impl InputStory {
         }
     }
 
+    fn on_search_event(
+        &mut self,
+        state: &Entity<SearchInputState>,
+        event: &SearchInputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (query, submitted) = match event {
+            SearchInputEvent::Change(query) => (query.clone(), false),
+            SearchInputEvent::Submit(query) => (query.clone(), true),
+            SearchInputEvent::FilterChange => (state.read(cx).value(cx), false),
+        };
+        let scopes = state.read(cx).selected_filters().join(", ");
+        self.search_message = format!(
+            "{} {:?} in [{}]",
+            if submitted { "Submitted" } else { "Searching" },
+            query,
+            scopes
+        )
+        .into();
+
+        // Simulate a slow search.
+        state.update(cx, |state, cx| state.set_loading(true, window, cx));
+        let state = state.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(500))
+                .await;
+            _ = state.update_in(cx, |state, window, cx| state.set_loading(false, window, cx));
+        })
+        .detach();
+        cx.notify();
+    }
+
     fn new_content_type_input(
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -369,6 +481,7 @@ impl InputStory {
             }
             InputEvent::Focus => println!("Focus"),
             InputEvent::Blur => println!("Blur"),
+            InputEvent::MaxLengthExceeded => println!("MaxLengthExceeded"),
         };
     }
 
@@ -501,6 +614,19 @@ impl Render for InputStory {
                             )),
                     ),
             )
+            .child(
+                section("Input with custom mask: Credit Card")
+                    .max_w_md()
+                    .child(Input::new(&self.card_input))
+                    .child(
+                        v_flex()
+                            .child(format!("Value: {:?}", self.card_input.read(cx).value()))
+                            .child(format!(
+                                "Unmask Value: {:?}",
+                                self.card_input.read(cx).unmask_value()
+                            )),
+                    ),
+            )
             .child(
                 section("Input Size")
                     .max_w_md()
@@ -512,6 +638,22 @@ impl Render for InputStory {
                     .max_w_md()
                     .child(Input::new(&self.input_esc).cleanable(true)),
             )
+            .child(
+                section("Max Length")
+                    .max_w_md()
+                    .child(Input::new(&self.max_length_input).show_count(true)),
+            )
+            .child(
+                section("Value History")
+                    .max_w_md()
+                    .child(Input::new(&self.history_input)),
+            )
+            .child(
+                section("Search Input")
+                    .max_w_md()
+                    .child(SearchInput::new(&self.search_input))
+                    .child(div().text_sm().child(self.search_message.clone())),
+            )
             .child(
                 section("Focused Input")
                     .max_w_md()
@@ -4,7 +4,7 @@ use gpui::{
 };
 
 use gpui_component::{
-    IconName, StyledExt,
+    IconName, LineClamp, MiddleEllipsis, StyledExt,
     button::{Button, ButtonVariant, ButtonVariants as _},
     checkbox::Checkbox,
     green_500, h_flex,
@@ -176,6 +176,39 @@ impl Render for LabelStory {
                         ),
                     ),
             )
+            .child(
+                section("Middle Ellipsis").max_w_md().child(
+                    v_flex()
+                        .w(px(240.))
+                        .gap_2()
+                        .child(
+                            Label::new("/Users/jason/workspace/gpui-component/crates/ui/src/label.rs")
+                                .middle_ellipsis(),
+                        )
+                        .child(
+                            h_flex().gap_2().child(Label::new("Path:")).child(
+                                div().flex_1().min_w_0().text_sm().child(MiddleEllipsis::new(
+                                    "/Users/jason/workspace/gpui-component/crates/ui/src/lib.rs",
+                                )),
+                            ),
+                        ),
+                ),
+            )
+            .child(
+                section("Line Clamp").max_w_md().child(
+                    LineClamp::new(
+                        "line-clamp",
+                        "GPUI Component is a UI component library for building fantastic \
+                        desktop applications using GPUI. It provides 60+ cross-platform \
+                        desktop UI components, with the theme support, virtualized table \
+                        and list, markdown and code editor, and so on.",
+                    )
+                    .lines(2)
+                    .expandable(true)
+                    .w(px(300.))
+                    .text_sm(),
+                ),
+            )
             .child(
                 section("Masked Label").max_w_md().child(
                     v_flex()
@@ -4,16 +4,17 @@ use fake::Fake;
 use gpui::{
     App, AppContext, Context, ElementId, Entity, FocusHandle, Focusable, InteractiveElement,
     IntoElement, ParentElement, Render, RenderOnce, ScrollStrategy, SharedString, Styled,
-    Subscription, Task, Window, actions, div, px,
+    Subscription, Task, Window, actions, div, prelude::FluentBuilder as _, px,
 };
 
 use gpui_component::{
     ActiveTheme, Icon, IconName, IndexPath, Selectable, Sizable,
     button::Button,
     checkbox::Checkbox,
+    dnd::{DragDropExt as _, DragItem, DropTarget},
     h_flex,
     label::Label,
-    list::{List, ListDelegate, ListEvent, ListItem, ListState},
+    list::{FuzzyMatcher, List, ListDelegate, ListEvent, ListItem, ListState},
     v_flex,
 };
 
@@ -48,6 +49,7 @@ struct CompanyListItem {
     base: ListItem,
     company: Rc<Company>,
     selected: bool,
+    highlight_indices: Vec<usize>,
 }
 
 impl CompanyListItem {
@@ -56,8 +58,14 @@ impl CompanyListItem {
             company,
             base: ListItem::new(id).selected(selected),
             selected,
+            highlight_indices: vec![],
         }
     }
+
+    fn highlight_indices(mut self, indices: Vec<usize>) -> Self {
+        self.highlight_indices = indices;
+        self
+    }
 }
 
 impl Selectable for CompanyListItem {
@@ -86,6 +94,13 @@ impl RenderOnce for CompanyListItem {
         };
 
         self.base
+            .label(self.company.name.clone())
+            .highlight_indices(self.highlight_indices)
+            .text_color(text_color)
+            .draggable(DragItem::new(
+                self.company.clone(),
+                self.company.name.clone(),
+            ))
             .px_2()
             .py_1()
             .overflow_x_hidden()
@@ -94,19 +109,9 @@ impl RenderOnce for CompanyListItem {
             .child(
                 h_flex()
                     .items_center()
-                    .justify_between()
+                    .justify_end()
                     .gap_2()
                     .text_color(text_color)
-                    .child(
-                        h_flex().gap_2().child(
-                            v_flex()
-                                .gap_1()
-                                .max_w(px(500.))
-                                .overflow_x_hidden()
-                                .flex_nowrap()
-                                .child(Label::new(self.company.name.clone()).whitespace_nowrap()),
-                        ),
-                    )
                     .child(
                         h_flex()
                             .gap_2()
@@ -142,6 +147,7 @@ struct CompanyListDelegate {
     confirmed_index: Option<IndexPath>,
     query: SharedString,
     loading: bool,
+    error: Option<SharedString>,
     eof: bool,
     lazy_load: bool,
 }
@@ -149,17 +155,11 @@ struct CompanyListDelegate {
 impl CompanyListDelegate {
     fn prepare(&mut self, query: impl Into<SharedString>) {
         self.query = query.into();
-        let companies: Vec<Rc<Company>> = self
-            ._companies
-            .iter()
-            .filter(|company| {
-                company
-                    .name
-                    .to_lowercase()
-                    .contains(&self.query.to_lowercase())
-            })
-            .cloned()
-            .collect();
+        let companies = FuzzyMatcher::new(&self.query)
+            .filter(self._companies.iter(), |company| company.name.as_str())
+            .into_iter()
+            .map(|(company, _)| company.clone())
+            .collect::<Vec<_>>();
         for company in companies.into_iter() {
             if let Some(ix) = self.industries.iter().position(|s| s == &company.industry) {
                 self.matched_companies[ix].push(company);
@@ -204,6 +204,13 @@ impl ListDelegate for CompanyListDelegate {
         self.matched_companies[section].len()
     }
 
+    fn item_label(&self, ix: IndexPath, _: &App) -> Option<SharedString> {
+        self.matched_companies
+            .get(ix.section)
+            .and_then(|c| c.get(ix.row))
+            .map(|company| company.name.clone())
+    }
+
     fn perform_search(
         &mut self,
         query: &str,
@@ -293,7 +300,14 @@ impl ListDelegate for CompanyListDelegate {
     ) -> Option<Self::Item> {
         let selected = Some(ix) == self.selected_index || Some(ix) == self.confirmed_index;
         if let Some(company) = self.matched_companies[ix.section].get(ix.row) {
-            return Some(CompanyListItem::new(ix, company.clone(), selected));
+            let highlight_indices = FuzzyMatcher::new(&self.query)
+                .match_text(&company.name)
+                .map(|matched| matched.indices)
+                .unwrap_or_default();
+            return Some(
+                CompanyListItem::new(ix, company.clone(), selected)
+                    .highlight_indices(highlight_indices),
+            );
         }
 
         None
@@ -303,6 +317,29 @@ impl ListDelegate for CompanyListDelegate {
         self.loading
     }
 
+    fn error(&self, _: &App) -> Option<SharedString> {
+        self.error.clone()
+    }
+
+    fn on_retry(&mut self, window: &mut Window, cx: &mut Context<ListState<Self>>) {
+        self.error = None;
+        self.loading = true;
+
+        cx.spawn_in(window, async move |view, window| {
+            // Simulate network request, delay 1s to load data.
+            window
+                .background_executor()
+                .timer(Duration::from_secs(1))
+                .await;
+
+            _ = view.update(window, |view, cx| {
+                view.delegate_mut().loading = false;
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
     fn has_more(&self, _: &App) -> bool {
         if self.loading {
             return false;
@@ -311,6 +348,18 @@ impl ListDelegate for CompanyListDelegate {
         return !self.eof;
     }
 
+    fn move_item(
+        &mut self,
+        from: IndexPath,
+        to: IndexPath,
+        _: &mut Window,
+        _: &mut Context<ListState<Self>>,
+    ) {
+        let companies = &mut self.matched_companies[from.section];
+        let company = companies.remove(from.row);
+        companies.insert(to.row, company);
+    }
+
     fn load_more_threshold(&self) -> usize {
         150
     }
@@ -342,8 +391,12 @@ pub struct ListStory {
     focus_handle: FocusHandle,
     company_list: Entity<ListState<CompanyListDelegate>>,
     selected_company: Option<Rc<Company>>,
+    /// The companies dropped into the favorites group.
+    favorites: Vec<Rc<Company>>,
     selectable: bool,
     searchable: bool,
+    reorderable: bool,
+    multi_selectable: bool,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -375,6 +428,7 @@ impl ListStory {
             confirmed_index: None,
             query: "".into(),
             loading: false,
+            error: None,
             eof: false,
             lazy_load: false,
         };
@@ -394,6 +448,12 @@ impl ListStory {
                     ListEvent::Cancel => {
                         println!("List Cancelled");
                     }
+                    ListEvent::Move(from, to) => {
+                        println!("List Moved: {:?} -> {:?}", from, to);
+                    }
+                    ListEvent::SelectionChanged(indexes) => {
+                        println!("List Selection Changed: {:?}", indexes);
+                    }
                 }),
             ];
 
@@ -423,8 +483,11 @@ impl ListStory {
             focus_handle: cx.focus_handle(),
             searchable: true,
             selectable: true,
+            reorderable: false,
+            multi_selectable: false,
             company_list,
             selected_company: None,
+            favorites: vec![],
             _subscriptions,
         }
     }
@@ -436,6 +499,70 @@ impl ListStory {
         }
     }
 
+    fn render_favorites(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+
+        v_flex()
+            .id("favorites")
+            .w(px(240.))
+            .min_h(px(160.))
+            .p_2()
+            .gap_1()
+            .border_1()
+            .border_dashed()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .child(
+                Label::new("Favorites")
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .drop_target(
+                DropTarget::<Rc<Company>>::new()
+                    .accept({
+                        let view = view.clone();
+                        move |company, _, cx| {
+                            !view
+                                .read(cx)
+                                .favorites
+                                .iter()
+                                .any(|item| item.name == company.name)
+                        }
+                    })
+                    .hover_style(|style, _, cx| {
+                        style
+                            .bg(cx.theme().drop_target)
+                            .border_color(cx.theme().drag_border)
+                    })
+                    .on_drop(move |company, _, cx| {
+                        view.update(cx, |this, cx| {
+                            this.favorites.push(company.clone());
+                            cx.notify();
+                        })
+                    }),
+            )
+            .when(self.favorites.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Drag companies here."),
+                )
+            })
+            .children(self.favorites.iter().enumerate().map(|(ix, company)| {
+                h_flex()
+                    .id(("favorite", ix))
+                    .gap_2()
+                    .px_2()
+                    .py_1()
+                    .text_sm()
+                    .rounded(cx.theme().radius)
+                    .hover(|this| this.bg(cx.theme().list_hover))
+                    .child(Icon::new(IconName::Star).small())
+                    .child(company.name.clone())
+            }))
+    }
+
     fn toggle_selectable(&mut self, selectable: bool, _: &mut Window, cx: &mut Context<Self>) {
         self.selectable = selectable;
         self.company_list.update(cx, |list, cx| {
@@ -443,6 +570,26 @@ impl ListStory {
         })
     }
 
+    fn toggle_reorderable(&mut self, reorderable: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.reorderable = reorderable;
+        self.company_list.update(cx, |list, cx| {
+            list.set_reorderable(self.reorderable, cx);
+        })
+    }
+
+    fn toggle_multi_selectable(
+        &mut self,
+        multi_selectable: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.multi_selectable = multi_selectable;
+        self.company_list.update(cx, |list, cx| {
+            list.set_multi_selectable(self.multi_selectable, cx);
+            list.set_checkbox(self.multi_selectable, cx);
+        })
+    }
+
     fn toggle_searchable(&mut self, searchable: bool, _: &mut Window, cx: &mut Context<Self>) {
         self.searchable = searchable;
         self.company_list.update(cx, |list, cx| {
@@ -568,6 +715,22 @@ impl Render for ListStory {
                                 this.toggle_searchable(*check, window, cx)
                             })),
                     )
+                    .child(
+                        Checkbox::new("reorderable")
+                            .label("Reorderable")
+                            .checked(self.reorderable)
+                            .on_click(cx.listener(|this, check: &bool, window, cx| {
+                                this.toggle_reorderable(*check, window, cx)
+                            })),
+                    )
+                    .child(
+                        Checkbox::new("multi_selectable")
+                            .label("Multi Select")
+                            .checked(self.multi_selectable)
+                            .on_click(cx.listener(|this, check: &bool, window, cx| {
+                                this.toggle_multi_selectable(*check, window, cx)
+                            })),
+                    )
                     .child(
                         Checkbox::new("loading")
                             .label("Loading")
@@ -579,6 +742,18 @@ impl Render for ListStory {
                                 })
                             })),
                     )
+                    .child(
+                        Checkbox::new("error")
+                            .label("Error")
+                            .checked(self.company_list.read(cx).delegate().error.is_some())
+                            .on_click(cx.listener(|this, check: &bool, _, cx| {
+                                this.company_list.update(cx, |this, cx| {
+                                    this.delegate_mut().error = check
+                                        .then(|| "Failed to load companies, network error.".into());
+                                    cx.notify();
+                                })
+                            })),
+                    )
                     .child(
                         Checkbox::new("lazy_load")
                             .label("Lazy Load")
@@ -592,13 +767,30 @@ impl Render for ListStory {
                     ),
             )
             .child(
-                List::new(&self.company_list)
-                    .p(px(8.))
+                h_flex()
                     .flex_1()
                     .w_full()
-                    .border_1()
-                    .border_color(cx.theme().border)
-                    .rounded(cx.theme().radius),
+                    .gap_4()
+                    .items_start()
+                    .child(
+                        div()
+                            .id("company-list")
+                            .size_full()
+                            .flex_1()
+                            .auto_scroll_on_drag::<Rc<Company>>(
+                                self.company_list.read(cx).scroll_handle(),
+                            )
+                            .child(
+                                List::new(&self.company_list)
+                                    .loading_placeholder(8)
+                                    .p(px(8.))
+                                    .size_full()
+                                    .border_1()
+                                    .border_color(cx.theme().border)
+                                    .rounded(cx.theme().radius),
+                            ),
+                    )
+                    .child(self.render_favorites(cx)),
             )
     }
 }
@@ -147,9 +147,9 @@ impl Render for MenuStory {
                                         }),
                                     ))
                                     .separator()
-                                    .menu("Copy", Box::new(Copy))
-                                    .menu("Cut", Box::new(Cut))
-                                    .menu("Paste", Box::new(Paste))
+                                    .menu("&Copy", Box::new(Copy))
+                                    .menu("Cu&t", Box::new(Cut))
+                                    .menu("&Paste", Box::new(Paste))
                                     .separator()
                                     .menu_with_check(
                                         format!("Check Side {:?}", check_side),
@@ -218,26 +218,13 @@ impl Render for MenuStory {
                                         menu.link("Crates", "https://crates.io")
                                             .link("Rust Docs", "https://docs.rs")
                                             .separator()
-                                            .submenu(
-                                                "Nested",
-                                                window,
-                                                cx,
-                                                |menu, window, cx| {
-                                                    menu.link("Docs.rs", "https://docs.rs")
-                                                        .separator()
-                                                        .submenu(
-                                                            "Deeper",
-                                                            window,
-                                                            cx,
-                                                            |menu, _, _| {
-                                                                menu.link(
-                                                                    "GPUI",
-                                                                    "https://gpui.rs",
-                                                                )
-                                                            },
-                                                        )
-                                                },
-                                            )
+                                            .submenu("Nested", window, cx, |menu, window, cx| {
+                                                menu.link("Docs.rs", "https://docs.rs")
+                                                    .separator()
+                                                    .submenu("Deeper", window, cx, |menu, _, _| {
+                                                        menu.link("GPUI", "https://gpui.rs")
+                                                    })
+                                            })
                                     })
                             }),
                     )
@@ -437,6 +424,22 @@ impl Render for MenuStory {
                                 }
                                 this.min_w(px(100.))
                             }),
+                    )
+                    .child(
+                        Button::new("dropdown-menu-virtual")
+                            .outline()
+                            .label("Move to folder... (5000 items)")
+                            .dropdown_menu_with_anchor(Anchor::TopRight, move |this, _, _| {
+                                // Only the visible items are rendered, type to jump to a folder.
+                                let mut this = this.max_h(px(360.)).label("Move to folder");
+                                for i in 0..5000 {
+                                    this = this.menu(
+                                        SharedString::from(format!("Folder {:04}", i)),
+                                        Box::new(Info(i)),
+                                    )
+                                }
+                                this
+                            }),
                     ),
             )
     }
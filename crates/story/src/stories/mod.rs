@@ -1,6 +1,8 @@
 use gpui::{AnyView, App, AppContext as _, Entity, Hsla, Pixels, Render, Window, px};
 use gpui_component::dock::PanelControl;
 
+use crate::StoryControls;
+
 mod accordion_story;
 mod alert_dialog_story;
 mod alert_story;
@@ -22,6 +24,8 @@ mod dialog_story;
 mod dropdown_button_story;
 mod editor_story;
 mod form_story;
+mod gantt_story;
+mod graph_story;
 mod group_box_story;
 mod hover_card_story;
 mod icon_story;
@@ -61,6 +65,7 @@ mod theme_story;
 mod toggle_story;
 mod tooltip_story;
 mod tree_story;
+mod tree_table_story;
 mod virtual_list_story;
 mod welcome_story;
 
@@ -85,6 +90,8 @@ pub use dialog_story::DialogStory;
 pub use dropdown_button_story::DropdownButtonStory;
 pub use editor_story::EditorStory;
 pub use form_story::FormStory;
+pub use gantt_story::GanttStory;
+pub use graph_story::GraphStory;
 pub use group_box_story::GroupBoxStory;
 pub use hover_card_story::HoverCardStory;
 pub use icon_story::IconStory;
@@ -124,6 +131,7 @@ pub use theme_story::ThemeColorsStory;
 pub use toggle_story::ToggleStory;
 pub use tooltip_story::TooltipStory;
 pub use tree_story::TreeStory;
+pub use tree_table_story::TreeTableStory;
 pub use virtual_list_story::VirtualListStory;
 
 pub use welcome_story::WelcomeStory;
@@ -187,4 +195,18 @@ pub trait Story: Render + Sized {
             });
         }
     }
+
+    /// Returns the controls to show in the sidebar of the story container.
+    fn controls(&self) -> Option<Entity<StoryControls>> {
+        None
+    }
+
+    fn controls_any(view: AnyView, cx: &App) -> Option<Entity<StoryControls>>
+    where
+        Self: 'static,
+    {
+        view.downcast::<Self>()
+            .ok()
+            .and_then(|story| story.read(cx).controls())
+    }
 }
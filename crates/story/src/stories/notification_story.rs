@@ -2,9 +2,10 @@ use gpui::{
     Anchor, App, AppContext, Context, Entity, FocusHandle, Focusable, InteractiveElement as _,
     IntoElement, ParentElement, Render, Styled, Window,
 };
+use std::time::Duration;
 
 use gpui_component::{
-    ActiveTheme, Theme, WindowExt as _,
+    ActiveTheme, Notifier, Theme, WindowExt as _,
     button::{Button, ButtonVariants},
     h_flex,
     menu::{DropdownMenu as _, PopupMenuItem},
@@ -383,6 +384,23 @@ impl Render for NotificationStory {
                         })),
                 ),
             )
+            .child(
+                section("From Background Task").child(
+                    Button::new("show-notify-background")
+                        .outline()
+                        .label("Notify after 1s")
+                        .on_click(cx.listener(|_, _, _, cx| {
+                            let notifier = Notifier::global(cx);
+                            let executor = cx.background_executor().clone();
+                            cx.background_spawn(async move {
+                                executor.timer(Duration::from_secs(1)).await;
+                                notifier
+                                    .notify(NotificationType::Success, "Background task finished.");
+                            })
+                            .detach();
+                        })),
+                ),
+            )
             .child({
                 struct ManualOpenNotification;
 
@@ -8,7 +8,10 @@ use crate::section;
 use gpui_component::{
     ActiveTheme, Disableable, IconName, Sizable,
     button::{Button, ButtonVariants},
-    input::{InputEvent, InputState, MaskPattern, NumberInput, NumberInputEvent, StepAction},
+    input::{
+        InputEvent, InputState, MaskPattern, NumberInput, NumberInputEvent, NumberInputFormat,
+        NumberType, StepAction,
+    },
     v_flex,
 };
 
@@ -20,6 +23,7 @@ pub struct NumberInputStory {
     number_input2: Entity<InputState>,
     number_input3: Entity<InputState>,
     number_input4: Entity<InputState>,
+    amount_input: Entity<InputState>,
     disabled_input: Entity<InputState>,
 
     _subscriptions: Vec<Subscription>,
@@ -106,6 +110,17 @@ impl NumberInputStory {
                 .min(0.)
         });
 
+        // Display with the locale group separators, and step the `i128` value exactly.
+        let amount_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Amount")
+                .default_value("9007199254740993")
+                .number_type(NumberType::Integer)
+                .number_format(NumberInputFormat::locale("de"))
+                .step(1_i128)
+                .min(0)
+        });
+
         let disabled_input = cx.new(|cx| {
             InputState::new(window, cx)
                 .default_value("100")
@@ -118,6 +133,7 @@ impl NumberInputStory {
             cx.subscribe_in(&number_input2, window, Self::on_input_event),
             cx.subscribe_in(&number_input3, window, Self::on_input_event),
             cx.subscribe_in(&number_input4, window, Self::on_input_event),
+            cx.subscribe_in(&amount_input, window, Self::on_input_event),
             cx.subscribe_in(&disabled_input, window, Self::on_input_event),
             cx.subscribe_in(&disabled_input, window, Self::on_number_input_event),
         ];
@@ -128,6 +144,7 @@ impl NumberInputStory {
             number_input2,
             number_input3,
             number_input4,
+            amount_input,
             disabled_input,
             _subscriptions,
         }
@@ -155,6 +172,7 @@ impl NumberInputStory {
             }
             InputEvent::Focus => println!("Focus"),
             InputEvent::Blur => println!("Blur"),
+            InputEvent::MaxLengthExceeded => println!("MaxLengthExceeded"),
         }
     }
 
@@ -228,5 +246,10 @@ impl Render for NumberInputStory {
                         .text_color(cx.theme().info),
                 ),
             )
+            .child(
+                section("Locale format with big integer")
+                    .max_w(px(260.))
+                    .child(NumberInput::new(&self.amount_input)),
+            )
     }
 }
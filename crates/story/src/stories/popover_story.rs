@@ -4,12 +4,12 @@ use gpui::{
     Render, Styled as _, WeakEntity, Window, actions, div, px,
 };
 use gpui_component::{
-    ActiveTheme, StyledExt, WindowExt,
+    ActiveTheme, Sizable, StyledExt, WindowExt,
     button::{Button, ButtonVariants as _},
     h_flex,
     input::{Input, InputState},
     list::{List, ListDelegate, ListItem, ListState},
-    popover::Popover,
+    popover::{Popover, PopoverTriggerMode},
     separator::Separator,
     v_flex,
 };
@@ -311,6 +311,30 @@ impl Render for PopoverStory {
                         }),
                 ),
             )
+            .child(
+                section("Hover to open Popover").child(
+                    Popover::new("popover-hover")
+                        .trigger_mode(PopoverTriggerMode::Hover)
+                        .anchor(Anchor::TopCenter)
+                        .trigger(Button::new("btn").outline().label("Hover Popover"))
+                        .text_sm()
+                        .w(px(300.))
+                        .content(|_, _, cx| {
+                            v_flex()
+                                .gap_2()
+                                .child("Move the cursor into this popover to keep it open.")
+                                .child(
+                                    Button::new("hover-action")
+                                        .small()
+                                        .primary()
+                                        .label("Dismiss")
+                                        .on_click(cx.listener(|_, _, _, cx| {
+                                            cx.emit(DismissEvent);
+                                        })),
+                                )
+                        }),
+                ),
+            )
             .child(
                 section("Styling Popover").child(
                     Popover::new("popover-1")
@@ -3,7 +3,7 @@ use gpui::{
     ParentElement as _, Pixels, Render, SharedString, Styled, Window, div, px,
 };
 use gpui_component::{
-    ActiveTheme, Sizable as _,
+    ActiveTheme, Disableable as _, Sizable as _,
     button::Button,
     h_flex,
     resizable::{ResizableState, h_resizable, resizable_panel, v_resizable},
@@ -15,6 +15,8 @@ pub struct ResizableStory {
     show_left: bool,
     use_flex_none: bool,
     programmatic_state: Entity<ResizableState>,
+    fraction_state: Entity<ResizableState>,
+    saved_layout: Option<serde_json::Value>,
 }
 
 impl super::Story for ResizableStory {
@@ -48,6 +50,8 @@ impl ResizableStory {
             show_left: true,
             use_flex_none: true,
             programmatic_state: cx.new(|_| ResizableState::default()),
+            fraction_state: cx.new(|_| ResizableState::default()),
+            saved_layout: None,
         }
     }
 }
@@ -266,5 +270,67 @@ impl Render for ResizableStory {
                             ),
                     ),
             )
+            // Proportional sizes: the panels are declared as fractions of the
+            // group, and the layout is saved and restored as fractions by
+            // `ResizableState::to_json` and `ResizableState::from_json`.
+            .child(
+                v_flex()
+                    .gap_2()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Button::new("save-layout")
+                                    .small()
+                                    .label("Save layout")
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        let json = this.fraction_state.read(cx).to_json();
+                                        println!("Saved layout: {}", json);
+                                        this.saved_layout = Some(json);
+                                        cx.notify();
+                                    })),
+                            )
+                            .child(
+                                Button::new("restore-layout")
+                                    .small()
+                                    .label("Restore layout")
+                                    .disabled(self.saved_layout.is_none())
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        let Some(json) = this.saved_layout.clone() else {
+                                            return;
+                                        };
+                                        this.fraction_state = cx.new(|_| {
+                                            ResizableState::from_json(json).unwrap_or_default()
+                                        });
+                                        cx.notify();
+                                    })),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .h(px(200.))
+                            .border_1()
+                            .border_color(cx.theme().border)
+                            .child(
+                                h_resizable("resizable-fraction")
+                                    .with_state(&self.fraction_state)
+                                    .child(
+                                        resizable_panel()
+                                            .size_fraction(0.25)
+                                            .child(panel_box("Left (25%)", cx)),
+                                    )
+                                    .child(
+                                        resizable_panel()
+                                            .size_fraction(0.5)
+                                            .child(panel_box("Center (50%)", cx)),
+                                    )
+                                    .child(
+                                        resizable_panel()
+                                            .size_fraction(0.25)
+                                            .child(panel_box("Right (25%)", cx)),
+                                    ),
+                            ),
+                    ),
+            )
     }
 }
@@ -4,10 +4,10 @@ use gpui::{
 };
 
 use gpui_component::{
-    ActiveTheme, Disableable as _, Sizable, h_flex, label::Label, switch::Switch, v_flex,
+    ActiveTheme, Disableable as _, Sizable, Size, h_flex, label::Label, switch::Switch, v_flex,
 };
 
-use crate::section;
+use crate::{StoryControls, section, story_source};
 
 pub struct SwitchStory {
     focus_handle: FocusHandle,
@@ -16,6 +16,8 @@ pub struct SwitchStory {
     switch3: bool,
     switch4: bool,
     switch5: bool,
+    playground_checked: bool,
+    controls: Entity<StoryControls>,
 }
 
 impl super::Story for SwitchStory {
@@ -30,6 +32,10 @@ impl super::Story for SwitchStory {
     fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render> {
         Self::view(window, cx)
     }
+
+    fn controls(&self) -> Option<Entity<StoryControls>> {
+        Some(self.controls.clone())
+    }
 }
 
 impl SwitchStory {
@@ -38,7 +44,18 @@ impl SwitchStory {
     }
 
     fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        let controls = cx.new(|cx| {
+            StoryControls::new()
+                .add_bool("disabled", "Disabled", false)
+                .add_enum("size", "Size", ["xs", "sm", "md", "lg"], 2)
+                .add_number("width", "Width", 240., 120.0..=480., 10.)
+                .add_color("color", "Color", cx.theme().primary)
+        });
+        cx.observe(&controls, |_, _, cx| cx.notify()).detach();
+
         Self {
+            playground_checked: true,
+            controls,
             focus_handle: cx.focus_handle(),
             switch1: true,
             switch2: false,
@@ -74,9 +91,32 @@ impl Render for SwitchStory {
                 .border_color(cx.theme().border)
         }
 
+        let controls = self.controls.read(cx);
+        let size = match controls.enum_ix("size") {
+            0 => Size::XSmall,
+            1 => Size::Small,
+            3 => Size::Large,
+            _ => Size::Medium,
+        };
+
         v_flex()
             .w_full()
             .gap_3()
+            .child(
+                section("Playground").child(
+                    Switch::new("switch-playground")
+                        .label("Airplane Mode")
+                        .checked(self.playground_checked)
+                        .disabled(controls.bool("disabled"))
+                        .with_size(size)
+                        .w(px(controls.number("width")))
+                        .color(controls.color("color"))
+                        .on_click(cx.listener(|view, checked, _, cx| {
+                            view.playground_checked = *checked;
+                            cx.notify();
+                        })),
+                ),
+            )
             .child(
                 card(cx)
                     .child(
@@ -118,6 +158,8 @@ impl Render for SwitchStory {
             )
             .child(
                 section("Disabled")
+                    .source(story_source!("switch_story.rs", "disabled"))
+                    // #region disabled
                     .child(Switch::new("switch3").disabled(true).on_click(|v, _, _| {
                         println!("Switch value changed: {:?}", v);
                     }))
@@ -131,51 +173,60 @@ impl Render for SwitchStory {
                                 println!("Switch value changed: {:?}", ev);
                             }),
                     ),
+                // #endregion
             )
             .child(
-                section("Custom Color").child(
-                    h_flex()
-                        .gap_4()
-                        .child(
-                            Switch::new("switch4")
-                                .checked(self.switch4)
-                                .label("Success")
-                                .color(theme.success)
-                                .on_click(cx.listener(|view, checked, _, cx| {
-                                    view.switch4 = *checked;
-                                    cx.notify();
-                                })),
-                        )
-                        .child(
-                            Switch::new("switch5")
-                                .checked(self.switch5)
-                                .label("Destructive")
-                                .color(theme.danger)
-                                .on_click(cx.listener(|view, checked, _, cx| {
-                                    view.switch5 = *checked;
-                                    cx.notify();
-                                })),
-                        )
-                        .child(
-                            Switch::new("switch4_disabled")
-                                .checked(true)
-                                .label("Disabled")
-                                .color(theme.success)
-                                .disabled(true),
-                        ),
-                ),
+                section("Custom Color")
+                    .source(story_source!("switch_story.rs", "custom-color"))
+                    .child(
+                        // #region custom-color
+                        h_flex()
+                            .gap_4()
+                            .child(
+                                Switch::new("switch4")
+                                    .checked(self.switch4)
+                                    .label("Success")
+                                    .color(theme.success)
+                                    .on_click(cx.listener(|view, checked, _, cx| {
+                                        view.switch4 = *checked;
+                                        cx.notify();
+                                    })),
+                            )
+                            .child(
+                                Switch::new("switch5")
+                                    .checked(self.switch5)
+                                    .label("Destructive")
+                                    .color(theme.danger)
+                                    .on_click(cx.listener(|view, checked, _, cx| {
+                                        view.switch5 = *checked;
+                                        cx.notify();
+                                    })),
+                            )
+                            .child(
+                                Switch::new("switch4_disabled")
+                                    .checked(true)
+                                    .label("Disabled")
+                                    .color(theme.success)
+                                    .disabled(true),
+                            ),
+                        // #endregion
+                    ),
             )
             .child(
-                section("Small Size").child(
-                    Switch::new("switch3")
-                        .checked(self.switch3)
-                        .label("Small Size")
-                        .small()
-                        .on_click(cx.listener(move |view, checked, _, cx| {
-                            view.switch3 = *checked;
-                            cx.notify();
-                        })),
-                ),
+                section("Small Size")
+                    .source(story_source!("switch_story.rs", "small-size"))
+                    .child(
+                        // #region small-size
+                        Switch::new("switch3")
+                            .checked(self.switch3)
+                            .label("Small Size")
+                            .small()
+                            .on_click(cx.listener(move |view, checked, _, cx| {
+                                view.switch3 = *checked;
+                                cx.notify();
+                            })),
+                        // #endregion
+                    ),
             )
     }
 }
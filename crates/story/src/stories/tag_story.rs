@@ -3,12 +3,28 @@ use gpui::{
     Styled, Window, px,
 };
 
-use gpui_component::{ColorName, Sizable, h_flex, indigo_50, indigo_500, tag::Tag, v_flex};
+use gpui_component::{
+    ColorName, Selectable, Sizable, h_flex, indigo_50, indigo_500,
+    tag::{Tag, TagGroup},
+    v_flex,
+};
 
 use crate::section;
 
+const FILTERS: [&str; 8] = [
+    "Open",
+    "Closed",
+    "Bug",
+    "Feature",
+    "Docs",
+    "Help Wanted",
+    "Good First Issue",
+    "Won't Fix",
+];
+
 pub struct TagStory {
     focus_handle: FocusHandle,
+    selected_filters: Vec<usize>,
 }
 
 impl super::Story for TagStory {
@@ -29,6 +45,7 @@ impl TagStory {
     pub(crate) fn new(_: &mut Window, cx: &mut App) -> Self {
         Self {
             focus_handle: cx.focus_handle(),
+            selected_filters: vec![0],
         }
     }
 
@@ -42,7 +59,7 @@ impl Focusable for TagStory {
     }
 }
 impl Render for TagStory {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
             .w_full()
             .gap_3()
@@ -138,5 +155,37 @@ impl Render for TagStory {
                     ),
                 ),
             )
+            .child(
+                section("TagGroup (filter chips)").child(
+                    v_flex().gap_2().w_full().child(
+                        TagGroup::new("filters")
+                            .filter(true)
+                            .children(FILTERS.iter().enumerate().map(|(ix, label)| {
+                                Tag::secondary()
+                                    .outline()
+                                    .rounded_full()
+                                    .selected(self.selected_filters.contains(&ix))
+                                    .child(*label)
+                            }))
+                            .on_change(cx.listener(|this, selected: &Vec<usize>, _, cx| {
+                                this.selected_filters = selected.clone();
+                                cx.notify();
+                            })),
+                    ),
+                ),
+            )
+            .child(
+                section("TagGroup (single line)").child(
+                    TagGroup::new("single-line")
+                        .single_line()
+                        .w(px(360.))
+                        .children(
+                            ColorName::all()
+                                .into_iter()
+                                .filter(|color| *color != ColorName::Gray)
+                                .map(|color| Tag::color(color).child(color.to_string())),
+                        ),
+                ),
+            )
     }
 }
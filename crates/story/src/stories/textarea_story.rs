@@ -90,6 +90,7 @@ impl TextareaStory {
             InputState::new(window, cx)
                 .auto_grow(1, 5)
                 .placeholder("Enter text here...")
+                .max_length(500)
                 .default_value(
                     "Hello 世界 this is a very long line of text \
                     to test if the horizontal scrolling function is working \
@@ -226,7 +227,7 @@ impl Render for TextareaStory {
             .child(
                 section("Auto Grow")
                     .max_w_md()
-                    .child(Input::new(&self.textarea_auto_grow)),
+                    .child(Input::new(&self.textarea_auto_grow).show_count(true)),
             )
             .child(
                 section("Auto Grow with No Wrap")
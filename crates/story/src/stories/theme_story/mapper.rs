@@ -142,6 +142,21 @@ pub fn parse_theme_key(key: &str) -> ParsedKey {
         "chart_3" => ("Chart", "Color 3", "chart.3"),
         "chart_4" => ("Chart", "Color 4", "chart.4"),
         "chart_5" => ("Chart", "Color 5", "chart.5"),
+        "chart_series_1" => ("Chart", "Series 1", "chart.series.1"),
+        "chart_series_2" => ("Chart", "Series 2", "chart.series.2"),
+        "chart_series_3" => ("Chart", "Series 3", "chart.series.3"),
+        "chart_series_4" => ("Chart", "Series 4", "chart.series.4"),
+        "chart_series_5" => ("Chart", "Series 5", "chart.series.5"),
+        "chart_series_6" => ("Chart", "Series 6", "chart.series.6"),
+        "chart_series_7" => ("Chart", "Series 7", "chart.series.7"),
+        "chart_series_8" => ("Chart", "Series 8", "chart.series.8"),
+        "chart_series_9" => ("Chart", "Series 9", "chart.series.9"),
+        "chart_series_10" => ("Chart", "Series 10", "chart.series.10"),
+        "chart_grid" => ("Chart", "Grid", "chart.grid"),
+        "chart_axis" => ("Chart", "Axis", "chart.axis"),
+        "chart_axis_foreground" => ("Chart", "Axis Foreground", "chart.axis.foreground"),
+        "chart_tooltip" => ("Chart", "Tooltip Background", "chart.tooltip.background"),
+        "chart_tooltip_foreground" => ("Chart", "Tooltip Foreground", "chart.tooltip.foreground"),
 
         // Danger / Success / Warning / Info
         "danger" => ("Danger", "Background", "danger.background"),
@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use gpui::{
+    App, AppContext as _, Context, Entity, FocusHandle, Focusable, IntoElement, ParentElement,
+    Render, SharedString, Styled, Subscription, Task, Window,
+};
+use gpui_component::{
+    table::{Column, DataTable, TableDelegate, TableEvent, TableState},
+    v_flex,
+};
+
+/// A flattened row of the tree.
+struct Row {
+    id: usize,
+    name: SharedString,
+    kind: &'static str,
+    size: usize,
+    depth: usize,
+    has_children: bool,
+    expanded: bool,
+    loading: bool,
+}
+
+struct TreeTableDelegate {
+    rows: Vec<Row>,
+    columns: Vec<Column>,
+    next_id: usize,
+    _load_tasks: Vec<Task<()>>,
+}
+
+impl TreeTableDelegate {
+    fn new() -> Self {
+        let mut this = Self {
+            rows: vec![],
+            columns: vec![
+                Column::new("name", "Name").width(320.),
+                Column::new("kind", "Kind").width(120.),
+                Column::new("size", "Size").width(120.).text_right(),
+            ],
+            next_id: 0,
+            _load_tasks: vec![],
+        };
+        this.rows = this.make_children("Project", 0);
+        this
+    }
+
+    /// Make some fake child rows, the folders are loaded lazily.
+    fn make_children(&mut self, parent: &str, depth: usize) -> Vec<Row> {
+        (0..5)
+            .map(|ix| {
+                let is_folder = depth < 3 && ix < 2;
+                self.next_id += 1;
+                Row {
+                    id: self.next_id,
+                    name: if is_folder {
+                        format!("{} Folder {}", parent, ix + 1).into()
+                    } else {
+                        format!("{} File {}.rs", parent, ix + 1).into()
+                    },
+                    kind: if is_folder { "Folder" } else { "File" },
+                    size: if is_folder { 0 } else { (ix + 1) * 1024 },
+                    depth,
+                    has_children: is_folder,
+                    expanded: false,
+                    loading: false,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the range of the descendant rows of the row at the given index.
+    fn descendants(&self, row_ix: usize) -> std::ops::Range<usize> {
+        let depth = self.rows[row_ix].depth;
+        let end = self.rows[row_ix + 1..]
+            .iter()
+            .position(|row| row.depth <= depth)
+            .map_or(self.rows.len(), |pos| row_ix + 1 + pos);
+        row_ix + 1..end
+    }
+}
+
+impl TableDelegate for TreeTableDelegate {
+    fn columns_count(&self, _: &App) -> usize {
+        self.columns.len()
+    }
+
+    fn rows_count(&self, _: &App) -> usize {
+        self.rows.len()
+    }
+
+    fn column(&self, col_ix: usize, _: &App) -> Column {
+        self.columns[col_ix].clone()
+    }
+
+    fn cell_text(&self, row_ix: usize, col_ix: usize, _: &App) -> String {
+        let Some(row) = self.rows.get(row_ix) else {
+            return String::new();
+        };
+
+        match col_ix {
+            0 => row.name.to_string(),
+            1 => row.kind.to_string(),
+            _ if row.has_children => String::new(),
+            _ => format!("{} KB", row.size / 1024),
+        }
+    }
+
+    fn row_depth(&self, row_ix: usize, _: &App) -> usize {
+        self.rows.get(row_ix).map_or(0, |row| row.depth)
+    }
+
+    fn has_children(&self, row_ix: usize, _: &App) -> bool {
+        self.rows.get(row_ix).map_or(false, |row| row.has_children)
+    }
+
+    fn is_children_expanded(&self, row_ix: usize, _: &App) -> bool {
+        self.rows.get(row_ix).map_or(false, |row| row.expanded)
+    }
+
+    fn is_children_loading(&self, row_ix: usize, _: &App) -> bool {
+        self.rows.get(row_ix).map_or(false, |row| row.loading)
+    }
+
+    fn set_children_expanded(
+        &mut self,
+        row_ix: usize,
+        expanded: bool,
+        _: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+        if !expanded {
+            let descendants = self.descendants(row_ix);
+            self.rows.drain(descendants);
+            self.rows[row_ix].expanded = false;
+            return;
+        }
+
+        let row = &mut self.rows[row_ix];
+        row.expanded = true;
+        row.loading = true;
+        let id = row.id;
+        let name = row.name.clone();
+        let depth = row.depth + 1;
+
+        // Simulate loading the children lazily.
+        self._load_tasks.push(cx.spawn(async move |table, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(500))
+                .await;
+
+            _ = table.update(cx, |table, cx| {
+                let delegate = table.delegate_mut();
+                let children = delegate.make_children(&name, depth);
+                let Some(row_ix) = delegate.rows.iter().position(|row| row.id == id) else {
+                    return;
+                };
+
+                let row = &mut delegate.rows[row_ix];
+                row.loading = false;
+                if row.expanded {
+                    delegate.rows.splice(row_ix + 1..row_ix + 1, children);
+                }
+                cx.notify();
+            });
+        }));
+    }
+}
+
+pub struct TreeTableStory {
+    focus_handle: FocusHandle,
+    table: Entity<TableState<TreeTableDelegate>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl super::Story for TreeTableStory {
+    fn title() -> &'static str {
+        "TreeTable"
+    }
+
+    fn description() -> &'static str {
+        "A DataTable with hierarchical rows, the children are loaded lazily."
+    }
+
+    fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render> {
+        Self::view(window, cx)
+    }
+}
+
+impl TreeTableStory {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let table = cx.new(|cx| TableState::new(TreeTableDelegate::new(), window, cx).tree(true));
+
+        let _subscriptions = vec![cx.subscribe(&table, |_, _, event, _| {
+            if let TableEvent::ExpandChildren(row_ix, expanded) = event {
+                println!("Expand children: {}, expanded: {}", row_ix, expanded);
+            }
+        })];
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            table,
+            _subscriptions,
+        }
+    }
+}
+
+impl Focusable for TreeTableStory {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for TreeTableStory {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().size_full().child(DataTable::new(&self.table))
+    }
+}
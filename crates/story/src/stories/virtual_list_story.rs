@@ -2,7 +2,8 @@ use std::{ops::Range, rc::Rc};
 
 use gpui::{
     App, AppContext, Context, Div, Entity, FocusHandle, Focusable, InteractiveElement, IntoElement,
-    ParentElement, Pixels, Render, ScrollStrategy, Size, Styled, Window, div, px, size,
+    ParentElement, Pixels, Render, ScrollStrategy, Size, Styled, Window, div,
+    prelude::FluentBuilder as _, px, size,
 };
 use gpui_component::{
     ActiveTheme as _, Selectable, Sizable, VirtualListScrollHandle,
@@ -10,7 +11,7 @@ use gpui_component::{
     h_flex,
     scroll::{ScrollableElement, ScrollbarAxis},
     separator::Separator,
-    v_flex, v_virtual_list,
+    v_flex, v_virtual_list, virtual_grid,
 };
 
 pub struct VirtualListStory {
@@ -60,9 +61,15 @@ impl VirtualListStory {
                 .map(|i| format!("Item {}", i))
                 .collect::<Vec<_>>();
             self.columns_count = 100;
-        } else {
+        } else if n == 3 {
             self.items = (0..5).map(|i| format!("Item {}", i)).collect::<Vec<_>>();
             self.columns_count = 10;
+        } else {
+            // Grid
+            self.items = (0..10000)
+                .map(|i| format!("Item {}", i))
+                .collect::<Vec<_>>();
+            self.columns_count = 1000;
         }
 
         self.item_sizes = Rc::new(self.items.iter().map(|_| ITEM_SIZE).collect());
@@ -108,6 +115,11 @@ impl VirtualListStory {
                                             .label("Size 3")
                                             .selected(self.size_mode == 3),
                                     )
+                                    .child(
+                                        Button::new("test-4")
+                                            .label("Grid")
+                                            .selected(self.size_mode == 4),
+                                    )
                                     .on_click(cx.listener(|view, clicks: &Vec<usize>, _, cx| {
                                         if clicks.contains(&0) {
                                             view.change_test_cases(0, cx)
@@ -117,6 +129,8 @@ impl VirtualListStory {
                                             view.change_test_cases(2, cx)
                                         } else if clicks.contains(&3) {
                                             view.change_test_cases(3, cx)
+                                        } else if clicks.contains(&4) {
+                                            view.change_test_cases(4, cx)
                                         }
                                     })),
                             )
@@ -196,7 +210,23 @@ impl VirtualListStory {
                                 this.scroll_handle.scroll_to_bottom();
                                 cx.notify();
                             })),
-                    ),
+                    )
+                    .when(self.size_mode == 4, |this| {
+                        this.child(
+                            Button::new("scroll-to-cell")
+                                .small()
+                                .outline()
+                                .label("Scroll to Cell (500, 200)")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.scroll_handle.scroll_to_cell(
+                                        500,
+                                        200,
+                                        ScrollStrategy::Center,
+                                    );
+                                    cx.notify();
+                                })),
+                        )
+                    }),
             )
     }
 }
@@ -253,35 +283,65 @@ impl Render for VirtualListStory {
                             .id("list")
                             .relative()
                             .size_full()
-                            .child(
-                                v_virtual_list(
-                                    cx.entity().clone(),
-                                    "items",
-                                    self.item_sizes.clone(),
-                                    move |story, visible_range, _, cx| {
-                                        story.visible_range = visible_range.clone();
+                            .when(self.size_mode == 4, |this| {
+                                this.child(
+                                    virtual_grid(
+                                        cx.entity().clone(),
+                                        "grid",
+                                        self.items.len(),
+                                        columns_count,
+                                        ITEM_SIZE,
+                                        move |story, rows, columns, _, cx| {
+                                            story.visible_range = rows.clone();
 
-                                        visible_range
-                                            .map(|ix| {
-                                                h_flex().gap_1().items_center().children(
-                                                    (0..columns_count).map(|i| {
-                                                        render_item(cx).child(if i == 0 {
-                                                            format!("row: {}", ix)
-                                                        } else {
-                                                            format!("{}", i)
-                                                        })
-                                                    }),
-                                                )
+                                            rows.flat_map(|row| {
+                                                columns.clone().map(move |column| (row, column))
+                                            })
+                                            .map(|(row, column)| {
+                                                render_item(cx)
+                                                    .child(format!("{}, {}", row, column))
                                             })
                                             .collect()
-                                    },
+                                        },
+                                    )
+                                    .track_scroll(&self.scroll_handle)
+                                    .p_4()
+                                    .border_1()
+                                    .border_color(cx.theme().border)
+                                    .gap_1(),
                                 )
-                                .track_scroll(&self.scroll_handle)
-                                .p_4()
-                                .border_1()
-                                .border_color(cx.theme().border)
-                                .gap_1(),
-                            )
+                            })
+                            .when(self.size_mode != 4, |this| {
+                                this.child(
+                                    v_virtual_list(
+                                        cx.entity().clone(),
+                                        "items",
+                                        self.item_sizes.clone(),
+                                        move |story, visible_range, _, cx| {
+                                            story.visible_range = visible_range.clone();
+
+                                            visible_range
+                                                .map(|ix| {
+                                                    h_flex().gap_1().items_center().children(
+                                                        (0..columns_count).map(|i| {
+                                                            render_item(cx).child(if i == 0 {
+                                                                format!("row: {}", ix)
+                                                            } else {
+                                                                format!("{}", i)
+                                                            })
+                                                        }),
+                                                    )
+                                                })
+                                                .collect()
+                                        },
+                                    )
+                                    .track_scroll(&self.scroll_handle)
+                                    .p_4()
+                                    .border_1()
+                                    .border_color(cx.theme().border)
+                                    .gap_1(),
+                                )
+                            })
                             .scrollbar(&self.scroll_handle, self.axis),
                     ),
                 ),
@@ -1,9 +1,9 @@
 use std::rc::Rc;
 
 use gpui::{
-    App, ClickEvent, ElementId, Empty, Hsla, InteractiveElement, IntoElement, ParentElement as _,
-    RenderOnce, Role, SharedString, StatefulInteractiveElement, StyleRefinement, Styled, Window,
-    div, prelude::FluentBuilder as _, px, rems, transparent_white,
+    AnyElement, App, ClickEvent, ElementId, Empty, Hsla, InteractiveElement, IntoElement,
+    ParentElement as _, RenderOnce, Role, SharedString, StatefulInteractiveElement,
+    StyleRefinement, Styled, Window, div, prelude::FluentBuilder as _, px, rems, transparent_white,
 };
 
 use crate::{
@@ -23,6 +23,16 @@ pub enum AlertVariant {
 }
 
 impl AlertVariant {
+    /// Returns the default icon of the variant.
+    pub fn icon_name(&self) -> IconName {
+        match self {
+            Self::Default | Self::Info => IconName::Info,
+            Self::Success => IconName::CircleCheck,
+            Self::Warning => IconName::TriangleAlert,
+            Self::Error => IconName::CircleX,
+        }
+    }
+
     fn fg(&self, cx: &App) -> Hsla {
         match self {
             Self::Default => cx.theme().foreground,
@@ -60,12 +70,15 @@ pub struct Alert {
     id: ElementId,
     style: StyleRefinement,
     variant: AlertVariant,
-    icon: Icon,
+    icon: Option<Icon>,
     title: Option<SharedString>,
     message: Text,
     size: Size,
     banner: bool,
+    actions: Vec<AnyElement>,
     on_close: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
+    dismissible: bool,
+    on_dismiss: Option<Rc<dyn Fn(&mut Window, &mut App) + 'static>>,
     visible: bool,
 }
 
@@ -76,53 +89,50 @@ impl Alert {
             id: id.into(),
             style: StyleRefinement::default(),
             variant: AlertVariant::default(),
-            icon: Icon::new(IconName::Info),
+            icon: None,
             title: None,
             message: message.into(),
             size: Size::default(),
             banner: false,
+            actions: vec![],
             visible: true,
             on_close: None,
+            dismissible: false,
+            on_dismiss: None,
         }
     }
 
     /// Create a new info [`AlertVariant::Info`] with the given message.
     pub fn info(id: impl Into<ElementId>, message: impl Into<Text>) -> Self {
-        Self::new(id, message)
-            .with_variant(AlertVariant::Info)
-            .icon(IconName::Info)
+        Self::new(id, message).with_variant(AlertVariant::Info)
     }
 
     /// Create a new [`AlertVariant::Success`] alert with the given message.
     pub fn success(id: impl Into<ElementId>, message: impl Into<Text>) -> Self {
-        Self::new(id, message)
-            .with_variant(AlertVariant::Success)
-            .icon(IconName::CircleCheck)
+        Self::new(id, message).with_variant(AlertVariant::Success)
     }
 
     /// Create a new [`AlertVariant::Warning`] alert with the given message.
     pub fn warning(id: impl Into<ElementId>, message: impl Into<Text>) -> Self {
-        Self::new(id, message)
-            .with_variant(AlertVariant::Warning)
-            .icon(IconName::TriangleAlert)
+        Self::new(id, message).with_variant(AlertVariant::Warning)
     }
 
     /// Create a new [`AlertVariant::Error`] alert with the given message.
     pub fn error(id: impl Into<ElementId>, message: impl Into<Text>) -> Self {
-        Self::new(id, message)
-            .with_variant(AlertVariant::Error)
-            .icon(IconName::CircleX)
+        Self::new(id, message).with_variant(AlertVariant::Error)
     }
 
     /// Sets the [`AlertVariant`] of the alert.
+    ///
+    /// If no icon is set, the default icon of the variant will be used.
     pub fn with_variant(mut self, variant: AlertVariant) -> Self {
         self.variant = variant;
         self
     }
 
-    /// Set the icon for the alert.
+    /// Set the icon for the alert, default is the icon of the [`AlertVariant`].
     pub fn icon(mut self, icon: impl Into<Icon>) -> Self {
-        self.icon = icon.into();
+        self.icon = Some(icon.into());
         self
     }
 
@@ -134,13 +144,43 @@ impl Alert {
 
     /// Set alert as banner style.
     ///
-    /// The `banner` style will make the alert take the full width of the container and not border and radius.
-    /// This mode will not display `title`.
+    /// The `banner` style will make the alert take the full width of the container
+    /// with only a bottom border and no radius, suitable for pinning at the top of a panel.
+    /// This mode will not display `title`, and the actions are displayed inline.
     pub fn banner(mut self) -> Self {
         self.banner = true;
         self
     }
 
+    /// Add an action element (e.g. a [`crate::button::Button`]) to the alert.
+    pub fn action(mut self, action: impl IntoElement) -> Self {
+        self.actions.push(action.into_any_element());
+        self
+    }
+
+    /// Add multiple action elements to the alert.
+    pub fn actions(mut self, actions: impl IntoIterator<Item = impl IntoElement>) -> Self {
+        self.actions
+            .extend(actions.into_iter().map(|action| action.into_any_element()));
+        self
+    }
+
+    /// Set whether the alert can be dismissed by the close button, default is `false`.
+    ///
+    /// Unlike [`Alert::on_close`], the dismissed state is kept by the alert itself (keyed by `id`),
+    /// so the alert will hide itself after clicking the close button.
+    pub fn dismissible(mut self, dismissible: bool) -> Self {
+        self.dismissible = dismissible;
+        self
+    }
+
+    /// Set a callback to be called when the alert is dismissed, this implies [`Alert::dismissible`].
+    pub fn on_dismiss(mut self, on_dismiss: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.dismissible = true;
+        self.on_dismiss = Some(Rc::new(on_dismiss));
+        self
+    }
+
     /// Set the visibility of the alert.
     pub fn visible(mut self, visible: bool) -> Self {
         self.visible = visible;
@@ -171,11 +211,46 @@ impl Styled for Alert {
 }
 
 impl RenderOnce for Alert {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         if !self.visible {
             return Empty.into_any_element();
         }
 
+        let dismissed = self
+            .dismissible
+            .then(|| window.use_keyed_state(self.id.clone(), cx, |_, _| false));
+        if dismissed.as_ref().is_some_and(|state| *state.read(cx)) {
+            return Empty.into_any_element();
+        }
+
+        let parent_view_id = window.current_view();
+        let on_close: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>> =
+            match (self.on_close, dismissed) {
+                (on_close, Some(dismissed)) => {
+                    let on_dismiss = self.on_dismiss;
+                    Some(Rc::new(move |ev, window, cx| {
+                        dismissed.update(cx, |dismissed, _| *dismissed = true);
+                        cx.notify(parent_view_id);
+                        if let Some(on_close) = on_close.as_ref() {
+                            on_close(ev, window, cx);
+                        }
+                        if let Some(on_dismiss) = on_dismiss.as_ref() {
+                            on_dismiss(window, cx);
+                        }
+                    }))
+                }
+                (on_close, None) => on_close,
+            };
+        let icon = self
+            .icon
+            .unwrap_or_else(|| Icon::new(self.variant.icon_name()));
+        let actions = (!self.actions.is_empty()).then_some(self.actions);
+        let (inline_actions, banner_actions) = if self.banner {
+            (None, actions)
+        } else {
+            (actions, None)
+        };
+
         let (radius, padding_x, padding_y, gap) = match self.size {
             Size::XSmall => (cx.theme().radius, px(12.), px(6.), px(6.)),
             Size::Small => (cx.theme().radius, px(12.), px(8.), px(6.)),
@@ -198,9 +273,14 @@ impl RenderOnce for Alert {
             .gap(gap)
             .justify_between()
             .text_sm()
-            .border_1()
             .border_color(border_color)
-            .when(!self.banner, |this| this.rounded(radius).items_start())
+            .map(|this| {
+                if self.banner {
+                    this.border_b_1()
+                } else {
+                    this.border_1().rounded(radius).items_start()
+                }
+            })
             .refine_style(&self.style)
             .child(
                 div()
@@ -209,11 +289,7 @@ impl RenderOnce for Alert {
                     .when(self.banner, |this| this.items_center())
                     .overflow_hidden()
                     .gap(gap)
-                    .child(
-                        div()
-                            .when(!self.banner, |this| this.mt(px(5.)))
-                            .child(self.icon),
-                    )
+                    .child(div().when(!self.banner, |this| this.mt(px(5.))).child(icon))
                     .child(
                         div()
                             .flex_1()
@@ -229,10 +305,16 @@ impl RenderOnce for Alert {
                             .child(
                                 self.message
                                     .style(TextViewStyle::default().paragraph_gap(rems(0.2))),
-                            ),
+                            )
+                            .when_some(inline_actions, |this, actions| {
+                                this.child(h_flex().mt_2().gap_2().flex_wrap().children(actions))
+                            }),
                     ),
             )
-            .when_some(self.on_close, |this, on_close| {
+            .when_some(banner_actions, |this, actions| {
+                this.child(h_flex().flex_shrink_0().gap_2().children(actions))
+            })
+            .when_some(on_close, |this, on_close| {
                 this.child(
                     div()
                         .id("close")
@@ -253,3 +335,49 @@ impl RenderOnce for Alert {
             .into_any_element()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_builder() {
+        let alert = Alert::warning("alert", "Message")
+            .title("Title")
+            .banner()
+            .action(div())
+            .actions([div(), div()])
+            .on_dismiss(|_, _| {});
+
+        assert_eq!(alert.variant, AlertVariant::Warning);
+        assert!(alert.icon.is_none());
+        assert_eq!(alert.title, Some("Title".into()));
+        assert!(alert.banner);
+        assert_eq!(alert.actions.len(), 3);
+        assert!(alert.dismissible);
+        assert!(alert.on_dismiss.is_some());
+    }
+
+    #[test]
+    fn test_alert_variant_icon_name() {
+        use crate::IconNamed as _;
+
+        assert_eq!(
+            AlertVariant::Default.icon_name().path(),
+            IconName::Info.path()
+        );
+        assert_eq!(AlertVariant::Info.icon_name().path(), IconName::Info.path());
+        assert_eq!(
+            AlertVariant::Success.icon_name().path(),
+            IconName::CircleCheck.path()
+        );
+        assert_eq!(
+            AlertVariant::Warning.icon_name().path(),
+            IconName::TriangleAlert.path()
+        );
+        assert_eq!(
+            AlertVariant::Error.icon_name().path(),
+            IconName::CircleX.path()
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use std::rc::Rc;
 
 use crate::{
-    ActiveTheme, Colorize as _, Disableable, FocusableExt as _, Icon, IconName, Selectable,
-    Sizable, Size, StyleSized, StyledExt,
+    ActiveTheme, Colorize as _, Disableable, ElementExt as _, FocusableExt as _, Icon, IconName,
+    Selectable, Sizable, Size, StyleSized, StyledExt,
     button::ButtonIcon,
     h_flex,
     tooltip::{ManagedTooltipExt as _, Tooltip},
@@ -210,6 +210,7 @@ pub struct Button {
 
     tab_index: isize,
     tab_stop: bool,
+    autofocus: bool,
 }
 
 impl From<Button> for AnyElement {
@@ -254,6 +255,7 @@ impl Button {
             dropdown_caret: false,
             tab_index: 0,
             tab_stop: true,
+            autofocus: false,
         }
     }
 
@@ -367,6 +369,14 @@ impl Button {
         self
     }
 
+    /// Set to focus the button when it is first rendered, default is false.
+    ///
+    /// This is useful to give the initial focus to a button in a dialog.
+    pub fn autofocus(mut self, autofocus: bool) -> Self {
+        self.autofocus = autofocus;
+        self
+    }
+
     /// Set to show a dropdown caret icon at the end of the button.
     pub fn dropdown_caret(mut self, dropdown_caret: bool) -> Self {
         self.dropdown_caret = dropdown_caret;
@@ -446,11 +456,22 @@ impl RenderOnce for Button {
             _ => self.size,
         };
 
+        let mut first_render = false;
         let focus_handle = window
-            .use_keyed_state(self.id.clone(), cx, |_, cx| cx.focus_handle())
+            .use_keyed_state(self.id.clone(), cx, |_, cx| {
+                first_render = true;
+                cx.focus_handle()
+            })
             .read(cx)
             .clone();
         let is_focused = focus_handle.is_focused(window);
+        if first_render && self.autofocus && !self.disabled {
+            // Defer to override the focus set by the container (e.g. Dialog) on open.
+            window.defer(cx, {
+                let focus_handle = focus_handle.clone();
+                move |window, cx| focus_handle.focus(window, cx)
+            });
+        }
 
         let rounding = match self.rounded {
             ButtonRounded::Small => cx.theme().radius * 0.5,
@@ -461,6 +482,7 @@ impl RenderOnce for Button {
         };
 
         self.base
+            .test_record(Some(self.id.clone()), self.label.clone())
             .role(if self.variant.is_link() {
                 Role::Link
             } else {
@@ -140,7 +140,7 @@ where
         let y = ScaleLinear::new(all_values, vec![height, 10.]);
 
         // Draw X axis
-        let mut axis = PlotAxis::new().stroke(cx.theme().border);
+        let mut axis = PlotAxis::new().stroke(cx.theme().chart_axis);
         if self.x_axis {
             let labels = build_band_labels(
                 &self.data,
@@ -148,7 +148,7 @@ where
                 &x,
                 band_width,
                 self.tick_margin,
-                cx.theme().muted_foreground,
+                cx.theme().chart_axis_foreground,
             );
             axis = axis.x(height).x_label(labels);
         }
@@ -158,7 +158,7 @@ where
         if self.grid {
             Grid::new()
                 .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
-                .stroke(cx.theme().border)
+                .stroke(cx.theme().chart_grid)
                 .dash_array(&[px(4.), px(2.)])
                 .paint(&bounds, window);
         }
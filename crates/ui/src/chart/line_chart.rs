@@ -174,14 +174,14 @@ where
         let height = bounds.size.height.as_f32() - axis_gap;
 
         // Draw X axis
-        let mut axis = PlotAxis::new().stroke(cx.theme().border);
+        let mut axis = PlotAxis::new().stroke(cx.theme().chart_axis);
         if self.x_axis {
             let labels = build_point_x_labels(
                 &self.data,
                 x_fn.as_ref(),
                 &x,
                 self.tick_margin,
-                cx.theme().muted_foreground,
+                cx.theme().chart_axis_foreground,
             );
             axis = axis.x(height).x_label(labels);
         }
@@ -191,13 +191,13 @@ where
         if self.grid {
             Grid::new()
                 .y((0..=3).map(|i| height * i as f32 / 4.0).collect())
-                .stroke(cx.theme().border)
+                .stroke(cx.theme().chart_grid)
                 .dash_array(&[px(4.), px(2.)])
                 .paint(&bounds, window);
         }
 
         // Draw line
-        let stroke = self.stroke.unwrap_or(cx.theme().chart_2);
+        let stroke = self.stroke.unwrap_or(cx.theme().chart_series(0));
         let x_fn = x_fn.clone();
         let y_fn = y_fn.clone();
         let mut line = Line::new()
@@ -258,7 +258,7 @@ where
         let d = self.data.get(state.index)?;
         let title: SharedString = x_fn(d).into();
         let value = y_fn(d).to_f64()?;
-        let stroke = self.stroke.unwrap_or(cx.theme().chart_2);
+        let stroke = self.stroke.unwrap_or(cx.theme().chart_series(0));
         let name = self.name.clone().unwrap_or_default();
 
         Some(
@@ -5,6 +5,8 @@ mod line_chart;
 mod pie_chart;
 mod radar_chart;
 mod sankey_chart;
+mod sparkline;
+mod waveform;
 
 pub use area_chart::AreaChart;
 pub use bar_chart::BarChart;
@@ -13,6 +15,8 @@ pub use line_chart::LineChart;
 pub use pie_chart::PieChart;
 pub use radar_chart::RadarChart;
 pub use sankey_chart::{SankeyChart, SankeyLabel};
+pub use sparkline::Sparkline;
+pub use waveform::Waveform;
 
 use gpui::{Hsla, SharedString, TextAlign};
 
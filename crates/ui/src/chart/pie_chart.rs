@@ -112,6 +112,8 @@ impl<T> PieChart<T> {
     }
 
     /// Set the color of the pie chart.
+    ///
+    /// Defaults to the theme chart palette, cycled per slice.
     pub fn color<H>(mut self, color: impl Fn(&T) -> H + 'static) -> Self
     where
         H: Into<Hsla> + 'static,
@@ -177,7 +179,7 @@ impl<T> Plot for PieChart<T> {
                 if let Some(color_fn) = self.color.as_ref() {
                     color_fn(a.data)
                 } else {
-                    cx.theme().chart_2
+                    cx.theme().chart_series(a.index)
                 },
                 Some(inner_radius),
                 Some(outer_radius),
@@ -113,7 +113,7 @@ where
 
     /// Set the stroke color of the most recently added series.
     ///
-    /// Defaults to the theme chart colors, cycled per series.
+    /// Defaults to the theme chart palette, cycled per series.
     pub fn stroke(mut self, stroke: impl Into<Hsla>) -> Self {
         self.strokes.push(stroke.into());
         self
@@ -133,7 +133,7 @@ where
         self
     }
 
-    /// Set the label text color (defaults to `cx.theme().muted_foreground`).
+    /// Set the label text color (defaults to `cx.theme().chart_axis_foreground`).
     pub fn label_color(mut self, color: impl Into<Hsla>) -> Self {
         self.label_color = Some(color.into());
         self
@@ -184,20 +184,12 @@ where
 
     /// The stroke color of the series at the given index, set or default.
     ///
-    /// Defaults to the theme chart colors, cycled per series.
+    /// Defaults to the theme chart palette, cycled per series.
     fn series_stroke(&self, ix: usize, cx: &App) -> Hsla {
-        let colors = [
-            cx.theme().chart_1,
-            cx.theme().chart_2,
-            cx.theme().chart_3,
-            cx.theme().chart_4,
-            cx.theme().chart_5,
-        ];
-
         self.strokes
             .get(ix)
             .copied()
-            .unwrap_or(colors[ix % colors.len()])
+            .unwrap_or(cx.theme().chart_series(ix))
     }
 
     /// The resolved outer radius for the given bounds.
@@ -266,7 +258,7 @@ where
 
         // Draw grid rings and spokes
         if self.grid {
-            let stroke = cx.theme().border;
+            let stroke = cx.theme().chart_grid;
 
             for level in 1..=self.grid_levels {
                 let radius = outer_radius * level as f32 / self.grid_levels as f32;
@@ -325,7 +317,7 @@ where
         };
 
         let label_radius = outer_radius + self.label_gap;
-        let label_color = self.label_color.unwrap_or(cx.theme().muted_foreground);
+        let label_color = self.label_color.unwrap_or(cx.theme().chart_axis_foreground);
 
         let labels = self.data.iter().enumerate().map(|(i, d)| {
             let angle = i as f32 * angle_step - HALF_PI;
@@ -278,7 +278,8 @@ impl<T> Plot for SankeyChart<T> {
                     }
                     if let Some(node_label) = &self.node_label {
                         lines.push(
-                            SankeyLabel::new(node_label(datum)).color(cx.theme().muted_foreground),
+                            SankeyLabel::new(node_label(datum))
+                                .color(cx.theme().chart_axis_foreground),
                         );
                     }
                     lines
@@ -353,20 +354,13 @@ impl<T> Plot for SankeyChart<T> {
             )
             .layout_from(topology);
 
-        let palette = [
-            cx.theme().chart_1,
-            cx.theme().chart_2,
-            cx.theme().chart_3,
-            cx.theme().chart_4,
-            cx.theme().chart_5,
-        ];
         let colors: Vec<Hsla> = self
             .nodes
             .iter()
             .enumerate()
             .map(|(index, datum)| match &self.node_color {
                 Some(color) => color(datum),
-                None => palette[index % palette.len()],
+                None => cx.theme().chart_series(index),
             })
             .collect();
 
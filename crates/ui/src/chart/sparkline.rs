@@ -0,0 +1,287 @@
+use std::sync::Arc;
+
+use gpui::{
+    App, Bounds, Hsla, IntoElement, ParentElement, PathBuilder, Pixels, RenderOnce,
+    StyleRefinement, Styled, Window, canvas, div, point, px,
+};
+
+use crate::{ActiveTheme as _, StyledExt as _};
+
+/// Returns the `(min, max)` of each bucket of the `values` split into `count` buckets.
+///
+/// Returns a bucket for each value if there are no more values than the `count`,
+/// so drawing thousands of values costs no more than the pixel columns.
+pub(super) fn min_max_buckets(values: &[f32], count: usize) -> Vec<(f32, f32)> {
+    if values.len() <= count {
+        return values.iter().map(|v| (*v, *v)).collect();
+    }
+
+    (0..count)
+        .map(|ix| {
+            let start = ix * values.len() / count;
+            let end = ((ix + 1) * values.len() / count).max(start + 1);
+            values[start..end]
+                .iter()
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| {
+                    (min.min(*v), max.max(*v))
+                })
+        })
+        .collect()
+}
+
+/// Returns the `(min, max)` of the `values`, or `(0, 0)` if empty.
+fn value_range(values: &[f32]) -> (f32, f32) {
+    if values.is_empty() {
+        return (0., 0.);
+    }
+
+    values
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| {
+            (min.min(*v), max.max(*v))
+        })
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum SparklineKind {
+    #[default]
+    Line,
+    Bar,
+}
+
+/// A tiny line or bar chart without axes, to show a trend inline, e.g. in a table cell,
+/// a list row or a status bar.
+///
+/// The values are downsampled to the pixel columns, so thousands of values are cheap.
+/// The default size is 80x20px, use the `Styled` methods to change it.
+///
+/// ```ignore
+/// Sparkline::new(vec![3., 5., 2., 8., 6.])
+/// Sparkline::new(values).bar().color(cx.theme().chart_2).w(px(120.))
+/// ```
+#[derive(IntoElement)]
+pub struct Sparkline {
+    values: Arc<[f32]>,
+    kind: SparklineKind,
+    range: Option<(f32, f32)>,
+    color: Option<Hsla>,
+    stroke_width: Pixels,
+    style: StyleRefinement,
+}
+
+impl Sparkline {
+    /// Create a new sparkline with the `values`.
+    pub fn new(values: impl Into<Arc<[f32]>>) -> Self {
+        Self {
+            values: values.into(),
+            kind: SparklineKind::default(),
+            range: None,
+            color: None,
+            stroke_width: px(1.5),
+            style: StyleRefinement::default(),
+        }
+    }
+
+    /// Draw the values as a line, this is the default.
+    pub fn line(mut self) -> Self {
+        self.kind = SparklineKind::Line;
+        self
+    }
+
+    /// Draw the values as bars from zero.
+    pub fn bar(mut self) -> Self {
+        self.kind = SparklineKind::Bar;
+        self
+    }
+
+    /// Set the value range of the height, default is the min and max of the values.
+    ///
+    /// Use the same range for the sparklines in a table to make them comparable.
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Set the color, default is the `chart_series(0)` color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the width of the line, default is 1.5px.
+    pub fn stroke_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.stroke_width = width.into();
+        self
+    }
+}
+
+impl Styled for Sparkline {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+/// Paint the `values` as a line in the `bounds`, the `y` maps a value to the y in the bounds.
+fn paint_line(
+    values: &[f32],
+    bounds: Bounds<Pixels>,
+    y: impl Fn(f32) -> Pixels,
+    color: Hsla,
+    stroke_width: Pixels,
+    window: &mut Window,
+) {
+    let columns = f32::from(bounds.size.width).ceil().max(1.) as usize;
+    let buckets = min_max_buckets(values, columns);
+    if buckets.len() < 2 {
+        return;
+    }
+
+    let step = bounds.size.width / (buckets.len() - 1) as f32;
+    let mut builder = PathBuilder::stroke(stroke_width);
+    for (ix, (min, max)) in buckets.iter().enumerate() {
+        let x = bounds.left() + step * ix as f32;
+        if ix == 0 {
+            builder.move_to(point(x, y(*min)));
+        } else {
+            builder.line_to(point(x, y(*min)));
+        }
+        if max != min {
+            builder.line_to(point(x, y(*max)));
+        }
+    }
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
+}
+
+/// Paint the `values` as bars from the `base` value in the `bounds`.
+fn paint_bars(
+    values: &[f32],
+    bounds: Bounds<Pixels>,
+    base: f32,
+    y: impl Fn(f32) -> Pixels,
+    color: Hsla,
+    window: &mut Window,
+) {
+    let columns = (f32::from(bounds.size.width) / 2.).floor().max(1.) as usize;
+    let buckets = min_max_buckets(values, columns);
+    if buckets.is_empty() {
+        return;
+    }
+
+    let slot = bounds.size.width / buckets.len() as f32;
+    let gap = if slot >= px(4.) { px(1.) } else { px(0.) };
+    let base_y = y(base);
+    let mut builder = PathBuilder::fill();
+    for (ix, (min, max)) in buckets.iter().enumerate() {
+        // Use the value farthest from the base.
+        let value = if (max - base).abs() >= (min - base).abs() {
+            *max
+        } else {
+            *min
+        };
+        let left = bounds.left() + slot * ix as f32;
+        let right = left + slot - gap;
+        let (top, bottom) = if value >= base {
+            (y(value), base_y)
+        } else {
+            (base_y, y(value))
+        };
+        // Keep at least 1px to show the zero values.
+        let top = top.min(bottom - px(1.));
+
+        builder.move_to(point(left, top));
+        builder.line_to(point(right, top));
+        builder.line_to(point(right, bottom));
+        builder.line_to(point(left, bottom));
+        builder.close();
+    }
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
+}
+
+impl RenderOnce for Sparkline {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let color = self.color.unwrap_or(cx.theme().chart_series(0));
+        let values = self.values;
+        let kind = self.kind;
+        let stroke_width = self.stroke_width;
+        let (min, max) = self.range.unwrap_or_else(|| {
+            let (min, max) = value_range(&values);
+            // Bars start from zero.
+            match kind {
+                SparklineKind::Line => (min, max),
+                SparklineKind::Bar => (min.min(0.), max.max(0.)),
+            }
+        });
+
+        div()
+            .flex_shrink_0()
+            .w(px(80.))
+            .h(px(20.))
+            .refine_style(&self.style)
+            .child(
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        // Keep the line inside the bounds.
+                        let inset = match kind {
+                            SparklineKind::Line => stroke_width / 2.,
+                            SparklineKind::Bar => px(0.),
+                        };
+                        let top = bounds.top() + inset;
+                        let height = bounds.size.height - inset * 2.;
+                        let span = max - min;
+                        let y = |value: f32| {
+                            let ratio = if span > 0. {
+                                ((value - min) / span).clamp(0., 1.)
+                            } else {
+                                0.5
+                            };
+                            top + height * (1. - ratio)
+                        };
+
+                        match kind {
+                            SparklineKind::Line => {
+                                paint_line(&values, bounds, y, color, stroke_width, window)
+                            }
+                            SparklineKind::Bar => {
+                                paint_bars(&values, bounds, 0f32.clamp(min, max), y, color, window)
+                            }
+                        }
+                    },
+                )
+                .size_full(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{min_max_buckets, value_range};
+
+    #[test]
+    fn test_min_max_buckets() {
+        assert_eq!(
+            min_max_buckets(&[1., 2., 3.], 5),
+            vec![(1., 1.), (2., 2.), (3., 3.)]
+        );
+        assert_eq!(
+            min_max_buckets(&[1., 5., 3., -2., 4., 0.], 3),
+            vec![(1., 5.), (-2., 3.), (0., 4.)]
+        );
+        // Uneven buckets cover all the values.
+        assert_eq!(
+            min_max_buckets(&[1., 2., 3., 4., 5.], 2),
+            vec![(1., 2.), (3., 5.)]
+        );
+        assert!(min_max_buckets(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_value_range() {
+        assert_eq!(value_range(&[3., -1., 2.]), (-1., 3.));
+        assert_eq!(value_range(&[]), (0., 0.));
+    }
+}
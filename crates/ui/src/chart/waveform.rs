@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use gpui::{
+    App, Bounds, Hsla, IntoElement, ParentElement, PathBuilder, Pixels, RenderOnce,
+    StyleRefinement, Styled, Window, canvas, div, fill, point, px, size,
+};
+
+use crate::{ActiveTheme as _, StyledExt as _};
+
+use super::sparkline::min_max_buckets;
+
+/// Returns the peak amplitude of each bar of the `samples`, in `0.0..=1.0`.
+fn peaks(samples: &[f32], bars: usize) -> Vec<f32> {
+    min_max_buckets(samples, bars)
+        .into_iter()
+        .map(|(min, max)| min.abs().max(max.abs()).min(1.))
+        .collect()
+}
+
+/// An audio waveform of the samples, drawn as the bars mirrored around the center.
+///
+/// The samples are in `-1.0..=1.0`, and are downsampled to the bars, so the waveform
+/// of a whole track is cheap to render in a list row. The default height is 32px.
+///
+/// ```ignore
+/// Waveform::new(samples).position(0.3).w(px(240.))
+/// ```
+#[derive(IntoElement)]
+pub struct Waveform {
+    samples: Arc<[f32]>,
+    position: Option<f32>,
+    color: Option<Hsla>,
+    played_color: Option<Hsla>,
+    bar_width: Pixels,
+    bar_gap: Pixels,
+    style: StyleRefinement,
+}
+
+impl Waveform {
+    /// Create a new waveform with the `samples` in `-1.0..=1.0`.
+    pub fn new(samples: impl Into<Arc<[f32]>>) -> Self {
+        Self {
+            samples: samples.into(),
+            position: None,
+            color: None,
+            played_color: None,
+            bar_width: px(2.),
+            bar_gap: px(1.),
+            style: StyleRefinement::default(),
+        }
+    }
+
+    /// Set the current position in `0.0..=1.0` to show the indicator, the bars before it
+    /// are drawn by the played color, default is `None` to hide.
+    pub fn position(mut self, position: impl Into<Option<f32>>) -> Self {
+        self.position = position.into().map(|position| position.clamp(0., 1.));
+        self
+    }
+
+    /// Set the color of the bars, default is the `muted_foreground` color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the color of the bars before the position, default is the `primary` color of the theme.
+    pub fn played_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.played_color = Some(color.into());
+        self
+    }
+
+    /// Set the width of the bars and the gap between them, default is 2px and 1px.
+    pub fn bar_width(mut self, width: impl Into<Pixels>, gap: impl Into<Pixels>) -> Self {
+        self.bar_width = width.into().max(px(1.));
+        self.bar_gap = gap.into();
+        self
+    }
+}
+
+impl Styled for Waveform {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Waveform {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let samples = self.samples;
+        let position = self.position;
+        let color = self.color.unwrap_or(cx.theme().muted_foreground);
+        let played_color = self.played_color.unwrap_or(cx.theme().primary);
+        let indicator_color = cx.theme().foreground;
+        let bar_width = self.bar_width;
+        let slot = self.bar_width + self.bar_gap;
+
+        div()
+            .flex_shrink_0()
+            .w_full()
+            .h(px(32.))
+            .refine_style(&self.style)
+            .child(
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        let bars = (bounds.size.width / slot).floor().max(1.) as usize;
+                        let played_x = position.map(|position| bounds.size.width * position);
+                        let center_y = bounds.center().y;
+                        let half = bounds.size.height / 2.;
+
+                        let mut played = PathBuilder::fill();
+                        let mut rest = PathBuilder::fill();
+                        for (ix, peak) in peaks(&samples, bars).into_iter().enumerate() {
+                            let x = slot * ix as f32;
+                            let builder = match played_x {
+                                Some(played_x) if x < played_x => &mut played,
+                                _ => &mut rest,
+                            };
+
+                            // Keep at least 1px to show the silence.
+                            let h = (half * peak).max(px(0.5));
+                            let left = bounds.left() + x;
+                            builder.move_to(point(left, center_y - h));
+                            builder.line_to(point(left + bar_width, center_y - h));
+                            builder.line_to(point(left + bar_width, center_y + h));
+                            builder.line_to(point(left, center_y + h));
+                            builder.close();
+                        }
+
+                        if let Ok(path) = played.build() {
+                            window.paint_path(path, played_color);
+                        }
+                        if let Ok(path) = rest.build() {
+                            window.paint_path(path, color);
+                        }
+
+                        if let Some(played_x) = played_x {
+                            let x =
+                                (bounds.left() + played_x - px(0.5)).min(bounds.right() - px(1.));
+                            window.paint_quad(fill(
+                                Bounds::new(
+                                    point(x, bounds.top()),
+                                    size(px(1.), bounds.size.height),
+                                ),
+                                indicator_color,
+                            ));
+                        }
+                    },
+                )
+                .size_full(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::peaks;
+
+    #[test]
+    fn test_peaks() {
+        assert_eq!(peaks(&[0.1, -0.5, 0.3, 0.2], 2), vec![0.5, 0.3]);
+        // Clamp the clipped samples.
+        assert_eq!(peaks(&[1.5, -0.2], 4), vec![1., 0.2]);
+        assert!(peaks(&[], 4).is_empty());
+    }
+}
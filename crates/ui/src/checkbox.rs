@@ -19,6 +19,7 @@ pub struct Checkbox {
     label: Option<Text>,
     children: Vec<AnyElement>,
     checked: bool,
+    indeterminate: bool,
     disabled: bool,
     size: Size,
     tab_stop: bool,
@@ -37,6 +38,7 @@ impl Checkbox {
             label: None,
             children: Vec::new(),
             checked: false,
+            indeterminate: false,
             disabled: false,
             size: Size::default(),
             on_click: None,
@@ -64,6 +66,15 @@ impl Checkbox {
         self
     }
 
+    /// Set the indeterminate state for the checkbox, default is false.
+    ///
+    /// The indeterminate state shows a minus sign instead of the check mark,
+    /// e.g. some of the children are checked, and the click changes it to checked.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
     /// Set the click handler for the checkbox.
     ///
     /// The `&bool` parameter indicates the new checked state after the click.
@@ -197,9 +208,32 @@ pub(crate) fn checkbox_check_icon(
         })
 }
 
+fn checkbox_indeterminate_icon(size: Size, disabled: bool, cx: &App) -> impl IntoElement {
+    let color = if disabled {
+        cx.theme().primary_foreground.opacity(0.5)
+    } else {
+        cx.theme().primary_foreground
+    };
+
+    svg()
+        .absolute()
+        .top_px()
+        .left_px()
+        .map(|this| match size {
+            Size::XSmall => this.size_2(),
+            Size::Small => this.size_2p5(),
+            Size::Medium => this.size_3(),
+            Size::Large => this.size_3p5(),
+            _ => this.size_3(),
+        })
+        .text_color(color)
+        .path(IconName::Minus.path())
+}
+
 impl RenderOnce for Checkbox {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let checked = self.checked;
+        let indeterminate = self.indeterminate;
+        let checked = self.checked && !indeterminate;
 
         let focus_handle = window
             .use_keyed_state(self.id.clone(), cx, |_, cx| cx.focus_handle())
@@ -207,7 +241,7 @@ impl RenderOnce for Checkbox {
             .clone();
         let is_focused = focus_handle.is_focused(window);
 
-        let border_color = if checked {
+        let border_color = if checked || indeterminate {
             cx.theme().primary
         } else {
             cx.theme().input
@@ -222,7 +256,9 @@ impl RenderOnce for Checkbox {
         self.base
             .id(self.id.clone())
             .role(Role::CheckBox)
-            .aria_toggled(if checked {
+            .aria_toggled(if indeterminate {
+                Toggled::Mixed
+            } else if checked {
                 Toggled::True
             } else {
                 Toggled::False
@@ -271,19 +307,25 @@ impl RenderOnce for Checkbox {
                     .border_color(color)
                     .rounded(radius)
                     .when(cx.theme().shadow && !self.disabled, |this| this.shadow_xs())
-                    .map(|this| match checked {
+                    .map(|this| match checked || indeterminate {
                         false => this.bg(cx.theme().input_background()),
                         true if self.disabled => this.bg(color),
                         true => this.bg(cx.theme().tokens.primary),
                     })
-                    .child(checkbox_check_icon(
-                        self.id,
-                        self.size,
-                        checked,
-                        self.disabled,
-                        window,
-                        cx,
-                    )),
+                    .map(|this| {
+                        if indeterminate {
+                            this.child(checkbox_indeterminate_icon(self.size, self.disabled, cx))
+                        } else {
+                            this.child(checkbox_check_icon(
+                                self.id,
+                                self.size,
+                                checked,
+                                self.disabled,
+                                window,
+                                cx,
+                            ))
+                        }
+                    }),
             )
             .when(self.label.is_some() || !self.children.is_empty(), |this| {
                 this.child(
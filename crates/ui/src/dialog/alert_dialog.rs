@@ -1,16 +1,31 @@
+use std::rc::Rc;
+
 use gpui::{
-    AnyElement, App, ClickEvent, InteractiveElement as _, IntoElement, MouseButton, ParentElement,
-    Pixels, RenderOnce, StyleRefinement, Styled, Window, div, prelude::FluentBuilder as _,
+    AnyElement, App, ClickEvent, ElementId, InteractiveElement as _, IntoElement, MouseButton,
+    ParentElement, Pixels, RenderOnce, SharedString, StyleRefinement, Styled, Window, div,
+    prelude::FluentBuilder as _,
 };
+use rust_i18n::t;
 
 use crate::{
-    StyledExt as _, WindowExt as _,
+    ActiveTheme as _, Icon, IconName, Sizable as _, StyledExt as _, WindowExt as _,
+    button::ButtonVariant,
     dialog::{
-        Dialog, DialogButtonProps, DialogDescription, DialogFooter, DialogHeader, DialogTitle,
+        Dialog, DialogButton, DialogButtonProps, DialogDescription, DialogFooter, DialogHeader,
+        DialogTitle,
     },
-    h_flex, v_flex,
+    h_flex,
+    input::{Input, InputState},
+    v_flex,
 };
 
+/// The preset of the [`AlertDialog`], used to render the default icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlertDialogPreset {
+    Info,
+    Destructive,
+}
+
 /// AlertDialog is a modal dialog that interrupts the user with important content
 /// and expects a response.
 ///
@@ -68,6 +83,8 @@ pub struct AlertDialog {
     title: Option<AnyElement>,
     description: Option<AnyElement>,
     button_props: DialogButtonProps,
+    preset: Option<AlertDialogPreset>,
+    confirmation_text: Option<SharedString>,
     children: Vec<AnyElement>,
 }
 
@@ -85,6 +102,8 @@ impl AlertDialog {
             title: None,
             description: None,
             button_props: DialogButtonProps::default(),
+            preset: None,
+            confirmation_text: None,
             children: Vec::new(),
         }
     }
@@ -97,6 +116,35 @@ impl AlertDialog {
         self
     }
 
+    /// Set to use info dialog, with an info icon and only the OK button.
+    pub fn info(mut self) -> Self {
+        self.preset = Some(AlertDialogPreset::Info);
+        self.button_props.show_cancel = false;
+        self
+    }
+
+    /// Set to use destructive dialog, with a warning icon, a Danger OK button and a Cancel button.
+    ///
+    /// The Cancel button is focused when the dialog is opened, and the `enter` key will cancel
+    /// the dialog, so the destructive action must be confirmed explicitly.
+    pub fn destructive(mut self) -> Self {
+        self.preset = Some(AlertDialogPreset::Destructive);
+        self.button_props.show_cancel = true;
+        self.button_props.ok_variant = ButtonVariant::Danger;
+        self.button_props.default_button = Some(DialogButton::Cancel);
+        self
+    }
+
+    /// Set to use destructive dialog that requires typing the `text` (e.g. `DELETE`) to confirm.
+    ///
+    /// The OK button is disabled until the typed text matches, then the `enter` key will confirm.
+    pub fn typed_confirmation(mut self, text: impl Into<SharedString>) -> Self {
+        self = self.destructive();
+        self.confirmation_text = Some(text.into());
+        self.button_props.default_button = Some(DialogButton::Ok);
+        self
+    }
+
     /// Sets the trigger element for the alert dialog.
     ///
     /// When a trigger is set, the dialog will render as a trigger element that opens the dialog when clicked.
@@ -267,7 +315,40 @@ impl AlertDialog {
     }
 
     /// Convert AlertDialog into a configured Dialog.
-    pub(crate) fn into_dialog(self, window: &mut Window, cx: &mut App) -> Dialog {
+    pub(crate) fn into_dialog(mut self, window: &mut Window, cx: &mut App) -> Dialog {
+        if self.icon.is_none() {
+            self.icon = self.preset.map(|preset| {
+                let (icon, color) = match preset {
+                    AlertDialogPreset::Info => (IconName::Info, cx.theme().info),
+                    AlertDialogPreset::Destructive => (IconName::TriangleAlert, cx.theme().danger),
+                };
+                Icon::new(icon).large().text_color(color).into_any_element()
+            });
+        }
+
+        let confirmation = self.confirmation_text.clone().map(|text| {
+            let state = window.use_keyed_state(
+                ElementId::Name(format!("alert-dialog-confirmation-{}", text).into()),
+                cx,
+                |window, cx| {
+                    // Give the initial focus to the input, the user must type to confirm.
+                    cx.defer_in(window, |state: &mut InputState, window, cx| {
+                        state.focus(window, cx);
+                    });
+                    InputState::new(window, cx).placeholder(text.clone())
+                },
+            );
+            (text, state)
+        });
+
+        if let Some((text, state)) = confirmation.clone() {
+            let is_matched = move |cx: &App| state.read(cx).value() == text;
+            self.button_props.ok_disabled = !is_matched(cx);
+            let on_ok = self.button_props.on_ok.clone();
+            self.button_props.on_ok =
+                Rc::new(move |event, window, cx| is_matched(cx) && on_ok(event, window, cx));
+        }
+
         let button_props = self.button_props.clone();
         let has_title = self.icon.is_some() || self.title.is_some();
         let has_header = has_title || self.description.is_some();
@@ -299,6 +380,19 @@ impl AlertDialog {
                 )
             })
             .children(self.children)
+            .when_some(confirmation, |this, (text, state)| {
+                this.child(
+                    v_flex()
+                        .gap_2()
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(t!("Dialog.type_to_confirm", text = text).to_string()),
+                        )
+                        .child(Input::new(&state)),
+                )
+            })
             .when(!has_footer, |this| {
                 // Default footer for AlertDialog if user doesn't provide one, with OK and optional Cancel button
                 this.footer(
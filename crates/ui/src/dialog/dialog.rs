@@ -3,15 +3,15 @@ use std::{rc::Rc, sync::LazyLock, time::Duration};
 use gpui::{
     Animation, AnimationExt as _, AnyElement, App, Bounds, BoxShadow, ClickEvent, Edges,
     FocusHandle, Hsla, InteractiveElement, IntoElement, KeyBinding, MouseButton, ParentElement,
-    Pixels, Point, RenderOnce, Role, SharedString, StatefulInteractiveElement as _, StyleRefinement,
-    Styled, Window, WindowControlArea, actions, anchored, div, hsla, point, prelude::FluentBuilder,
-    px,
+    Pixels, Point, RenderOnce, Role, SharedString, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Window, WindowControlArea, actions, anchored, div, hsla, point,
+    prelude::FluentBuilder, px,
 };
 use rust_i18n::t;
 
 use crate::{
-    ActiveTheme as _, FocusTrapElement as _, IconName, Root, Sizable as _, StyledExt,
-    TITLE_BAR_HEIGHT, WindowExt as _,
+    ActiveTheme as _, Disableable as _, FocusTrapElement as _, IconName, Root, Sizable as _,
+    StyledExt, TITLE_BAR_HEIGHT, WindowExt as _,
     animation::cubic_bezier,
     button::{Button, ButtonVariant, ButtonVariants as _},
     dialog::{DialogContent, DialogTitle},
@@ -32,14 +32,23 @@ pub(crate) fn init(cx: &mut App) {
     ]);
 }
 
+/// The footer buttons of the dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogButton {
+    Ok,
+    Cancel,
+}
+
 /// Dialog button props.
 #[derive(Clone)]
 pub struct DialogButtonProps {
     pub(crate) ok_text: Option<SharedString>,
     pub(crate) ok_variant: ButtonVariant,
+    pub(crate) ok_disabled: bool,
     pub(crate) cancel_text: Option<SharedString>,
     pub(crate) cancel_variant: ButtonVariant,
     pub(crate) show_cancel: bool,
+    pub(crate) default_button: Option<DialogButton>,
     pub(crate) on_ok: Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) -> bool + 'static>,
     pub(crate) on_cancel: Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) -> bool + 'static>,
     pub(crate) on_close: Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>,
@@ -50,9 +59,11 @@ impl Default for DialogButtonProps {
         Self {
             ok_text: None,
             ok_variant: ButtonVariant::Primary,
+            ok_disabled: false,
             cancel_text: None,
             cancel_variant: ButtonVariant::default(),
             show_cancel: false,
+            default_button: None,
             on_ok: Rc::new(|_, _, _| true),
             on_cancel: Rc::new(|_, _, _| true),
             on_close: Rc::new(|_, _, _| {}),
@@ -91,6 +102,25 @@ impl DialogButtonProps {
         self
     }
 
+    /// Sets the default button of the dialog.
+    ///
+    /// The default button receives the initial focus when the dialog is opened,
+    /// and is triggered by the `enter` key.
+    ///
+    /// If not set, the OK button is the default.
+    pub fn default_button(mut self, button: DialogButton) -> Self {
+        self.default_button = Some(button);
+        self
+    }
+
+    /// Returns the resolved default button, see [`Self::default_button`].
+    pub(crate) fn resolved_default_button(&self) -> DialogButton {
+        match self.default_button {
+            Some(DialogButton::Cancel) if self.show_cancel => DialogButton::Cancel,
+            _ => DialogButton::Ok,
+        }
+    }
+
     /// Sets the callback for when the dialog is has been confirmed.
     ///
     /// The callback should return `true` to close the dialog, if return `false` the dialog will not be closed.
@@ -122,10 +152,15 @@ impl DialogButtonProps {
             .clone()
             .unwrap_or_else(|| t!("Dialog.ok").into());
         let ok_variant = self.ok_variant;
+        // Only focus the OK button when it is explicitly set as default,
+        // otherwise keep the focus on the dialog as before.
+        let autofocus = self.default_button == Some(DialogButton::Ok) && !self.ok_disabled;
 
         Button::new("ok")
             .label(ok_text)
             .with_variant(ok_variant)
+            .disabled(self.ok_disabled)
+            .autofocus(autofocus)
             .on_click({
                 let on_ok = on_ok.clone();
                 let on_close = on_close.clone();
@@ -148,10 +183,12 @@ impl DialogButtonProps {
             .clone()
             .unwrap_or_else(|| t!("Dialog.cancel").into());
         let cancel_variant = self.cancel_variant;
+        let autofocus = self.resolved_default_button() == DialogButton::Cancel;
 
         Button::new("cancel")
             .label(cancel_text)
             .with_variant(cancel_variant)
+            .autofocus(autofocus)
             .on_click({
                 let on_cancel = on_cancel.clone();
                 let on_close = on_close.clone();
@@ -454,6 +491,7 @@ impl RenderOnce for Dialog {
         let on_close = self.button_props.on_close.clone();
         let on_ok = self.button_props.on_ok.clone();
         let on_cancel = self.button_props.on_cancel.clone();
+        let default_button = self.button_props.resolved_default_button();
 
         let window_paddings = crate::window_border::window_paddings(window);
         let view_size = window.viewport_size()
@@ -563,9 +601,20 @@ impl RenderOnce for Dialog {
                                 })
                                 .on_action({
                                     let on_ok = on_ok.clone();
+                                    let on_cancel = on_cancel.clone();
                                     let on_close = on_close.clone();
                                     move |_: &ConfirmDialog, window, cx| {
-                                        if on_ok(&ClickEvent::default(), window, cx) {
+                                        // The `enter` key triggers the default button, so it
+                                        // will not confirm a destructive action by accident.
+                                        let confirmed = match default_button {
+                                            DialogButton::Ok => {
+                                                on_ok(&ClickEvent::default(), window, cx)
+                                            }
+                                            DialogButton::Cancel => {
+                                                on_cancel(&ClickEvent::default(), window, cx)
+                                            }
+                                        };
+                                        if confirmed {
                                             Self::defer_close_dialog(window, cx);
                                             on_close(&ClickEvent::default(), window, cx);
                                         }
@@ -676,3 +725,29 @@ impl RenderOnce for Dialog {
             .into_any_element()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolved_default_button() {
+        let props = DialogButtonProps::default();
+        assert_eq!(props.resolved_default_button(), DialogButton::Ok);
+
+        // A Danger OK button does not change the default button.
+        let props = DialogButtonProps::default()
+            .show_cancel(true)
+            .ok_variant(ButtonVariant::Danger);
+        assert_eq!(props.resolved_default_button(), DialogButton::Ok);
+
+        let props = DialogButtonProps::default()
+            .show_cancel(true)
+            .default_button(DialogButton::Cancel);
+        assert_eq!(props.resolved_default_button(), DialogButton::Cancel);
+
+        // Without the Cancel button, the OK button is always the default.
+        let props = DialogButtonProps::default().default_button(DialogButton::Cancel);
+        assert_eq!(props.resolved_default_button(), DialogButton::Ok);
+    }
+}
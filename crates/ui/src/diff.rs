@@ -0,0 +1,616 @@
+//! Line and word diff of texts.
+//!
+//! The lines are diffed by the [Myers algorithm], and the changed words of the modified
+//! lines are refined by another pass over the words, e.g. to highlight the changes in
+//! a side-by-side view.
+//!
+//! ```
+//! use gpui_component::diff::{DiffOptions, HunkKind, diff_lines};
+//!
+//! let old = "fn main() {\n    println!(\"Hello\");\n}";
+//! let new = "fn main() {\n    println!(\"Hello, world\");\n}";
+//!
+//! let hunks = diff_lines(old, new, &DiffOptions::default());
+//! assert_eq!(hunks.len(), 1);
+//! assert_eq!(hunks[0].kind(), HunkKind::Modified);
+//! assert_eq!(hunks[0].old_rows, 1..2);
+//! assert_eq!(&new[hunks[0].new_changes[0].clone()], ", world");
+//! ```
+//!
+//! [Myers algorithm]: http://www.xmailserver.org/diff2.pdf
+use std::{borrow::Cow, ops::Range};
+
+/// Skip the word refinement of a hunk with more words than this, to keep the diff fast.
+const MAX_WORD_DIFF_TOKENS: usize = 5000;
+
+/// The kind of a [`DiffOp`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffTag {
+    /// The items are the same in both sides.
+    Equal,
+    /// The items are removed from the old side.
+    Delete,
+    /// The items are added to the new side.
+    Insert,
+}
+
+/// A run of the same [`DiffTag`] returned by [`diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiffOp {
+    pub tag: DiffTag,
+    /// The range of the items in the old side, empty for [`DiffTag::Insert`].
+    pub old: Range<usize>,
+    /// The range of the items in the new side, empty for [`DiffTag::Delete`].
+    pub new: Range<usize>,
+}
+
+/// How to compare the whitespaces, like the options of `git diff`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Whitespace {
+    /// Compare the whitespaces exactly.
+    #[default]
+    Exact,
+    /// Ignore the whitespaces at the end of the lines, like `--ignore-space-at-eol`.
+    IgnoreTrailing,
+    /// Ignore the changes in the amount of whitespaces, like `--ignore-space-change`.
+    IgnoreChange,
+    /// Ignore all the whitespaces, like `--ignore-all-space`.
+    IgnoreAll,
+}
+
+/// Options of [`diff_lines`] and [`diff_words`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffOptions {
+    whitespace: Whitespace,
+    word_diff: bool,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            whitespace: Whitespace::Exact,
+            word_diff: true,
+        }
+    }
+}
+
+impl DiffOptions {
+    /// Set how to compare the whitespaces, default is [`Whitespace::Exact`].
+    pub fn whitespace(mut self, whitespace: Whitespace) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Set whether to refine the modified lines to the changed words, default is true.
+    pub fn word_diff(mut self, word_diff: bool) -> Self {
+        self.word_diff = word_diff;
+        self
+    }
+}
+
+/// The kind of a [`Hunk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HunkKind {
+    /// The lines are added to the new text.
+    Added,
+    /// The lines are removed from the old text.
+    Deleted,
+    /// The lines of the old text are replaced by the lines of the new text.
+    Modified,
+}
+
+/// A changed block of lines returned by [`diff_lines`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hunk {
+    /// The rows of the old text, empty for the [`HunkKind::Added`] hunk.
+    pub old_rows: Range<usize>,
+    /// The rows of the new text, empty for the [`HunkKind::Deleted`] hunk.
+    pub new_rows: Range<usize>,
+    /// The byte ranges of the changed words in the old text.
+    ///
+    /// Only for the [`HunkKind::Modified`] hunk when the word diff is enabled.
+    pub old_changes: Vec<Range<usize>>,
+    /// The byte ranges of the changed words in the new text.
+    ///
+    /// Only for the [`HunkKind::Modified`] hunk when the word diff is enabled.
+    pub new_changes: Vec<Range<usize>>,
+}
+
+impl Hunk {
+    /// Returns the kind of the hunk.
+    pub fn kind(&self) -> HunkKind {
+        if self.old_rows.is_empty() {
+            HunkKind::Added
+        } else if self.new_rows.is_empty() {
+            HunkKind::Deleted
+        } else {
+            HunkKind::Modified
+        }
+    }
+}
+
+/// Diff two slices by the Myers algorithm, returns the runs of the equal and changed items.
+///
+/// The adjacent [`DiffTag::Delete`] and [`DiffTag::Insert`] runs are ordered by delete first.
+pub fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut ops = Vec::new();
+    push_op(&mut ops, DiffTag::Equal, 0..prefix, 0..prefix);
+
+    let (mut old_ix, mut new_ix) = (prefix, prefix);
+    let steps = myers(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+    for tag in steps {
+        match tag {
+            DiffTag::Equal => {
+                push_op(&mut ops, tag, old_ix..old_ix + 1, new_ix..new_ix + 1);
+                old_ix += 1;
+                new_ix += 1;
+            }
+            DiffTag::Delete => {
+                push_op(&mut ops, tag, old_ix..old_ix + 1, new_ix..new_ix);
+                old_ix += 1;
+            }
+            DiffTag::Insert => {
+                push_op(&mut ops, tag, old_ix..old_ix, new_ix..new_ix + 1);
+                new_ix += 1;
+            }
+        }
+    }
+
+    push_op(
+        &mut ops,
+        DiffTag::Equal,
+        old_ix..old.len(),
+        new_ix..new.len(),
+    );
+    ops
+}
+
+/// Append the op to the ops, merge into the last one if it has the same tag.
+fn push_op(ops: &mut Vec<DiffOp>, tag: DiffTag, old: Range<usize>, new: Range<usize>) {
+    if old.is_empty() && new.is_empty() {
+        return;
+    }
+
+    if let Some(last) = ops.last_mut()
+        && last.tag == tag
+    {
+        last.old.end = old.end;
+        last.new.end = new.end;
+        return;
+    }
+
+    ops.push(DiffOp { tag, old, new });
+}
+
+/// Returns the shortest edit script of one step per item.
+fn myers<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffTag> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    if n == 0 || m == 0 {
+        let tag = if n == 0 {
+            DiffTag::Insert
+        } else {
+            DiffTag::Delete
+        };
+        return vec![tag; (n + m) as usize];
+    }
+
+    let max = n + m;
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    let mut trace = Vec::new();
+
+    'outer: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let ix = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[ix - 1] < v[ix + 1]) {
+                v[ix + 1]
+            } else {
+                v[ix - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[ix] = x;
+            if x >= n && y >= m {
+                break 'outer;
+            }
+        }
+    }
+
+    // Walk back from the end to find the path.
+    let mut steps = Vec::with_capacity((n + m) as usize);
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let ix = (k + offset) as usize;
+        let prev_k = if k == -d || (k != d && v[ix - 1] < v[ix + 1]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[(prev_k + offset) as usize];
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            steps.push(DiffTag::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            steps.push(if x == prev_x {
+                DiffTag::Insert
+            } else {
+                DiffTag::Delete
+            });
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    steps.reverse();
+    steps
+}
+
+/// Diff two texts by lines, returns the changed hunks.
+///
+/// The lines are split by `\n`, the same as the rows of the editor, and a `\r` at the end
+/// of the line is ignored.
+pub fn diff_lines(old: &str, new: &str, options: &DiffOptions) -> Vec<Hunk> {
+    let old_lines = line_ranges(old);
+    let new_lines = line_ranges(new);
+    let old_keys = old_lines
+        .iter()
+        .map(|range| line_key(&old[range.clone()], options.whitespace))
+        .collect::<Vec<_>>();
+    let new_keys = new_lines
+        .iter()
+        .map(|range| line_key(&new[range.clone()], options.whitespace))
+        .collect::<Vec<_>>();
+
+    let ops = diff(&old_keys, &new_keys);
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for op in ops {
+        if op.tag == DiffTag::Equal {
+            continue;
+        }
+
+        // Merge the delete and the followed insert into a modified hunk.
+        if let Some(last) = hunks.last_mut()
+            && last.old_rows.end == op.old.start
+            && last.new_rows.end == op.new.start
+        {
+            last.old_rows.end = op.old.end;
+            last.new_rows.end = op.new.end;
+            continue;
+        }
+
+        hunks.push(Hunk {
+            old_rows: op.old,
+            new_rows: op.new,
+            old_changes: vec![],
+            new_changes: vec![],
+        });
+    }
+
+    if options.word_diff {
+        for hunk in hunks.iter_mut() {
+            if hunk.kind() != HunkKind::Modified {
+                continue;
+            }
+
+            let old_range =
+                old_lines[hunk.old_rows.start].start..old_lines[hunk.old_rows.end - 1].end;
+            let new_range =
+                new_lines[hunk.new_rows.start].start..new_lines[hunk.new_rows.end - 1].end;
+            let (old_changes, new_changes) =
+                diff_words(&old[old_range.clone()], &new[new_range.clone()], options);
+            hunk.old_changes = offset_ranges(old_changes, old_range.start);
+            hunk.new_changes = offset_ranges(new_changes, new_range.start);
+        }
+    }
+
+    hunks
+}
+
+/// Diff two texts by words, returns the byte ranges of the changed words in the old and the new text.
+///
+/// A word is a run of the alphanumeric chars, a run of the whitespaces or a single other char.
+/// The adjacent changed words are merged into one range.
+pub fn diff_words(
+    old: &str,
+    new: &str,
+    options: &DiffOptions,
+) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let old_words = word_ranges(old, options.whitespace);
+    let new_words = word_ranges(new, options.whitespace);
+    if old_words.len() + new_words.len() > MAX_WORD_DIFF_TOKENS {
+        return (vec![], vec![]);
+    }
+
+    let old_keys = old_words
+        .iter()
+        .map(|range| word_key(&old[range.clone()], options.whitespace))
+        .collect::<Vec<_>>();
+    let new_keys = new_words
+        .iter()
+        .map(|range| word_key(&new[range.clone()], options.whitespace))
+        .collect::<Vec<_>>();
+
+    let mut old_changes = vec![];
+    let mut new_changes = vec![];
+    for op in diff(&old_keys, &new_keys) {
+        match op.tag {
+            DiffTag::Equal => {}
+            DiffTag::Delete => push_range(&mut old_changes, &old_words[op.old]),
+            DiffTag::Insert => push_range(&mut new_changes, &new_words[op.new]),
+        }
+    }
+
+    (old_changes, new_changes)
+}
+
+/// Returns the byte ranges of the lines, excluding the `\n`.
+fn line_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut start = 0;
+    let mut ranges = text
+        .match_indices('\n')
+        .map(|(ix, _)| {
+            let range = start..ix;
+            start = ix + 1;
+            range
+        })
+        .collect::<Vec<_>>();
+    ranges.push(start..text.len());
+    ranges
+}
+
+fn line_key(line: &str, whitespace: Whitespace) -> Cow<'_, str> {
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    match whitespace {
+        Whitespace::Exact => Cow::Borrowed(line),
+        Whitespace::IgnoreTrailing => Cow::Borrowed(line.trim_end()),
+        Whitespace::IgnoreChange => {
+            let mut key = String::with_capacity(line.len());
+            for (ix, part) in line.split_whitespace().enumerate() {
+                if ix > 0 || line.starts_with(char::is_whitespace) {
+                    key.push(' ');
+                }
+                key.push_str(part);
+            }
+            Cow::Owned(key)
+        }
+        Whitespace::IgnoreAll => Cow::Owned(line.split_whitespace().collect()),
+    }
+}
+
+/// Returns the byte ranges of the words, the whitespaces are skipped for [`Whitespace::IgnoreAll`].
+fn word_ranges(text: &str, whitespace: Whitespace) -> Vec<Range<usize>> {
+    #[derive(PartialEq)]
+    enum CharKind {
+        Word,
+        Whitespace,
+        Other,
+    }
+
+    fn char_kind(c: char) -> CharKind {
+        if c.is_alphanumeric() || c == '_' {
+            CharKind::Word
+        } else if c.is_whitespace() {
+            CharKind::Whitespace
+        } else {
+            CharKind::Other
+        }
+    }
+
+    let mut ranges: Vec<Range<usize>> = vec![];
+    let mut last_kind = None;
+    for (ix, c) in text.char_indices() {
+        let kind = char_kind(c);
+        let end = ix + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if kind != CharKind::Other && last_kind.as_ref() == Some(&kind) => {
+                last.end = end;
+            }
+            _ => ranges.push(ix..end),
+        }
+        last_kind = Some(kind);
+    }
+
+    if whitespace == Whitespace::IgnoreAll {
+        ranges.retain(|range| !text[range.clone()].starts_with(char::is_whitespace));
+    }
+    ranges
+}
+
+fn word_key(word: &str, whitespace: Whitespace) -> &str {
+    let is_whitespace = word.starts_with(char::is_whitespace);
+    match whitespace {
+        Whitespace::IgnoreChange | Whitespace::IgnoreTrailing if is_whitespace => " ",
+        _ => word,
+    }
+}
+
+/// Append the range of the words, merge into the last range if they are adjacent.
+fn push_range(ranges: &mut Vec<Range<usize>>, words: &[Range<usize>]) {
+    let (Some(first), Some(last)) = (words.first(), words.last()) else {
+        return;
+    };
+
+    match ranges.last_mut() {
+        Some(prev) if prev.end == first.start => prev.end = last.end,
+        _ => ranges.push(first.start..last.end),
+    }
+}
+
+fn offset_ranges(ranges: Vec<Range<usize>>, offset: usize) -> Vec<Range<usize>> {
+    ranges
+        .into_iter()
+        .map(|range| range.start + offset..range.end + offset)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply<T: Clone>(old: &[T], new: &[T], ops: &[DiffOp]) -> Vec<T> {
+        ops.iter()
+            .flat_map(|op| match op.tag {
+                DiffTag::Equal => old[op.old.clone()].to_vec(),
+                DiffTag::Delete => vec![],
+                DiffTag::Insert => new[op.new.clone()].to_vec(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = "ABCABBA".chars().collect::<Vec<_>>();
+        let new = "CBABAC".chars().collect::<Vec<_>>();
+        let ops = diff(&old, &new);
+        assert_eq!(apply(&old, &new, &ops), new);
+        // The shortest edit script of the paper has 5 edits.
+        let edits = ops
+            .iter()
+            .filter(|op| op.tag != DiffTag::Equal)
+            .map(|op| op.old.len() + op.new.len())
+            .sum::<usize>();
+        assert_eq!(edits, 5);
+
+        assert_eq!(diff::<char>(&[], &[]), vec![]);
+        assert_eq!(
+            diff(&['a'], &[]),
+            vec![DiffOp {
+                tag: DiffTag::Delete,
+                old: 0..1,
+                new: 0..0
+            }]
+        );
+        assert_eq!(
+            diff(&['a', 'b'], &['a', 'c', 'b']),
+            vec![
+                DiffOp {
+                    tag: DiffTag::Equal,
+                    old: 0..1,
+                    new: 0..1
+                },
+                DiffOp {
+                    tag: DiffTag::Insert,
+                    old: 1..1,
+                    new: 1..2
+                },
+                DiffOp {
+                    tag: DiffTag::Equal,
+                    old: 1..2,
+                    new: 2..3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "a\nb\nc\nd";
+        let new = "a\nB\nc\nd\ne";
+        let hunks = diff_lines(old, new, &DiffOptions::default());
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].kind(), HunkKind::Modified);
+        assert_eq!(
+            (hunks[0].old_rows.clone(), hunks[0].new_rows.clone()),
+            (1..2, 1..2)
+        );
+        assert_eq!(hunks[0].old_changes, vec![2..3]);
+        assert_eq!(hunks[0].new_changes, vec![2..3]);
+        assert_eq!(hunks[1].kind(), HunkKind::Added);
+        assert_eq!(
+            (hunks[1].old_rows.clone(), hunks[1].new_rows.clone()),
+            (4..4, 4..5)
+        );
+
+        let hunks = diff_lines("a\nb\nc", "a\nc", &DiffOptions::default());
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].kind(), HunkKind::Deleted);
+        assert_eq!(hunks[0].old_rows, 1..2);
+
+        let options = DiffOptions::default().word_diff(false);
+        let hunks = diff_lines("let a = 1;", "let b = 2;", &options);
+        assert!(hunks[0].old_changes.is_empty());
+
+        assert!(diff_lines("a\r\nb", "a\nb", &DiffOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lines_whitespace() {
+        let old = "if a {\n    b(1,  2);\n}";
+        let new = "if a {\n\tb(1, 2);  \n}";
+        let hunks =
+            |whitespace| diff_lines(old, new, &DiffOptions::default().whitespace(whitespace)).len();
+        assert_eq!(hunks(Whitespace::Exact), 1);
+        assert_eq!(hunks(Whitespace::IgnoreTrailing), 1);
+        assert_eq!(hunks(Whitespace::IgnoreChange), 0);
+        assert_eq!(hunks(Whitespace::IgnoreAll), 0);
+
+        let hunks = diff_lines(
+            "a b",
+            "ab",
+            &DiffOptions::default().whitespace(Whitespace::IgnoreChange),
+        );
+        assert_eq!(hunks.len(), 1);
+        assert!(
+            diff_lines(
+                "a b",
+                "ab",
+                &DiffOptions::default().whitespace(Whitespace::IgnoreAll)
+            )
+            .is_empty()
+        );
+        assert!(
+            diff_lines(
+                "a ",
+                "a",
+                &DiffOptions::default().whitespace(Whitespace::IgnoreTrailing)
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_diff_words() {
+        let old = "let foo = bar(1);";
+        let new = "let foo_baz = bar(1, 2);";
+        let (old_changes, new_changes) = diff_words(old, new, &DiffOptions::default());
+        assert_eq!(
+            old_changes
+                .iter()
+                .map(|r| &old[r.clone()])
+                .collect::<Vec<_>>(),
+            vec!["foo"]
+        );
+        assert_eq!(
+            new_changes
+                .iter()
+                .map(|r| &new[r.clone()])
+                .collect::<Vec<_>>(),
+            vec!["foo_baz", ", 2"]
+        );
+
+        let options = DiffOptions::default().whitespace(Whitespace::IgnoreChange);
+        assert_eq!(diff_words("a  b", "a b", &options), (vec![], vec![]));
+        let (_, new_changes) = diff_words("你好", "你们好", &DiffOptions::default());
+        assert_eq!(new_changes, vec![0..9]);
+    }
+}
@@ -0,0 +1,274 @@
+//! Drag and drop with typed payloads between components.
+//!
+//! - Use [`DragItem`] as the payload of the drag, it renders a small preview under the mouse.
+//! - Use [`DropTarget`] to accept the payloads of a type, with an optional accept predicate
+//!   and the style to apply while the payload is dragged over.
+//! - Use [`DragDropExt::auto_scroll_on_drag`] to scroll a container when dragging near the edges.
+//!
+//! The payload is matched by the Rust type, so any component can be a drag source or a drop
+//! target for the same type, e.g. drag a [`crate::list::List`] row into a sidebar group.
+use std::rc::Rc;
+
+use gpui::{
+    App, AppContext as _, Context, InteractiveElement, IntoElement, ParentElement as _, Render,
+    SharedString, StatefulInteractiveElement, StyleRefinement, Styled as _, Window, div,
+    prelude::FluentBuilder as _, px,
+};
+
+use crate::{
+    ActiveTheme as _, Icon, Sizable as _, h_flex,
+    scroll::{AutoScroll, ScrollbarHandle},
+};
+
+/// A typed drag payload, with a label and an optional icon to render the drag preview.
+#[derive(Clone)]
+pub struct DragItem<T: Clone + 'static> {
+    value: T,
+    label: SharedString,
+    icon: Option<Icon>,
+}
+
+impl<T: Clone + 'static> DragItem<T> {
+    /// Create a new drag item with the `value` and the `label` to show in the preview.
+    pub fn new(value: T, label: impl Into<SharedString>) -> Self {
+        Self {
+            value,
+            label: label.into(),
+            icon: None,
+        }
+    }
+
+    /// Set the icon to show in the preview.
+    pub fn icon(mut self, icon: impl Into<Icon>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Returns the value of the payload.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the label of the payload.
+    pub fn label(&self) -> &SharedString {
+        &self.label
+    }
+}
+
+impl<T: Clone + 'static> Render for DragItem<T> {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .id("drag-item")
+            .cursor_grab()
+            .gap_2()
+            .py_1()
+            .px_3()
+            .max_w(px(240.))
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .text_sm()
+            .border_1()
+            .border_color(cx.theme().drag_border)
+            .rounded(cx.theme().radius)
+            .text_color(cx.theme().popover_foreground)
+            .bg(cx.theme().popover)
+            .shadow_md()
+            .opacity(0.9)
+            .when_some(self.icon.clone(), |this, icon| this.child(icon.small()))
+            .child(div().text_ellipsis().child(self.label.clone()))
+    }
+}
+
+/// A drop target to accept the [`DragItem`] payloads of the type `T`.
+pub struct DropTarget<T: Clone + 'static> {
+    accept: Option<Rc<dyn Fn(&T, &mut Window, &mut App) -> bool>>,
+    hover_style: Option<Rc<dyn Fn(StyleRefinement, &T, &App) -> StyleRefinement>>,
+    on_drop: Option<Rc<dyn Fn(&T, &mut Window, &mut App)>>,
+}
+
+impl<T: Clone + 'static> DropTarget<T> {
+    /// Create a new drop target, it accepts all the payloads of the type `T` by default.
+    pub fn new() -> Self {
+        Self {
+            accept: None,
+            hover_style: None,
+            on_drop: None,
+        }
+    }
+
+    /// Set the predicate to check if the payload can be dropped.
+    ///
+    /// The hover style and the drop listener are skipped for the rejected payloads.
+    pub fn accept(mut self, f: impl Fn(&T, &mut Window, &mut App) -> bool + 'static) -> Self {
+        self.accept = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the style to apply while an accepted payload is dragged over.
+    ///
+    /// Default is the `drop_target` background of the theme.
+    pub fn hover_style(
+        mut self,
+        f: impl Fn(StyleRefinement, &T, &App) -> StyleRefinement + 'static,
+    ) -> Self {
+        self.hover_style = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the listener to call when an accepted payload is dropped.
+    pub fn on_drop(mut self, f: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_drop = Some(Rc::new(f));
+        self
+    }
+}
+
+/// Extension methods for the elements to use the drag and drop.
+pub trait DragDropExt: InteractiveElement + Sized {
+    /// Make the element draggable with the `item` as the payload.
+    fn draggable<T: Clone + 'static>(self, item: DragItem<T>) -> Self
+    where
+        Self: StatefulInteractiveElement,
+    {
+        self.on_drag(item, |item, _, _, cx| {
+            cx.stop_propagation();
+            cx.new(|_| item.clone())
+        })
+    }
+
+    /// Make the element a drop target of the [`DragItem<T>`] payloads.
+    ///
+    /// An element only has one accept predicate, the last one is used if this is
+    /// called multiple times with different types.
+    fn drop_target<T: Clone + 'static>(self, target: DropTarget<T>) -> Self {
+        let DropTarget {
+            accept,
+            hover_style,
+            on_drop,
+        } = target;
+
+        let mut this = self;
+        if let Some(accept) = accept {
+            this = this.can_drop(move |value, window, cx| {
+                value
+                    .downcast_ref::<DragItem<T>>()
+                    .map_or(true, |item| accept(&item.value, window, cx))
+            });
+        }
+
+        this =
+            this.drag_over::<DragItem<T>>(move |style, item, _, cx| match hover_style.as_ref() {
+                Some(hover_style) => hover_style(style, &item.value, cx),
+                None => style.bg(cx.theme().tokens.drop_target),
+            });
+
+        if let Some(on_drop) = on_drop {
+            this = this.on_drop(move |item: &DragItem<T>, window, cx| {
+                on_drop(&item.value, window, cx);
+            });
+        }
+
+        this
+    }
+
+    /// Scroll the `handle` when dragging a [`DragItem<T>`] near the top or bottom edge
+    /// of this element, the element should be the viewport of the scroll handle.
+    fn auto_scroll_on_drag<T: Clone + 'static>(
+        self,
+        handle: &(impl ScrollbarHandle + Clone),
+    ) -> Self {
+        let handle = handle.clone();
+        self.on_drag_move::<DragItem<T>>(move |event, window, _| {
+            let bounds = event.bounds;
+            let position = event.event.position;
+            if !bounds.contains(&position) {
+                return;
+            }
+
+            let Some(delta) = AutoScroll::compute_delta(position.y, bounds) else {
+                return;
+            };
+
+            let max_offset = (handle.content_size().height - bounds.size.height).max(px(0.));
+            let mut offset = handle.offset();
+            offset.y = (offset.y - delta).max(-max_offset).min(px(0.));
+            if offset != handle.offset() {
+                handle.set_offset(offset);
+                window.refresh();
+            }
+        })
+    }
+}
+
+impl<E: InteractiveElement> DragDropExt for E {}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use gpui::{
+        Context, InteractiveElement as _, IntoElement, ParentElement as _, Render, Styled as _,
+        TestAppContext, Window, div, point, px,
+    };
+
+    use super::{DragDropExt as _, DragItem, DropTarget};
+    use crate::test::TestHarness;
+
+    struct DndView {
+        dropped: Rc<RefCell<Vec<(&'static str, usize)>>>,
+    }
+
+    impl Render for DndView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            let target = |name: &'static str, accept: bool| {
+                let dropped = self.dropped.clone();
+                div()
+                    .id(name)
+                    .debug_selector(move || name.into())
+                    .w(px(100.))
+                    .h(px(50.))
+                    .drop_target(
+                        DropTarget::<usize>::new()
+                            .accept(move |_, _, _| accept)
+                            .on_drop(move |value, _, _| dropped.borrow_mut().push((name, *value))),
+                    )
+            };
+
+            div()
+                .size_full()
+                .child(
+                    div()
+                        .id("source")
+                        .debug_selector(|| "source".into())
+                        .w(px(100.))
+                        .h(px(50.))
+                        .draggable(DragItem::new(42usize, "Item")),
+                )
+                .child(target("accepted", true))
+                .child(target("rejected", false))
+        }
+    }
+
+    #[test]
+    fn test_drag_item() {
+        let item = DragItem::new(1, "Item");
+        assert_eq!(*item.value(), 1);
+        assert_eq!(item.label().as_ref(), "Item");
+    }
+
+    #[gpui::test]
+    fn test_drop_target(cx: &mut TestAppContext) {
+        let dropped = Rc::new(RefCell::new(vec![]));
+        let mut harness = TestHarness::new(cx, {
+            let dropped = dropped.clone();
+            move |_, _| DndView { dropped }
+        });
+
+        let source = harness.bounds_of("source").unwrap().center();
+        for name in ["accepted", "rejected"] {
+            let target = harness.bounds_of(name).unwrap().center();
+            harness.drag(&[source, source + point(px(10.), px(10.)), target]);
+        }
+
+        assert_eq!(*dropped.borrow(), vec![("accepted", 42)]);
+    }
+}
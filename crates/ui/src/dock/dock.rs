@@ -1,20 +1,30 @@
 //! Dock is a fixed container that places at left, bottom, right of the Windows.
 
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, sync::Arc, time::Duration};
 
 use gpui::{
-    App, AppContext, Axis, Context, Element, Empty, Entity, IntoElement, MouseMoveEvent,
-    MouseUpEvent, ParentElement as _, Pixels, Point, Render, Style, StyleRefinement, Styled as _,
-    WeakEntity, Window, div, prelude::FluentBuilder as _, px,
+    App, AppContext, Axis, Context, Div, Element, Empty, Entity, InteractiveElement as _,
+    IntoElement, MouseMoveEvent, MouseUpEvent, ParentElement as _, Pixels, Point, Render,
+    SharedString, Style, StyleRefinement, Styled as _, Task, WeakEntity, Window, deferred, div,
+    prelude::FluentBuilder as _, px,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    StyledExt,
+    ActiveTheme as _, Sizable as _, StyledExt,
+    button::{Button, ButtonVariants as _},
+    h_flex,
     resizable::{PANEL_MIN_SIZE, resize_handle},
+    v_flex,
 };
 
-use super::{DockArea, DockItem, PanelView, TabPanel};
+use super::{DockArea, DockItem, PanelView, StackPanel, TabPanel, Tiles};
+
+/// The width (or height for the bottom Dock) of the tool strip of a collapsed Dock.
+const TOOL_STRIP_SIZE: Pixels = px(36.);
+/// The delay to slide out the unpinned Dock when hovering an icon of the tool strip.
+const PEEK_DELAY: Duration = Duration::from_millis(300);
 
 #[derive(Clone)]
 struct ResizePanel;
@@ -25,7 +35,7 @@ impl Render for ResizePanel {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub enum DockPlacement {
     #[serde(rename = "center")]
     Center,
@@ -71,10 +81,17 @@ pub struct Dock {
     pub(super) open: bool,
     /// Whether the Dock is collapsible, default: true
     pub(super) collapsible: bool,
+    /// Whether to show a tool strip of panel icons when the Dock is collapsed, default: false
+    pub(super) tool_strip: bool,
+    /// Whether the Dock is pinned, default: true
+    ///
+    /// The unpinned Dock is collapsed to the tool strip, and slides over the content when open.
+    pub(super) pinned: bool,
 
     // Runtime state
     /// Whether the Dock is resizing
     resizing: bool,
+    _peek_task: Task<()>,
 }
 
 impl Dock {
@@ -99,15 +116,20 @@ impl Dock {
 
         Self::subscribe_panel_events(dock_area.clone(), &panel, window, cx);
 
-        Self {
+        let this = Self {
             placement,
             dock_area,
             panel,
             open: true,
             collapsible: true,
+            tool_strip: false,
+            pinned: true,
             size: px(200.0),
             resizing: false,
-        }
+            _peek_task: Task::ready(()),
+        };
+        this.update_tab_panels_placement(cx);
+        this
     }
 
     pub fn left(
@@ -145,16 +167,45 @@ impl Dock {
         cx.notify();
     }
 
+    /// Set whether to show a tool strip when the Dock is collapsed, default: false
+    ///
+    /// The tool strip is a thin bar of the panel icons (see [`super::Panel::icon`]),
+    /// click an icon to open the Dock with that panel active.
+    pub fn set_tool_strip(&mut self, tool_strip: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.tool_strip = tool_strip;
+        cx.notify();
+    }
+
+    /// Set whether the Dock is pinned, default: true
+    ///
+    /// The unpinned Dock is collapsed to a tool strip of the panel icons (like the auto-hide tool
+    /// windows), click or hover an icon to slide the Dock over the content, and click outside to
+    /// hide it.
+    pub fn set_pinned(&mut self, pinned: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.pinned = pinned;
+        self.set_open(pinned, window, cx);
+    }
+
+    /// Returns true if the Dock is pinned.
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn from_state(
         dock_area: WeakEntity<DockArea>,
         placement: DockPlacement,
         size: Pixels,
         panel: DockItem,
         open: bool,
+        pinned: bool,
+        tool_strip: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
         Self::subscribe_panel_events(dock_area.clone(), &panel, window, cx);
+        // The unpinned Dock is always hidden at the start.
+        let open = open && pinned;
 
         if !open {
             match panel.clone() {
@@ -172,15 +223,20 @@ impl Dock {
             }
         }
 
-        Self {
+        let this = Self {
             placement,
             dock_area,
             panel,
             open,
             size,
             collapsible: true,
+            tool_strip,
+            pinned,
             resizing: false,
-        }
+            _peek_task: Task::ready(()),
+        };
+        this.update_tab_panels_placement(cx);
+        this
     }
 
     fn subscribe_panel_events(
@@ -231,9 +287,16 @@ impl Dock {
 
     pub fn set_panel(&mut self, panel: DockItem, _: &mut Window, cx: &mut Context<Self>) {
         self.panel = panel;
+        self.update_tab_panels_placement(cx);
         cx.notify();
     }
 
+    /// Cache the placement of the Dock in the TabPanels, to avoid to look up the Dock of a
+    /// TabPanel from the DockArea on every render.
+    fn update_tab_panels_placement(&self, cx: &mut App) {
+        set_tab_panels_placement(&self.panel.view(), Some(self.placement), cx);
+    }
+
     pub fn panel(&self) -> &DockItem {
         &self.panel
     }
@@ -278,6 +341,7 @@ impl Dock {
     ) {
         self.panel
             .add_panel(panel, &self.dock_area, None, window, cx);
+        self.update_tab_panels_placement(cx);
         cx.notify();
     }
 
@@ -292,6 +356,167 @@ impl Dock {
         cx.notify();
     }
 
+    fn render_tool_strip(&mut self, _: &mut Window, cx: &mut Context<Self>) -> Div {
+        let mut tab_panels = vec![];
+        collect_tab_panels(&self.panel.view(), cx, &mut tab_panels);
+
+        let mut buttons = vec![];
+        for tab_panel in tab_panels {
+            for (ix, panel) in tab_panel.read(cx).panels.iter().enumerate() {
+                if !panel.visible(cx) {
+                    continue;
+                }
+
+                let tab_name = panel.tab_name(cx);
+                let button = Button::new(SharedString::from(format!(
+                    "tool-strip:{}",
+                    panel.panel_id(cx)
+                )))
+                .ghost()
+                .small()
+                .tab_stop(false)
+                .map(|this| match panel.icon(cx) {
+                    Some(icon) => this.icon(icon),
+                    None => this.label(
+                        tab_name
+                            .as_ref()
+                            .and_then(|name| name.chars().next())
+                            .map(|c| c.to_uppercase().to_string())
+                            .unwrap_or_default(),
+                    ),
+                })
+                .when_some(tab_name, |this, tab_name| this.tooltip(tab_name))
+                .when(!self.pinned, |this| {
+                    this.on_hover(cx.listener({
+                        let tab_panel = tab_panel.clone();
+                        move |this, hovered: &bool, window, cx| {
+                            this.peek_on_hover(*hovered, tab_panel.clone(), ix, window, cx);
+                        }
+                    }))
+                })
+                .on_click(cx.listener({
+                    let tab_panel = tab_panel.clone();
+                    move |this, _, window, cx| {
+                        this.open_panel(&tab_panel, ix, window, cx);
+                    }
+                }));
+
+                buttons.push(button);
+            }
+        }
+
+        match self.placement {
+            DockPlacement::Bottom => h_flex().w_full().h(TOOL_STRIP_SIZE).px_1().border_t_1(),
+            DockPlacement::Left => v_flex().h_full().w(TOOL_STRIP_SIZE).py_1().border_r_1(),
+            DockPlacement::Right => v_flex().h_full().w(TOOL_STRIP_SIZE).py_1().border_l_1(),
+            DockPlacement::Center => unreachable!(),
+        }
+        .flex_none()
+        .items_center()
+        .gap_1()
+        .bg(cx.theme().tokens.tab_bar)
+        .border_color(cx.theme().border)
+        .children(buttons)
+    }
+
+    /// Open the Dock with the panel at `ix` of the `tab_panel` active.
+    fn open_panel(
+        &mut self,
+        tab_panel: &Entity<TabPanel>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self._peek_task = Task::ready(());
+        tab_panel.update(cx, |tab_panel, cx| {
+            tab_panel.set_active_ix(ix, window, cx);
+        });
+        if !self.open {
+            self.set_open(true, window, cx);
+        }
+    }
+
+    /// Slide out the unpinned Dock after hovering the tool strip icon for a while.
+    fn peek_on_hover(
+        &mut self,
+        hovered: bool,
+        tab_panel: Entity<TabPanel>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !hovered {
+            self._peek_task = Task::ready(());
+            return;
+        }
+
+        self._peek_task = cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(PEEK_DELAY).await;
+            _ = this.update_in(cx, |this, window, cx| {
+                this.open_panel(&tab_panel, ix, window, cx);
+            });
+        });
+    }
+
+    /// Render the unpinned Dock, the panel slides over the content next to the tool strip.
+    fn render_auto_hide(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Div {
+        let tool_strip = self.render_tool_strip(window, cx);
+        if !self.open {
+            return tool_strip;
+        }
+
+        let panel = self
+            .render_panel(window, cx)
+            .occlude()
+            .bg(cx.theme().background)
+            .border_color(cx.theme().border)
+            .shadow_lg()
+            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                if this.open && !this.resizing {
+                    this.set_open(false, window, cx);
+                }
+            }))
+            .map(|this| match self.placement {
+                DockPlacement::Left => this.absolute().top_0().left(TOOL_STRIP_SIZE).border_r_1(),
+                DockPlacement::Right => this.absolute().top_0().right(TOOL_STRIP_SIZE).border_l_1(),
+                DockPlacement::Bottom => this
+                    .absolute()
+                    .left_0()
+                    .bottom(TOOL_STRIP_SIZE)
+                    .border_t_1(),
+                DockPlacement::Center => unreachable!(),
+            });
+
+        tool_strip
+            .relative()
+            .child(deferred(panel).with_priority(1))
+    }
+
+    /// Render the panel of the Dock with the resize handle.
+    fn render_panel(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Div {
+        let cache_style = StyleRefinement::default().absolute().size_full();
+
+        div()
+            .relative()
+            .overflow_hidden()
+            .map(|this| match self.placement {
+                DockPlacement::Left | DockPlacement::Right => this.h_flex().h_full().w(self.size),
+                DockPlacement::Bottom => this.w_full().h(self.size),
+                DockPlacement::Center => unreachable!(),
+            })
+            .map(|this| match &self.panel {
+                DockItem::Split { view, .. } => this.child(view.clone()),
+                DockItem::Tabs { view, .. } => this.child(view.clone()),
+                DockItem::Panel { view, .. } => this.child(view.clone().view().cached(cache_style)),
+                // Not support to render Tiles and Tile into Dock
+                DockItem::Tiles { .. } => this,
+            })
+            .child(self.render_resize_handle(window, cx))
+            .child(DockElement {
+                view: cx.entity().clone(),
+            })
+    }
+
     fn render_resize_handle(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let axis = self.placement.axis();
         let view = cx.entity().clone();
@@ -333,7 +558,7 @@ impl Dock {
         if let Some(left_dock) = &dock_area.left_dock {
             if left_dock.entity_id() != cx.entity().entity_id() {
                 let left_dock_read = left_dock.read(cx);
-                if left_dock_read.is_open() {
+                if left_dock_read.is_open() && left_dock_read.is_pinned() {
                     left_dock_size = left_dock_read.size;
                 }
             }
@@ -343,18 +568,20 @@ impl Dock {
         if let Some(right_dock) = &dock_area.right_dock {
             if right_dock.entity_id() != cx.entity().entity_id() {
                 let right_dock_read = right_dock.read(cx);
-                if right_dock_read.is_open() {
+                if right_dock_read.is_open() && right_dock_read.is_pinned() {
                     right_dock_size = right_dock_read.size;
                 }
             }
         }
 
+        // The unpinned Dock is placed next to the tool strip.
+        let offset = if self.pinned { px(0.) } else { TOOL_STRIP_SIZE };
         let size = match self.placement {
             DockPlacement::Left => mouse_position.x - area_bounds.left(),
             DockPlacement::Right => area_bounds.right() - mouse_position.x,
             DockPlacement::Bottom => area_bounds.bottom() - mouse_position.y,
             DockPlacement::Center => unreachable!(),
-        };
+        } - offset;
         match self.placement {
             DockPlacement::Left => {
                 let max_size =
@@ -383,35 +610,60 @@ impl Dock {
 
 impl Render for Dock {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl gpui::IntoElement {
+        if !self.pinned {
+            return self.render_auto_hide(window, cx);
+        }
+
+        if !self.open && self.tool_strip {
+            return self.render_tool_strip(window, cx);
+        }
+
         if !self.open && !self.placement.is_bottom() {
             return div();
         }
 
-        let cache_style = StyleRefinement::default().absolute().size_full();
-
-        div()
-            .relative()
-            .overflow_hidden()
-            .map(|this| match self.placement {
-                DockPlacement::Left | DockPlacement::Right => this.h_flex().h_full().w(self.size),
-                DockPlacement::Bottom => this.w_full().h(self.size),
-                DockPlacement::Center => unreachable!(),
-            })
+        self.render_panel(window, cx)
             // Bottom Dock should keep the title bar, then user can click the Toggle button
             .when(!self.open && self.placement.is_bottom(), |this| {
                 this.h(px(29.))
             })
-            .map(|this| match &self.panel {
-                DockItem::Split { view, .. } => this.child(view.clone()),
-                DockItem::Tabs { view, .. } => this.child(view.clone()),
-                DockItem::Panel { view, .. } => this.child(view.clone().view().cached(cache_style)),
-                // Not support to render Tiles and Tile into Dock
-                DockItem::Tiles { .. } => this,
-            })
-            .child(self.render_resize_handle(window, cx))
-            .child(DockElement {
-                view: cx.entity().clone(),
-            })
+    }
+}
+
+/// Collect all the TabPanels in the `view`, from the left-top to the right-bottom.
+pub(super) fn collect_tab_panels(
+    view: &Arc<dyn PanelView>,
+    cx: &App,
+    tab_panels: &mut Vec<Entity<TabPanel>>,
+) {
+    let view = view.view();
+    if let Ok(tab_panel) = view.clone().downcast::<TabPanel>() {
+        tab_panels.push(tab_panel);
+    } else if let Ok(stack_panel) = view.clone().downcast::<StackPanel>() {
+        for panel in stack_panel.read(cx).panels.iter() {
+            collect_tab_panels(panel, cx, tab_panels);
+        }
+    } else if let Ok(tiles) = view.downcast::<Tiles>() {
+        for item in tiles.read(cx).panels.iter() {
+            collect_tab_panels(&item.panel, cx, tab_panels);
+        }
+    }
+}
+
+/// Set the cached placement of the Dock in all the TabPanels in the `view`.
+///
+/// Use `None` when the TabPanels are moved out of a Dock, e.g. to the center or a floating window.
+pub(super) fn set_tab_panels_placement(
+    view: &Arc<dyn PanelView>,
+    placement: Option<DockPlacement>,
+    cx: &mut App,
+) {
+    let mut tab_panels = vec![];
+    collect_tab_panels(view, cx, &mut tab_panels);
+    for tab_panel in tab_panels {
+        tab_panel.update(cx, |tab_panel, _| {
+            tab_panel.dock_placement = placement;
+        });
     }
 }
 
@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use gpui::{
+    AnyWindowHandle, App, Bounds, Context, DragMoveEvent, Entity, InteractiveElement as _,
+    IntoElement, MouseButton, MouseUpEvent, ParentElement as _, Pixels, Point, Render, Size,
+    Styled as _, Subscription, WeakEntity, Window, div, px,
+};
+
+use crate::{ActiveTheme as _, Root};
+
+use super::{
+    DockArea, DockEvent, DockPlacement, DragPanel, Panel as _, PanelEvent, PanelState, PanelView,
+    TabPanel, can_close_panels,
+};
+
+/// The default size of the floating window for a torn out panel.
+pub(super) const FLOATING_WINDOW_SIZE: Size<Pixels> = Size {
+    width: px(480.),
+    height: px(360.),
+};
+
+/// The root view of a floating window that holds the panels torn out from the [`DockArea`].
+///
+/// Drag a tab back over the window of the dock area, or close the floating window to dock the panels back.
+pub struct FloatingWindow {
+    dock_area: WeakEntity<DockArea>,
+    /// The window of the dock area.
+    dock_window: AnyWindowHandle,
+    /// The floating window self.
+    pub(super) window: AnyWindowHandle,
+    pub(super) tab_panel: Entity<TabPanel>,
+    /// The TabPanel the panel was torn out from, to dock the panel back into it.
+    origin: Option<WeakEntity<TabPanel>>,
+    /// The dock the panel was torn out from, to dock the panel back if the `origin` is gone.
+    placement: Option<DockPlacement>,
+    bounds: Bounds<Pixels>,
+    /// The dragging tab outside the window, and the mouse position on the screen.
+    drag_out: Option<(DragPanel, Point<Pixels>)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl FloatingWindow {
+    pub(super) fn new(
+        dock_area: WeakEntity<DockArea>,
+        dock_window: AnyWindowHandle,
+        tab_panel: Entity<TabPanel>,
+        origin: Option<WeakEntity<TabPanel>>,
+        placement: Option<DockPlacement>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        tab_panel.update(cx, |tab_panel, _| {
+            tab_panel.floating = true;
+            tab_panel.dock_placement = None;
+        });
+        match tab_panel.read(cx).active_panel(cx) {
+            Some(panel) => panel.focus_handle(cx).focus(window, cx),
+            // All panels of the restored state have been removed.
+            None => window.defer(cx, |window, _| window.remove_window()),
+        }
+
+        let _subscriptions = vec![
+            cx.observe_window_bounds(window, |this, window, cx| {
+                this.bounds = window.bounds();
+                this.emit_layout_changed(cx);
+            }),
+            cx.subscribe_in(
+                &tab_panel,
+                window,
+                |this, tab_panel, event: &PanelEvent, window, cx| {
+                    if !matches!(event, PanelEvent::LayoutChanged) {
+                        return;
+                    }
+
+                    // All panels are closed or docked back.
+                    if tab_panel.read(cx).panels.is_empty() {
+                        window.remove_window();
+                    }
+                    this.emit_layout_changed(cx);
+                },
+            ),
+        ];
+
+        let view = cx.entity().downgrade();
+        window.on_window_should_close(cx, move |window, cx| {
+            view.update(cx, |this, cx| this.on_should_close(window, cx))
+                .unwrap_or(true)
+        });
+
+        Self {
+            dock_area,
+            dock_window,
+            window: window.window_handle(),
+            tab_panel,
+            origin,
+            placement,
+            bounds: window.bounds(),
+            drag_out: None,
+            _subscriptions,
+        }
+    }
+
+    /// Dump the panels in the floating window to a [`PanelState`] with the window bounds.
+    pub(super) fn dump(&self, cx: &App) -> PanelState {
+        let mut state = self.tab_panel.read(cx).dump(cx);
+        state.floating_bounds = Some(self.bounds);
+        state.floating_placement = self.placement;
+        state
+    }
+
+    fn emit_layout_changed(&self, cx: &mut App) {
+        _ = self
+            .dock_area
+            .update(cx, |_, cx| cx.emit(DockEvent::LayoutChanged));
+    }
+
+    /// Move the panel back to the dock area.
+    ///
+    /// The panel is kept in the floating window if the dock area or its window has been released.
+    fn dock_back(
+        &mut self,
+        panel: Arc<dyn PanelView>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<()> {
+        let Some(dock_area) = self.dock_area.upgrade() else {
+            return Err(anyhow!("the dock area has been released"));
+        };
+
+        self.tab_panel.update(cx, |tab_panel, cx| {
+            tab_panel.remove_panel(panel.clone(), window, cx);
+        });
+
+        let origin = self.origin.clone();
+        let placement = self.placement;
+        let result = self.dock_window.update(cx, |_, window, cx| {
+            dock_area.update(cx, |dock_area, cx| {
+                dock_area.dock_panel_back(panel.clone(), origin, placement, window, cx);
+            });
+        });
+        if result.is_err() {
+            self.tab_panel.update(cx, |tab_panel, cx| {
+                tab_panel.add_panel(panel, window, cx);
+            });
+        }
+        result
+    }
+
+    fn dock_back_all(&mut self, window: &mut Window, cx: &mut App) -> Result<()> {
+        for panel in self.tab_panel.read(cx).panels.clone() {
+            self.dock_back(panel, window, cx)?;
+        }
+        Ok(())
+    }
+
+    /// Dock back the panels before the window is closed.
+    ///
+    /// If failed, the panels are dropped with the window, so they are asked by
+    /// [`Panel::can_close`](super::Panel::can_close) first, and the window is kept
+    /// if any of them vetoes.
+    fn on_should_close(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Err(err) = self.dock_back_all(window, cx) else {
+            return true;
+        };
+        tracing::error!("failed to dock back the panels: {:?}", err);
+
+        let panels = self.tab_panel.read(cx).panels.clone();
+        let can_close = can_close_panels(panels, window, cx);
+        cx.spawn_in(window, async move |_, cx| {
+            if can_close.await {
+                _ = cx.update(|window, _| window.remove_window());
+            }
+        })
+        .detach();
+        false
+    }
+
+    fn on_drag_move(
+        &mut self,
+        event: &DragMoveEvent<DragPanel>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let drag = event.drag(cx);
+        if drag.tab_panel != self.tab_panel {
+            return;
+        }
+
+        self.drag_out = screen_position_outside_window(event.event.position, window)
+            .map(|position| (drag.clone(), position));
+    }
+
+    fn on_mouse_up_out(&mut self, _: &MouseUpEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((drag, position)) = self.drag_out.take() else {
+            return;
+        };
+
+        // Dock back if dropped over the window of the dock area.
+        let dock_bounds = self
+            .dock_window
+            .update(cx, |_, window, _| window.bounds())
+            .ok();
+        if dock_bounds.is_some_and(|bounds| bounds.contains(&position)) {
+            if let Err(err) = self.dock_back(drag.panel, window, cx) {
+                tracing::error!("failed to dock back the panel: {:?}", err);
+            }
+        }
+    }
+}
+
+impl Render for FloatingWindow {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("floating-window")
+            .size_full()
+            .bg(cx.theme().background)
+            .on_drag_move(cx.listener(Self::on_drag_move))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up_out))
+            .child(self.tab_panel.clone())
+            .children(Root::render_dialog_layer(window, cx))
+            .children(Root::render_notification_layer(window, cx))
+    }
+}
+
+/// Returns the mouse position on the screen, if the `position` is outside of the window.
+pub(super) fn screen_position_outside_window(
+    position: Point<Pixels>,
+    window: &Window,
+) -> Option<Point<Pixels>> {
+    let viewport = Bounds::new(Point::default(), window.viewport_size());
+    if viewport.contains(&position) {
+        return None;
+    }
+
+    Some(window.bounds().origin + position)
+}
@@ -1,4 +1,5 @@
 mod dock;
+mod floating;
 mod invalid_panel;
 mod panel;
 mod stack_panel;
@@ -6,28 +7,82 @@ mod state;
 mod tab_panel;
 mod tiles;
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use gpui::{
-    AnyElement, AnyView, App, AppContext, Axis, Bounds, Context, Edges, Entity, EntityId,
-    EventEmitter, InteractiveElement as _, IntoElement, ParentElement as _, Pixels, Render,
-    SharedString, Styled, Subscription, WeakEntity, Window, actions, div, prelude::FluentBuilder,
+    Action, AnyElement, AnyView, App, AppContext, Axis, Bounds, Context, DragMoveEvent, Edges,
+    Entity, EntityId, EventEmitter, Focusable as _, InteractiveElement as _, IntoElement,
+    KeyBinding, MouseButton, MouseUpEvent, ParentElement as _, Pixels, Point, Render, SharedString,
+    Styled, Subscription, Task, WeakEntity, Window, WindowBounds, WindowOptions, actions, div,
+    point, prelude::FluentBuilder, px,
 };
-use std::sync::Arc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
 pub use dock::*;
+pub use floating::FloatingWindow;
 pub use panel::*;
 pub use stack_panel::*;
 pub use state::*;
 pub use tab_panel::*;
 pub use tiles::*;
 
-use crate::ElementExt;
+use crate::{ElementExt, Root};
+
+use floating::{FLOATING_WINDOW_SIZE, screen_position_outside_window};
+
+const CONTEXT: &str = "DockArea";
+/// The max number of the recently closed panels to keep.
+const MAX_CLOSED_PANELS: usize = 10;
 
 pub(crate) fn init(cx: &mut App) {
     PanelRegistry::init(cx);
+
+    cx.bind_keys([
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-b", ToggleLeftDock, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-b", ToggleLeftDock, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-j", ToggleBottomDock, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-j", ToggleBottomDock, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-b", ToggleRightDock, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-alt-b", ToggleRightDock, Some(CONTEXT)),
+        KeyBinding::new("secondary-shift-t", ReopenClosedPanel, Some(CONTEXT)),
+        KeyBinding::new("secondary-k secondary-right", FocusNextPanel, Some(CONTEXT)),
+        KeyBinding::new(
+            "secondary-k secondary-left",
+            FocusPreviousPanel,
+            Some(CONTEXT),
+        ),
+        KeyBinding::new("shift-escape", ToggleZoom, Some(CONTEXT)),
+    ]);
 }
 
-actions!(dock, [ToggleZoom, ClosePanel]);
+actions!(
+    dock,
+    [
+        ToggleZoom,
+        ClosePanel,
+        ToggleLeftDock,
+        ToggleBottomDock,
+        ToggleRightDock,
+        ReopenClosedPanel,
+        FocusNextPanel,
+        FocusPreviousPanel,
+        EvenOutSplits
+    ]
+);
+
+/// Move the active panel of the focused TabPanel to the dock at the placement.
+///
+/// Bind it in a keymap like `"cmd-alt-left": ["dock::MovePanelTo", "left"]`.
+#[derive(Action, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[action(namespace = dock)]
+pub struct MovePanelTo(pub DockPlacement);
 
 pub enum DockEvent {
     /// The layout of the dock has changed, subscribers this to save the layout.
@@ -40,6 +95,18 @@ pub enum DockEvent {
     DragDrop(AnyDrag),
 }
 
+/// A panel closed by the user, see [`DockArea::closed_panels`].
+#[derive(Clone)]
+pub struct ClosedPanel {
+    /// The title of the panel, from the [`Panel::tab_name`] or the [`Panel::editable_title`].
+    pub title: SharedString,
+    /// The serialized state of the panel to build it again.
+    pub state: PanelState,
+    /// The TabPanel that the panel was closed from, and the index in it.
+    tab_panel: WeakEntity<TabPanel>,
+    ix: usize,
+}
+
 /// The main area of the dock.
 pub struct DockArea {
     id: SharedString,
@@ -70,6 +137,21 @@ pub struct DockArea {
     /// The panel style, default is [`PanelStyle::Default`](PanelStyle::Default).
     pub(crate) panel_style: PanelStyle,
 
+    /// The recently closed panels, the most recent first.
+    closed_panels: Vec<ClosedPanel>,
+
+    /// Allow to tear out the panels into floating windows, see [`Self::set_tear_out`].
+    tear_out: bool,
+    /// The dragging tab outside the window, and the mouse position on the screen.
+    tear_out_drag: Option<(DragPanel, Point<Pixels>)>,
+    /// The floating windows of the torn out panels.
+    floating_windows: Vec<WeakEntity<FloatingWindow>>,
+
+    /// The named layout presets in the order they were saved, see [`Self::save_layout`].
+    layouts: Vec<(SharedString, DockAreaState)>,
+    /// The name of the layout preset last saved or applied.
+    active_layout: Option<SharedString>,
+
     _subscriptions: Vec<Subscription>,
 }
 
@@ -177,6 +259,61 @@ impl DockItem {
         self
     }
 
+    /// Only mount the panel content when the tab is first activated, only valid for [`DockItem::Tabs`].
+    ///
+    /// See [`TabPanel::lazy`].
+    pub fn lazy(self, cx: &mut App) -> Self {
+        debug_assert!(
+            matches!(self, Self::Tabs { .. }),
+            "lazy can only be set for DockItem::Tabs"
+        );
+
+        if let Self::Tabs { ref view, .. } = self {
+            view.update(cx, |tab_panel, _| {
+                tab_panel.lazy = true;
+            });
+        }
+        self
+    }
+
+    /// Keep the deactivated panel content mounted, only valid for [`DockItem::Tabs`].
+    ///
+    /// See [`TabPanel::keep_alive`].
+    pub fn keep_alive(self, keep_alive: bool, cx: &mut App) -> Self {
+        debug_assert!(
+            matches!(self, Self::Tabs { .. }),
+            "keep_alive can only be set for DockItem::Tabs"
+        );
+
+        if let Self::Tabs { ref view, .. } = self {
+            view.update(cx, |tab_panel, _| {
+                tab_panel.keep_alive = keep_alive;
+            });
+        }
+        self
+    }
+
+    /// Set a callback to be called before a tab is activated, only valid for [`DockItem::Tabs`].
+    ///
+    /// See [`TabPanel::on_will_activate`].
+    pub fn on_will_activate(
+        self,
+        on_will_activate: impl Fn(&Arc<dyn PanelView>, &mut Window, &mut App) + 'static,
+        cx: &mut App,
+    ) -> Self {
+        debug_assert!(
+            matches!(self, Self::Tabs { .. }),
+            "on_will_activate can only be set for DockItem::Tabs"
+        );
+
+        if let Self::Tabs { ref view, .. } = self {
+            view.update(cx, |tab_panel, _| {
+                tab_panel.on_will_activate = Some(Rc::new(on_will_activate));
+            });
+        }
+        self
+    }
+
     /// Create DockItem::Split with given split layout.
     pub fn split(
         axis: Axis,
@@ -546,10 +683,19 @@ impl DockArea {
             toggle_button_visible: true,
             locked: false,
             panel_style: PanelStyle::default(),
+            closed_panels: vec![],
+            tear_out: false,
+            tear_out_drag: None,
+            floating_windows: vec![],
+            layouts: vec![],
+            active_layout: None,
             _subscriptions: vec![],
         };
 
         this.subscribe_panel(&stack_panel, window, cx);
+        // The panels in the floating windows can not be docked back without the dock area.
+        this._subscriptions
+            .push(cx.on_release(|this, cx| this.close_floating_windows(cx)));
 
         this
     }
@@ -606,25 +752,80 @@ impl DockArea {
     }
 
     /// Remove the left dock.
+    ///
+    /// The panels in it are dropped without asking, see [`Self::close_dock`] to ask them first.
     pub fn remove_left_dock(&mut self, _: &mut Window, _: &mut Context<Self>) {
         self.left_dock = None;
     }
 
     /// Remove the bottom dock.
+    ///
+    /// The panels in it are dropped without asking, see [`Self::close_dock`] to ask them first.
     pub fn remove_bottom_dock(&mut self, _: &mut Window, _: &mut Context<Self>) {
         self.bottom_dock = None;
     }
 
     /// Remove the right dock.
+    ///
+    /// The panels in it are dropped without asking, see [`Self::close_dock`] to ask them first.
     pub fn remove_right_dock(&mut self, _: &mut Window, _: &mut Context<Self>) {
         self.right_dock = None;
     }
 
+    /// Close the dock at the given placement, if all panels in it can be closed.
+    ///
+    /// The panels are asked one by one by [`Panel::can_close`], the dock is kept if any of them
+    /// vetoes. Returns a task that resolves to `true` if the dock has been closed.
+    pub fn close_dock(
+        &mut self,
+        placement: DockPlacement,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<bool> {
+        let dock = match placement {
+            DockPlacement::Left => &self.left_dock,
+            DockPlacement::Bottom => &self.bottom_dock,
+            DockPlacement::Right => &self.right_dock,
+            DockPlacement::Center => return Task::ready(false),
+        };
+        let Some(dock) = dock else {
+            return Task::ready(true);
+        };
+
+        let mut tab_panels = vec![];
+        collect_tab_panels(&dock.read(cx).panel.view(), cx, &mut tab_panels);
+        let panels = tab_panels
+            .iter()
+            .flat_map(|tab_panel| tab_panel.read(cx).panels.clone())
+            .collect();
+
+        let can_close = can_close_panels(panels, window, cx);
+        cx.spawn_in(window, async move |this, cx| {
+            if !can_close.await {
+                return false;
+            }
+
+            this.update_in(cx, |this, window, cx| {
+                match placement {
+                    DockPlacement::Left => this.left_dock = None,
+                    DockPlacement::Bottom => this.bottom_dock = None,
+                    DockPlacement::Right => this.right_dock = None,
+                    DockPlacement::Center => {}
+                }
+                this.update_toggle_button_tab_panels(window, cx);
+                cx.emit(DockEvent::LayoutChanged);
+                cx.notify();
+            })
+            .is_ok()
+        })
+    }
+
     /// The the DockItem as the center of the dock area.
     ///
     /// This is used to render at the Center of the DockArea.
     pub fn set_center(&mut self, center: DockItem, window: &mut Window, cx: &mut Context<Self>) {
         self.subscribe_item(&center, window, cx);
+        set_tab_panels_placement(&center.view(), None, cx);
         self.center = center;
         self.update_toggle_button_tab_panels(window, cx);
         cx.notify();
@@ -701,6 +902,15 @@ impl DockArea {
         self.locked = locked;
     }
 
+    /// Set whether to allow tearing out the panels into floating windows, default: false
+    ///
+    /// When enabled, drag a tab outside the window to detach the panel into a floating window,
+    /// drag the tab back over the dock area or close the floating window to dock it back.
+    pub fn set_tear_out(&mut self, tear_out: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.tear_out = tear_out;
+        cx.notify();
+    }
+
     /// Determine if the dock area is locked.
     #[inline]
     pub fn is_locked(&self) -> bool {
@@ -767,6 +977,40 @@ impl DockArea {
         }
     }
 
+    /// Set whether to show a tool strip for the collapsed docks, default: false
+    ///
+    /// When enabled, a collapsed dock keeps a thin strip of its panel icons (like the activity bar
+    /// of VS Code), click an icon to open the dock with that panel active.
+    ///
+    /// The tool strip and the size of each dock are saved by [`Self::dump`] and restored by
+    /// [`Self::load`]. A dock replaced by [`Self::set_left_dock`] and the like starts without one.
+    ///
+    /// Only the left, bottom, right dock can have a tool strip.
+    pub fn set_dock_tool_strip(
+        &mut self,
+        tool_strip_edges: Edges<bool>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(left_dock) = self.left_dock.as_ref() {
+            left_dock.update(cx, |dock, cx| {
+                dock.set_tool_strip(tool_strip_edges.left, window, cx);
+            });
+        }
+
+        if let Some(bottom_dock) = self.bottom_dock.as_ref() {
+            bottom_dock.update(cx, |dock, cx| {
+                dock.set_tool_strip(tool_strip_edges.bottom, window, cx);
+            });
+        }
+
+        if let Some(right_dock) = self.right_dock.as_ref() {
+            right_dock.update(cx, |dock, cx| {
+                dock.set_tool_strip(tool_strip_edges.right, window, cx);
+            });
+        }
+    }
+
     /// Determine if the dock at the given placement is collapsible.
     pub fn is_dock_collapsible(&self, placement: DockPlacement, cx: &App) -> bool {
         match placement {
@@ -810,6 +1054,60 @@ impl DockArea {
         }
     }
 
+    /// Set whether the dock at the given placement is pinned, default: true
+    ///
+    /// The unpinned dock is collapsed to a tool strip of its panel icons, and slides over the
+    /// content when hovering or clicking an icon, see [`Dock::set_pinned`].
+    ///
+    /// Only the left, bottom, right dock can be unpinned.
+    pub fn set_dock_pinned(
+        &mut self,
+        placement: DockPlacement,
+        pinned: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let dock = match placement {
+            DockPlacement::Left => &self.left_dock,
+            DockPlacement::Bottom => &self.bottom_dock,
+            DockPlacement::Right => &self.right_dock,
+            DockPlacement::Center => return,
+        };
+
+        if let Some(dock) = dock {
+            dock.update(cx, |view, cx| {
+                view.set_pinned(pinned, window, cx);
+            });
+            cx.emit(DockEvent::LayoutChanged);
+        }
+    }
+
+    /// Determine if the dock at the given placement is pinned.
+    pub fn is_dock_pinned(&self, placement: DockPlacement, cx: &App) -> bool {
+        match placement {
+            DockPlacement::Left => self.left_dock.as_ref(),
+            DockPlacement::Bottom => self.bottom_dock.as_ref(),
+            DockPlacement::Right => self.right_dock.as_ref(),
+            DockPlacement::Center => None,
+        }
+        .map(|dock| dock.read(cx).is_pinned())
+        .unwrap_or(true)
+    }
+
+    fn on_action_toggle_dock(
+        &mut self,
+        placement: DockPlacement,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.is_dock_collapsible(placement, cx) {
+            cx.propagate();
+            return;
+        }
+
+        self.toggle_dock(placement, window, cx);
+    }
+
     /// Set the visibility of the toggle button.
     pub fn set_toggle_button_visible(&mut self, visible: bool, _: &mut Context<Self>) {
         self.toggle_button_visible = visible;
@@ -922,31 +1220,521 @@ impl DockArea {
         self.remove_panel(panel.clone(), DockPlacement::Bottom, window, cx);
     }
 
+    /// Returns the recently closed panels, the most recent first.
+    pub fn closed_panels(&self) -> &[ClosedPanel] {
+        &self.closed_panels
+    }
+
+    /// Record the panel closed from the `tab_panel` at `ix`, to reopen it later.
+    pub(crate) fn push_closed_panel(
+        &mut self,
+        panel: &Arc<dyn PanelView>,
+        tab_panel: WeakEntity<TabPanel>,
+        ix: usize,
+        cx: &App,
+    ) {
+        let state = panel.dump(cx);
+        let title = panel
+            .tab_name(cx)
+            .or_else(|| panel.editable_title(cx))
+            .unwrap_or_else(|| state.panel_name.clone().into());
+
+        self.closed_panels.insert(
+            0,
+            ClosedPanel {
+                title,
+                state,
+                tab_panel,
+                ix,
+            },
+        );
+        self.closed_panels.truncate(MAX_CLOSED_PANELS);
+    }
+
+    /// Reopen the recently closed panel at the given index of [`Self::closed_panels`].
+    ///
+    /// The panel is built again from the [`ClosedPanel::state`], and added back to the tabs
+    /// it was closed from, or to the center if the tabs no longer exist.
+    pub fn reopen_closed_panel(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if ix >= self.closed_panels.len() {
+            return;
+        }
+
+        let closed = self.closed_panels.remove(ix);
+        let panel: Arc<dyn PanelView> = PanelRegistry::build_panel(
+            &closed.state.panel_name,
+            cx.entity().downgrade(),
+            &closed.state,
+            &closed.state.info,
+            window,
+            cx,
+        )
+        .into();
+
+        // The empty TabPanel has been removed from the layout.
+        match closed
+            .tab_panel
+            .upgrade()
+            .filter(|tab_panel| !tab_panel.read(cx).panels.is_empty())
+        {
+            Some(tab_panel) => tab_panel.update(cx, |tab_panel, cx| {
+                let ix = closed.ix.min(tab_panel.panels.len());
+                tab_panel.insert_panel_at(panel.clone(), ix, window, cx);
+            }),
+            None => self.add_panel(panel.clone(), DockPlacement::Center, None, window, cx),
+        }
+        panel.focus_handle(cx).focus(window, cx);
+        cx.notify();
+    }
+
+    fn on_action_reopen_closed_panel(
+        &mut self,
+        _: &ReopenClosedPanel,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.closed_panels.is_empty() {
+            cx.propagate();
+            return;
+        }
+
+        self.reopen_closed_panel(0, window, cx);
+    }
+
+    /// Even out the sizes of all the splits in the dock area, the locked panels keep their sizes.
+    ///
+    /// See also [`StackPanel::even_out`], [`StackPanel::lock_size`].
+    pub fn even_out_splits(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let docks = [&self.left_dock, &self.bottom_dock, &self.right_dock];
+        let items = docks
+            .into_iter()
+            .flatten()
+            .map(|dock| dock.read(cx).panel.clone())
+            .chain(std::iter::once(self.center.clone()))
+            .collect::<Vec<_>>();
+
+        for item in items {
+            if let DockItem::Split { view, .. } = item {
+                view.update(cx, |stack_panel, cx| stack_panel.even_out(window, cx));
+            }
+        }
+    }
+
+    /// Returns the panels replaced by loading the `state`, all the panels in the center, the
+    /// floating windows and the docks in the `state`, or all the docks if `remove_docks`.
+    fn replaced_panels(
+        &self,
+        state: &DockAreaState,
+        remove_docks: bool,
+        cx: &App,
+    ) -> Vec<Arc<dyn PanelView>> {
+        let docks = [
+            (&self.left_dock, state.left_dock.is_some()),
+            (&self.bottom_dock, state.bottom_dock.is_some()),
+            (&self.right_dock, state.right_dock.is_some()),
+        ];
+        let mut tab_panels = vec![];
+        for (dock, replaced) in docks {
+            if let Some(dock) = dock.as_ref().filter(|_| replaced || remove_docks) {
+                collect_tab_panels(&dock.read(cx).panel.view(), cx, &mut tab_panels);
+            }
+        }
+        collect_tab_panels(&self.center.view(), cx, &mut tab_panels);
+        tab_panels.extend(
+            self.floating_windows
+                .iter()
+                .filter_map(|view| view.upgrade())
+                .map(|view| view.read(cx).tab_panel.clone()),
+        );
+
+        tab_panels
+            .iter()
+            .flat_map(|tab_panel| tab_panel.read(cx).panels.clone())
+            .collect()
+    }
+
+    /// Returns the TabPanels in the visible areas, in the order of left dock, center,
+    /// right dock and bottom dock.
+    fn visible_tab_panels(&self, cx: &App) -> Vec<Entity<TabPanel>> {
+        let mut tab_panels = vec![];
+        let collect_dock = |dock: &Option<Entity<Dock>>, tab_panels: &mut Vec<_>| {
+            let dock = dock.as_ref().map(|dock| dock.read(cx));
+            if let Some(dock) = dock.filter(|dock| dock.is_open()) {
+                collect_tab_panels(&dock.panel.view(), cx, tab_panels);
+            }
+        };
+
+        collect_dock(&self.left_dock, &mut tab_panels);
+        collect_tab_panels(&self.center.view(), cx, &mut tab_panels);
+        collect_dock(&self.right_dock, &mut tab_panels);
+        collect_dock(&self.bottom_dock, &mut tab_panels);
+        tab_panels
+    }
+
+    /// Returns the TabPanel that contains the focused panel.
+    fn focused_tab_panel(&self, window: &Window, cx: &App) -> Option<Entity<TabPanel>> {
+        self.visible_tab_panels(cx).into_iter().find(|tab_panel| {
+            tab_panel
+                .read(cx)
+                .focus_handle(cx)
+                .contains_focused(window, cx)
+        })
+    }
+
+    fn focus_panel_by_offset(
+        &mut self,
+        offset: isize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.zoom_view.is_some() {
+            cx.propagate();
+            return;
+        }
+
+        let tab_panels = self.visible_tab_panels(cx);
+        if tab_panels.is_empty() {
+            cx.propagate();
+            return;
+        }
+
+        let focused = self.focused_tab_panel(window, cx);
+        let len = tab_panels.len() as isize;
+        let ix = match tab_panels
+            .iter()
+            .position(|tab_panel| Some(tab_panel) == focused.as_ref())
+        {
+            Some(ix) => (ix as isize + offset).rem_euclid(len) as usize,
+            None if offset > 0 => 0,
+            None => tab_panels.len() - 1,
+        };
+
+        tab_panels[ix].update(cx, |tab_panel, cx| {
+            tab_panel.focus_active_panel(window, cx);
+        });
+    }
+
+    fn on_action_move_panel_to(
+        &mut self,
+        action: &MovePanelTo,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let placement = action.0;
+        if self.locked || self.zoom_view.is_some() {
+            cx.propagate();
+            return;
+        }
+        let Some(tab_panel) = self.focused_tab_panel(window, cx) else {
+            cx.propagate();
+            return;
+        };
+        let Some(panel) = tab_panel.read(cx).active_panel(cx) else {
+            return;
+        };
+        let current = tab_panel
+            .read(cx)
+            .dock_placement
+            .unwrap_or(DockPlacement::Center);
+        if current == placement {
+            return;
+        }
+
+        tab_panel.update(cx, |tab_panel, cx| {
+            tab_panel.remove_panel(panel.clone(), window, cx);
+        });
+        self.add_panel(panel.clone(), placement, None, window, cx);
+        if !self.is_dock_open(placement, cx) {
+            self.toggle_dock(placement, window, cx);
+        }
+
+        cx.defer_in(window, move |_, window, cx| {
+            panel.focus_handle(cx).focus(window, cx);
+        });
+        cx.emit(DockEvent::LayoutChanged);
+        cx.notify();
+    }
+
+    fn on_panel_drag_move(
+        &mut self,
+        event: &DragMoveEvent<DragPanel>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let drag = event.drag(cx);
+        let tab_panel = drag.tab_panel.read(cx);
+        if tab_panel.floating || tab_panel.dock_area != cx.entity().downgrade() {
+            return;
+        }
+
+        self.tear_out_drag = screen_position_outside_window(event.event.position, window)
+            .map(|position| (drag.clone(), position));
+    }
+
+    fn on_mouse_up_out(&mut self, _: &MouseUpEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((drag, position)) = self.tear_out_drag.take() {
+            self.tear_out_panel(drag, position, window, cx);
+        }
+    }
+
+    /// Detach the dragged panel into a floating window at the `position` on the screen.
+    fn tear_out_panel(
+        &mut self,
+        drag: DragPanel,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let panel = drag.panel.clone();
+        drag.tab_panel.update(cx, |tab_panel, cx| {
+            tab_panel.remove_panel(panel.clone(), window, cx);
+        });
+
+        // Keep the dragged tab under the mouse.
+        let bounds = Bounds::new(position - point(px(40.), px(16.)), FLOATING_WINDOW_SIZE);
+        let placement = drag.tab_panel.read(cx).dock_placement;
+        self.open_floating_window(
+            bounds,
+            Some(drag.tab_panel.downgrade()),
+            placement,
+            move |dock_area, window, cx| {
+                cx.new(|cx| {
+                    let mut tab_panel = TabPanel::new(None, dock_area, window, cx);
+                    tab_panel.add_panel(panel, window, cx);
+                    tab_panel
+                })
+            },
+            window,
+            cx,
+        );
+    }
+
+    /// Open a floating window at the `bounds` on the screen for the TabPanel built by `build`.
+    ///
+    /// The TabPanel is built in the floating window, so the panels are added to the new window.
+    fn open_floating_window<F>(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        origin: Option<WeakEntity<TabPanel>>,
+        placement: Option<DockPlacement>,
+        build: F,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) where
+        F: FnOnce(WeakEntity<DockArea>, &mut Window, &mut App) -> Entity<TabPanel> + 'static,
+    {
+        // Defer to avoid opening the window while the dock area window is being built.
+        cx.defer_in(window, move |this, window, cx| {
+            let dock_area = cx.entity().downgrade();
+            let dock_window = window.window_handle();
+            let options = WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            };
+
+            let mut floating_window = None;
+            let result = cx.open_window(options, |window, cx| {
+                let tab_panel = build(dock_area.clone(), window, cx);
+                let view = cx.new(|cx| {
+                    FloatingWindow::new(
+                        dock_area,
+                        dock_window,
+                        tab_panel,
+                        origin,
+                        placement,
+                        window,
+                        cx,
+                    )
+                });
+                floating_window = Some(view.downgrade());
+                cx.new(|cx| Root::new(view, window, cx))
+            });
+            if let Err(err) = result {
+                tracing::error!("failed to open floating window: {:?}", err);
+                return;
+            }
+
+            this.floating_windows
+                .retain(|view| view.upgrade().is_some());
+            this.floating_windows.extend(floating_window);
+            cx.emit(DockEvent::LayoutChanged);
+            cx.notify();
+        });
+    }
+
+    /// Dock the panel from a floating window back to the `origin` tabs,
+    /// or to the dock at the `placement` if the tabs no longer exist.
+    pub(crate) fn dock_panel_back(
+        &mut self,
+        panel: Arc<dyn PanelView>,
+        origin: Option<WeakEntity<TabPanel>>,
+        placement: Option<DockPlacement>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // The empty TabPanel has been removed from the layout.
+        match origin
+            .and_then(|tab_panel| tab_panel.upgrade())
+            .filter(|tab_panel| !tab_panel.read(cx).panels.is_empty())
+        {
+            Some(tab_panel) => tab_panel.update(cx, |tab_panel, cx| {
+                tab_panel.add_panel(panel.clone(), window, cx);
+            }),
+            None => {
+                let placement = placement.unwrap_or(DockPlacement::Center);
+                self.add_panel(panel.clone(), placement, None, window, cx)
+            }
+        }
+        panel.focus_handle(cx).focus(window, cx);
+        cx.emit(DockEvent::LayoutChanged);
+        cx.notify();
+    }
+
+    /// Close the floating windows without docking the panels back.
+    fn close_floating_windows(&mut self, cx: &mut App) {
+        for view in self.floating_windows.drain(..) {
+            let Some(view) = view.upgrade() else {
+                continue;
+            };
+
+            let floating_window = view.read(cx).window;
+            cx.defer(move |cx| {
+                _ = floating_window.update(cx, |_, window, _| window.remove_window());
+            });
+        }
+    }
+
     /// Load the state of the DockArea from the DockAreaState.
     ///
+    /// The live panels that are not in the state are dropped without asking,
+    /// see [`Self::try_load`] to ask them by [`Panel::can_close`] first.
+    ///
     /// See also [DockeArea::dump].
     pub fn load(
         &mut self,
         state: DockAreaState,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) -> Result<()> {
+        self.load_reusing(state, vec![], window, cx)
+    }
+
+    /// Load the state like [`Self::load`], but the live panels that would be dropped are asked
+    /// by [`Panel::can_close`] first.
+    ///
+    /// Returns a task that resolves to `true` if the state has been loaded,
+    /// or `false` if any of the panels vetoes.
+    pub fn try_load(
+        &mut self,
+        state: DockAreaState,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<bool>> {
+        let panels = self.replaced_panels(&state, false, cx);
+        self.after_can_close(panels, window, cx, move |this, window, cx| {
+            this.load(state, window, cx)
+        })
+    }
+
+    /// Run `f` after all the `panels` agree to close by [`Panel::can_close`],
+    /// or right away if there are no panels.
+    fn after_can_close(
+        &mut self,
+        panels: Vec<Arc<dyn PanelView>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&mut Self, &mut Window, &mut Context<Self>) -> Result<()> + 'static,
+    ) -> Task<Result<bool>> {
+        if panels.is_empty() {
+            return Task::ready(f(self, window, cx).map(|_| true));
+        }
+
+        let can_close = can_close_panels(panels, window, cx);
+        cx.spawn_in(window, async move |this, cx| {
+            if !can_close.await {
+                return Ok(false);
+            }
+
+            this.update_in(cx, |this, window, cx| f(this, window, cx))??;
+            Ok(true)
+        })
+    }
+
+    /// Load the state like [`Self::load`], the `reusable` panels are reused if match.
+    fn load_reusing(
+        &mut self,
+        state: DockAreaState,
+        mut reusable: ReusablePanels,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Result<()> {
         self.version = state.version;
         let weak_self = cx.entity().downgrade();
 
         if let Some(left_dock_state) = state.left_dock {
-            self.left_dock = Some(left_dock_state.to_dock(weak_self.clone(), window, cx));
+            self.left_dock =
+                Some(left_dock_state.to_dock_reusing(weak_self.clone(), &mut reusable, window, cx));
         }
 
         if let Some(right_dock_state) = state.right_dock {
-            self.right_dock = Some(right_dock_state.to_dock(weak_self.clone(), window, cx));
+            self.right_dock = Some(right_dock_state.to_dock_reusing(
+                weak_self.clone(),
+                &mut reusable,
+                window,
+                cx,
+            ));
         }
 
         if let Some(bottom_dock_state) = state.bottom_dock {
-            self.bottom_dock = Some(bottom_dock_state.to_dock(weak_self.clone(), window, cx));
+            self.bottom_dock = Some(bottom_dock_state.to_dock_reusing(
+                weak_self.clone(),
+                &mut reusable,
+                window,
+                cx,
+            ));
+        }
+
+        self.center = state
+            .center
+            .to_item_reusing(weak_self.clone(), &mut reusable, window, cx);
+        set_tab_panels_placement(&self.center.view(), None, cx);
+
+        self.close_floating_windows(cx);
+        // The floating panels are built later in their windows.
+        let reusable = Rc::new(RefCell::new(reusable));
+        for panel_state in state.floating {
+            let Some(bounds) = panel_state.floating_bounds else {
+                continue;
+            };
+            if !matches!(panel_state.info, PanelInfo::Tabs { .. }) {
+                continue;
+            }
+
+            let placement = panel_state.floating_placement;
+            self.open_floating_window(
+                bounds,
+                None,
+                placement,
+                {
+                    let reusable = reusable.clone();
+                    move |dock_area, window, cx| {
+                        let item = panel_state.to_item_reusing(
+                            dock_area.clone(),
+                            &mut reusable.borrow_mut(),
+                            window,
+                            cx,
+                        );
+                        match item {
+                            DockItem::Tabs { view, .. } => view,
+                            _ => cx.new(|cx| TabPanel::new(None, dock_area, window, cx)),
+                        }
+                    }
+                },
+                window,
+                cx,
+            );
         }
 
-        self.center = state.center.to_item(weak_self, window, cx);
         self.update_toggle_button_tab_panels(window, cx);
         Ok(())
     }
@@ -971,15 +1759,164 @@ impl DockArea {
             .as_ref()
             .map(|dock| DockState::new(dock.clone(), cx));
 
+        let floating = self
+            .floating_windows
+            .iter()
+            .filter_map(|view| view.upgrade())
+            .map(|view| view.read(cx).dump(cx))
+            .collect();
+
         DockAreaState {
             version: self.version,
             center,
             left_dock,
             right_dock,
             bottom_dock,
+            floating,
         }
     }
 
+    /// Save the current layout as a named preset, replace the preset if the name already exists.
+    ///
+    /// This is useful to offer the workspace layouts like "Coding", "Debugging" for users to
+    /// switch between by [`DockArea::apply_layout`].
+    pub fn save_layout(&mut self, name: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let name = name.into();
+        let state = self.dump(cx);
+        self.set_layout(name.clone(), state, cx);
+        self.active_layout = Some(name);
+    }
+
+    /// Set the named layout preset with the state, e.g. to restore the presets saved by the app.
+    pub fn set_layout(
+        &mut self,
+        name: impl Into<SharedString>,
+        state: DockAreaState,
+        cx: &mut Context<Self>,
+    ) {
+        let name = name.into();
+        if let Some((_, layout)) = self.layouts.iter_mut().find(|(n, _)| n == &name) {
+            *layout = state;
+        } else {
+            self.layouts.push((name, state));
+        }
+        cx.notify();
+    }
+
+    /// Remove the named layout preset, returns the state of the removed preset.
+    pub fn remove_layout(&mut self, name: &str, cx: &mut Context<Self>) -> Option<DockAreaState> {
+        let ix = self.layouts.iter().position(|(n, _)| n.as_ref() == name)?;
+        if self
+            .active_layout
+            .as_ref()
+            .is_some_and(|active| active.as_ref() == name)
+        {
+            self.active_layout = None;
+        }
+        cx.notify();
+        Some(self.layouts.remove(ix).1)
+    }
+
+    /// Returns the state of the named layout preset.
+    pub fn layout(&self, name: &str) -> Option<&DockAreaState> {
+        self.layouts
+            .iter()
+            .find(|(n, _)| n.as_ref() == name)
+            .map(|(_, state)| state)
+    }
+
+    /// Returns the names of the layout presets in the order they were saved.
+    pub fn layouts(&self) -> impl Iterator<Item = &SharedString> {
+        self.layouts.iter().map(|(name, _)| name)
+    }
+
+    /// Returns the name of the layout preset last saved or applied.
+    pub fn active_layout(&self) -> Option<&SharedString> {
+        self.active_layout.as_ref()
+    }
+
+    /// Apply the named layout preset that saved by [`DockArea::save_layout`].
+    ///
+    /// The live panels with the same name and state in the preset are moved to the new layout,
+    /// the others are built from the preset by the [`PanelRegistry`].
+    ///
+    /// The live panels that are not in the preset are dropped without asking,
+    /// see [`Self::try_apply_layout`] to ask them by [`Panel::can_close`] first.
+    pub fn apply_layout(
+        &mut self,
+        name: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let (name, state) = self.find_layout(name)?;
+        let reusable = self.reusable_panels(&state, cx);
+        self.apply_layout_reusing(name, state, reusable, window, cx)
+    }
+
+    /// Apply the named layout preset like [`Self::apply_layout`], but the live panels that
+    /// are not in the preset are asked by [`Panel::can_close`] first.
+    ///
+    /// Returns a task that resolves to `true` if the preset has been applied,
+    /// or `false` if any of the panels vetoes.
+    pub fn try_apply_layout(
+        &mut self,
+        name: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<bool>> {
+        let (name, state) = match self.find_layout(name) {
+            Ok(layout) => layout,
+            Err(err) => return Task::ready(Err(err)),
+        };
+        let reusable = self.reusable_panels(&state, cx);
+        let unused = state.unused_panels(&reusable);
+        self.after_can_close(unused, window, cx, move |this, window, cx| {
+            this.apply_layout_reusing(name, state, reusable, window, cx)
+        })
+    }
+
+    fn find_layout(&self, name: &str) -> Result<(SharedString, DockAreaState)> {
+        self.layouts
+            .iter()
+            .find(|(n, _)| n.as_ref() == name)
+            .cloned()
+            .ok_or_else(|| anyhow!("layout preset `{}` not found", name))
+    }
+
+    /// Returns the live panels replaced by applying the preset `state`, with their states.
+    fn reusable_panels(&self, state: &DockAreaState, cx: &App) -> ReusablePanels {
+        self.replaced_panels(state, true, cx)
+            .into_iter()
+            .map(|panel| (panel.dump(cx), panel))
+            .collect()
+    }
+
+    fn apply_layout_reusing(
+        &mut self,
+        name: SharedString,
+        state: DockAreaState,
+        reusable: ReusablePanels,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        // The docks are not in the preset should be removed.
+        if state.left_dock.is_none() {
+            self.left_dock = None;
+        }
+        if state.right_dock.is_none() {
+            self.right_dock = None;
+        }
+        if state.bottom_dock.is_none() {
+            self.bottom_dock = None;
+        }
+        self.zoom_view = None;
+        self.load_reusing(state, reusable, window, cx)?;
+        self.active_layout = Some(name);
+        cx.emit(DockEvent::LayoutChanged);
+        cx.notify();
+        Ok(())
+    }
+
     /// Subscribe event on the panels
     #[allow(clippy::only_used_in_recursion)]
     fn subscribe_item(&mut self, item: &DockItem, window: &mut Window, cx: &mut Context<Self>) {
@@ -1056,6 +1993,7 @@ impl DockArea {
                         .detach();
                         cx.emit(DockEvent::LayoutChanged);
                     }
+                    PanelEvent::Rename(_) => {}
                 },
             );
 
@@ -1119,6 +2057,31 @@ impl Render for DockArea {
 
         div()
             .id("dock-area")
+            .key_context(CONTEXT)
+            .on_action(cx.listener(|this, _: &ToggleLeftDock, window, cx| {
+                this.on_action_toggle_dock(DockPlacement::Left, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &ToggleBottomDock, window, cx| {
+                this.on_action_toggle_dock(DockPlacement::Bottom, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &ToggleRightDock, window, cx| {
+                this.on_action_toggle_dock(DockPlacement::Right, window, cx)
+            }))
+            .on_action(cx.listener(Self::on_action_reopen_closed_panel))
+            .on_action(cx.listener(|this, _: &FocusNextPanel, window, cx| {
+                this.focus_panel_by_offset(1, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &FocusPreviousPanel, window, cx| {
+                this.focus_panel_by_offset(-1, window, cx)
+            }))
+            .on_action(cx.listener(Self::on_action_move_panel_to))
+            .on_action(
+                cx.listener(|this, _: &EvenOutSplits, window, cx| this.even_out_splits(window, cx)),
+            )
+            .when(self.tear_out, |this| {
+                this.on_drag_move(cx.listener(Self::on_panel_drag_move))
+                    .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up_out))
+            })
             .relative()
             .size_full()
             .overflow_hidden()
@@ -1,7 +1,8 @@
-use crate::{button::Button, dock::TabPanel, menu::PopupMenu};
+use crate::{Icon, button::Button, dock::TabPanel, menu::PopupMenu};
 use gpui::{
     AnyElement, AnyView, App, AppContext as _, Context, Entity, EntityId, EventEmitter,
-    FocusHandle, Focusable, Global, Hsla, IntoElement, Render, SharedString, WeakEntity, Window,
+    FocusHandle, Focusable, Global, Hsla, IntoElement, Render, SharedString, Task, WeakEntity,
+    Window,
 };
 use rust_i18n::t;
 use std::{collections::HashMap, sync::Arc};
@@ -12,6 +13,8 @@ pub enum PanelEvent {
     ZoomIn,
     ZoomOut,
     LayoutChanged,
+    /// The panel title has been renamed inline by double-clicking the tab, see [`Panel::editable_title`].
+    Rename(SharedString),
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -58,6 +61,14 @@ pub trait Panel: EventEmitter<PanelEvent> + Render + Focusable {
     /// Once you have defined a panel name, this must not be changed.
     fn panel_name(&self) -> &'static str;
 
+    /// The version of the panel state, default is 0.
+    ///
+    /// Increase it when the [`Panel::dump`] state is changed incompatibly, and register a migration
+    /// to upgrade the state of the old version, see [`register_panel_migration`].
+    fn panel_version(&self) -> usize {
+        0
+    }
+
     /// The name of the tab of the panel, default is `None`.
     ///
     /// Used to display in the already collapsed tab panel.
@@ -65,6 +76,13 @@ pub trait Panel: EventEmitter<PanelEvent> + Render + Focusable {
         None
     }
 
+    /// The icon of the panel, default is `None`.
+    ///
+    /// Used to display in the tool strip of the collapsed Dock, see [`DockArea::set_dock_tool_strip`].
+    fn icon(&self, cx: &App) -> Option<Icon> {
+        None
+    }
+
     /// The title of the panel
     fn title(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         t!("Dock.Unnamed")
@@ -86,6 +104,38 @@ pub trait Panel: EventEmitter<PanelEvent> + Render + Focusable {
         None::<gpui::Div>
     }
 
+    /// The icon shown before the title in the tab, default is `None`.
+    fn tab_icon(&self, cx: &App) -> Option<Icon> {
+        None
+    }
+
+    /// Whether the panel has unsaved changes, default is `false`.
+    ///
+    /// When true, a dot is shown after the title in the tab.
+    fn dirty(&self, cx: &App) -> bool {
+        false
+    }
+
+    /// Whether the panel is loading, default is `false`.
+    ///
+    /// When true, a spinner is shown in place of the [`Panel::tab_icon`].
+    fn loading(&self, cx: &App) -> bool {
+        false
+    }
+
+    /// The plain text title to edit inline, default is `None`.
+    ///
+    /// Return `Some` to allow renaming the panel by double-clicking the tab,
+    /// the [`Panel::rename`] will be called with the new title when confirmed.
+    fn editable_title(&self, cx: &App) -> Option<SharedString> {
+        None
+    }
+
+    /// Rename the panel title, called after the inline rename is confirmed.
+    ///
+    /// A [`PanelEvent::Rename`] will be emitted after this.
+    fn rename(&mut self, title: SharedString, window: &mut Window, cx: &mut Context<Self>) {}
+
     /// Whether the panel can be closed, default is `true`.
     ///
     /// This method called in Panel render, we should make sure it is fast.
@@ -93,6 +143,14 @@ pub trait Panel: EventEmitter<PanelEvent> + Render + Focusable {
         true
     }
 
+    /// Ask the panel whether it can be closed now, default is `Task::ready(true)`.
+    ///
+    /// This is called before the panel is closed by the user or the Dock it belongs to is closed,
+    /// a panel with unsaved changes can show a confirm dialog and resolve `false` to veto the close.
+    fn can_close(&self, window: &mut Window, cx: &mut Context<Self>) -> Task<bool> {
+        Task::ready(true)
+    }
+
     /// Return `PanelControl` if the panel is zoomable, default is `PanelControl::Menu`.
     ///
     /// This method called in Panel render, we should make sure it is fast.
@@ -169,10 +227,17 @@ pub trait PanelView: 'static + Send + Sync {
     fn panel_name(&self, cx: &App) -> &'static str;
     fn panel_id(&self, cx: &App) -> EntityId;
     fn tab_name(&self, cx: &App) -> Option<SharedString>;
+    fn icon(&self, cx: &App) -> Option<Icon>;
     fn title(&self, window: &mut Window, cx: &mut App) -> AnyElement;
     fn title_suffix(&self, window: &mut Window, cx: &mut App) -> Option<AnyElement>;
     fn title_style(&self, cx: &App) -> Option<TitleStyle>;
+    fn tab_icon(&self, cx: &App) -> Option<Icon>;
+    fn dirty(&self, cx: &App) -> bool;
+    fn loading(&self, cx: &App) -> bool;
+    fn editable_title(&self, cx: &App) -> Option<SharedString>;
+    fn rename(&self, title: SharedString, window: &mut Window, cx: &mut App);
     fn closable(&self, cx: &App) -> bool;
+    fn can_close(&self, window: &mut Window, cx: &mut App) -> Task<bool>;
     fn zoomable(&self, cx: &App) -> Option<PanelControl>;
     fn visible(&self, cx: &App) -> bool;
     fn set_active(&self, active: bool, window: &mut Window, cx: &mut App);
@@ -200,6 +265,10 @@ impl<T: Panel> PanelView for Entity<T> {
         self.read(cx).tab_name(cx)
     }
 
+    fn icon(&self, cx: &App) -> Option<Icon> {
+        self.read(cx).icon(cx)
+    }
+
     fn title(&self, window: &mut Window, cx: &mut App) -> AnyElement {
         self.update(cx, |this, cx| this.title(window, cx).into_any_element())
     }
@@ -215,10 +284,37 @@ impl<T: Panel> PanelView for Entity<T> {
         self.read(cx).title_style(cx)
     }
 
+    fn tab_icon(&self, cx: &App) -> Option<Icon> {
+        self.read(cx).tab_icon(cx)
+    }
+
+    fn dirty(&self, cx: &App) -> bool {
+        self.read(cx).dirty(cx)
+    }
+
+    fn loading(&self, cx: &App) -> bool {
+        self.read(cx).loading(cx)
+    }
+
+    fn editable_title(&self, cx: &App) -> Option<SharedString> {
+        self.read(cx).editable_title(cx)
+    }
+
+    fn rename(&self, title: SharedString, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| {
+            this.rename(title.clone(), window, cx);
+            cx.emit(PanelEvent::Rename(title));
+        })
+    }
+
     fn closable(&self, cx: &App) -> bool {
         self.read(cx).closable(cx)
     }
 
+    fn can_close(&self, window: &mut Window, cx: &mut App) -> Task<bool> {
+        self.update(cx, |this, cx| this.can_close(window, cx))
+    }
+
     fn zoomable(&self, cx: &App) -> Option<PanelControl> {
         self.read(cx).zoomable(cx)
     }
@@ -290,6 +386,29 @@ impl PartialEq for dyn PanelView {
     }
 }
 
+/// Ask the panels one by one by [`Panel::can_close`], resolves `false` once any of them vetoes.
+pub(super) fn can_close_panels(
+    panels: Vec<Arc<dyn PanelView>>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<bool> {
+    window.spawn(cx, async move |cx| {
+        for panel in panels {
+            let Ok(can_close) = cx.update(|window, cx| panel.can_close(window, cx)) else {
+                return false;
+            };
+            if !can_close.await {
+                return false;
+            }
+        }
+
+        true
+    })
+}
+
+/// The max number of the migrations applied to a panel state, to avoid the endless renames.
+pub(super) const MAX_MIGRATIONS: usize = 16;
+
 pub struct PanelRegistry {
     pub(super) items: HashMap<
         String,
@@ -303,6 +422,7 @@ pub struct PanelRegistry {
             ) -> Box<dyn PanelView>,
         >,
     >,
+    pub(super) migrations: HashMap<String, Arc<dyn Fn(PanelState) -> Option<PanelState>>>,
 }
 impl PanelRegistry {
     /// Initialize the panel registry.
@@ -315,6 +435,7 @@ impl PanelRegistry {
     pub fn new() -> Self {
         Self {
             items: HashMap::new(),
+            migrations: HashMap::new(),
         }
     }
 
@@ -346,9 +467,46 @@ impl PanelRegistry {
             return view;
         } else {
             // Show an invalid panel if the panel is not registered.
+            tracing::warn!(
+                "the `{}` panel is not registered in PanelRegistry",
+                panel_name
+            );
             Box::new(cx.new(|cx| InvalidPanel::new(&panel_name, panel_state.clone(), window, cx)))
         }
     }
+
+    /// Migrate the panel state by the registered migrations, see [`register_panel_migration`].
+    ///
+    /// The migration of the new name is applied again if the panel has been renamed.
+    /// Returns `None` if the panel has been removed.
+    pub(super) fn migrate(state: PanelState, cx: &App) -> Option<PanelState> {
+        let Some(registry) = cx.try_global::<PanelRegistry>() else {
+            return Some(state);
+        };
+
+        let mut state = state;
+        for _ in 0..MAX_MIGRATIONS {
+            let Some(migrate) = registry.migrations.get(&state.panel_name) else {
+                return Some(state);
+            };
+
+            let panel_name = state.panel_name.clone();
+            state = migrate(state)?;
+            if state.panel_name == panel_name {
+                return Some(state);
+            }
+        }
+
+        // The migrations may rename the panels in a cycle, keep the last state.
+        if registry.migrations.contains_key(&state.panel_name) {
+            tracing::warn!(
+                "the migrations of the `{}` panel exceed {} renames",
+                state.panel_name,
+                MAX_MIGRATIONS
+            );
+        }
+        Some(state)
+    }
 }
 impl Global for PanelRegistry {}
 
@@ -369,3 +527,24 @@ where
         .items
         .insert(panel_name.to_string(), Arc::new(deserialize));
 }
+
+/// Register a migration for the state of the panel by panel_name to global registry.
+///
+/// The `migrate` is called with the state restored by [`DockArea::load`] before building the panel,
+/// check the [`PanelState::version`] to upgrade the state of an old [`Panel::panel_version`].
+///
+/// - Return the state with a new `panel_name` if the panel has been renamed.
+/// - Return `None` to drop the panel from the layout if it has been removed.
+///
+/// The migrations are registered separately from [`register_panel`], because they are keyed by
+/// the panel_name in the saved layout, which can be a name that is no longer registered by
+/// [`register_panel`] after the panel has been renamed or removed.
+pub fn register_panel_migration<F>(cx: &mut App, panel_name: &str, migrate: F)
+where
+    F: Fn(PanelState) -> Option<PanelState> + 'static,
+{
+    PanelRegistry::init(cx);
+    PanelRegistry::global_mut(cx)
+        .migrations
+        .insert(panel_name.to_string(), Arc::new(migrate));
+}
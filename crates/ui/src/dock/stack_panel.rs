@@ -14,7 +14,7 @@ use super::{DockArea, Panel, PanelEvent, PanelState, PanelView, TabPanel};
 use gpui::{
     App, AppContext as _, Axis, Context, DismissEvent, Entity, EventEmitter, FocusHandle,
     Focusable, IntoElement, ParentElement, Pixels, Render, Styled, Subscription, WeakEntity,
-    Window,
+    Window, prelude::FluentBuilder as _,
 };
 use smallvec::SmallVec;
 
@@ -42,8 +42,12 @@ impl Panel for StackPanel {
         }
     }
     fn dump(&self, cx: &App) -> PanelState {
-        let sizes = self.state.read(cx).sizes().clone();
+        let resizable = self.state.read(cx);
+        let sizes = resizable.sizes().clone();
         let mut state = PanelState::new(self);
+        state.locked = (0..self.panels.len())
+            .filter(|ix| resizable.is_locked(*ix))
+            .collect();
         for panel in &self.panels {
             state.add_child(panel.dump(cx));
             state.info = PanelInfo::stack(sizes.clone(), self.axis);
@@ -400,6 +404,96 @@ impl StackPanel {
         });
     }
 
+    /// Set the split ratios of the panels, e.g. `[0.25, 0.75]`.
+    ///
+    /// The ratios are normalized to sum up to `1.0`, so the panels keep the same proportions
+    /// whatever the size of the stack panel is.
+    ///
+    /// Returns `false` and the ratios are ignored if the number of them is not the same as the
+    /// panels.
+    pub fn set_split_ratio(
+        &mut self,
+        ratios: Vec<f32>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if ratios.len() != self.panels.len() {
+            return false;
+        }
+
+        self.state.update(cx, |state, cx| {
+            state.set_fractions(ratios, cx);
+        });
+        cx.emit(PanelEvent::LayoutChanged);
+        cx.notify();
+        true
+    }
+
+    /// Returns the split ratios of the panels, the sum is `1.0`.
+    pub fn split_ratio(&self, cx: &App) -> Vec<f32> {
+        self.state.read(cx).fractions()
+    }
+
+    /// Lock the size of the panel, default: false
+    ///
+    /// The locked panel keeps its size when other panels are added, removed or the stack panel
+    /// is resized, and it can't be resized by dragging. The lock is saved in the [`PanelState`].
+    pub fn lock_size(
+        &mut self,
+        panel: Arc<dyn PanelView>,
+        locked: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.index_of_panel(panel) else {
+            return;
+        };
+
+        self.state.update(cx, |state, cx| {
+            state.set_locked(ix, locked, cx);
+        });
+        cx.notify();
+    }
+
+    /// Lock the sizes of the panels at the `ixs`, to restore the locks from the [`PanelState`].
+    pub(super) fn lock_sizes_at(&mut self, ixs: &[usize], cx: &mut Context<Self>) {
+        self.state.update(cx, |state, cx| {
+            for ix in ixs {
+                state.set_locked(*ix, true, cx);
+            }
+        });
+        cx.notify();
+    }
+
+    /// Returns true if the size of the panel is locked.
+    pub fn is_size_locked(&self, panel: Arc<dyn PanelView>, cx: &App) -> bool {
+        self.index_of_panel(panel)
+            .is_some_and(|ix| self.state.read(cx).is_locked(ix))
+    }
+
+    /// Even out the sizes of the panels, including the nested stack panels.
+    ///
+    /// The locked panels keep their sizes, the others share the rest equally.
+    pub fn even_out(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.state.read(cx);
+        let locked = (0..self.panels.len())
+            .map(|ix| state.is_locked(ix))
+            .collect::<Vec<_>>();
+        let sizes = state
+            .sizes()
+            .iter()
+            .map(|size| size.as_f32())
+            .collect::<Vec<_>>();
+        let ratios = even_ratios(&sizes, &locked);
+        self.set_split_ratio(ratios, window, cx);
+
+        for panel in self.panels.clone() {
+            if let Ok(stack_panel) = panel.view().downcast::<Self>() {
+                stack_panel.update(cx, |stack_panel, cx| stack_panel.even_out(window, cx));
+            }
+        }
+    }
+
     /// Change the axis of the stack panel.
     pub(super) fn set_axis(&mut self, axis: Axis, _: &mut Window, cx: &mut Context<Self>) {
         self.axis = axis;
@@ -424,11 +518,65 @@ impl Render for StackPanel {
                 ResizablePanelGroup::new("stack-panel-group")
                     .with_state(&self.state)
                     .axis(self.axis)
-                    .children(self.panels.clone().into_iter().map(|panel| {
-                        resizable_panel()
-                            .child(panel.view())
-                            .visible(panel.visible(cx))
-                    })),
+                    .children(
+                        self.panels
+                            .clone()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(ix, panel)| {
+                                resizable_panel()
+                                    .child(panel.view())
+                                    .visible(panel.visible(cx))
+                                    .when(self.state.read(cx).is_locked(ix), |this| {
+                                        this.flex_none()
+                                    })
+                            }),
+                    ),
             )
     }
 }
+
+/// The ratios to even out the panels, the `locked` panels keep their sizes.
+fn even_ratios(sizes: &[f32], locked: &[bool]) -> Vec<f32> {
+    let total = sizes.iter().sum::<f32>();
+    let locked_size = sizes
+        .iter()
+        .zip(locked)
+        .filter(|(_, locked)| **locked)
+        .map(|(size, _)| size)
+        .sum::<f32>();
+    let unlocked_count = locked.iter().filter(|locked| !**locked).count();
+    if total <= 0. {
+        return vec![1.; sizes.len()];
+    }
+    if unlocked_count == 0 {
+        return sizes.to_vec();
+    }
+
+    let even_size = (total - locked_size).max(0.) / unlocked_count as f32;
+    sizes
+        .iter()
+        .zip(locked)
+        .map(|(size, locked)| if *locked { *size } else { even_size })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::even_ratios;
+
+    #[test]
+    fn test_even_ratios() {
+        assert_eq!(even_ratios(&[], &[]), Vec::<f32>::new());
+        assert_eq!(
+            even_ratios(&[100., 300.], &[false, false]),
+            vec![200., 200.]
+        );
+        assert_eq!(
+            even_ratios(&[100., 300., 200.], &[true, false, false]),
+            vec![100., 250., 250.]
+        );
+        assert_eq!(even_ratios(&[100., 300.], &[true, true]), vec![100., 300.]);
+        assert_eq!(even_ratios(&[0., 0.], &[false, false]), vec![1., 1.]);
+    }
+}
@@ -1,8 +1,14 @@
+use std::sync::Arc;
+
 use gpui::{App, AppContext, Axis, Bounds, Entity, Pixels, WeakEntity, Window, point, px, size};
 use itertools::Itertools as _;
 use serde::{Deserialize, Serialize};
 
-use super::{Dock, DockArea, DockItem, DockPlacement, Panel, PanelRegistry};
+use super::{Dock, DockArea, DockItem, DockPlacement, Panel, PanelRegistry, PanelView};
+
+/// The live panels with their dumped states, to reuse instead of building the panels again
+/// when loading a state, see [`DockArea::apply_layout`].
+pub(super) type ReusablePanels = Vec<(PanelState, Arc<dyn PanelView>)>;
 
 /// Used to serialize and deserialize the DockArea
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
@@ -19,6 +25,24 @@ pub struct DockAreaState {
     pub right_dock: Option<DockState>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bottom_dock: Option<DockState>,
+    /// The panels torn out into floating windows, see [`DockArea::set_tear_out`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub floating: Vec<PanelState>,
+}
+
+impl DockAreaState {
+    /// Returns the panels in the `reusable` that are not reused by loading the state.
+    pub(super) fn unused_panels(&self, reusable: &ReusablePanels) -> Vec<Arc<dyn PanelView>> {
+        let mut unused = reusable.clone();
+        [&self.left_dock, &self.right_dock, &self.bottom_dock]
+            .into_iter()
+            .flatten()
+            .map(|dock| &dock.panel)
+            .chain(std::iter::once(&self.center))
+            .chain(&self.floating)
+            .for_each(|state| state.take_all_reusable(&mut unused));
+        unused.into_iter().map(|(_, panel)| panel).collect()
+    }
 }
 
 /// Used to serialize and deserialize the Dock
@@ -28,6 +52,12 @@ pub struct DockState {
     placement: DockPlacement,
     size: Pixels,
     open: bool,
+    /// Whether the Dock is unpinned to auto hide, see [`Dock::set_pinned`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    unpinned: bool,
+    /// Whether to show the tool strip when the Dock is collapsed, see [`Dock::set_tool_strip`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    tool_strip: bool,
 }
 
 impl DockState {
@@ -38,6 +68,8 @@ impl DockState {
             placement: dock.placement,
             size: dock.size,
             open: dock.open,
+            unpinned: !dock.pinned,
+            tool_strip: dock.tool_strip,
             panel: dock.panel.view().dump(cx),
         }
     }
@@ -49,7 +81,20 @@ impl DockState {
         window: &mut Window,
         cx: &mut App,
     ) -> Entity<Dock> {
-        let item = self.panel.to_item(dock_area.clone(), window, cx);
+        self.to_dock_reusing(dock_area, &mut vec![], window, cx)
+    }
+
+    /// Convert the DockState to Dock, the panels in the `reusable` are reused if match.
+    pub(super) fn to_dock_reusing(
+        &self,
+        dock_area: WeakEntity<DockArea>,
+        reusable: &mut ReusablePanels,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Dock> {
+        let item = self
+            .panel
+            .to_item_reusing(dock_area.clone(), reusable, window, cx);
         cx.new(|cx| {
             Dock::from_state(
                 dock_area.clone(),
@@ -57,6 +102,8 @@ impl DockState {
                 self.size,
                 item,
                 self.open,
+                !self.unpinned,
+                self.tool_strip,
                 window,
                 cx,
             )
@@ -70,6 +117,19 @@ pub struct PanelState {
     pub panel_name: String,
     pub children: Vec<PanelState>,
     pub info: PanelInfo,
+    /// The version of the panel state, see [`Panel::panel_version`].
+    #[serde(default)]
+    pub version: usize,
+    /// The bounds of the floating window on the screen, if the panel is torn out from the dock.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floating_bounds: Option<Bounds<Pixels>>,
+    /// The dock the floating panel was torn out from, to dock it back after restored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floating_placement: Option<DockPlacement>,
+    /// The indexes of the children with locked sizes in the stack,
+    /// see [`StackPanel::lock_size`](super::StackPanel::lock_size).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locked: Vec<usize>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
@@ -81,7 +141,10 @@ pub struct TileMeta {
 impl Default for TileMeta {
     fn default() -> Self {
         Self {
-            bounds: Bounds { origin: point(px(10.), px(10.)), size: size(px(200.), px(200.)) },
+            bounds: Bounds {
+                origin: point(px(10.), px(10.)),
+                size: size(px(200.), px(200.)),
+            },
             z_index: 0,
         }
     }
@@ -110,7 +173,10 @@ pub enum PanelInfo {
 
 impl PanelInfo {
     pub fn stack(sizes: Vec<Pixels>, axis: Axis) -> Self {
-        Self::Stack { sizes, axis: if axis == Axis::Horizontal { 0 } else { 1 } }
+        Self::Stack {
+            sizes,
+            axis: if axis == Axis::Horizontal { 0 } else { 1 },
+        }
     }
 
     pub fn tabs(active_index: usize) -> Self {
@@ -127,9 +193,11 @@ impl PanelInfo {
 
     pub fn axis(&self) -> Option<Axis> {
         match self {
-            Self::Stack { axis, .. } => {
-                Some(if *axis == 0 { Axis::Horizontal } else { Axis::Vertical })
-            }
+            Self::Stack { axis, .. } => Some(if *axis == 0 {
+                Axis::Horizontal
+            } else {
+                Axis::Vertical
+            }),
             _ => None,
         }
     }
@@ -147,6 +215,31 @@ impl PanelInfo {
             _ => None,
         }
     }
+
+    /// Returns the info with only the children at the `ixs` are kept, the `ixs` must be sorted.
+    fn retain_children(&self, ixs: &[usize]) -> Self {
+        match self {
+            Self::Stack { sizes, axis } => Self::Stack {
+                sizes: ixs
+                    .iter()
+                    .filter_map(|ix| sizes.get(*ix).copied())
+                    .collect(),
+                axis: *axis,
+            },
+            Self::Tabs { active_index } => {
+                // Keep the active panel, or the panel before it if it has been removed.
+                let active_index = ixs.iter().rposition(|ix| ix <= active_index).unwrap_or(0);
+                Self::Tabs { active_index }
+            }
+            Self::Tiles { metas } => Self::Tiles {
+                metas: ixs
+                    .iter()
+                    .map(|ix| metas.get(*ix).copied().unwrap_or_default())
+                    .collect(),
+            },
+            Self::Panel(_) => self.clone(),
+        }
+    }
 }
 
 impl Default for PanelState {
@@ -155,38 +248,162 @@ impl Default for PanelState {
             panel_name: "".to_string(),
             children: Vec::new(),
             info: PanelInfo::Panel(serde_json::Value::Null),
+            version: 0,
+            floating_bounds: None,
+            floating_placement: None,
+            locked: Vec::new(),
         }
     }
 }
 
 impl PanelState {
     pub fn new<P: Panel>(panel: &P) -> Self {
-        Self { panel_name: panel.panel_name().to_string(), ..Default::default() }
+        Self {
+            panel_name: panel.panel_name().to_string(),
+            version: panel.panel_version(),
+            ..Default::default()
+        }
     }
 
     pub fn add_child(&mut self, panel: PanelState) {
         self.children.push(panel);
     }
 
+    /// Build the [`DockItem`] from the state.
+    ///
+    /// The states of the panels are migrated by the registered migrations first,
+    /// see [`register_panel_migration`](super::register_panel_migration).
     pub fn to_item(
         &self,
         dock_area: WeakEntity<DockArea>,
         window: &mut Window,
         cx: &mut App,
+    ) -> DockItem {
+        self.to_item_reusing(dock_area, &mut vec![], window, cx)
+    }
+
+    /// Build the [`DockItem`] from the state like [`Self::to_item`], but reuse the panels in
+    /// the `reusable` that have the same name and info, instead of building them again.
+    pub(super) fn to_item_reusing(
+        &self,
+        dock_area: WeakEntity<DockArea>,
+        reusable: &mut ReusablePanels,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> DockItem {
+        match self.migrate(cx) {
+            Some(state) => state.build_item(dock_area, reusable, window, cx),
+            // The removed panel is replaced by an empty tabs.
+            None if matches!(self.info, PanelInfo::Panel(_)) => {
+                DockItem::tabs(vec![], &dock_area, window, cx)
+            }
+            None => {
+                let info = self.info.retain_children(&[]);
+                let state = PanelState {
+                    children: vec![],
+                    info,
+                    locked: vec![],
+                    ..self.clone()
+                };
+                state.build_item(dock_area, reusable, window, cx)
+            }
+        }
+    }
+
+    /// Migrate the state and the children by the registered migrations.
+    ///
+    /// Returns `None` if the panel has been removed,
+    /// or all the children of the container panel have been removed.
+    fn migrate(&self, cx: &App) -> Option<PanelState> {
+        if let PanelInfo::Panel(_) = self.info {
+            return PanelRegistry::migrate(self.clone(), cx);
+        }
+
+        if self.children.is_empty() {
+            return Some(self.clone());
+        }
+
+        let mut ixs = vec![];
+        let mut children = vec![];
+        for (ix, child) in self.children.iter().enumerate() {
+            if let Some(child) = child.migrate(cx) {
+                ixs.push(ix);
+                children.push(child);
+            }
+        }
+
+        if children.is_empty() {
+            return None;
+        }
+
+        let info = self.info.retain_children(&ixs);
+        Some(PanelState {
+            panel_name: self.panel_name.clone(),
+            children,
+            info,
+            version: self.version,
+            floating_bounds: self.floating_bounds,
+            floating_placement: self.floating_placement,
+            locked: self.retain_locked(&ixs),
+        })
+    }
+
+    /// Returns the indexes of the locked children in the kept children at the `ixs`.
+    fn retain_locked(&self, ixs: &[usize]) -> Vec<usize> {
+        self.locked
+            .iter()
+            .filter_map(|ix| ixs.iter().position(|i| i == ix))
+            .collect()
+    }
+
+    /// Take the panel that has the same name and info as the state out of the `reusable`.
+    fn take_reusable(&self, reusable: &mut ReusablePanels) -> Option<Arc<dyn PanelView>> {
+        let ix = reusable.iter().position(|(state, _)| {
+            state.panel_name == self.panel_name && state.info == self.info
+        })?;
+        Some(reusable.remove(ix).1)
+    }
+
+    /// Take the panels of the state and the children out of the `reusable`.
+    fn take_all_reusable(&self, reusable: &mut ReusablePanels) {
+        if let PanelInfo::Panel(_) = self.info {
+            self.take_reusable(reusable);
+        }
+        for child in &self.children {
+            child.take_all_reusable(reusable);
+        }
+    }
+
+    fn build_item(
+        &self,
+        dock_area: WeakEntity<DockArea>,
+        reusable: &mut ReusablePanels,
+        window: &mut Window,
+        cx: &mut App,
     ) -> DockItem {
         let info = self.info.clone();
 
         let items: Vec<DockItem> = self
             .children
             .iter()
-            .map(|child| child.to_item(dock_area.clone(), window, cx))
+            .map(|child| child.build_item(dock_area.clone(), reusable, window, cx))
             .collect();
 
         match info {
             PanelInfo::Stack { sizes, axis } => {
-                let axis = if axis == 0 { Axis::Horizontal } else { Axis::Vertical };
+                let axis = if axis == 0 {
+                    Axis::Horizontal
+                } else {
+                    Axis::Vertical
+                };
                 let sizes = sizes.iter().map(|s| Some(*s)).collect_vec();
-                DockItem::split_with_sizes(axis, items, sizes, &dock_area, window, cx)
+                let item = DockItem::split_with_sizes(axis, items, sizes, &dock_area, window, cx);
+                if let DockItem::Split { view, .. } = &item {
+                    view.update(cx, |stack_panel, cx| {
+                        stack_panel.lock_sizes_at(&self.locked, cx)
+                    });
+                }
+                item
             }
             PanelInfo::Tabs { active_index } => {
                 if items.len() == 1 {
@@ -204,20 +421,28 @@ impl PanelState {
                     })
                     .collect_vec();
 
+                let active_index = active_index.min(items.len().saturating_sub(1));
                 DockItem::tabs(items, &dock_area, window, cx).active_index(active_index, cx)
             }
             PanelInfo::Panel(_) => {
-                let view = PanelRegistry::build_panel(
-                    &self.panel_name,
-                    dock_area.clone(),
-                    self,
-                    &info,
-                    window,
-                    cx,
-                );
-                DockItem::tabs(vec![view.into()], &dock_area, window, cx)
+                let view = match self.take_reusable(reusable) {
+                    Some(view) => view,
+                    None => PanelRegistry::build_panel(
+                        &self.panel_name,
+                        dock_area.clone(),
+                        self,
+                        &info,
+                        window,
+                        cx,
+                    )
+                    .into(),
+                };
+                DockItem::tabs(vec![view], &dock_area, window, cx)
+            }
+            PanelInfo::Tiles { mut metas } => {
+                metas.resize(items.len(), TileMeta::default());
+                DockItem::tiles(items, metas, &dock_area, window, cx)
             }
-            PanelInfo::Tiles { metas } => DockItem::tiles(items, metas, &dock_area, window, cx),
         }
     }
 }
@@ -227,6 +452,7 @@ mod tests {
     use gpui::px;
 
     use super::*;
+    use crate::dock::register_panel_migration;
     #[test]
     fn test_deserialize_item_state() {
         let json = include_str!("../fixtures/layout.json");
@@ -236,12 +462,16 @@ mod tests {
         assert_eq!(state.center.children.len(), 2);
         assert_eq!(state.center.children[0].panel_name, "TabPanel");
         assert_eq!(state.center.children[1].children.len(), 1);
-        assert_eq!(state.center.children[1].children[0].panel_name, "StoryContainer");
+        assert_eq!(
+            state.center.children[1].children[0].panel_name,
+            "StoryContainer"
+        );
         assert_eq!(state.center.children[1].panel_name, "TabPanel");
 
         let left_dock = state.left_dock.unwrap();
         assert_eq!(left_dock.open, true);
         assert_eq!(left_dock.size, px(350.0));
+        assert_eq!(left_dock.tool_strip, false);
         assert_eq!(left_dock.placement, DockPlacement::Left);
         assert_eq!(left_dock.panel.panel_name, "TabPanel");
         assert_eq!(left_dock.panel.children.len(), 1);
@@ -260,5 +490,144 @@ mod tests {
         assert_eq!(right_dock.panel.panel_name, "TabPanel");
         assert_eq!(right_dock.panel.children.len(), 1);
         assert_eq!(right_dock.panel.children[0].panel_name, "StoryContainer");
+
+        assert!(state.floating.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_floating_panel_state() {
+        let mut panel = PanelState::default();
+        panel.panel_name = "TabPanel".to_string();
+        let bounds = Bounds::new(point(px(100.), px(50.)), size(px(480.), px(360.)));
+        panel.floating_bounds = Some(bounds);
+        panel.floating_placement = Some(DockPlacement::Left);
+        let state = DockAreaState {
+            floating: vec![panel.clone()],
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&state).unwrap();
+        let state: DockAreaState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.floating, vec![panel]);
+        assert_eq!(state.center.floating_bounds, None);
+        assert!(!json.contains("\"floating_bounds\":null"));
+        assert!(!json.contains("\"floating_placement\":null"));
+    }
+
+    #[test]
+    fn test_retain_children_info() {
+        let info = PanelInfo::stack(vec![px(100.), px(200.), px(300.)], Axis::Vertical);
+        assert_eq!(
+            info.retain_children(&[0, 2]).sizes(),
+            Some(&vec![px(100.), px(300.)])
+        );
+        let state = PanelState {
+            info,
+            locked: vec![1, 2],
+            ..Default::default()
+        };
+        assert_eq!(state.retain_locked(&[0, 2]), vec![1]);
+
+        let info = PanelInfo::tabs(2);
+        assert_eq!(info.retain_children(&[0, 1, 2]).active_index(), Some(2));
+        assert_eq!(info.retain_children(&[0, 3]).active_index(), Some(0));
+        assert_eq!(info.retain_children(&[3, 4]).active_index(), Some(0));
+
+        let meta = TileMeta::from(Bounds::new(point(px(1.), px(2.)), size(px(3.), px(4.))));
+        let info = PanelInfo::tiles(vec![TileMeta::default(), meta]);
+        let expected = PanelInfo::tiles(vec![meta, TileMeta::default()]);
+        assert_eq!(info.retain_children(&[1, 2]), expected);
+    }
+
+    #[gpui::test]
+    fn test_migrate_panel_state(cx: &mut gpui::TestAppContext) {
+        let panel = |name: &str, version: usize| PanelState {
+            panel_name: name.to_string(),
+            version,
+            ..Default::default()
+        };
+        let state = PanelState {
+            panel_name: "StackPanel".to_string(),
+            children: vec![
+                PanelState {
+                    panel_name: "TabPanel".to_string(),
+                    children: vec![panel("OldPanel", 0), panel("RemovedPanel", 0)],
+                    info: PanelInfo::tabs(1),
+                    ..Default::default()
+                },
+                PanelState {
+                    panel_name: "TabPanel".to_string(),
+                    children: vec![panel("RemovedPanel", 0)],
+                    info: PanelInfo::tabs(0),
+                    ..Default::default()
+                },
+            ],
+            info: PanelInfo::stack(vec![px(100.), px(200.)], Axis::Horizontal),
+            ..Default::default()
+        };
+
+        cx.update(|cx| {
+            register_panel_migration(cx, "OldPanel", |mut state| {
+                state.panel_name = "NewPanel".to_string();
+                Some(state)
+            });
+            register_panel_migration(cx, "NewPanel", |mut state| {
+                if state.version < 1 {
+                    state.version = 1;
+                }
+                Some(state)
+            });
+            register_panel_migration(cx, "RemovedPanel", |_| None);
+
+            let state = state.migrate(cx).unwrap();
+            assert_eq!(state.children.len(), 1);
+            assert_eq!(state.info.sizes(), Some(&vec![px(100.)]));
+
+            let tabs = &state.children[0];
+            assert_eq!(tabs.info.active_index(), Some(0));
+            assert_eq!(tabs.children, vec![panel("NewPanel", 1)]);
+        });
+    }
+
+    #[gpui::test]
+    fn test_removed_panel_to_item(cx: &mut gpui::TestAppContext) {
+        let cx = cx.add_empty_window();
+        cx.update(|window, cx| {
+            register_panel_migration(cx, "RemovedPanel", |_| None);
+
+            let state = PanelState {
+                panel_name: "RemovedPanel".to_string(),
+                ..Default::default()
+            };
+            let DockItem::Tabs { items, .. } = state.to_item(WeakEntity::new_invalid(), window, cx)
+            else {
+                panic!("the removed panel should be an empty tabs");
+            };
+            assert!(items.is_empty());
+        });
+    }
+
+    #[gpui::test]
+    fn test_migrate_renamed_panel_cycle(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            register_panel_migration(cx, "PingPanel", |mut state| {
+                state.panel_name = "PongPanel".to_string();
+                state.version += 1;
+                Some(state)
+            });
+            register_panel_migration(cx, "PongPanel", |mut state| {
+                state.panel_name = "PingPanel".to_string();
+                state.version += 1;
+                Some(state)
+            });
+
+            let state = PanelState {
+                panel_name: "PingPanel".to_string(),
+                ..Default::default()
+            };
+            let state = state.migrate(cx).unwrap();
+            assert_eq!(state.version, crate::dock::panel::MAX_MIGRATIONS);
+            assert_eq!(state.panel_name, "PingPanel");
+        });
     }
 }
@@ -1,19 +1,23 @@
-use std::sync::Arc;
+use std::{collections::HashSet, rc::Rc, sync::Arc};
 
 use gpui::{
-    Anchor, App, AppContext, Context, DismissEvent, Div, DragMoveEvent, Empty, Entity,
-    EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, ParentElement,
-    Pixels, Render, ScrollHandle, SharedString, StatefulInteractiveElement, StyleRefinement,
-    Styled, WeakEntity, Window, div, prelude::FluentBuilder, px, relative, rems,
+    Anchor, AnyElement, App, AppContext, ClickEvent, Context, DismissEvent, Div, DragMoveEvent,
+    Empty, Entity, EntityId, EventEmitter, FocusHandle, Focusable, InteractiveElement as _,
+    IntoElement, ParentElement, Pixels, Render, ScrollHandle, SharedString,
+    StatefulInteractiveElement, StyleRefinement, Styled, Subscription, WeakEntity, Window, div,
+    prelude::FluentBuilder, px, relative, rems,
 };
 use rust_i18n::t;
 
 use crate::{
-    ActiveTheme, AxisExt, IconName, Placement, Selectable, Sizable,
+    ActiveTheme, AxisExt, Icon, IconName, InteractiveElementExt as _, Placement, Selectable,
+    Sizable,
     button::{Button, ButtonVariants as _},
     dock::PanelInfo,
     h_flex,
-    menu::{DropdownMenu, PopupMenu},
+    input::{Escape, Input, InputEvent, InputState, SelectAll},
+    menu::{DropdownMenu, PopupMenu, PopupMenuItem},
+    spinner::Spinner,
     tab::{Tab, TabBar},
     v_flex,
 };
@@ -32,8 +36,10 @@ struct TabState {
     active_panel: Option<Arc<dyn PanelView>>,
 }
 
+/// The drag payload of a panel tab in the [`DockArea`], it can be accepted by the drop
+/// targets outside the dock, e.g. `div().on_drop(|drag: &DragPanel, window, cx| ..)`.
 #[derive(Clone)]
-pub(crate) struct DragPanel {
+pub struct DragPanel {
     pub(crate) panel: Arc<dyn PanelView>,
     pub(crate) tab_panel: Entity<TabPanel>,
 }
@@ -42,6 +48,11 @@ impl DragPanel {
     pub(crate) fn new(panel: Arc<dyn PanelView>, tab_panel: Entity<TabPanel>) -> Self {
         Self { panel, tab_panel }
     }
+
+    /// Returns the dragged panel.
+    pub fn panel(&self) -> &Arc<dyn PanelView> {
+        &self.panel
+    }
 }
 
 impl Render for DragPanel {
@@ -66,7 +77,7 @@ impl Render for DragPanel {
 
 pub struct TabPanel {
     focus_handle: FocusHandle,
-    dock_area: WeakEntity<DockArea>,
+    pub(super) dock_area: WeakEntity<DockArea>,
     /// The stock_panel can be None, if is None, that means the panels can't be split or move
     stack_panel: Option<WeakEntity<StackPanel>>,
     pub(crate) panels: Vec<Arc<dyn PanelView>>,
@@ -85,6 +96,25 @@ pub struct TabPanel {
     will_split_placement: Option<Placement>,
     /// Is TabPanel used in Tiles.
     in_tiles: bool,
+    /// Is TabPanel used in a floating window, see [`DockArea::set_tear_out`].
+    pub(super) floating: bool,
+    /// The placement of the Dock that contains this TabPanel, cached when added to the Dock.
+    pub(super) dock_placement: Option<DockPlacement>,
+    /// Only mount the panel content when it is first activated, see [`Self::lazy`].
+    pub(super) lazy: bool,
+    /// Keep the deactivated panel content mounted, see [`Self::keep_alive`].
+    pub(super) keep_alive: bool,
+    /// The panels that have been activated, for the lazy mounting.
+    activated_panels: HashSet<EntityId>,
+    pub(super) on_will_activate: Option<Rc<dyn Fn(&Arc<dyn PanelView>, &mut Window, &mut App)>>,
+    /// The inline rename of a panel title, see [`Panel::editable_title`].
+    renaming: Option<TabRename>,
+}
+
+struct TabRename {
+    panel_id: EntityId,
+    input: Entity<InputState>,
+    _subscription: Subscription,
 }
 
 impl Panel for TabPanel {
@@ -98,6 +128,22 @@ impl Panel for TabPanel {
             .unwrap_or("Empty Tab".into_any_element())
     }
 
+    fn icon(&self, cx: &App) -> Option<Icon> {
+        self.active_panel(cx).and_then(|panel| panel.icon(cx))
+    }
+
+    fn tab_icon(&self, cx: &App) -> Option<Icon> {
+        self.active_panel(cx).and_then(|panel| panel.tab_icon(cx))
+    }
+
+    fn dirty(&self, cx: &App) -> bool {
+        self.active_panel(cx).is_some_and(|panel| panel.dirty(cx))
+    }
+
+    fn loading(&self, cx: &App) -> bool {
+        self.active_panel(cx).is_some_and(|panel| panel.loading(cx))
+    }
+
     fn closable(&self, cx: &App) -> bool {
         if !self.closable {
             return false;
@@ -179,9 +225,44 @@ impl TabPanel {
             collapsed: false,
             closable: true,
             in_tiles: false,
+            floating: false,
+            dock_placement: None,
+            lazy: false,
+            keep_alive: false,
+            activated_panels: HashSet::new(),
+            on_will_activate: None,
+            renaming: None,
         }
     }
 
+    /// Only mount the panel content when the tab is first activated.
+    ///
+    /// This only affects when [`Self::keep_alive`] is enabled, otherwise only the
+    /// active panel is mounted.
+    pub fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    /// Set true to keep the deactivated panel content mounted (hidden), so that
+    /// the element states like the scroll position and the input state are preserved.
+    ///
+    /// Default is false, the deactivated panel content is dropped from the element tree.
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Set a callback to be called before a panel is activated by switching tabs,
+    /// e.g. to prefetch the data of the panel.
+    pub fn on_will_activate(
+        mut self,
+        on_will_activate: impl Fn(&Arc<dyn PanelView>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_will_activate = Some(Rc::new(on_will_activate));
+        self
+    }
+
     /// Mark the TabPanel as being used in Tiles.
     pub(super) fn set_in_tiles(&mut self, in_tiles: bool) {
         self.in_tiles = in_tiles;
@@ -211,12 +292,17 @@ impl TabPanel {
         self.active_ix
     }
 
-    fn set_active_ix(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn set_active_ix(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
         if ix == self.active_ix {
             return;
         }
 
         let last_active_ix = self.active_ix;
+        if let (Some(on_will_activate), Some(panel)) =
+            (self.on_will_activate.clone(), self.panels.get(ix).cloned())
+        {
+            on_will_activate(&panel, window, cx);
+        }
 
         self.active_ix = ix;
         self.pending_scroll_to_ix = Some(ix);
@@ -306,7 +392,7 @@ impl TabPanel {
         cx.notify();
     }
 
-    fn insert_panel_at(
+    pub(super) fn insert_panel_at(
         &mut self,
         panel: Arc<dyn PanelView>,
         ix: usize,
@@ -349,6 +435,7 @@ impl TabPanel {
     ) {
         panel.on_removed(window, cx);
         let panel_view = panel.view();
+        self.activated_panels.remove(&panel_view.entity_id());
         self.panels.retain(|p| p.view() != panel_view);
         if self.active_ix >= self.panels.len() {
             self.set_active_ix(self.panels.len().saturating_sub(1), window, cx)
@@ -430,6 +517,14 @@ impl TabPanel {
     ///
     /// E.g. if the parent and self only have one panel, it is not draggable.
     fn draggable(&self, cx: &App) -> bool {
+        if self.floating {
+            // Drag the tabs out of the floating window to dock them back.
+            return self
+                .dock_area
+                .upgrade()
+                .is_some_and(|dock_area| !dock_area.read(cx).is_locked());
+        }
+
         !self.is_locked(cx) && !self.is_last_panel(cx)
     }
 
@@ -440,6 +535,134 @@ impl TabPanel {
         !self.is_locked(cx)
     }
 
+    /// Start the inline rename of the panel at the `ix`, if the panel has an editable title.
+    fn start_rename(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(panel) = self.panels.get(ix).cloned() else {
+            return;
+        };
+        if self.is_renaming(&panel, cx) {
+            return;
+        }
+        let Some(title) = panel.editable_title(cx) else {
+            return;
+        };
+
+        let input = cx.new(|cx| {
+            let mut input = InputState::new(window, cx).default_value(title);
+            input.select_all(&SelectAll, window, cx);
+            input
+        });
+        input.update(cx, |input, cx| input.focus(window, cx));
+
+        let _subscription =
+            cx.subscribe_in(&input, window, |this, _, event, window, cx| match event {
+                InputEvent::PressEnter { .. } => this.confirm_rename(true, window, cx),
+                InputEvent::Blur => this.confirm_rename(false, window, cx),
+                _ => {}
+            });
+
+        self.renaming = Some(TabRename {
+            panel_id: panel.panel_id(cx),
+            input,
+            _subscription,
+        });
+        cx.notify();
+    }
+
+    /// Apply the inline rename, and focus back to the panel if `focus` is true.
+    fn confirm_rename(&mut self, focus: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(rename) = self.renaming.take() else {
+            return;
+        };
+        cx.notify();
+
+        let Some(panel) = self
+            .panels
+            .iter()
+            .find(|panel| panel.panel_id(cx) == rename.panel_id)
+            .cloned()
+        else {
+            return;
+        };
+
+        let title: SharedString = rename.input.read(cx).value().trim().to_string().into();
+        if !title.is_empty() && Some(&title) != panel.editable_title(cx).as_ref() {
+            panel.rename(title, window, cx);
+        }
+        if focus {
+            panel.focus_handle(cx).focus(window, cx);
+        }
+    }
+
+    fn cancel_rename(&mut self, _: &Escape, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(rename) = self.renaming.take() else {
+            return;
+        };
+
+        if let Some(panel) = self
+            .panels
+            .iter()
+            .find(|panel| panel.panel_id(cx) == rename.panel_id)
+        {
+            panel.focus_handle(cx).focus(window, cx);
+        }
+        cx.notify();
+    }
+
+    fn is_renaming(&self, panel: &Arc<dyn PanelView>, cx: &App) -> bool {
+        self.renaming
+            .as_ref()
+            .is_some_and(|rename| rename.panel_id == panel.panel_id(cx))
+    }
+
+    /// Render the title of the panel in the tab, with the icon, loading and dirty status.
+    fn render_panel_title(
+        &self,
+        panel: &Arc<dyn PanelView>,
+        title: AnyElement,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        if let Some(rename) = self
+            .renaming
+            .as_ref()
+            .filter(|_| self.is_renaming(panel, cx))
+        {
+            return div()
+                .w_32()
+                .on_action(cx.listener(Self::cancel_rename))
+                .child(Input::new(&rename.input).xsmall().appearance(false))
+                .into_any_element();
+        }
+
+        h_flex()
+            .min_w_0()
+            .gap_1p5()
+            .map(|this| {
+                if panel.loading(cx) {
+                    this.child(Spinner::new().xsmall())
+                } else {
+                    this.children(panel.tab_icon(cx).map(|icon| icon.xsmall()))
+                }
+            })
+            .child(
+                div()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .child(title),
+            )
+            .when(panel.dirty(cx), |this| {
+                this.child(
+                    div()
+                        .flex_shrink_0()
+                        .size_1p5()
+                        .rounded_full()
+                        .bg(cx.theme().muted_foreground),
+                )
+            })
+            .into_any_element()
+    }
+
     fn render_toolbar(
         &mut self,
         state: &TabState,
@@ -453,6 +676,14 @@ impl TabPanel {
         let zoomed = self.zoomed;
         let view = cx.entity().clone();
         let zoomable_toolbar_visible = state.zoomable.map_or(false, |v| v.toolbar_visible());
+        // The placement and pinned state of the Dock that contains this TabPanel.
+        let dock_pinned = self.dock_placement.and_then(|placement| {
+            let dock_area = self.dock_area.upgrade()?;
+            let dock_area = dock_area.read(cx);
+            dock_area
+                .is_dock_collapsible(placement, cx)
+                .then(|| (placement, dock_area.is_dock_pinned(placement, cx)))
+        });
 
         h_flex()
             .gap_1()
@@ -490,6 +721,33 @@ impl TabPanel {
                     this
                 }
             })
+            .when_some(
+                dock_pinned.filter(|_| !zoomed),
+                |this, (placement, pinned)| {
+                    let (icon, tooltip) = if pinned {
+                        (IconName::PinOff, t!("Dock.Unpin"))
+                    } else {
+                        (IconName::Pin, t!("Dock.Pin"))
+                    };
+
+                    this.child(
+                        Button::new("pin")
+                            .icon(icon)
+                            .xsmall()
+                            .ghost()
+                            .tab_stop(false)
+                            .tooltip(tooltip)
+                            .on_click(cx.listener(move |view, _, window, cx| {
+                                let Some(dock_area) = view.dock_area.upgrade() else {
+                                    return;
+                                };
+                                dock_area.update(cx, |dock_area, cx| {
+                                    dock_area.set_dock_pinned(placement, !pinned, window, cx);
+                                });
+                            })),
+                    )
+                },
+            )
             .child(
                 Button::new("menu")
                     .icon(IconName::Ellipsis)
@@ -499,9 +757,23 @@ impl TabPanel {
                     .dropdown_menu({
                         let zoomable = state.zoomable.map_or(false, |v| v.menu_visible());
                         let closable = state.closable;
+                        let dock_placement = self.dock_placement;
 
                         move |menu, window, cx| {
-                            view.update(cx, |this, cx| {
+                            let dock_area = view.read(cx).dock_area.clone();
+                            let closed_titles = dock_area
+                                .upgrade()
+                                .map(|dock_area| {
+                                    dock_area
+                                        .read(cx)
+                                        .closed_panels()
+                                        .iter()
+                                        .map(|closed| closed.title.clone())
+                                        .collect::<Vec<_>>()
+                                })
+                                .unwrap_or_default();
+
+                            let menu = view.update(cx, |this, cx| {
                                 this.dropdown_menu(menu, window, cx)
                                     .separator()
                                     .menu_with_disabled(
@@ -513,11 +785,51 @@ impl TabPanel {
                                         Box::new(ToggleZoom),
                                         !zoomable,
                                     )
-                                    .when(closable, |this| {
+                                    .when(closable || dock_placement.is_some(), |this| {
                                         this.separator()
-                                            .menu(t!("Dock.Close"), Box::new(ClosePanel))
                                     })
-                            })
+                                    .when(closable, |this| {
+                                        this.menu(t!("Dock.Close"), Box::new(ClosePanel))
+                                    })
+                                    .when_some(dock_placement, |this, placement| {
+                                        let dock_area = dock_area.clone();
+                                        this.item(
+                                            PopupMenuItem::new(t!("Dock.Close Dock")).on_click(
+                                                move |_, window, cx| {
+                                                    _ = dock_area.update(cx, |dock_area, cx| {
+                                                        dock_area
+                                                            .close_dock(placement, window, cx)
+                                                            .detach();
+                                                    });
+                                                },
+                                            ),
+                                        )
+                                    })
+                            });
+
+                            if closed_titles.is_empty() {
+                                return menu;
+                            }
+                            menu.separator().submenu(
+                                t!("Dock.Recently Closed"),
+                                window,
+                                cx,
+                                move |mut menu, _, _| {
+                                    for (ix, title) in closed_titles.iter().enumerate() {
+                                        let dock_area = dock_area.clone();
+                                        menu =
+                                            menu.item(PopupMenuItem::new(title.clone()).on_click(
+                                                move |_, window, cx| {
+                                                    _ = dock_area.update(cx, |dock_area, cx| {
+                                                        dock_area
+                                                            .reopen_closed_panel(ix, window, cx);
+                                                    });
+                                                },
+                                            ));
+                                    }
+                                    menu
+                                },
+                            )
                         }
                     })
                     .anchor(Anchor::TopRight),
@@ -640,6 +952,7 @@ impl TabPanel {
             }
 
             let title_style = panel.title_style(cx);
+            let is_renaming = self.is_renaming(panel, cx);
 
             return h_flex()
                 .justify_between()
@@ -671,8 +984,15 @@ impl TabPanel {
                         .overflow_hidden()
                         .text_ellipsis()
                         .whitespace_nowrap()
-                        .child(panel.title(window, cx))
-                        .when(state.draggable, |this| {
+                        .child({
+                            let title = panel.title(window, cx);
+                            self.render_panel_title(panel, title, cx)
+                        })
+                        .on_double_click(cx.listener(|this, _, window, cx| {
+                            let ix = this.active_ix;
+                            this.start_rename(ix, window, cx);
+                        }))
+                        .when(state.draggable && !is_renaming, |this| {
                             this.on_drag(
                                 DragPanel {
                                     panel: panel.clone(),
@@ -742,24 +1062,28 @@ impl TabPanel {
                 if self.collapsed {
                     active = false;
                 }
+                let is_renaming = self.is_renaming(panel, cx);
 
                 Some(
                     Tab::new()
                         .ix(ix)
                         .tab_bar_prefix(has_extend_dock_button)
-                        .map(|this| {
-                            if let Some(tab_name) = panel.tab_name(cx) {
-                                this.child(tab_name)
-                            } else {
-                                this.child(panel.title(window, cx))
-                            }
+                        .child({
+                            let title = match panel.tab_name(cx) {
+                                Some(tab_name) => tab_name.into_any_element(),
+                                None => panel.title(window, cx),
+                            };
+                            self.render_panel_title(panel, title, cx)
                         })
                         .selected(active)
                         .on_click(cx.listener({
                             let is_collapsed = self.collapsed;
                             let dock_area = self.dock_area.clone();
-                            move |view, _, window, cx| {
+                            move |view, event: &ClickEvent, window, cx| {
                                 view.set_active_ix(ix, window, cx);
+                                if event.click_count() == 2 && !is_collapsed {
+                                    view.start_rename(ix, window, cx);
+                                }
 
                                 // Open dock if clicked on the collapsed bottom dock
                                 if is_bottom_dock && is_collapsed {
@@ -770,7 +1094,7 @@ impl TabPanel {
                             }
                         }))
                         .when(!droppable, |this| {
-                            this.when(state.draggable, |this| {
+                            this.when(state.draggable && !is_renaming, |this| {
                                 this.on_drag(
                                     DragPanel::new(panel.clone(), view.clone()),
                                     |drag, _, _, cx| {
@@ -846,8 +1170,32 @@ impl TabPanel {
             .into_any_element()
     }
 
+    /// Returns the panels to mount, and true if it is the active panel.
+    fn mounted_panels(&mut self, state: &TabState, cx: &App) -> Vec<(Arc<dyn PanelView>, bool)> {
+        let Some(active_panel) = state.active_panel.clone() else {
+            return vec![];
+        };
+
+        let active_id = active_panel.view().entity_id();
+        self.activated_panels.insert(active_id);
+        if !self.keep_alive {
+            return vec![(active_panel, true)];
+        }
+
+        self.visible_panels(cx)
+            .filter_map(|panel| {
+                let id = panel.view().entity_id();
+                if self.lazy && !self.activated_panels.contains(&id) {
+                    return None;
+                }
+
+                Some((panel, id == active_id))
+            })
+            .collect()
+    }
+
     fn render_active_panel(
-        &self,
+        &mut self,
         state: &TabState,
         _: &mut Window,
         cx: &mut Context<Self>,
@@ -856,9 +1204,10 @@ impl TabPanel {
             return Empty {}.into_any_element();
         }
 
-        let Some(active_panel) = state.active_panel.as_ref() else {
+        let mounted_panels = self.mounted_panels(state, cx);
+        if mounted_panels.is_empty() {
             return Empty {}.into_any_element();
-        };
+        }
 
         let is_render_in_tabs = self.panels.len() > 1 && self.inner_padding(cx);
 
@@ -867,18 +1216,16 @@ impl TabPanel {
             .group("")
             .flex_1()
             .when(is_render_in_tabs, |this| this.pt_2())
-            .child(
+            .children(mounted_panels.into_iter().map(|(panel, is_active)| {
+                let view = panel.view();
                 div()
-                    .id("tab-content")
+                    .id(("tab-content", view.entity_id().as_u64()))
                     .overflow_y_scroll()
                     .overflow_x_hidden()
                     .flex_1()
-                    .child(
-                        active_panel
-                            .view()
-                            .cached(StyleRefinement::default().absolute().size_full()),
-                    ),
-            )
+                    .when(!is_active, |this| this.hidden())
+                    .child(view.cached(StyleRefinement::default().absolute().size_full()))
+            }))
             .when(state.droppable, |this| {
                 this.on_drag_move(cx.listener(Self::on_panel_drag_move))
                     .child(
@@ -1000,8 +1347,13 @@ impl TabPanel {
         cx: &mut Context<Self>,
     ) {
         let dock_area = self.dock_area.clone();
+        let dock_placement = self.dock_placement;
         // wrap the panel in a TabPanel
-        let new_tab_panel = cx.new(|cx| Self::new(None, dock_area.clone(), window, cx));
+        let new_tab_panel = cx.new(|cx| {
+            let mut tab_panel = Self::new(None, dock_area.clone(), window, cx);
+            tab_panel.dock_placement = dock_placement;
+            tab_panel
+        });
         new_tab_panel.update(cx, |view, cx| {
             view.add_panel(panel, window, cx);
         });
@@ -1109,7 +1461,7 @@ impl TabPanel {
         cx.emit(PanelEvent::LayoutChanged);
     }
 
-    fn focus_active_panel(&self, window: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn focus_active_panel(&self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(active_panel) = self.active_panel(cx) {
             active_panel.focus_handle(cx).focus(window, cx);
         }
@@ -1154,9 +1506,40 @@ impl TabPanel {
         if !self.closable(cx) {
             return;
         }
-        if let Some(panel) = self.active_panel(cx) {
-            self.remove_panel(panel, window, cx);
-        }
+        let Some(panel) = self.active_panel(cx) else {
+            return;
+        };
+
+        let can_close = panel.can_close(window, cx);
+        cx.spawn_in(window, async move |this, cx| {
+            if !can_close.await {
+                return;
+            }
+
+            _ = this.update_in(cx, |this, window, cx| {
+                this.close_panel(panel, window, cx);
+            });
+        })
+        .detach();
+    }
+
+    /// Close the panel after [`Panel::can_close`] is confirmed.
+    fn close_panel(
+        &mut self,
+        panel: Arc<dyn PanelView>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // The panel may be removed or moved while waiting for the confirmation.
+        let Some(ix) = self.panels.iter().position(|p| p.view() == panel.view()) else {
+            return;
+        };
+
+        let tab_panel = cx.entity().downgrade();
+        _ = self.dock_area.update(cx, |dock_area, cx| {
+            dock_area.push_closed_panel(&panel, tab_panel, ix, cx);
+        });
+        self.remove_panel(panel, window, cx);
 
         // Remove self from the parent DockArea.
         // This is ensure to remove from Tiles
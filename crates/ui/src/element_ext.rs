@@ -1,5 +1,6 @@
 use gpui::{
-    AnyElement, App, Bounds, IntoElement, ParentElement, Pixels, Styled as _, Window, canvas,
+    AnyElement, App, Bounds, ElementId, IntoElement, ParentElement, Pixels, SharedString,
+    Styled as _, Window, canvas,
 };
 
 use crate::{Sizable, Size};
@@ -49,6 +50,27 @@ pub trait ElementExt: ParentElement + Sized {
                 .size_full(),
         )
     }
+
+    /// Record the element with the `id` and the rendered `text` in the frames drawn by the
+    /// [`TestHarness`], to find it by [`TestHarness::find_by_id`] or [`TestHarness::find_by_text`].
+    ///
+    /// Does nothing without the `test-support` feature.
+    ///
+    /// [`TestHarness`]: crate::test::TestHarness
+    /// [`TestHarness::find_by_id`]: crate::test::TestHarness::find_by_id
+    /// [`TestHarness::find_by_text`]: crate::test::TestHarness::find_by_text
+    fn test_record(self, id: Option<ElementId>, text: Option<SharedString>) -> Self {
+        #[cfg(any(test, feature = "test-support"))]
+        return self.on_prepaint(move |bounds, _, cx| {
+            crate::test::record_element(id, text, bounds, cx);
+        });
+
+        #[cfg(not(any(test, feature = "test-support")))]
+        {
+            _ = (id, text);
+            self
+        }
+    }
 }
 
 impl<T: ParentElement> ElementExt for T {}
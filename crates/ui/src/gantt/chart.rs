@@ -0,0 +1,612 @@
+use std::ops::Range;
+
+use chrono::{Datelike as _, Duration, NaiveDate, Weekday};
+use gpui::{
+    AnyElement, App, AppContext as _, Bounds, ContentMask, Context, DragMoveEvent, ElementId,
+    Empty, Entity, EntityId, Hsla, InteractiveElement as _, IntoElement, ListSizingBehavior,
+    MouseButton, MouseDownEvent, ParentElement, PathBuilder, Pixels, Point, Render, RenderOnce,
+    ScrollWheelEvent, StatefulInteractiveElement as _, StyleRefinement, Styled, Window, canvas,
+    div, fill, point, prelude::FluentBuilder as _, px, relative, uniform_list,
+};
+use rust_i18n::t;
+
+use crate::{
+    ActiveTheme as _, StyledExt as _,
+    element_ext::ElementExt as _,
+    h_flex,
+    scroll::{ScrollableElement as _, ScrollbarHandle as _},
+    theme::Colorize as _,
+    v_flex,
+};
+
+use super::{
+    state::{GanttState, HANDLE_WIDTH},
+    task::GanttTask,
+};
+
+const HEADER_HEIGHT: Pixels = px(48.);
+/// The horizontal gap of the dependency arrows from the bars.
+const ARROW_GAP: Pixels = px(8.);
+
+#[derive(Clone)]
+struct DragGanttBar(EntityId);
+
+impl Render for DragGanttBar {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
+
+/// Returns the points of the dependency arrow from the end of a bar at `from`
+/// to the start of another bar at `to`.
+///
+/// The arrow goes around the rows when the `to` bar starts before the `from` bar ends.
+fn dependency_path(
+    from: Point<Pixels>,
+    to: Point<Pixels>,
+    gap: Pixels,
+    row_height: Pixels,
+) -> Vec<Point<Pixels>> {
+    if to.x - from.x >= gap * 2. {
+        let x = from.x + gap;
+        return vec![from, point(x, from.y), point(x, to.y), to];
+    }
+
+    let y = if to.y >= from.y {
+        to.y - row_height / 2.
+    } else {
+        to.y + row_height / 2.
+    };
+    vec![
+        from,
+        point(from.x + gap, from.y),
+        point(from.x + gap, y),
+        point(to.x - gap, y),
+        point(to.x - gap, to.y),
+        to,
+    ]
+}
+
+/// The dependency arrows and the today marker, painted over the rows.
+struct Overlay {
+    arrows: Vec<Vec<Point<Pixels>>>,
+    today_x: Pixels,
+    arrow_color: Hsla,
+    today_color: Hsla,
+}
+
+impl GanttState {
+    /// Returns the range of the visible days from the first date of the timeline.
+    fn visible_days(&self) -> Range<i64> {
+        let start = (self.scroll_x / self.day_width).floor() as i64;
+        let end =
+            ((self.scroll_x + self.timeline_bounds.size.width) / self.day_width).ceil() as i64 + 1;
+        start.max(0)..end.min(self.total_days)
+    }
+
+    /// Returns the range of the visible rows in the viewport of the `height`.
+    fn visible_rows(&self, height: Pixels) -> Range<usize> {
+        let offset_y = -self.scroll_handle.offset().y;
+        let start = (offset_y / self.row_height).floor().max(0.) as usize;
+        let end = ((offset_y + height) / self.row_height).ceil().max(0.) as usize;
+        start..end.min(self.tasks.len())
+    }
+
+    fn layout_overlay(&self, bounds: Bounds<Pixels>, cx: &App) -> Overlay {
+        let rows = self.visible_rows(bounds.size.height);
+        let offset_y = self.scroll_handle.offset().y;
+        let row_center = |ix: usize| {
+            bounds.top() + offset_y + self.row_height * ix as f32 + self.row_height / 2.
+        };
+        let left = bounds.left() - self.scroll_x;
+
+        let mut arrows = vec![];
+        for &(pred, succ) in &self.dependencies {
+            // Skip the arrows out of the visible rows.
+            if pred.max(succ) < rows.start || pred.min(succ) >= rows.end {
+                continue;
+            }
+
+            let from = point(left + self.date_x(self.tasks[pred].end), row_center(pred));
+            let to = point(left + self.date_x(self.tasks[succ].start), row_center(succ));
+            arrows.push(dependency_path(from, to, ARROW_GAP, self.row_height));
+        }
+
+        Overlay {
+            arrows,
+            today_x: left + self.date_x(self.today) + self.day_width / 2.,
+            arrow_color: cx.theme().muted_foreground,
+            today_color: cx.theme().danger,
+        }
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        let days = self.visible_days();
+
+        let mut months = vec![];
+        let first_visible = self.first_date + Duration::days(days.start);
+        let last_visible = self.first_date + Duration::days(days.end);
+        let mut month = first_visible.with_day(1).unwrap_or(first_visible);
+        while month < last_visible {
+            let next = if month.month() == 12 {
+                NaiveDate::from_ymd_opt(month.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1)
+            }
+            .unwrap_or(last_visible);
+
+            let x = self.date_x(month) - self.scroll_x;
+            months.push(
+                div()
+                    .absolute()
+                    .top_0()
+                    .left(x)
+                    .w(self.date_x(next) - self.date_x(month))
+                    .h_full()
+                    .border_l_1()
+                    .border_color(cx.theme().table_row_border)
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .child(
+                        // Keep the label visible while scrolling.
+                        div().pl((-x).max(px(0.)) + px(8.)).child(format!(
+                            "{}/{:02}",
+                            month.year(),
+                            month.month()
+                        )),
+                    ),
+            );
+            month = next;
+        }
+
+        let step = if self.day_width >= px(20.) {
+            Some(1)
+        } else if self.day_width * 7. >= px(28.) {
+            Some(7)
+        } else {
+            None
+        };
+        let day_labels = step.map(|step| {
+            // Include the week started before the first visible day.
+            ((days.start - step + 1).max(0)..days.end)
+                .filter_map(|d| {
+                    let date = self.first_date + Duration::days(d);
+                    if step == 7 && date.weekday() != Weekday::Mon {
+                        return None;
+                    }
+
+                    let weekend = matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+                    Some(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .left(self.day_width * d as f32 - self.scroll_x)
+                            .w(self.day_width * step as f32)
+                            .h_full()
+                            .flex()
+                            .items_center()
+                            .when(step == 1, |this| this.justify_center())
+                            .when(step > 1, |this| this.pl_1())
+                            .border_l_1()
+                            .border_color(cx.theme().table_row_border)
+                            .when(weekend && step == 1, |this| {
+                                this.text_color(cx.theme().muted_foreground)
+                            })
+                            .child(date.day().to_string()),
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+
+        h_flex()
+            .h(HEADER_HEIGHT)
+            .flex_shrink_0()
+            .text_xs()
+            .bg(cx.theme().table_head)
+            .text_color(cx.theme().table_head_foreground)
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .child(
+                h_flex()
+                    .w(self.label_width)
+                    .h_full()
+                    .flex_shrink_0()
+                    .px_3()
+                    .border_r_1()
+                    .border_color(cx.theme().table_row_border)
+                    .font_medium()
+                    .child(t!("Gantt.task").to_string()),
+            )
+            .child(
+                v_flex()
+                    .relative()
+                    .flex_1()
+                    .h_full()
+                    .overflow_hidden()
+                    .on_prepaint(move |bounds, _, cx| {
+                        state.update(cx, |state, cx| {
+                            if state.timeline_bounds != bounds {
+                                state.timeline_bounds = bounds;
+                                state.set_scroll_x(state.scroll_x);
+                                cx.notify();
+                            }
+                        });
+                    })
+                    .child(div().relative().h_1_2().children(months))
+                    .child(
+                        div()
+                            .relative()
+                            .h_1_2()
+                            .border_t_1()
+                            .border_color(cx.theme().table_row_border)
+                            .when_some(day_labels, |this, labels| this.children(labels))
+                            .child(
+                                div()
+                                    .absolute()
+                                    .top_0()
+                                    .bottom_0()
+                                    .left(
+                                        self.date_x(self.today) + self.day_width / 2.
+                                            - self.scroll_x
+                                            - px(1.),
+                                    )
+                                    .w(px(2.))
+                                    .bg(cx.theme().danger),
+                            ),
+                    ),
+            )
+    }
+
+    fn render_bar(&self, ix: usize, task: &GanttTask, cx: &mut Context<Self>) -> AnyElement {
+        let entity_id = cx.entity_id();
+        let color = task.color.unwrap_or(cx.theme().chart_2);
+        let x = self.date_x(task.start) - self.scroll_x;
+        let inset = px(6.);
+        let height = self.row_height - inset * 2.;
+        let dragging = self.dragging.map(|drag| drag.ix) == Some(ix);
+
+        let bar = if task.is_milestone() {
+            div()
+                .id(("bar", ix))
+                .absolute()
+                .top(inset)
+                .left(x - height / 2.)
+                .size(height)
+                .child(
+                    canvas(
+                        |_, _, _| {},
+                        move |bounds, _, window, _| {
+                            let center = bounds.center();
+                            let half = bounds.size.height / 2.;
+                            let mut builder = PathBuilder::fill();
+                            builder.move_to(point(center.x, center.y - half));
+                            builder.line_to(point(center.x + half, center.y));
+                            builder.line_to(point(center.x, center.y + half));
+                            builder.line_to(point(center.x - half, center.y));
+                            builder.close();
+                            if let Ok(path) = builder.build() {
+                                window.paint_path(path, color);
+                            }
+                        },
+                    )
+                    .size_full(),
+                )
+        } else {
+            let width = self.day_width * task.days() as f32;
+            let resizable = !task.is_locked() && width >= HANDLE_WIDTH * 3.;
+
+            div()
+                .id(("bar", ix))
+                .absolute()
+                .top(inset)
+                .left(x)
+                .h(height)
+                .w(width)
+                .overflow_hidden()
+                .rounded(cx.theme().radius.min(px(4.)))
+                .bg(color.opacity(0.35))
+                .when(dragging, |this| this.shadow_md())
+                .child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .h_full()
+                        .w(relative(task.progress))
+                        .bg(color),
+                )
+                .child(
+                    h_flex()
+                        .relative()
+                        .h_full()
+                        .px_2()
+                        .text_xs()
+                        .whitespace_nowrap()
+                        .text_color(cx.theme().foreground)
+                        .child(task.name.clone()),
+                )
+                .when(resizable, |this| {
+                    this.child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .bottom_0()
+                            .left_0()
+                            .w(HANDLE_WIDTH)
+                            .cursor_col_resize(),
+                    )
+                    .child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .bottom_0()
+                            .right_0()
+                            .w(HANDLE_WIDTH)
+                            .cursor_col_resize(),
+                    )
+                })
+        };
+
+        bar.when(!task.is_locked(), |this| {
+            this.cursor_grab()
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, e: &MouseDownEvent, _, _| {
+                        this.start_drag(ix, e.position.x);
+                    }),
+                )
+                .on_drag(DragGanttBar(entity_id), |drag, _, _, cx| {
+                    cx.stop_propagation();
+                    cx.new(|_| drag.clone())
+                })
+        })
+        .into_any_element()
+    }
+
+    fn render_row(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let task = &self.tasks[ix];
+        let selected = self.selected_ix == Some(ix);
+
+        h_flex()
+            .id(ix)
+            .w_full()
+            .h(self.row_height)
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .when(selected, |this| this.bg(cx.theme().table_active))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, _, cx| this.select(ix, cx)),
+            )
+            .child(
+                div()
+                    .w(self.label_width)
+                    .h_full()
+                    .flex_shrink_0()
+                    .flex()
+                    .items_center()
+                    .px_3()
+                    .border_r_1()
+                    .border_color(cx.theme().table_row_border)
+                    .bg(cx.theme().table)
+                    .when(selected, |this| this.bg(cx.theme().table_active))
+                    .text_sm()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .text_ellipsis()
+                    .child(task.name.clone()),
+            )
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .h_full()
+                    .overflow_hidden()
+                    .child(self.render_bar(ix, task, cx)),
+            )
+    }
+}
+
+impl Render for GanttState {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.entity();
+        let label_width = self.label_width;
+
+        v_flex()
+            .id("gantt-state")
+            .size_full()
+            .on_scroll_wheel(cx.listener(|this, e: &ScrollWheelEvent, window, cx| {
+                let delta = e.delta.pixel_delta(window.line_height());
+                if delta.x != px(0.) {
+                    this.set_scroll_x(this.scroll_x - delta.x);
+                    cx.notify();
+                }
+            }))
+            .on_drag_move(cx.listener(|this, e: &DragMoveEvent<DragGanttBar>, _, cx| {
+                if e.drag(cx).0 != cx.entity_id() {
+                    return;
+                }
+
+                this.drag_to(e.event.position.x, cx);
+            }))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _, _, cx| this.end_drag(cx)),
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                cx.listener(|this, _, _, cx| this.end_drag(cx)),
+            )
+            .child(self.render_header(cx))
+            .child(
+                div()
+                    .id("gantt-body")
+                    .relative()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .child(
+                        uniform_list(
+                            "gantt-rows",
+                            self.tasks.len(),
+                            cx.processor(move |state, range: Range<usize>, _, cx| {
+                                range.map(|ix| state.render_row(ix, cx)).collect::<Vec<_>>()
+                            }),
+                        )
+                        .size_full()
+                        .track_scroll(&self.scroll_handle)
+                        .with_sizing_behavior(ListSizingBehavior::Auto),
+                    )
+                    .child(
+                        canvas(
+                            move |bounds, _, cx| state.read(cx).layout_overlay(bounds, cx),
+                            move |bounds, overlay, window, _| {
+                                window.with_content_mask(Some(ContentMask { bounds }), |window| {
+                                    for points in &overlay.arrows {
+                                        let mut builder = PathBuilder::stroke(px(1.));
+                                        builder.move_to(points[0]);
+                                        for p in &points[1..] {
+                                            builder.line_to(*p);
+                                        }
+                                        if let Ok(path) = builder.build() {
+                                            window.paint_path(path, overlay.arrow_color);
+                                        }
+
+                                        let to = points[points.len() - 1];
+                                        let mut builder = PathBuilder::fill();
+                                        builder.move_to(point(to.x - px(5.), to.y - px(4.)));
+                                        builder.line_to(to);
+                                        builder.line_to(point(to.x - px(5.), to.y + px(4.)));
+                                        builder.close();
+                                        if let Ok(path) = builder.build() {
+                                            window.paint_path(path, overlay.arrow_color);
+                                        }
+                                    }
+
+                                    window.paint_quad(fill(
+                                        Bounds::new(
+                                            point(overlay.today_x - px(1.), bounds.top()),
+                                            gpui::size(px(2.), bounds.size.height),
+                                        ),
+                                        overlay.today_color.opacity(0.6),
+                                    ));
+                                });
+                            },
+                        )
+                        .absolute()
+                        .top_0()
+                        .bottom_0()
+                        .right_0()
+                        .left(label_width),
+                    )
+                    .vertical_scrollbar(&self.scroll_handle),
+            )
+    }
+}
+
+/// A gantt chart to show the [`GanttTask`]s on a zoomable timeline.
+///
+/// The rows are virtualized, so it's fine to have hundreds of tasks.
+///
+/// ```ignore
+/// let state = cx.new(|cx| {
+///     GanttState::new(cx).tasks(vec![
+///         GanttTask::new("design", "Design", start, start + Duration::days(5)),
+///         GanttTask::new("build", "Build", start + Duration::days(5), start + Duration::days(12))
+///             .depends_on("design"),
+///     ])
+/// });
+///
+/// GanttChart::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct GanttChart {
+    id: ElementId,
+    state: Entity<GanttState>,
+    style: StyleRefinement,
+    label_width: Option<Pixels>,
+}
+
+impl GanttChart {
+    /// Create a new gantt chart with the `state`.
+    pub fn new(state: &Entity<GanttState>) -> Self {
+        Self {
+            id: ElementId::Name(format!("gantt-{}", state.entity_id()).into()),
+            state: state.clone(),
+            style: StyleRefinement::default(),
+            label_width: None,
+        }
+    }
+
+    /// Set the width of the task name column, default is 200px.
+    pub fn label_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.label_width = Some(width.into());
+        self
+    }
+}
+
+impl Styled for GanttChart {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for GanttChart {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(label_width) = self.label_width {
+            self.state
+                .update(cx, |state, _| state.label_width = label_width);
+        }
+
+        div()
+            .id(self.id)
+            .size_full()
+            .overflow_hidden()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px};
+
+    use super::dependency_path;
+
+    #[test]
+    fn test_dependency_path() {
+        let from = point(px(100.), px(16.));
+
+        // The next bar starts after the gap.
+        let to = point(px(140.), px(48.));
+        assert_eq!(
+            dependency_path(from, to, px(8.), px(32.)),
+            vec![from, point(px(108.), px(16.)), point(px(108.), px(48.)), to]
+        );
+
+        // The next bar starts before the end, go around by the row boundary.
+        let to = point(px(60.), px(80.));
+        assert_eq!(
+            dependency_path(from, to, px(8.), px(32.)),
+            vec![
+                from,
+                point(px(108.), px(16.)),
+                point(px(108.), px(64.)),
+                point(px(52.), px(64.)),
+                point(px(52.), px(80.)),
+                to,
+            ]
+        );
+
+        // The next bar is above.
+        let to = point(px(60.), px(-16.));
+        assert_eq!(
+            dependency_path(from, to, px(8.), px(32.))[2],
+            point(px(108.), px(0.))
+        );
+    }
+}
@@ -0,0 +1,7 @@
+mod chart;
+mod state;
+mod task;
+
+pub use chart::*;
+pub use state::*;
+pub use task::*;
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+
+use chrono::{Local, NaiveDate};
+use gpui::{
+    App, Bounds, Context, EventEmitter, Pixels, ScrollStrategy, SharedString,
+    UniformListScrollHandle, px,
+};
+
+use super::task::{
+    BarDragKind, GanttTask, bar_drag_kind, days_for_offset, drag_dates, timeline_range,
+};
+
+/// The days before the first and after the last task on the timeline.
+const TIMELINE_PADDING: i64 = 7;
+pub(super) const MIN_DAY_WIDTH: Pixels = px(4.);
+pub(super) const MAX_DAY_WIDTH: Pixels = px(96.);
+const ZOOM_FACTOR: f32 = 1.25;
+/// The width of the resize handles on the edges of a bar.
+pub(super) const HANDLE_WIDTH: Pixels = px(6.);
+
+/// Events emitted by the [`GanttState`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum GanttEvent {
+    /// A task bar was moved or resized by dragging, with the new dates.
+    Change {
+        id: SharedString,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    /// A task was selected by clicking.
+    Select(SharedString),
+}
+
+/// The bar being dragged.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct BarDrag {
+    pub(super) ix: usize,
+    pub(super) kind: BarDragKind,
+    origin_x: Pixels,
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+/// State of the [`super::GanttChart`].
+pub struct GanttState {
+    pub(super) tasks: Vec<GanttTask>,
+    /// The `(predecessor, successor)` row indices of the dependencies.
+    pub(super) dependencies: Vec<(usize, usize)>,
+    pub(super) scroll_handle: UniformListScrollHandle,
+    /// The first date of the timeline.
+    pub(super) first_date: NaiveDate,
+    /// The number of days of the timeline.
+    pub(super) total_days: i64,
+    pub(super) day_width: Pixels,
+    pub(super) row_height: Pixels,
+    pub(super) label_width: Pixels,
+    /// The horizontal scroll offset of the timeline, positive to scroll right.
+    pub(super) scroll_x: Pixels,
+    pub(super) today: NaiveDate,
+    pub(super) selected_ix: Option<usize>,
+    pub(super) dragging: Option<BarDrag>,
+    /// The bounds of the timeline header, used to map the mouse position to the dates.
+    pub(super) timeline_bounds: Bounds<Pixels>,
+}
+
+impl EventEmitter<GanttEvent> for GanttState {}
+
+impl GanttState {
+    /// Create a new empty gantt state.
+    pub fn new(_: &mut App) -> Self {
+        let today = Local::now().naive_local().date();
+        let (first_date, total_days) = timeline_range(&[], today, TIMELINE_PADDING);
+
+        Self {
+            tasks: Vec::new(),
+            dependencies: Vec::new(),
+            scroll_handle: UniformListScrollHandle::default(),
+            first_date,
+            total_days,
+            day_width: px(24.),
+            row_height: px(32.),
+            label_width: px(200.),
+            scroll_x: px(0.),
+            today,
+            selected_ix: None,
+            dragging: None,
+            timeline_bounds: Bounds::default(),
+        }
+    }
+
+    /// Set the tasks, each task is rendered in a row.
+    pub fn tasks(mut self, tasks: impl Into<Vec<GanttTask>>) -> Self {
+        self.tasks = tasks.into();
+        self.update_dependencies();
+        self.update_range();
+        self
+    }
+
+    /// Update the tasks, the selection and the dragging are reset.
+    pub fn set_tasks(&mut self, tasks: impl Into<Vec<GanttTask>>, cx: &mut Context<Self>) {
+        self.tasks = tasks.into();
+        self.selected_ix = None;
+        self.dragging = None;
+        self.update_dependencies();
+        self.update_range();
+        cx.notify();
+    }
+
+    /// Returns the task of the row `ix`.
+    pub fn task(&self, ix: usize) -> Option<&GanttTask> {
+        self.tasks.get(ix)
+    }
+
+    /// Set the date of the today marker, default is the local date.
+    pub fn today(mut self, today: NaiveDate) -> Self {
+        self.today = today;
+        self.update_range();
+        self
+    }
+
+    /// Set the width of a day on the timeline, default is 24px.
+    pub fn day_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.day_width = width.into().max(MIN_DAY_WIDTH).min(MAX_DAY_WIDTH);
+        self
+    }
+
+    /// Returns the width of a day on the timeline.
+    pub fn zoom(&self) -> Pixels {
+        self.day_width
+    }
+
+    /// Update the width of a day on the timeline, the date at the center of
+    /// the timeline is kept in place.
+    pub fn set_zoom(&mut self, width: impl Into<Pixels>, cx: &mut Context<Self>) {
+        let width = width.into().max(MIN_DAY_WIDTH).min(MAX_DAY_WIDTH);
+        if width == self.day_width {
+            return;
+        }
+
+        let half = self.timeline_bounds.size.width / 2.;
+        let center = (self.scroll_x + half) / self.day_width;
+        self.day_width = width;
+        self.set_scroll_x(width * center - half);
+        cx.notify();
+    }
+
+    /// Zoom in the timeline.
+    pub fn zoom_in(&mut self, cx: &mut Context<Self>) {
+        self.set_zoom(self.day_width * ZOOM_FACTOR, cx);
+    }
+
+    /// Zoom out the timeline.
+    pub fn zoom_out(&mut self, cx: &mut Context<Self>) {
+        self.set_zoom(self.day_width / ZOOM_FACTOR, cx);
+    }
+
+    /// Set the height of the rows, default is 32px.
+    pub fn row_height(mut self, height: impl Into<Pixels>) -> Self {
+        self.row_height = height.into();
+        self
+    }
+
+    /// Returns the selected row index.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected_ix
+    }
+
+    /// Set the selected row index, or `None` to clear the selection.
+    pub fn set_selected_index(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.selected_ix = ix.filter(|ix| *ix < self.tasks.len());
+        cx.notify();
+    }
+
+    /// Returns the scroll handle of the rows.
+    pub fn scroll_handle(&self) -> &UniformListScrollHandle {
+        &self.scroll_handle
+    }
+
+    /// Scroll the timeline to center the today marker.
+    pub fn scroll_to_today(&mut self, cx: &mut Context<Self>) {
+        self.scroll_to_date(self.today, cx);
+    }
+
+    /// Scroll the timeline to center the `date`.
+    pub fn scroll_to_date(&mut self, date: NaiveDate, cx: &mut Context<Self>) {
+        self.set_scroll_x(self.date_x(date) - self.timeline_bounds.size.width / 2.);
+        cx.notify();
+    }
+
+    /// Scroll to the row of the task with the `id`, and the timeline to its start date.
+    pub fn scroll_to_task(&mut self, id: &SharedString, cx: &mut Context<Self>) {
+        let Some(ix) = self.tasks.iter().position(|task| &task.id == id) else {
+            return;
+        };
+
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        self.set_scroll_x(self.date_x(self.tasks[ix].start) - self.day_width * 2.);
+        cx.notify();
+    }
+
+    /// Returns the x offset of the `date` from the start of the timeline.
+    pub(super) fn date_x(&self, date: NaiveDate) -> Pixels {
+        self.day_width * (date - self.first_date).num_days() as f32
+    }
+
+    pub(super) fn max_scroll_x(&self) -> Pixels {
+        (self.day_width * self.total_days as f32 - self.timeline_bounds.size.width).max(px(0.))
+    }
+
+    pub(super) fn set_scroll_x(&mut self, scroll_x: Pixels) {
+        self.scroll_x = scroll_x.min(self.max_scroll_x()).max(px(0.));
+    }
+
+    fn update_dependencies(&mut self) {
+        let index = self
+            .tasks
+            .iter()
+            .enumerate()
+            .map(|(ix, task)| (task.id.clone(), ix))
+            .collect::<HashMap<_, _>>();
+
+        self.dependencies = self
+            .tasks
+            .iter()
+            .enumerate()
+            .flat_map(|(ix, task)| {
+                task.dependencies
+                    .iter()
+                    .filter_map(|id| index.get(id).map(|pred| (*pred, ix)))
+                    .filter(|(pred, ix)| pred != ix)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+
+    fn update_range(&mut self) {
+        let (first_date, total_days) = timeline_range(&self.tasks, self.today, TIMELINE_PADDING);
+        // Keep the visible dates in place when the timeline grows at the start.
+        self.scroll_x += self.day_width * (self.first_date - first_date).num_days() as f32;
+        self.first_date = first_date;
+        self.total_days = total_days;
+        self.set_scroll_x(self.scroll_x);
+    }
+
+    pub(super) fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(task) = self.tasks.get(ix) else {
+            return;
+        };
+
+        self.selected_ix = Some(ix);
+        cx.emit(GanttEvent::Select(task.id.clone()));
+        cx.notify();
+    }
+
+    /// Start to drag the bar of the row `ix` by the mouse down at the window `x`.
+    pub(super) fn start_drag(&mut self, ix: usize, x: Pixels) {
+        let Some(task) = self.tasks.get(ix) else {
+            return;
+        };
+        if task.is_locked() {
+            return;
+        }
+
+        let bar_x = self.timeline_bounds.left() + self.date_x(task.start) - self.scroll_x;
+        let width = self.day_width * task.days() as f32;
+        self.dragging = Some(BarDrag {
+            ix,
+            kind: bar_drag_kind(x - bar_x, width, HANDLE_WIDTH),
+            origin_x: x,
+            start: task.start,
+            end: task.end,
+        });
+    }
+
+    pub(super) fn drag_to(&mut self, x: Pixels, cx: &mut Context<Self>) {
+        let Some(drag) = self.dragging else {
+            return;
+        };
+
+        let delta = days_for_offset(x - drag.origin_x, self.day_width);
+        let (start, end) = drag_dates(drag.kind, drag.start, drag.end, delta);
+        let Some(task) = self.tasks.get_mut(drag.ix) else {
+            return;
+        };
+        if task.start != start || task.end != end {
+            task.start = start;
+            task.end = end;
+            cx.notify();
+        }
+    }
+
+    pub(super) fn end_drag(&mut self, cx: &mut Context<Self>) {
+        let Some(drag) = self.dragging.take() else {
+            return;
+        };
+        let Some(task) = self.tasks.get(drag.ix) else {
+            return;
+        };
+
+        if task.start != drag.start || task.end != drag.end {
+            cx.emit(GanttEvent::Change {
+                id: task.id.clone(),
+                start: task.start,
+                end: task.end,
+            });
+            self.update_range();
+        }
+        cx.notify();
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::{Duration, NaiveDate};
+use gpui::{Hsla, Pixels, SharedString};
+
+/// A task of the [`super::GanttChart`], rendered as a bar from `start` to `end`.
+///
+/// The `end` date is exclusive, a task of one day has `end = start + 1`,
+/// and a task with `start == end` is rendered as a milestone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GanttTask {
+    pub id: SharedString,
+    pub name: SharedString,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// The progress of the task in `0.0..=1.0`.
+    pub progress: f32,
+    /// The ids of the tasks this task depends on (finish-to-start).
+    pub dependencies: Vec<SharedString>,
+    pub color: Option<Hsla>,
+    pub(super) locked: bool,
+}
+
+impl GanttTask {
+    /// Create a new task with the `id` to identify it in the events, and the `name` to display.
+    pub fn new(
+        id: impl Into<SharedString>,
+        name: impl Into<SharedString>,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            start,
+            end: end.max(start),
+            progress: 0.,
+            dependencies: Vec::new(),
+            color: None,
+            locked: false,
+        }
+    }
+
+    /// Set the progress of the task, clamped to `0.0..=1.0`.
+    pub fn progress(mut self, progress: f32) -> Self {
+        self.progress = progress.clamp(0., 1.);
+        self
+    }
+
+    /// Add a dependency on the task of the `id`, an arrow is drawn from the end of that task.
+    pub fn depends_on(mut self, id: impl Into<SharedString>) -> Self {
+        self.dependencies.push(id.into());
+        self
+    }
+
+    /// Set the bar color, default is the `chart_2` color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set true to disable moving and resizing the bar, default is false.
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    /// Returns true if the task can't be moved or resized.
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Returns true if the task is a milestone (zero duration).
+    #[inline]
+    pub fn is_milestone(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the duration of the task in days.
+    #[inline]
+    pub fn days(&self) -> i64 {
+        (self.end - self.start).num_days()
+    }
+}
+
+/// The part of a bar being dragged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum BarDragKind {
+    Move,
+    ResizeStart,
+    ResizeEnd,
+}
+
+/// Returns the new `(start, end)` of a bar dragged by `delta` days.
+///
+/// Resizing keeps at least one day, so a bar never turns into a milestone by resizing.
+pub(super) fn drag_dates(
+    kind: BarDragKind,
+    start: NaiveDate,
+    end: NaiveDate,
+    delta: i64,
+) -> (NaiveDate, NaiveDate) {
+    let delta = Duration::days(delta);
+    match kind {
+        BarDragKind::Move => (start + delta, end + delta),
+        BarDragKind::ResizeStart => ((start + delta).min(end - Duration::days(1)), end),
+        BarDragKind::ResizeEnd => (start, (end + delta).max(start + Duration::days(1))),
+    }
+}
+
+/// Returns the part of a bar of the `width` to drag by the mouse down at `x` relative to the bar.
+///
+/// The edges of the `handle` width resize the bar, the bars too narrow to have the handles are only moved.
+pub(super) fn bar_drag_kind(x: Pixels, width: Pixels, handle: Pixels) -> BarDragKind {
+    if width < handle * 3. {
+        return BarDragKind::Move;
+    }
+
+    if x < handle {
+        BarDragKind::ResizeStart
+    } else if x > width - handle {
+        BarDragKind::ResizeEnd
+    } else {
+        BarDragKind::Move
+    }
+}
+
+/// Returns the number of whole days for the horizontal drag distance `dx`.
+pub(super) fn days_for_offset(dx: Pixels, day_width: Pixels) -> i64 {
+    if day_width <= Pixels::ZERO {
+        return 0;
+    }
+
+    (dx / day_width).round() as i64
+}
+
+/// Returns the first date and the number of days of the timeline to cover the `tasks`
+/// and the `today`, with `padding` days on both sides.
+pub(super) fn timeline_range(
+    tasks: &[GanttTask],
+    today: NaiveDate,
+    padding: i64,
+) -> (NaiveDate, i64) {
+    let mut first = today;
+    let mut last = today + Duration::days(1);
+    for task in tasks {
+        first = first.min(task.start);
+        last = last.max(task.end);
+    }
+
+    let first = first - Duration::days(padding);
+    let last = last + Duration::days(padding);
+    (first, (last - first).num_days())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use gpui::{SharedString, px};
+
+    use super::{
+        BarDragKind, GanttTask, bar_drag_kind, days_for_offset, drag_dates, timeline_range,
+    };
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_drag_dates() {
+        let (start, end) = (date(3, 10), date(3, 14));
+
+        assert_eq!(
+            drag_dates(BarDragKind::Move, start, end, 3),
+            (date(3, 13), date(3, 17))
+        );
+        assert_eq!(
+            drag_dates(BarDragKind::Move, start, end, -10),
+            (date(2, 28), date(3, 4))
+        );
+        assert_eq!(
+            drag_dates(BarDragKind::ResizeStart, start, end, -2),
+            (date(3, 8), end)
+        );
+        assert_eq!(
+            drag_dates(BarDragKind::ResizeStart, start, end, 10),
+            (date(3, 13), end)
+        );
+        assert_eq!(
+            drag_dates(BarDragKind::ResizeEnd, start, end, 2),
+            (start, date(3, 16))
+        );
+        assert_eq!(
+            drag_dates(BarDragKind::ResizeEnd, start, end, -10),
+            (start, date(3, 11))
+        );
+    }
+
+    #[test]
+    fn test_bar_drag_kind() {
+        let handle = px(6.);
+        assert_eq!(
+            bar_drag_kind(px(2.), px(100.), handle),
+            BarDragKind::ResizeStart
+        );
+        assert_eq!(bar_drag_kind(px(50.), px(100.), handle), BarDragKind::Move);
+        assert_eq!(
+            bar_drag_kind(px(97.), px(100.), handle),
+            BarDragKind::ResizeEnd
+        );
+        // Too narrow to resize.
+        assert_eq!(bar_drag_kind(px(2.), px(12.), handle), BarDragKind::Move);
+        assert_eq!(bar_drag_kind(px(11.), px(12.), handle), BarDragKind::Move);
+    }
+
+    #[test]
+    fn test_days_for_offset() {
+        assert_eq!(days_for_offset(px(0.), px(20.)), 0);
+        assert_eq!(days_for_offset(px(9.), px(20.)), 0);
+        assert_eq!(days_for_offset(px(11.), px(20.)), 1);
+        assert_eq!(days_for_offset(px(-31.), px(20.)), -2);
+        assert_eq!(days_for_offset(px(100.), px(0.)), 0);
+    }
+
+    #[test]
+    fn test_timeline_range() {
+        let tasks = vec![
+            GanttTask::new("a", "A", date(3, 10), date(3, 14)),
+            GanttTask::new("b", "B", date(3, 12), date(3, 20)),
+        ];
+
+        assert_eq!(timeline_range(&tasks, date(3, 15), 2), (date(3, 8), 14));
+        // Today is out of the tasks.
+        assert_eq!(timeline_range(&tasks, date(3, 1), 0), (date(3, 1), 19));
+        assert_eq!(timeline_range(&[], date(3, 1), 1), (date(2, 28), 3));
+    }
+
+    #[test]
+    fn test_task() {
+        let task = GanttTask::new("a", "A", date(3, 10), date(3, 8)).progress(1.5);
+        assert!(task.is_milestone());
+        assert_eq!(task.days(), 0);
+        assert_eq!(task.progress, 1.);
+
+        let task = GanttTask::new("b", "B", date(3, 10), date(3, 12)).depends_on("a");
+        assert_eq!(task.days(), 2);
+        assert_eq!(task.dependencies, vec![SharedString::from("a")]);
+    }
+}
@@ -1,5 +1,4 @@
 use gpui::{App, ElementId, Entity, FocusHandle, Global, OwnedMenu};
-use std::collections::HashSet;
 
 use crate::text::{SelectionScope, TextViewState};
 
@@ -11,10 +10,11 @@ impl Global for GlobalState {}
 
 pub struct GlobalState {
     pub(crate) text_view_state_stack: Vec<Entity<TextViewState>>,
-    /// Set of open popover IDs that use deferred rendering.
-    /// When this set is not empty, we are inside at least one deferred context.
-    /// This is used to prevent double-deferred elements which would cause GPUI to panic.
-    open_deferred_popovers: HashSet<ElementId>,
+    /// Stack of open popover IDs that use deferred rendering, in opening order.
+    /// When this stack is not empty, we are inside at least one deferred context.
+    /// This is used to prevent double-deferred elements which would cause GPUI to panic,
+    /// and to dismiss nested popovers from the innermost one outwards.
+    open_deferred_popovers: Vec<ElementId>,
     /// Application menus storage
     app_menus: Vec<OwnedMenu>,
     /// When true, the window-level text selection must not start on the
@@ -34,7 +34,7 @@ impl GlobalState {
     pub(crate) fn new() -> Self {
         Self {
             text_view_state_stack: Vec::new(),
-            open_deferred_popovers: HashSet::new(),
+            open_deferred_popovers: Vec::new(),
             app_menus: Vec::new(),
             suppress_text_selection: false,
             selection_scope_stack: Vec::new(),
@@ -88,14 +88,25 @@ impl GlobalState {
 
     /// Register a popover that uses deferred rendering as open.
     pub(crate) fn register_deferred_popover(&mut self, focus_handle: &FocusHandle) {
-        self.open_deferred_popovers
-            .insert(format!("{focus_handle:?}").into());
+        let element_id: ElementId = format!("{focus_handle:?}").into();
+        if !self.open_deferred_popovers.contains(&element_id) {
+            self.open_deferred_popovers.push(element_id);
+        }
     }
 
     /// Unregister a popover when it closes.
     pub(crate) fn unregister_deferred_popover(&mut self, focus_handle: &FocusHandle) {
         let element_id: ElementId = format!("{focus_handle:?}").into();
-        self.open_deferred_popovers.remove(&element_id);
+        self.open_deferred_popovers.retain(|id| id != &element_id);
+    }
+
+    /// Returns true if the popover is the most recently opened deferred popover.
+    ///
+    /// A popover that has nested popovers (e.g. a `Select` inside a `Popover`) opened
+    /// above it is not the topmost, so it should leave the outside click to the nested one.
+    pub(crate) fn is_topmost_deferred_popover(&self, focus_handle: &FocusHandle) -> bool {
+        let element_id: ElementId = format!("{focus_handle:?}").into();
+        self.open_deferred_popovers.last() == Some(&element_id)
     }
 
     /// Get the application menus
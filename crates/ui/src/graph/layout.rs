@@ -0,0 +1,346 @@
+use gpui::{Bounds, Pixels, Point, Size, point, px, size};
+
+/// The direction of the levels of the [`super::GraphState`] layout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphDirection {
+    /// The roots are at the top, the children are below the parents.
+    #[default]
+    TopDown,
+    /// The roots are at the left, the children are at the right of the parents.
+    LeftRight,
+}
+
+impl GraphDirection {
+    /// Returns the `(main, cross)` lengths of the `size`, the main axis is the axis of the levels.
+    fn split(self, size: Size<Pixels>) -> (Pixels, Pixels) {
+        match self {
+            Self::TopDown => (size.height, size.width),
+            Self::LeftRight => (size.width, size.height),
+        }
+    }
+
+    fn join(self, main: Pixels, cross: Pixels) -> Point<Pixels> {
+        match self {
+            Self::TopDown => point(cross, main),
+            Self::LeftRight => point(main, cross),
+        }
+    }
+}
+
+/// A forest to layout, the nodes are identified by the index of the `sizes`.
+pub(super) struct LayoutTree<'a> {
+    pub(super) roots: &'a [usize],
+    pub(super) children: &'a [Vec<usize>],
+    pub(super) sizes: &'a [Size<Pixels>],
+}
+
+/// Returns the bounds of the nodes of the `tree` in the layered tree layout.
+///
+/// The nodes of the same depth are on the same level, and a parent is centered on
+/// its children. The `level_gap` is the space between the levels, and the `sibling_gap`
+/// is the space between the subtrees of the same level.
+pub(super) fn layout_tree(
+    tree: &LayoutTree,
+    direction: GraphDirection,
+    level_gap: Pixels,
+    sibling_gap: Pixels,
+) -> Vec<Bounds<Pixels>> {
+    let len = tree.sizes.len();
+    let mut layout = TreeLayout {
+        tree,
+        direction,
+        sibling_gap,
+        depths: vec![0; len],
+        levels: vec![],
+        extents: vec![px(0.); len],
+        centers: vec![px(0.); len],
+    };
+
+    for root in tree.roots {
+        layout.measure(*root, 0);
+    }
+    let mut cursor = px(0.);
+    for root in tree.roots {
+        layout.place(*root, cursor);
+        cursor += layout.extents[*root] + sibling_gap;
+    }
+
+    let mut level_starts = Vec::with_capacity(layout.levels.len());
+    let mut main = px(0.);
+    for level in &layout.levels {
+        level_starts.push(main);
+        main += *level + level_gap;
+    }
+
+    (0..len)
+        .map(|ix| {
+            let size = tree.sizes[ix];
+            let (node_main, node_cross) = direction.split(size);
+            let depth = layout.depths[ix];
+            // Center the node in the main axis of its level.
+            let main = level_starts.get(depth).copied().unwrap_or_default()
+                + (layout.levels.get(depth).copied().unwrap_or_default() - node_main) / 2.;
+            Bounds::new(
+                direction.join(main, layout.centers[ix] - node_cross / 2.),
+                size,
+            )
+        })
+        .collect()
+}
+
+struct TreeLayout<'a> {
+    tree: &'a LayoutTree<'a>,
+    direction: GraphDirection,
+    sibling_gap: Pixels,
+    depths: Vec<usize>,
+    /// The main size of the levels.
+    levels: Vec<Pixels>,
+    /// The cross extent of the subtrees.
+    extents: Vec<Pixels>,
+    /// The cross center of the nodes.
+    centers: Vec<Pixels>,
+}
+
+impl TreeLayout<'_> {
+    fn measure(&mut self, ix: usize, depth: usize) {
+        self.depths[ix] = depth;
+        let (main, cross) = self.direction.split(self.tree.sizes[ix]);
+        if self.levels.len() <= depth {
+            self.levels.push(px(0.));
+        }
+        self.levels[depth] = self.levels[depth].max(main);
+
+        let tree = self.tree;
+        let mut span = px(0.);
+        for (i, child) in tree.children[ix].iter().enumerate() {
+            self.measure(*child, depth + 1);
+            if i > 0 {
+                span += self.sibling_gap;
+            }
+            span += self.extents[*child];
+        }
+        self.extents[ix] = cross.max(span);
+    }
+
+    fn place(&mut self, ix: usize, start: Pixels) {
+        self.centers[ix] = start + self.extents[ix] / 2.;
+
+        let tree = self.tree;
+        let children = &tree.children[ix];
+        let span = children
+            .iter()
+            .fold(px(0.), |span, child| span + self.extents[*child])
+            + self.sibling_gap * children.len().saturating_sub(1) as f32;
+        let mut cursor = start + (self.extents[ix] - span) / 2.;
+        for child in children {
+            self.place(*child, cursor);
+            cursor += self.extents[*child] + self.sibling_gap;
+        }
+    }
+}
+
+/// Returns the points of the edge from the `from` node to the `to` node.
+///
+/// The edge goes out from the far side of the `from` node in the `direction`, and into
+/// the near side of the `to` node, with an elbow at the middle of the gap. When the `to`
+/// node is not after the `from` node, the edge goes around by the far sides of both nodes.
+pub(super) fn edge_path(
+    from: Bounds<Pixels>,
+    to: Bounds<Pixels>,
+    direction: GraphDirection,
+    gap: Pixels,
+) -> Vec<Point<Pixels>> {
+    match direction {
+        GraphDirection::TopDown => {
+            let start = point(from.center().x, from.bottom());
+            if to.top() >= from.bottom() {
+                let end = point(to.center().x, to.top());
+                let y = (start.y + end.y) / 2.;
+                return vec![start, point(start.x, y), point(end.x, y), end];
+            }
+
+            let end = point(to.center().x, to.bottom());
+            let y = start.y.max(end.y) + gap;
+            vec![start, point(start.x, y), point(end.x, y), end]
+        }
+        GraphDirection::LeftRight => {
+            let start = point(from.right(), from.center().y);
+            if to.left() >= from.right() {
+                let end = point(to.left(), to.center().y);
+                let x = (start.x + end.x) / 2.;
+                return vec![start, point(x, start.y), point(x, end.y), end];
+            }
+
+            let end = point(to.right(), to.center().y);
+            let x = start.x.max(end.x) + gap;
+            vec![start, point(x, start.y), point(x, end.y), end]
+        }
+    }
+}
+
+/// Returns the points of the arrow head at the `to` end of the segment from `from`.
+pub(super) fn arrow_head(
+    from: Point<Pixels>,
+    to: Point<Pixels>,
+    length: Pixels,
+) -> [Point<Pixels>; 3] {
+    let dx = f32::from(to.x - from.x);
+    let dy = f32::from(to.y - from.y);
+    let len = (dx * dx + dy * dy).sqrt();
+    let (ux, uy) = if len > 0. {
+        (dx / len, dy / len)
+    } else {
+        (0., 1.)
+    };
+
+    let length = f32::from(length);
+    let half = length * 0.6;
+    let base = point(to.x - px(ux * length), to.y - px(uy * length));
+    [
+        point(base.x - px(uy * half), base.y + px(ux * half)),
+        to,
+        point(base.x + px(uy * half), base.y - px(ux * half)),
+    ]
+}
+
+/// Returns the bounds to cover all the `bounds`.
+pub(super) fn union_bounds(bounds: &[Bounds<Pixels>]) -> Option<Bounds<Pixels>> {
+    bounds.iter().copied().reduce(|a, b| a.union(&b))
+}
+
+/// Returns the `(zoom, offset)` to fit the `content` in the center of the `viewport`,
+/// the zoom is clamped to `min_zoom..=max_zoom`.
+pub(super) fn fit_transform(
+    content: Bounds<Pixels>,
+    viewport: Size<Pixels>,
+    padding: Pixels,
+    min_zoom: f32,
+    max_zoom: f32,
+) -> (f32, Point<Pixels>) {
+    let available = size(
+        (viewport.width - padding * 2.).max(px(1.)),
+        (viewport.height - padding * 2.).max(px(1.)),
+    );
+    let zoom = if content.size.width <= px(0.) || content.size.height <= px(0.) {
+        1.
+    } else {
+        (available.width / content.size.width).min(available.height / content.size.height)
+    }
+    .clamp(min_zoom, max_zoom);
+
+    let center = content.center();
+    let offset = point(
+        viewport.width / 2. - center.x * zoom,
+        viewport.height / 2. - center.y * zoom,
+    );
+    (zoom, offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Bounds, point, px, size};
+
+    use super::{GraphDirection, LayoutTree, arrow_head, edge_path, fit_transform, layout_tree};
+
+    #[test]
+    fn test_layout_tree() {
+        // 0 -> [1, 2], 2 -> [3], and the root 4.
+        let children = vec![vec![1, 2], vec![], vec![3], vec![], vec![]];
+        let sizes = vec![size(px(100.), px(40.)); 5];
+        let tree = LayoutTree {
+            roots: &[0, 4],
+            children: &children,
+            sizes: &sizes,
+        };
+
+        let bounds = layout_tree(&tree, GraphDirection::TopDown, px(20.), px(10.));
+        assert_eq!(bounds[1].origin, point(px(0.), px(60.)));
+        assert_eq!(bounds[2].origin, point(px(110.), px(60.)));
+        assert_eq!(bounds[3].origin, point(px(110.), px(120.)));
+        // The parent is centered on the children.
+        assert_eq!(bounds[0].origin, point(px(55.), px(0.)));
+        // The next root is after the subtree.
+        assert_eq!(bounds[4].origin, point(px(220.), px(0.)));
+
+        let bounds = layout_tree(&tree, GraphDirection::LeftRight, px(20.), px(10.));
+        assert_eq!(bounds[0].origin, point(px(0.), px(25.)));
+        assert_eq!(bounds[1].origin, point(px(120.), px(0.)));
+        assert_eq!(bounds[2].origin, point(px(120.), px(50.)));
+        assert_eq!(bounds[3].origin, point(px(240.), px(50.)));
+        assert_eq!(bounds[4].origin, point(px(0.), px(100.)));
+    }
+
+    #[test]
+    fn test_layout_tree_wide_parent() {
+        // The parent is wider than its children, the children are centered.
+        let children = vec![vec![1], vec![]];
+        let sizes = vec![size(px(200.), px(40.)), size(px(50.), px(20.))];
+        let tree = LayoutTree {
+            roots: &[0],
+            children: &children,
+            sizes: &sizes,
+        };
+
+        let bounds = layout_tree(&tree, GraphDirection::TopDown, px(20.), px(10.));
+        assert_eq!(bounds[0].origin, point(px(0.), px(0.)));
+        assert_eq!(bounds[1].origin, point(px(75.), px(60.)));
+    }
+
+    #[test]
+    fn test_edge_path() {
+        let from = Bounds::new(point(px(0.), px(0.)), size(px(100.), px(40.)));
+        let to = Bounds::new(point(px(200.), px(80.)), size(px(100.), px(40.)));
+        assert_eq!(
+            edge_path(from, to, GraphDirection::TopDown, px(10.)),
+            vec![
+                point(px(50.), px(40.)),
+                point(px(50.), px(60.)),
+                point(px(250.), px(60.)),
+                point(px(250.), px(80.)),
+            ]
+        );
+
+        // Go around when the target is not below.
+        assert_eq!(
+            edge_path(to, from, GraphDirection::TopDown, px(10.)),
+            vec![
+                point(px(250.), px(120.)),
+                point(px(250.), px(130.)),
+                point(px(50.), px(130.)),
+                point(px(50.), px(40.)),
+            ]
+        );
+
+        let to = Bounds::new(point(px(160.), px(100.)), size(px(100.), px(40.)));
+        assert_eq!(
+            edge_path(from, to, GraphDirection::LeftRight, px(10.)),
+            vec![
+                point(px(100.), px(20.)),
+                point(px(130.), px(20.)),
+                point(px(130.), px(120.)),
+                point(px(160.), px(120.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arrow_head() {
+        let [left, tip, right] = arrow_head(point(px(0.), px(0.)), point(px(0.), px(20.)), px(10.));
+        assert_eq!(tip, point(px(0.), px(20.)));
+        assert_eq!(left, point(px(-6.), px(10.)));
+        assert_eq!(right, point(px(6.), px(10.)));
+    }
+
+    #[test]
+    fn test_fit_transform() {
+        let content = Bounds::new(point(px(0.), px(0.)), size(px(400.), px(100.)));
+        let (zoom, offset) = fit_transform(content, size(px(220.), px(220.)), px(10.), 0.1, 2.);
+        assert_eq!(zoom, 0.5);
+        assert_eq!(offset, point(px(10.), px(85.)));
+
+        // Clamp the zoom for the small content.
+        let (zoom, offset) = fit_transform(content, size(px(2000.), px(2000.)), px(0.), 0.1, 2.);
+        assert_eq!(zoom, 2.);
+        assert_eq!(offset, point(px(600.), px(900.)));
+    }
+}
@@ -0,0 +1,7 @@
+mod layout;
+mod state;
+mod view;
+
+pub use layout::GraphDirection;
+pub use state::*;
+pub use view::*;
@@ -0,0 +1,501 @@
+use std::collections::{HashMap, HashSet};
+
+use gpui::{
+    AnyElement, App, Bounds, Context, EventEmitter, IntoElement, Pixels, Point, SharedString, Size,
+    Window, point, px, size,
+};
+
+use super::layout::{GraphDirection, LayoutTree, fit_transform, layout_tree, union_bounds};
+
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 4.;
+const ZOOM_FACTOR: f32 = 1.25;
+const FIT_PADDING: Pixels = px(24.);
+
+/// A delegate to provide the nodes of the [`GraphState`].
+///
+/// The nodes are identified by the ids, the children of a node are only
+/// requested after the node is expanded, so the large trees can be loaded lazily.
+#[allow(unused)]
+pub trait GraphDelegate: Sized + 'static {
+    /// Return the ids of the root nodes.
+    fn roots(&self, cx: &App) -> Vec<SharedString>;
+
+    /// Return the ids of the children of the node, only called for the expanded nodes.
+    fn children(&self, id: &SharedString, cx: &App) -> Vec<SharedString>;
+
+    /// Return true if the node can be expanded, default is true if it has children.
+    ///
+    /// Override this to show the expand button before the children are loaded.
+    fn has_children(&self, id: &SharedString, cx: &App) -> bool {
+        !self.children(id, cx).is_empty()
+    }
+
+    /// Return the extra links between the nodes besides the parent-child edges, default is empty.
+    ///
+    /// The links are only drawn when both nodes are visible in the graph.
+    fn links(&self, cx: &App) -> Vec<(SharedString, SharedString)> {
+        vec![]
+    }
+
+    /// Return the size of the node at zoom 1.0, default is 160x56.
+    fn node_size(&self, id: &SharedString, cx: &App) -> Size<Pixels> {
+        size(px(160.), px(56.))
+    }
+
+    /// Return true to expand the node by default, default is true for the roots.
+    fn expanded_by_default(&self, id: &SharedString, depth: usize, cx: &App) -> bool {
+        depth == 0
+    }
+
+    /// Called before the node is expanded, the children can be loaded here.
+    ///
+    /// If the children are loaded asynchronously, call [`GraphState::refresh`] after loaded.
+    fn will_expand(
+        &mut self,
+        id: &SharedString,
+        window: &mut Window,
+        cx: &mut Context<GraphState<Self>>,
+    ) {
+    }
+
+    /// Render the content of the node.
+    ///
+    /// The content is scaled by the zoom, use the `rems` based sizes (e.g. `text_sm`, `p_2`)
+    /// to scale with it.
+    fn render_node(
+        &mut self,
+        id: &SharedString,
+        node: GraphNodeState,
+        window: &mut Window,
+        cx: &mut Context<GraphState<Self>>,
+    ) -> impl IntoElement;
+}
+
+/// The state of a node to render by the [`GraphDelegate`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GraphNodeState {
+    /// The depth of the node, the roots are 0.
+    pub depth: usize,
+    pub expanded: bool,
+    pub has_children: bool,
+    pub selected: bool,
+    pub hovered: bool,
+}
+
+/// Events emitted by the [`GraphState`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphEvent {
+    /// A node was clicked.
+    Click(SharedString),
+    /// The mouse entered a node, or `None` when left.
+    Hover(Option<SharedString>),
+    /// A node was expanded.
+    Expand(SharedString),
+    /// A node was collapsed.
+    Collapse(SharedString),
+}
+
+/// A node in the layout.
+pub(super) struct GraphNode {
+    pub(super) id: SharedString,
+    /// The bounds at zoom 1.0.
+    pub(super) bounds: Bounds<Pixels>,
+    pub(super) depth: usize,
+    pub(super) has_children: bool,
+}
+
+/// State of the [`super::Graph`].
+pub struct GraphState<D: GraphDelegate> {
+    delegate: D,
+    pub(super) direction: GraphDirection,
+    level_gap: Pixels,
+    sibling_gap: Pixels,
+    pub(super) nodes: Vec<GraphNode>,
+    pub(super) index: HashMap<SharedString, usize>,
+    /// The `(parent, child)` node indices.
+    pub(super) edges: Vec<(usize, usize)>,
+    /// The `(from, to)` node indices of the extra links.
+    pub(super) links: Vec<(usize, usize)>,
+    expanded: HashSet<SharedString>,
+    /// The nodes already expanded or collapsed once, so [`GraphDelegate::expanded_by_default`]
+    /// is not applied again.
+    touched: HashSet<SharedString>,
+    pub(super) zoom: f32,
+    /// The position of the layout origin relative to the viewport.
+    pub(super) offset: Point<Pixels>,
+    pub(super) selected: Option<SharedString>,
+    pub(super) hovered: Option<SharedString>,
+    /// The last mouse position while panning.
+    pub(super) panning: Option<Point<Pixels>>,
+    /// The bounds of the viewport, updated on prepaint.
+    pub(super) bounds: Bounds<Pixels>,
+    /// Fit the graph to the viewport on the next prepaint.
+    pub(super) pending_fit: bool,
+    /// Layout the nodes again on the next render.
+    pub(super) pending_layout: bool,
+}
+
+impl<D: GraphDelegate> EventEmitter<GraphEvent> for GraphState<D> {}
+
+impl<D: GraphDelegate> GraphState<D> {
+    /// Create a new graph state with the `delegate`, the graph is fitted to the viewport
+    /// on the first paint.
+    pub fn new(delegate: D, _: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            delegate,
+            direction: GraphDirection::default(),
+            level_gap: px(48.),
+            sibling_gap: px(24.),
+            nodes: vec![],
+            index: HashMap::new(),
+            edges: vec![],
+            links: vec![],
+            expanded: HashSet::new(),
+            touched: HashSet::new(),
+            zoom: 1.,
+            offset: Point::default(),
+            selected: None,
+            hovered: None,
+            panning: None,
+            bounds: Bounds::default(),
+            pending_fit: true,
+            pending_layout: false,
+        };
+        this.relayout(cx);
+        this
+    }
+
+    /// Set the direction of the layout, default is [`GraphDirection::TopDown`].
+    pub fn direction(mut self, direction: GraphDirection) -> Self {
+        self.direction = direction;
+        self.pending_layout = true;
+        self
+    }
+
+    /// Update the direction of the layout, and fit the graph to the viewport.
+    pub fn set_direction(&mut self, direction: GraphDirection, cx: &mut Context<Self>) {
+        self.direction = direction;
+        self.relayout(cx);
+        self.fit(cx);
+    }
+
+    /// Set the space between the levels and between the sibling subtrees, default is 48px and 24px.
+    pub fn gaps(mut self, level_gap: impl Into<Pixels>, sibling_gap: impl Into<Pixels>) -> Self {
+        self.level_gap = level_gap.into();
+        self.sibling_gap = sibling_gap.into();
+        self.pending_layout = true;
+        self
+    }
+
+    /// Returns the delegate.
+    pub fn delegate(&self) -> &D {
+        &self.delegate
+    }
+
+    /// Returns the mutable delegate, call [`GraphState::refresh`] after the nodes changed.
+    pub fn delegate_mut(&mut self) -> &mut D {
+        &mut self.delegate
+    }
+
+    /// Request the nodes from the delegate again, the visible nodes are kept in place.
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        let anchor = self.anchor();
+        self.relayout(cx);
+        self.restore_anchor(anchor);
+        cx.notify();
+    }
+
+    /// Returns true if the node is expanded.
+    pub fn is_expanded(&self, id: &SharedString) -> bool {
+        self.expanded.contains(id)
+    }
+
+    /// Expand or collapse the node, the node is kept in place.
+    pub fn set_expanded(
+        &mut self,
+        id: &SharedString,
+        expanded: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.is_expanded(id) == expanded {
+            return;
+        }
+
+        self.touched.insert(id.clone());
+        if expanded {
+            self.delegate.will_expand(id, window, cx);
+            self.expanded.insert(id.clone());
+        } else {
+            self.expanded.remove(id);
+        }
+
+        let anchor = self
+            .index
+            .get(id)
+            .map(|ix| (id.clone(), self.node_origin(*ix)));
+        self.relayout(cx);
+        self.restore_anchor(anchor);
+
+        cx.emit(if expanded {
+            GraphEvent::Expand(id.clone())
+        } else {
+            GraphEvent::Collapse(id.clone())
+        });
+        cx.notify();
+    }
+
+    /// Toggle the expanded state of the node.
+    pub fn toggle_expanded(
+        &mut self,
+        id: &SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_expanded(id, !self.is_expanded(id), window, cx);
+    }
+
+    /// Returns the selected node id.
+    pub fn selected(&self) -> Option<&SharedString> {
+        self.selected.as_ref()
+    }
+
+    /// Set the selected node, or `None` to clear the selection.
+    pub fn set_selected(&mut self, id: Option<SharedString>, cx: &mut Context<Self>) {
+        self.selected = id;
+        cx.notify();
+    }
+
+    /// Returns the zoom of the graph.
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Set the zoom, the center of the viewport is kept in place.
+    pub fn set_zoom(&mut self, zoom: f32, cx: &mut Context<Self>) {
+        let center = point(self.bounds.size.width / 2., self.bounds.size.height / 2.);
+        self.zoom_at(zoom, center);
+        cx.notify();
+    }
+
+    /// Zoom in the graph.
+    pub fn zoom_in(&mut self, cx: &mut Context<Self>) {
+        self.set_zoom(self.zoom * ZOOM_FACTOR, cx);
+    }
+
+    /// Zoom out the graph.
+    pub fn zoom_out(&mut self, cx: &mut Context<Self>) {
+        self.set_zoom(self.zoom / ZOOM_FACTOR, cx);
+    }
+
+    /// Fit the graph to the viewport.
+    pub fn fit(&mut self, cx: &mut Context<Self>) {
+        self.pending_fit = true;
+        cx.notify();
+    }
+
+    /// Scroll the node to the center of the viewport.
+    pub fn scroll_to_node(&mut self, id: &SharedString, cx: &mut Context<Self>) {
+        let Some(ix) = self.index.get(id) else {
+            return;
+        };
+
+        let center = self.nodes[*ix].bounds.center();
+        self.offset = point(
+            self.bounds.size.width / 2. - center.x * self.zoom,
+            self.bounds.size.height / 2. - center.y * self.zoom,
+        );
+        cx.notify();
+    }
+
+    /// Zoom to the `zoom` with the `position` relative to the viewport kept in place.
+    pub(super) fn zoom_at(&mut self, zoom: f32, position: Point<Pixels>) {
+        let zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        let scale = zoom / self.zoom;
+        self.offset = point(
+            position.x - (position.x - self.offset.x) * scale,
+            position.y - (position.y - self.offset.y) * scale,
+        );
+        self.zoom = zoom;
+    }
+
+    pub(super) fn apply_fit(&mut self) {
+        self.pending_fit = false;
+        let bounds = self
+            .nodes
+            .iter()
+            .map(|node| node.bounds)
+            .collect::<Vec<_>>();
+        let Some(content) = union_bounds(&bounds) else {
+            return;
+        };
+
+        (self.zoom, self.offset) =
+            fit_transform(content, self.bounds.size, FIT_PADDING, MIN_ZOOM, 1.);
+    }
+
+    /// Returns the bounds of the node relative to the viewport.
+    pub(super) fn node_bounds(&self, ix: usize) -> Bounds<Pixels> {
+        let bounds = self.nodes[ix].bounds;
+        Bounds::new(self.node_origin(ix), bounds.size * self.zoom)
+    }
+
+    fn node_origin(&self, ix: usize) -> Point<Pixels> {
+        let origin = self.nodes[ix].bounds.origin;
+        point(
+            self.offset.x + origin.x * self.zoom,
+            self.offset.y + origin.y * self.zoom,
+        )
+    }
+
+    pub(super) fn node_state(&self, ix: usize) -> GraphNodeState {
+        let node = &self.nodes[ix];
+        GraphNodeState {
+            depth: node.depth,
+            expanded: self.expanded.contains(&node.id),
+            has_children: node.has_children,
+            selected: self.selected.as_ref() == Some(&node.id),
+            hovered: self.hovered.as_ref() == Some(&node.id),
+        }
+    }
+
+    pub(super) fn render_node(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let id = self.nodes[ix].id.clone();
+        let node = self.node_state(ix);
+        self.delegate
+            .render_node(&id, node, window, cx)
+            .into_any_element()
+    }
+
+    pub(super) fn click(&mut self, id: SharedString, cx: &mut Context<Self>) {
+        self.selected = Some(id.clone());
+        cx.emit(GraphEvent::Click(id));
+        cx.notify();
+    }
+
+    pub(super) fn hover(&mut self, id: &SharedString, hovered: bool, cx: &mut Context<Self>) {
+        let hovered = if hovered {
+            Some(id.clone())
+        } else if self.hovered.as_ref() == Some(id) {
+            None
+        } else {
+            return;
+        };
+
+        if self.hovered != hovered {
+            self.hovered = hovered.clone();
+            cx.emit(GraphEvent::Hover(hovered));
+            cx.notify();
+        }
+    }
+
+    /// Returns the first visible node and its position, to keep it in place after the relayout.
+    fn anchor(&self) -> Option<(SharedString, Point<Pixels>)> {
+        let viewport = Bounds::new(Point::default(), self.bounds.size);
+        (0..self.nodes.len())
+            .find(|ix| self.node_bounds(*ix).intersects(&viewport))
+            .map(|ix| (self.nodes[ix].id.clone(), self.node_origin(ix)))
+    }
+
+    fn restore_anchor(&mut self, anchor: Option<(SharedString, Point<Pixels>)>) {
+        let Some((id, position)) = anchor else {
+            return;
+        };
+        let Some(ix) = self.index.get(&id).copied() else {
+            return;
+        };
+
+        let origin = self.node_origin(ix);
+        self.offset = self.offset + position - origin;
+    }
+
+    /// Request the visible nodes from the delegate and layout them.
+    ///
+    /// A node reachable from multiple parents is placed under the first one, the other
+    /// parent-child relations are drawn as the links.
+    pub(super) fn relayout(&mut self, cx: &App) {
+        self.pending_layout = false;
+        let mut nodes: Vec<GraphNode> = vec![];
+        let mut index = HashMap::new();
+        let mut children: Vec<Vec<usize>> = vec![];
+        let mut roots = vec![];
+        let mut extra_links = vec![];
+
+        let mut stack = vec![];
+        for id in self.delegate.roots(cx).into_iter().rev() {
+            stack.push((None, id, 0));
+        }
+        while let Some((parent, id, depth)) = stack.pop() {
+            if let Some(ix) = index.get(&id).copied() {
+                if let Some(parent) = parent {
+                    extra_links.push((parent, ix));
+                }
+                continue;
+            }
+
+            let ix = nodes.len();
+            index.insert(id.clone(), ix);
+            children.push(vec![]);
+            match parent {
+                Some(parent) => children[parent].push(ix),
+                None => roots.push(ix),
+            }
+
+            if !self.touched.contains(&id) && self.delegate.expanded_by_default(&id, depth, cx) {
+                self.expanded.insert(id.clone());
+            }
+            let has_children = self.delegate.has_children(&id, cx);
+            if self.expanded.contains(&id) {
+                for child in self.delegate.children(&id, cx).into_iter().rev() {
+                    stack.push((Some(ix), child, depth + 1));
+                }
+            }
+
+            nodes.push(GraphNode {
+                bounds: Bounds::new(Point::default(), self.delegate.node_size(&id, cx)),
+                id,
+                depth,
+                has_children,
+            });
+        }
+
+        let sizes = nodes
+            .iter()
+            .map(|node| node.bounds.size)
+            .collect::<Vec<_>>();
+        let bounds = layout_tree(
+            &LayoutTree {
+                roots: &roots,
+                children: &children,
+                sizes: &sizes,
+            },
+            self.direction,
+            self.level_gap,
+            self.sibling_gap,
+        );
+        for (node, bounds) in nodes.iter_mut().zip(bounds) {
+            node.bounds = bounds;
+        }
+
+        self.edges = children
+            .iter()
+            .enumerate()
+            .flat_map(|(parent, children)| children.iter().map(move |child| (parent, *child)))
+            .collect();
+        self.links = extra_links;
+        for (from, to) in self.delegate.links(cx) {
+            if let (Some(from), Some(to)) = (index.get(&from), index.get(&to)) {
+                self.links.push((*from, *to));
+            }
+        }
+
+        self.nodes = nodes;
+        self.index = index;
+    }
+}
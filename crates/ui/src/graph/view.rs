@@ -0,0 +1,458 @@
+use gpui::{
+    AnyElement, App, AppContext as _, Bounds, ClickEvent, ContentMask, Context, DragMoveEvent,
+    Element, ElementId, Empty, Entity, EntityId, GlobalElementId, Hsla, InspectorElementId,
+    InteractiveElement as _, IntoElement, LayoutId, MouseButton, MouseDownEvent, ParentElement,
+    PathBuilder, Pixels, Point, Render, RenderOnce, ScrollWheelEvent,
+    StatefulInteractiveElement as _, Style, StyleRefinement, Styled, Window, canvas, div,
+    prelude::FluentBuilder as _, px, relative,
+};
+
+use crate::{
+    ActiveTheme as _, Icon, IconName, Sizable as _, StyledExt as _,
+    button::{Button, ButtonVariants as _},
+    element_ext::ElementExt as _,
+    h_flex,
+};
+
+use super::{
+    layout::{GraphDirection, arrow_head, edge_path},
+    state::{GraphDelegate, GraphState},
+};
+
+const TOGGLE_SIZE: Pixels = px(16.);
+const ARROW_SIZE: Pixels = px(6.);
+
+#[derive(Clone)]
+struct DragGraphPan(EntityId);
+
+impl Render for DragGraphPan {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
+
+/// An element to layout and paint the child with the rem size scaled by the `zoom`,
+/// so the `rems` based content of the nodes is scaled with the graph.
+struct Zoomed {
+    zoom: f32,
+    child: AnyElement,
+}
+
+impl IntoElement for Zoomed {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for Zoomed {
+    type RequestLayoutState = Pixels;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let rem_size = window.rem_size() * self.zoom;
+        let child = window.with_rem_size(Some(rem_size), |window| {
+            self.child.request_layout(window, cx)
+        });
+
+        let mut style = Style::default();
+        style.size.width = relative(1.).into();
+        style.size.height = relative(1.).into();
+        (window.request_layout(style, Some(child), cx), rem_size)
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        _: Bounds<Pixels>,
+        rem_size: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        window.with_rem_size(Some(*rem_size), |window| {
+            self.child.prepaint(window, cx);
+        });
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        _: Bounds<Pixels>,
+        rem_size: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        window.with_rem_size(Some(*rem_size), |window| {
+            self.child.paint(window, cx);
+        });
+    }
+}
+
+/// The edges and links to paint under the nodes.
+struct EdgesLayout {
+    edges: Vec<Vec<Point<Pixels>>>,
+    links: Vec<Vec<Point<Pixels>>>,
+    edge_color: Hsla,
+    link_color: Hsla,
+}
+
+fn paint_edge(points: &[Point<Pixels>], color: Hsla, window: &mut Window) {
+    let mut builder = PathBuilder::stroke(px(1.));
+    builder.move_to(points[0]);
+    for p in &points[1..] {
+        builder.line_to(*p);
+    }
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
+
+    let [left, tip, right] = arrow_head(
+        points[points.len() - 2],
+        points[points.len() - 1],
+        ARROW_SIZE,
+    );
+    let mut builder = PathBuilder::fill();
+    builder.move_to(left);
+    builder.line_to(tip);
+    builder.line_to(right);
+    builder.close();
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
+}
+
+impl<D: GraphDelegate> GraphState<D> {
+    fn layout_edges(&self, bounds: Bounds<Pixels>, cx: &App) -> EdgesLayout {
+        let viewport = Bounds::new(Point::default(), bounds.size);
+        let gap = px(12.) * self.zoom;
+        let path = |from: usize, to: usize| {
+            let from = self.node_bounds(from);
+            let to = self.node_bounds(to);
+            // Skip the edges out of the viewport.
+            if !from.union(&to).intersects(&viewport) {
+                return None;
+            }
+
+            Some(
+                edge_path(from, to, self.direction, gap)
+                    .into_iter()
+                    .map(|p| p + bounds.origin)
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        EdgesLayout {
+            edges: self
+                .edges
+                .iter()
+                .filter_map(|(from, to)| path(*from, *to))
+                .collect(),
+            links: self
+                .links
+                .iter()
+                .filter_map(|(from, to)| path(*from, *to))
+                .collect(),
+            edge_color: cx.theme().muted_foreground.opacity(0.6),
+            link_color: cx.theme().chart_2,
+        }
+    }
+
+    fn render_toggle(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let id = self.nodes[ix].id.clone();
+        let expanded = self.is_expanded(&id);
+        let size = self.node_bounds(ix).size;
+
+        div()
+            .id("toggle")
+            .absolute()
+            .map(|this| match self.direction {
+                GraphDirection::TopDown => this
+                    .left(size.width / 2. - TOGGLE_SIZE / 2.)
+                    .bottom(-TOGGLE_SIZE / 2.),
+                GraphDirection::LeftRight => this
+                    .top(size.height / 2. - TOGGLE_SIZE / 2.)
+                    .right(-TOGGLE_SIZE / 2.),
+            })
+            .size(TOGGLE_SIZE)
+            .flex()
+            .items_center()
+            .justify_center()
+            .rounded_full()
+            .border_1()
+            .border_color(cx.theme().border)
+            .bg(cx.theme().background)
+            .text_color(cx.theme().muted_foreground)
+            .cursor_pointer()
+            .hover(|this| this.text_color(cx.theme().foreground))
+            .child(
+                Icon::new(if expanded {
+                    IconName::Minus
+                } else {
+                    IconName::Plus
+                })
+                .xsmall(),
+            )
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                cx.stop_propagation();
+                this.toggle_expanded(&id, window, cx);
+            }))
+    }
+
+    fn render_node_frame(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let id = self.nodes[ix].id.clone();
+        let bounds = self.node_bounds(ix);
+        let node = self.node_state(ix);
+        let content = self.render_node(ix, window, cx);
+
+        div()
+            .id(id.clone())
+            .absolute()
+            .left(bounds.left())
+            .top(bounds.top())
+            .w(bounds.size.width)
+            .h(bounds.size.height)
+            .child(
+                div()
+                    .size_full()
+                    .overflow_hidden()
+                    .rounded(cx.theme().radius * self.zoom)
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .bg(cx.theme().popover)
+                    .text_color(cx.theme().popover_foreground)
+                    .when(node.hovered, |this| {
+                        this.border_color(cx.theme().primary.opacity(0.5))
+                    })
+                    .when(node.selected, |this| this.border_color(cx.theme().ring))
+                    .child(Zoomed {
+                        zoom: self.zoom,
+                        child: content,
+                    }),
+            )
+            .when(node.has_children, |this| {
+                this.child(self.render_toggle(ix, cx))
+            })
+            .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                // Click the node without panning.
+                cx.stop_propagation();
+            })
+            .on_hover(cx.listener({
+                let id = id.clone();
+                move |this, hovered: &bool, _, cx| this.hover(&id, *hovered, cx)
+            }))
+            .on_click(cx.listener(move |this, _: &ClickEvent, _, cx| {
+                this.click(id.clone(), cx);
+            }))
+    }
+
+    fn render_controls(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .absolute()
+            .bottom_2()
+            .right_2()
+            .gap_1()
+            .child(
+                Button::new("zoom-in")
+                    .icon(IconName::Plus)
+                    .small()
+                    .outline()
+                    .on_click(cx.listener(|this, _, _, cx| this.zoom_in(cx))),
+            )
+            .child(
+                Button::new("zoom-out")
+                    .icon(IconName::Minus)
+                    .small()
+                    .outline()
+                    .on_click(cx.listener(|this, _, _, cx| this.zoom_out(cx))),
+            )
+            .child(
+                Button::new("fit")
+                    .icon(IconName::Maximize)
+                    .small()
+                    .outline()
+                    .on_click(cx.listener(|this, _, _, cx| this.fit(cx))),
+            )
+    }
+}
+
+impl<D: GraphDelegate> Render for GraphState<D> {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.pending_layout {
+            self.relayout(cx);
+        }
+
+        let state = cx.entity();
+        let entity_id = cx.entity_id();
+        let viewport = Bounds::new(Point::default(), self.bounds.size);
+        let nodes = (0..self.nodes.len())
+            .filter(|ix| self.node_bounds(*ix).intersects(&viewport))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|ix| self.render_node_frame(ix, window, cx).into_any_element())
+            .collect::<Vec<_>>();
+
+        div()
+            .id("graph-state")
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .on_prepaint({
+                let state = state.clone();
+                move |bounds, _, cx| {
+                    state.update(cx, |state, cx| {
+                        if state.bounds != bounds {
+                            state.bounds = bounds;
+                            cx.notify();
+                        }
+                        if state.pending_fit && bounds.size.width > px(0.) {
+                            state.apply_fit();
+                            cx.notify();
+                        }
+                    });
+                }
+            })
+            .on_scroll_wheel(cx.listener(|this, e: &ScrollWheelEvent, window, cx| {
+                let delta = e.delta.pixel_delta(window.line_height());
+                if e.modifiers.secondary() {
+                    let position = e.position - this.bounds.origin;
+                    this.zoom_at(this.zoom * (delta.y / px(200.)).exp(), position);
+                } else {
+                    this.offset = this.offset + delta;
+                }
+                cx.stop_propagation();
+                cx.notify();
+            }))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, e: &MouseDownEvent, _, _| {
+                    this.panning = Some(e.position);
+                }),
+            )
+            .on_drag(DragGraphPan(entity_id), |drag, _, _, cx| {
+                cx.stop_propagation();
+                cx.new(|_| drag.clone())
+            })
+            .on_drag_move(cx.listener(|this, e: &DragMoveEvent<DragGraphPan>, _, cx| {
+                if e.drag(cx).0 != cx.entity_id() {
+                    return;
+                }
+                let Some(last) = this.panning else {
+                    return;
+                };
+
+                let position = e.event.position;
+                this.offset = this.offset + position - last;
+                this.panning = Some(position);
+                cx.notify();
+            }))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _, _, _| this.panning = None),
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                cx.listener(|this, _, _, _| this.panning = None),
+            )
+            .child(
+                canvas(
+                    move |bounds, _, cx| state.read(cx).layout_edges(bounds, cx),
+                    move |bounds, layout, window, _| {
+                        window.with_content_mask(Some(ContentMask { bounds }), |window| {
+                            for points in &layout.edges {
+                                paint_edge(points, layout.edge_color, window);
+                            }
+                            for points in &layout.links {
+                                paint_edge(points, layout.link_color, window);
+                            }
+                        });
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(nodes)
+            .child(self.render_controls(cx))
+    }
+}
+
+/// A node-link diagram to show the nodes of the [`GraphDelegate`] in a layered tree layout,
+/// e.g. an org chart.
+///
+/// Drag the background to pan, scroll with `cmd` (`ctrl` on Windows and Linux) to zoom,
+/// and click the button under a node to expand or collapse its children.
+///
+/// ```ignore
+/// let state = cx.new(|cx| GraphState::new(MyDelegate::new(), window, cx));
+///
+/// Graph::new(&state)
+/// ```
+#[derive(IntoElement)]
+pub struct Graph<D: GraphDelegate + 'static> {
+    state: Entity<GraphState<D>>,
+    style: StyleRefinement,
+}
+
+impl<D> Graph<D>
+where
+    D: GraphDelegate + 'static,
+{
+    /// Create a new graph element with the `state`.
+    pub fn new(state: &Entity<GraphState<D>>) -> Self {
+        Self {
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl<D> Styled for Graph<D>
+where
+    D: GraphDelegate + 'static,
+{
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl<D> RenderOnce for Graph<D>
+where
+    D: GraphDelegate + 'static,
+{
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        div()
+            .id(ElementId::Name(
+                format!("graph-{}", self.state.entity_id()).into(),
+            ))
+            .size_full()
+            .overflow_hidden()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .bg(cx.theme().background)
+            .child(self.state)
+            .refine_style(&self.style)
+    }
+}
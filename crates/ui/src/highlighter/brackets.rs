@@ -0,0 +1,52 @@
+/// A pair of brackets or quotes of a language, see [`LanguageConfig::brackets`].
+///
+/// [`LanguageConfig::brackets`]: crate::highlighter::LanguageConfig::brackets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BracketPair {
+    pub open: char,
+    pub close: char,
+    /// Whether to insert the close character when typing the open character, default is true.
+    pub auto_close: bool,
+    /// Whether to surround the selection when typing the open character, default is true.
+    pub surround: bool,
+}
+
+impl BracketPair {
+    pub const fn new(open: char, close: char) -> Self {
+        Self {
+            open,
+            close,
+            auto_close: true,
+            surround: true,
+        }
+    }
+
+    /// Set whether to insert the close character when typing the open character.
+    pub const fn auto_close(mut self, auto_close: bool) -> Self {
+        self.auto_close = auto_close;
+        self
+    }
+
+    /// Set whether to surround the selection when typing the open character.
+    pub const fn surround(mut self, surround: bool) -> Self {
+        self.surround = surround;
+        self
+    }
+
+    /// Returns true if the open and close characters are the same, e.g. `"`.
+    #[inline]
+    pub fn is_quote(&self) -> bool {
+        self.open == self.close
+    }
+
+    /// The default bracket pairs: `()`, `[]`, `{}`, `""` and `''`.
+    pub fn defaults() -> Vec<BracketPair> {
+        vec![
+            BracketPair::new('(', ')'),
+            BracketPair::new('[', ']'),
+            BracketPair::new('{', '}'),
+            BracketPair::new('"', '"'),
+            BracketPair::new('\'', '\''),
+        ]
+    }
+}
@@ -0,0 +1,37 @@
+use gpui::SharedString;
+
+/// The comment tokens of a language, see [`LanguageConfig::comment_tokens`].
+///
+/// [`LanguageConfig::comment_tokens`]: crate::highlighter::LanguageConfig::comment_tokens
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CommentTokens {
+    /// The line comment token, e.g. `//`.
+    pub line: Option<SharedString>,
+    /// The block comment start and end tokens, e.g. `/*` and `*/`.
+    pub block: Option<(SharedString, SharedString)>,
+}
+
+impl CommentTokens {
+    /// Create the comment tokens without any token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the line comment token.
+    pub fn line(mut self, token: impl Into<SharedString>) -> Self {
+        self.line = Some(token.into());
+        self
+    }
+
+    /// Set the block comment start and end tokens.
+    pub fn block(mut self, start: impl Into<SharedString>, end: impl Into<SharedString>) -> Self {
+        self.block = Some((start.into(), end.into()));
+        self
+    }
+
+    /// Returns true if there is no comment token.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.line.is_none() && self.block.is_none()
+    }
+}
@@ -4,13 +4,13 @@ use std::{
     usize,
 };
 
-use gpui::{px, App, HighlightStyle, Hsla, SharedString, UnderlineStyle};
+use gpui::{App, HighlightStyle, Hsla, SharedString, UnderlineStyle, px};
 use ropey::Rope;
 use sum_tree::{Bias, SeekTarget, SumTree};
 
 use crate::{
-    input::{Position, RopeExt as _},
     ActiveTheme,
+    input::{Position, RopeExt as _},
 };
 
 pub type DiagnosticRelatedInformation = lsp_types::DiagnosticRelatedInformation;
@@ -331,7 +331,6 @@ impl DiagnosticSet {
         styles
     }
 
-    #[allow(unused)]
     pub(crate) fn iter(&self) -> impl Iterator<Item = &DiagnosticEntry> {
         self.diagnostics.iter()
     }
@@ -31,6 +31,8 @@ pub struct SyntaxHighlighter {
     query: Option<Query>,
     /// The full injections query. This is used to build injection layers during parsing.
     injections_query: Option<Arc<Query>>,
+    /// The indents query for auto-indent, see [`LanguageConfig::indents`].
+    indents_query: Option<Query>,
 
     locals_pattern_index: usize,
     highlights_pattern_index: usize,
@@ -348,6 +350,7 @@ impl SyntaxHighlighter {
             language,
             query: None,
             injections_query: None,
+            indents_query: None,
             locals_pattern_index: 0,
             highlights_pattern_index: 0,
             non_local_variable_patterns: Vec::new(),
@@ -416,6 +419,14 @@ impl SyntaxHighlighter {
             None
         };
 
+        let indents_query = if !config.indents.is_empty() {
+            Query::new(grammar, &config.indents)
+                .inspect_err(|err| tracing::warn!("invalid indents query of {}: {}", lang, err))
+                .ok()
+        } else {
+            None
+        };
+
         // Injection layers are computed separately during parsing, so do not
         // emit injection captures from the main highlight query.
         for pattern_index in 0..locals_pattern_index {
@@ -467,6 +478,7 @@ impl SyntaxHighlighter {
             language: config.name.clone(),
             query: Some(query),
             injections_query,
+            indents_query,
 
             locals_pattern_index,
             highlights_pattern_index,
@@ -493,6 +505,46 @@ impl SyntaxHighlighter {
         self.tree.as_ref()
     }
 
+    /// Returns the byte ranges of the indented blocks that contain the `offset`, by the
+    /// indents query of the language, sorted from the outermost to the innermost.
+    ///
+    /// The range is from the start of the `@indent` node to the start of its `@end`
+    /// capture, or to the end of the node if there is no `@end`.
+    pub fn indent_ranges(&self, offset: usize) -> Vec<Range<usize>> {
+        let (Some(query), Some(tree)) = (self.indents_query.as_ref(), self.tree.as_ref()) else {
+            return vec![];
+        };
+        let Some(indent_ix) = query.capture_index_for_name("indent") else {
+            return vec![];
+        };
+        let end_ix = query.capture_index_for_name("end");
+
+        let mut cursor = QueryCursor::new();
+        cursor.set_byte_range(offset.saturating_sub(1)..offset + 1);
+        let mut matches = cursor.matches(query, tree.root_node(), TextProvider(&self.text));
+
+        let mut ranges: Vec<Range<usize>> = vec![];
+        while let Some(m) = matches.next() {
+            let Some(node) = m.captures.iter().find(|cap| cap.index == indent_ix) else {
+                continue;
+            };
+            let end = m
+                .captures
+                .iter()
+                .find(|cap| Some(cap.index) == end_ix)
+                .map_or(node.node.end_byte(), |cap| cap.node.start_byte());
+
+            let range = node.node.start_byte()..end;
+            if range.start < offset && offset <= range.end {
+                ranges.push(range);
+            }
+        }
+
+        ranges.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+        ranges.dedup_by_key(|range| range.start);
+        ranges
+    }
+
     /// Apply only the structural `edit` to the existing tree and update the stored text,
     /// without re-parsing.
     pub fn edit_tree(&mut self, edit: Option<InputEdit>, text: &Rope) {
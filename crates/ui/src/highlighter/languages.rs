@@ -1,6 +1,6 @@
 use gpui::SharedString;
 
-use crate::highlighter::LanguageConfig;
+use crate::highlighter::{BracketPair, CommentTokens, LanguageConfig};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, enum_iterator::Sequence)]
 pub enum Language {
@@ -365,6 +365,131 @@ impl Language {
         languages.into_iter().map(SharedString::from).collect()
     }
 
+    /// Return the bracket pairs for auto-closing and matching in the code editor.
+    fn brackets(&self) -> Vec<BracketPair> {
+        #[allow(unused_mut)]
+        let mut brackets = BracketPair::defaults();
+        match self {
+            // The `'` is also used for the lifetimes.
+            #[cfg(feature = "tree-sitter-rust")]
+            Self::Rust => {
+                if let Some(quote) = brackets.iter_mut().find(|pair| pair.open == '\'') {
+                    quote.auto_close = false;
+                }
+            }
+            #[cfg(feature = "tree-sitter-go")]
+            Self::Go => brackets.push(BracketPair::new('`', '`')),
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => brackets.push(BracketPair::new('`', '`')),
+            #[cfg(feature = "tree-sitter-typescript")]
+            Self::TypeScript => brackets.push(BracketPair::new('`', '`')),
+            #[cfg(feature = "tree-sitter-tsx")]
+            Self::Tsx => brackets.push(BracketPair::new('`', '`')),
+            #[cfg(feature = "tree-sitter-markdown")]
+            Self::Markdown => brackets.push(BracketPair::new('`', '`')),
+            _ => {}
+        }
+        brackets
+    }
+
+    /// Return the comment tokens for toggling comments in the code editor.
+    fn comment_tokens(&self) -> CommentTokens {
+        #[allow(unused_variables)]
+        let c_like = CommentTokens::new().line("//").block("/*", "*/");
+        #[allow(unused_variables)]
+        let html_like = CommentTokens::new().block("<!--", "-->");
+        #[allow(unused_variables)]
+        let hash = CommentTokens::new().line("#");
+
+        match self {
+            #[cfg(feature = "tree-sitter-astro")]
+            Self::Astro => html_like,
+            #[cfg(feature = "tree-sitter-bash")]
+            Self::Bash => hash,
+            #[cfg(feature = "tree-sitter-c")]
+            Self::C => c_like,
+            #[cfg(feature = "tree-sitter-cmake")]
+            Self::CMake => hash.block("#[[", "]]"),
+            #[cfg(feature = "tree-sitter-csharp")]
+            Self::CSharp => c_like,
+            #[cfg(feature = "tree-sitter-cpp")]
+            Self::Cpp => c_like,
+            #[cfg(feature = "tree-sitter-css")]
+            Self::Css => CommentTokens::new().block("/*", "*/"),
+            #[cfg(feature = "tree-sitter-ejs")]
+            Self::Ejs => CommentTokens::new().block("<%#", "%>"),
+            #[cfg(feature = "tree-sitter-elixir")]
+            Self::Elixir => hash,
+            #[cfg(feature = "tree-sitter-erb")]
+            Self::Erb => CommentTokens::new().block("<%#", "%>"),
+            #[cfg(feature = "tree-sitter-go")]
+            Self::Go => c_like,
+            #[cfg(feature = "tree-sitter-graphql")]
+            Self::GraphQL => hash,
+            #[cfg(feature = "tree-sitter-html")]
+            Self::Html => html_like,
+            #[cfg(feature = "tree-sitter-java")]
+            Self::Java => c_like,
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => c_like,
+            #[cfg(feature = "tree-sitter-kotlin")]
+            Self::Kotlin => c_like,
+            #[cfg(feature = "tree-sitter-lua")]
+            Self::Lua => CommentTokens::new().line("--").block("--[[", "]]"),
+            #[cfg(feature = "tree-sitter-make")]
+            Self::Make => hash,
+            #[cfg(feature = "tree-sitter-markdown")]
+            Self::Markdown => html_like,
+            #[cfg(feature = "tree-sitter-php")]
+            Self::Php => c_like,
+            #[cfg(feature = "tree-sitter-proto")]
+            Self::Proto => c_like,
+            #[cfg(feature = "tree-sitter-python")]
+            Self::Python => hash,
+            #[cfg(feature = "tree-sitter-ruby")]
+            Self::Ruby => hash,
+            #[cfg(feature = "tree-sitter-rust")]
+            Self::Rust => c_like,
+            #[cfg(feature = "tree-sitter-scala")]
+            Self::Scala => c_like,
+            #[cfg(feature = "tree-sitter-sql")]
+            Self::Sql => CommentTokens::new().line("--").block("/*", "*/"),
+            #[cfg(feature = "tree-sitter-svelte")]
+            Self::Svelte => html_like,
+            #[cfg(feature = "tree-sitter-swift")]
+            Self::Swift => c_like,
+            #[cfg(feature = "tree-sitter-toml")]
+            Self::Toml => hash,
+            #[cfg(feature = "tree-sitter-tsx")]
+            Self::Tsx => c_like,
+            #[cfg(feature = "tree-sitter-typescript")]
+            Self::TypeScript => c_like,
+            #[cfg(feature = "tree-sitter-yaml")]
+            Self::Yaml => hash,
+            #[cfg(feature = "tree-sitter-zig")]
+            Self::Zig => CommentTokens::new().line("//"),
+            _ => CommentTokens::default(),
+        }
+    }
+
+    /// Return the tree-sitter query for auto-indent in the code editor.
+    fn indents(&self) -> &'static str {
+        match self {
+            Self::Json => include_str!("languages/json/indents.scm"),
+            #[cfg(feature = "tree-sitter-rust")]
+            Self::Rust => include_str!("languages/rust/indents.scm"),
+            #[cfg(feature = "tree-sitter-go")]
+            Self::Go => include_str!("languages/go/indents.scm"),
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => include_str!("languages/javascript/indents.scm"),
+            #[cfg(feature = "tree-sitter-typescript")]
+            Self::TypeScript => include_str!("languages/javascript/indents.scm"),
+            #[cfg(feature = "tree-sitter-tsx")]
+            Self::Tsx => include_str!("languages/javascript/indents.scm"),
+            _ => "",
+        }
+    }
+
     /// Return the language info for the language.
     ///
     /// (language, query, injection, locals)
@@ -616,6 +741,9 @@ impl Language {
             injection,
             locals,
         )
+        .brackets(self.brackets())
+        .comment_tokens(self.comment_tokens())
+        .indents(self.indents())
     }
 }
 
@@ -1,5 +1,9 @@
 // Diagnostics module - works on all platforms (no tree-sitter dependency)
+mod brackets;
+mod comments;
 mod diagnostics;
+pub use brackets::*;
+pub use comments::*;
 pub use diagnostics::*;
 
 // Native implementation with full tree-sitter support
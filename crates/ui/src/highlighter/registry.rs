@@ -10,7 +10,7 @@ use std::{
 
 use crate::{
     ActiveTheme, DEFAULT_THEME_COLORS, ThemeMode,
-    highlighter::{Language, languages},
+    highlighter::{BracketPair, CommentTokens, Language, languages},
 };
 
 pub(super) const HIGHLIGHT_NAMES: [&str; 41] = [
@@ -65,6 +65,16 @@ pub struct LanguageConfig {
     pub highlights: SharedString,
     pub injections: SharedString,
     pub locals: SharedString,
+    /// The bracket pairs for auto-closing and matching in the code editor,
+    /// default is [`BracketPair::defaults`].
+    pub brackets: Vec<BracketPair>,
+    /// The comment tokens to toggle comments in the code editor, default is empty.
+    pub comment_tokens: CommentTokens,
+    /// The tree-sitter query for auto-indent and the active indent guide, default is empty.
+    ///
+    /// The `@indent` captures the node to indent its content, and the optional `@end`
+    /// captures the closing token of it, e.g.: `(block "}" @end) @indent`.
+    pub indents: SharedString,
 }
 
 impl LanguageConfig {
@@ -83,6 +93,9 @@ impl LanguageConfig {
             highlights: SharedString::from(highlights.to_string()),
             injections: SharedString::from(injections.to_string()),
             locals: SharedString::from(locals.to_string()),
+            brackets: BracketPair::defaults(),
+            comment_tokens: CommentTokens::default(),
+            indents: SharedString::default(),
         }
     }
 
@@ -95,9 +108,30 @@ impl LanguageConfig {
             highlights: SharedString::default(),
             injections: SharedString::default(),
             locals: SharedString::default(),
+            brackets: BracketPair::defaults(),
+            comment_tokens: CommentTokens::default(),
+            indents: SharedString::default(),
         }
     }
 
+    /// Set the bracket pairs for auto-closing and matching in the code editor.
+    pub fn brackets(mut self, brackets: Vec<BracketPair>) -> Self {
+        self.brackets = brackets;
+        self
+    }
+
+    /// Set the comment tokens to toggle comments in the code editor.
+    pub fn comment_tokens(mut self, comment_tokens: CommentTokens) -> Self {
+        self.comment_tokens = comment_tokens;
+        self
+    }
+
+    /// Set the tree-sitter query for auto-indent, see [`LanguageConfig::indents`].
+    pub fn indents(mut self, indents: &str) -> Self {
+        self.indents = SharedString::from(indents.to_string());
+        self
+    }
+
     /// Whether this language has a grammar to parse with.
     pub fn has_grammar(&self) -> bool {
         self.language.is_some()
@@ -448,6 +482,15 @@ pub struct HighlightThemeStyle {
     /// Falls back to [`Self::editor_background`] when unset.
     #[serde(rename = "editor.gutter.background")]
     pub editor_gutter_background: Option<Hsla>,
+    /// Background color of the occurrences of the symbol under the cursor (read access).
+    #[serde(rename = "editor.document_highlight.read_background")]
+    pub editor_document_highlight_read_background: Option<Hsla>,
+    /// Background color of the occurrences of the symbol under the cursor (write access).
+    #[serde(rename = "editor.document_highlight.write_background")]
+    pub editor_document_highlight_write_background: Option<Hsla>,
+    /// Background color of the matching brackets under the cursor.
+    #[serde(rename = "editor.document_highlight.bracket_background")]
+    pub editor_document_highlight_bracket_background: Option<Hsla>,
     #[serde(flatten)]
     pub status: StatusColors,
     #[serde(rename = "syntax")]
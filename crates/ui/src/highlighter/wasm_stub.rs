@@ -55,6 +55,10 @@ impl SyntaxHighlighter {
     pub fn tree(&self) -> Option<&crate::input::Tree> {
         None
     }
+
+    pub fn indent_ranges(&self, _offset: usize) -> Vec<Range<usize>> {
+        Vec::new()
+    }
 }
 
 // Language enum stub
@@ -75,6 +79,8 @@ impl Language {
     pub fn config(&self) -> LanguageConfig {
         LanguageConfig {
             name: "unknown".into(),
+            brackets: super::BracketPair::defaults(),
+            comment_tokens: super::CommentTokens::default(),
         }
     }
 
@@ -87,6 +93,8 @@ impl Language {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LanguageConfig {
     pub name: SharedString,
+    pub brackets: Vec<super::BracketPair>,
+    pub comment_tokens: super::CommentTokens,
 }
 
 impl LanguageConfig {
@@ -408,6 +416,12 @@ pub struct HighlightThemeStyle {
     pub editor_invisible: Option<gpui::Hsla>,
     #[serde(rename = "editor.gutter.background")]
     pub editor_gutter_background: Option<gpui::Hsla>,
+    #[serde(rename = "editor.document_highlight.read_background")]
+    pub editor_document_highlight_read_background: Option<gpui::Hsla>,
+    #[serde(rename = "editor.document_highlight.write_background")]
+    pub editor_document_highlight_write_background: Option<gpui::Hsla>,
+    #[serde(rename = "editor.document_highlight.bracket_background")]
+    pub editor_document_highlight_bracket_background: Option<gpui::Hsla>,
     #[serde(flatten)]
     pub status: StatusColors,
     #[serde(rename = "syntax")]
@@ -0,0 +1,219 @@
+use std::ops::Range;
+
+use gpui::{Context, Window};
+
+use crate::{
+    highlighter::{BracketPair, LanguageRegistry},
+    input::{InputState, RopeExt as _},
+};
+
+/// The max number of characters to scan for the matching bracket to highlight.
+const MAX_MATCHING_SCAN_CHARS: usize = 50_000;
+
+impl InputState {
+    /// Set whether to auto-close the brackets and quotes in code editor mode, default is true.
+    ///
+    /// When enabled, typing an open bracket inserts the close one, typing a close bracket
+    /// before the same one moves over it, and typing an open bracket with a selection
+    /// surrounds the selection. The pairs come from [`LanguageConfig::brackets`].
+    ///
+    /// [`LanguageConfig::brackets`]: crate::highlighter::LanguageConfig::brackets
+    pub fn auto_close_brackets(mut self, auto_close: bool) -> Self {
+        self.auto_close_brackets = auto_close;
+        self
+    }
+
+    /// Update whether to auto-close the brackets and quotes.
+    pub fn set_auto_close_brackets(
+        &mut self,
+        auto_close: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.auto_close_brackets = auto_close;
+        cx.notify();
+    }
+
+    /// Returns the bracket pairs of the current language.
+    pub(super) fn bracket_pairs(&self) -> Vec<BracketPair> {
+        self.mode
+            .language()
+            .and_then(|language| LanguageRegistry::singleton().language(language))
+            .map(|config| config.brackets)
+            .unwrap_or_else(BracketPair::defaults)
+    }
+
+    fn previous_char(&self, offset: usize) -> Option<char> {
+        self.text.chars_at(offset).reversed().next()
+    }
+
+    /// Handle the typed text for the bracket pairs, called before the text is inserted.
+    ///
+    /// Returns true if the input has been handled.
+    pub(super) fn handle_bracket_input(
+        &mut self,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.auto_close_brackets || !self.mode.is_code_editor() {
+            return false;
+        }
+
+        let mut chars = new_text.chars();
+        let (Some(ch), None) = (chars.next(), chars.next()) else {
+            return false;
+        };
+
+        let pairs = self.bracket_pairs();
+        let range: Range<usize> = self.selected_range.into();
+
+        if !range.is_empty() {
+            let Some(pair) = pairs.iter().find(|pair| pair.open == ch && pair.surround) else {
+                return false;
+            };
+
+            let selected_text = self.text.slice(range.clone()).to_string();
+            let new_text = format!("{}{}{}", pair.open, selected_text, pair.close);
+            let range_utf16 = self.range_to_utf16(&range);
+            self.replace_text_in_range_silent(Some(range_utf16), &new_text, window, cx);
+
+            // Keep the surrounded text selected.
+            let start = range.start + pair.open.len_utf8();
+            self.selected_range = (start..start + selected_text.len()).into();
+            cx.notify();
+            return true;
+        }
+
+        let offset = range.start;
+        let next_char = self.text.char_at(offset);
+
+        // Move over the close character, if it is the next one.
+        if next_char == Some(ch) && pairs.iter().any(|pair| pair.close == ch) {
+            self.move_to(offset + ch.len_utf8(), None, cx);
+            return true;
+        }
+
+        let Some(pair) = pairs.iter().find(|pair| pair.open == ch && pair.auto_close) else {
+            return false;
+        };
+
+        // Only close the bracket before a whitespace, a delimiter or a close bracket,
+        // to avoid closing when typing before a word, e.g. `|foo` -> `(|foo`.
+        let is_closable_before = next_char.is_none_or(|c| {
+            c.is_whitespace() || matches!(c, ',' | ';' | ':') || pairs.iter().any(|p| p.close == c)
+        });
+        // Do not close the quote after a word, e.g. `don't`.
+        let is_closable_after = !pair.is_quote()
+            || !self
+                .previous_char(offset)
+                .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == ch);
+        if !is_closable_before || !is_closable_after {
+            return false;
+        }
+
+        // Insert the close character, then let the open character to be inserted as
+        // a normal input, so that the completion is still triggered.
+        self.replace_text_in_range_silent(None, &pair.close.to_string(), window, cx);
+        self.selected_range = (offset..offset).into();
+        false
+    }
+
+    /// Returns the range of the empty bracket pair around the cursor, e.g. `(|)`,
+    /// used to delete both brackets by backspace.
+    pub(super) fn empty_bracket_pair_range(&self) -> Option<Range<usize>> {
+        if !self.auto_close_brackets || !self.mode.is_code_editor() {
+            return None;
+        }
+
+        let offset = self.cursor();
+        let prev_char = self.previous_char(offset)?;
+        let next_char = self.text.char_at(offset)?;
+        self.bracket_pairs()
+            .iter()
+            .find(|pair| pair.auto_close && pair.open == prev_char && pair.close == next_char)
+            .map(|_| offset - prev_char.len_utf8()..offset + next_char.len_utf8())
+    }
+
+    /// Returns the offset of the bracket matching the bracket at the `offset`.
+    ///
+    /// The quotes are ignored, only scan `limit` characters at most.
+    pub(super) fn matching_bracket(
+        &self,
+        offset: usize,
+        pairs: &[BracketPair],
+        limit: usize,
+    ) -> Option<usize> {
+        let bracket = self.text.char_at(offset)?;
+        let (pair, forward) = pairs
+            .iter()
+            .filter(|pair| !pair.is_quote())
+            .find_map(|pair| {
+                if pair.open == bracket {
+                    Some((pair, true))
+                } else if pair.close == bracket {
+                    Some((pair, false))
+                } else {
+                    None
+                }
+            })?;
+
+        let mut depth = 0usize;
+        if forward {
+            let mut pos = offset;
+            for c in self.text.chars_at(offset).take(limit) {
+                if c == pair.open {
+                    depth += 1;
+                } else if c == pair.close {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(pos);
+                    }
+                }
+                pos += c.len_utf8();
+            }
+        } else {
+            let mut pos = offset + bracket.len_utf8();
+            for c in self.text.chars_at(pos).reversed().take(limit) {
+                pos -= c.len_utf8();
+                if c == pair.close {
+                    depth += 1;
+                } else if c == pair.open {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(pos);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the ranges of the bracket at the cursor and its matching bracket,
+    /// for highlighting in code editor mode.
+    ///
+    /// The bracket after the cursor is preferred, then the one before the cursor.
+    pub(super) fn matching_bracket_ranges(&self) -> Option<[Range<usize>; 2]> {
+        if !self.mode.is_code_editor() || !self.selected_range.is_empty() {
+            return None;
+        }
+
+        let pairs = self.bracket_pairs();
+        let cursor = self.cursor();
+        [
+            Some(cursor),
+            self.previous_char(cursor).map(|c| cursor - c.len_utf8()),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|offset| {
+            let matched = self.matching_bracket(offset, &pairs, MAX_MATCHING_SCAN_CHARS)?;
+            let len = |offset: usize| self.text.char_at(offset).map_or(0, |c| c.len_utf8());
+            Some([
+                offset..offset + len(offset),
+                matched..matched + len(matched),
+            ])
+        })
+    }
+}
@@ -0,0 +1,354 @@
+use std::ops::{Range, RangeInclusive};
+
+use gpui::{Context, Window};
+
+use crate::{
+    highlighter::{CommentTokens, LanguageRegistry},
+    input::{InputState, RopeExt as _, ToggleBlockComment, ToggleLineComment},
+};
+
+/// Returns the leading whitespace of the `line`.
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Returns the edits to toggle the line comments of the `text`, the edits are the byte
+/// ranges of the `text` and the new texts, sorted by the start.
+///
+/// The blank lines are skipped. The lines are uncommented if all the lines are commented,
+/// otherwise the token is inserted after the common leading whitespace of the lines, so
+/// the comments are aligned even if the lines are indented by mixed tabs and spaces.
+fn toggle_line_comment_edits(text: &str, token: &str) -> Vec<(Range<usize>, String)> {
+    let mut offset = 0;
+    let mut lines = vec![];
+    for line in text.split('\n') {
+        if !line.trim().is_empty() {
+            lines.push((offset, line));
+        }
+        offset += line.len() + 1;
+    }
+
+    if lines.is_empty() {
+        return vec![];
+    }
+
+    let commented = lines
+        .iter()
+        .all(|(_, line)| line.trim_start().starts_with(token));
+
+    if commented {
+        return lines
+            .into_iter()
+            .map(|(offset, line)| {
+                let start = offset + leading_whitespace(line).len();
+                let mut end = start + token.len();
+                if line[end - offset..].starts_with(' ') {
+                    end += 1;
+                }
+                (start..end, String::new())
+            })
+            .collect();
+    }
+
+    let mut indent = leading_whitespace(lines[0].1);
+    for (_, line) in &lines[1..] {
+        let len = indent
+            .char_indices()
+            .zip(leading_whitespace(line).chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((ix, c), _)| ix + c.len_utf8());
+        indent = &indent[..len];
+    }
+
+    lines
+        .into_iter()
+        .map(|(offset, _)| {
+            let at = offset + indent.len();
+            (at..at, format!("{} ", token))
+        })
+        .collect()
+}
+
+/// Returns the edits to toggle the block comment around the `text`, the leading and
+/// trailing whitespace are kept out of the comment.
+fn toggle_block_comment_edits(
+    text: &str,
+    start_token: &str,
+    end_token: &str,
+) -> Vec<(Range<usize>, String)> {
+    let start = leading_whitespace(text).len();
+    let end = text.trim_end().len();
+    if start >= end {
+        return vec![];
+    }
+
+    let content = &text[start..end];
+    if content.len() >= start_token.len() + end_token.len()
+        && content.starts_with(start_token)
+        && content.ends_with(end_token)
+    {
+        let mut open = start..start + start_token.len();
+        let mut close = end - end_token.len()..end;
+        if text[open.end..close.start].starts_with(' ') {
+            open.end += 1;
+        }
+        if close.start > open.end && text[open.end..close.start].ends_with(' ') {
+            close.start -= 1;
+        }
+        return vec![(open, String::new()), (close, String::new())];
+    }
+
+    vec![
+        (start..start, format!("{} ", start_token)),
+        (end..end, format!(" {}", end_token)),
+    ]
+}
+
+/// Returns the new offset of the `offset` after the `edits` are applied, the `edits`
+/// are sorted by the start. An offset inside a removed range is moved to its start.
+fn adjust_offset_for_edits(offset: usize, edits: &[(Range<usize>, String)]) -> usize {
+    let mut delta = 0isize;
+    for (range, new_text) in edits {
+        if range.start > offset || (range.start == offset && !range.is_empty()) {
+            break;
+        }
+
+        if range.end > offset {
+            return (range.start as isize + delta) as usize;
+        }
+        delta += new_text.len() as isize - range.len() as isize;
+    }
+
+    (offset as isize + delta) as usize
+}
+
+impl InputState {
+    pub(super) fn toggle_line_comment(
+        &mut self,
+        _: &ToggleLineComment,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode.is_code_editor() {
+            cx.propagate();
+            return;
+        }
+
+        let tokens = self.comment_tokens();
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            let rows = this.selected_rows();
+            let start = this.text.line_start_offset(*rows.start());
+            let end = this.text.line_end_offset(*rows.end());
+            let text = this.text.slice(start..end).to_string();
+
+            // Use the block comment for the languages without line comment, e.g. CSS.
+            let edits = match (&tokens.line, &tokens.block) {
+                (Some(token), _) => toggle_line_comment_edits(&text, token),
+                (None, Some((start_token, end_token))) => {
+                    toggle_block_comment_edits(&text, start_token, end_token)
+                }
+                (None, None) => return,
+            };
+            this.apply_comment_edits(start, edits, window, cx);
+        });
+    }
+
+    pub(super) fn toggle_block_comment(
+        &mut self,
+        _: &ToggleBlockComment,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode.is_code_editor() {
+            cx.propagate();
+            return;
+        }
+
+        let tokens = self.comment_tokens();
+        let Some((start_token, end_token)) = tokens.block.clone() else {
+            // Fallback to the line comment for the languages without block comment.
+            self.toggle_line_comment(&ToggleLineComment, window, cx);
+            return;
+        };
+
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            let range = if this.selected_range.is_empty() {
+                let row = this.text.offset_to_point(this.cursor()).row;
+                this.text.line_start_offset(row)..this.text.line_end_offset(row)
+            } else {
+                this.selected_range.start..this.selected_range.end
+            };
+            let text = this.text.slice(range.clone()).to_string();
+            this.apply_comment_edits(
+                range.start,
+                toggle_block_comment_edits(&text, &start_token, &end_token),
+                window,
+                cx,
+            );
+        });
+    }
+
+    /// Returns the comment tokens of the current language.
+    fn comment_tokens(&self) -> CommentTokens {
+        self.mode
+            .language()
+            .and_then(|language| LanguageRegistry::singleton().language(language))
+            .map(|config| config.comment_tokens)
+            .unwrap_or_default()
+    }
+
+    /// Returns the first and the last row of the selection.
+    ///
+    /// The last row is excluded if the selection ends at its start, e.g. the
+    /// lines are selected by `shift-down`.
+    pub(super) fn selected_rows(&self) -> RangeInclusive<usize> {
+        let start = self.text.offset_to_point(self.selected_range.start).row;
+        let end = self.text.offset_to_point(self.selected_range.end);
+        if end.row > start && end.column == 0 {
+            start..=end.row - 1
+        } else {
+            start..=end.row
+        }
+    }
+
+    /// Apply the edits relative to the `base` offset as one undo step, and keep the
+    /// selection on the same text.
+    fn apply_comment_edits(
+        &mut self,
+        base: usize,
+        edits: Vec<(Range<usize>, String)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if edits.is_empty() {
+            return;
+        }
+
+        let edits = edits
+            .into_iter()
+            .map(|(range, new_text)| (range.start + base..range.end + base, new_text))
+            .collect::<Vec<_>>();
+        let start = adjust_offset_for_edits(self.selected_range.start, &edits);
+        let end = adjust_offset_for_edits(self.selected_range.end, &edits);
+
+        // Apply from the end, so that the ranges of the edits before are not changed.
+        for (ix, (range, new_text)) in edits.iter().rev().enumerate() {
+            if ix > 0 {
+                self.history.start_grouping();
+            }
+            let range_utf16 = self.range_to_utf16(range);
+            self.replace_text_in_range_silent(Some(range_utf16), new_text, window, cx);
+        }
+        self.history.end_grouping();
+
+        self.selected_range = (start..end).into();
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adjust_offset_for_edits, toggle_block_comment_edits, toggle_line_comment_edits};
+
+    fn apply(text: &str, edits: &[(std::ops::Range<usize>, String)]) -> String {
+        let mut text = text.to_string();
+        for (range, new_text) in edits.iter().rev() {
+            text.replace_range(range.clone(), new_text);
+        }
+        text
+    }
+
+    #[test]
+    fn test_toggle_line_comment() {
+        let text = "fn main() {\n    let a = 1;\n\n    a\n}";
+        let commented = apply(text, &toggle_line_comment_edits(text, "//"));
+        assert_eq!(
+            commented,
+            "// fn main() {\n//     let a = 1;\n\n//     a\n// }"
+        );
+        assert_eq!(
+            apply(&commented, &toggle_line_comment_edits(&commented, "//")),
+            text
+        );
+
+        // Insert after the common indent.
+        let text = "    let a = 1;\n        a";
+        let commented = apply(text, &toggle_line_comment_edits(text, "#"));
+        assert_eq!(commented, "    # let a = 1;\n    #     a");
+        assert_eq!(
+            apply(&commented, &toggle_line_comment_edits(&commented, "#")),
+            text
+        );
+
+        // Mixed tabs and spaces.
+        let text = "\t  a\n\t\tb\n\t c";
+        assert_eq!(
+            apply(text, &toggle_line_comment_edits(text, "//")),
+            "\t//   a\n\t// \tb\n\t//  c"
+        );
+        let text = "    a\n\tb";
+        assert_eq!(
+            apply(text, &toggle_line_comment_edits(text, "//")),
+            "//     a\n// \tb"
+        );
+
+        // Some lines are not commented, comment all.
+        let text = "// a\nb";
+        assert_eq!(
+            apply(text, &toggle_line_comment_edits(text, "//")),
+            "// // a\n// b"
+        );
+
+        // Uncomment without the space after the token.
+        let text = "  //a\n  // b\r\n";
+        assert_eq!(
+            apply(text, &toggle_line_comment_edits(text, "//")),
+            "  a\n  b\r\n"
+        );
+
+        assert!(toggle_line_comment_edits("  \n", "//").is_empty());
+    }
+
+    #[test]
+    fn test_toggle_block_comment() {
+        let text = "  let a = 1;\n  a  ";
+        let commented = apply(text, &toggle_block_comment_edits(text, "/*", "*/"));
+        assert_eq!(commented, "  /* let a = 1;\n  a */  ");
+        assert_eq!(
+            apply(
+                &commented,
+                &toggle_block_comment_edits(&commented, "/*", "*/")
+            ),
+            text
+        );
+
+        // Without the spaces inside.
+        let text = "<!--a-->";
+        assert_eq!(
+            apply(text, &toggle_block_comment_edits(text, "<!--", "-->")),
+            "a"
+        );
+        // Empty comment.
+        let text = "/* */";
+        assert_eq!(
+            apply(text, &toggle_block_comment_edits(text, "/*", "*/")),
+            ""
+        );
+
+        assert!(toggle_block_comment_edits("  ", "/*", "*/").is_empty());
+    }
+
+    #[test]
+    fn test_adjust_offset_for_edits() {
+        let edits = vec![(4..4, "// ".to_string()), (10..13, "".to_string())];
+        assert_eq!(adjust_offset_for_edits(0, &edits), 0);
+        // Insert at the offset, move after the insertion.
+        assert_eq!(adjust_offset_for_edits(4, &edits), 7);
+        assert_eq!(adjust_offset_for_edits(10, &edits), 13);
+        // Inside the removed range.
+        assert_eq!(adjust_offset_for_edits(11, &edits), 13);
+        assert_eq!(adjust_offset_for_edits(13, &edits), 13);
+        assert_eq!(adjust_offset_for_edits(20, &edits), 20);
+    }
+}
@@ -0,0 +1,515 @@
+use std::collections::HashSet;
+
+use gpui::{
+    App, Context, ElementId, Entity, EventEmitter, InteractiveElement as _, IntoElement,
+    ParentElement as _, RenderOnce, SharedString, StatefulInteractiveElement as _, StyleRefinement,
+    Styled, Subscription, Window, div, prelude::FluentBuilder as _,
+};
+use rust_i18n::t;
+
+use crate::{
+    ActiveTheme, Icon, IconName, Sizable as _, StyledExt as _, h_flex,
+    highlighter::{Diagnostic, DiagnosticSeverity},
+    v_flex,
+};
+
+use super::{InputState, Position};
+
+/// How the [`DiagnosticsPanel`] groups the diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiagnosticsGroupBy {
+    /// Group by the source input (file).
+    #[default]
+    File,
+    /// Group by the severity.
+    Severity,
+}
+
+/// Events emitted by the [`DiagnosticsPanelState`].
+#[derive(Debug, Clone)]
+pub enum DiagnosticsPanelEvent {
+    /// A diagnostic has been clicked, and the cursor of the `input` has been moved to the `position`.
+    Select {
+        input: Entity<InputState>,
+        position: Position,
+    },
+}
+
+struct DiagnosticsSource {
+    name: SharedString,
+    input: Entity<InputState>,
+    _subscription: Subscription,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DiagnosticItem {
+    /// The index of the source in [`DiagnosticsPanelState::sources`].
+    source_ix: usize,
+    diagnostic: Diagnostic,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DiagnosticGroup {
+    id: SharedString,
+    label: SharedString,
+    severity: Option<DiagnosticSeverity>,
+    items: Vec<DiagnosticItem>,
+}
+
+/// The state of the [`DiagnosticsPanel`].
+///
+/// Collects the diagnostics of one or more [`InputState`]s, and refreshes when any of them is notified.
+pub struct DiagnosticsPanelState {
+    sources: Vec<DiagnosticsSource>,
+    group_by: DiagnosticsGroupBy,
+    /// The ids of the collapsed groups.
+    collapsed: HashSet<SharedString>,
+}
+
+impl EventEmitter<DiagnosticsPanelEvent> for DiagnosticsPanelState {}
+
+impl DiagnosticsPanelState {
+    /// Create a new [`DiagnosticsPanelState`].
+    pub fn new(_: &mut Window, _: &mut Context<Self>) -> Self {
+        Self {
+            sources: vec![],
+            group_by: DiagnosticsGroupBy::default(),
+            collapsed: HashSet::new(),
+        }
+    }
+
+    /// Set how the diagnostics are grouped, default is [`DiagnosticsGroupBy::File`].
+    pub fn group_by(mut self, group_by: DiagnosticsGroupBy) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    /// Set how the diagnostics are grouped.
+    pub fn set_group_by(
+        &mut self,
+        group_by: DiagnosticsGroupBy,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.group_by = group_by;
+        self.collapsed.clear();
+        cx.notify();
+    }
+
+    /// Add an [`InputState`] to collect diagnostics from, with the `name` (e.g. the file name) of it.
+    ///
+    /// Adding the same input again only updates the name.
+    pub fn add_input(
+        &mut self,
+        name: impl Into<SharedString>,
+        input: &Entity<InputState>,
+        cx: &mut Context<Self>,
+    ) {
+        let name = name.into();
+        if let Some(source) = self.sources.iter_mut().find(|s| &s.input == input) {
+            source.name = name;
+        } else {
+            self.sources.push(DiagnosticsSource {
+                name,
+                input: input.clone(),
+                _subscription: cx.observe(input, |_, _, cx| cx.notify()),
+            });
+        }
+        cx.notify();
+    }
+
+    /// Remove an [`InputState`] from the panel.
+    pub fn remove_input(&mut self, input: &Entity<InputState>, cx: &mut Context<Self>) {
+        self.sources.retain(|s| &s.input != input);
+        cx.notify();
+    }
+
+    /// Return the total number of diagnostics.
+    pub fn len(&self, cx: &App) -> usize {
+        self.sources
+            .iter()
+            .filter_map(|s| s.input.read(cx).diagnostics())
+            .map(|set| set.len())
+            .sum()
+    }
+
+    /// Return the number of diagnostics with the `severity`.
+    pub fn count(&self, severity: DiagnosticSeverity, cx: &App) -> usize {
+        self.items(cx)
+            .iter()
+            .filter(|item| item.diagnostic.severity == severity)
+            .count()
+    }
+
+    fn items(&self, cx: &App) -> Vec<DiagnosticItem> {
+        let mut items = vec![];
+        for (source_ix, source) in self.sources.iter().enumerate() {
+            let Some(set) = source.input.read(cx).diagnostics() else {
+                continue;
+            };
+
+            items.extend(set.iter().map(|entry| DiagnosticItem {
+                source_ix,
+                diagnostic: entry.diagnostic.clone(),
+            }));
+        }
+        items
+    }
+
+    fn groups(&self, cx: &App) -> Vec<DiagnosticGroup> {
+        let names = self
+            .sources
+            .iter()
+            .map(|s| s.name.clone())
+            .collect::<Vec<_>>();
+        group_items(self.items(cx), self.group_by, &names)
+    }
+
+    fn toggle_group(&mut self, id: &SharedString, cx: &mut Context<Self>) {
+        if !self.collapsed.remove(id) {
+            self.collapsed.insert(id.clone());
+        }
+        cx.notify();
+    }
+
+    fn select(&mut self, item: &DiagnosticItem, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(source) = self.sources.get(item.source_ix) else {
+            return;
+        };
+
+        let input = source.input.clone();
+        let position = item.diagnostic.range.start;
+        input.update(cx, |state, cx| {
+            state.set_cursor_position(position, window, cx);
+        });
+        cx.emit(DiagnosticsPanelEvent::Select { input, position });
+    }
+}
+
+fn severity_order(severity: DiagnosticSeverity) -> usize {
+    match severity {
+        DiagnosticSeverity::Error => 0,
+        DiagnosticSeverity::Warning => 1,
+        DiagnosticSeverity::Info => 2,
+        DiagnosticSeverity::Hint => 3,
+    }
+}
+
+fn severity_label(severity: DiagnosticSeverity) -> SharedString {
+    match severity {
+        DiagnosticSeverity::Error => t!("DiagnosticsPanel.Error"),
+        DiagnosticSeverity::Warning => t!("DiagnosticsPanel.Warning"),
+        DiagnosticSeverity::Info => t!("DiagnosticsPanel.Info"),
+        DiagnosticSeverity::Hint => t!("DiagnosticsPanel.Hint"),
+    }
+    .to_string()
+    .into()
+}
+
+fn severity_icon(severity: DiagnosticSeverity, cx: &App) -> Icon {
+    let icon = match severity {
+        DiagnosticSeverity::Error => IconName::CircleX,
+        DiagnosticSeverity::Warning => IconName::TriangleAlert,
+        DiagnosticSeverity::Info | DiagnosticSeverity::Hint => IconName::Info,
+    };
+
+    Icon::new(icon).text_color(severity.fg(cx))
+}
+
+/// Group the diagnostics, the empty groups are omitted.
+///
+/// - [`DiagnosticsGroupBy::File`]: groups in the source order, items sorted by severity then position.
+/// - [`DiagnosticsGroupBy::Severity`]: groups from Error to Hint, items sorted by source then position.
+fn group_items(
+    mut items: Vec<DiagnosticItem>,
+    group_by: DiagnosticsGroupBy,
+    names: &[SharedString],
+) -> Vec<DiagnosticGroup> {
+    let position = |item: &DiagnosticItem| {
+        let start = item.diagnostic.range.start;
+        (start.line, start.character)
+    };
+
+    match group_by {
+        DiagnosticsGroupBy::File => {
+            items.sort_by_key(|item| {
+                (
+                    item.source_ix,
+                    severity_order(item.diagnostic.severity),
+                    position(item),
+                )
+            });
+
+            let mut groups: Vec<DiagnosticGroup> = vec![];
+            for item in items {
+                match groups.last_mut() {
+                    Some(group) if group.items[0].source_ix == item.source_ix => {
+                        group.items.push(item)
+                    }
+                    _ => groups.push(DiagnosticGroup {
+                        id: format!("file-{}", item.source_ix).into(),
+                        label: names.get(item.source_ix).cloned().unwrap_or_default(),
+                        severity: None,
+                        items: vec![item],
+                    }),
+                }
+            }
+            groups
+        }
+        DiagnosticsGroupBy::Severity => {
+            items.sort_by_key(|item| {
+                (
+                    severity_order(item.diagnostic.severity),
+                    item.source_ix,
+                    position(item),
+                )
+            });
+
+            let mut groups: Vec<DiagnosticGroup> = vec![];
+            for item in items {
+                let severity = item.diagnostic.severity;
+                match groups.last_mut() {
+                    Some(group) if group.severity == Some(severity) => group.items.push(item),
+                    _ => groups.push(DiagnosticGroup {
+                        id: format!("severity-{}", severity_order(severity)).into(),
+                        label: severity_label(severity),
+                        severity: Some(severity),
+                        items: vec![item],
+                    }),
+                }
+            }
+            groups
+        }
+    }
+}
+
+/// A problems panel that lists the diagnostics of the [`DiagnosticsPanelState`].
+///
+/// Click a diagnostic to move the cursor of its input to the position.
+#[derive(IntoElement)]
+pub struct DiagnosticsPanel {
+    state: Entity<DiagnosticsPanelState>,
+    style: StyleRefinement,
+}
+
+impl DiagnosticsPanel {
+    /// Create a new [`DiagnosticsPanel`] bind to the [`DiagnosticsPanelState`].
+    pub fn new(state: &Entity<DiagnosticsPanelState>) -> Self {
+        Self {
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for DiagnosticsPanel {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl DiagnosticsPanel {
+    fn render_group(
+        state: &Entity<DiagnosticsPanelState>,
+        group: DiagnosticGroup,
+        collapsed: bool,
+        show_name: bool,
+        names: &[SharedString],
+        cx: &App,
+    ) -> impl IntoElement {
+        let id = group.id.clone();
+
+        v_flex()
+            .child(
+                h_flex()
+                    .id(ElementId::from(id.clone()))
+                    .gap_1()
+                    .px_2()
+                    .py_0p5()
+                    .cursor_pointer()
+                    .hover(|this| this.bg(cx.theme().list_hover))
+                    .child(
+                        Icon::new(if collapsed {
+                            IconName::ChevronRight
+                        } else {
+                            IconName::ChevronDown
+                        })
+                        .xsmall()
+                        .text_color(cx.theme().muted_foreground),
+                    )
+                    .map(|this| match group.severity {
+                        Some(severity) => this.child(severity_icon(severity, cx).xsmall()),
+                        None => this.child(Icon::new(IconName::File).xsmall()),
+                    })
+                    .child(div().font_medium().child(group.label.clone()))
+                    .child(
+                        div()
+                            .px_1p5()
+                            .rounded_full()
+                            .text_xs()
+                            .bg(cx.theme().muted)
+                            .text_color(cx.theme().muted_foreground)
+                            .child(group.items.len().to_string()),
+                    )
+                    .on_click({
+                        let state = state.clone();
+                        move |_, _, cx| {
+                            state.update(cx, |state, cx| state.toggle_group(&id, cx));
+                        }
+                    }),
+            )
+            .when(!collapsed, |this| {
+                this.children(group.items.into_iter().enumerate().map(|(ix, item)| {
+                    Self::render_item(state, &group.id, ix, item, show_name, names, cx)
+                }))
+            })
+    }
+
+    fn render_item(
+        state: &Entity<DiagnosticsPanelState>,
+        group_id: &SharedString,
+        ix: usize,
+        item: DiagnosticItem,
+        show_name: bool,
+        names: &[SharedString],
+        cx: &App,
+    ) -> impl IntoElement {
+        let diagnostic = &item.diagnostic;
+        let start = diagnostic.range.start;
+        let source = match (&diagnostic.source, &diagnostic.code) {
+            (Some(source), Some(code)) => Some(format!("{}({})", source, code)),
+            (Some(source), None) => Some(source.to_string()),
+            (None, Some(code)) => Some(code.to_string()),
+            (None, None) => None,
+        };
+        let name = show_name
+            .then(|| names.get(item.source_ix).cloned())
+            .flatten();
+
+        h_flex()
+            .id(ElementId::NamedInteger(group_id.clone(), ix as u64))
+            .gap_1p5()
+            .pl_7()
+            .pr_2()
+            .py_0p5()
+            .cursor_pointer()
+            .hover(|this| this.bg(cx.theme().list_hover))
+            .child(severity_icon(diagnostic.severity, cx).xsmall())
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .child(diagnostic.message.clone()),
+            )
+            .child(
+                h_flex()
+                    .flex_shrink_0()
+                    .gap_1()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .when_some(source, |this, source| this.child(source))
+                    .when_some(name, |this, name| this.child(name))
+                    .child(format!("[{}:{}]", start.line + 1, start.character + 1)),
+            )
+            .on_click({
+                let state = state.clone();
+                move |_, window, cx| {
+                    state.update(cx, |state, cx| state.select(&item, window, cx));
+                }
+            })
+    }
+}
+
+impl RenderOnce for DiagnosticsPanel {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let groups = state.groups(cx);
+        let show_name = state.group_by == DiagnosticsGroupBy::Severity;
+        let names = state
+            .sources
+            .iter()
+            .map(|s| s.name.clone())
+            .collect::<Vec<_>>();
+        let collapsed = state.collapsed.clone();
+
+        v_flex()
+            .id(("diagnostics-panel", self.state.entity_id()))
+            .size_full()
+            .py_1()
+            .text_sm()
+            .overflow_y_scroll()
+            .refine_style(&self.style)
+            .when(groups.is_empty(), |this| {
+                this.child(
+                    div()
+                        .px_2()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(t!("DiagnosticsPanel.empty").to_string()),
+                )
+            })
+            .children(groups.into_iter().map(|group| {
+                let is_collapsed = collapsed.contains(&group.id);
+                Self::render_group(&self.state, group, is_collapsed, show_name, &names, cx)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(source_ix: usize, line: u32, severity: DiagnosticSeverity) -> DiagnosticItem {
+        DiagnosticItem {
+            source_ix,
+            diagnostic: Diagnostic::new(
+                Position::new(line, 0)..Position::new(line, 1),
+                format!("{}:{}", source_ix, line),
+            )
+            .with_severity(severity),
+        }
+    }
+
+    fn messages(group: &DiagnosticGroup) -> Vec<&str> {
+        group
+            .items
+            .iter()
+            .map(|item| item.diagnostic.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_group_items() {
+        let names: Vec<SharedString> = vec!["a.rs".into(), "b.rs".into()];
+        let items = vec![
+            item(1, 3, DiagnosticSeverity::Warning),
+            item(0, 5, DiagnosticSeverity::Hint),
+            item(0, 9, DiagnosticSeverity::Error),
+            item(1, 1, DiagnosticSeverity::Error),
+            item(0, 2, DiagnosticSeverity::Error),
+        ];
+
+        let groups = group_items(items.clone(), DiagnosticsGroupBy::File, &names);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].label.as_str(), "a.rs");
+        assert_eq!(messages(&groups[0]), vec!["0:2", "0:9", "0:5"]);
+        assert_eq!(groups[1].label.as_str(), "b.rs");
+        assert_eq!(messages(&groups[1]), vec!["1:1", "1:3"]);
+
+        let groups = group_items(items, DiagnosticsGroupBy::Severity, &names);
+        assert_eq!(
+            groups.iter().map(|g| g.severity).collect::<Vec<_>>(),
+            vec![
+                Some(DiagnosticSeverity::Error),
+                Some(DiagnosticSeverity::Warning),
+                Some(DiagnosticSeverity::Hint),
+            ]
+        );
+        assert_eq!(messages(&groups[0]), vec!["0:2", "0:9", "1:1"]);
+        assert_eq!(messages(&groups[1]), vec!["1:3"]);
+        assert_eq!(messages(&groups[2]), vec!["0:5"]);
+
+        assert!(group_items(vec![], DiagnosticsGroupBy::File, &names).is_empty());
+    }
+}
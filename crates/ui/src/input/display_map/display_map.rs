@@ -8,16 +8,21 @@ use std::ops::Range;
 
 use gpui::{App, Font, Pixels};
 use ropey::Rope;
+use sum_tree::SumTree;
 
 use super::fold_map::FoldMap;
 use super::folding::FoldRange;
-use super::text_wrapper::{LineItem, WrapDisplayPoint};
+use super::text_wrapper::{LineItem, TextWrapper, WrapDisplayPoint};
 use super::wrap_map::WrapMap;
 use super::{BufferPoint, DisplayPoint};
 use crate::input::Point as TreeSitterPoint;
 use crate::input::display_map::WrapPoint;
 use crate::input::rope_ext::RopeExt as _;
 
+/// The lines of a text prepared without soft wrap, see [`DisplayMap::prepare_lines`].
+#[derive(Clone)]
+pub(crate) struct PreparedLines(SumTree<LineItem>);
+
 /// DisplayMap is the main interface for Editor/Input coordinate mapping.
 ///
 /// It manages the two-layer projection:
@@ -139,6 +144,12 @@ impl DisplayMap {
         self.rebuild_fold_projection();
     }
 
+    /// Fold all the fold candidates
+    pub fn fold_all(&mut self) {
+        self.fold_map.fold_all();
+        self.rebuild_fold_projection();
+    }
+
     /// Toggle fold at the given start_line
     pub fn toggle_fold(&mut self, start_line: usize) {
         self.fold_map.toggle_fold(start_line);
@@ -157,6 +168,12 @@ impl DisplayMap {
         self.fold_map.is_fold_candidate(start_line)
     }
 
+    /// Get all fold candidates
+    #[inline]
+    pub fn fold_candidates(&self) -> &[FoldRange] {
+        self.fold_map.fold_candidates()
+    }
+
     /// Get all currently folded ranges
     #[inline]
     pub fn folded_ranges(&self) -> &[FoldRange] {
@@ -250,6 +267,22 @@ impl DisplayMap {
         self.rebuild_fold_projection();
     }
 
+    /// Prepare the lines of the `text` without soft wrap, see [`Self::set_text_with_lines`].
+    ///
+    /// It does not need the text system, so a very large text can be prepared on a
+    /// background thread.
+    pub(crate) fn prepare_lines(text: &Rope) -> PreparedLines {
+        PreparedLines(TextWrapper::unwrapped_lines(text))
+    }
+
+    /// Initialize with text and the lines from [`Self::prepare_lines`].
+    ///
+    /// If the soft wrap is enabled, the `lines` are ignored and the text is wrapped instead.
+    pub(crate) fn set_text_with_lines(&mut self, text: &Rope, lines: PreparedLines, cx: &mut App) {
+        self.wrap_map.set_text_with_lines(text, lines.0, cx);
+        self.rebuild_fold_projection();
+    }
+
     // ==================== Internal Helpers ====================
 
     /// Rebuild fold projection after wrap_map or fold state changes
@@ -160,6 +160,12 @@ impl FoldMap {
         }
     }
 
+    /// Fold all the candidates
+    pub fn fold_all(&mut self) {
+        self.folded = self.candidates.clone();
+        self.needs_rebuild = true;
+    }
+
     /// Toggle fold at the given start_line
     pub fn toggle_fold(&mut self, start_line: usize) {
         let is_folded = self.is_folded_at(start_line);
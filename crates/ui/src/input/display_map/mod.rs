@@ -18,6 +18,7 @@ mod wrap_map;
 
 // Re-export public API
 pub use self::display_map::DisplayMap;
+pub(crate) use self::display_map::PreparedLines;
 pub(crate) use self::text_wrapper::LineLayout;
 
 // Re-export FoldRange and extract_fold_ranges
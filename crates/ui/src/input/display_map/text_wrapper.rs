@@ -2,13 +2,16 @@ use gpui::Half;
 use std::ops::Range;
 
 use gpui::{
-    App, Font, LineFragment, Pixels, Point, ShapedLine, Size, TextAlign, Window, point, px, size,
+    App, Bounds, ContentMask, Font, LineFragment, Pixels, Point, ShapedLine, Size, TextAlign,
+    Window, point, px, size,
 };
 use ropey::Rope;
 use smallvec::SmallVec;
 use sum_tree::{Bias, Dimensions, SumTree};
 
-use crate::input::{LastLayout, Point as TreeSitterPoint, RopeExt, WhitespaceIndicators};
+use crate::input::{
+    LastLayout, Point as TreeSitterPoint, RopeExt, WhitespaceIndicators, WhitespaceVisibility,
+};
 
 /// A line with soft wrapped lines info.
 #[derive(Debug, Clone)]
@@ -345,6 +348,38 @@ impl TextWrapper {
         self.text = changed_text.clone();
     }
 
+    /// Build the lines of the `text` without soft wrap.
+    ///
+    /// It does not need the text system, so it can run on a background thread.
+    pub(crate) fn unwrapped_lines(text: &Rope) -> SumTree<LineItem> {
+        SumTree::from_iter(
+            text.iter_lines().map(|line| LineItem {
+                len: line.len(),
+                wrapped_lines: smallvec::smallvec![0..line.len()],
+            }),
+            &(),
+        )
+    }
+
+    /// Set the text with the `lines` built by [`Self::unwrapped_lines`].
+    ///
+    /// If the soft wrap is enabled, the `lines` are ignored and the text is wrapped instead.
+    pub(crate) fn set_text_with_lines(
+        &mut self,
+        text: &Rope,
+        lines: SumTree<LineItem>,
+        cx: &mut App,
+    ) {
+        self._initialized = true;
+        self.text = text.clone();
+        if self.wrap_width.is_some() {
+            self.lines = SumTree::new(&());
+            self.update_all(text, cx);
+        } else {
+            self.lines = lines;
+        }
+    }
+
     /// Update the text wrapper and recalculate the wrapped lines.
     ///
     /// If the `text` is the same as the current text, do nothing.
@@ -445,6 +480,19 @@ impl WrapDisplayPoint {
     }
 }
 
+/// A virtual text (e.g. LSP inlay hint) placed between the characters of a line.
+///
+/// The inlay is not a part of the text, the text after it is shifted right by its width.
+pub(crate) struct InlayLayout {
+    /// The index of the soft wrapped line.
+    wrap_ix: usize,
+    /// The local byte index in the soft wrapped line, the inlay is placed before this index.
+    offset: usize,
+    /// The x of the `offset` in the soft wrapped line, without the inlays.
+    x: Pixels,
+    label: ShapedLine,
+}
+
 /// The layout info of a line with soft wrapped lines.
 pub(crate) struct LineLayout {
     /// Total bytes length of this line.
@@ -455,6 +503,10 @@ pub(crate) struct LineLayout {
     pub(crate) whitespace_indicators: Option<WhitespaceIndicators>,
     /// Whitespace indicators: (line_index, x_position, is_tab)
     pub(crate) whitespace_chars: Vec<(usize, Pixels, bool)>,
+    /// Line ending indicator: (line_index, x_position, is_crlf)
+    pub(crate) line_ending: Option<(usize, Pixels, bool)>,
+    /// The inlays sorted by position.
+    inlays: Vec<InlayLayout>,
 }
 
 impl LineLayout {
@@ -464,7 +516,9 @@ impl LineLayout {
             longest_width: px(0.),
             wrapped_lines: SmallVec::new(),
             whitespace_chars: Vec::new(),
+            line_ending: None,
             whitespace_indicators: None,
+            inlays: Vec::new(),
         }
     }
 
@@ -484,20 +538,109 @@ impl LineLayout {
         self.wrapped_lines = wrapped_lines;
     }
 
-    pub(crate) fn with_whitespaces(mut self, indicators: Option<WhitespaceIndicators>) -> Self {
+    /// Set the inlays of this line, must be called after [`Self::lines`].
+    ///
+    /// The `inlays` are the local byte index in this line and the label, sorted by the index.
+    pub(crate) fn with_inlays(mut self, inlays: Vec<(usize, ShapedLine)>) -> Self {
+        for (offset, label) in inlays {
+            let mut acc_len = 0;
+            for (wrap_ix, line) in self.wrapped_lines.iter().enumerate() {
+                let is_last = wrap_ix + 1 == self.wrapped_lines.len();
+                if offset < acc_len + line.len || is_last {
+                    let offset = offset.saturating_sub(acc_len).min(line.len);
+                    self.inlays.push(InlayLayout {
+                        wrap_ix,
+                        offset,
+                        x: line.x_for_index(offset),
+                        label,
+                    });
+                    break;
+                }
+                acc_len += line.len;
+            }
+        }
+
+        for (wrap_ix, line) in self.wrapped_lines.iter().enumerate() {
+            let width = line.width + self.inlays_width(wrap_ix, usize::MAX);
+            self.longest_width = self.longest_width.max(width);
+        }
+        self
+    }
+
+    /// Returns the total width of the inlays before the `ix` in the soft wrapped line.
+    fn inlays_width(&self, wrap_ix: usize, ix: usize) -> Pixels {
+        self.inlays
+            .iter()
+            .filter(|inlay| inlay.wrap_ix == wrap_ix && inlay.offset < ix)
+            .map(|inlay| inlay.label.width)
+            .sum()
+    }
+
+    /// Returns the x of the `ix` in the soft wrapped line, include the inlays before it.
+    ///
+    /// The `ix` at an inlay is placed before the inlay.
+    fn x_for_index(&self, wrap_ix: usize, ix: usize) -> Pixels {
+        self.wrapped_lines[wrap_ix].x_for_index(ix) + self.inlays_width(wrap_ix, ix)
+    }
+
+    /// Convert the `x` in the soft wrapped line with the inlays to the x without the inlays.
+    ///
+    /// The `x` over an inlay is moved to the position of the inlay.
+    fn x_without_inlays(&self, wrap_ix: usize, x: Pixels) -> Pixels {
+        let mut inlays_width = px(0.);
+        for inlay in self.inlays.iter().filter(|inlay| inlay.wrap_ix == wrap_ix) {
+            let start = inlay.x + inlays_width;
+            if x < start {
+                break;
+            }
+            if x < start + inlay.label.width {
+                return inlay.x;
+            }
+            inlays_width += inlay.label.width;
+        }
+
+        x - inlays_width
+    }
+
+    /// Set the whitespace indicators of this line, must be called after [`Self::with_inlays`].
+    ///
+    /// The `has_line_ending` is false for the last line of the text.
+    pub(crate) fn with_whitespaces(
+        mut self,
+        indicators: Option<WhitespaceIndicators>,
+        has_line_ending: bool,
+    ) -> Self {
         self.whitespace_indicators = indicators;
         let Some(indicators) = self.whitespace_indicators.as_ref() else {
             return self;
         };
 
         let space_indicator_offset = indicators.space.width.half();
+        let text = self
+            .wrapped_lines
+            .iter()
+            .map(|line| line.text.as_ref())
+            .collect::<String>();
+        let visible_start = match indicators.visibility {
+            WhitespaceVisibility::None => text.len(),
+            WhitespaceVisibility::Trailing => trailing_whitespace_start(&text),
+            WhitespaceVisibility::All => 0,
+        };
 
+        let mut line_start = 0;
         for (line_index, wrapped_line) in self.wrapped_lines.iter().enumerate() {
             for (relative_offset, c) in wrapped_line.text.char_indices() {
+                if line_start + relative_offset < visible_start {
+                    continue;
+                }
+
                 if matches!(c, ' ' | '\t') {
                     let is_tab = c == '\t';
-                    let start_x = wrapped_line.x_for_index(relative_offset);
-                    let end_x = wrapped_line.x_for_index(relative_offset + c.len_utf8());
+                    // The character is painted after the inlays at its index.
+                    let inlays_width = self.inlays_width(line_index, relative_offset + 1);
+                    let start_x = wrapped_line.x_for_index(relative_offset) + inlays_width;
+                    let end_x =
+                        wrapped_line.x_for_index(relative_offset + c.len_utf8()) + inlays_width;
                     // Center the indicator in the actual character's space
                     let x_position = if c == ' ' {
                         (start_x + end_x).half() - space_indicator_offset
@@ -508,6 +651,18 @@ impl LineLayout {
                     self.whitespace_chars.push((line_index, x_position, is_tab));
                 }
             }
+            line_start += wrapped_line.len;
+        }
+
+        if has_line_ending
+            && indicators.line_endings.is_some()
+            && let Some(last) = self.wrapped_lines.last()
+        {
+            let line_index = self.wrapped_lines.len() - 1;
+            let is_crlf = text.ends_with('\r');
+            let end = last.len - if is_crlf { 1 } else { 0 };
+            let x_position = last.x_for_index(end) + self.inlays_width(line_index, usize::MAX);
+            self.line_ending = Some((line_index, x_position, is_crlf));
         }
         self
     }
@@ -549,7 +704,7 @@ impl LineLayout {
             };
 
             if matches {
-                let x = line.x_for_index(offset.saturating_sub(acc_len)) + x_offset;
+                let x = self.x_for_index(i, offset.saturating_sub(acc_len)) + x_offset;
                 return Some(point(x, offset_y));
             }
 
@@ -570,8 +725,8 @@ impl LineLayout {
 
         for (i, line) in self.wrapped_lines.iter().enumerate() {
             let is_last = i + 1 == self.wrapped_lines.len();
-            if x <= line.width {
-                let mut ix = line.closest_index_for_x(x);
+            if x <= line.width + self.inlays_width(i, usize::MAX) {
+                let mut ix = line.closest_index_for_x(self.x_without_inlays(i, x));
                 if !is_last && ix == line.text.len() {
                     // For soft wrap line, we can't put the cursor at the end of the line.
                     let c_len = line.text.chars().last().map(|c| c.len_utf8()).unwrap_or(0);
@@ -602,7 +757,7 @@ impl LineLayout {
             let is_last = i + 1 == self.wrapped_lines.len();
             let line_bottom = line_top + last_layout.line_height;
             if pos.y >= line_top && pos.y < line_bottom {
-                let mut ix = line.closest_index_for_x(pos.x - x_offset);
+                let mut ix = line.closest_index_for_x(self.x_without_inlays(i, pos.x - x_offset));
                 if !is_last && ix == line.text.len() {
                     // For soft wrap line, we can't put the cursor at the end of the line.
                     let c_len = line.text.chars().last().map(|c| c.len_utf8()).unwrap_or(0);
@@ -626,10 +781,10 @@ impl LineLayout {
         let mut offset = 0;
         let mut line_top = px(0.);
         let x_offset = last_layout.alignment_offset(self.longest_width);
-        for line in self.wrapped_lines.iter() {
+        for (i, line) in self.wrapped_lines.iter().enumerate() {
             let line_bottom = line_top + last_layout.line_height;
             if pos.y >= line_top && pos.y < line_bottom {
-                let ix = line.index_for_x(pos.x - x_offset)?;
+                let ix = line.index_for_x(self.x_without_inlays(i, pos.x - x_offset))?;
                 return Some(offset + ix);
             }
 
@@ -654,8 +809,39 @@ impl LineLayout {
         cx: &mut App,
     ) {
         for (ix, line) in self.wrapped_lines.iter().enumerate() {
-            _ = line.paint(
-                pos + point(px(0.), ix * line_height),
+            let line_pos = pos + point(px(0.), ix * line_height);
+            if !self.inlays.iter().any(|inlay| inlay.wrap_ix == ix) {
+                _ = line.paint(line_pos, line_height, text_align, align_width, window, cx);
+                continue;
+            }
+
+            // Paint the text segments between the inlays, each shifted by the inlays before it.
+            let mask = window.content_mask().bounds;
+            let mut segment_left = mask.left();
+            let mut inlays_width = px(0.);
+            for inlay in self.inlays.iter().filter(|inlay| inlay.wrap_ix == ix) {
+                let inlay_pos = line_pos + point(inlay.x + inlays_width, px(0.));
+                Self::paint_segment(
+                    line,
+                    line_pos + point(inlays_width, px(0.)),
+                    segment_left..inlay_pos.x,
+                    line_height,
+                    text_align,
+                    align_width,
+                    window,
+                    cx,
+                );
+                _ = inlay
+                    .label
+                    .paint(inlay_pos, line_height, text_align, None, window, cx);
+
+                inlays_width += inlay.label.width;
+                segment_left = inlay_pos.x + inlay.label.width;
+            }
+            Self::paint_segment(
+                line,
+                line_pos + point(inlays_width, px(0.)),
+                segment_left..mask.right(),
                 line_height,
                 text_align,
                 align_width,
@@ -680,7 +866,50 @@ impl LineLayout {
 
                 _ = invisible.paint(origin, line_height, text_align, align_width, window, cx);
             }
+
+            if let Some(((lf, crlf), (line_index, x_position, is_crlf))) =
+                indicators.line_endings.as_ref().zip(self.line_ending)
+            {
+                let invisible = if is_crlf { crlf } else { lf };
+                let origin = point(pos.x + x_position, pos.y + line_index as f32 * line_height);
+                _ = invisible.paint(origin, line_height, text_align, align_width, window, cx);
+            }
+        }
+    }
+}
+
+/// Returns the byte offset of the trailing whitespaces (spaces and tabs) of the `line`,
+/// the `\r` of the line ending is not included in the whitespaces.
+fn trailing_whitespace_start(line: &str) -> usize {
+    line.trim_end_matches('\r')
+        .trim_end_matches([' ', '\t'])
+        .len()
+}
+
+impl LineLayout {
+    /// Paint the part of the `line` in the `x_range` (window coordinates).
+    fn paint_segment(
+        line: &ShapedLine,
+        pos: Point<Pixels>,
+        x_range: Range<Pixels>,
+        line_height: Pixels,
+        text_align: TextAlign,
+        align_width: Option<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if x_range.start >= x_range.end {
+            return;
         }
+
+        let mask = window.content_mask().bounds;
+        let bounds = Bounds::from_corners(
+            point(x_range.start, mask.top()),
+            point(x_range.end, mask.bottom()),
+        );
+        window.with_content_mask(Some(ContentMask { bounds }), |window| {
+            _ = line.paint(pos, line_height, text_align, align_width, window, cx);
+        });
     }
 }
 
@@ -691,6 +920,15 @@ mod tests {
 
     use gpui::{Boundary, FontFeatures, FontStyle, FontWeight, px};
 
+    #[test]
+    fn test_unwrapped_lines() {
+        let lines = TextWrapper::unwrapped_lines(&Rope::from("Hello\r\n\n世界\n"));
+        let lens = lines.iter().map(|line| line.len()).collect::<Vec<_>>();
+        assert_eq!(lens, vec![6, 0, 6, 0]);
+        assert!(lines.iter().all(|line| line.lines_len() == 1));
+        assert_eq!(lines.summary().wrap_rows, 4);
+    }
+
     #[test]
     fn test_update() {
         let font = gpui::Font {
@@ -1168,4 +1406,13 @@ mod tests {
             15
         );
     }
+
+    #[test]
+    fn test_trailing_whitespace_start() {
+        assert_eq!(trailing_whitespace_start("let a = 1;"), 10);
+        assert_eq!(trailing_whitespace_start("let a = 1; \t "), 10);
+        assert_eq!(trailing_whitespace_start("  a  \r"), 3);
+        assert_eq!(trailing_whitespace_start("\t  "), 0);
+        assert_eq!(trailing_whitespace_start(""), 0);
+    }
 }
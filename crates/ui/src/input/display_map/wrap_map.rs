@@ -8,6 +8,7 @@ use std::ops::Range;
 
 use gpui::{App, Font, Pixels};
 use ropey::Rope;
+use sum_tree::SumTree;
 
 use super::fold_map::FoldMap;
 use super::text_wrapper::{LineItem, TextWrapper, WrapDisplayPoint};
@@ -131,6 +132,16 @@ impl WrapMap {
         self.wrapper.prepare_if_need(text, cx);
     }
 
+    /// Initialize with text and the lines prepared without soft wrap.
+    pub(crate) fn set_text_with_lines(
+        &mut self,
+        text: &Rope,
+        lines: SumTree<LineItem>,
+        cx: &mut App,
+    ) {
+        self.wrapper.set_text_with_lines(text, lines, cx);
+    }
+
     /// Get access to the underlying wrapper (for rendering/hit-testing)
     pub(crate) fn wrapper(&self) -> &TextWrapper {
         &self.wrapper
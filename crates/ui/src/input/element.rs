@@ -21,7 +21,10 @@ use crate::{
     scroll::Scrollbar,
 };
 
-use super::{InputState, LastLayout, WhitespaceIndicators, mode::InputMode};
+use super::{
+    InputState, LastLayout, WhitespaceIndicators, WhitespaceVisibility, mode::InputMode,
+    sticky_scroll::StickyHeadersLayout,
+};
 
 const BOTTOM_MARGIN_ROWS: usize = 3;
 pub(super) const RIGHT_MARGIN: Pixels = px(10.);
@@ -217,6 +220,44 @@ fn masked_display_offset(text: &Rope, original_offset: usize) -> usize {
     text.offset_to_char_index(original_offset) * MASK_CHAR.len_utf8()
 }
 
+/// The caret height for the input size.
+fn cursor_height(size: crate::Size, line_height: Pixels) -> Pixels {
+    match size {
+        crate::Size::Large => 1.,
+        crate::Size::Small => 0.75,
+        _ => 0.85,
+    }
+    *line_height
+}
+
+/// Resolve a cursor or selection endpoint at `offset` on buffer line `row` to a
+/// content-space position.
+fn caret_position(
+    state: &InputState,
+    last_layout: &LastLayout,
+    row: usize,
+    offset: usize,
+    affinity: bool,
+) -> Point<Pixels> {
+    // y of the top of buffer line `row` in content space.
+    let top = last_layout.line_height * state.display_map.buffer_line_to_display_row(row);
+    let line_origin = point(px(0.), top);
+
+    if let Some(vi) = last_layout
+        .visible_buffer_lines
+        .iter()
+        .position(|&bl| bl == row)
+    {
+        let line = &last_layout.lines[vi];
+        let line_start = last_layout.visible_line_byte_offsets[vi];
+        let local = offset.saturating_sub(line_start);
+        if let Some(pos) = line.position_for_index(local, last_layout, affinity) {
+            return line_origin + pos;
+        }
+    }
+    line_origin
+}
+
 /// Minimum pixel padding the cursor is kept clear of the viewport's
 /// top/bottom edges before auto-scroll engages. Backs
 /// [`InputState::cursor_surrounding_lines`].
@@ -322,6 +363,7 @@ impl TextElement {
                 state.update(cx, |state, _| {
                     state.auto_scroll.stop();
                     state.selecting = false;
+                    state.column_selection = None;
                 });
             }
         });
@@ -346,7 +388,6 @@ impl TextElement {
 
         let line_height = last_layout.line_height;
         let visible_range = &last_layout.visible_range;
-        let lines = &last_layout.lines;
         let line_number_width = last_layout.line_number_width;
 
         let mut selected_range = state.selected_range;
@@ -379,21 +420,8 @@ impl TextElement {
         );
 
         // Resolve a cursor or selection endpoint to a content-space position.
-        let visible_buffer_lines = &last_layout.visible_buffer_lines;
         let caret_for = |row: usize, offset: usize, affinity: bool| -> Point<Pixels> {
-            // y of the top of buffer line `row` in content space.
-            let top = line_height * state.display_map.buffer_line_to_display_row(row);
-            let line_origin = point(px(0.), top);
-
-            if let Some(vi) = visible_buffer_lines.iter().position(|&bl| bl == row) {
-                let line = &lines[vi];
-                let line_start = last_layout.visible_line_byte_offsets[vi];
-                let local = offset.saturating_sub(line_start);
-                if let Some(pos) = line.position_for_index(local, last_layout, affinity) {
-                    return line_origin + pos;
-                }
-            }
-            line_origin
+            caret_position(state, last_layout, row, offset, affinity)
         };
 
         let current_row = Some(cursor_row);
@@ -468,11 +496,7 @@ impl TextElement {
             }
 
             // cursor bounds
-            let cursor_height = match state.size {
-                crate::Size::Large => 1.,
-                crate::Size::Small => 0.75,
-                _ => 0.85,
-            } * line_height;
+            let cursor_height = cursor_height(state.size, line_height);
 
             // Match the caret to the deferred scroll target (applied below) that
             // the text paints at; otherwise the caret follows the cursor-scroll
@@ -490,12 +514,29 @@ impl TextElement {
             } else {
                 cursor_x
             };
+
+            // The Vim Normal mode has a block cursor covers the char under it.
+            let cursor_width = if state.is_vim_block_cursor() && !state.masked {
+                match state.text.char_at(cursor) {
+                    Some(c) if c != '\n' && c != '\r' => {
+                        let next_pos = caret_for(cursor_row, cursor + c.len_utf8(), false);
+                        if next_pos.y == cursor_pos.y && next_pos.x > cursor_pos.x {
+                            next_pos.x - cursor_pos.x
+                        } else {
+                            cursor_height / 2.
+                        }
+                    }
+                    _ => cursor_height / 2.,
+                }
+            } else {
+                CURSOR_WIDTH
+            };
             Some(Bounds::new(
                 point(
                     cursor_x,
                     bounds.top() + cursor_pos.y + ((line_height - cursor_height) / 2.),
                 ),
-                size(CURSOR_WIDTH, cursor_height),
+                size(cursor_width, cursor_height),
             ))
         };
 
@@ -688,6 +729,39 @@ impl TextElement {
         paths
     }
 
+    fn layout_occurrences(
+        &self,
+        last_layout: &LastLayout,
+        bounds: &Bounds<Pixels>,
+        cx: &mut App,
+    ) -> Vec<(Path<Pixels>, bool)> {
+        let state = self.state.read(cx);
+        state
+            .occurrences_for_range(&last_layout.visible_range_offset)
+            .into_iter()
+            .filter_map(|(range, is_write)| {
+                Self::layout_match_range(range, last_layout, bounds).map(|path| (path, is_write))
+            })
+            .collect()
+    }
+
+    fn layout_matching_brackets(
+        &self,
+        last_layout: &LastLayout,
+        bounds: &Bounds<Pixels>,
+        cx: &mut App,
+    ) -> Vec<Path<Pixels>> {
+        let state = self.state.read(cx);
+        let Some(ranges) = state.matching_bracket_ranges() else {
+            return vec![];
+        };
+
+        ranges
+            .into_iter()
+            .filter_map(|range| Self::layout_match_range(range, last_layout, bounds))
+            .collect()
+    }
+
     fn layout_hover_highlight(
         &self,
         last_layout: &LastLayout,
@@ -761,6 +835,51 @@ impl TextElement {
         Self::layout_match_range(range, &last_layout, bounds)
     }
 
+    /// Layout the carets and selections of the extra cursors (multi-cursor editing).
+    fn layout_extra_cursors(
+        &self,
+        last_layout: &LastLayout,
+        bounds: &Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (Vec<Bounds<Pixels>>, Vec<Path<Pixels>>) {
+        let state = self.state.read(cx);
+        if state.extra_cursors.is_empty() || !state.focus_handle.is_focused(window) {
+            return (vec![], vec![]);
+        }
+
+        let line_height = last_layout.line_height;
+        let cursor_height = cursor_height(state.size, line_height);
+        let visible_range_offset = &last_layout.visible_range_offset;
+
+        let mut cursor_bounds = vec![];
+        let mut selection_paths = vec![];
+        for cursor in state.extra_cursors.iter() {
+            let range = cursor.range.start.max(visible_range_offset.start)
+                ..cursor.range.end.min(visible_range_offset.end);
+            if let Some(path) = Self::layout_match_range(range, last_layout, bounds) {
+                selection_paths.push(path);
+            }
+
+            let head = cursor.head();
+            if head < visible_range_offset.start || head > visible_range_offset.end {
+                continue;
+            }
+
+            let row = state.text.offset_to_point(head).row;
+            let pos = caret_position(state, last_layout, row, head, false);
+            cursor_bounds.push(Bounds::new(
+                point(
+                    bounds.left() + last_layout.line_number_width + pos.x,
+                    bounds.top() + pos.y + ((line_height - cursor_height) / 2.),
+                ),
+                size(CURSOR_WIDTH, cursor_height),
+            ));
+        }
+
+        (cursor_bounds, selection_paths)
+    }
+
     /// Calculate the visible range of lines in the viewport.
     ///
     /// Returns
@@ -880,7 +999,7 @@ impl TextElement {
         (line_number_width, line_number_len)
     }
 
-    /// Layout shaped lines for whitespace indicators (space and tab).
+    /// Layout shaped lines for whitespace indicators (space, tab and line ending).
     ///
     /// Returns `WhitespaceIndicators` with shaped lines for space and tab characters.
     fn layout_whitespace_indicators(
@@ -890,7 +1009,9 @@ impl TextElement {
         window: &mut Window,
         cx: &App,
     ) -> Option<WhitespaceIndicators> {
-        if !state.show_whitespaces {
+        let visibility = state.whitespace_visibility();
+        let show_line_endings = state.show_line_endings && state.mode.is_multi_line();
+        if visibility == WhitespaceVisibility::None && !show_line_endings {
             return None;
         }
 
@@ -901,40 +1022,34 @@ impl TextElement {
             .editor_invisible
             .unwrap_or(cx.theme().muted_foreground);
 
-        let space_font_size = text_size.half();
-        let tab_font_size = text_size;
+        let mut shape = |text: &'static str, font_size: Pixels| {
+            let text = SharedString::new_static(text);
+            window.text_system().shape_line(
+                text.clone(),
+                font_size,
+                &[TextRun {
+                    len: text.len(),
+                    font: style.font(),
+                    color: invisible_color,
+                    background_color: None,
+                    underline: None,
+                    strikethrough: None,
+                }],
+                None,
+            )
+        };
 
-        let space_text = SharedString::new_static("•");
-        let space = window.text_system().shape_line(
-            space_text.clone(),
-            space_font_size,
-            &[TextRun {
-                len: space_text.len(),
-                font: style.font(),
-                color: invisible_color,
-                background_color: None,
-                underline: None,
-                strikethrough: None,
-            }],
-            None,
-        );
+        let space = shape("•", text_size.half());
+        let tab = shape("→", text_size);
+        let line_endings =
+            show_line_endings.then(|| (shape("¬", text_size), shape("¤¬", text_size)));
 
-        let tab_text = SharedString::new_static("→");
-        let tab = window.text_system().shape_line(
-            tab_text.clone(),
-            tab_font_size,
-            &[TextRun {
-                len: tab_text.len(),
-                font: style.font(),
-                color: invisible_color,
-                background_color: None,
-                underline: None,
-                strikethrough: None,
-            }],
-            None,
-        );
-
-        Some(WhitespaceIndicators { space, tab })
+        Some(WhitespaceIndicators {
+            space,
+            tab,
+            visibility,
+            line_endings,
+        })
     }
 
     /// Compute inline completion ghost lines for rendering.
@@ -1125,8 +1240,7 @@ impl TextElement {
                         cx.stop_propagation();
 
                         state.update(cx, |state, cx| {
-                            state.display_map.toggle_fold(buffer_line);
-                            cx.notify();
+                            state.toggle_fold(buffer_line, cx);
                         });
                     }
                 })
@@ -1182,6 +1296,7 @@ impl TextElement {
         runs: &[TextRun],
         bg_segments: &[(Range<usize>, Hsla)],
         whitespace_indicators: Option<WhitespaceIndicators>,
+        inlays: &[(usize, ShapedLine)],
         window: &mut Window,
     ) -> Vec<LineLayout> {
         let is_single_line = state.mode.is_single_line();
@@ -1196,7 +1311,7 @@ impl TextElement {
 
             let line_layout = LineLayout::new()
                 .lines(smallvec::smallvec![shaped_line])
-                .with_whitespaces(whitespace_indicators);
+                .with_whitespaces(whitespace_indicators, false);
             return vec![line_layout];
         }
 
@@ -1218,7 +1333,7 @@ impl TextElement {
             // Keep placeholder lines in a single layout to stay parallel with visible_* metadata.
             let line_layout = LineLayout::new()
                 .lines(placeholder_lines)
-                .with_whitespaces(whitespace_indicators);
+                .with_whitespaces(whitespace_indicators, false);
             return vec![line_layout];
         }
 
@@ -1227,6 +1342,7 @@ impl TextElement {
         // This is separate from the visible_text offset because runs from highlight_lines
         // only cover visible (non-folded) lines.
         let mut run_offset = 0;
+        let lines_len = state.text.lines_len();
 
         for (vi, &buffer_line) in last_layout.visible_buffer_lines.iter().enumerate() {
             let line_text: String = display_text.slice_line(buffer_line).into();
@@ -1259,9 +1375,21 @@ impl TextElement {
                 wrapped_lines.push(shaped_line);
             }
 
+            let line_start = last_layout.visible_line_byte_offsets[vi];
+            let line_inlays = inlays
+                .iter()
+                .filter(|(offset, _)| {
+                    *offset >= line_start && *offset <= line_start + line_text.len()
+                })
+                .map(|(offset, label)| (offset - line_start, label.clone()))
+                .collect();
+
+            // The last line has no line ending.
+            let has_line_ending = buffer_line + 1 < lines_len;
             let line_layout = LineLayout::new()
                 .lines(wrapped_lines)
-                .with_whitespaces(whitespace_indicators.clone());
+                .with_inlays(line_inlays)
+                .with_whitespaces(whitespace_indicators.clone(), has_line_ending);
             lines.push(line_layout);
 
             // +1 for the `\n`
@@ -1271,6 +1399,43 @@ impl TextElement {
         lines
     }
 
+    /// Shape the labels of the LSP inlay hints in the visible range.
+    ///
+    /// Returns the byte offsets and the shaped labels.
+    fn layout_inlay_hints(
+        state: &InputState,
+        last_layout: &LastLayout,
+        font_size: Pixels,
+        text_style: &TextStyle,
+        window: &mut Window,
+        cx: &App,
+    ) -> Vec<(usize, ShapedLine)> {
+        if !state.mode.is_code_editor() || state.masked || state.text.len() == 0 {
+            return vec![];
+        }
+
+        let color = cx.theme().muted_foreground.opacity(0.7);
+        state
+            .lsp
+            .inlay_hints_for_range(&state.text, &last_layout.visible_range_offset)
+            .into_iter()
+            .map(|(offset, label)| {
+                let run = TextRun {
+                    len: label.len(),
+                    font: text_style.font(),
+                    color,
+                    background_color: None,
+                    underline: None,
+                    strikethrough: None,
+                };
+                let label = window
+                    .text_system()
+                    .shape_line(label, font_size, &[run], None);
+                (offset, label)
+            })
+            .collect()
+    }
+
     /// First usize is the offset of skipped.
     fn highlight_lines(
         &mut self,
@@ -1388,14 +1553,29 @@ pub(super) struct PrepaintState {
     /// row index (zero based), no wrap, same line as the cursor.
     current_row: Option<usize>,
     selection_path: Option<Path<Pixels>>,
+    /// Carets of the extra cursors, already scrolled.
+    extra_cursor_bounds: Vec<Bounds<Pixels>>,
+    extra_selection_paths: Vec<Path<Pixels>>,
     hover_highlight_path: Option<Path<Pixels>>,
+    /// The occurrences of the word under the cursor, and true if it's a write access.
+    occurrence_paths: Vec<(Path<Pixels>, bool)>,
+    /// The bracket at the cursor and its matching bracket.
+    bracket_paths: Vec<Path<Pixels>>,
     search_match_paths: Vec<(Path<Pixels>, bool)>,
     document_color_paths: Vec<(Path<Pixels>, Hsla)>,
     hover_definition_hitbox: Option<Hitbox>,
     indent_guides_path: Option<Path<Pixels>>,
+    active_indent_guide_path: Option<Path<Pixels>>,
+    rulers_path: Option<Path<Pixels>>,
     bounds: Bounds<Pixels>,
     /// Fold icon layout data
     fold_icon_layout: FoldIconLayout,
+    /// The gutter marks of the visible lines.
+    gutter_mark_elements: Vec<AnyElement>,
+    /// The lightbulb of the code actions at the cursor.
+    code_action_lightbulb: Option<AnyElement>,
+    /// The headers of the enclosing scopes pinned at the top.
+    sticky_headers: Option<StickyHeadersLayout>,
     // Inline completion rendering data
     /// Shaped ghost lines to paint after cursor row (completion lines 2+)
     ghost_lines: Vec<ShapedLine>,
@@ -1696,6 +1876,9 @@ impl Element for TextElement {
         let whitespace_indicators =
             Self::layout_whitespace_indicators(&state, text_size, &text_style, window, cx);
 
+        let inlays =
+            Self::layout_inlay_hints(&state, &last_layout, text_size, &text_style, window, cx);
+
         let lines = Self::layout_lines(
             &state,
             &display_text,
@@ -1704,6 +1887,7 @@ impl Element for TextElement {
             &runs,
             &document_colors,
             whitespace_indicators,
+            &inlays,
             window,
         );
 
@@ -1813,17 +1997,24 @@ impl Element for TextElement {
 
         let search_match_paths = self.layout_search_matches(&last_layout, &mut bounds, cx);
         let selection_path = self.layout_selections(&last_layout, &mut bounds, window, cx);
+        let (extra_cursor_bounds, extra_selection_paths) =
+            self.layout_extra_cursors(&last_layout, &bounds, window, cx);
         let hover_highlight_path = self.layout_hover_highlight(&last_layout, &mut bounds, cx);
+        let occurrence_paths = self.layout_occurrences(&last_layout, &bounds, cx);
+        let bracket_paths = self.layout_matching_brackets(&last_layout, &bounds, cx);
         let document_color_paths =
             self.layout_document_colors(&document_colors, &last_layout, &bounds, cx);
 
         let state = self.state.read(cx);
         let line_numbers = if state.mode.line_number() {
             let mut line_numbers = Vec::with_capacity(last_layout.visible_buffer_lines.len());
+            let highlight_style = &cx.theme().highlight_theme.style;
             let other_line_runs = vec![TextRun {
                 len: line_number_len,
                 font: style.font(),
-                color: cx.theme().muted_foreground,
+                color: highlight_style
+                    .editor_line_number
+                    .unwrap_or(cx.theme().muted_foreground),
                 background_color: None,
                 underline: None,
                 strikethrough: None,
@@ -1831,7 +2022,9 @@ impl Element for TextElement {
             let current_line_runs = vec![TextRun {
                 len: line_number_len,
                 font: style.font(),
-                color: cx.theme().foreground,
+                color: highlight_style
+                    .editor_active_line_number
+                    .unwrap_or(cx.theme().foreground),
                 background_color: None,
                 underline: None,
                 strikethrough: None,
@@ -1843,8 +2036,12 @@ impl Element for TextElement {
                 .iter()
                 .zip(last_layout.visible_buffer_lines.iter())
             {
-                let line_no: SharedString =
-                    format!("{:>width$}", buffer_line + 1, width = line_number_len).into();
+                let line_no: SharedString = format!(
+                    "{:>width$}",
+                    state.line_number_mode.number(buffer_line, current_row),
+                    width = line_number_len
+                )
+                .into();
 
                 let runs = if current_row == Some(buffer_line) {
                     &current_line_runs
@@ -1871,6 +2068,16 @@ impl Element for TextElement {
         let hover_definition_hitbox = self.layout_hover_definition_hitbox(state, window, cx);
         let indent_guides_path =
             self.layout_indent_guides(state, &bounds, &last_layout, &text_style, window);
+        let active_indent_guide_path =
+            self.layout_active_indent_guide(state, &bounds, &last_layout, &text_style, window);
+        let rulers_path = self.layout_rulers(
+            state,
+            &bounds,
+            &input_bounds,
+            &last_layout,
+            &text_style,
+            window,
+        );
         state
             .editor_scrollbar_snapshot
             .set(Some(EditorScrollbarSnapshot::new(
@@ -1882,6 +2089,19 @@ impl Element for TextElement {
             )));
         let fold_icon_layout =
             self.layout_fold_icons(original_x, &bounds, &last_layout, window, cx);
+        let gutter_mark_elements =
+            self.layout_gutter_marks(original_x, &bounds, &last_layout, window, cx);
+        let code_action_lightbulb =
+            self.layout_code_action_lightbulb(original_x, &bounds, &last_layout, window, cx);
+        let sticky_headers = self.layout_sticky_headers(
+            &bounds,
+            &input_bounds,
+            &last_layout,
+            line_number_len,
+            &text_style,
+            window,
+            cx,
+        );
 
         PrepaintState {
             bounds,
@@ -1892,12 +2112,21 @@ impl Element for TextElement {
             cursor_scroll_offset,
             current_row,
             selection_path,
+            extra_cursor_bounds,
+            extra_selection_paths,
             search_match_paths,
             hover_highlight_path,
+            occurrence_paths,
+            bracket_paths,
             hover_definition_hitbox,
             document_color_paths,
             indent_guides_path,
+            active_indent_guide_path,
+            rulers_path,
             fold_icon_layout,
+            gutter_mark_elements,
+            code_action_lightbulb,
+            sticky_headers,
             ghost_first_line,
             ghost_lines,
             ghost_lines_height,
@@ -1914,13 +2143,24 @@ impl Element for TextElement {
         window: &mut Window,
         cx: &mut App,
     ) {
-        let (focus_handle, show_cursor, disabled, selected_range) = {
+        let (
+            focus_handle,
+            show_cursor,
+            disabled,
+            selected_range,
+            block_cursor,
+            highlight_current_line,
+        ) = {
             let state = self.state.read(cx);
             (
                 state.focus_handle.clone(),
                 state.show_cursor(window, cx),
                 state.disabled,
                 state.selected_range,
+                state.is_vim_block_cursor(),
+                state.mode.is_code_editor()
+                    && state.mode.is_multi_line()
+                    && state.highlight_current_line,
             )
         };
         let focused = focus_handle.is_focused(window);
@@ -1967,6 +2207,7 @@ impl Element for TextElement {
             .highlight_theme
             .style
             .editor_active_line
+            .filter(|_| highlight_current_line)
             .map(|color| if disabled { color.opacity(0.5) } else { color });
         let editor_background = if disabled {
             cx.theme().editor_background().opacity(0.5)
@@ -1975,35 +2216,66 @@ impl Element for TextElement {
         };
 
         // Paint active line
-        let mut offset_y = px(0.);
-        if let Some(line_numbers) = prepaint.line_numbers.as_ref() {
-            offset_y += invisible_top_padding;
-
-            // Each item is the normal lines.
-            for (lines, &buffer_line) in line_numbers
+        if let Some(bg_color) = active_line_color {
+            let mut offset_y = invisible_top_padding;
+            for (line, &buffer_line) in prepaint
+                .last_layout
+                .lines
                 .iter()
                 .zip(prepaint.last_layout.visible_buffer_lines.iter())
             {
-                let is_active = prepaint.current_row == Some(buffer_line);
-                let p = point(input_bounds.origin.x, origin.y + offset_y);
-                let height = line_height * lines.len() as f32;
-                // Paint the current line background
-                if is_active {
-                    if let Some(bg_color) = active_line_color {
-                        window.paint_quad(fill(
-                            Bounds::new(p, size(bounds.size.width, height)),
-                            bg_color,
-                        ));
-                    }
+                let height = line_height * line.wrapped_lines.len().max(1) as f32;
+                if prepaint.current_row == Some(buffer_line) {
+                    let p = point(input_bounds.origin.x, origin.y + offset_y);
+                    window.paint_quad(fill(
+                        Bounds::new(p, size(bounds.size.width, height)),
+                        bg_color,
+                    ));
+                    break;
                 }
                 offset_y += height;
             }
         }
 
+        // Paint rulers
+        if let Some(path) = prepaint.rulers_path.take() {
+            window.paint_path(path, cx.theme().border);
+        }
+
         // Paint indent guides
         if let Some(path) = prepaint.indent_guides_path.take() {
             window.paint_path(path, cx.theme().border.opacity(0.85));
         }
+        if let Some(path) = prepaint.active_indent_guide_path.take() {
+            window.paint_path(path, cx.theme().muted_foreground.opacity(0.5));
+        }
+
+        // Paint occurrences of the word under the cursor
+        if !prepaint.occurrence_paths.is_empty() {
+            let style = &cx.theme().highlight_theme.style;
+            let read_bg = style
+                .editor_document_highlight_read_background
+                .unwrap_or(cx.theme().selection.opacity(0.35));
+            let write_bg = style
+                .editor_document_highlight_write_background
+                .unwrap_or(cx.theme().selection.opacity(0.6));
+            for (path, is_write) in prepaint.occurrence_paths.drain(..) {
+                window.paint_path(path, if is_write { write_bg } else { read_bg });
+            }
+        }
+
+        // Paint matching brackets
+        if !prepaint.bracket_paths.is_empty() {
+            let bracket_bg = cx
+                .theme()
+                .highlight_theme
+                .style
+                .editor_document_highlight_bracket_background
+                .unwrap_or(cx.theme().selection.opacity(0.5));
+            for path in prepaint.bracket_paths.drain(..) {
+                window.paint_path(path, bracket_bg);
+            }
+        }
 
         // Paint selections
         if window.is_window_active() {
@@ -2019,6 +2291,9 @@ impl Element for TextElement {
             if let Some(path) = prepaint.selection_path.take() {
                 window.paint_path(path, cx.theme().selection);
             }
+            for path in prepaint.extra_selection_paths.drain(..) {
+                window.paint_path(path, cx.theme().selection);
+            }
 
             // Paint hover highlight
             if let Some(path) = prepaint.hover_highlight_path.take() {
@@ -2113,7 +2388,15 @@ impl Element for TextElement {
         // Paint blinking cursor
         if focused && show_cursor {
             if let Some(cursor_bounds) = prepaint.cursor_bounds_with_scroll() {
-                window.paint_quad(fill(cursor_bounds, cx.theme().caret));
+                let caret = if block_cursor {
+                    cx.theme().caret.opacity(0.5)
+                } else {
+                    cx.theme().caret
+                };
+                window.paint_quad(fill(cursor_bounds, caret));
+            }
+            for cursor_bounds in prepaint.extra_cursor_bounds.iter() {
+                window.paint_quad(fill(*cursor_bounds, cx.theme().caret));
             }
         }
 
@@ -2181,7 +2464,26 @@ impl Element for TextElement {
             cx,
         );
 
+        for element in prepaint.gutter_mark_elements.iter_mut() {
+            element.paint(window, cx);
+        }
+        if let Some(element) = prepaint.code_action_lightbulb.as_mut() {
+            element.paint(window, cx);
+        }
+
+        if let Some(sticky_headers) = prepaint.sticky_headers.as_ref() {
+            self.paint_sticky_headers(sticky_headers, window, cx);
+        }
+
         self.state.update(cx, |state, cx| {
+            if state.last_cursor != Some(state.cursor())
+                || state.last_selected_range != Some(selected_range)
+            {
+                state.update_document_highlights(window, cx);
+                state.update_code_action_hint(window, cx);
+            }
+            state.update_inlay_hints(&prepaint.last_layout.visible_range_offset, window, cx);
+
             state.last_layout = Some(prepaint.last_layout.clone());
             state.last_bounds = Some(bounds);
             state.last_cursor = Some(state.cursor());
@@ -0,0 +1,136 @@
+use gpui::{Context, Window};
+
+use crate::input::{Fold, FoldAll, FoldRange, InputState, RopeExt as _, Unfold, UnfoldAll};
+
+impl InputState {
+    /// Returns the foldable ranges of the code editor, sorted by start line.
+    ///
+    /// The fold candidates are extracted from the syntax tree, so this is empty
+    /// if the language has no tree-sitter grammar or folding is disabled.
+    pub fn fold_candidates(&self) -> &[FoldRange] {
+        self.display_map.fold_candidates()
+    }
+
+    /// Returns the currently folded ranges, sorted by start line.
+    pub fn folded_ranges(&self) -> &[FoldRange] {
+        self.display_map.folded_ranges()
+    }
+
+    /// Returns true if the fold starting at the given buffer line is folded.
+    pub fn is_folded(&self, start_line: usize) -> bool {
+        self.display_map.is_folded_at(start_line)
+    }
+
+    /// Fold or unfold the fold candidate starting at the given buffer line.
+    ///
+    /// Does nothing if there is no fold candidate starting at the line.
+    pub fn set_folded(&mut self, start_line: usize, folded: bool, cx: &mut Context<Self>) {
+        if !self.mode.is_folding() || !self.display_map.is_fold_candidate(start_line) {
+            return;
+        }
+
+        self.display_map.set_folded(start_line, folded);
+        self.ensure_cursor_visible(cx);
+        cx.notify();
+    }
+
+    /// Toggle the fold starting at the given buffer line.
+    pub fn toggle_fold(&mut self, start_line: usize, cx: &mut Context<Self>) {
+        let folded = self.is_folded(start_line);
+        self.set_folded(start_line, !folded, cx);
+    }
+
+    /// Fold all the fold candidates.
+    pub fn fold_all(&mut self, cx: &mut Context<Self>) {
+        if !self.mode.is_folding() {
+            return;
+        }
+
+        self.display_map.fold_all();
+        self.ensure_cursor_visible(cx);
+        cx.notify();
+    }
+
+    /// Unfold all the folded ranges.
+    pub fn unfold_all(&mut self, cx: &mut Context<Self>) {
+        self.display_map.clear_folds();
+        cx.notify();
+    }
+
+    /// Move the cursor out of the folded lines, to the end of the fold header line.
+    fn ensure_cursor_visible(&mut self, cx: &mut Context<Self>) {
+        let line = self.text.offset_to_point(self.cursor()).row;
+        if !self.display_map.is_buffer_line_hidden(line) {
+            return;
+        }
+
+        let offset = self.clamp_offset_to_visible_backward(self.cursor());
+        self.move_to(offset, None, cx);
+    }
+
+    /// Returns the innermost fold range that contains the cursor line and matches `f`.
+    fn fold_at_cursor(
+        &self,
+        ranges: &[FoldRange],
+        f: impl Fn(&FoldRange) -> bool,
+    ) -> Option<FoldRange> {
+        let line = self.text.offset_to_point(self.cursor()).row;
+        ranges
+            .iter()
+            .filter(|range| range.start_line <= line && line <= range.end_line && f(range))
+            .max_by_key(|range| range.start_line)
+            .copied()
+    }
+
+    pub(super) fn on_action_fold(&mut self, _: &Fold, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.mode.is_folding() {
+            cx.propagate();
+            return;
+        }
+
+        if let Some(range) = self.fold_at_cursor(self.fold_candidates(), |range| {
+            !self.is_folded(range.start_line)
+        }) {
+            self.set_folded(range.start_line, true, cx);
+        }
+    }
+
+    pub(super) fn on_action_unfold(&mut self, _: &Unfold, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.mode.is_folding() {
+            cx.propagate();
+            return;
+        }
+
+        if let Some(range) = self.fold_at_cursor(self.folded_ranges(), |_| true) {
+            self.set_folded(range.start_line, false, cx);
+        }
+    }
+
+    pub(super) fn on_action_fold_all(
+        &mut self,
+        _: &FoldAll,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode.is_folding() {
+            cx.propagate();
+            return;
+        }
+
+        self.fold_all(cx);
+    }
+
+    pub(super) fn on_action_unfold_all(
+        &mut self,
+        _: &UnfoldAll,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode.is_folding() {
+            cx.propagate();
+            return;
+        }
+
+        self.unfold_all(cx);
+    }
+}
@@ -0,0 +1,277 @@
+use std::{ops::Range, rc::Rc};
+
+use gpui::{
+    AnyElement, App, Bounds, Context, Half as _, Hsla, InteractiveElement as _, IntoElement,
+    MouseButton, ParentElement as _, Pixels, SharedString, StatefulInteractiveElement as _,
+    Styled as _, Window, div, point, prelude::FluentBuilder as _, px, size,
+};
+
+use crate::{
+    ActiveTheme as _,
+    input::{
+        InputState, LastLayout, RopeExt as _,
+        element::{LINE_NUMBER_RIGHT_MARGIN, TextElement},
+    },
+    tooltip::Tooltip,
+};
+
+/// The width of the gutter mark bar.
+const MARK_WIDTH: Pixels = px(3.);
+/// The width of the gutter mark hitbox, for clicking and hovering.
+const MARK_HITBOX_WIDTH: Pixels = px(6.);
+/// The height of the [`GutterMarkKind::Deleted`] mark.
+const DELETED_MARK_HEIGHT: Pixels = px(4.);
+
+/// The kind of a [`GutterMark`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GutterMarkKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+impl GutterMarkKind {
+    fn color(&self, cx: &App) -> Hsla {
+        match self {
+            Self::Added => cx.theme().success,
+            Self::Modified => cx.theme().info,
+            Self::Deleted => cx.theme().danger,
+        }
+    }
+}
+
+/// A line indicator in the gutter of the code editor, e.g. the git diff hunks.
+///
+/// See [`InputState::set_gutter_marks`].
+#[derive(Clone)]
+pub struct GutterMark {
+    pub kind: GutterMarkKind,
+    /// The buffer rows (0-based) of the mark, empty for [`GutterMarkKind::Deleted`].
+    pub rows: Range<usize>,
+    /// The text to show on hover, e.g. the original lines of the hunk.
+    pub preview: Option<SharedString>,
+    on_click: Option<Rc<dyn Fn(&GutterMark, &mut Window, &mut App)>>,
+}
+
+impl GutterMark {
+    fn new(kind: GutterMarkKind, rows: Range<usize>) -> Self {
+        Self {
+            kind,
+            rows,
+            preview: None,
+            on_click: None,
+        }
+    }
+
+    /// Create a mark for the added rows.
+    pub fn added(rows: Range<usize>) -> Self {
+        Self::new(GutterMarkKind::Added, rows)
+    }
+
+    /// Create a mark for the modified rows.
+    pub fn modified(rows: Range<usize>) -> Self {
+        Self::new(GutterMarkKind::Modified, rows)
+    }
+
+    /// Create a mark for the lines deleted before the `row`.
+    ///
+    /// Use the total number of lines as `row` for the lines deleted at the end.
+    pub fn deleted(row: usize) -> Self {
+        Self::new(GutterMarkKind::Deleted, row..row)
+    }
+
+    /// Set the text to show on hover, e.g. the original lines of the hunk.
+    pub fn preview(mut self, preview: impl Into<SharedString>) -> Self {
+        self.preview = Some(preview.into());
+        self
+    }
+
+    /// Set a handler to call when the mark is clicked, e.g. to revert the hunk.
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(&GutterMark, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Returns the bounds of the mark in the `row`, relative to the top-left of the row.
+    ///
+    /// The `last_row` is the last buffer row, for the deleted mark at the end.
+    fn bounds_in_row(
+        &self,
+        row: usize,
+        last_row: usize,
+        row_height: Pixels,
+    ) -> Option<Bounds<Pixels>> {
+        match self.kind {
+            GutterMarkKind::Deleted => {
+                let y = if self.rows.start == row {
+                    px(0.)
+                } else if row == last_row && self.rows.start > last_row {
+                    row_height
+                } else {
+                    return None;
+                };
+
+                Some(Bounds::new(
+                    point(px(0.), y - DELETED_MARK_HEIGHT.half()),
+                    size(MARK_HITBOX_WIDTH, DELETED_MARK_HEIGHT),
+                ))
+            }
+            _ => self
+                .rows
+                .contains(&row)
+                .then(|| Bounds::new(point(px(0.), px(0.)), size(MARK_HITBOX_WIDTH, row_height))),
+        }
+    }
+}
+
+impl InputState {
+    /// Set the gutter marks of the code editor, e.g. the added/modified/deleted lines of git diff.
+    ///
+    /// The marks are not moved by the edits, update them after the text changed,
+    /// e.g. on [`super::InputEvent::Change`].
+    pub fn set_gutter_marks(
+        &mut self,
+        marks: impl Into<Vec<GutterMark>>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        debug_assert!(self.mode.is_code_editor());
+        self.gutter_marks = Rc::new(marks.into());
+        cx.notify();
+    }
+
+    /// Returns the gutter marks, see [`Self::set_gutter_marks`].
+    pub fn gutter_marks(&self) -> &[GutterMark] {
+        &self.gutter_marks
+    }
+}
+
+impl TextElement {
+    /// Layout the gutter marks of the visible lines, at the right of the line numbers.
+    ///
+    /// One element is created for each mark in each visible row, so that wrapped
+    /// and folded lines are handled like the line numbers.
+    pub(super) fn layout_gutter_marks(
+        &self,
+        origin_x: Pixels,
+        bounds: &Bounds<Pixels>,
+        last_layout: &LastLayout,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        let state = self.state.read(cx);
+        if state.gutter_marks.is_empty() || !state.mode.is_code_editor() {
+            return vec![];
+        }
+
+        let marks = state.gutter_marks.clone();
+        let last_row = state.text.lines_len().saturating_sub(1);
+        let x = origin_x + last_layout.line_number_width - LINE_NUMBER_RIGHT_MARGIN;
+        let mut elements = vec![];
+        let mut offset_y = last_layout.visible_top;
+
+        for (line, &buffer_line) in last_layout
+            .lines
+            .iter()
+            .zip(last_layout.visible_buffer_lines.iter())
+        {
+            let row_height = line.wrapped_lines.len() * last_layout.line_height;
+            for mark in marks.iter() {
+                let Some(mark_bounds) = mark.bounds_in_row(buffer_line, last_row, row_height)
+                else {
+                    continue;
+                };
+
+                let mut element = Self::render_gutter_mark(mark, elements.len(), cx);
+                element.prepaint_as_root(
+                    point(x, bounds.origin.y + offset_y) + mark_bounds.origin,
+                    mark_bounds.size.into(),
+                    window,
+                    cx,
+                );
+                elements.push(element);
+            }
+
+            offset_y += row_height;
+        }
+
+        elements
+    }
+
+    fn render_gutter_mark(mark: &GutterMark, ix: usize, cx: &App) -> AnyElement {
+        let color = mark.kind.color(cx);
+        let is_deleted = mark.kind == GutterMarkKind::Deleted;
+
+        div()
+            .id(("gutter-mark", ix))
+            .size_full()
+            .child(
+                div()
+                    .h_full()
+                    .w(if is_deleted {
+                        MARK_HITBOX_WIDTH
+                    } else {
+                        MARK_WIDTH
+                    })
+                    .bg(color),
+            )
+            .when_some(mark.on_click.clone(), |this, on_click| {
+                let mark = mark.clone();
+                this.cursor_pointer()
+                    .on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                        cx.stop_propagation();
+                        on_click(&mark, window, cx);
+                    })
+            })
+            .when_some(mark.preview.clone(), |this, preview| {
+                this.tooltip(move |window, cx| {
+                    let preview = preview.clone();
+                    Tooltip::element(move |_, cx| {
+                        div()
+                            .font_family(cx.theme().mono_font_family.clone())
+                            .text_xs()
+                            .whitespace_nowrap()
+                            .child(preview.clone())
+                    })
+                    .build(window, cx)
+                })
+            })
+            .into_any_element()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px};
+
+    use super::GutterMark;
+
+    #[test]
+    fn test_gutter_mark_bounds() {
+        let mark = GutterMark::added(2..4);
+        assert!(mark.bounds_in_row(1, 10, px(20.)).is_none());
+        assert_eq!(
+            mark.bounds_in_row(3, 10, px(20.)).map(|b| b.size.height),
+            Some(px(20.))
+        );
+        assert!(mark.bounds_in_row(4, 10, px(20.)).is_none());
+
+        // Deleted before a row, on the top edge of the row.
+        let mark = GutterMark::deleted(5);
+        assert_eq!(
+            mark.bounds_in_row(5, 10, px(20.)).map(|b| b.origin),
+            Some(point(px(0.), px(-2.)))
+        );
+        assert!(mark.bounds_in_row(4, 10, px(20.)).is_none());
+
+        // Deleted at the end, on the bottom edge of the last row.
+        let mark = GutterMark::deleted(11);
+        assert_eq!(
+            mark.bounds_in_row(10, 10, px(20.)).map(|b| b.origin),
+            Some(point(px(0.), px(18.)))
+        );
+    }
+}
@@ -3,6 +3,7 @@ use gpui::{
     TextRun, TextStyle, Window, point, px,
 };
 use ropey::RopeSlice;
+use std::ops::Range;
 
 use crate::{
     RopeExt,
@@ -88,7 +89,12 @@ impl InputMode {
 
 impl TextElement {
     /// Measure the indent width in pixels for given column count.
-    fn measure_indent_width(&self, style: &TextStyle, column: usize, window: &Window) -> Pixels {
+    pub(super) fn measure_indent_width(
+        &self,
+        style: &TextStyle,
+        column: usize,
+        window: &Window,
+    ) -> Pixels {
         let font_size = style.font_size.to_pixels(window.rem_size());
         let layout = window.text_system().shape_line(
             SharedString::from(" ".repeat(column)),
@@ -167,9 +173,159 @@ impl TextElement {
         let path = builder.build().unwrap();
         Some(path)
     }
+
+    /// Layout the indent guide of the block containing the cursor, see
+    /// [`InputState::highlight_active_indent_guide`].
+    pub(super) fn layout_active_indent_guide(
+        &self,
+        state: &InputState,
+        bounds: &Bounds<Pixels>,
+        last_layout: &LastLayout,
+        text_style: &TextStyle,
+        window: &mut Window,
+    ) -> Option<Path<Pixels>> {
+        if !state.mode.has_indent_guides() || !state.highlight_active_indent_guide {
+            return None;
+        }
+
+        let rows = state.active_indent_rows()?;
+        let tab_size = state.mode.tab_size();
+        let indent_count = tab_size.indent_count(&state.text.slice_line(rows.start - 1));
+        let indent_width = self.measure_indent_width(text_style, tab_size.tab_size, window);
+        let x = last_layout.line_number_width
+            + indent_width * indent_count as f32 / tab_size.tab_size as f32;
+
+        let line_height = last_layout.line_height;
+        let mut builder = PathBuilder::stroke(px(1.));
+        let mut offset_y = last_layout.visible_top;
+        let mut is_empty = true;
+        for (buffer_line, line_layout) in last_layout
+            .visible_buffer_lines
+            .iter()
+            .zip(last_layout.lines.iter())
+        {
+            let height = line_layout.wrapped_lines.len() * line_height;
+            if rows.contains(buffer_line) {
+                builder.move_to(point(x, offset_y));
+                builder.line_to(point(x, offset_y + height));
+                is_empty = false;
+            }
+            offset_y += height;
+        }
+
+        if is_empty {
+            return None;
+        }
+
+        builder.translate(bounds.origin);
+        builder.build().ok()
+    }
+}
+
+/// Returns the text to insert for a new line, and the cursor offset in it.
+///
+/// The `indent` is the indent of the current line, and the new line is indented one more
+/// `tab`. If `close` is true, the closing token after the cursor is moved to another new
+/// line with the `indent`.
+fn new_line_with_indent(indent: &str, tab: &str, close: bool) -> (String, usize) {
+    let text = format!("\n{}{}", indent, tab);
+    let cursor = text.len();
+    if close {
+        (format!("{}\n{}", text, indent), cursor)
+    } else {
+        (text, cursor)
+    }
 }
 
 impl InputState {
+    /// Returns the indented blocks containing the `offset` by the indents query of the
+    /// language, sorted from the outermost to the innermost.
+    fn indent_ranges(&self, offset: usize) -> Vec<Range<usize>> {
+        if !self.mode.is_code_editor() {
+            return vec![];
+        }
+
+        let Some(highlighter) = self.mode.highlighter() else {
+            return vec![];
+        };
+        let highlighter = highlighter.borrow();
+        highlighter
+            .as_ref()
+            .map(|highlighter| highlighter.indent_ranges(offset))
+            .unwrap_or_default()
+    }
+
+    /// Returns the rows inside the innermost multi-line block containing the cursor,
+    /// excluding the rows of the opening and the closing tokens.
+    fn active_indent_rows(&self) -> Option<Range<usize>> {
+        let cursor = self.cursor();
+        self.indent_ranges(cursor)
+            .into_iter()
+            .rev()
+            .find_map(|range| {
+                let start_row = self.text.offset_to_point(range.start).row;
+                let end_row = self.text.offset_to_point(range.end).row;
+                (end_row > start_row + 1).then(|| start_row + 1..end_row)
+            })
+    }
+
+    /// Returns the text to insert for a new line at the cursor and the cursor offset in it,
+    /// by the indents query of the language.
+    ///
+    /// Returns None if the innermost block containing the cursor is not opened on the
+    /// current line, then the indent of the line is kept by [`Self::indent_of_next_line`].
+    pub(super) fn auto_indent_new_line(&self) -> Option<(String, usize)> {
+        let cursor = self.cursor();
+        let range = self.indent_ranges(cursor).pop()?;
+        let row = self.text.offset_to_point(cursor).row;
+        if self.text.offset_to_point(range.start).row != row {
+            return None;
+        }
+
+        let line = self.text.slice_line(row).to_string();
+        let indent = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+        // Move the closing token to a new line, if it is right after the cursor.
+        let close = range.end == cursor
+            && self
+                .text
+                .char_at(range.end)
+                .is_some_and(|c| !c.is_whitespace());
+
+        Some(new_line_with_indent(
+            indent,
+            &self.mode.tab_size().to_string(),
+            close,
+        ))
+    }
+
+    /// Set whether to highlight the indent guide of the block containing the cursor,
+    /// default is false.
+    ///
+    /// The block is found by the indents query of the language, see [`LanguageConfig::indents`].
+    ///
+    /// Only for [`InputMode::CodeEditor`] mode.
+    ///
+    /// [`LanguageConfig::indents`]: crate::highlighter::LanguageConfig::indents
+    pub fn highlight_active_indent_guide(mut self, highlight: bool) -> Self {
+        debug_assert!(self.mode.is_code_editor());
+        self.highlight_active_indent_guide = highlight;
+        self
+    }
+
+    /// Set whether to highlight the indent guide of the block containing the cursor.
+    ///
+    /// Only for [`InputMode::CodeEditor`] mode.
+    pub fn set_highlight_active_indent_guide(
+        &mut self,
+        highlight: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        debug_assert!(self.mode.is_code_editor());
+        self.highlight_active_indent_guide = highlight;
+        cx.notify();
+    }
+
     /// Set whether to show indent guides in code editor mode, default is true.
     ///
     /// Only for [`InputMode::CodeEditor`] mode.
@@ -226,11 +382,16 @@ impl InputState {
         if self.accept_inline_completion(window, cx) {
             return;
         }
-        self.indent(false, window, cx);
+        if self.next_snippet_tabstop(cx) {
+            return;
+        }
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            this.indent(false, window, cx)
+        });
     }
 
     pub(super) fn indent_block(&mut self, _: &Indent, window: &mut Window, cx: &mut Context<Self>) {
-        self.indent(true, window, cx);
+        self.for_each_cursor(window, cx, |this, window, cx| this.indent(true, window, cx));
     }
 
     pub(super) fn outdent_inline(
@@ -239,7 +400,12 @@ impl InputState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.outdent(false, window, cx);
+        if self.prev_snippet_tabstop(cx) {
+            return;
+        }
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            this.outdent(false, window, cx)
+        });
     }
 
     pub(super) fn outdent_block(
@@ -248,7 +414,9 @@ impl InputState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.outdent(true, window, cx);
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            this.outdent(true, window, cx)
+        });
     }
 
     pub(super) fn indent(&mut self, block: bool, window: &mut Window, cx: &mut Context<Self>) {
@@ -388,7 +556,7 @@ impl InputState {
 mod tests {
     use ropey::RopeSlice;
 
-    use super::TabSize;
+    use super::{TabSize, new_line_with_indent};
 
     #[test]
     fn test_tab_size() {
@@ -429,4 +597,21 @@ mod tests {
         assert_eq!(tab.indent_count(&RopeSlice::from(" \t abc  ")), 6);
         assert_eq!(tab.indent_count(&RopeSlice::from("abc")), 0);
     }
+
+    #[test]
+    fn test_new_line_with_indent() {
+        assert_eq!(new_line_with_indent("", "  ", false), ("\n  ".into(), 3));
+        assert_eq!(
+            new_line_with_indent("    ", "    ", false),
+            ("\n        ".into(), 9)
+        );
+        assert_eq!(
+            new_line_with_indent("  ", "  ", true),
+            ("\n    \n  ".into(), 5)
+        );
+        assert_eq!(
+            new_line_with_indent("\t", "\t", true),
+            ("\n\t\t\n\t".into(), 3)
+        );
+    }
 }
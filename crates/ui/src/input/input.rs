@@ -3,8 +3,9 @@ use std::rc::Rc;
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
     AnyElement, App, DefiniteLength, Edges, EdgesRefinement, Entity, Hsla, InteractiveElement as _,
-    IntoElement, MouseButton, MouseDownEvent, ParentElement as _, Rems, RenderOnce, Role,
-    StatefulInteractiveElement as _, StyleRefinement, Styled, TextAlign, Window, div, px, relative,
+    IntoElement, KeyContext, MouseButton, MouseDownEvent, ParentElement as _, Rems, RenderOnce,
+    Role, StatefulInteractiveElement as _, StyleRefinement, Styled, TextAlign, Window, anchored,
+    deferred, div, px, relative,
 };
 
 use crate::button::{Button, ButtonVariants as _};
@@ -18,6 +19,7 @@ use crate::{Sizable, StyleSized};
 
 use super::{
     InputContentType, InputState, content_type::sync_native_content_type, element::EditorScrollbar,
+    minimap::EditorMinimap,
 };
 
 /// Returns `(background, foreground)` colors for input-like components.
@@ -44,6 +46,7 @@ pub struct Input {
     appearance: bool,
     cleanable: bool,
     mask_toggle: bool,
+    show_count: bool,
     disabled: bool,
     bordered: bool,
     focus_bordered: bool,
@@ -89,6 +92,7 @@ impl Input {
             appearance: true,
             cleanable: false,
             mask_toggle: false,
+            show_count: false,
             disabled: false,
             bordered: true,
             focus_bordered: true,
@@ -152,6 +156,15 @@ impl Input {
         self
     }
 
+    /// Set whether to show the number of characters, e.g. `123/500` with the
+    /// [`InputState::max_length`], default is false.
+    ///
+    /// The counter is shown as a suffix in the single-line mode, or under the text in the multi-line mode.
+    pub fn show_count(mut self, show_count: bool) -> Self {
+        self.show_count = show_count;
+        self
+    }
+
     /// Set the semantic content type for password managers and autofill.
     ///
     /// This is a component-level semantic hint. It does not change the text
@@ -288,11 +301,30 @@ impl Input {
         }
     }
 
+    fn render_count(state: &InputState, cx: &App) -> impl IntoElement {
+        let count = state.char_count();
+        let (label, exceeded) = match state.max_length {
+            Some(max_length) => (format!("{}/{}", count, max_length), count > max_length),
+            None => (count.to_string(), false),
+        };
+
+        div()
+            .flex_shrink_0()
+            .text_xs()
+            .text_color(if exceeded {
+                cx.theme().danger
+            } else {
+                cx.theme().muted_foreground
+            })
+            .child(label)
+    }
+
     /// This method must after the refine_style.
     fn render_editor(
         paddings: EdgesRefinement<DefiniteLength>,
         input_state: &Entity<InputState>,
         state: &InputState,
+        count: Option<AnyElement>,
         window: &Window,
     ) -> impl IntoElement {
         let base_size = window.text_style().font_size;
@@ -317,6 +349,17 @@ impl Input {
                 .unwrap_or(px(0.)),
         };
 
+        let show_minimap = state.is_minimap_visible();
+        // The minimap takes the right side, so the scrollbar can't extend into the right padding.
+        let paddings = if show_minimap {
+            Edges {
+                right: px(0.),
+                ..paddings
+            }
+        } else {
+            paddings
+        };
+
         state.editor_scrollbar_paddings.set(paddings);
         state.editor_scrollbar_snapshot.set(None);
 
@@ -324,12 +367,24 @@ impl Input {
             .size_full()
             .children(state.search_panel.clone())
             .child(
-                div()
-                    .relative()
+                h_flex()
                     .flex_1()
-                    .child(input_state.clone())
-                    .child(EditorScrollbar::new(input_state.clone())),
+                    .items_start()
+                    .child(
+                        div()
+                            .relative()
+                            .flex_1()
+                            .h_full()
+                            .child(input_state.clone())
+                            .child(EditorScrollbar::new(input_state.clone())),
+                    )
+                    .when(show_minimap, |this| {
+                        this.child(EditorMinimap::new(input_state.clone()))
+                    }),
             )
+            .when_some(count, |this, count| {
+                this.child(h_flex().justify_end().pt_1().child(count))
+            })
     }
 }
 
@@ -391,13 +446,31 @@ impl RenderOnce for Input {
             && !state.loading
             && state.text.len() > 0
             && state.mode.is_single_line();
-        let has_suffix = suffix.is_some() || state.loading || self.mask_toggle || show_clear_button;
+        let count = self
+            .show_count
+            .then(|| Self::render_count(state, cx).into_any_element());
+        let (count, editor_count) = if state.mode.is_multi_line() {
+            (None, count)
+        } else {
+            (count, None)
+        };
+        let has_suffix = suffix.is_some()
+            || state.loading
+            || self.mask_toggle
+            || show_clear_button
+            || count.is_some();
+
+        let mut key_context = KeyContext::new_with_defaults();
+        key_context.add(crate::input::CONTEXT);
+        if let Some(vim_mode) = state.current_vim_mode() {
+            key_context.set("vim_mode", vim_mode.as_str());
+        }
 
         div()
             .id(("input", self.state.entity_id()))
             .role(accessibility_role)
             .flex()
-            .key_context(crate::input::CONTEXT)
+            .key_context(key_context)
             .track_focus(&state.focus_handle.clone())
             .tab_index(self.tab_index)
             .when(!state.disabled, |this| {
@@ -415,6 +488,7 @@ impl RenderOnce for Input {
                     .on_action(window.listener_for(&self.state, InputState::cut))
                     .on_action(window.listener_for(&self.state, InputState::undo))
                     .on_action(window.listener_for(&self.state, InputState::redo))
+                    .on_action(window.listener_for(&self.state, InputState::on_action_vim_keys))
                     .when(state.mode.is_multi_line(), |this| {
                         this.on_action(window.listener_for(&self.state, InputState::indent_inline))
                             .on_action(window.listener_for(&self.state, InputState::outdent_inline))
@@ -436,15 +510,41 @@ impl RenderOnce for Input {
                     .on_action(window.listener_for(&self.state, InputState::select_up))
                     .on_action(window.listener_for(&self.state, InputState::select_down))
                     .on_action(window.listener_for(&self.state, InputState::page_up))
-                    .on_action(window.listener_for(&self.state, InputState::page_down));
+                    .on_action(window.listener_for(&self.state, InputState::page_down))
+                    .on_action(window.listener_for(&self.state, InputState::select_next_occurrence))
+                    .on_action(
+                        window.listener_for(&self.state, InputState::toggle_column_selection),
+                    )
+                    .on_action(window.listener_for(&self.state, InputState::on_action_fold))
+                    .on_action(window.listener_for(&self.state, InputState::on_action_unfold))
+                    .on_action(window.listener_for(&self.state, InputState::on_action_fold_all))
+                    .on_action(window.listener_for(&self.state, InputState::on_action_unfold_all));
 
-                let result = result.on_action(
-                    window.listener_for(&self.state, InputState::on_action_go_to_definition),
-                );
+                let result = result
+                    .on_action(
+                        window.listener_for(&self.state, InputState::on_action_go_to_definition),
+                    )
+                    .on_action(
+                        window.listener_for(&self.state, InputState::on_action_peek_definition),
+                    )
+                    .on_action(window.listener_for(&self.state, InputState::on_action_rename))
+                    .on_action(window.listener_for(&self.state, InputState::on_action_format))
+                    .on_action(
+                        window.listener_for(&self.state, InputState::on_action_format_selection),
+                    )
+                    .on_action(window.listener_for(&self.state, InputState::toggle_line_comment))
+                    .on_action(window.listener_for(&self.state, InputState::toggle_block_comment))
+                    .on_action(window.listener_for(&self.state, InputState::duplicate_line))
+                    .on_action(window.listener_for(&self.state, InputState::move_line_up))
+                    .on_action(window.listener_for(&self.state, InputState::move_line_down))
+                    .on_action(window.listener_for(&self.state, InputState::join_lines))
+                    .on_action(window.listener_for(&self.state, InputState::delete_line))
+                    .on_action(window.listener_for(&self.state, InputState::sort_lines));
 
                 result
             })
             .on_action(window.listener_for(&self.state, InputState::select_all))
+            .on_action(window.listener_for(&self.state, InputState::transpose))
             .on_action(window.listener_for(&self.state, InputState::select_to_start_of_line))
             .on_action(window.listener_for(&self.state, InputState::select_to_end_of_line))
             .on_action(window.listener_for(&self.state, InputState::select_to_previous_word))
@@ -460,6 +560,7 @@ impl RenderOnce for Input {
             .on_action(window.listener_for(&self.state, InputState::show_character_palette))
             .on_action(window.listener_for(&self.state, InputState::copy))
             .on_action(window.listener_for(&self.state, InputState::on_action_search))
+            .on_action(window.listener_for(&self.state, InputState::on_action_replace))
             .on_key_down(window.listener_for(&self.state, InputState::on_key_down))
             .on_mouse_down(
                 MouseButton::Left,
@@ -513,10 +614,36 @@ impl RenderOnce for Input {
             }))
             .when(state.mode.is_multi_line(), |mut this| {
                 let paddings = this.style().padding.clone();
-                this.child(Self::render_editor(paddings, &self.state, &state, window))
+                this.child(Self::render_editor(
+                    paddings,
+                    &self.state,
+                    &state,
+                    editor_count,
+                    window,
+                ))
             })
             .when(!state.mode.is_multi_line(), |this| {
-                this.child(self.state.clone())
+                this.child(self.state.clone()).when_some(
+                    state.search_panel.clone(),
+                    |this, search_panel| {
+                        // The single line input has no room for the search bar, show it as an overlay.
+                        this.relative().child(
+                            div().absolute().top_full().left_0().child(deferred(
+                                anchored().snap_to_window_with_margin(px(8.)).child(
+                                    div()
+                                        .mt_1()
+                                        .w(px(420.))
+                                        .border_1()
+                                        .border_color(cx.theme().border)
+                                        .rounded(cx.theme().radius)
+                                        .overflow_hidden()
+                                        .shadow_md()
+                                        .child(search_panel),
+                                ),
+                            )),
+                        )
+                    },
+                )
             })
             .when(has_suffix, |this| {
                 this.pr(self.size.input_px()).child(
@@ -542,6 +669,7 @@ impl RenderOnce for Input {
                                 }
                             }))
                         })
+                        .children(count)
                         .children(suffix),
                 )
             })
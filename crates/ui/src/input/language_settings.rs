@@ -0,0 +1,280 @@
+use std::{collections::HashMap, rc::Rc};
+
+use gpui::{Context, SharedString, Window};
+
+use crate::input::{InputState, RopeExt as _, TabSize, mode::InputMode};
+
+/// The editor settings of a language, see [`LanguageSettingsTable`].
+///
+/// The `None` fields are not changed when the settings are applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageSettings {
+    pub tab_size: Option<usize>,
+    pub hard_tabs: Option<bool>,
+    pub soft_wrap: Option<bool>,
+    pub rulers: Option<Vec<usize>>,
+    pub trim_trailing_whitespace: Option<bool>,
+}
+
+impl LanguageSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tab size in spaces.
+    pub fn tab_size(mut self, tab_size: usize) -> Self {
+        self.tab_size = Some(tab_size);
+        self
+    }
+
+    /// Set true to use `\t` as tab indent.
+    pub fn hard_tabs(mut self, hard_tabs: bool) -> Self {
+        self.hard_tabs = Some(hard_tabs);
+        self
+    }
+
+    /// Set the soft wrap mode.
+    pub fn soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = Some(soft_wrap);
+        self
+    }
+
+    /// Set the columns of the vertical rulers.
+    pub fn rulers(mut self, rulers: impl Into<Vec<usize>>) -> Self {
+        self.rulers = Some(rulers.into());
+        self
+    }
+
+    /// Set true to trim the trailing whitespace of the lines on save.
+    pub fn trim_trailing_whitespace(mut self, trim: bool) -> Self {
+        self.trim_trailing_whitespace = Some(trim);
+        self
+    }
+
+    /// Returns a new settings with the `Some` fields of `other` override this one.
+    fn merge(&self, other: &LanguageSettings) -> LanguageSettings {
+        LanguageSettings {
+            tab_size: other.tab_size.or(self.tab_size),
+            hard_tabs: other.hard_tabs.or(self.hard_tabs),
+            soft_wrap: other.soft_wrap.or(self.soft_wrap),
+            rulers: other.rulers.clone().or_else(|| self.rulers.clone()),
+            trim_trailing_whitespace: other
+                .trim_trailing_whitespace
+                .or(self.trim_trailing_whitespace),
+        }
+    }
+}
+
+/// A table of [`LanguageSettings`] by the highlighter language name.
+///
+/// The settings are resolved by the language of [`InputMode::CodeEditor`], so one
+/// [`InputState`] can host different file types, see [`InputState::language_settings`].
+///
+/// ```ignore
+/// let settings = LanguageSettingsTable::new()
+///     .default_settings(LanguageSettings::new().tab_size(2).rulers([100]))
+///     .language("python", LanguageSettings::new().tab_size(4).rulers([79]))
+///     .language("go", LanguageSettings::new().tab_size(4).hard_tabs(true))
+///     .language("markdown", LanguageSettings::new().soft_wrap(true).trim_trailing_whitespace(false));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LanguageSettingsTable {
+    defaults: LanguageSettings,
+    languages: HashMap<SharedString, LanguageSettings>,
+}
+
+impl LanguageSettingsTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the default settings, used for the languages without settings,
+    /// and the fields not set by a language.
+    pub fn default_settings(mut self, settings: LanguageSettings) -> Self {
+        self.defaults = settings;
+        self
+    }
+
+    /// Set the settings of the language, the name is case-insensitive.
+    pub fn language(
+        mut self,
+        language: impl Into<SharedString>,
+        settings: LanguageSettings,
+    ) -> Self {
+        let language: SharedString = language.into();
+        self.languages
+            .insert(language.to_lowercase().into(), settings);
+        self
+    }
+
+    /// Resolve the settings of the language, merged with the default settings.
+    pub fn resolve(&self, language: &str) -> LanguageSettings {
+        match self.languages.get(language.to_lowercase().as_str()) {
+            Some(settings) => self.defaults.merge(settings),
+            None => self.defaults.clone(),
+        }
+    }
+}
+
+impl InputState {
+    /// Set the per-language settings, only for [`InputMode::CodeEditor`] mode.
+    ///
+    /// The settings of the current language are applied now, and applied again
+    /// when the language is changed by [`InputState::set_highlighter`].
+    ///
+    /// This must be called after [`InputState::code_editor`].
+    pub fn language_settings(mut self, settings: LanguageSettingsTable) -> Self {
+        debug_assert!(self.mode.is_code_editor());
+        self.language_settings = Some(Rc::new(settings));
+        if let Some(settings) = self.resolved_language_settings() {
+            if let Some(soft_wrap) = settings.soft_wrap {
+                self.soft_wrap = soft_wrap;
+            }
+            self.apply_language_settings(settings);
+        }
+        self
+    }
+
+    /// Update the per-language settings, and apply the settings of the current language.
+    pub fn set_language_settings(
+        &mut self,
+        settings: LanguageSettingsTable,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.language_settings = Some(Rc::new(settings));
+        self.update_language_settings(cx);
+    }
+
+    /// Returns the resolved settings of the current language, if [`Self::language_settings`] is set.
+    pub fn current_language_settings(&self) -> Option<LanguageSettings> {
+        self.resolved_language_settings()
+    }
+
+    fn resolved_language_settings(&self) -> Option<LanguageSettings> {
+        let table = self.language_settings.as_ref()?;
+        let language = self.mode.language()?;
+        Some(table.resolve(language))
+    }
+
+    /// Apply the settings of the current language, called when the language is changed.
+    pub(super) fn update_language_settings(&mut self, cx: &mut Context<Self>) {
+        let Some(settings) = self.resolved_language_settings() else {
+            return;
+        };
+
+        if let Some(soft_wrap) = settings.soft_wrap {
+            if soft_wrap != self.soft_wrap {
+                self.update_soft_wrap(soft_wrap, cx);
+            }
+        }
+        self.apply_language_settings(settings);
+        cx.notify();
+    }
+
+    fn apply_language_settings(&mut self, settings: LanguageSettings) {
+        if let InputMode::CodeEditor { tab, .. } = &mut self.mode {
+            *tab = TabSize {
+                tab_size: settings.tab_size.unwrap_or(tab.tab_size),
+                hard_tabs: settings.hard_tabs.unwrap_or(tab.hard_tabs),
+            };
+        }
+        if let Some(rulers) = settings.rulers {
+            self.rulers = rulers;
+        }
+        if let Some(trim) = settings.trim_trailing_whitespace {
+            self.trim_trailing_whitespace = trim;
+        }
+    }
+
+    /// Set true to trim the trailing whitespace of the lines in [`Self::prepare_for_save`],
+    /// default is false.
+    pub fn trim_trailing_whitespace_on_save(mut self, trim: bool) -> Self {
+        self.trim_trailing_whitespace = trim;
+        self
+    }
+
+    /// Prepare the text to be saved, call this before reading the [`Self::value`] to save.
+    ///
+    /// This trims the trailing whitespace of the lines if enabled by
+    /// [`Self::trim_trailing_whitespace_on_save`] or the [`LanguageSettings`].
+    pub fn prepare_for_save(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.trim_trailing_whitespace {
+            self.trim_trailing_whitespace(window, cx);
+        }
+    }
+
+    /// Remove the trailing spaces and tabs of all the lines.
+    ///
+    /// The cursor is kept on the same position in the text.
+    pub fn trim_trailing_whitespace(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let mut ranges = vec![];
+        for row in 0..self.text.lines_len() {
+            let line = self.text.slice_line(row).to_string();
+            let content = line.strip_suffix('\r').unwrap_or(&line);
+            let trimmed = content.trim_end_matches([' ', '\t']);
+            if trimmed.len() < content.len() {
+                let start = self.text.line_start_offset(row);
+                ranges.push(start + trimmed.len()..start + content.len());
+            }
+        }
+
+        if ranges.is_empty() {
+            return;
+        }
+
+        let mut cursor = self.cursor();
+        for range in ranges.iter().rev() {
+            if cursor >= range.end {
+                cursor -= range.len();
+            } else if cursor > range.start {
+                cursor = range.start;
+            }
+
+            let range_utf16 = self.range_to_utf16(range);
+            self.replace_text_in_range_silent(Some(range_utf16), "", window, cx);
+        }
+        self.move_to(cursor, None, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LanguageSettings, LanguageSettingsTable};
+
+    #[test]
+    fn test_resolve_language_settings() {
+        let table = LanguageSettingsTable::new()
+            .default_settings(
+                LanguageSettings::new()
+                    .tab_size(2)
+                    .rulers([100])
+                    .trim_trailing_whitespace(true),
+            )
+            .language("Python", LanguageSettings::new().tab_size(4).rulers([79]))
+            .language("go", LanguageSettings::new().hard_tabs(true));
+
+        assert_eq!(
+            table.resolve("python"),
+            LanguageSettings::new()
+                .tab_size(4)
+                .rulers([79])
+                .trim_trailing_whitespace(true)
+        );
+        assert_eq!(
+            table.resolve("go"),
+            LanguageSettings::new()
+                .tab_size(2)
+                .hard_tabs(true)
+                .rulers([100])
+                .trim_trailing_whitespace(true)
+        );
+        assert_eq!(
+            table.resolve("rust"),
+            LanguageSettings::new()
+                .tab_size(2)
+                .rulers([100])
+                .trim_trailing_whitespace(true)
+        );
+    }
+}
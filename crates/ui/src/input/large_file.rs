@@ -0,0 +1,263 @@
+use std::ops::Range;
+
+use futures::{Stream, StreamExt as _};
+use gpui::{Context, SharedString, Task, Window};
+use ropey::{Rope, RopeBuilder};
+
+use crate::{
+    async_util::unbounded,
+    input::{DisplayMap, InputState, display_map::PreparedLines},
+};
+
+/// The default size threshold (in bytes) of the large file, 20 MB.
+pub(super) const DEFAULT_LARGE_FILE_THRESHOLD: usize = 20 * 1024 * 1024;
+/// The size of the chunks to split the text in [`InputState::load_value`].
+const CHUNK_SIZE: usize = 1024 * 1024;
+/// The size of the head of the text to display before the loading finished.
+const PREVIEW_SIZE: usize = 256 * 1024;
+
+/// The state of the loading text, see [`InputState::load_value`].
+pub(super) struct ValueLoading {
+    /// The disabled state before the loading, the input is read-only during the loading.
+    was_disabled: bool,
+    _task: Task<()>,
+}
+
+impl InputState {
+    /// Set the size threshold (in bytes) of the large file, default is 20 MB.
+    ///
+    /// When the text is larger than the threshold, the expensive features are disabled:
+    ///
+    /// - The syntax highlighting, and the folding based on it.
+    /// - The soft wrap, it will be restored when a smaller text is set.
+    pub fn large_file_threshold(mut self, threshold: usize) -> Self {
+        self.large_file_threshold = threshold;
+        self
+    }
+
+    /// Returns true if the text is larger than the [`Self::large_file_threshold`].
+    pub fn is_large_file(&self) -> bool {
+        self.large_file.is_some()
+    }
+
+    /// Returns true if the text is loading by [`Self::load_value`] or [`Self::load_chunks`].
+    pub fn is_loading_value(&self) -> bool {
+        self.value_loading.is_some()
+    }
+
+    /// Set the text like [`Self::set_value`], but without blocking the UI for a very large text.
+    ///
+    /// The text is split into chunks to build on a background task, the head of the text
+    /// is displayed immediately, and the input is read-only until the loading finished.
+    pub fn load_value(
+        &mut self,
+        value: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let value: SharedString = value.into();
+        let chunks = chunk_ranges(&value, CHUNK_SIZE)
+            .into_iter()
+            .map(move |range| value[range].to_string());
+        self.load_chunks(futures::stream::iter(chunks), window, cx);
+    }
+
+    /// Load the text from a stream of chunks, e.g. reading a file in chunks.
+    ///
+    /// See [`Self::load_value`], calling it again cancels the previous loading.
+    pub fn load_chunks<S>(
+        &mut self,
+        chunks: impl Stream<Item = S> + Send + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) where
+        S: AsRef<str> + Send + 'static,
+    {
+        self.cancel_value_loading();
+
+        let threshold = self
+            .mode
+            .is_multi_line()
+            .then_some(self.large_file_threshold);
+        let (preview_tx, preview_rx) = unbounded::<String>();
+        let build_task = cx.background_spawn(async move {
+            let mut chunks = Box::pin(chunks);
+            let mut builder = RopeBuilder::new();
+            let mut preview = Some(String::new());
+            while let Some(chunk) = chunks.next().await {
+                let chunk = chunk.as_ref();
+                builder.append(chunk);
+
+                if let Some(head) = preview.as_mut() {
+                    head.push_str(chunk);
+                    if head.len() >= PREVIEW_SIZE {
+                        let mut head = preview.take().unwrap_or_default();
+                        // Only display the complete lines.
+                        if let Some(ix) = head.rfind('\n') {
+                            head.truncate(ix + 1);
+                        }
+                        _ = preview_tx.try_send(head);
+                    }
+                }
+            }
+            preview_tx.close();
+
+            // Prepare the display lines of the large file here, it's too slow on the main thread.
+            let text = builder.finish();
+            let lines = threshold
+                .is_some_and(|threshold| text.len() > threshold)
+                .then(|| DisplayMap::prepare_lines(&text));
+            (text, lines)
+        });
+
+        let task = cx.spawn_in(window, async move |this, cx| {
+            if let Ok(head) = preview_rx.recv().await {
+                _ = this.update_in(cx, |this, window, cx| {
+                    let loading = this.value_loading.take();
+                    this.set_value(head, window, cx);
+                    this.value_loading = loading;
+                });
+            }
+
+            let (text, lines) = build_task.await;
+            _ = this.update(cx, |this, cx| {
+                this.finish_value_loading(text, lines, cx);
+            });
+        });
+
+        self.value_loading = Some(ValueLoading {
+            was_disabled: self.disabled,
+            _task: task,
+        });
+        self.disabled = true;
+        cx.notify();
+    }
+
+    /// Cancel the loading started by [`Self::load_value`], if any.
+    pub(super) fn cancel_value_loading(&mut self) {
+        if let Some(loading) = self.value_loading.take() {
+            self.disabled = loading.was_disabled;
+        }
+    }
+
+    fn finish_value_loading(
+        &mut self,
+        text: Rope,
+        lines: Option<PreparedLines>,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_loaded_text(&text, lines.clone(), cx);
+
+        // Set the loaded text to the inputs sharing the buffer directly, instead of syncing
+        // the whole text as an edit.
+        let Some(group) = self.shared_buffer.clone() else {
+            return;
+        };
+        let entity_id = cx.entity_id();
+        group.borrow_mut().retain(|peer| peer.upgrade().is_some());
+        let peers = group.borrow().clone();
+        for peer in peers {
+            if peer.entity_id() == entity_id {
+                continue;
+            }
+
+            _ = peer.update(cx, |peer, cx| {
+                peer.set_loaded_text(&text, lines.clone(), cx);
+            });
+        }
+    }
+
+    /// Set the text loaded by [`Self::load_chunks`], the `lines` are prepared on the background
+    /// thread for a large file, so nothing here is O(n) on the main thread.
+    fn set_loaded_text(
+        &mut self,
+        text: &Rope,
+        lines: Option<PreparedLines>,
+        cx: &mut Context<Self>,
+    ) {
+        self.cancel_value_loading();
+        self.update_large_file(text.len(), cx);
+
+        self.text = text.clone();
+        self.number_formatted = false;
+        self.extra_cursors.clear();
+        self.ime_marked_range = None;
+        self.snippet = None;
+        if let Some(diagnostics) = self.mode.diagnostics_mut() {
+            diagnostics.reset(&self.text)
+        }
+        self.display_map.clear_folds();
+        // Not a large file, the lines are cheap to prepare, or wrapped by the display map.
+        let lines = lines.unwrap_or_else(|| DisplayMap::prepare_lines(&self.text));
+        self.display_map.set_text_with_lines(&self.text, lines, cx);
+        self.reset_highlighter(cx);
+
+        self.reset_selection();
+        self.reset_lsp_state();
+        self.reset_scroll_to_start();
+        self.history.clear();
+        self.update_search_in_background(cx);
+        self.mode.update_auto_grow(&self.display_map);
+        cx.notify();
+    }
+
+    /// Enable or disable the large file mode for the text of `len` bytes.
+    pub(super) fn update_large_file(&mut self, len: usize, cx: &mut Context<Self>) {
+        let large = self.mode.is_multi_line() && len > self.large_file_threshold;
+        if large == self.large_file.is_some() {
+            return;
+        }
+
+        self.mode.set_large_file(large);
+        if large {
+            self.large_file = Some(self.soft_wrap);
+            self.reset_highlighter(cx);
+            self.display_map.clear_folds();
+            self.display_map.set_fold_candidates(vec![]);
+            // Wrapping all the lines is expensive, disable it before setting the text.
+            if self.soft_wrap {
+                self.update_soft_wrap(false, cx);
+            }
+        } else if let Some(soft_wrap) = self.large_file.take() {
+            if soft_wrap {
+                self.update_soft_wrap(true, cx);
+            }
+        }
+    }
+}
+
+/// Split the `text` into ranges of about `size` bytes, on the char boundaries.
+fn chunk_ranges(text: &str, size: usize) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + size.max(1)).min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        ranges.push(start..end);
+        start = end;
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::chunk_ranges;
+
+    #[test]
+    fn test_chunk_ranges() {
+        assert_eq!(chunk_ranges("", 4), vec![]);
+        assert_eq!(chunk_ranges("abcdefghij", 4), vec![0..4, 4..8, 8..10]);
+        assert_eq!(chunk_ranges("abc", 10), vec![0..3]);
+
+        // Do not split the multi-byte chars, "你" is 3 bytes.
+        let text = "a你好b";
+        let ranges = chunk_ranges(text, 2);
+        assert_eq!(ranges, vec![0..4, 4..7, 7..8]);
+        assert_eq!(
+            ranges.into_iter().map(|r| &text[r]).collect::<String>(),
+            text
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use std::ops::Range;
+
+use gpui::{Context, Window};
+use sum_tree::Bias;
+
+use crate::input::{
+    DeleteLine, DuplicateLine, InputState, JoinLines, MoveLineDown, MoveLineUp, RopeExt as _,
+    SortLines, Transpose,
+};
+
+/// Returns the lines of the `text` joined into one line, and the offset of the first join.
+///
+/// The trailing whitespace of each line and the leading whitespace of the next line are
+/// replaced by a single space, no space is added next to an empty line.
+fn join_lines(text: &str) -> (String, usize) {
+    let mut joined = String::with_capacity(text.len());
+    let mut first_join = None;
+    for (ix, line) in text.split('\n').enumerate() {
+        let line = if ix == 0 { line } else { line.trim_start() };
+        if ix > 0 {
+            let trimmed_len = joined.trim_end().len();
+            joined.truncate(trimmed_len);
+            first_join.get_or_insert(joined.len());
+            if !joined.is_empty() && !line.is_empty() {
+                joined.push(' ');
+            }
+        }
+        joined.push_str(line);
+    }
+
+    let first_join = first_join.unwrap_or(joined.len());
+    (joined, first_join)
+}
+
+/// Returns the lines of the `text` sorted in ascending order.
+fn sort_lines(text: &str) -> String {
+    let mut lines = text.split('\n').collect::<Vec<_>>();
+    lines.sort();
+    lines.join("\n")
+}
+
+/// Returns the edit to swap the characters around the `column` of the `line`, and the new
+/// column of the cursor.
+///
+/// The character before and after the cursor are swapped and the cursor moves forward,
+/// at the end of the line the two characters before the cursor are swapped.
+fn transpose_chars(line: &str, column: usize) -> Option<(Range<usize>, String, usize)> {
+    let (before, after) = line.split_at(column);
+    let (range, new_column) = match (before.chars().next_back(), after.chars().next()) {
+        (Some(prev), Some(next)) => (
+            column - prev.len_utf8()..column + next.len_utf8(),
+            column + next.len_utf8(),
+        ),
+        (Some(prev), None) => {
+            let start = before[..column - prev.len_utf8()]
+                .chars()
+                .next_back()?
+                .len_utf8();
+            (column - prev.len_utf8() - start..column, column)
+        }
+        _ => return None,
+    };
+
+    let mut chars = line[range.clone()].chars();
+    let first = chars.next()?;
+    let second = chars.next()?;
+    Some((range, format!("{}{}", second, first), new_column))
+}
+
+impl InputState {
+    pub(super) fn duplicate_line(
+        &mut self,
+        _: &DuplicateLine,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            // Duplicate the selected text, or the lines of the cursor.
+            let (at, new_text) = if this.selected_range.is_empty() {
+                let rows = this.selected_rows();
+                let start = this.text.line_start_offset(*rows.start());
+                let end = this.text.line_end_offset(*rows.end());
+                let lines = this.text.slice(start..end).to_string();
+                (end, format!("\n{}", lines))
+            } else {
+                let range = this.selected_range.start..this.selected_range.end;
+                (range.end, this.text.slice(range).to_string())
+            };
+
+            // Move the selection to the copy.
+            let delta = new_text.len();
+            let selection = this.selected_range.start + delta..this.selected_range.end + delta;
+            this.replace_lines(at..at, &new_text, selection, window, cx);
+        });
+    }
+
+    pub(super) fn move_line_up(
+        &mut self,
+        _: &MoveLineUp,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            let rows = this.selected_rows();
+            if *rows.start() == 0 {
+                return;
+            }
+
+            let start = this.text.line_start_offset(*rows.start() - 1);
+            let mid = this.text.line_start_offset(*rows.start());
+            let end = this.text.line_end_offset(*rows.end());
+            let prev_line = this.text.slice(start..mid - 1).to_string();
+            let lines = this.text.slice(mid..end).to_string();
+
+            let delta = mid - start;
+            let selection = this.selected_range.start - delta..this.selected_range.end - delta;
+            this.replace_lines(
+                start..end,
+                &format!("{}\n{}", lines, prev_line),
+                selection,
+                window,
+                cx,
+            );
+        });
+    }
+
+    pub(super) fn move_line_down(
+        &mut self,
+        _: &MoveLineDown,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            let rows = this.selected_rows();
+            if *rows.end() + 1 >= this.text.lines_len() {
+                return;
+            }
+
+            let start = this.text.line_start_offset(*rows.start());
+            let mid = this.text.line_end_offset(*rows.end());
+            let end = this.text.line_end_offset(*rows.end() + 1);
+            let lines = this.text.slice(start..mid).to_string();
+            let next_line = this.text.slice(mid + 1..end).to_string();
+
+            let delta = end - mid;
+            let selection = this.selected_range.start + delta..this.selected_range.end + delta;
+            this.replace_lines(
+                start..end,
+                &format!("{}\n{}", next_line, lines),
+                selection,
+                window,
+                cx,
+            );
+        });
+    }
+
+    pub(super) fn join_lines(
+        &mut self,
+        _: &JoinLines,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            // Join the selected lines, or the line of the cursor with the next line.
+            let rows = this.selected_rows();
+            let last_row = if rows.start() == rows.end() {
+                *rows.end() + 1
+            } else {
+                *rows.end()
+            };
+            if last_row >= this.text.lines_len() {
+                return;
+            }
+
+            let start = this.text.line_start_offset(*rows.start());
+            let end = this.text.line_end_offset(last_row);
+            let (joined, first_join) = join_lines(&this.text.slice(start..end).to_string());
+
+            let selection = if this.selected_range.is_empty() {
+                start + first_join..start + first_join
+            } else {
+                start..start + joined.len()
+            };
+            this.replace_lines(start..end, &joined, selection, window, cx);
+        });
+    }
+
+    pub(super) fn delete_line(
+        &mut self,
+        _: &DeleteLine,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            let rows = this.selected_rows();
+            let column = this.text.offset_to_point(this.cursor()).column;
+
+            // Delete the line break after the lines, or before the last line, and keep the
+            // cursor column on the line that takes the place of the deleted lines.
+            let (range, line_start, line_len) = if *rows.end() + 1 < this.text.lines_len() {
+                let start = this.text.line_start_offset(*rows.start());
+                let end = this.text.line_start_offset(*rows.end() + 1);
+                (start..end, start, this.text.line_len(*rows.end() + 1))
+            } else if *rows.start() > 0 {
+                let row = *rows.start() - 1;
+                let start = this.text.line_end_offset(row);
+                let line_start = this.text.line_start_offset(row);
+                (start..this.text.len(), line_start, this.text.line_len(row))
+            } else {
+                (0..this.text.len(), 0, 0)
+            };
+            if range.is_empty() {
+                return;
+            }
+
+            let offset = line_start + column.min(line_len);
+            this.replace_lines(range, "", offset..offset, window, cx);
+            let offset = this.text.clip_offset(offset, Bias::Left);
+            this.selected_range = (offset..offset).into();
+        });
+    }
+
+    pub(super) fn transpose(&mut self, _: &Transpose, window: &mut Window, cx: &mut Context<Self>) {
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            if !this.selected_range.is_empty() {
+                return;
+            }
+
+            let point = this.text.offset_to_point(this.cursor());
+            let line_start = this.text.line_start_offset(point.row);
+            let line = this.text.slice_line(point.row).to_string();
+            let Some((range, new_text, column)) = transpose_chars(&line, point.column) else {
+                return;
+            };
+
+            let cursor = line_start + column;
+            this.replace_lines(
+                line_start + range.start..line_start + range.end,
+                &new_text,
+                cursor..cursor,
+                window,
+                cx,
+            );
+        });
+    }
+
+    pub(super) fn sort_lines(
+        &mut self,
+        _: &SortLines,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            let rows = this.selected_rows();
+            if rows.start() == rows.end() {
+                return;
+            }
+
+            let start = this.text.line_start_offset(*rows.start());
+            let end = this.text.line_end_offset(*rows.end());
+            let sorted = sort_lines(&this.text.slice(start..end).to_string());
+            this.replace_lines(start..end, &sorted, start..end, window, cx);
+        });
+    }
+
+    /// Replace the `range` with the `new_text` as one undo step, and select the `selection`.
+    fn replace_lines(
+        &mut self,
+        range: Range<usize>,
+        new_text: &str,
+        selection: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let range_utf16 = self.range_to_utf16(&range);
+        self.replace_text_in_range_silent(Some(range_utf16), new_text, window, cx);
+        self.selected_range = selection.into();
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{join_lines, sort_lines, transpose_chars};
+
+    #[test]
+    fn test_join_lines() {
+        assert_eq!(join_lines("a\nb"), ("a b".to_string(), 1));
+        assert_eq!(
+            join_lines("let a = 1;  \n    let b = 2;\n\tc"),
+            ("let a = 1; let b = 2; c".to_string(), 10)
+        );
+        // No space next to an empty line.
+        assert_eq!(join_lines("a\n\nb"), ("ab".to_string(), 1));
+        assert_eq!(join_lines("\n  b"), ("b".to_string(), 0));
+        assert_eq!(join_lines("a"), ("a".to_string(), 1));
+    }
+
+    #[test]
+    fn test_sort_lines() {
+        assert_eq!(sort_lines("c\na\nb"), "a\nb\nc");
+        assert_eq!(sort_lines("b\n\nB\na"), "\nB\na\nb");
+        assert_eq!(sort_lines("a"), "a");
+    }
+
+    #[test]
+    fn test_transpose_chars() {
+        // Swap the characters around the cursor and move forward.
+        assert_eq!(transpose_chars("abc", 1), Some((0..2, "ba".to_string(), 2)));
+        // At the end of the line, swap the two characters before.
+        assert_eq!(transpose_chars("abc", 3), Some((1..3, "cb".to_string(), 3)));
+        assert_eq!(
+            transpose_chars("中文a", 3),
+            Some((0..6, "文中".to_string(), 6))
+        );
+        assert_eq!(transpose_chars("abc", 0), None);
+        assert_eq!(transpose_chars("a", 1), None);
+        assert_eq!(transpose_chars("", 0), None);
+    }
+}
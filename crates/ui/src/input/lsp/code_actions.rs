@@ -1,13 +1,27 @@
 use anyhow::Result;
-use gpui::{App, Context, Entity, SharedString, Task, Window};
-use lsp_types::CodeAction;
-use std::ops::Range;
+use gpui::{
+    AnyElement, App, AsyncWindowContext, Bounds, Context, Entity, Half as _,
+    InteractiveElement as _, IntoElement as _, MouseButton, Pixels, SharedString, Styled as _,
+    Task, Window, point, px, size,
+};
+use instant::Duration;
+use lsp_types::{CodeAction, CodeActionKind};
+use rust_i18n::t;
+use std::{ops::Range, rc::Rc};
 
-use crate::input::{
-    InputState, ToggleCodeActions,
-    popovers::{CodeActionItem, CodeActionMenu, ContextMenu},
+use crate::{
+    ActiveTheme as _, IconName, Sizable as _,
+    button::{Button, ButtonVariants as _},
+    input::{
+        InputState, LastLayout, RopeExt as _, ToggleCodeActions,
+        element::TextElement,
+        popovers::{CodeActionItem, CodeActionMenu, ContextMenu},
+    },
 };
 
+/// The size of the lightbulb in the gutter.
+const LIGHTBULB_SIZE: Pixels = px(16.);
+
 pub trait CodeActionProvider {
     /// The id for this CodeAction.
     fn id(&self) -> SharedString;
@@ -53,41 +67,12 @@ impl InputState {
         cx: &mut Context<Self>,
     ) {
         let providers = self.lsp.code_action_providers.clone();
-        let menu = match self.context_menu_content.as_ref() {
-            Some(ContextMenu::CodeAction(menu)) => Some(menu),
-            _ => None,
-        };
-
-        let menu = match menu {
-            Some(menu) => menu.clone(),
-            None => {
-                let menu = CodeActionMenu::new(cx.entity(), window, cx);
-                self.context_menu_content = Some(ContextMenu::CodeAction(menu.clone()));
-                menu
-            }
-        };
-
+        let menu = self.code_action_menu(window, cx);
         let range = self.selected_range.start..self.selected_range.end;
 
         let state = cx.entity();
         self._context_menu_task = cx.spawn_in(window, async move |editor, cx| {
-            let mut provider_responses = vec![];
-            _ = cx.update(|window, cx| {
-                for provider in providers {
-                    let task = provider.code_actions(state.clone(), range.clone(), window, cx);
-                    provider_responses.push((provider.id(), task));
-                }
-            });
-
-            let mut code_actions: Vec<CodeActionItem> = vec![];
-            for (provider_id, provider_responses) in provider_responses {
-                if let Some(responses) = provider_responses.await.ok() {
-                    code_actions.extend(responses.into_iter().map(|action| CodeActionItem {
-                        provider_id: provider_id.clone(),
-                        action,
-                    }))
-                }
-            }
+            let code_actions = fetch_code_actions(state, providers, range, cx).await;
 
             if code_actions.is_empty() {
                 _ = menu.update(cx, |menu, cx| {
@@ -115,6 +100,104 @@ impl InputState {
         });
     }
 
+    /// Returns the opened code action menu, or create a new one.
+    fn code_action_menu(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<CodeActionMenu> {
+        if let Some(ContextMenu::CodeAction(menu)) = self.context_menu_content.as_ref() {
+            return menu.clone();
+        }
+
+        let menu = CodeActionMenu::new(cx.entity(), window, cx);
+        self.context_menu_content = Some(ContextMenu::CodeAction(menu.clone()));
+        menu
+    }
+
+    /// Set true to show a lightbulb in the gutter when code actions are available at the
+    /// cursor, default is true.
+    ///
+    /// The code actions are fetched from the [`CodeActionProvider`]s after the cursor moved,
+    /// click the lightbulb to open the code actions menu.
+    pub fn code_action_lightbulb(mut self, lightbulb: bool) -> Self {
+        self.code_action_lightbulb = lightbulb;
+        self
+    }
+
+    /// Update whether to show a lightbulb when code actions are available at the cursor.
+    pub fn set_code_action_lightbulb(
+        &mut self,
+        lightbulb: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.code_action_lightbulb = lightbulb;
+        self.update_code_action_hint(window, cx);
+        cx.notify();
+    }
+
+    /// Fetch the code actions at the cursor for the lightbulb, after the cursor moved.
+    pub(crate) fn update_code_action_hint(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.code_action_lightbulb
+            || !self.mode.is_code_editor()
+            || self.lsp.code_action_providers.is_empty()
+        {
+            self.lsp.code_action_hint = None;
+            self.lsp._code_action_hint_task = Task::ready(());
+            return;
+        }
+
+        let range = self.selected_range.start..self.selected_range.end;
+        let row = self.text.offset_to_point(self.cursor()).row;
+        // Keep the lightbulb in the same row until the new result, to avoid flickering.
+        if self
+            .lsp
+            .code_action_hint
+            .as_ref()
+            .is_some_and(|hint| hint.row != row)
+        {
+            self.lsp.code_action_hint = None;
+        }
+
+        let text = self.text.clone();
+        let providers = self.lsp.code_action_providers.clone();
+        let state = cx.entity();
+
+        // debounce timer 250ms
+        self.lsp._code_action_hint_task = cx.spawn_in(window, async move |editor, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(250))
+                .await;
+
+            let items = fetch_code_actions(state, providers, range, cx).await;
+            _ = editor.update(cx, |editor, cx| {
+                // The text has been changed, the result is outdated.
+                if editor.text != text {
+                    return;
+                }
+
+                editor.lsp.code_action_hint =
+                    (!items.is_empty()).then(|| CodeActionHint { row, items });
+                cx.notify();
+            });
+        });
+    }
+
+    /// Show the code actions of the lightbulb in the menu.
+    fn show_code_action_hint(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(hint) = self.lsp.code_action_hint.as_ref() else {
+            return;
+        };
+
+        let items = hint.items.clone();
+        let offset = self.cursor();
+        let menu = self.code_action_menu(window, cx);
+        menu.update(cx, |menu, cx| menu.show(offset, items, window, cx));
+        self.focus_handle.focus(window, cx);
+        cx.notify();
+    }
+
     pub(crate) fn perform_code_action(
         &mut self,
         item: &CodeActionItem,
@@ -138,3 +221,216 @@ impl InputState {
         .detach();
     }
 }
+
+/// The code actions available in the row of the cursor, see [`InputState::code_action_lightbulb`].
+pub(crate) struct CodeActionHint {
+    row: usize,
+    items: Vec<CodeActionItem>,
+}
+
+/// The group of the code actions in the menu, by the kind of the action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum CodeActionGroup {
+    QuickFix,
+    Refactor,
+    Source,
+    Other,
+}
+
+impl CodeActionGroup {
+    /// Returns the group of the `action`, the kinds are hierarchical, e.g. `refactor.extract`
+    /// is in the [`CodeActionGroup::Refactor`] group.
+    pub(crate) fn of(action: &CodeAction) -> Self {
+        let Some(kind) = action.kind.as_ref().map(|kind| kind.as_str()) else {
+            return Self::Other;
+        };
+
+        let is_kind = |base: CodeActionKind| {
+            kind.strip_prefix(base.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        };
+
+        if is_kind(CodeActionKind::QUICKFIX) {
+            Self::QuickFix
+        } else if is_kind(CodeActionKind::REFACTOR) {
+            Self::Refactor
+        } else if is_kind(CodeActionKind::SOURCE) {
+            Self::Source
+        } else {
+            Self::Other
+        }
+    }
+
+    pub(crate) fn label(&self) -> SharedString {
+        match self {
+            Self::QuickFix => t!("Input.Quick Fix"),
+            Self::Refactor => t!("Input.Refactor"),
+            Self::Source => t!("Input.Source Action"),
+            Self::Other => t!("Input.More Actions"),
+        }
+        .into()
+    }
+}
+
+/// Sort the code actions by the [`CodeActionGroup`], and the preferred ones first in
+/// each group, the order of the providers is kept for the rest.
+pub(crate) fn sort_code_actions(items: &mut [CodeActionItem]) {
+    items.sort_by_key(|item| {
+        (
+            CodeActionGroup::of(&item.action),
+            item.action.is_preferred != Some(true),
+        )
+    });
+}
+
+/// Fetches the code actions of all the `providers` for the `range`.
+async fn fetch_code_actions(
+    state: Entity<InputState>,
+    providers: Vec<Rc<dyn CodeActionProvider>>,
+    range: Range<usize>,
+    cx: &mut AsyncWindowContext,
+) -> Vec<CodeActionItem> {
+    let mut provider_responses = vec![];
+    _ = cx.update(|window, cx| {
+        for provider in providers {
+            let task = provider.code_actions(state.clone(), range.clone(), window, cx);
+            provider_responses.push((provider.id(), task));
+        }
+    });
+
+    let mut code_actions: Vec<CodeActionItem> = vec![];
+    for (provider_id, provider_responses) in provider_responses {
+        if let Some(responses) = provider_responses.await.ok() {
+            code_actions.extend(responses.into_iter().map(|action| CodeActionItem {
+                provider_id: provider_id.clone(),
+                action,
+            }))
+        }
+    }
+    code_actions
+}
+
+impl TextElement {
+    /// Layout the lightbulb at the left of the gutter, in the row of the cursor.
+    pub(crate) fn layout_code_action_lightbulb(
+        &self,
+        origin_x: Pixels,
+        bounds: &Bounds<Pixels>,
+        last_layout: &LastLayout,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        let state = self.state.read(cx);
+        let row = state.lsp.code_action_hint.as_ref()?.row;
+        if last_layout.line_number_width < LIGHTBULB_SIZE {
+            return None;
+        }
+
+        // The lightbulb is hidden if the row is not visible, e.g. folded or scrolled out.
+        let mut offset_y = last_layout.visible_top;
+        let mut lines = last_layout
+            .lines
+            .iter()
+            .zip(last_layout.visible_buffer_lines.iter());
+        loop {
+            let (line, &buffer_line) = lines.next()?;
+            if buffer_line == row {
+                break;
+            }
+            offset_y += line.wrapped_lines.len() * last_layout.line_height;
+        }
+
+        let origin = point(
+            origin_x,
+            bounds.origin.y + offset_y + (last_layout.line_height - LIGHTBULB_SIZE).half(),
+        );
+        let mut element = Button::new("code-action-lightbulb")
+            .ghost()
+            .xsmall()
+            .size(LIGHTBULB_SIZE)
+            .icon(IconName::Lightbulb)
+            .text_color(cx.theme().warning)
+            .on_mouse_down(MouseButton::Left, {
+                let state = self.state.clone();
+                move |_, window, cx| {
+                    cx.stop_propagation();
+                    state.update(cx, |state, cx| state.show_code_action_hint(window, cx));
+                }
+            })
+            .into_any_element();
+
+        element.prepaint_as_root(
+            origin,
+            size(LIGHTBULB_SIZE, LIGHTBULB_SIZE).into(),
+            window,
+            cx,
+        );
+        Some(element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{CodeAction, CodeActionKind};
+
+    use super::{CodeActionGroup, sort_code_actions};
+    use crate::input::popovers::CodeActionItem;
+
+    fn item(title: &str, kind: Option<CodeActionKind>, is_preferred: bool) -> CodeActionItem {
+        CodeActionItem {
+            provider_id: "test".into(),
+            action: CodeAction {
+                title: title.into(),
+                kind,
+                is_preferred: is_preferred.then_some(true),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_code_action_group() {
+        let group = |kind: &str| {
+            CodeActionGroup::of(
+                &item("", Some(CodeActionKind::from(kind.to_string())), false).action,
+            )
+        };
+
+        assert_eq!(group("quickfix"), CodeActionGroup::QuickFix);
+        assert_eq!(group("refactor"), CodeActionGroup::Refactor);
+        assert_eq!(group("refactor.extract"), CodeActionGroup::Refactor);
+        assert_eq!(group("source.organizeImports"), CodeActionGroup::Source);
+        assert_eq!(group("refactoring"), CodeActionGroup::Other);
+        assert_eq!(group(""), CodeActionGroup::Other);
+        assert_eq!(
+            CodeActionGroup::of(&item("", None, false).action),
+            CodeActionGroup::Other
+        );
+    }
+
+    #[test]
+    fn test_sort_code_actions() {
+        let mut items = vec![
+            item("Extract", Some(CodeActionKind::REFACTOR_EXTRACT), false),
+            item(
+                "Organize",
+                Some(CodeActionKind::SOURCE_ORGANIZE_IMPORTS),
+                false,
+            ),
+            item("Fix A", Some(CodeActionKind::QUICKFIX), false),
+            item("Other", None, false),
+            item("Fix B", Some(CodeActionKind::QUICKFIX), true),
+            item("Inline", Some(CodeActionKind::REFACTOR_INLINE), false),
+        ];
+        sort_code_actions(&mut items);
+
+        let titles = items
+            .iter()
+            .map(|item| item.action.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            vec!["Fix B", "Fix A", "Extract", "Inline", "Organize", "Other"]
+        );
+    }
+}
@@ -1,13 +1,17 @@
 use anyhow::Result;
 use gpui::{
-    App, Context, HighlightStyle, Hitbox, MouseDownEvent, Task, UnderlineStyle, Window, px,
+    App, Context, Focusable as _, HighlightStyle, Hitbox, MouseDownEvent, Task, UnderlineStyle,
+    Window, px,
 };
 use ropey::Rope;
 use std::{ops::Range, rc::Rc};
 
 use crate::{
     ActiveTheme,
-    input::{GoToDefinition, InputState, RopeExt, element::TextElement},
+    input::{
+        GoToDefinition, InputState, PeekDefinition, RopeExt, element::TextElement,
+        popovers::DefinitionPeek,
+    },
 };
 
 /// Definition provider
@@ -113,16 +117,92 @@ impl InputState {
         _: &GoToDefinition,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.request_definitions_at_cursor(false, window, cx);
+    }
+
+    pub(crate) fn on_action_peek_definition(
+        &mut self,
+        _: &PeekDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.request_definitions_at_cursor(true, window, cx);
+    }
+
+    /// Request the definitions of the symbol at the cursor, then go to or peek them.
+    fn request_definitions_at_cursor(
+        &mut self,
+        peek: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let offset = self.cursor();
+
+        // Reuse the locations of the last hover if the cursor is in the symbol.
         if let Some((symbol_range, locations)) = self.hover_definition.last_location.clone() {
-            if !(symbol_range.start..=symbol_range.end).contains(&offset) {
+            if (symbol_range.start..=symbol_range.end).contains(&offset) {
+                self.show_definitions(offset, locations.to_vec(), peek, window, cx);
                 return;
             }
+        }
 
-            if let Some(location) = locations.first().cloned() {
-                self.go_to_definition(&location, window, cx);
-            }
+        let Some(provider) = self.lsp.definition_provider.clone() else {
+            return;
+        };
+
+        let task = provider.definitions(&self.text, offset, window, cx);
+        let editor = cx.entity();
+        self.lsp._definition_task = cx.spawn_in(window, async move |_, cx| {
+            let locations = task.await?;
+
+            _ = cx.update(|window, cx| {
+                editor.update(cx, |editor, cx| {
+                    editor.show_definitions(offset, locations, peek, window, cx);
+                })
+            });
+
+            Ok(())
+        });
+    }
+
+    /// Go to the definition if there is only one location and not `peek`,
+    /// otherwise open the "Peek Definition" view.
+    fn show_definitions(
+        &mut self,
+        offset: usize,
+        locations: Vec<lsp_types::LocationLink>,
+        peek: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match locations.as_slice() {
+            [] => {}
+            [location] if !peek => self.go_to_definition(location, window, cx),
+            _ => self.open_definition_peek(offset, locations, window, cx),
+        }
+    }
+
+    /// Open the "Peek Definition" view below the line of the `offset`.
+    pub(crate) fn open_definition_peek(
+        &mut self,
+        offset: usize,
+        locations: Vec<lsp_types::LocationLink>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.clear_hover_state(cx);
+        let peek = DefinitionPeek::new(cx.entity(), offset, locations, window, cx);
+        peek.read(cx).focus_handle(cx).focus(window, cx);
+        self.definition_peek = Some(peek);
+        cx.notify();
+    }
+
+    /// Close the "Peek Definition" view, and focus back to the editor.
+    pub(crate) fn close_definition_peek(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.definition_peek.take().is_some() {
+            self.focus(window, cx);
+            cx.notify();
         }
     }
 
@@ -145,11 +225,12 @@ impl InputState {
             return false;
         }
 
-        let Some(location) = self.hover_definition.locations.first().cloned() else {
+        let locations = self.hover_definition.locations.to_vec();
+        if locations.is_empty() {
             return false;
-        };
+        }
 
-        self.go_to_definition(&location, window, cx);
+        self.show_definitions(offset, locations, false, window, cx);
 
         true
     }
@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use gpui::{App, Context, Task, Window};
+use instant::Duration;
+use lsp_types::{DocumentHighlight, DocumentHighlightKind};
+use ropey::Rope;
+use sum_tree::Bias;
+
+use crate::input::{InputState, Lsp, RopeExt};
+
+/// Document highlight provider
+///
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_documentHighlight
+pub trait DocumentHighlightProvider {
+    /// Fetches the occurrences of the symbol at the offset.
+    ///
+    /// textDocument/documentHighlight
+    ///
+    /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_documentHighlight
+    fn document_highlights(
+        &self,
+        _text: &Rope,
+        _offset: usize,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Result<Vec<DocumentHighlight>>>;
+}
+
+impl Lsp {
+    /// Get the document highlights in the byte range.
+    ///
+    /// Returns byte ranges, and true if the occurrence is a write access.
+    pub(crate) fn document_highlights_for_range(
+        &self,
+        range: &Range<usize>,
+    ) -> Vec<(Range<usize>, bool)> {
+        self.document_highlights
+            .iter()
+            .filter(|(highlight, _)| highlight.start >= range.start && highlight.end <= range.end)
+            .cloned()
+            .collect()
+    }
+}
+
+impl InputState {
+    /// Update the occurrences of the word under the cursor by the [`DocumentHighlightProvider`].
+    pub(crate) fn update_document_highlights(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(provider) = self.lsp.document_highlight_provider.clone() else {
+            return;
+        };
+
+        let offset = self.cursor();
+        if !self.highlight_occurrences
+            || !self.selected_range.is_empty()
+            || self.text.word_range(offset).is_none()
+        {
+            self.lsp.document_highlights.clear();
+            self.lsp._document_highlight_task = Task::ready(());
+            return;
+        }
+
+        let text = self.text.clone();
+        let editor = cx.entity();
+
+        // debounce timer 100ms
+        self.lsp._document_highlight_task = cx.spawn_in(window, async move |_, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(100))
+                .await;
+
+            let Some(task) = cx
+                .update(|window, cx| provider.document_highlights(&text, offset, window, cx))
+                .ok()
+            else {
+                return;
+            };
+
+            let highlights = task.await.unwrap_or_default();
+            _ = editor.update(cx, |editor, cx| {
+                // The text has been changed, the result is outdated.
+                if editor.text != text {
+                    return;
+                }
+
+                let mut document_highlights = highlights
+                    .iter()
+                    .map(|highlight| {
+                        let start = text.position_to_offset(&highlight.range.start);
+                        let end = text.position_to_offset(&highlight.range.end);
+                        let is_write = highlight.kind == Some(DocumentHighlightKind::WRITE);
+
+                        (start..end, is_write)
+                    })
+                    .collect::<Vec<_>>();
+                document_highlights.sort_by_key(|(range, _)| range.start);
+
+                if document_highlights != editor.lsp.document_highlights {
+                    editor.lsp.document_highlights = document_highlights;
+                    cx.notify();
+                }
+            });
+        });
+    }
+
+    /// Returns the ranges of the occurrences of the word under the cursor in the byte `range`.
+    ///
+    /// Returns byte ranges, and true if the occurrence is a write access.
+    ///
+    /// This uses the [`DocumentHighlightProvider`] if present, otherwise matches the whole
+    /// words in the text.
+    pub(crate) fn occurrences_for_range(&self, range: &Range<usize>) -> Vec<(Range<usize>, bool)> {
+        // Preview the edits of the renaming symbol.
+        if self.rename_editor.is_some() {
+            return self
+                .lsp
+                .rename_ranges_for_range(range)
+                .into_iter()
+                .map(|range| (range, true))
+                .collect();
+        }
+
+        if !self.highlight_occurrences
+            || !self.mode.is_code_editor()
+            || !self.selected_range.is_empty()
+        {
+            return vec![];
+        }
+
+        if self.lsp.document_highlight_provider.is_some() {
+            return self.lsp.document_highlights_for_range(range);
+        }
+
+        let Some(word_range) = self.text.word_range(self.cursor()) else {
+            return vec![];
+        };
+        let word = self.text.slice(word_range).to_string();
+
+        let start = self.text.clip_offset(range.start, Bias::Left);
+        let end = self
+            .text
+            .clip_offset(range.end.min(self.text.len()), Bias::Right);
+        let text = self.text.slice(start..end).to_string();
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+        text.match_indices(&word)
+            .filter(|(ix, _)| {
+                let before = text[..*ix].chars().next_back();
+                let after = text[ix + word.len()..].chars().next();
+                !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+            })
+            .map(|(ix, _)| (start + ix..start + ix + word.len(), false))
+            .collect()
+    }
+}
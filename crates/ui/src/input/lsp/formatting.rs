@@ -0,0 +1,255 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use gpui::{App, Context, Task, Window};
+use lsp_types::{FormattingOptions, TextEdit};
+use ropey::Rope;
+
+use crate::input::{Format, FormatSelection, InputState, RopeExt};
+
+/// Formatting provider, to format the document or the selection by an external formatter.
+///
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_formatting
+pub trait FormattingProvider {
+    /// Returns the edits to format the whole document.
+    ///
+    /// textDocument/formatting
+    ///
+    /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_formatting
+    fn format(
+        &self,
+        text: &Rope,
+        options: FormattingOptions,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<TextEdit>>>;
+
+    /// Returns the edits to format the byte range of the document.
+    ///
+    /// Default returns no edits, that is the range formatting is not supported.
+    ///
+    /// textDocument/rangeFormatting
+    ///
+    /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_rangeFormatting
+    fn format_range(
+        &self,
+        _text: &Rope,
+        _range: Range<usize>,
+        _options: FormattingOptions,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Result<Vec<TextEdit>>> {
+        Task::ready(Ok(vec![]))
+    }
+}
+
+/// Returns the new byte offset of the `offset` after the `edits` are applied.
+///
+/// The `edits` are the byte ranges of the `text` and the new texts, sorted by the start.
+///
+/// If the offset is inside an edit, it is moved to the same count of non-whitespace
+/// characters in the new text, so the cursor stays on the same token when only the
+/// whitespace is changed, which is the most common case of formatting.
+fn adjust_offset_for_edits(text: &Rope, offset: usize, edits: &[(Range<usize>, String)]) -> usize {
+    let mut delta = 0isize;
+    for (range, new_text) in edits {
+        if offset >= range.end {
+            delta += new_text.len() as isize - range.len() as isize;
+            continue;
+        }
+
+        if offset > range.start {
+            let count = text
+                .slice(range.start..offset)
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .count();
+
+            let mut ix = 0;
+            if count > 0 {
+                ix = new_text.len();
+                let mut seen = 0;
+                for (i, c) in new_text.char_indices() {
+                    if !c.is_whitespace() {
+                        seen += 1;
+                        if seen == count {
+                            ix = i + c.len_utf8();
+                            break;
+                        }
+                    }
+                }
+            }
+
+            return (range.start as isize + delta) as usize + ix;
+        }
+
+        break;
+    }
+
+    (offset as isize + delta).max(0) as usize
+}
+
+impl InputState {
+    pub(crate) fn on_action_format(
+        &mut self,
+        _: &Format,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.format(window, cx);
+    }
+
+    pub(crate) fn on_action_format_selection(
+        &mut self,
+        _: &FormatSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.format_selection(window, cx);
+    }
+
+    /// The formatting options by the tab size of the editor.
+    fn formatting_options(&self) -> FormattingOptions {
+        let tab_size = self.mode.tab_size();
+        FormattingOptions {
+            tab_size: tab_size.tab_size as u32,
+            insert_spaces: !tab_size.hard_tabs,
+            trim_trailing_whitespace: Some(self.trim_trailing_whitespace),
+            ..Default::default()
+        }
+    }
+
+    /// Format the whole document by the [`FormattingProvider`].
+    pub fn format(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(provider) = self.lsp.formatting_provider.clone() else {
+            return;
+        };
+        if self.disabled || !self.mode.is_code_editor() {
+            return;
+        }
+
+        let text = self.text.clone();
+        let task = provider.format(&text, self.formatting_options(), window, cx);
+        self.spawn_formatting(text, task, window, cx);
+    }
+
+    /// Format the selected text by the [`FormattingProvider`], or the line of the cursor
+    /// if there is no selection.
+    pub fn format_selection(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(provider) = self.lsp.formatting_provider.clone() else {
+            return;
+        };
+        if self.disabled || !self.mode.is_code_editor() {
+            return;
+        }
+
+        let range = if self.selected_range.is_empty() {
+            let row = self.text.offset_to_point(self.cursor()).row;
+            self.text.line_start_offset(row)..self.text.line_end_offset(row)
+        } else {
+            self.selected_range.start..self.selected_range.end
+        };
+
+        let text = self.text.clone();
+        let task = provider.format_range(&text, range, self.formatting_options(), window, cx);
+        self.spawn_formatting(text, task, window, cx);
+    }
+
+    fn spawn_formatting(
+        &mut self,
+        text: Rope,
+        task: Task<Result<Vec<TextEdit>>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.lsp._formatting_task = cx.spawn_in(window, async move |editor, cx| {
+            let edits = task.await?;
+
+            editor.update_in(cx, |editor, window, cx| {
+                // Skip the outdated edits, the text has changed while formatting.
+                if editor.text != text {
+                    return;
+                }
+
+                editor.apply_formatting(edits, window, cx);
+            })?;
+
+            Ok(())
+        });
+    }
+
+    /// Apply the formatting edits as one undo step, the edits are relative to the
+    /// current text, and the cursor is kept on the same position in the text.
+    pub(crate) fn apply_formatting(
+        &mut self,
+        edits: Vec<TextEdit>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if edits.is_empty() {
+            return;
+        }
+
+        let mut edits = edits
+            .into_iter()
+            .map(|edit| {
+                let start = self.text.position_to_offset(&edit.range.start);
+                let end = self.text.position_to_offset(&edit.range.end);
+                (start..end, edit.new_text)
+            })
+            .collect::<Vec<_>>();
+        edits.sort_by_key(|(range, _)| range.start);
+
+        let cursor = adjust_offset_for_edits(&self.text, self.cursor(), &edits);
+
+        // Apply from the end, so that the ranges of the edits before are not changed.
+        for (ix, (range, new_text)) in edits.iter().rev().enumerate() {
+            if ix > 0 {
+                self.history.start_grouping();
+            }
+            let range_utf16 = self.range_to_utf16(range);
+            self.replace_text_in_range_silent(Some(range_utf16), new_text, window, cx);
+        }
+        self.history.end_grouping();
+
+        self.move_to(cursor.min(self.text.len()), None, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ropey::Rope;
+
+    use super::adjust_offset_for_edits;
+
+    #[test]
+    fn test_adjust_offset_for_edits() {
+        let text = Rope::from("fn  main( ){\nlet a=1;\n}");
+        let edits = vec![
+            (2..4, " ".to_string()),
+            (9..10, "".to_string()),
+            (11..11, " ".to_string()),
+            (13..13, "    ".to_string()),
+            (18..19, " = ".to_string()),
+        ];
+
+        // Before all edits.
+        assert_eq!(adjust_offset_for_edits(&text, 1, &edits), 1);
+        // After `main`, the 2 spaces before are replaced by 1.
+        assert_eq!(adjust_offset_for_edits(&text, 8, &edits), 7);
+        // After `let`, the indent is inserted.
+        assert_eq!(adjust_offset_for_edits(&text, 16, &edits), 19);
+        // Inside the `=` edit, after `=`.
+        assert_eq!(adjust_offset_for_edits(&text, 19, &edits), 23);
+        // At the end.
+        assert_eq!(adjust_offset_for_edits(&text, 23, &edits), 28);
+
+        // A whole document edit keeps the cursor on the same token.
+        let text = Rope::from("let  a=1;");
+        let edits = vec![(0..9, "let a = 1;".to_string())];
+        assert_eq!(adjust_offset_for_edits(&text, 6, &edits), 5);
+        assert_eq!(adjust_offset_for_edits(&text, 8, &edits), 9);
+        assert_eq!(adjust_offset_for_edits(&text, 0, &edits), 0);
+        assert_eq!(adjust_offset_for_edits(&text, 9, &edits), 10);
+    }
+}
@@ -0,0 +1,206 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use gpui::{App, Context, SharedString, Task, Window};
+use instant::Duration;
+use lsp_types::{InlayHint, InlayHintLabel};
+use ropey::Rope;
+
+use crate::input::{InputState, Lsp, RopeExt};
+
+/// Inlay hint provider, e.g. the type and parameter name hints of rust-analyzer.
+///
+/// The hints are rendered as dimmed virtual text in the code editor, they are
+/// only requested for the visible range, and requested again after the text is
+/// changed or the visible range is scrolled out of the requested range.
+///
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_inlayHint
+pub trait InlayHintProvider {
+    /// Fetches the inlay hints for the specified byte range.
+    ///
+    /// textDocument/inlayHint
+    ///
+    /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_inlayHint
+    fn inlay_hints(
+        &self,
+        text: &Rope,
+        range: Range<usize>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<InlayHint>>>;
+}
+
+/// Returns the text to display for the inlay hint, include the padding.
+fn inlay_hint_label(hint: &InlayHint) -> SharedString {
+    let label = match &hint.label {
+        InlayHintLabel::String(label) => label.clone(),
+        InlayHintLabel::LabelParts(parts) => parts.iter().map(|part| part.value.as_str()).collect(),
+    };
+
+    let left = if hint.padding_left == Some(true) {
+        " "
+    } else {
+        ""
+    };
+    let right = if hint.padding_right == Some(true) {
+        " "
+    } else {
+        ""
+    };
+    format!("{}{}{}", left, label, right).into()
+}
+
+impl Lsp {
+    /// Get the inlay hints in the visible byte range.
+    ///
+    /// Returns the byte offsets and labels, sorted by the offset.
+    pub(crate) fn inlay_hints_for_range(
+        &self,
+        text: &Rope,
+        visible_range: &Range<usize>,
+    ) -> Vec<(usize, SharedString)> {
+        self.inlay_hints
+            .iter()
+            .filter_map(|(position, label)| {
+                let offset = text.position_to_offset(position);
+                if offset < visible_range.start || offset > visible_range.end {
+                    return None;
+                }
+
+                Some((offset, label.clone()))
+            })
+            .collect()
+    }
+}
+
+impl InputState {
+    /// Request the inlay hints by the [`InlayHintProvider`], if the visible byte range
+    /// is not covered by the last request.
+    ///
+    /// The last request is discarded when the text changed, see [`Lsp::update`].
+    pub(crate) fn update_inlay_hints(
+        &mut self,
+        visible_range: &Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(provider) = self.lsp.inlay_hint_provider.clone() else {
+            return;
+        };
+        if !self.mode.is_code_editor() {
+            return;
+        }
+
+        if self.lsp.inlay_hints_range.as_ref().is_some_and(|range| {
+            range.start <= visible_range.start && range.end >= visible_range.end
+        }) {
+            return;
+        }
+
+        let text = self.text.clone();
+        let range = visible_range.clone();
+        self.lsp.inlay_hints_range = Some(range.clone());
+        let editor = cx.entity();
+
+        // debounce timer 100ms
+        self.lsp._inlay_hints_task = cx.spawn_in(window, async move |_, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(100))
+                .await;
+
+            let Some(task) = cx
+                .update(|window, cx| provider.inlay_hints(&text, range, window, cx))
+                .ok()
+            else {
+                return;
+            };
+
+            let Ok(hints) = task.await else {
+                return;
+            };
+            _ = editor.update(cx, |editor, cx| {
+                // The text has been changed, the result is outdated.
+                if editor.text != text {
+                    return;
+                }
+
+                let mut inlay_hints = hints
+                    .iter()
+                    .map(|hint| (hint.position, inlay_hint_label(hint)))
+                    .collect::<Vec<_>>();
+                inlay_hints.sort_by_key(|(position, _)| *position);
+
+                if inlay_hints != editor.lsp.inlay_hints {
+                    editor.lsp.inlay_hints = inlay_hints;
+                    cx.notify();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{InlayHint, InlayHintLabel, InlayHintLabelPart, Position};
+    use ropey::Rope;
+
+    use super::inlay_hint_label;
+    use crate::input::Lsp;
+
+    fn hint(position: Position, label: InlayHintLabel) -> InlayHint {
+        InlayHint {
+            position,
+            label,
+            kind: None,
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: None,
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_inlay_hint_label() {
+        let mut type_hint = hint(Position::new(0, 5), InlayHintLabel::String(": i32".into()));
+        assert_eq!(inlay_hint_label(&type_hint).as_ref(), ": i32");
+        type_hint.padding_left = Some(true);
+        type_hint.padding_right = Some(true);
+        assert_eq!(inlay_hint_label(&type_hint).as_ref(), " : i32 ");
+
+        let parts_hint = hint(
+            Position::new(0, 0),
+            InlayHintLabel::LabelParts(vec![
+                InlayHintLabelPart {
+                    value: ": Vec<".into(),
+                    ..Default::default()
+                },
+                InlayHintLabelPart {
+                    value: "String>".into(),
+                    ..Default::default()
+                },
+            ]),
+        );
+        assert_eq!(inlay_hint_label(&parts_hint).as_ref(), ": Vec<String>");
+    }
+
+    #[test]
+    fn test_inlay_hints_for_range() {
+        let text = Rope::from("let a = 1;\nlet b = foo(a);\n");
+        let mut lsp = Lsp::default();
+        lsp.inlay_hints = vec![
+            (Position::new(0, 5), ": i32".into()),
+            (Position::new(1, 5), ": i32".into()),
+            (Position::new(1, 12), "x: ".into()),
+        ];
+
+        assert_eq!(
+            lsp.inlay_hints_for_range(&text, &(0..10)),
+            vec![(5, ": i32".into())]
+        );
+        assert_eq!(
+            lsp.inlay_hints_for_range(&text, &(11..26)),
+            vec![(16, ": i32".into()), (23, "x: ".into())]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use gpui::{App, Context, Hsla, MouseMoveEvent, SharedString, Task, Window};
 use ropey::Rope;
-use std::rc::Rc;
+use std::{ops::Range, rc::Rc};
 
 use crate::input::{InputState, RopeExt, popovers::ContextMenu};
 
@@ -9,15 +9,25 @@ mod code_actions;
 mod completions;
 mod definitions;
 mod document_colors;
+mod document_highlights;
+mod formatting;
 mod hover;
+mod inlay_hints;
+mod rename;
 mod semantic_tokens;
+mod signature_help;
 
 pub use code_actions::*;
 pub use completions::*;
 pub use definitions::*;
 pub use document_colors::*;
+pub use document_highlights::*;
+pub use formatting::*;
 pub use hover::*;
+pub use inlay_hints::*;
+pub use rename::*;
 pub use semantic_tokens::*;
+pub use signature_help::*;
 
 /// Host hook to show a document when following an LSP location
 /// (Go to Definition), modeled after the `window/showDocument` request.
@@ -45,22 +55,60 @@ pub struct Lsp {
     pub definition_provider: Option<Rc<dyn DefinitionProvider>>,
     /// The document color provider.
     pub document_color_provider: Option<Rc<dyn DocumentColorProvider>>,
+    /// The document highlight provider.
+    pub document_highlight_provider: Option<Rc<dyn DocumentHighlightProvider>>,
     /// The range semantic tokens provider.
     pub semantic_tokens_provider: Option<Rc<dyn DocumentRangeSemanticTokensProvider>>,
+    /// The full document semantic tokens provider, with delta updates.
+    ///
+    /// Takes precedence over the `semantic_tokens_provider` when both are set.
+    pub document_semantic_tokens_provider: Option<Rc<dyn SemanticTokensProvider>>,
+    /// The inlay hint provider.
+    pub inlay_hint_provider: Option<Rc<dyn InlayHintProvider>>,
+    /// The signature help provider.
+    pub signature_help_provider: Option<Rc<dyn SignatureHelpProvider>>,
+    /// The rename provider.
+    pub rename_provider: Option<Rc<dyn RenameProvider>>,
+    /// The document formatting provider.
+    pub formatting_provider: Option<Rc<dyn FormattingProvider>>,
     /// Optional host hook to show documents for Go to Definition locations,
     /// following the `window/showDocument` request (see [`ShowDocumentHandler`]).
     ///
     /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#window_showDocument
     pub show_document: Option<ShowDocumentHandler>,
+    /// The URI of the document in the editor.
+    ///
+    /// The Peek Definition view only previews the definitions in this document,
+    /// the definitions in the other documents are shown by the URI.
+    pub document_uri: Option<lsp_types::Uri>,
 
     document_colors: Vec<(lsp_types::Range, Hsla)>,
+    /// The occurrences of the symbol under the cursor, and true if it's a write access.
+    document_highlights: Vec<(Range<usize>, bool)>,
     /// Cached semantic tokens as absolute position ranges + theme token-type
     /// names. Color is resolved from the name at paint time so theme switches
     /// take effect without a refetch.
     semantic_tokens: Vec<(lsp_types::Range, SharedString)>,
+    /// The last result of the full document semantic tokens, to apply the delta edits.
+    semantic_tokens_result: lsp_types::SemanticTokens,
+    /// The inlay hints positions and labels, sorted by position.
+    inlay_hints: Vec<(lsp_types::Position, SharedString)>,
+    /// The byte range of the last inlay hints request, `None` to request again.
+    inlay_hints_range: Option<Range<usize>>,
+    /// The byte ranges of the rename preview edits.
+    rename_ranges: Vec<Range<usize>>,
+    /// The code actions at the cursor, to show the lightbulb.
+    code_action_hint: Option<CodeActionHint>,
     _hover_task: Task<Result<()>>,
+    _definition_task: Task<Result<()>>,
     _document_color_task: Task<()>,
+    _document_highlight_task: Task<()>,
     _semantic_tokens_task: Task<()>,
+    _inlay_hints_task: Task<()>,
+    _signature_help_task: Task<Result<()>>,
+    _rename_task: Task<Result<()>>,
+    _formatting_task: Task<Result<()>>,
+    _code_action_hint_task: Task<()>,
 }
 
 impl Default for Lsp {
@@ -71,13 +119,33 @@ impl Default for Lsp {
             hover_provider: None,
             definition_provider: None,
             document_color_provider: None,
+            document_highlight_provider: None,
             semantic_tokens_provider: None,
+            document_semantic_tokens_provider: None,
+            inlay_hint_provider: None,
+            signature_help_provider: None,
+            rename_provider: None,
+            formatting_provider: None,
             show_document: None,
+            document_uri: None,
             document_colors: vec![],
+            document_highlights: vec![],
             semantic_tokens: vec![],
+            semantic_tokens_result: lsp_types::SemanticTokens::default(),
+            inlay_hints: vec![],
+            inlay_hints_range: None,
+            rename_ranges: vec![],
+            code_action_hint: None,
             _hover_task: Task::ready(Ok(())),
+            _definition_task: Task::ready(Ok(())),
             _document_color_task: Task::ready(()),
+            _document_highlight_task: Task::ready(()),
             _semantic_tokens_task: Task::ready(()),
+            _inlay_hints_task: Task::ready(()),
+            _signature_help_task: Task::ready(Ok(())),
+            _rename_task: Task::ready(Ok(())),
+            _formatting_task: Task::ready(Ok(())),
+            _code_action_hint_task: Task::ready(()),
         }
     }
 }
@@ -90,17 +158,33 @@ impl Lsp {
         window: &mut Window,
         cx: &mut Context<InputState>,
     ) {
+        self.document_highlights.clear();
+        self.code_action_hint = None;
         self.update_document_colors(text, window, cx);
         self.update_semantic_tokens(text, window, cx);
+        // The inlay hints are requested for the visible range on the next paint.
+        self.inlay_hints_range = None;
     }
 
     /// Reset all LSP states.
     pub(crate) fn reset(&mut self) {
         self.document_colors.clear();
+        self.document_highlights.clear();
         self.semantic_tokens.clear();
+        self.semantic_tokens_result = lsp_types::SemanticTokens::default();
+        self.inlay_hints.clear();
+        self.inlay_hints_range = None;
         self._hover_task = Task::ready(Ok(()));
+        self._definition_task = Task::ready(Ok(()));
         self._document_color_task = Task::ready(());
+        self._document_highlight_task = Task::ready(());
         self._semantic_tokens_task = Task::ready(());
+        self._inlay_hints_task = Task::ready(());
+        self._signature_help_task = Task::ready(Ok(()));
+        self._rename_task = Task::ready(Ok(()));
+        self._formatting_task = Task::ready(Ok(()));
+        self.code_action_hint = None;
+        self._code_action_hint_task = Task::ready(());
     }
 }
 
@@ -0,0 +1,206 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use gpui::{App, Context, SharedString, Task, Window};
+use lsp_types::{PrepareRenameResponse, TextEdit};
+use ropey::Rope;
+
+use crate::input::{InputState, Lsp, Rename, RopeExt, popovers::RenameEditor};
+
+/// Rename provider, to rename the symbol under the cursor.
+///
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_rename
+pub trait RenameProvider {
+    /// Checks if the symbol at the offset can be renamed, and returns the range to rename.
+    ///
+    /// Return `None` if the symbol can not be renamed. The default is the word at the offset.
+    ///
+    /// textDocument/prepareRename
+    ///
+    /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_prepareRename
+    fn prepare_rename(
+        &self,
+        _text: &Rope,
+        _offset: usize,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Result<Option<PrepareRenameResponse>>> {
+        Task::ready(Ok(Some(PrepareRenameResponse::DefaultBehavior {
+            default_behavior: true,
+        })))
+    }
+
+    /// Returns the edits to rename the symbol at the offset to the `new_name`.
+    ///
+    /// This is also called while typing the new name to preview the edits. Only the
+    /// edits of the current document should be returned, e.g. the edits of the current
+    /// document URI in the `WorkspaceEdit`.
+    ///
+    /// textDocument/rename
+    ///
+    /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_rename
+    fn rename(
+        &self,
+        text: &Rope,
+        offset: usize,
+        new_name: &str,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<TextEdit>>>;
+}
+
+/// Returns the byte range and the placeholder to rename by the prepare rename response.
+fn prepare_rename_range(
+    text: &Rope,
+    offset: usize,
+    response: PrepareRenameResponse,
+) -> Option<(Range<usize>, SharedString)> {
+    let range = match &response {
+        PrepareRenameResponse::Range(range)
+        | PrepareRenameResponse::RangeWithPlaceholder { range, .. } => {
+            text.position_to_offset(&range.start)..text.position_to_offset(&range.end)
+        }
+        PrepareRenameResponse::DefaultBehavior { .. } => text.word_range(offset)?,
+    };
+
+    let placeholder = match response {
+        PrepareRenameResponse::RangeWithPlaceholder { placeholder, .. } => placeholder,
+        _ => text.slice(range.clone()).to_string(),
+    };
+
+    Some((range, placeholder.into()))
+}
+
+impl Lsp {
+    /// Get the ranges of the rename preview edits in the byte range.
+    pub(crate) fn rename_ranges_for_range(&self, range: &Range<usize>) -> Vec<Range<usize>> {
+        self.rename_ranges
+            .iter()
+            .filter(|r| r.start >= range.start && r.end <= range.end)
+            .cloned()
+            .collect()
+    }
+}
+
+impl InputState {
+    pub(crate) fn on_action_rename(
+        &mut self,
+        _: &Rename,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.start_rename(window, cx);
+    }
+
+    /// Open the inline rename editor for the symbol at the cursor, by the [`RenameProvider`].
+    pub fn start_rename(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(provider) = self.lsp.rename_provider.clone() else {
+            return;
+        };
+        if self.disabled || !self.mode.is_code_editor() {
+            return;
+        }
+
+        let offset = self.cursor();
+        let task = provider.prepare_rename(&self.text, offset, window, cx);
+        self.lsp._rename_task = cx.spawn_in(window, async move |editor, cx| {
+            let response = task.await?;
+
+            editor.update_in(cx, |editor, window, cx| {
+                let Some((range, placeholder)) = response
+                    .and_then(|response| prepare_rename_range(&editor.text, offset, response))
+                else {
+                    return;
+                };
+
+                editor.clear_hover_state(cx);
+                editor.hide_context_menu(cx);
+                let rename_editor =
+                    RenameEditor::new(cx.entity(), offset, range, placeholder, window, cx);
+                rename_editor.read(cx).focus(window, cx);
+                editor.rename_editor = Some(rename_editor);
+                cx.notify();
+            })?;
+
+            Ok(())
+        });
+    }
+
+    /// Set the ranges of the edits to preview, they are highlighted like the occurrences.
+    pub(crate) fn set_rename_preview(&mut self, edits: &[TextEdit], cx: &mut Context<Self>) {
+        self.lsp.rename_ranges = edits
+            .iter()
+            .map(|edit| {
+                self.text.position_to_offset(&edit.range.start)
+                    ..self.text.position_to_offset(&edit.range.end)
+            })
+            .collect();
+        cx.notify();
+    }
+
+    /// Apply the rename edits, the edits are relative to the text before the rename.
+    pub(crate) fn apply_rename(
+        &mut self,
+        edits: Vec<TextEdit>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut edits = edits;
+        // Apply from the end, so that the positions of the edits before are not changed.
+        edits.sort_by(|a, b| b.range.start.cmp(&a.range.start));
+        self.apply_lsp_edits(&edits, window, cx);
+    }
+
+    /// Close the rename editor, and focus back to the editor.
+    pub(crate) fn close_rename(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.lsp.rename_ranges.clear();
+        self.lsp._rename_task = Task::ready(Ok(()));
+        if self.rename_editor.take().is_some() {
+            self.focus(window, cx);
+            cx.notify();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{Position, PrepareRenameResponse, Range};
+    use ropey::Rope;
+
+    use super::prepare_rename_range;
+
+    #[test]
+    fn test_prepare_rename_range() {
+        let text = Rope::from("let foo = 1;\nfoo + bar");
+
+        let response = PrepareRenameResponse::DefaultBehavior {
+            default_behavior: true,
+        };
+        assert_eq!(
+            prepare_rename_range(&text, 14, response),
+            Some((13..16, "foo".into()))
+        );
+
+        let response =
+            PrepareRenameResponse::Range(Range::new(Position::new(1, 6), Position::new(1, 9)));
+        assert_eq!(
+            prepare_rename_range(&text, 20, response),
+            Some((19..22, "bar".into()))
+        );
+
+        let response = PrepareRenameResponse::RangeWithPlaceholder {
+            range: Range::new(Position::new(0, 4), Position::new(0, 7)),
+            placeholder: "Foo".into(),
+        };
+        assert_eq!(
+            prepare_rename_range(&text, 5, response),
+            Some((4..7, "Foo".into()))
+        );
+
+        // No word at the offset.
+        let response = PrepareRenameResponse::DefaultBehavior {
+            default_behavior: true,
+        };
+        assert_eq!(prepare_rename_range(&text, 9, response), None);
+    }
+}
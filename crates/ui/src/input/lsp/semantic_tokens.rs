@@ -3,7 +3,10 @@ use std::ops::Range;
 use anyhow::Result;
 use gpui::{App, Context, HighlightStyle, SharedString, Task, Window};
 use instant::Duration;
-use lsp_types::{Position, SemanticTokens, SemanticTokensLegend};
+use lsp_types::{
+    Position, SemanticToken, SemanticTokens, SemanticTokensEdit, SemanticTokensFullDeltaResult,
+    SemanticTokensLegend,
+};
 use ropey::Rope;
 
 use crate::highlighter::HighlightTheme;
@@ -55,6 +58,55 @@ pub trait DocumentRangeSemanticTokensProvider {
     ) -> Task<Result<SemanticTokens>>;
 }
 
+/// A provider of semantic highlighting tokens for the whole document, with
+/// delta updates for large documents.
+///
+/// This is the editor counterpart of the LSP `textDocument/semanticTokens/full`
+/// and `textDocument/semanticTokens/full/delta` requests (and Monaco Editor's
+/// `DocumentSemanticTokensProvider`). It is installed on
+/// `Lsp::document_semantic_tokens_provider`, and takes precedence over the
+/// [`DocumentRangeSemanticTokensProvider`] when both are set. The tokens are
+/// themed in the same way, see [`DocumentRangeSemanticTokensProvider`].
+///
+/// After the first full request, the `result_id` of the last result is passed
+/// to [`semantic_tokens_delta`](Self::semantic_tokens_delta), so the server
+/// can return only the edits of the token data instead of all the tokens.
+pub trait SemanticTokensProvider {
+    /// The legend naming the numeric `token_type` field of the tokens.
+    fn legend(&self) -> SemanticTokensLegend;
+
+    /// Fetches semantic tokens for the whole document.
+    ///
+    /// textDocument/semanticTokens/full
+    ///
+    /// <https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#semanticTokens_fullRequest>
+    fn semantic_tokens(
+        &self,
+        text: &Rope,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<SemanticTokens>>;
+
+    /// Fetches the edits of the semantic tokens since the result of `previous_result_id`.
+    ///
+    /// textDocument/semanticTokens/full/delta
+    ///
+    /// Default is to fetch all the tokens by [`semantic_tokens`](Self::semantic_tokens).
+    ///
+    /// <https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#semanticTokens_deltaRequest>
+    fn semantic_tokens_delta(
+        &self,
+        text: &Rope,
+        previous_result_id: &str,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<SemanticTokensFullDeltaResult>> {
+        _ = previous_result_id;
+        let task = self.semantic_tokens(text, window, cx);
+        cx.background_spawn(async move { task.await.map(SemanticTokensFullDeltaResult::Tokens) })
+    }
+}
+
 impl Lsp {
     /// Get semantic token styles that intersect with the visible byte range,
     /// resolving each cached token's type name against `theme`.
@@ -112,6 +164,11 @@ impl Lsp {
         window: &mut Window,
         cx: &mut Context<InputState>,
     ) {
+        if self.document_semantic_tokens_provider.is_some() {
+            self.update_document_semantic_tokens(text, window, cx);
+            return;
+        }
+
         let Some(provider) = self.semantic_tokens_provider.as_ref() else {
             return;
         };
@@ -150,6 +207,114 @@ impl Lsp {
     }
 }
 
+impl Lsp {
+    /// Fetch the semantic tokens by the [`SemanticTokensProvider`], request the
+    /// delta if there is a previous result.
+    fn update_document_semantic_tokens(
+        &mut self,
+        text: &Rope,
+        window: &mut Window,
+        cx: &mut Context<InputState>,
+    ) {
+        let Some(provider) = self.document_semantic_tokens_provider.clone() else {
+            return;
+        };
+
+        let legend = provider.legend();
+        let text = text.clone();
+        let mut previous_result_id = self.semantic_tokens_result.result_id.clone();
+        let input_state = cx.entity();
+
+        // debounce timer 100ms
+        self._semantic_tokens_task = cx.spawn_in(window, async move |_, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(100))
+                .await;
+
+            loop {
+                let task_result = cx
+                    .update(|window, cx| match previous_result_id.as_deref() {
+                        Some(result_id) => {
+                            provider.semantic_tokens_delta(&text, result_id, window, cx)
+                        }
+                        None => {
+                            let task = provider.semantic_tokens(&text, window, cx);
+                            cx.background_spawn(async move {
+                                task.await.map(SemanticTokensFullDeltaResult::Tokens)
+                            })
+                        }
+                    })
+                    .ok();
+
+                let Some(task) = task_result else {
+                    return;
+                };
+                let Ok(result) = task.await else {
+                    return;
+                };
+
+                let Ok(outdated) = input_state.update(cx, |input_state, cx| {
+                    let lsp = &mut input_state.lsp;
+                    match result {
+                        SemanticTokensFullDeltaResult::Tokens(tokens) => {
+                            lsp.semantic_tokens_result = tokens;
+                        }
+                        SemanticTokensFullDeltaResult::TokensDelta(delta) => {
+                            // The delta is based on an outdated result.
+                            if lsp.semantic_tokens_result.result_id != previous_result_id {
+                                lsp.semantic_tokens_result = SemanticTokens::default();
+                                return true;
+                            }
+
+                            apply_semantic_tokens_edits(
+                                &mut lsp.semantic_tokens_result.data,
+                                delta.edits,
+                            );
+                            lsp.semantic_tokens_result.result_id = delta.result_id;
+                        }
+                        SemanticTokensFullDeltaResult::PartialTokensDelta { edits } => {
+                            apply_semantic_tokens_edits(
+                                &mut lsp.semantic_tokens_result.data,
+                                edits,
+                            );
+                        }
+                    }
+
+                    let decoded = decode_semantic_tokens(&lsp.semantic_tokens_result, &legend);
+                    if decoded != lsp.semantic_tokens {
+                        lsp.semantic_tokens = decoded;
+                        cx.notify();
+                    }
+                    false
+                }) else {
+                    return;
+                };
+
+                if !outdated {
+                    return;
+                }
+
+                // Request all the tokens again.
+                previous_result_id = None;
+            }
+        });
+    }
+}
+
+/// Apply the `edits` of `textDocument/semanticTokens/full/delta` to the token `data`.
+///
+/// The `start` and `delete_count` of the edits are the indexes of the integers
+/// in the LSP encoding, each token is 5 integers.
+fn apply_semantic_tokens_edits(data: &mut Vec<SemanticToken>, mut edits: Vec<SemanticTokensEdit>) {
+    // Apply from the end, so that the indexes of the earlier edits are not changed.
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.start));
+    for edit in edits {
+        let start = (edit.start as usize / 5).min(data.len());
+        let end = (start + edit.delete_count as usize / 5).min(data.len());
+        data.splice(start..end, edit.data.unwrap_or_default());
+    }
+}
+
 /// Decode the LSP delta-encoding of `tokens` into absolute
 /// (position-range, type-name) pairs, sorted by start position.
 ///
@@ -256,6 +421,39 @@ mod tests {
         assert!(decode_semantic_tokens(&tokens, &legend()).is_empty());
     }
 
+    #[test]
+    fn test_apply_semantic_tokens_edits() {
+        let token = |delta_start: u32| SemanticToken {
+            delta_line: 0,
+            delta_start,
+            length: 1,
+            token_type: 0,
+            token_modifiers_bitset: 0,
+        };
+
+        let mut data = vec![token(0), token(1), token(2), token(3)];
+        apply_semantic_tokens_edits(
+            &mut data,
+            vec![
+                // Replace the 2nd token by 2 tokens.
+                SemanticTokensEdit {
+                    start: 5,
+                    delete_count: 5,
+                    data: Some(vec![token(10), token(11)]),
+                },
+                // Delete the last token.
+                SemanticTokensEdit {
+                    start: 15,
+                    delete_count: 5,
+                    data: None,
+                },
+            ],
+        );
+
+        let starts = data.iter().map(|t| t.delta_start).collect::<Vec<_>>();
+        assert_eq!(starts, vec![0, 10, 11, 2]);
+    }
+
     #[test]
     fn test_for_range_resolves_and_windows() {
         let text = Rope::from("SELECT * FROM users\n-- a comment line\n");
@@ -0,0 +1,212 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use gpui::{App, Context, Task, Window};
+use lsp_types::{
+    ParameterLabel, SignatureHelp, SignatureHelpContext, SignatureHelpTriggerKind,
+    SignatureInformation,
+};
+use ropey::Rope;
+
+use crate::input::{InputState, popovers::SignatureHelpPopover};
+
+/// Signature help provider, to show the signatures of the function call under the cursor.
+///
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_signatureHelp
+pub trait SignatureHelpProvider {
+    /// Fetches the signature help at the byte offset of the cursor.
+    ///
+    /// Return `None` if the cursor is not in a function call, the popup will be hidden.
+    ///
+    /// textDocument/signatureHelp
+    ///
+    /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_signatureHelp
+    fn signature_help(
+        &self,
+        text: &Rope,
+        offset: usize,
+        context: SignatureHelpContext,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Option<SignatureHelp>>>;
+
+    /// Determines if the signature help should be triggered by the typed text.
+    ///
+    /// Default is `(` and `,`.
+    fn is_signature_help_trigger(&self, new_text: &str) -> bool {
+        matches!(new_text, "(" | ",")
+    }
+}
+
+/// Returns the byte range of the active parameter in the signature label.
+pub(crate) fn active_parameter_range(
+    signature: &SignatureInformation,
+    active_parameter: Option<u32>,
+) -> Option<Range<usize>> {
+    let ix = signature.active_parameter.or(active_parameter)? as usize;
+    let parameter = signature.parameters.as_ref()?.get(ix)?;
+    let label = &signature.label;
+
+    match &parameter.label {
+        ParameterLabel::Simple(name) => {
+            let start = label.find(name.as_str())?;
+            Some(start..start + name.len())
+        }
+        ParameterLabel::LabelOffsets([start, end]) => {
+            // The offsets are in UTF-16 code units.
+            let mut utf16_ix = 0;
+            let mut range = None::<Range<usize>>;
+            for (byte_ix, c) in label.char_indices() {
+                if utf16_ix == *start as usize {
+                    range = Some(byte_ix..label.len());
+                }
+                if utf16_ix == *end as usize {
+                    range = range.map(|range| range.start..byte_ix);
+                    break;
+                }
+                utf16_ix += c.len_utf16();
+            }
+            range.filter(|range| !range.is_empty())
+        }
+    }
+}
+
+impl InputState {
+    /// Request the signature help, if the typed text is a trigger character, or the
+    /// signature help popup is open (to update the active parameter).
+    pub(crate) fn handle_signature_help_trigger(
+        &mut self,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(provider) = self.lsp.signature_help_provider.clone() else {
+            return;
+        };
+
+        let active_signature_help = self
+            .signature_help_popover
+            .as_ref()
+            .map(|popover| popover.read(cx).signature_help.clone());
+        let is_trigger = provider.is_signature_help_trigger(new_text);
+        if !is_trigger && active_signature_help.is_none() {
+            return;
+        }
+
+        let context = SignatureHelpContext {
+            trigger_kind: if is_trigger {
+                SignatureHelpTriggerKind::TRIGGER_CHARACTER
+            } else {
+                SignatureHelpTriggerKind::CONTENT_CHANGE
+            },
+            trigger_character: is_trigger.then(|| new_text.to_string()),
+            is_retrigger: active_signature_help.is_some(),
+            active_signature_help,
+        };
+
+        let offset = self.cursor();
+        let task = provider.signature_help(&self.text, offset, context, window, cx);
+        self.lsp._signature_help_task = cx.spawn_in(window, async move |editor, cx| {
+            let result = task.await?;
+
+            editor.update_in(cx, |editor, window, cx| {
+                if !editor.focus_handle.is_focused(window) {
+                    return;
+                }
+
+                match result.filter(|help| !help.signatures.is_empty()) {
+                    Some(help) => match editor.signature_help_popover.as_ref() {
+                        Some(popover) => popover.update(cx, |popover, cx| {
+                            popover.update_signature_help(help, cx);
+                        }),
+                        None => {
+                            editor.signature_help_popover =
+                                Some(SignatureHelpPopover::new(cx.entity(), offset, help, cx));
+                        }
+                    },
+                    None => editor.signature_help_popover = None,
+                }
+                cx.notify();
+            })?;
+
+            Ok(())
+        });
+    }
+
+    /// Hide the signature help popup, returns true if it was open.
+    pub(crate) fn hide_signature_help(&mut self, cx: &mut Context<Self>) -> bool {
+        self.lsp._signature_help_task = Task::ready(Ok(()));
+        if self.signature_help_popover.take().is_some() {
+            cx.notify();
+            return true;
+        }
+
+        false
+    }
+
+    /// Select the previous or next signature of the overloads in the signature help popup.
+    ///
+    /// Returns true if handled, that is the popup has more than one signature.
+    pub(crate) fn select_signature(&mut self, delta: isize, cx: &mut Context<Self>) -> bool {
+        let Some(popover) = self.signature_help_popover.as_ref() else {
+            return false;
+        };
+
+        popover.update(cx, |popover, cx| popover.select_signature(delta, cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{ParameterInformation, ParameterLabel, SignatureInformation};
+
+    use super::active_parameter_range;
+
+    fn signature(label: &str, parameters: Vec<ParameterLabel>) -> SignatureInformation {
+        SignatureInformation {
+            label: label.to_string(),
+            documentation: None,
+            parameters: Some(
+                parameters
+                    .into_iter()
+                    .map(|label| ParameterInformation {
+                        label,
+                        documentation: None,
+                    })
+                    .collect(),
+            ),
+            active_parameter: None,
+        }
+    }
+
+    #[test]
+    fn test_active_parameter_range() {
+        let sig = signature(
+            "fn add(a: i32, b: i32) -> i32",
+            vec![
+                ParameterLabel::Simple("a: i32".into()),
+                ParameterLabel::Simple("b: i32".into()),
+            ],
+        );
+        assert_eq!(active_parameter_range(&sig, Some(0)), Some(7..13));
+        assert_eq!(active_parameter_range(&sig, Some(1)), Some(15..21));
+        assert_eq!(active_parameter_range(&sig, Some(2)), None);
+        assert_eq!(active_parameter_range(&sig, None), None);
+
+        // The active parameter of the signature takes precedence.
+        let mut sig = sig;
+        sig.active_parameter = Some(1);
+        assert_eq!(active_parameter_range(&sig, Some(0)), Some(15..21));
+
+        // The offsets are in UTF-16.
+        let sig = signature(
+            "fn 你好(名字: &str, n: u8)",
+            vec![
+                ParameterLabel::LabelOffsets([6, 14]),
+                ParameterLabel::LabelOffsets([16, 21]),
+            ],
+        );
+        assert_eq!(active_parameter_range(&sig, Some(0)), Some(10..22));
+        assert_eq!(active_parameter_range(&sig, Some(1)), Some(24..29));
+    }
+}
@@ -1,7 +1,32 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, rc::Rc};
 
 use gpui::SharedString;
 
+/// A custom mask to format the input text, see [`MaskPattern::custom`].
+///
+/// Use this for the formats can't be described by a pattern, e.g. the credit card number
+/// has a different grouping by the card type.
+pub trait MaskProvider {
+    /// Format the text, the text may be partially formatted while typing,
+    /// e.g. `4111 11112` after a `2` is typed.
+    fn mask(&self, text: &str) -> SharedString;
+
+    /// Extract the raw value from the masked text.
+    fn unmask(&self, mask_text: &str) -> String;
+
+    /// Check if the text is valid, the invalid input is rejected.
+    ///
+    /// Like [`Self::mask`], the text may be partially formatted. Default is always true.
+    fn is_valid(&self, _text: &str) -> bool {
+        true
+    }
+
+    /// The placeholder of the input, default is None.
+    fn placeholder(&self) -> Option<String> {
+        None
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum MaskToken {
     /// 0 Digit, equivalent to `[0]`
@@ -88,6 +113,8 @@ pub enum MaskPattern {
         /// Number of fraction digits, e.g. 2 for 123.45
         fraction: Option<usize>,
     },
+    /// A custom mask by [`MaskProvider`].
+    Custom(Rc<dyn MaskProvider>),
 }
 
 impl From<&str> for MaskPattern {
@@ -130,11 +157,17 @@ impl MaskPattern {
         }
     }
 
+    /// Create a custom mask pattern by a [`MaskProvider`].
+    pub fn custom(provider: impl MaskProvider + 'static) -> Self {
+        Self::Custom(Rc::new(provider))
+    }
+
     #[allow(unused)]
     fn tokens(&self) -> Option<&Vec<MaskToken>> {
         match self {
             Self::Pattern { tokens, .. } => Some(tokens),
             Self::Number { .. } => None,
+            Self::Custom(_) => None,
             Self::None => None,
         }
     }
@@ -153,6 +186,7 @@ impl MaskPattern {
                 Some(tokens.iter().map(|token| token.placeholder()).collect())
             }
             Self::Number { .. } => None,
+            Self::Custom(provider) => provider.placeholder(),
             Self::None => None,
         }
     }
@@ -162,6 +196,7 @@ impl MaskPattern {
         match self {
             Self::Pattern { tokens, .. } => tokens.is_empty(),
             Self::Number { .. } => false,
+            Self::Custom(_) => false,
             Self::None => true,
         }
     }
@@ -239,6 +274,7 @@ impl MaskPattern {
 
                 true
             }
+            Self::Custom(provider) => provider.is_valid(mask_text),
             Self::None => true,
         }
     }
@@ -269,6 +305,7 @@ impl MaskPattern {
                 false
             }
             Self::Number { .. } => true,
+            Self::Custom(_) => true,
             Self::None => true,
         }
     }
@@ -366,6 +403,7 @@ impl MaskPattern {
                 }
                 result.into()
             }
+            Self::Custom(provider) => provider.mask(text),
             Self::None => text.to_owned().into(),
         }
     }
@@ -406,6 +444,7 @@ impl MaskPattern {
                 }
                 result
             }
+            Self::Custom(provider) => provider.unmask(mask_text),
             Self::None => mask_text.to_owned(),
         }
     }
@@ -697,6 +736,71 @@ mod tests {
         assert_eq!(mask.mask("-.5"), "-.5");
     }
 
+    #[test]
+    fn test_custom_mask() {
+        use gpui::SharedString;
+
+        use crate::input::MaskProvider;
+
+        /// Credit card number, American Express is grouped by 4-6-5, others by 4-4-4-4.
+        struct CreditCardMask;
+        impl CreditCardMask {
+            fn groups(digits: &str) -> &'static [usize] {
+                if digits.starts_with("34") || digits.starts_with("37") {
+                    &[4, 6, 5]
+                } else {
+                    &[4, 4, 4, 4]
+                }
+            }
+        }
+
+        impl MaskProvider for CreditCardMask {
+            fn mask(&self, text: &str) -> SharedString {
+                let digits = self.unmask(text);
+                let mut result = String::new();
+                let mut chars = digits.chars();
+                for (ix, len) in Self::groups(&digits).iter().enumerate() {
+                    let group: String = chars.by_ref().take(*len).collect();
+                    if group.is_empty() {
+                        break;
+                    }
+                    if ix > 0 {
+                        result.push(' ');
+                    }
+                    result.push_str(&group);
+                }
+                result.into()
+            }
+
+            fn unmask(&self, mask_text: &str) -> String {
+                mask_text.chars().filter(|ch| *ch != ' ').collect()
+            }
+
+            fn is_valid(&self, text: &str) -> bool {
+                let digits = self.unmask(text);
+                digits.chars().all(|ch| ch.is_ascii_digit())
+                    && digits.len() <= Self::groups(&digits).iter().sum()
+            }
+
+            fn placeholder(&self) -> Option<String> {
+                Some("Card number".into())
+            }
+        }
+
+        let mask = MaskPattern::custom(CreditCardMask);
+        assert_eq!(mask.is_none(), false);
+        assert_eq!(mask.placeholder(), Some("Card number".into()));
+        assert_eq!(mask.mask("411111"), "4111 11");
+        assert_eq!(mask.mask("4111 11112"), "4111 1111 2");
+        assert_eq!(mask.mask("4111111111111111"), "4111 1111 1111 1111");
+        assert_eq!(mask.mask("378282246310005"), "3782 822463 10005");
+        assert_eq!(mask.unmask("3782 822463 10005"), "378282246310005");
+        assert_eq!(mask.is_valid("4111 1111 1111 1111"), true);
+        assert_eq!(mask.is_valid("4111 1111 1111 11112"), false);
+        assert_eq!(mask.is_valid("3782 822463 100051"), false);
+        assert_eq!(mask.is_valid("4111 a"), false);
+    }
+
     #[test]
     fn test_normalize_number_input() {
         use std::borrow::Cow;
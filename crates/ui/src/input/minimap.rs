@@ -0,0 +1,403 @@
+use gpui::{
+    App, Bounds, Element, ElementId, Entity, GlobalElementId, Hitbox, HitboxBehavior, Hsla,
+    IntoElement, LayoutId, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
+    Pixels, Style, Window, fill, point, px, relative, size,
+};
+
+use crate::{
+    ActiveTheme as _,
+    input::{DisplayPoint, InputState, RopeExt as _},
+};
+
+/// The width of the minimap column.
+pub(super) const MINIMAP_WIDTH: Pixels = px(100.);
+/// The height of a display row in the minimap.
+const MINIMAP_ROW_HEIGHT: Pixels = px(2.);
+/// The width of a character in the minimap.
+const MINIMAP_CHAR_WIDTH: Pixels = px(1.);
+/// The width of the diagnostic marks at the right edge of the minimap.
+const DIAGNOSTIC_MARK_WIDTH: Pixels = px(4.);
+
+/// The scroll geometry of the minimap, shared by layout and mouse handling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MinimapGeometry {
+    /// The total height of the minimap content, `rows * MINIMAP_ROW_HEIGHT`.
+    content_height: Pixels,
+    /// The minimap scroll offset, the content scrolls along with the editor
+    /// when it's taller than the minimap.
+    scroll_top: Pixels,
+    /// The viewport indicator (slider) top, relative to the minimap bounds.
+    slider_top: Pixels,
+    slider_height: Pixels,
+    /// The max vertical scroll offset of the editor.
+    max_editor_scroll: Pixels,
+}
+
+impl MinimapGeometry {
+    fn new(
+        row_count: usize,
+        minimap_height: Pixels,
+        line_height: Pixels,
+        viewport_height: Pixels,
+        editor_scroll_top: Pixels,
+        editor_scroll_height: Pixels,
+    ) -> Self {
+        let content_height = MINIMAP_ROW_HEIGHT * row_count as f32;
+        let max_editor_scroll = (editor_scroll_height - viewport_height).max(px(0.));
+        let scale = MINIMAP_ROW_HEIGHT / line_height;
+
+        let scroll_top = if content_height > minimap_height && max_editor_scroll > px(0.) {
+            (content_height - minimap_height)
+                * (editor_scroll_top / max_editor_scroll).clamp(0., 1.)
+        } else {
+            px(0.)
+        };
+
+        Self {
+            content_height,
+            scroll_top,
+            slider_top: editor_scroll_top * scale - scroll_top,
+            slider_height: (viewport_height * scale).max(MINIMAP_ROW_HEIGHT * 2.),
+            max_editor_scroll,
+        }
+    }
+
+    /// Returns the editor scroll top to center the display row at `y` (relative to the minimap).
+    fn scroll_top_for_click(
+        &self,
+        y: Pixels,
+        line_height: Pixels,
+        viewport_height: Pixels,
+    ) -> Pixels {
+        let row = ((y + self.scroll_top) / MINIMAP_ROW_HEIGHT).floor();
+        (line_height * row - viewport_height / 2.).clamp(px(0.), self.max_editor_scroll)
+    }
+
+    /// Returns the editor scroll top to move the slider top to `slider_top`.
+    fn scroll_top_for_drag(&self, slider_top: Pixels, minimap_height: Pixels) -> Pixels {
+        let track = (self.content_height.min(minimap_height) - self.slider_height).max(px(1.));
+        self.max_editor_scroll * (slider_top / track).clamp(0., 1.)
+    }
+}
+
+/// A scaled-down preview of the code editor with a viewport indicator and diagnostics marks.
+///
+/// Click to scroll the clicked line to the center, or drag the viewport indicator to scroll.
+pub(super) struct EditorMinimap {
+    state: Entity<InputState>,
+}
+
+impl EditorMinimap {
+    pub(super) fn new(state: Entity<InputState>) -> Self {
+        Self { state }
+    }
+}
+
+pub(super) struct MinimapPrepaintState {
+    hitbox: Hitbox,
+    geometry: MinimapGeometry,
+    line_height: Pixels,
+    viewport_height: Pixels,
+    quads: Vec<PaintQuad>,
+}
+
+impl IntoElement for EditorMinimap {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for EditorMinimap {
+    type RequestLayoutState = ();
+    type PrepaintState = Option<MinimapPrepaintState>;
+
+    fn id(&self) -> Option<ElementId> {
+        Some("editor-minimap".into())
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&gpui::InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.flex_shrink = 0.;
+        style.size.width = MINIMAP_WIDTH.into();
+        style.size.height = relative(1.).into();
+
+        (window.request_layout(style, [], cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let state = self.state.read(cx);
+        let line_height = state.last_layout.as_ref()?.line_height;
+        let viewport_height = state.input_bounds.size.height;
+        let display_map = &state.display_map;
+        let row_count = display_map.display_row_count();
+
+        let geometry = MinimapGeometry::new(
+            row_count,
+            bounds.size.height,
+            line_height,
+            viewport_height,
+            -state.scroll_handle.offset().y,
+            state.scroll_size.height,
+        );
+
+        let text_color = cx.theme().foreground.opacity(0.35);
+        let max_cols = ((MINIMAP_WIDTH - DIAGNOSTIC_MARK_WIDTH) / MINIMAP_CHAR_WIDTH) as usize;
+        let tab_size = state.mode.tab_size().tab_size;
+
+        let first_row = (geometry.scroll_top / MINIMAP_ROW_HEIGHT).floor() as usize;
+        let last_row = (first_row + (bounds.size.height / MINIMAP_ROW_HEIGHT).ceil() as usize + 1)
+            .min(row_count);
+
+        let mut quads = vec![];
+        let row_top =
+            |row: usize| bounds.origin.y + MINIMAP_ROW_HEIGHT * row as f32 - geometry.scroll_top;
+
+        for row in first_row..last_row {
+            let start = display_map.display_pos_to_buffer_pos(DisplayPoint::new(row, 0));
+            let line = state.text.slice_line(start.line);
+            let end_col = if row + 1 < row_count {
+                let next = display_map.display_pos_to_buffer_pos(DisplayPoint::new(row + 1, 0));
+                if next.line == start.line {
+                    next.col
+                } else {
+                    line.len()
+                }
+            } else {
+                line.len()
+            };
+            let segment = line.slice(start.col.min(end_col)..end_col);
+
+            // Paint a bar for each run of non-whitespace characters.
+            let y = row_top(row);
+            let mut col = 0;
+            let mut run_start: Option<usize> = None;
+            for c in segment.chars() {
+                if col >= max_cols {
+                    break;
+                }
+
+                if c.is_whitespace() {
+                    if let Some(run_start) = run_start.take() {
+                        quads.push(minimap_bar(bounds, y, run_start..col, text_color));
+                    }
+                    col += if c == '\t' { tab_size } else { 1 };
+                } else {
+                    run_start.get_or_insert(col);
+                    col += 1;
+                }
+            }
+            if let Some(run_start) = run_start {
+                quads.push(minimap_bar(
+                    bounds,
+                    y,
+                    run_start..col.min(max_cols),
+                    text_color,
+                ));
+            }
+        }
+
+        if let Some(diagnostics) = state.mode.diagnostics() {
+            for entry in diagnostics.iter() {
+                let line = state.text.offset_to_point(entry.range.start).row;
+                let row = display_map.buffer_line_to_display_row(line);
+                if row < first_row || row >= last_row {
+                    continue;
+                }
+
+                quads.push(fill(
+                    Bounds::new(
+                        point(
+                            bounds.right() - DIAGNOSTIC_MARK_WIDTH,
+                            row_top(row) - MINIMAP_ROW_HEIGHT / 2.,
+                        ),
+                        size(DIAGNOSTIC_MARK_WIDTH, MINIMAP_ROW_HEIGHT * 2.),
+                    ),
+                    entry.severity.fg(cx),
+                ));
+            }
+        }
+
+        quads.push(fill(
+            Bounds::new(
+                point(bounds.origin.x, bounds.origin.y + geometry.slider_top),
+                size(bounds.size.width, geometry.slider_height),
+            ),
+            cx.theme().scrollbar_thumb.opacity(0.3),
+        ));
+
+        let hitbox = window.insert_hitbox(bounds, HitboxBehavior::Normal);
+        Some(MinimapPrepaintState {
+            hitbox,
+            geometry,
+            line_height,
+            viewport_height,
+            quads,
+        })
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        _: &mut App,
+    ) {
+        let Some(prepaint) = prepaint.take() else {
+            return;
+        };
+
+        window.with_content_mask(Some(gpui::ContentMask { bounds }), |window| {
+            for quad in prepaint.quads {
+                window.paint_quad(quad);
+            }
+        });
+
+        let MinimapPrepaintState {
+            hitbox,
+            geometry,
+            line_height,
+            viewport_height,
+            ..
+        } = prepaint;
+
+        window.on_mouse_event({
+            let state = self.state.clone();
+            let hitbox = hitbox.clone();
+            move |event: &MouseDownEvent, phase, window, cx| {
+                if !phase.bubble()
+                    || event.button != MouseButton::Left
+                    || !hitbox.is_hovered(window)
+                {
+                    return;
+                }
+
+                let y = event.position.y - bounds.origin.y;
+                let slider = geometry.slider_top..geometry.slider_top + geometry.slider_height;
+                state.update(cx, |state, cx| {
+                    if slider.contains(&y) {
+                        state.minimap_drag_offset = Some(y - geometry.slider_top);
+                    } else {
+                        let scroll_top =
+                            geometry.scroll_top_for_click(y, line_height, viewport_height);
+                        state.minimap_drag_offset = Some(geometry.slider_height / 2.);
+                        state.set_minimap_scroll_top(scroll_top, cx);
+                    }
+                });
+                cx.stop_propagation();
+            }
+        });
+
+        window.on_mouse_event({
+            let state = self.state.clone();
+            move |event: &MouseMoveEvent, phase, _, cx| {
+                if !phase.bubble() || event.pressed_button != Some(MouseButton::Left) {
+                    return;
+                }
+
+                let Some(drag_offset) = state.read(cx).minimap_drag_offset else {
+                    return;
+                };
+
+                let slider_top = event.position.y - bounds.origin.y - drag_offset;
+                let scroll_top = geometry.scroll_top_for_drag(slider_top, bounds.size.height);
+                state.update(cx, |state, cx| state.set_minimap_scroll_top(scroll_top, cx));
+            }
+        });
+
+        window.on_mouse_event({
+            let state = self.state.clone();
+            move |_: &MouseUpEvent, phase, _, cx| {
+                if !phase.bubble() {
+                    return;
+                }
+
+                state.update(cx, |state, _| state.minimap_drag_offset = None);
+            }
+        });
+    }
+}
+
+fn minimap_bar(
+    bounds: Bounds<Pixels>,
+    y: Pixels,
+    cols: std::ops::Range<usize>,
+    color: Hsla,
+) -> PaintQuad {
+    fill(
+        Bounds::new(
+            point(bounds.origin.x + MINIMAP_CHAR_WIDTH * cols.start as f32, y),
+            size(
+                MINIMAP_CHAR_WIDTH * cols.len() as f32,
+                MINIMAP_ROW_HEIGHT * 0.75,
+            ),
+        ),
+        color,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::MinimapGeometry;
+
+    #[test]
+    fn test_minimap_geometry_short_content() {
+        // 100 rows = 200px of minimap content, fits in the 500px minimap.
+        let geometry = MinimapGeometry::new(100, px(500.), px(20.), px(400.), px(600.), px(2000.));
+        assert_eq!(geometry.content_height, px(200.));
+        assert_eq!(geometry.scroll_top, px(0.));
+        assert_eq!(geometry.slider_top, px(60.));
+        assert_eq!(geometry.slider_height, px(40.));
+        assert_eq!(geometry.max_editor_scroll, px(1600.));
+
+        // Click the row 50 (y = 100px), scroll it to the center.
+        assert_eq!(
+            geometry.scroll_top_for_click(px(100.), px(20.), px(400.)),
+            px(800.)
+        );
+        // Click the first row, clamped to the top.
+        assert_eq!(
+            geometry.scroll_top_for_click(px(0.), px(20.), px(400.)),
+            px(0.)
+        );
+        assert_eq!(geometry.scroll_top_for_drag(px(80.), px(500.)), px(800.));
+        assert_eq!(geometry.scroll_top_for_drag(px(-10.), px(500.)), px(0.));
+    }
+
+    #[test]
+    fn test_minimap_geometry_long_content() {
+        // 1000 rows = 2000px of minimap content, taller than the 500px minimap.
+        let geometry =
+            MinimapGeometry::new(1000, px(500.), px(20.), px(400.), px(9800.), px(20000.));
+        assert_eq!(geometry.content_height, px(2000.));
+        assert_eq!(geometry.max_editor_scroll, px(19600.));
+        // Scrolled to the middle, the minimap content is scrolled to the middle too.
+        assert_eq!(geometry.scroll_top, px(750.));
+        assert_eq!(geometry.slider_top, px(230.));
+        assert_eq!(geometry.scroll_top_for_drag(px(230.), px(500.)), px(9800.));
+    }
+}
@@ -2,41 +2,66 @@
 pub(super) const MASK_CHAR: char = '•';
 
 mod blink_cursor;
+mod brackets;
 mod change;
 mod clear_button;
+mod comment;
 mod content_type;
 mod cursor;
+mod diagnostics_panel;
 mod display_map;
 mod element;
+mod fold;
+mod gutter_marks;
 mod indent;
 mod input;
+mod language_settings;
+mod large_file;
+mod line_ops;
 mod lsp;
 mod mask_pattern;
+mod minimap;
 mod mode;
 mod movement;
+mod multi_cursor;
 #[cfg(target_os = "macos")]
 mod native;
+mod number_format;
 mod number_input;
 mod otp_input;
 pub(crate) mod popovers;
 mod rope_ext;
+mod rulers;
 mod search;
+mod search_input;
 mod selection;
+mod snippet;
+mod split;
 mod state;
+mod sticky_scroll;
+mod value_history;
+mod vim;
 
 pub(crate) use clear_button::*;
 pub use content_type::*;
 pub use cursor::*;
+pub use diagnostics_panel::*;
 #[cfg(not(feature = "tree-sitter"))]
 pub use display_map::Tree;
 pub use display_map::{BufferPoint, DisplayMap, DisplayPoint, FoldRange};
+pub use gutter_marks::{GutterMark, GutterMarkKind};
 pub use indent::TabSize;
 pub use input::*;
+pub use language_settings::{LanguageSettings, LanguageSettingsTable};
 pub use lsp::*;
 pub use lsp_types::Position;
-pub use mask_pattern::MaskPattern;
+pub use mask_pattern::{MaskPattern, MaskProvider};
+pub(crate) use number_format::group_digits;
+pub use number_format::{NumberInputFormat, NumberType, NumberValue};
 pub use number_input::{NumberInput, NumberInputEvent, NumberStep, StepAction};
 pub use otp_input::*;
-pub use rope_ext::{InputEdit, Point, RopeExt, RopeLines};
+pub use rope_ext::{InputEdit, LineEnding, Point, RopeEdits, RopeExt, RopeLines};
 pub use ropey::Rope;
+pub use search_input::*;
 pub use state::*;
+pub use vim::{VimKeymap, VimKeys, VimMode, VimRegister};
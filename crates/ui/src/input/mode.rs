@@ -47,6 +47,8 @@ pub(crate) enum InputMode {
         highlighter: Rc<RefCell<Option<SyntaxHighlighter>>>,
         diagnostics: DiagnosticSet,
         parse_task: Rc<RefCell<Option<Task<()>>>>,
+        /// Disable the syntax highlighting for the large file.
+        large_file: bool,
     },
 }
 
@@ -80,6 +82,7 @@ impl InputMode {
             folding: true,
             diagnostics: DiagnosticSet::new(&Rope::new()),
             parse_task: Rc::new(RefCell::new(None)),
+            large_file: false,
         }
     }
 
@@ -101,6 +104,13 @@ impl InputMode {
         self
     }
 
+    /// Set to disable the syntax highlighting for the large file.
+    pub(super) fn set_large_file(&mut self, large: bool) {
+        if let InputMode::CodeEditor { large_file, .. } = self {
+            *large_file = large;
+        }
+    }
+
     #[inline]
     pub(super) fn is_single_line(&self) -> bool {
         !self.is_multi_line()
@@ -238,8 +248,13 @@ impl InputMode {
                 highlighter,
                 parse_task,
                 folding,
+                large_file,
                 ..
             } => {
+                if *large_file {
+                    return None;
+                }
+
                 if !force && highlighter.borrow().is_some() {
                     return None;
                 }
@@ -309,6 +324,14 @@ impl InputMode {
         }
     }
 
+    /// Get the highlighter language, only for [`InputMode::CodeEditor`] mode.
+    pub(super) fn language(&self) -> Option<&SharedString> {
+        match self {
+            InputMode::CodeEditor { language, .. } => Some(language),
+            _ => None,
+        }
+    }
+
     /// Get a reference to the highlighter (if available)
     pub(super) fn highlighter(&self) -> Option<&Rc<RefCell<Option<SyntaxHighlighter>>>> {
         match self {
@@ -424,6 +447,7 @@ mod tests {
             highlighter: Default::default(),
             diagnostics: DiagnosticSet::new(&Rope::new()),
             parse_task: Default::default(),
+            large_file: false,
         };
         assert_eq!(mode.is_code_editor(), true);
         assert_eq!(mode.is_multi_line(), false);
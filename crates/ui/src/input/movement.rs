@@ -45,12 +45,14 @@ impl InputState {
         cx: &mut Context<Self>,
     ) {
         let offset = offset.clamp(0, self.text.len());
+        self.extra_cursors.clear();
         self.cursor_line_end_affinity = false;
         self.selected_range = (offset..offset).into();
         self.scroll_to(offset, direction, cx);
         self.pause_blink_cursor(cx);
         self.update_preferred_column();
         self.hide_context_menu(cx);
+        self.hide_signature_help(cx);
         self.clear_inline_completion(cx);
         cx.notify()
     }
@@ -136,7 +138,11 @@ impl InputState {
         cx.notify();
     }
 
-    pub(super) fn left(&mut self, _: &MoveLeft, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn left(&mut self, action: &MoveLeft, window: &mut Window, cx: &mut Context<Self>) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| this.left(action, window, cx));
+            return;
+        }
         self.pause_blink_cursor(cx);
         if self.selected_range.is_empty() {
             self.move_to(self.previous_boundary(self.cursor()), None, cx);
@@ -145,7 +151,18 @@ impl InputState {
         }
     }
 
-    pub(super) fn right(&mut self, _: &MoveRight, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn right(
+        &mut self,
+        action: &MoveRight,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.right(action, window, cx)
+            });
+            return;
+        }
         self.pause_blink_cursor(cx);
         if self.selected_range.is_empty() {
             self.move_to(self.next_boundary(self.selected_range.end), None, cx);
@@ -159,7 +176,17 @@ impl InputState {
             return;
         }
 
+        if self.select_signature(-1, cx) {
+            return;
+        }
+
         if self.mode.is_single_line() {
+            self.recall_history_value(-1, window, cx);
+            return;
+        }
+
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| this.up(action, window, cx));
             return;
         }
 
@@ -179,7 +206,17 @@ impl InputState {
             return;
         }
 
+        if self.select_signature(1, cx) {
+            return;
+        }
+
         if self.mode.is_single_line() {
+            self.recall_history_value(1, window, cx);
+            return;
+        }
+
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| this.down(action, window, cx));
             return;
         }
 
@@ -226,13 +263,21 @@ impl InputState {
         self.move_vertical(display_lines, window, cx);
     }
 
-    pub(super) fn home(&mut self, _: &MoveHome, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn home(&mut self, action: &MoveHome, window: &mut Window, cx: &mut Context<Self>) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| this.home(action, window, cx));
+            return;
+        }
         self.pause_blink_cursor(cx);
         let offset = self.start_of_line();
         self.move_to(offset, Some(MoveDirection::Up), cx);
     }
 
-    pub(super) fn end(&mut self, _: &MoveEnd, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn end(&mut self, action: &MoveEnd, window: &mut Window, cx: &mut Context<Self>) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| this.end(action, window, cx));
+            return;
+        }
         self.pause_blink_cursor(cx);
         let offset = self.end_of_line();
         self.move_to(offset, Some(MoveDirection::Down), cx);
@@ -254,20 +299,32 @@ impl InputState {
 
     pub(super) fn move_to_previous_word(
         &mut self,
-        _: &MoveToPreviousWord,
-        _: &mut Window,
+        action: &MoveToPreviousWord,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.move_to_previous_word(action, window, cx)
+            });
+            return;
+        }
         let offset = self.previous_start_of_word();
         self.move_to(offset, None, cx);
     }
 
     pub(super) fn move_to_next_word(
         &mut self,
-        _: &MoveToNextWord,
-        _: &mut Window,
+        action: &MoveToNextWord,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.move_to_next_word(action, window, cx)
+            });
+            return;
+        }
         let offset = self.next_end_of_word();
         self.move_to(offset, None, cx);
     }
@@ -0,0 +1,487 @@
+use std::ops::Range;
+
+use gpui::{ClipboardItem, Context, Pixels, Window};
+use ropey::Rope;
+
+use crate::input::{
+    InputState, RopeExt as _, SelectNextOccurrence, Selection, ToggleColumnSelection,
+};
+
+/// A cursor with its selection, used for the extra cursors of multi-cursor editing.
+///
+/// The primary cursor is kept in [`InputState`] itself (`selected_range`,
+/// `selection_reversed` and `preferred_column`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct CursorState {
+    pub(super) range: Selection,
+    pub(super) reversed: bool,
+    pub(super) preferred_column: Option<(Pixels, usize)>,
+}
+
+impl CursorState {
+    /// The offset of the caret, the moving end of the selection.
+    pub(super) fn head(&self) -> usize {
+        if self.reversed {
+            self.range.start
+        } else {
+            self.range.end
+        }
+    }
+
+    fn shift(&mut self, delta: isize, min: usize) {
+        let shift = |offset: usize| offset.saturating_add_signed(delta).max(min);
+        self.range = (shift(self.range.start)..shift(self.range.end)).into();
+    }
+}
+
+/// The state of the column (block) selection while dragging.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct ColumnSelection {
+    /// The offset where the drag started.
+    anchor: usize,
+    /// True if the mouse has moved away from the anchor.
+    dragged: bool,
+}
+
+/// Returns the char column of the offset in its line.
+fn char_column(text: &Rope, offset: usize) -> (usize, usize) {
+    let row = text.offset_to_point(offset).row;
+    let column = text
+        .slice(text.line_start_offset(row)..offset)
+        .chars()
+        .count();
+    (row, column)
+}
+
+/// Returns the byte ranges of the rectangular selection between the `anchor` and `head`
+/// offsets, one range per line, by the char columns.
+///
+/// The lines shorter than the start column are skipped, unless the selection has no width.
+fn column_selection_ranges(text: &Rope, anchor: usize, head: usize) -> Vec<Range<usize>> {
+    let (anchor_row, anchor_column) = char_column(text, anchor);
+    let (head_row, head_column) = char_column(text, head);
+    let start_column = anchor_column.min(head_column);
+    let end_column = anchor_column.max(head_column);
+
+    let mut ranges = vec![];
+    for row in anchor_row.min(head_row)..=anchor_row.max(head_row) {
+        let line = text.slice_line(row);
+        let line_len = line.chars().count();
+        if line_len < start_column && start_column != end_column {
+            continue;
+        }
+
+        let line_start = text.line_start_offset(row);
+        let offset_for_column = |column: usize| {
+            line_start
+                + line
+                    .chars()
+                    .take(column)
+                    .map(|c| c.len_utf8())
+                    .sum::<usize>()
+        };
+        ranges.push(offset_for_column(start_column)..offset_for_column(end_column));
+    }
+
+    if ranges.is_empty() {
+        ranges.push(head..head);
+    }
+    ranges
+}
+
+impl InputState {
+    /// Returns true if there are extra cursors besides the primary cursor.
+    pub(super) fn has_extra_cursors(&self) -> bool {
+        !self.extra_cursors.is_empty()
+    }
+
+    /// Returns the selected ranges of all cursors, sorted by offset.
+    ///
+    /// Besides the primary selection ([`InputState::selected_range`]), this includes
+    /// the extra cursors added by Alt+Click or Cmd+D (Ctrl+D on Windows/Linux).
+    pub fn selected_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges = self
+            .extra_cursors
+            .iter()
+            .map(|cursor| cursor.range.into())
+            .chain(std::iter::once(self.selected_range.into()))
+            .collect::<Vec<Range<usize>>>();
+        ranges.sort_by_key(|range| range.start);
+        ranges
+    }
+
+    /// Remove all the extra cursors, keep the primary cursor.
+    ///
+    /// Returns true if there were extra cursors.
+    pub(super) fn clear_extra_cursors(&mut self) -> bool {
+        if self.extra_cursors.is_empty() {
+            return false;
+        }
+
+        self.extra_cursors.clear();
+        true
+    }
+
+    fn primary_cursor(&self) -> CursorState {
+        CursorState {
+            range: self.selected_range,
+            reversed: self.selection_reversed,
+            preferred_column: self.preferred_column,
+        }
+    }
+
+    fn set_primary_cursor(&mut self, cursor: CursorState) {
+        self.selected_range = cursor.range;
+        self.selection_reversed = cursor.reversed;
+        self.preferred_column = cursor.preferred_column;
+        self.selected_word_range = None;
+    }
+
+    /// Add a cursor at the given offset (Alt+Click).
+    ///
+    /// If there is already an extra cursor at the offset, it will be removed instead.
+    pub(super) fn add_cursor(&mut self, offset: usize, cx: &mut Context<Self>) {
+        let offset = offset.min(self.text.len());
+        if let Some(ix) = self
+            .extra_cursors
+            .iter()
+            .position(|cursor| cursor.head() == offset)
+        {
+            self.extra_cursors.remove(ix);
+            cx.notify();
+            return;
+        }
+
+        if self.cursor() == offset {
+            return;
+        }
+
+        let primary = self.primary_cursor();
+        self.extra_cursors.push(primary);
+        self.set_primary_cursor(CursorState {
+            range: (offset..offset).into(),
+            reversed: false,
+            preferred_column: None,
+        });
+        self.update_preferred_column();
+        self.merge_cursors();
+        self.pause_blink_cursor(cx);
+        cx.notify();
+    }
+
+    /// Set the cursors to select the `ranges`, the first range is the primary cursor.
+    pub(super) fn set_cursors(&mut self, ranges: &[Range<usize>], cx: &mut Context<Self>) {
+        let Some((first, rest)) = ranges.split_first() else {
+            return;
+        };
+
+        let cursor = |range: &Range<usize>| CursorState {
+            range: range.clone().into(),
+            reversed: false,
+            preferred_column: None,
+        };
+        self.set_primary_cursor(cursor(first));
+        self.extra_cursors = rest.iter().map(cursor).collect();
+        self.update_preferred_column();
+        self.merge_cursors();
+        self.scroll_to(self.cursor(), None, cx);
+        self.pause_blink_cursor(cx);
+        cx.notify();
+    }
+
+    /// Toggle the column selection mode, when enabled, dragging the mouse makes a column
+    /// (block) selection, the same as Alt+Drag.
+    pub(super) fn toggle_column_selection(
+        &mut self,
+        _: &ToggleColumnSelection,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.column_selection_mode = !self.column_selection_mode;
+        cx.notify();
+    }
+
+    /// Returns true if the column selection mode is enabled.
+    pub fn column_selection_mode(&self) -> bool {
+        self.column_selection_mode
+    }
+
+    /// Set the column selection mode, see [`ToggleColumnSelection`].
+    pub fn set_column_selection_mode(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        self.column_selection_mode = enabled;
+        cx.notify();
+    }
+
+    /// Start a column selection at the offset, on mouse down.
+    pub(super) fn start_column_selection(&mut self, offset: usize) {
+        self.column_selection = Some(ColumnSelection {
+            anchor: offset,
+            dragged: false,
+        });
+    }
+
+    /// Select the column (block) from the anchor to the `head` offset, with a cursor on each line.
+    ///
+    /// Returns false if there is no column selection in progress.
+    pub(super) fn select_column_to(&mut self, head: usize, cx: &mut Context<Self>) -> bool {
+        let Some(selection) = self.column_selection.as_mut() else {
+            return false;
+        };
+        // Keep the cursor added by Alt+Click, until the mouse moves away.
+        if !selection.dragged && head == selection.anchor {
+            return true;
+        }
+        selection.dragged = true;
+
+        let anchor = selection.anchor;
+        let head = head.min(self.text.len());
+        let reversed = char_column(&self.text, head).1 < char_column(&self.text, anchor).1;
+        let mut cursors = column_selection_ranges(&self.text, anchor, head)
+            .into_iter()
+            .map(|range| CursorState {
+                range: range.into(),
+                reversed,
+                preferred_column: None,
+            })
+            .collect::<Vec<_>>();
+
+        // The primary cursor is on the line of the head.
+        let primary = if head < anchor {
+            cursors.remove(0)
+        } else {
+            cursors.pop().unwrap_or(self.primary_cursor())
+        };
+        self.set_primary_cursor(primary);
+        self.extra_cursors = cursors;
+        self.update_preferred_column();
+        self.pause_blink_cursor(cx);
+        cx.notify();
+        true
+    }
+
+    /// Merge the overlapping cursors and keep the extra cursors sorted by offset.
+    fn merge_cursors(&mut self) {
+        let mut cursors = std::mem::take(&mut self.extra_cursors)
+            .into_iter()
+            .map(|cursor| (false, cursor))
+            .collect::<Vec<_>>();
+        cursors.push((true, self.primary_cursor()));
+        cursors.sort_by_key(|(_, cursor)| (cursor.range.start, cursor.range.end));
+
+        let mut merged: Vec<(bool, CursorState)> = Vec::with_capacity(cursors.len());
+        for (is_primary, cursor) in cursors {
+            if let Some((last_is_primary, last)) = merged.last_mut() {
+                let overlaps = cursor.range.start < last.range.end
+                    || cursor.range.start == last.range.start
+                    || (cursor.range.start == last.range.end
+                        && (cursor.range.is_empty() || last.range.is_empty()));
+                if overlaps {
+                    last.range.end = last.range.end.max(cursor.range.end);
+                    if is_primary {
+                        *last_is_primary = true;
+                        last.preferred_column = cursor.preferred_column;
+                    }
+                    continue;
+                }
+            }
+            merged.push((is_primary, cursor));
+        }
+
+        for (is_primary, cursor) in merged {
+            if is_primary {
+                self.set_primary_cursor(cursor);
+            } else {
+                self.extra_cursors.push(cursor);
+            }
+        }
+    }
+
+    /// Run `f` once for every cursor, with the cursor as the primary selection.
+    ///
+    /// The cursors are visited from the last to the first, so an edit made at one
+    /// cursor never moves the offsets of the cursors not yet visited. All the
+    /// edits are grouped into a single undo step.
+    pub(super) fn for_each_cursor(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        mut f: impl FnMut(&mut Self, &mut Window, &mut Context<Self>),
+    ) {
+        if self.extra_cursors.is_empty() {
+            f(self, window, cx);
+            return;
+        }
+
+        let mut cursors = std::mem::take(&mut self.extra_cursors)
+            .into_iter()
+            .map(|cursor| (false, cursor))
+            .collect::<Vec<_>>();
+        cursors.push((true, self.primary_cursor()));
+        cursors.sort_by_key(|(_, cursor)| std::cmp::Reverse(cursor.range.start));
+
+        let mut visited: Vec<(bool, CursorState)> = Vec::with_capacity(cursors.len());
+        for (ix, (is_primary, cursor)) in cursors.into_iter().enumerate() {
+            self.set_primary_cursor(cursor);
+            let old_len = self.text.len();
+            if ix > 0 {
+                self.history.start_grouping();
+            }
+            f(self, window, cx);
+            self.history.end_grouping();
+
+            // Drop any cursors that `f` may have added (e.g. nested actions).
+            self.extra_cursors.clear();
+
+            let current = self.primary_cursor();
+            let delta = self.text.len() as isize - old_len as isize;
+            if delta != 0 {
+                for (_, cursor) in visited.iter_mut() {
+                    cursor.shift(delta, current.range.start);
+                }
+            }
+            visited.push((is_primary, current));
+        }
+
+        for (is_primary, cursor) in visited {
+            if is_primary {
+                self.set_primary_cursor(cursor);
+            } else {
+                self.extra_cursors.push(cursor);
+            }
+        }
+        self.merge_cursors();
+        self.scroll_to(self.cursor(), None, cx);
+        cx.notify();
+    }
+
+    /// Returns the selected text of every cursor, sorted by offset.
+    pub(super) fn selected_texts(&self) -> Vec<String> {
+        self.selected_ranges()
+            .into_iter()
+            .map(|range| self.text.slice(range).to_string())
+            .collect()
+    }
+
+    /// Copy the selections of all cursors, joined by newlines.
+    pub(super) fn copy_cursors(&mut self, cx: &mut Context<Self>) {
+        let texts = self.selected_texts();
+        if texts.iter().all(|text| text.is_empty()) {
+            return;
+        }
+
+        cx.write_to_clipboard(ClipboardItem::new_string(texts.join("\n")));
+    }
+
+    /// Paste the text at every cursor.
+    ///
+    /// If the text has exactly one line per cursor (e.g. copied from the same
+    /// number of cursors), each cursor gets its own line.
+    pub(super) fn paste_cursors(
+        &mut self,
+        text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let cursor_count = self.extra_cursors.len() + 1;
+        let lines = text.split('\n').collect::<Vec<_>>();
+        // The cursors are visited from the last to the first, pop the lines from the end.
+        let mut lines = if lines.len() == cursor_count {
+            lines
+        } else {
+            vec![text; cursor_count]
+        };
+
+        self.for_each_cursor(window, cx, |this, window, cx| {
+            let line = lines.pop().unwrap_or_default();
+            this.replace_text_in_range_silent(None, line, window, cx);
+        });
+    }
+
+    /// Add the next occurrence of the selected text as a new cursor (Cmd+D).
+    ///
+    /// If nothing is selected, select the word under the cursor first.
+    pub(super) fn select_next_occurrence(
+        &mut self,
+        _: &SelectNextOccurrence,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode.is_multi_line() {
+            cx.propagate();
+            return;
+        }
+
+        if self.selected_range.is_empty() {
+            self.select_word(self.cursor(), window, cx);
+            self.selected_word_range = None;
+            self.selection_reversed = false;
+            return;
+        }
+
+        let text = self.text.to_string();
+        let query = &text[Range::<usize>::from(self.selected_range)];
+        let ranges = self.selected_ranges();
+        let search_start = ranges.iter().map(|range| range.end).max().unwrap_or(0);
+
+        let overlaps_cursor = |start: usize| {
+            let end = start + query.len();
+            ranges
+                .iter()
+                .any(|range| start < range.end && end > range.start)
+        };
+        let next = text[search_start..]
+            .match_indices(query)
+            .map(|(ix, _)| search_start + ix)
+            .chain(text[..search_start].match_indices(query).map(|(ix, _)| ix))
+            .find(|&start| !overlaps_cursor(start));
+
+        let Some(start) = next else {
+            return;
+        };
+
+        let primary = self.primary_cursor();
+        self.extra_cursors.push(primary);
+        self.set_primary_cursor(CursorState {
+            range: (start..start + query.len()).into(),
+            reversed: false,
+            preferred_column: None,
+        });
+        self.merge_cursors();
+        self.scroll_to(self.cursor(), None, cx);
+        self.pause_blink_cursor(cx);
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ropey::Rope;
+
+    use super::column_selection_ranges;
+
+    #[test]
+    fn test_column_selection_ranges() {
+        let text = Rope::from("hello world\nhi\n你好世界!\nfoo bar baz");
+
+        // Down and right.
+        assert_eq!(
+            column_selection_ranges(&text, 1, 32),
+            vec![1..3, 13..14, 18..24, 30..32]
+        );
+        // Up and left, the same block.
+        assert_eq!(
+            column_selection_ranges(&text, 32, 1),
+            vec![1..3, 13..14, 18..24, 30..32]
+        );
+        // The short line is skipped.
+        assert_eq!(
+            column_selection_ranges(&text, 3, 34),
+            vec![3..5, 24..28, 32..34]
+        );
+        // No width, a cursor on each line.
+        assert_eq!(
+            column_selection_ranges(&text, 4, 33),
+            vec![4..4, 14..14, 27..27, 33..33]
+        );
+    }
+}
@@ -0,0 +1,632 @@
+use std::fmt;
+
+use gpui::{Context, Window};
+#[cfg(feature = "decimal")]
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive as _, ToPrimitive as _},
+};
+
+use crate::input::{InputState, MaskPattern, StepAction};
+
+/// The numeric type of the [`super::NumberInput`] value, default is [`NumberType::Float`].
+///
+/// See [`InputState::number_type`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NumberType {
+    /// A `f64` value, the stepped value keeps the fraction digits of the value and the step.
+    #[default]
+    Float,
+    /// An `i128` value, the fraction is not allowed.
+    Integer,
+    /// A [`rust_decimal::Decimal`] value, for the financial quantities without float rounding.
+    #[cfg(feature = "decimal")]
+    Decimal,
+}
+
+/// A number value of the [`super::NumberInput`], used for the `min`, `max` and `step`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberValue {
+    Float(f64),
+    Integer(i128),
+    #[cfg(feature = "decimal")]
+    Decimal(Decimal),
+}
+
+impl NumberValue {
+    /// Parse the raw text (without group separators) as the [`NumberType`].
+    pub fn parse(text: &str, ty: NumberType) -> Option<Self> {
+        let text = text.trim();
+        match ty {
+            NumberType::Float => text.parse::<f64>().ok().map(Self::Float),
+            NumberType::Integer => text.parse::<i128>().ok().map(Self::Integer),
+            #[cfg(feature = "decimal")]
+            NumberType::Decimal => text.parse::<Decimal>().ok().map(Self::Decimal),
+        }
+    }
+
+    /// Returns the value as `f64`, may lose precision.
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Self::Float(value) => *value,
+            Self::Integer(value) => *value as f64,
+            #[cfg(feature = "decimal")]
+            Self::Decimal(value) => value.to_f64().unwrap_or(f64::NAN),
+        }
+    }
+
+    /// Returns the value as `i128`, the fraction is rounded.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Self::Float(value) => value.is_finite().then(|| value.round() as i128),
+            Self::Integer(value) => Some(*value),
+            #[cfg(feature = "decimal")]
+            Self::Decimal(value) => value.round().to_i128(),
+        }
+    }
+
+    /// Returns the value as [`Decimal`].
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Self::Float(value) => Decimal::from_f64(*value),
+            Self::Integer(value) => Decimal::from_i128(*value),
+            Self::Decimal(value) => Some(*value),
+        }
+    }
+}
+
+impl fmt::Display for NumberValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Float(value) => value.fmt(f),
+            Self::Integer(value) => value.fmt(f),
+            #[cfg(feature = "decimal")]
+            Self::Decimal(value) => value.fmt(f),
+        }
+    }
+}
+
+impl From<f64> for NumberValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<f32> for NumberValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value as f64)
+    }
+}
+
+impl From<i32> for NumberValue {
+    fn from(value: i32) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl From<i64> for NumberValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl From<u64> for NumberValue {
+    fn from(value: u64) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl From<i128> for NumberValue {
+    fn from(value: i128) -> Self {
+        Self::Integer(value)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl From<Decimal> for NumberValue {
+    fn from(value: Decimal) -> Self {
+        Self::Decimal(value)
+    }
+}
+
+/// The number types to step and clamp exactly.
+trait ExactNumber: Copy + PartialOrd + fmt::Display {
+    const ZERO: Self;
+
+    fn from_value(value: NumberValue) -> Option<Self>;
+    fn step_add(self, other: Self) -> Option<Self>;
+    fn step_sub(self, other: Self) -> Option<Self>;
+}
+
+impl ExactNumber for i128 {
+    const ZERO: Self = 0;
+
+    fn from_value(value: NumberValue) -> Option<Self> {
+        value.as_i128()
+    }
+
+    fn step_add(self, other: Self) -> Option<Self> {
+        self.checked_add(other)
+    }
+
+    fn step_sub(self, other: Self) -> Option<Self> {
+        self.checked_sub(other)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl ExactNumber for Decimal {
+    const ZERO: Self = Decimal::ZERO;
+
+    fn from_value(value: NumberValue) -> Option<Self> {
+        value.as_decimal()
+    }
+
+    fn step_add(self, other: Self) -> Option<Self> {
+        self.checked_add(other)
+    }
+
+    fn step_sub(self, other: Self) -> Option<Self> {
+        self.checked_sub(other)
+    }
+}
+
+fn step_exact<T: ExactNumber>(
+    value: &str,
+    action: StepAction,
+    step: NumberValue,
+    min: Option<NumberValue>,
+    max: Option<NumberValue>,
+) -> Option<String>
+where
+    T: std::str::FromStr,
+{
+    let current = value.trim().parse::<T>().ok();
+    let step = T::from_value(step)?;
+    let base = current.unwrap_or(T::ZERO);
+    let mut new_value = match action {
+        StepAction::Increment => base.step_add(step)?,
+        StepAction::Decrement => base.step_sub(step)?,
+    };
+    if let Some(min) = min.and_then(T::from_value) {
+        if new_value < min {
+            new_value = min;
+        }
+    }
+    if let Some(max) = max.and_then(T::from_value) {
+        if new_value > max {
+            new_value = max;
+        }
+    }
+
+    // Same as the float, stepping must move the value in the pressed direction.
+    if let Some(current) = current {
+        let moved = match action {
+            StepAction::Increment => new_value > current,
+            StepAction::Decrement => new_value < current,
+        };
+        if !moved {
+            return None;
+        }
+    }
+
+    Some(new_value.to_string())
+}
+
+fn clamp_exact<T: ExactNumber>(
+    value: &str,
+    min: Option<NumberValue>,
+    max: Option<NumberValue>,
+) -> Option<String>
+where
+    T: std::str::FromStr,
+{
+    let value = value.trim().parse::<T>().ok()?;
+    if let Some(min) = min.and_then(T::from_value) {
+        if value < min {
+            return Some(min.to_string());
+        }
+    }
+    if let Some(max) = max.and_then(T::from_value) {
+        if value > max {
+            return Some(max.to_string());
+        }
+    }
+    None
+}
+
+/// Step the raw `value` of the [`NumberType::Integer`] or [`NumberType::Decimal`] type
+/// exactly, and clamp the result to the `min`/`max` range.
+///
+/// Returns `None` if stepping cannot move the value in the given direction.
+pub(super) fn step_exact_value(
+    value: &str,
+    action: StepAction,
+    step: NumberValue,
+    min: Option<NumberValue>,
+    max: Option<NumberValue>,
+    ty: NumberType,
+) -> Option<String> {
+    match ty {
+        NumberType::Float => None,
+        NumberType::Integer => step_exact::<i128>(value, action, step, min, max),
+        #[cfg(feature = "decimal")]
+        NumberType::Decimal => step_exact::<Decimal>(value, action, step, min, max),
+    }
+}
+
+/// Returns the clamped text if the raw `value` is out of the `min`/`max` range.
+pub(super) fn clamp_number_text(
+    value: &str,
+    ty: NumberType,
+    min: Option<NumberValue>,
+    max: Option<NumberValue>,
+) -> Option<String> {
+    match ty {
+        NumberType::Float => {
+            let value = value.trim().parse::<f64>().ok()?;
+            match (min.map(|v| v.as_f64()), max.map(|v| v.as_f64())) {
+                (Some(min), _) if value < min => Some(min.to_string()),
+                (_, Some(max)) if value > max => Some(max.to_string()),
+                _ => None,
+            }
+        }
+        NumberType::Integer => clamp_exact::<i128>(value, min, max),
+        #[cfg(feature = "decimal")]
+        NumberType::Decimal => clamp_exact::<Decimal>(value, min, max),
+    }
+}
+
+/// Group the integer digits from the right, e.g. `1234567` to `1,234,567`.
+///
+/// The first group has `group_size` digits, and the others have `secondary_group_size`
+/// (or `group_size` if None) digits, e.g. `12,34,567` in India.
+pub(crate) fn group_digits(
+    digits: &str,
+    separator: char,
+    group_size: usize,
+    secondary_group_size: Option<usize>,
+) -> String {
+    let mut groups = vec![];
+    let mut end = digits.len();
+    let mut size = group_size.max(1);
+    while end > size {
+        groups.push(&digits[end - size..end]);
+        end -= size;
+        size = secondary_group_size.unwrap_or(group_size).max(1);
+    }
+    groups.push(&digits[..end]);
+    groups.reverse();
+
+    let mut result = String::with_capacity(digits.len() + groups.len());
+    for (ix, group) in groups.iter().enumerate() {
+        if ix > 0 {
+            result.push(separator);
+        }
+        result.push_str(group);
+    }
+    result
+}
+
+/// The locale-aware display format of the [`super::NumberInput`] value.
+///
+/// The value is displayed with the group separators when the input is not focused,
+/// and the raw value is shown for editing. See [`InputState::number_format`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberInputFormat {
+    /// The group (thousands) separator, e.g. `,` in `1,234`.
+    pub group_separator: char,
+    /// The decimal separator, e.g. `.` in `1.5`.
+    pub decimal_separator: char,
+    /// The size of the first group from the decimal separator, default is 3.
+    pub group_size: usize,
+    /// The size of the other groups, e.g. 2 for `12,34,567` in India,
+    /// default is None to use the `group_size`.
+    pub secondary_group_size: Option<usize>,
+}
+
+impl Default for NumberInputFormat {
+    fn default() -> Self {
+        Self::new(',', '.')
+    }
+}
+
+impl NumberInputFormat {
+    pub fn new(group_separator: char, decimal_separator: char) -> Self {
+        Self {
+            group_separator,
+            decimal_separator,
+            group_size: 3,
+            secondary_group_size: None,
+        }
+    }
+
+    /// Set the size of the groups after the first one.
+    pub fn secondary_group_size(mut self, size: usize) -> Self {
+        self.secondary_group_size = Some(size);
+        self
+    }
+
+    /// Returns the number format of the locale, e.g. `en-US`, `de`, `fr-FR`.
+    ///
+    /// The unknown locale uses `1,234.5`.
+    pub fn locale(locale: &str) -> Self {
+        let locale = locale.replace('_', "-").to_lowercase();
+        match locale.as_str() {
+            "en-in" | "hi" | "hi-in" => return Self::new(',', '.').secondary_group_size(2),
+            "de-ch" | "fr-ch" | "it-ch" => return Self::new('’', '.'),
+            "es-mx" | "pt-pt" => return Self::new(',', '.'),
+            _ => {}
+        }
+
+        let language = locale.split('-').next().unwrap_or_default();
+        match language {
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "vi" => {
+                Self::new('.', ',')
+            }
+            "fr" => Self::new('\u{202f}', ','),
+            "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "hu" | "bg" => {
+                Self::new('\u{a0}', ',')
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Returns the number format of the current locale, see [`crate::locale`].
+    pub fn current() -> Self {
+        Self::locale(&crate::locale())
+    }
+
+    /// Format the raw value, e.g. `-1234567.5` to `-1,234,567.5`.
+    ///
+    /// The text is returned as is if it is not a number.
+    pub fn format(&self, raw: &str) -> String {
+        let (sign, unsigned) = match raw.strip_prefix(['-', '+']) {
+            Some(rest) => (&raw[..1], rest),
+            None => ("", raw),
+        };
+        let (int_part, frac_part) = match unsigned.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (unsigned, None),
+        };
+        if !int_part.chars().all(|c| c.is_ascii_digit())
+            || !frac_part.is_none_or(|frac| frac.chars().all(|c| c.is_ascii_digit()))
+        {
+            return raw.to_string();
+        }
+
+        let mut result = String::from(sign);
+        result.push_str(&group_digits(
+            int_part,
+            self.group_separator,
+            self.group_size,
+            self.secondary_group_size,
+        ));
+        if let Some(frac) = frac_part {
+            result.push(self.decimal_separator);
+            result.push_str(frac);
+        }
+        result
+    }
+
+    /// Convert the formatted text back to the raw value, e.g. `1.234,5` to `1234.5`.
+    pub fn unformat(&self, text: &str) -> String {
+        text.chars()
+            .filter(|c| *c != self.group_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect()
+    }
+}
+
+impl InputState {
+    /// Set the numeric type of the [`super::NumberInput`] value, default is [`NumberType::Float`].
+    ///
+    /// The stepping and clamping by `step`, `min` and `max` are calculated in the type,
+    /// so the [`NumberType::Integer`] and [`NumberType::Decimal`] values have no float rounding.
+    pub fn number_type(mut self, ty: NumberType) -> Self {
+        debug_assert!(self.mode.is_single_line());
+        self.number_type = ty;
+        self
+    }
+
+    /// Set the display format of the [`super::NumberInput`], default is None.
+    ///
+    /// When the input is not focused, the value is displayed with the group separators
+    /// and the decimal separator of the format, e.g. `1.234.567,5` for `de`.
+    /// The raw value is shown when focused to edit.
+    ///
+    /// Use [`Self::unmask_value`] or [`Self::number_value`] to get the raw value.
+    pub fn number_format(mut self, format: NumberInputFormat) -> Self {
+        debug_assert!(self.mode.is_single_line());
+        self.number_format = Some(format);
+        self
+    }
+
+    /// Update the display format of the [`super::NumberInput`], see [`Self::number_format`].
+    pub fn set_number_format(
+        &mut self,
+        format: Option<NumberInputFormat>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_number_display(false, window, cx);
+        self.number_format = format;
+        cx.notify();
+    }
+
+    /// Returns the value parsed as the [`NumberType`], None if the value is empty or invalid.
+    pub fn number_value(&self) -> Option<NumberValue> {
+        NumberValue::parse(&self.unmask_value(), self.number_type)
+    }
+
+    /// Check the raw number text is valid for the [`NumberType`].
+    pub(super) fn is_valid_number_type(&self, text: &str) -> bool {
+        if !matches!(self.mask_pattern, MaskPattern::Number { .. }) {
+            return true;
+        }
+
+        match self.number_type {
+            NumberType::Integer => !text.contains('.'),
+            _ => true,
+        }
+    }
+
+    /// Switch the text between the formatted display and the raw value.
+    pub(super) fn update_number_display(
+        &mut self,
+        formatted: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(format) = self.number_format.clone() else {
+            return;
+        };
+        if self.number_formatted == formatted {
+            return;
+        }
+
+        let raw = self.unmask_value().to_string();
+        let text = if formatted {
+            if NumberValue::parse(&raw, self.number_type).is_none() {
+                return;
+            }
+            format.format(&raw)
+        } else {
+            raw
+        };
+
+        // The formatted text is not checked by the mask and validation.
+        self.number_formatted = formatted;
+        let was_ignore = self.history.ignore;
+        self.history.ignore = true;
+        self.emit_events = false;
+        let range = self.range_to_utf16(&(0..self.text.len()));
+        self.replace_text_in_range_silent(Some(range), &text, window, cx);
+        self.history.ignore = was_ignore;
+        self.emit_events = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NumberInputFormat, NumberType, NumberValue, clamp_number_text, step_exact_value};
+    use crate::input::StepAction;
+
+    #[test]
+    fn test_number_format() {
+        let format = NumberInputFormat::locale("en-US");
+        assert_eq!(format.format("1234567.891"), "1,234,567.891");
+        assert_eq!(format.format("-1234"), "-1,234");
+        assert_eq!(format.format("123"), "123");
+        assert_eq!(format.format("abc"), "abc");
+        assert_eq!(format.unformat("-1,234,567.891"), "-1234567.891");
+
+        let format = NumberInputFormat::locale("de_DE");
+        assert_eq!(format.format("1234567.5"), "1.234.567,5");
+        assert_eq!(format.unformat("1.234.567,5"), "1234567.5");
+
+        let format = NumberInputFormat::locale("en-IN");
+        assert_eq!(format.format("123456789"), "12,34,56,789");
+        assert_eq!(format.unformat("12,34,56,789"), "123456789");
+
+        let format = NumberInputFormat::locale("fr");
+        assert_eq!(format.format("1234.5"), "1\u{202f}234,5");
+
+        assert_eq!(
+            NumberInputFormat::locale("unknown"),
+            NumberInputFormat::default()
+        );
+    }
+
+    #[test]
+    fn test_step_exact_value() {
+        let max = i128::MAX.to_string();
+        assert_eq!(
+            step_exact_value(
+                &max,
+                StepAction::Decrement,
+                1.into(),
+                None,
+                None,
+                NumberType::Integer
+            ),
+            Some("170141183460469231731687303715884105726".to_string())
+        );
+        // Overflow does nothing.
+        assert_eq!(
+            step_exact_value(
+                &max,
+                StepAction::Increment,
+                1.into(),
+                None,
+                None,
+                NumberType::Integer
+            ),
+            None
+        );
+        assert_eq!(
+            step_exact_value(
+                "8",
+                StepAction::Increment,
+                5.into(),
+                None,
+                Some(10.into()),
+                NumberType::Integer
+            ),
+            Some("10".to_string())
+        );
+        assert_eq!(
+            step_exact_value(
+                "",
+                StepAction::Decrement,
+                1.into(),
+                Some(0.into()),
+                None,
+                NumberType::Integer
+            ),
+            Some("0".to_string())
+        );
+
+        assert_eq!(
+            clamp_number_text("20", NumberType::Integer, None, Some(10.into())),
+            Some("10".to_string())
+        );
+        assert_eq!(
+            clamp_number_text("5", NumberType::Integer, None, Some(10.into())),
+            None
+        );
+        assert_eq!(
+            NumberValue::parse("42", NumberType::Integer),
+            Some(NumberValue::Integer(42))
+        );
+        assert_eq!(NumberValue::parse("4.2", NumberType::Integer), None);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_step_decimal_value() {
+        assert_eq!(
+            step_exact_value(
+                "0.1",
+                StepAction::Increment,
+                0.2.into(),
+                None,
+                None,
+                NumberType::Decimal
+            ),
+            Some("0.3".to_string())
+        );
+        assert_eq!(
+            step_exact_value(
+                "99999999999999999.99",
+                StepAction::Increment,
+                0.01.into(),
+                None,
+                None,
+                NumberType::Decimal
+            ),
+            Some("100000000000000000.00".to_string())
+        );
+    }
+}
@@ -16,7 +16,10 @@ use crate::{
     h_flex,
 };
 
-use super::{Input, InputState, MaskPattern};
+use super::{
+    Input, InputState, MaskPattern,
+    number_format::{NumberType, NumberValue, step_exact_value},
+};
 
 actions!(number_input, [Increment, Decrement]);
 
@@ -127,13 +130,28 @@ impl InputState {
         if let Some(step) = self.number_step.clone() {
             let value = self.unmask_value();
             let current = value.trim().parse::<f64>().unwrap_or(0.);
-            let step = step.value(current, action, cx);
-            if let Some(new_value) =
-                step_value(&value, action, step, self.number_min, self.number_max)
-            {
+            let new_value = match self.number_type {
+                NumberType::Float => step_value(
+                    &value,
+                    action,
+                    step.value(current, action, cx),
+                    self.number_min.map(|v| v.as_f64()),
+                    self.number_max.map(|v| v.as_f64()),
+                ),
+                ty => step_exact_value(
+                    &value,
+                    action,
+                    step.number_value(current, action, cx),
+                    self.number_min,
+                    self.number_max,
+                    ty,
+                ),
+            };
+            if let Some(new_value) = new_value {
                 // The stepped value must pass the `pattern`/`validate` check,
                 // otherwise fall back to emit the event to let the caller handle it.
                 if self.is_valid_input(&new_value, cx) {
+                    self.number_formatted = false;
                     let range = self.range_to_utf16(&(0..self.text.len()));
                     self.replace_text_in_range_silent(Some(range), &new_value, window, cx);
                     return;
@@ -156,6 +174,9 @@ impl InputState {
 pub enum NumberStep {
     /// A fixed step value.
     Fixed(f64),
+    /// A fixed step value of the [`NumberType::Integer`] or [`NumberType::Decimal`] value,
+    /// to step without float rounding, e.g. `NumberStep::from(1_i128)`.
+    Exact(NumberValue),
     /// Calculate the step value from the current value and direction.
     ByValue(Rc<dyn Fn(f64, StepAction, &mut Context<InputState>) -> f64>),
 }
@@ -187,9 +208,23 @@ impl NumberStep {
     ) -> f64 {
         match self {
             Self::Fixed(step) => *step,
+            Self::Exact(step) => step.as_f64(),
             Self::ByValue(f) => f(current, action, cx),
         }
     }
+
+    /// Return the step value as [`NumberValue`] for the exact stepping.
+    pub(super) fn number_value(
+        &self,
+        current: f64,
+        action: StepAction,
+        cx: &mut Context<InputState>,
+    ) -> NumberValue {
+        match self {
+            Self::Exact(step) => *step,
+            _ => NumberValue::Float(self.value(current, action, cx)),
+        }
+    }
 }
 
 impl From<f64> for NumberStep {
@@ -198,6 +233,31 @@ impl From<f64> for NumberStep {
     }
 }
 
+impl From<i32> for NumberStep {
+    fn from(step: i32) -> Self {
+        Self::Exact(step.into())
+    }
+}
+
+impl From<i64> for NumberStep {
+    fn from(step: i64) -> Self {
+        Self::Exact(step.into())
+    }
+}
+
+impl From<i128> for NumberStep {
+    fn from(step: i128) -> Self {
+        Self::Exact(step.into())
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl From<rust_decimal::Decimal> for NumberStep {
+    fn from(step: rust_decimal::Decimal) -> Self {
+        Self::Exact(step.into())
+    }
+}
+
 /// Step the `value` by `step` and clamp the result to the `min`/`max` range.
 ///
 /// Returns `None` if stepping cannot move the value in the given direction
@@ -297,7 +357,22 @@ impl RenderOnce for NumberInput {
             });
         }
 
-        let numeric_value = self.state.read(cx).value().parse::<f64>().ok();
+        // Display the formatted value when not editing, e.g. after `set_value`.
+        let state = self.state.read(cx);
+        if state.number_format.is_some()
+            && !state.number_formatted
+            && !state.focus_handle.is_focused(window)
+        {
+            self.state.update(cx, |state, cx| {
+                state.update_number_display(true, window, cx);
+            });
+        }
+
+        let numeric_value = self
+            .state
+            .read(cx)
+            .number_value()
+            .map(|value| value.as_f64());
 
         h_flex()
             .id(("number-input", self.state.entity_id()))
@@ -13,7 +13,7 @@ const MAX_MENU_HEIGHT: Pixels = px(480.);
 
 use crate::{
     ActiveTheme, IndexPath, Selectable, actions, h_flex,
-    input::{self, InputState, popovers::editor_popover},
+    input::{self, CodeActionGroup, InputState, popovers::editor_popover},
     list::{List, ListDelegate, ListEvent, ListState},
 };
 
@@ -26,31 +26,50 @@ pub(crate) struct CodeActionItem {
 
 struct MenuDelegate {
     menu: Entity<CodeActionMenu>,
-    items: Vec<Rc<CodeActionItem>>,
-    selected_ix: usize,
+    /// The items grouped by the kind, see [`CodeActionGroup`].
+    sections: Vec<(CodeActionGroup, Vec<Rc<CodeActionItem>>)>,
+    selected_ix: IndexPath,
 }
 
 impl MenuDelegate {
-    fn set_items(&mut self, items: Vec<CodeActionItem>) {
-        self.items = items.into_iter().map(Rc::new).collect();
-        self.selected_ix = 0;
+    fn set_items(&mut self, mut items: Vec<CodeActionItem>) {
+        input::sort_code_actions(&mut items);
+
+        self.sections.clear();
+        for item in items {
+            let group = CodeActionGroup::of(&item.action);
+            match self.sections.last_mut() {
+                Some((last_group, group_items)) if *last_group == group => {
+                    group_items.push(Rc::new(item))
+                }
+                _ => self.sections.push((group, vec![Rc::new(item)])),
+            }
+        }
+        self.selected_ix = IndexPath::default();
+    }
+
+    fn is_empty(&self) -> bool {
+        self.sections.is_empty()
     }
 
     fn selected_item(&self) -> Option<&Rc<CodeActionItem>> {
-        self.items.get(self.selected_ix)
+        self.sections
+            .get(self.selected_ix.section)?
+            .1
+            .get(self.selected_ix.row)
     }
 }
 
 #[derive(IntoElement)]
 struct MenuItem {
-    ix: usize,
+    ix: IndexPath,
     item: Rc<CodeActionItem>,
     children: Vec<AnyElement>,
     selected: bool,
 }
 
 impl MenuItem {
-    fn new(ix: usize, item: Rc<CodeActionItem>) -> Self {
+    fn new(ix: IndexPath, item: Rc<CodeActionItem>) -> Self {
         Self {
             ix,
             item,
@@ -105,8 +124,37 @@ impl EventEmitter<DismissEvent> for MenuDelegate {}
 impl ListDelegate for MenuDelegate {
     type Item = MenuItem;
 
-    fn items_count(&self, _: usize, _: &gpui::App) -> usize {
-        self.items.len()
+    fn sections_count(&self, _: &App) -> usize {
+        self.sections.len().max(1)
+    }
+
+    fn items_count(&self, section: usize, _: &gpui::App) -> usize {
+        self.sections
+            .get(section)
+            .map_or(0, |(_, items)| items.len())
+    }
+
+    fn render_section_header(
+        &mut self,
+        section: usize,
+        _: &mut Window,
+        cx: &mut Context<ListState<Self>>,
+    ) -> Option<impl IntoElement> {
+        // No header for a single group.
+        if self.sections.len() < 2 {
+            return None;
+        }
+
+        let (group, _) = self.sections.get(section)?;
+        Some(
+            div()
+                .px_1()
+                .pt_1()
+                .pb_0p5()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(group.label()),
+        )
     }
 
     fn render_item(
@@ -115,8 +163,8 @@ impl ListDelegate for MenuDelegate {
         _: &mut Window,
         _: &mut Context<ListState<Self>>,
     ) -> Option<Self::Item> {
-        let item = self.items.get(ix.row)?;
-        Some(MenuItem::new(ix.row, item.clone()))
+        let item = self.sections.get(ix.section)?.1.get(ix.row)?;
+        Some(MenuItem::new(ix, item.clone()))
     }
 
     fn set_selected_index(
@@ -125,7 +173,7 @@ impl ListDelegate for MenuDelegate {
         _: &mut Window,
         cx: &mut Context<ListState<Self>>,
     ) {
-        self.selected_ix = ix.map(|i| i.row).unwrap_or(0);
+        self.selected_ix = ix.unwrap_or_default();
         cx.notify();
     }
 
@@ -163,8 +211,8 @@ impl CodeActionMenu {
             let view = cx.entity();
             let menu = MenuDelegate {
                 menu: view,
-                items: vec![],
-                selected_ix: 0,
+                sections: vec![],
+                selected_ix: IndexPath::default(),
             };
 
             let list = cx.new(|cx| ListState::new(menu, window, cx));
@@ -309,7 +357,7 @@ impl Render for CodeActionMenu {
             return Empty.into_any_element();
         }
 
-        if self.list.read(cx).delegate().items.is_empty() {
+        if self.list.read(cx).delegate().is_empty() {
             self.open = false;
             return Empty.into_any_element();
         }
@@ -0,0 +1,439 @@
+use std::rc::Rc;
+
+use gpui::{
+    App, AppContext as _, ClickEvent, Context, Entity, FocusHandle, Focusable,
+    InteractiveElement as _, IntoElement, KeyBinding, ParentElement as _, Pixels, Render,
+    SharedString, StatefulInteractiveElement as _, Styled as _, Window, anchored, deferred, div,
+    point, prelude::FluentBuilder as _, px,
+};
+use lsp_types::{LocationLink, Uri};
+use ropey::Rope;
+use rust_i18n::t;
+
+use crate::{
+    ActiveTheme as _, IconName, Sizable as _, StyledExt as _,
+    actions::{Cancel, Confirm, SelectDown, SelectUp},
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    input::{Input, InputState, RopeExt as _, mode::InputMode},
+    v_flex,
+};
+
+const CONTEXT: &str = "DefinitionPeek";
+/// The height of the peek view.
+const PEEK_HEIGHT: Pixels = px(260.);
+/// The width of the locations list, shown when there are multiple definitions.
+const LIST_WIDTH: Pixels = px(240.);
+/// The number of lines to show above the target line in the preview.
+const PREVIEW_CONTEXT_LINES: usize = 3;
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("up", SelectUp, Some(CONTEXT)),
+        KeyBinding::new("down", SelectDown, Some(CONTEXT)),
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+        KeyBinding::new("escape", Cancel, Some(CONTEXT)),
+    ]);
+}
+
+/// An inline "Peek Definition" view, shown below the line of the symbol.
+///
+/// It has a preview editor of the selected definition, and a list of the locations when
+/// there are multiple definitions.
+///
+/// Only the definitions in the document of the editor are previewed, the others are shown by
+/// the URI, and opened by [`InputState::go_to_definition`].
+pub(crate) struct DefinitionPeek {
+    editor: Entity<InputState>,
+    focus_handle: FocusHandle,
+    /// The byte offset of the symbol, the peek is shown below its line.
+    pub(crate) offset: usize,
+    locations: Rc<Vec<LocationLink>>,
+    /// The URI of the document of the editor, see [`crate::input::Lsp::document_uri`].
+    document_uri: Option<Uri>,
+    selected_ix: usize,
+    preview: Entity<InputState>,
+}
+
+impl DefinitionPeek {
+    pub(crate) fn new(
+        editor: Entity<InputState>,
+        offset: usize,
+        locations: Vec<LocationLink>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        let (language, text, tab_size, document_uri) = {
+            let editor = editor.read(cx);
+            let language: SharedString = match &editor.mode {
+                InputMode::CodeEditor { language, .. } => language.clone(),
+                _ => "text".into(),
+            };
+            (
+                language,
+                editor.text.to_string(),
+                editor.mode.tab_size(),
+                editor.lsp.document_uri.clone(),
+            )
+        };
+
+        let preview = cx.new(|cx| {
+            InputState::new(window, cx)
+                .code_editor(language)
+                .tab_size(tab_size)
+                .soft_wrap(false)
+                .highlight_occurrences(false)
+                .default_value(text)
+        });
+
+        cx.new(|cx| {
+            let mut this = Self {
+                editor,
+                focus_handle: cx.focus_handle(),
+                offset,
+                locations: Rc::new(locations),
+                document_uri,
+                selected_ix: 0,
+                preview,
+            };
+            this.select(0, window, cx);
+            this
+        })
+    }
+
+    fn selected_location(&self) -> Option<&LocationLink> {
+        self.locations.get(self.selected_ix)
+    }
+
+    /// Select the location at `ix`, and show it in the preview if it is in the document.
+    fn select(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_ix = ix.min(self.locations.len().saturating_sub(1));
+        let Some(location) = self.selected_location().cloned() else {
+            return;
+        };
+        if !is_in_document(&location, self.document_uri.as_ref()) {
+            cx.notify();
+            return;
+        }
+
+        let line_height = self
+            .editor
+            .read(cx)
+            .line_height()
+            .unwrap_or(window.line_height());
+        self.preview.update(cx, |preview, cx| {
+            let range = location.target_selection_range;
+            let start = preview.text.position_to_offset(&range.start);
+            let end = preview.text.position_to_offset(&range.end);
+            preview.set_selected_range(start..end, cx);
+
+            let top_line = (range.start.line as usize).saturating_sub(PREVIEW_CONTEXT_LINES);
+            preview.set_scroll_offset(point(px(0.), -line_height * top_line as f32), cx);
+        });
+        cx.notify();
+    }
+
+    fn on_action_select_up(&mut self, _: &SelectUp, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = if self.selected_ix == 0 {
+            self.locations.len().saturating_sub(1)
+        } else {
+            self.selected_ix - 1
+        };
+        self.select(ix, window, cx);
+    }
+
+    fn on_action_select_down(
+        &mut self,
+        _: &SelectDown,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ix = if self.selected_ix + 1 >= self.locations.len() {
+            0
+        } else {
+            self.selected_ix + 1
+        };
+        self.select(ix, window, cx);
+    }
+
+    fn on_action_confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.go_to_selected(window, cx);
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor.update(cx, |editor, cx| {
+            editor.close_definition_peek(window, cx);
+        });
+    }
+
+    fn go_to_selected(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(location) = self.selected_location().cloned() else {
+            return;
+        };
+
+        self.editor.update(cx, |editor, cx| {
+            editor.close_definition_peek(window, cx);
+            editor.go_to_definition(&location, window, cx);
+        });
+    }
+
+    /// Returns the label of the location, e.g.: `main.rs:10`.
+    fn location_label(location: &LocationLink) -> SharedString {
+        let path = location.target_uri.path().as_str();
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        format!(
+            "{}:{}",
+            file_name,
+            location.target_selection_range.start.line + 1
+        )
+        .into()
+    }
+
+    fn render_location(
+        &self,
+        ix: usize,
+        location: &LocationLink,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let selected = ix == self.selected_ix;
+        let detail = location_detail(
+            location,
+            &self.preview.read(cx).text,
+            self.document_uri.as_ref(),
+        );
+
+        v_flex()
+            .id(ix)
+            .px_2()
+            .py_1()
+            .gap_0p5()
+            .cursor_pointer()
+            .when(selected, |this| this.bg(cx.theme().accent))
+            .hover(|this| this.bg(cx.theme().accent.opacity(0.5)))
+            .child(
+                div()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(Self::location_label(location)),
+            )
+            .child(div().truncate().child(detail))
+            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                if event.click_count() > 1 {
+                    this.go_to_selected(window, cx);
+                } else {
+                    this.select(ix, window, cx);
+                }
+            }))
+    }
+}
+
+impl Focusable for DefinitionPeek {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DefinitionPeek {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let position = {
+            let editor = self.editor.read(cx);
+            match (editor.last_layout.as_ref(), editor.last_bounds) {
+                (Some(last_layout), Some(last_bounds)) => {
+                    let (_, _, pos) = editor.line_and_position_for_offset(self.offset);
+                    pos.map(|pos| {
+                        point(
+                            editor.input_bounds.left(),
+                            last_bounds.origin.y + pos.y + last_layout.line_height,
+                        )
+                    })
+                }
+                _ => None,
+            }
+        };
+
+        // The line of the symbol is scrolled out of the viewport.
+        let Some(position) = position else {
+            return div().into_any_element();
+        };
+
+        let width = self.editor.read(cx).input_bounds.size.width;
+        let has_list = self.locations.len() > 1;
+        let title = self
+            .selected_location()
+            .map(Self::location_label)
+            .unwrap_or_default();
+        // The definition in another document can't be previewed, show its URI instead.
+        let external_uri = self
+            .selected_location()
+            .filter(|location| !is_in_document(location, self.document_uri.as_ref()))
+            .map(|location| location.target_uri.to_string());
+
+        deferred(
+            anchored().position(position).snap_to_window().child(
+                v_flex()
+                    .id("definition-peek")
+                    .key_context(CONTEXT)
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::on_action_select_up))
+                    .on_action(cx.listener(Self::on_action_select_down))
+                    .on_action(cx.listener(Self::on_action_confirm))
+                    .on_action(cx.listener(Self::on_action_cancel))
+                    .occlude()
+                    .w(width)
+                    .h(PEEK_HEIGHT)
+                    .text_xs()
+                    .bg(cx.theme().editor_background())
+                    .border_y_1()
+                    .border_color(cx.theme().primary)
+                    .shadow_md()
+                    .child(
+                        h_flex()
+                            .id("header")
+                            .flex_none()
+                            .justify_between()
+                            .px_2()
+                            .py_1()
+                            .gap_2()
+                            .bg(cx.theme().muted)
+                            .child(
+                                h_flex()
+                                    .id("title")
+                                    .gap_2()
+                                    .cursor_pointer()
+                                    .child(title)
+                                    .when(has_list, |this| {
+                                        this.child(
+                                            div().text_color(cx.theme().muted_foreground).child(
+                                                t!(
+                                                    "Input.Definitions",
+                                                    count = self.locations.len()
+                                                )
+                                                .to_string(),
+                                            ),
+                                        )
+                                    })
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.go_to_selected(window, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("close")
+                                    .icon(IconName::Close)
+                                    .ghost()
+                                    .xsmall()
+                                    .tab_stop(false)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.on_action_cancel(&Cancel, window, cx);
+                                    })),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .flex_1()
+                            .overflow_hidden()
+                            .child(div().flex_1().h_full().overflow_hidden().map(|this| {
+                                match external_uri {
+                                    Some(uri) => this.child(
+                                        v_flex()
+                                            .id("external")
+                                            .size_full()
+                                            .items_center()
+                                            .justify_center()
+                                            .p_2()
+                                            .cursor_pointer()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(uri)
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.go_to_selected(window, cx);
+                                            })),
+                                    ),
+                                    None => this.child(
+                                        Input::new(&self.preview)
+                                            .appearance(false)
+                                            .h_full()
+                                            .text_xs(),
+                                    ),
+                                }
+                            }))
+                            .when(has_list, |this| {
+                                this.child(
+                                    v_flex()
+                                        .id("locations")
+                                        .flex_none()
+                                        .w(LIST_WIDTH)
+                                        .h_full()
+                                        .overflow_y_scroll()
+                                        .border_l_1()
+                                        .border_color(cx.theme().border)
+                                        .children(self.locations.clone().iter().enumerate().map(
+                                            |(ix, location)| self.render_location(ix, location, cx),
+                                        )),
+                                )
+                            }),
+                    ),
+            ),
+        )
+        .with_priority(1)
+        .into_any_element()
+    }
+}
+
+/// Returns true if the `location` is in the document of the `document_uri`, to preview it inline.
+fn is_in_document(location: &LocationLink, document_uri: Option<&Uri>) -> bool {
+    document_uri == Some(&location.target_uri)
+}
+
+/// Returns the detail of the location in the list, the target line if it is in the document
+/// of the `text`, otherwise the URI.
+fn location_detail(location: &LocationLink, text: &Rope, document_uri: Option<&Uri>) -> String {
+    if !is_in_document(location, document_uri) {
+        return location.target_uri.to_string();
+    }
+
+    text.slice_line(location.target_selection_range.start.line as usize)
+        .to_string()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use lsp_types::{LocationLink, Position, Range, Uri};
+    use ropey::Rope;
+
+    use super::{is_in_document, location_detail};
+
+    fn location(uri: &str, line: u32) -> LocationLink {
+        let range = Range::new(Position::new(line, 4), Position::new(line, 7));
+        LocationLink {
+            origin_selection_range: None,
+            target_uri: Uri::from_str(uri).unwrap(),
+            target_range: range,
+            target_selection_range: range,
+        }
+    }
+
+    #[test]
+    fn test_cross_document_location() {
+        let text = Rope::from("mod a;\n    fn foo() {}\n");
+        let document_uri = Uri::from_str("file:///src/main.rs").unwrap();
+        let local = location("file:///src/main.rs", 1);
+        let other = location("file:///src/lib.rs", 20);
+
+        assert!(is_in_document(&local, Some(&document_uri)));
+        assert!(!is_in_document(&other, Some(&document_uri)));
+        // The document is unknown, so nothing can be previewed.
+        assert!(!is_in_document(&local, None));
+
+        assert_eq!(
+            location_detail(&local, &text, Some(&document_uri)),
+            "fn foo() {}"
+        );
+        // The line 20 of the other document is not read from the text of the editor.
+        assert_eq!(
+            location_detail(&other, &text, Some(&document_uri)),
+            "file:///src/lib.rs"
+        );
+    }
+}
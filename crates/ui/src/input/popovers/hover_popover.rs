@@ -74,6 +74,7 @@ pub(crate) struct Popover {
     editor: Entity<InputState>,
     range: Range<usize>,
     width_limit: Range<Pixels>,
+    sticky: bool,
     content_builder: Box<dyn Fn(&mut Window, &mut App) -> AnyElement>,
 }
 
@@ -100,10 +101,18 @@ impl Popover {
             range,
             style: StyleRefinement::default(),
             width_limit: px(200.)..px(500.),
+            sticky: false,
             content_builder: Box::new(move |window, cx| (f)(window, cx).into_any_element()),
         }
     }
 
+    /// Keep the popover open when the mouse moves out or clicks outside,
+    /// the owner is responsible to hide it.
+    pub fn sticky(mut self) -> Self {
+        self.sticky = true;
+        self
+    }
+
     /// Get the bounds of the range in the editor, if it is visible.
     fn trigger_bounds(&self, cx: &App) -> Option<Bounds<Pixels>> {
         let editor = self.editor.read(cx);
@@ -266,6 +275,9 @@ impl Element for Popover {
         };
 
         popover.paint(window, cx);
+        if self.sticky {
+            return;
+        }
 
         let editor = self.editor.clone();
         // Mouse down out to hide.
@@ -1,12 +1,18 @@
 mod code_action_menu;
 mod completion_menu;
+mod definition_peek;
 mod diagnostic_popover;
 mod hover_popover;
+mod rename_editor;
+mod signature_help_popover;
 
 pub(crate) use code_action_menu::*;
 pub(crate) use completion_menu::*;
+pub(crate) use definition_peek::DefinitionPeek;
 pub(crate) use diagnostic_popover::*;
 pub(crate) use hover_popover::*;
+pub(crate) use rename_editor::RenameEditor;
+pub(crate) use signature_help_popover::*;
 
 use gpui::{
     App, Div, ElementId, Entity, InteractiveElement as _, IntoElement, SharedString, Stateful,
@@ -18,6 +24,10 @@ use crate::{
     text::{TextView, TextViewStyle},
 };
 
+pub(crate) fn init(cx: &mut App) {
+    definition_peek::init(cx);
+}
+
 pub(crate) enum ContextMenu {
     Completion(Entity<CompletionMenu>),
     CodeAction(Entity<CodeActionMenu>),
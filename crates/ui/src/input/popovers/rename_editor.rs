@@ -0,0 +1,243 @@
+use std::ops::Range;
+
+use gpui::{
+    App, AppContext as _, Context, Entity, InteractiveElement as _, IntoElement,
+    ParentElement as _, Render, SharedString, Styled as _, Subscription, Task, Window, anchored,
+    deferred, div, point, px,
+};
+use instant::Duration;
+use lsp_types::TextEdit;
+use rust_i18n::t;
+
+use crate::{
+    ActiveTheme as _, Sizable as _, StyledExt as _,
+    input::{Escape, Input, InputEvent, InputState},
+    v_flex,
+};
+
+/// The min width of the rename editor.
+const MIN_WIDTH: gpui::Pixels = px(160.);
+
+/// An inline editor over the symbol to rename, the edits of the new name are previewed
+/// in the editor as the occurrences highlights.
+pub(crate) struct RenameEditor {
+    editor: Entity<InputState>,
+    /// The byte offset of the cursor when the rename started.
+    offset: usize,
+    /// The byte range of the symbol to rename.
+    range: Range<usize>,
+    old_name: SharedString,
+    input: Entity<InputState>,
+    /// The new name and the edits of the last preview.
+    preview: Option<(SharedString, Vec<TextEdit>)>,
+    _preview_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl RenameEditor {
+    pub(crate) fn new(
+        editor: Entity<InputState>,
+        offset: usize,
+        range: Range<usize>,
+        old_name: SharedString,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        cx.new(|cx| {
+            let input = cx.new(|cx| {
+                let mut input = InputState::new(window, cx).default_value(old_name.clone());
+                input.select_all(&crate::input::SelectAll, window, cx);
+                input
+            });
+
+            let _subscriptions = vec![cx.subscribe_in(&input, window, Self::on_input_event)];
+            let mut this = Self {
+                editor,
+                offset,
+                range,
+                old_name,
+                input,
+                preview: None,
+                _preview_task: Task::ready(()),
+                _subscriptions,
+            };
+            this.update_preview(Duration::ZERO, window, cx);
+            this
+        })
+    }
+
+    pub(crate) fn focus(&self, window: &mut Window, cx: &mut App) {
+        self.input.read(cx).focus_handle.focus(window, cx);
+    }
+
+    fn on_input_event(
+        &mut self,
+        _: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::Change => self.update_preview(Duration::from_millis(150), window, cx),
+            InputEvent::PressEnter { .. } => self.confirm(window, cx),
+            InputEvent::Blur => self.cancel(window, cx),
+            InputEvent::Focus | InputEvent::MaxLengthExceeded => {}
+        }
+    }
+
+    fn new_name(&self, cx: &App) -> SharedString {
+        self.input.read(cx).value().trim().to_string().into()
+    }
+
+    /// Request the rename edits of the new name to preview, after the `debounce`.
+    fn update_preview(&mut self, debounce: Duration, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(provider) = self.editor.read(cx).lsp.rename_provider.clone() else {
+            return;
+        };
+
+        let new_name = self.new_name(cx);
+        if new_name.is_empty() {
+            self.preview = None;
+            self.editor
+                .update(cx, |editor, cx| editor.set_rename_preview(&[], cx));
+            return;
+        }
+
+        let editor = self.editor.clone();
+        let offset = self.offset;
+        self._preview_task = cx.spawn_in(window, async move |this, cx| {
+            if !debounce.is_zero() {
+                cx.background_executor().timer(debounce).await;
+            }
+
+            let Ok(task) = cx.update(|window, cx| {
+                let text = editor.read(cx).text.clone();
+                provider.rename(&text, offset, &new_name, window, cx)
+            }) else {
+                return;
+            };
+            let Ok(edits) = task.await else {
+                return;
+            };
+
+            _ = this.update(cx, |this, cx| {
+                editor.update(cx, |editor, cx| editor.set_rename_preview(&edits, cx));
+                this.preview = Some((new_name, edits));
+                cx.notify();
+            });
+        });
+    }
+
+    /// Apply the rename edits of the new name, then close.
+    fn confirm(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let new_name = self.new_name(cx);
+        if new_name.is_empty() || new_name == self.old_name {
+            self.cancel(window, cx);
+            return;
+        }
+
+        // Use the edits of the preview, if it is up to date.
+        if let Some((name, edits)) = self.preview.take() {
+            if name == new_name {
+                self.editor.update(cx, |editor, cx| {
+                    editor.close_rename(window, cx);
+                    editor.apply_rename(edits, window, cx);
+                });
+                return;
+            }
+        }
+
+        let Some(provider) = self.editor.read(cx).lsp.rename_provider.clone() else {
+            return;
+        };
+        let text = self.editor.read(cx).text.clone();
+        let task = provider.rename(&text, self.offset, &new_name, window, cx);
+        let editor = self.editor.clone();
+        self._preview_task = cx.spawn_in(window, async move |_, cx| {
+            let Ok(edits) = task.await else {
+                return;
+            };
+
+            _ = editor.update_in(cx, |editor, window, cx| {
+                editor.close_rename(window, cx);
+                editor.apply_rename(edits, window, cx);
+            });
+        });
+    }
+
+    fn cancel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor.update(cx, |editor, cx| {
+            editor.close_rename(window, cx);
+        });
+    }
+
+    fn on_action_escape(&mut self, _: &Escape, window: &mut Window, cx: &mut Context<Self>) {
+        self.cancel(window, cx);
+    }
+}
+
+impl Render for RenameEditor {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let bounds = {
+            let editor = self.editor.read(cx);
+            match (editor.last_layout.as_ref(), editor.last_bounds) {
+                (Some(last_layout), Some(last_bounds)) => {
+                    let (_, _, start) = editor.line_and_position_for_offset(self.range.start);
+                    let (_, _, end) = editor.line_and_position_for_offset(self.range.end);
+                    start.map(|start| {
+                        let width = end.map(|end| end.x - start.x).unwrap_or_default();
+                        (
+                            last_bounds.origin + start,
+                            width.max(MIN_WIDTH),
+                            last_layout.line_height,
+                        )
+                    })
+                }
+                _ => None,
+            }
+        };
+
+        // The symbol is scrolled out of the viewport.
+        let Some((position, width, line_height)) = bounds else {
+            return div().into_any_element();
+        };
+
+        let edits_count = self
+            .preview
+            .as_ref()
+            .map(|(_, edits)| edits.len())
+            .unwrap_or_default();
+
+        deferred(
+            anchored()
+                .position(position - point(px(5.), px(5.)))
+                .snap_to_window_with_margin(px(8.))
+                .child(
+                    v_flex()
+                        .id("rename-editor")
+                        .on_action(cx.listener(Self::on_action_escape))
+                        .occlude()
+                        .w(width + px(10.))
+                        .p_1()
+                        .gap_1()
+                        .popover_style(cx)
+                        .shadow_md()
+                        .child(
+                            Input::new(&self.input)
+                                .xsmall()
+                                .h(line_height + px(2.))
+                                .font_family(cx.theme().mono_font_family.clone()),
+                        )
+                        .child(
+                            div()
+                                .px_1()
+                                .text_xs()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(t!("Input.Rename Edits", count = edits_count).to_string()),
+                        ),
+                ),
+        )
+        .with_priority(1)
+        .into_any_element()
+    }
+}
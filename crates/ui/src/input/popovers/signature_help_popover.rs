@@ -0,0 +1,168 @@
+use gpui::{
+    App, AppContext as _, Context, Entity, FontWeight, HighlightStyle, IntoElement,
+    ParentElement as _, Render, Styled, StyledText, Window, div, prelude::FluentBuilder as _,
+};
+use lsp_types::{Documentation, SignatureHelp};
+
+use crate::{
+    ActiveTheme as _, h_flex,
+    input::{
+        InputState, active_parameter_range,
+        popovers::{Popover, render_markdown},
+    },
+    v_flex,
+};
+
+/// The popup to show the signatures of the function call, with the active parameter bolded.
+pub struct SignatureHelpPopover {
+    editor: Entity<InputState>,
+    /// The byte offset of the cursor when the signature help is requested.
+    offset: usize,
+    pub(crate) signature_help: SignatureHelp,
+    /// The index of the signature to show, in the overloads.
+    active_signature: usize,
+}
+
+impl SignatureHelpPopover {
+    pub fn new(
+        editor: Entity<InputState>,
+        offset: usize,
+        signature_help: SignatureHelp,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        cx.new(|_| {
+            let mut this = Self {
+                editor,
+                offset,
+                signature_help: SignatureHelp {
+                    signatures: vec![],
+                    active_signature: None,
+                    active_parameter: None,
+                },
+                active_signature: 0,
+            };
+            this.set_signature_help(offset, signature_help);
+            this
+        })
+    }
+
+    fn set_signature_help(&mut self, offset: usize, signature_help: SignatureHelp) {
+        self.offset = offset;
+        self.active_signature = (signature_help.active_signature.unwrap_or(0) as usize)
+            .min(signature_help.signatures.len().saturating_sub(1));
+        self.signature_help = signature_help;
+    }
+
+    /// Update the signature help on retrigger, keep the popup at the same position.
+    pub(crate) fn update_signature_help(
+        &mut self,
+        signature_help: SignatureHelp,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_signature_help(self.offset, signature_help);
+        cx.notify();
+    }
+
+    /// Select the previous (negative `delta`) or next signature, wrapping around.
+    ///
+    /// Returns false if there is only one signature.
+    pub(crate) fn select_signature(&mut self, delta: isize, cx: &mut Context<Self>) -> bool {
+        let len = self.signature_help.signatures.len();
+        if len <= 1 {
+            return false;
+        }
+
+        self.active_signature =
+            (self.active_signature as isize + delta).rem_euclid(len as isize) as usize;
+        self.signature_help.active_signature = Some(self.active_signature as u32);
+        cx.notify();
+        true
+    }
+}
+
+fn documentation_text(documentation: &Documentation) -> String {
+    match documentation {
+        Documentation::String(s) => s.clone(),
+        Documentation::MarkupContent(content) => content.value.clone(),
+    }
+}
+
+impl Render for SignatureHelpPopover {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(signature) = self
+            .signature_help
+            .signatures
+            .get(self.active_signature)
+            .cloned()
+        else {
+            return div().into_any_element();
+        };
+
+        let count = self.signature_help.signatures.len();
+        let position = self.active_signature + 1;
+        let active_parameter = signature
+            .active_parameter
+            .or(self.signature_help.active_parameter);
+        let parameter_range = active_parameter_range(&signature, active_parameter);
+        let highlights = parameter_range
+            .map(|range| {
+                vec![(
+                    range,
+                    HighlightStyle {
+                        font_weight: Some(FontWeight::BOLD),
+                        color: Some(cx.theme().foreground),
+                        ..Default::default()
+                    },
+                )]
+            })
+            .unwrap_or_default();
+
+        // The documentation of the active parameter, or the signature.
+        let documentation = active_parameter
+            .and_then(|ix| signature.parameters.as_ref()?.get(ix as usize))
+            .and_then(|parameter| parameter.documentation.as_ref())
+            .or(signature.documentation.as_ref())
+            .map(documentation_text)
+            .filter(|text| !text.trim().is_empty());
+
+        let muted_foreground = cx.theme().muted_foreground;
+        let mono_font_family = cx.theme().mono_font_family.clone();
+
+        Popover::new(
+            "signature-help-popover",
+            self.editor.clone(),
+            self.offset..self.offset,
+            move |window, cx| {
+                v_flex()
+                    .gap_1()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .px_1()
+                            .when(count > 1, |this| {
+                                this.child(
+                                    div()
+                                        .flex_none()
+                                        .text_color(muted_foreground)
+                                        .child(format!("{}/{}", position, count)),
+                                )
+                            })
+                            .child(
+                                div()
+                                    .font_family(mono_font_family.clone())
+                                    .text_color(muted_foreground)
+                                    .child(
+                                        StyledText::new(signature.label.clone())
+                                            .with_highlights(highlights.clone()),
+                                    ),
+                            ),
+                    )
+                    .when_some(documentation.clone(), |this, documentation| {
+                        this.child(render_markdown("documentation", documentation, window, cx))
+                    })
+            },
+        )
+        .sticky()
+        .into_any_element()
+    }
+}
@@ -1,5 +1,6 @@
-use std::ops::Range;
+use std::{borrow::Cow, ops::Range};
 
+use anyhow::{Result, anyhow};
 use ropey::{LineType, Rope, RopeSlice};
 use sum_tree::Bias;
 
@@ -83,6 +84,173 @@ impl<'a> Iterator for RopeLines<'a> {
 impl std::iter::ExactSizeIterator for RopeLines<'_> {}
 impl std::iter::FusedIterator for RopeLines<'_> {}
 
+/// The line ending of the text, see [`RopeExt::line_ending`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`, used on Unix and macOS.
+    #[default]
+    Lf,
+    /// `\r\n`, used on Windows.
+    CrLf,
+    /// `\r`, used on the classic Mac OS.
+    Cr,
+}
+
+impl LineEnding {
+    /// Returns the line ending as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+            Self::Cr => "\r",
+        }
+    }
+
+    /// Replace all the line endings in the `text` with this one.
+    ///
+    /// ```
+    /// use gpui_component::LineEnding;
+    /// assert_eq!(LineEnding::Lf.normalize("a\r\nb\rc\n"), "a\nb\nc\n");
+    /// assert_eq!(LineEnding::CrLf.normalize("a\nb"), "a\r\nb");
+    /// ```
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut chars = text.char_indices().peekable();
+        let mut normalized: Option<String> = None;
+        let mut last = 0;
+        while let Some((ix, c)) = chars.next() {
+            let len = match c {
+                '\r' if chars.peek().is_some_and(|(_, c)| *c == '\n') => {
+                    chars.next();
+                    2
+                }
+                '\r' | '\n' => 1,
+                _ => continue,
+            };
+            if &text[ix..ix + len] == self.as_str() {
+                if let Some(normalized) = normalized.as_mut() {
+                    normalized.push_str(&text[last..ix + len]);
+                    last = ix + len;
+                }
+                continue;
+            }
+
+            let normalized = normalized.get_or_insert_with(|| String::with_capacity(text.len()));
+            normalized.push_str(&text[last..ix]);
+            normalized.push_str(self.as_str());
+            last = ix + len;
+        }
+
+        match normalized {
+            Some(mut normalized) => {
+                normalized.push_str(&text[last..]);
+                Cow::Owned(normalized)
+            }
+            None => Cow::Borrowed(text),
+        }
+    }
+}
+
+/// The number of each kind of the line endings in a text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct LineEndingCounts {
+    lf: usize,
+    crlf: usize,
+    cr: usize,
+}
+
+impl LineEndingCounts {
+    fn new(rope: &Rope) -> Self {
+        let mut counts = Self::default();
+        let mut prev_cr = false;
+        for chunk in rope.chunks() {
+            for b in chunk.bytes() {
+                match b {
+                    b'\n' if prev_cr => {
+                        counts.cr -= 1;
+                        counts.crlf += 1;
+                    }
+                    b'\n' => counts.lf += 1,
+                    b'\r' => counts.cr += 1,
+                    _ => {}
+                }
+                prev_cr = b == b'\r';
+            }
+        }
+        counts
+    }
+}
+
+/// The result of [`RopeExt::apply_edits`], to map the offsets in the old text to the new text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RopeEdits {
+    /// The old byte ranges and the byte lengths of the new texts, sorted by the range.
+    edits: Vec<(Range<usize>, usize)>,
+}
+
+impl RopeEdits {
+    /// Returns true if there are no edits.
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Map the byte `offset` in the old text to the offset in the new text.
+    ///
+    /// The offset inside a replaced range, or at an insertion, is moved to the start of
+    /// the new text with `Bias::Left`, or to the end with `Bias::Right`.
+    ///
+    /// ```
+    /// use gpui_component::{Rope, RopeExt};
+    /// use sum_tree::Bias;
+    ///
+    /// let mut rope = Rope::from("Hello World");
+    /// let edits = rope.apply_edits(&[(0..5, "Hi"), (11..11, "!")]).unwrap();
+    /// assert_eq!(rope.to_string(), "Hi World!");
+    /// assert_eq!(edits.map_offset(6, Bias::Left), 3);
+    /// assert_eq!(edits.map_offset(11, Bias::Left), 8);
+    /// assert_eq!(edits.map_offset(11, Bias::Right), 9);
+    /// ```
+    pub fn map_offset(&self, offset: usize, bias: Bias) -> usize {
+        let mut delta: isize = 0;
+        for (range, new_len) in &self.edits {
+            if offset < range.start || (offset == range.start && !range.is_empty()) {
+                break;
+            }
+
+            let new_start = (range.start as isize + delta) as usize;
+            if offset < range.end || (offset == range.end && range.is_empty()) {
+                return match bias {
+                    Bias::Left => new_start,
+                    Bias::Right => new_start + new_len,
+                };
+            }
+
+            delta += *new_len as isize - range.len() as isize;
+        }
+
+        (offset as isize + delta) as usize
+    }
+
+    /// Map the byte `range` in the old text to the range in the new text.
+    ///
+    /// The range is expanded to cover the new texts of the edits at its boundaries.
+    pub fn map_range(&self, range: &Range<usize>) -> Range<usize> {
+        self.map_offset(range.start, Bias::Left)..self.map_offset(range.end, Bias::Right)
+    }
+
+    /// Returns the byte ranges of the new texts in the new text.
+    pub fn new_ranges(&self) -> Vec<Range<usize>> {
+        let mut delta: isize = 0;
+        self.edits
+            .iter()
+            .map(|(range, new_len)| {
+                let start = (range.start as isize + delta) as usize;
+                delta += *new_len as isize - range.len() as isize;
+                start..start + new_len
+            })
+            .collect()
+    }
+}
+
 /// An extension trait for [`Rope`] to provide additional utility methods.
 pub trait RopeExt {
     /// Start offset of the line at the given row (0-based) index.
@@ -278,6 +446,59 @@ pub trait RopeExt {
     /// assert_eq!(rope.offset_to_char_index(4), 3);
     /// ```
     fn offset_to_char_index(&self, offset: usize) -> usize;
+
+    /// Get the byte offset from the given LSP [`Position`] (0-based).
+    ///
+    /// The column is in UTF-16 code units, and clipped to the end of the line.
+    ///
+    /// ```
+    /// use gpui_component::{Rope, RopeExt, input::Position};
+    /// let rope = Rope::from("a🎉b\n中文");
+    /// assert_eq!(rope.position_utf16_to_offset(&Position::new(0, 3)), "a🎉".len());
+    /// assert_eq!(rope.position_utf16_to_offset(&Position::new(1, 1)), "a🎉b\n中".len());
+    /// ```
+    fn position_utf16_to_offset(&self, pos: &Position) -> usize;
+
+    /// Get the LSP [`Position`] (0-based) from the given byte offset.
+    ///
+    /// The column is in UTF-16 code units.
+    fn offset_to_position_utf16(&self, offset: usize) -> Position;
+
+    /// Convert the LSP [`lsp_types::Range`] to the byte range, see [`Self::position_utf16_to_offset`].
+    fn lsp_range_to_range(&self, range: &lsp_types::Range) -> Range<usize> {
+        self.position_utf16_to_offset(&range.start)..self.position_utf16_to_offset(&range.end)
+    }
+
+    /// Convert the byte range to the LSP [`lsp_types::Range`], see [`Self::offset_to_position_utf16`].
+    fn range_to_lsp_range(&self, range: &Range<usize>) -> lsp_types::Range {
+        lsp_types::Range::new(
+            self.offset_to_position_utf16(range.start),
+            self.offset_to_position_utf16(range.end),
+        )
+    }
+
+    /// Apply a batch of edits of the byte ranges in the current text, all at once.
+    ///
+    /// The edits can be in any order, but must not overlap. If any of the edits is
+    /// invalid (overlapping, out of bounds or not on the char boundaries), an error
+    /// is returned and the text is not changed.
+    ///
+    /// The returned [`RopeEdits`] maps the offsets in the old text to the new text.
+    ///
+    /// ```
+    /// use gpui_component::{Rope, RopeExt};
+    /// let mut rope = Rope::from("let a = 1;\nlet b = a;");
+    /// rope.apply_edits(&[(15..16, "c"), (4..5, "c"), (19..20, "c")]).unwrap();
+    /// assert_eq!(rope.to_string(), "let c = 1;\nlet c = c;");
+    /// assert!(rope.apply_edits(&[(0..5, ""), (4..6, "")]).is_err());
+    /// ```
+    fn apply_edits<T: AsRef<str>>(&mut self, edits: &[(Range<usize>, T)]) -> Result<RopeEdits>;
+
+    /// Returns the most used line ending in the text, default is [`LineEnding::Lf`].
+    fn line_ending(&self) -> LineEnding;
+
+    /// Returns true if the text has more than one kind of line endings.
+    fn has_mixed_line_endings(&self) -> bool;
 }
 
 impl RopeExt for Rope {
@@ -454,11 +675,86 @@ impl RopeExt for Rope {
         let offset = self.clip_offset(offset, Bias::Right);
         self.slice(..offset).chars().count()
     }
+
+    fn position_utf16_to_offset(&self, pos: &Position) -> usize {
+        let row = pos.line as usize;
+        if row >= self.lines_len() {
+            return self.len();
+        }
+
+        let line = self.slice_line(row);
+        let column = (pos.character as usize).min(line.len_utf16());
+        self.line_start_offset(row) + line.utf16_to_byte_idx(column)
+    }
+
+    fn offset_to_position_utf16(&self, offset: usize) -> Position {
+        let point = self.offset_to_point(offset);
+        let line = self.slice_line(point.row);
+        let character = line.byte_to_utf16_idx(point.column.min(line.len()));
+        Position::new(point.row as u32, character as u32)
+    }
+
+    fn apply_edits<T: AsRef<str>>(&mut self, edits: &[(Range<usize>, T)]) -> Result<RopeEdits> {
+        let mut sorted = edits.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|(range, _)| (range.start, range.end));
+
+        let mut prev_end = 0;
+        for (range, _) in &sorted {
+            if range.start > range.end || range.end > self.len() {
+                return Err(anyhow!("edit range {:?} is out of bounds", range));
+            }
+            if range.start < prev_end {
+                return Err(anyhow!("edit range {:?} overlaps with another edit", range));
+            }
+            if !self.is_char_boundary(range.start) || !self.is_char_boundary(range.end) {
+                return Err(anyhow!(
+                    "edit range {:?} is not on the char boundaries",
+                    range
+                ));
+            }
+            prev_end = range.end;
+        }
+
+        // Apply from the end, so the ranges of the previous edits are not shifted.
+        for (range, new_text) in sorted.iter().rev() {
+            self.remove(range.clone());
+            self.insert(range.start, new_text.as_ref());
+        }
+
+        Ok(RopeEdits {
+            edits: sorted
+                .into_iter()
+                .map(|(range, new_text)| (range.clone(), new_text.as_ref().len()))
+                .collect(),
+        })
+    }
+
+    fn line_ending(&self) -> LineEnding {
+        let counts = LineEndingCounts::new(self);
+        if counts.crlf > counts.lf && counts.crlf >= counts.cr {
+            LineEnding::CrLf
+        } else if counts.cr > counts.lf && counts.cr > counts.crlf {
+            LineEnding::Cr
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    fn has_mixed_line_endings(&self) -> bool {
+        let counts = LineEndingCounts::new(self);
+        [counts.lf, counts.crlf, counts.cr]
+            .iter()
+            .filter(|count| **count > 0)
+            .count()
+            > 1
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Point;
+    use std::borrow::Cow;
+
+    use super::{LineEnding, Point};
     use ropey::Rope;
     use sum_tree::Bias;
 
@@ -721,4 +1017,110 @@ mod tests {
         assert_eq!(rope.offset_to_char_index(6), 4);
         assert_eq!(rope.offset_to_char_index(10), 5);
     }
+
+    #[test]
+    fn test_position_utf16() {
+        let rope = Rope::from("a🎉b\r\n中文\nRope");
+        assert_eq!(rope.position_utf16_to_offset(&Position::new(0, 0)), 0);
+        assert_eq!(rope.position_utf16_to_offset(&Position::new(0, 1)), 1);
+        assert_eq!(
+            rope.position_utf16_to_offset(&Position::new(0, 3)),
+            "a🎉".len()
+        );
+        assert_eq!(
+            rope.position_utf16_to_offset(&Position::new(1, 1)),
+            "a🎉b\r\n中".len()
+        );
+        // Clip to the line end and the text end.
+        assert_eq!(
+            rope.position_utf16_to_offset(&Position::new(2, 100)),
+            rope.len()
+        );
+        assert_eq!(
+            rope.position_utf16_to_offset(&Position::new(10, 0)),
+            rope.len()
+        );
+
+        assert_eq!(rope.offset_to_position_utf16(0), Position::new(0, 0));
+        assert_eq!(
+            rope.offset_to_position_utf16("a🎉".len()),
+            Position::new(0, 3)
+        );
+        assert_eq!(
+            rope.offset_to_position_utf16("a🎉b\r\n中文".len()),
+            Position::new(1, 2)
+        );
+        assert_eq!(
+            rope.offset_to_position_utf16(rope.len()),
+            Position::new(2, 4)
+        );
+
+        let range = "a🎉".len().."a🎉b\r\n中".len();
+        let lsp_range = rope.range_to_lsp_range(&range);
+        assert_eq!(lsp_range.start, Position::new(0, 3));
+        assert_eq!(lsp_range.end, Position::new(1, 1));
+        assert_eq!(rope.lsp_range_to_range(&lsp_range), range);
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let mut rope = Rope::from("Hello World, 中文");
+        let edits = rope
+            .apply_edits(&[(13..19, "Rope".to_string()), (0..5, "Hi".to_string())])
+            .unwrap();
+        assert_eq!(rope.to_string(), "Hi World, Rope");
+        assert_eq!(edits.new_ranges(), vec![0..2, 10..14]);
+
+        assert_eq!(edits.map_offset(0, Bias::Left), 0);
+        assert_eq!(edits.map_offset(3, Bias::Left), 0);
+        assert_eq!(edits.map_offset(3, Bias::Right), 2);
+        assert_eq!(edits.map_offset(5, Bias::Left), 2);
+        assert_eq!(edits.map_offset(6, Bias::Left), 3);
+        assert_eq!(edits.map_offset(13, Bias::Left), 10);
+        assert_eq!(edits.map_offset(19, Bias::Left), 14);
+        assert_eq!(edits.map_range(&(4..16)), 0..14);
+
+        // Insert at the same offset of a replace
+        let mut rope = Rope::from("abc");
+        let edits = rope.apply_edits(&[(1..1, "X"), (1..2, "Y")]).unwrap();
+        assert_eq!(rope.to_string(), "aXYc");
+        assert_eq!(edits.map_offset(1, Bias::Left), 1);
+        assert_eq!(edits.map_offset(1, Bias::Right), 2);
+        assert_eq!(edits.map_offset(2, Bias::Left), 3);
+
+        // Invalid edits keep the text unchanged.
+        let mut rope = Rope::from("中文");
+        assert!(rope.apply_edits(&[(0..3, ""), (2..4, "")]).is_err());
+        assert!(rope.apply_edits(&[(0..3, ""), (4..6, "")]).is_err());
+        assert!(rope.apply_edits(&[(0..3, ""), (3..7, "")]).is_err());
+        assert_eq!(rope.to_string(), "中文");
+
+        let edits = rope.apply_edits::<&str>(&[]).unwrap();
+        assert!(edits.is_empty());
+        assert_eq!(edits.map_offset(3, Bias::Left), 3);
+    }
+
+    #[test]
+    fn test_line_ending() {
+        assert_eq!(Rope::from("").line_ending(), LineEnding::Lf);
+        assert_eq!(Rope::from("a\nb\n").line_ending(), LineEnding::Lf);
+        assert_eq!(Rope::from("a\r\nb\r\nc\n").line_ending(), LineEnding::CrLf);
+        assert_eq!(Rope::from("a\rb\r").line_ending(), LineEnding::Cr);
+
+        assert!(!Rope::from("a\r\nb\r\n").has_mixed_line_endings());
+        assert!(Rope::from("a\r\nb\n").has_mixed_line_endings());
+        assert!(Rope::from("a\rb\n").has_mixed_line_endings());
+
+        // The CRLF across the chunks
+        let mut rope = Rope::from(format!("{}\r", "a".repeat(2000)).as_str());
+        rope.insert(rope.len(), "\nb");
+        assert_eq!(rope.line_ending(), LineEnding::CrLf);
+        assert!(!rope.has_mixed_line_endings());
+
+        assert_eq!(LineEnding::Lf.normalize("a\nb"), "a\nb");
+        assert!(matches!(LineEnding::Lf.normalize("a\nb"), Cow::Borrowed(_)));
+        assert_eq!(LineEnding::Lf.normalize("a\r\nb\rc"), "a\nb\nc");
+        assert_eq!(LineEnding::CrLf.normalize("a\nb\r\nc\r"), "a\r\nb\r\nc\r\n");
+        assert_eq!(LineEnding::Cr.normalize("a\r\nb\n"), "a\rb\r");
+    }
 }
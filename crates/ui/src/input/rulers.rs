@@ -0,0 +1,67 @@
+use gpui::{Bounds, Context, Path, PathBuilder, Pixels, TextStyle, Window, point, px};
+
+use crate::input::{InputState, LastLayout, element::TextElement};
+
+impl TextElement {
+    /// Layout the vertical rulers at the columns of [`InputState::rulers`].
+    ///
+    /// The `bounds` is the scrolled text bounds, and `input_bounds` is the unscrolled one,
+    /// the rulers span the full height of the input.
+    pub(super) fn layout_rulers(
+        &self,
+        state: &InputState,
+        bounds: &Bounds<Pixels>,
+        input_bounds: &Bounds<Pixels>,
+        last_layout: &LastLayout,
+        text_style: &TextStyle,
+        window: &mut Window,
+    ) -> Option<Path<Pixels>> {
+        if state.rulers.is_empty() || !state.mode.is_multi_line() {
+            return None;
+        }
+
+        let column_width = self.measure_indent_width(text_style, 1, window);
+        let min_x = input_bounds.left() + last_layout.line_number_width;
+        let mut builder = PathBuilder::stroke(px(1.));
+        let mut has_ruler = false;
+
+        for &column in &state.rulers {
+            let x = bounds.left() + last_layout.line_number_width + column_width * column as f32;
+            // Scrolled out of the viewport.
+            if x < min_x || x > input_bounds.right() {
+                continue;
+            }
+
+            builder.move_to(point(x, input_bounds.top()));
+            builder.line_to(point(x, input_bounds.bottom()));
+            has_ruler = true;
+        }
+
+        if !has_ruler {
+            return None;
+        }
+
+        builder.build().ok()
+    }
+}
+
+impl InputState {
+    /// Set the columns to show vertical rulers for multi-line input, default is empty.
+    ///
+    /// For example `vec![80, 120]` to show rulers after the 80th and 120th columns.
+    pub fn rulers(mut self, rulers: impl Into<Vec<usize>>) -> Self {
+        self.rulers = rulers.into();
+        self
+    }
+
+    /// Update the columns of the vertical rulers.
+    pub fn set_rulers(
+        &mut self,
+        rulers: impl Into<Vec<usize>>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.rulers = rulers.into();
+        cx.notify();
+    }
+}
@@ -1,11 +1,12 @@
 use aho_corasick::AhoCorasick;
+use regex::Regex;
 use rust_i18n::t;
 use std::{ops::Range, rc::Rc};
 
 use gpui::{
     App, AppContext as _, Context, Empty, Entity, FocusHandle, Focusable, Half,
     InteractiveElement as _, IntoElement, ParentElement as _, Pixels, Render, Styled, Subscription,
-    Window, actions, div, prelude::FluentBuilder as _,
+    Task, Window, actions, div, prelude::FluentBuilder as _,
 };
 use ropey::Rope;
 
@@ -14,7 +15,7 @@ use crate::{
     button::{Button, ButtonVariants},
     h_flex,
     input::{
-        Enter, Escape, IndentInline, Input, InputEvent, InputState, RopeExt as _, Search,
+        Enter, Escape, IndentInline, Input, InputEvent, InputState, Replace, RopeExt as _, Search,
         movement::MoveDirection,
     },
     label::Label,
@@ -25,15 +26,37 @@ const CONTEXT: &'static str = "SearchPanel";
 
 actions!(input, [Tab]);
 
+/// The options to match the search query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Match the query case sensitively.
+    pub case_sensitive: bool,
+    /// Only match whole words.
+    pub whole_word: bool,
+    /// Treat the query as a regular expression.
+    pub regex: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum SearchQuery {
+    Text(AhoCorasick),
+    Regex(Regex),
+}
+
 #[derive(Debug, Clone)]
 pub struct SearchMatcher {
     text: Rope,
-    pub query: Option<AhoCorasick>,
+    pub query: Option<SearchQuery>,
+    options: SearchOptions,
+    /// The error message if the query is an invalid regular expression.
+    pub(super) error: Option<String>,
 
     pub(super) matched_ranges: Rc<Vec<Range<usize>>>,
     pub(super) current_match_ix: usize,
     /// Is in replacing mode, if true, the next update will update the current match index based on matched ranges.
     replacing: bool,
+    /// Increased when the matches are changed, to drop the outdated background matching.
+    version: usize,
 }
 
 impl SearchMatcher {
@@ -41,9 +64,12 @@ impl SearchMatcher {
         Self {
             text: "".into(),
             query: None,
+            options: SearchOptions::default(),
+            error: None,
             matched_ranges: Rc::new(Vec::new()),
             current_match_ix: 0,
             replacing: false,
+            version: 0,
         }
     }
 
@@ -57,18 +83,35 @@ impl SearchMatcher {
         self.update_matches();
     }
 
+    /// Update source text like [`Self::update`], but find the matches on a background thread.
+    ///
+    /// Returns the version of the matches and the task, apply the result by [`Self::set_matches`]
+    /// if the version is not changed.
+    fn update_in_background(&mut self, text: &Rope, cx: &App) -> (usize, Task<Vec<Range<usize>>>) {
+        self.text = text.clone();
+        self.set_matches(vec![]);
+
+        let version = self.version;
+        let Some(query) = self.query.clone() else {
+            return (version, Task::ready(vec![]));
+        };
+        let options = self.options;
+        let text = text.clone();
+        let task =
+            cx.background_spawn(async move { find_matches(&query, options, &text.to_string()) });
+        (version, task)
+    }
+
     fn update_matches(&mut self) {
-        let mut new_ranges = Vec::new();
-        if let Some(query) = &self.query {
-            let text = self.text.to_string();
-            // FIXME: Use stream find
-            let matches = query.stream_find_iter(text.as_bytes());
+        let new_ranges = match &self.query {
+            Some(query) => find_matches(query, self.options, &self.text.to_string()),
+            None => vec![],
+        };
+        self.set_matches(new_ranges);
+    }
 
-            for query_match in matches.into_iter() {
-                let query_match = query_match.expect("query match for select all action");
-                new_ranges.push(query_match.range());
-            }
-        }
+    fn set_matches(&mut self, new_ranges: Vec<Range<usize>>) {
+        self.version += 1;
         self.matched_ranges = Rc::new(new_ranges);
         if !self.replacing {
             self.current_match_ix = 0;
@@ -81,20 +124,57 @@ impl SearchMatcher {
     }
 
     /// Update the search query and reset the current match index.
-    pub fn update_query(&mut self, query: &str, case_insensitive: bool) {
+    ///
+    /// If the query is an invalid regular expression, there will be no matches
+    /// and the [`SearchMatcher::error`] is set.
+    pub fn update_query(&mut self, query: &str, options: SearchOptions) {
+        self.options = options;
+        self.error = None;
+        self.query = None;
+
         if query.len() > 0 {
-            self.query = Some(
-                AhoCorasick::builder()
-                    .ascii_case_insensitive(case_insensitive)
-                    .build(&[query.to_string()])
-                    .expect("failed to build AhoCorasick query in SearchMatcher"),
-            );
-        } else {
-            self.query = None;
+            if options.regex {
+                match regex::RegexBuilder::new(query)
+                    .case_insensitive(!options.case_sensitive)
+                    .multi_line(true)
+                    .build()
+                {
+                    Ok(regex) => self.query = Some(SearchQuery::Regex(regex)),
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+            } else {
+                self.query = Some(SearchQuery::Text(
+                    AhoCorasick::builder()
+                        .ascii_case_insensitive(!options.case_sensitive)
+                        .build(&[query.to_string()])
+                        .expect("failed to build AhoCorasick query in SearchMatcher"),
+                ));
+            }
         }
         self.update_matches();
     }
 
+    /// Returns the text to replace the matched `range` with.
+    ///
+    /// In regex mode, the capture groups (e.g. `$1`, `${name}`) in `replacement` are expanded,
+    /// the `text` is the searched text as a string, build it once for replacing many matches.
+    fn replacement(&self, text: &str, range: &Range<usize>, replacement: &str) -> String {
+        let Some(SearchQuery::Regex(regex)) = &self.query else {
+            return replacement.to_string();
+        };
+
+        let Some(captures) = regex
+            .captures_at(text, range.start)
+            .filter(|captures| captures.get(0).map(|m| m.range()) == Some(range.clone()))
+        else {
+            return replacement.to_string();
+        };
+
+        let mut new_text = String::new();
+        captures.expand(replacement, &mut new_text);
+        new_text
+    }
+
     /// Returns the number of matches found.
     #[allow(unused)]
     #[inline]
@@ -139,6 +219,44 @@ impl SearchMatcher {
     }
 }
 
+/// Find the ranges of the `query` in the `text`.
+fn find_matches(query: &SearchQuery, options: SearchOptions, text: &str) -> Vec<Range<usize>> {
+    let mut new_ranges = Vec::new();
+    match query {
+        SearchQuery::Text(query) => {
+            // FIXME: Use stream find
+            let matches = query.stream_find_iter(text.as_bytes());
+
+            for query_match in matches.into_iter() {
+                let query_match = query_match.expect("query match for select all action");
+                new_ranges.push(query_match.range());
+            }
+        }
+        SearchQuery::Regex(regex) => {
+            // Skip the empty matches (e.g. `^` or `a*`), they can't be highlighted or replaced.
+            new_ranges.extend(
+                regex
+                    .find_iter(text)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| m.range()),
+            );
+        }
+    }
+
+    if options.whole_word {
+        new_ranges.retain(|range| is_whole_word(text, range));
+    }
+    new_ranges
+}
+
+/// Returns true if the `range` is not surrounded by word characters.
+fn is_whole_word(text: &str, range: &Range<usize>) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let before = text[..range.start].chars().next_back();
+    let after = text[range.end..].chars().next();
+    !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+}
+
 impl Iterator for SearchMatcher {
     type Item = Range<usize>;
 
@@ -170,12 +288,13 @@ pub(super) struct SearchPanel {
     editor: Entity<InputState>,
     search_input: Entity<InputState>,
     replace_input: Entity<InputState>,
-    case_insensitive: bool,
+    options: SearchOptions,
     replace_mode: bool,
     matcher: SearchMatcher,
     input_width: Pixels,
 
     open: bool,
+    _matches_task: Task<()>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -192,13 +311,51 @@ impl InputState {
         });
     }
 
+    /// Like [`Self::update_search`], but find the matches on a background thread for a very large text.
+    pub(super) fn update_search_in_background(&mut self, cx: &mut App) {
+        let Some(search_panel) = self.search_panel.as_ref() else {
+            return;
+        };
+
+        let text = self.text.clone();
+        search_panel.update(cx, |this, cx| {
+            let (version, task) = this.matcher.update_in_background(&text, cx);
+            this._matches_task = cx.spawn(async move |this, cx| {
+                let ranges = task.await;
+                _ = this.update(cx, |this, cx| {
+                    if this.matcher.version != version {
+                        return;
+                    }
+
+                    this.matcher.set_matches(ranges);
+                    this.editor.update(cx, |_, cx| cx.notify());
+                    cx.notify();
+                });
+            });
+        });
+    }
+
     pub(super) fn on_action_search(
         &mut self,
         _: &Search,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.open_search_panel(false, window, cx);
+    }
+
+    pub(super) fn on_action_replace(
+        &mut self,
+        _: &Replace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_search_panel(true, window, cx);
+    }
+
+    fn open_search_panel(&mut self, replace: bool, window: &mut Window, cx: &mut Context<Self>) {
         if !self.searchable {
+            cx.propagate();
             return;
         }
 
@@ -213,7 +370,7 @@ impl InputState {
         search_panel.update(cx, |this, cx| {
             this.editor = editor;
             this.matcher.update(&text);
-            this.show(&selected_text, window, cx);
+            this.show(&selected_text, replace, window, cx);
         });
         self.search_panel = Some(search_panel);
         cx.notify();
@@ -265,11 +422,12 @@ impl SearchPanel {
                 editor,
                 search_input,
                 replace_input,
-                case_insensitive: true,
+                options: SearchOptions::default(),
                 replace_mode: false,
                 matcher: SearchMatcher::new(),
                 open: true,
                 input_width: Pixels::ZERO,
+                _matches_task: Task::ready(()),
                 _subscriptions,
             }
         })
@@ -278,16 +436,11 @@ impl SearchPanel {
     pub(super) fn show(
         &mut self,
         selected_text: &Rope,
+        replace: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         self.open = true;
-        self.search_input
-            .read(cx)
-            .focus_handle
-            .clone()
-            .focus(window, cx);
-
         self.search_input.update(cx, |this, cx| {
             if selected_text.len() > 0 {
                 // Set value will emit to update_search_query
@@ -295,6 +448,22 @@ impl SearchPanel {
             }
             this.select_all(&super::SelectAll, window, cx);
         });
+
+        if replace && self.replaceable(cx) {
+            self.replace_mode = true;
+            self.replace_input
+                .read(cx)
+                .focus_handle
+                .clone()
+                .focus(window, cx);
+        } else {
+            self.search_input
+                .read(cx)
+                .focus_handle
+                .clone()
+                .focus(window, cx);
+        }
+        cx.notify();
     }
 
     fn update_search_query(&mut self, cx: &mut Context<Self>) {
@@ -306,8 +475,7 @@ impl SearchPanel {
             .as_ref()
             .map(|l| l.visible_range_offset.clone());
 
-        self.matcher
-            .update_query(query.as_str(), self.case_insensitive);
+        self.matcher.update_query(query.as_str(), self.options);
 
         if let Some(visible_range_offset) = visible_range_offset {
             self.matcher
@@ -390,6 +558,8 @@ impl SearchPanel {
             .cloned()
         {
             let text_state = self.editor.clone();
+            let text = self.matcher.text.to_string();
+            let new_text = self.matcher.replacement(&text, &range, new_text.as_str());
             let next_match_ix = self.matcher.next_ix().unwrap_or(previous_match_ix);
             let next_range = self.matcher.peek().unwrap_or(range.clone());
             self.matcher.current_match_ix = next_match_ix;
@@ -426,13 +596,23 @@ impl SearchPanel {
             return;
         }
 
+        let text = self.matcher.text.to_string();
+        let replacements = ranges
+            .iter()
+            .map(|range| {
+                (
+                    range.clone(),
+                    self.matcher.replacement(&text, range, new_text.as_str()),
+                )
+            })
+            .collect::<Vec<_>>();
         let editor = self.editor.clone();
         cx.spawn_in(window, async move |_, cx| {
             cx.update(|window, cx| {
                 editor.update(cx, |state, cx| {
                     // Replace from the end to avoid messing up the ranges.
                     let mut rope = state.text.clone();
-                    for range in ranges.iter().rev() {
+                    for (range, new_text) in replacements.iter().rev() {
                         rope.replace(range.clone(), new_text.as_str());
                     }
                     state.replace_text_in_range_silent(
@@ -498,17 +678,49 @@ impl Render for SearchPanel {
                                 Input::new(&self.search_input)
                                     .focus_bordered(false)
                                     .suffix(
-                                        Button::new("case-insensitive")
-                                            .selected(!self.case_insensitive)
-                                            .xsmall()
-                                            .compact()
-                                            .ghost()
-                                            .icon(IconName::CaseSensitive)
-                                            .on_click(cx.listener(|this, _, _, cx| {
-                                                this.case_insensitive = !this.case_insensitive;
-                                                this.update_search_query(cx);
-                                                cx.notify();
-                                            })),
+                                        h_flex()
+                                            .gap_0p5()
+                                            .child(
+                                                Button::new("case-sensitive")
+                                                    .selected(self.options.case_sensitive)
+                                                    .xsmall()
+                                                    .compact()
+                                                    .ghost()
+                                                    .icon(IconName::CaseSensitive)
+                                                    .tooltip(t!("Input.Match Case"))
+                                                    .on_click(cx.listener(|this, _, _, cx| {
+                                                        this.options.case_sensitive =
+                                                            !this.options.case_sensitive;
+                                                        this.update_search_query(cx);
+                                                    })),
+                                            )
+                                            .child(
+                                                Button::new("whole-word")
+                                                    .selected(self.options.whole_word)
+                                                    .xsmall()
+                                                    .compact()
+                                                    .ghost()
+                                                    .icon(IconName::WholeWord)
+                                                    .tooltip(t!("Input.Match Whole Word"))
+                                                    .on_click(cx.listener(|this, _, _, cx| {
+                                                        this.options.whole_word =
+                                                            !this.options.whole_word;
+                                                        this.update_search_query(cx);
+                                                    })),
+                                            )
+                                            .child(
+                                                Button::new("regex")
+                                                    .selected(self.options.regex)
+                                                    .xsmall()
+                                                    .compact()
+                                                    .ghost()
+                                                    .icon(IconName::Regex)
+                                                    .tooltip(t!("Input.Use Regular Expression"))
+                                                    .on_click(cx.listener(|this, _, _, cx| {
+                                                        this.options.regex = !this.options.regex;
+                                                        this.update_search_query(cx);
+                                                    })),
+                                            ),
                                     )
                                     .small()
                                     .w_full()
@@ -568,12 +780,18 @@ impl Render for SearchPanel {
                             })),
                     )
                     .child(
-                        Label::new(self.matcher.label())
-                            .when(!has_matches, |this| {
-                                this.text_color(cx.theme().muted_foreground)
-                            })
-                            .text_left()
-                            .min_w_16(),
+                        Label::new(match self.matcher.error {
+                            Some(_) => t!("Input.Invalid Regex").to_string(),
+                            None => self.matcher.label(),
+                        })
+                        .when(!has_matches, |this| {
+                            this.text_color(cx.theme().muted_foreground)
+                        })
+                        .when(self.matcher.error.is_some(), |this| {
+                            this.text_color(cx.theme().danger)
+                        })
+                        .text_left()
+                        .min_w_16(),
                     )
                     .child(div().w_7())
                     .child(
@@ -630,7 +848,7 @@ mod tests {
     fn test_search() {
         let mut matcher = SearchMatcher::new();
         matcher.update(&Rope::from("Hello 世界 this is a Is test string."));
-        matcher.update_query("Is", true);
+        matcher.update_query("Is", SearchOptions::default());
 
         assert_eq!(matcher.len(), 3);
         let mut matches = matcher.clone();
@@ -648,7 +866,13 @@ mod tests {
         assert_eq!(matches.current_match_ix, 0);
         assert_eq!(matches.next_back(), Some(23..25));
 
-        matcher.update_query("IS", false);
+        matcher.update_query(
+            "IS",
+            SearchOptions {
+                case_sensitive: true,
+                ..Default::default()
+            },
+        );
         assert_eq!(matcher.len(), 0);
         assert_eq!(matcher.next(), None);
         assert_eq!(matcher.next_back(), None);
@@ -658,7 +882,7 @@ mod tests {
     fn test_search_label() {
         let mut matcher = SearchMatcher::new();
         matcher.update(&Rope::from("Hello 世界 this is a Is test string."));
-        matcher.update_query("Is", true);
+        matcher.update_query("Is", SearchOptions::default());
         assert_eq!(matcher.label(), "1/3");
         matcher.next();
         assert_eq!(matcher.label(), "2/3");
@@ -667,7 +891,13 @@ mod tests {
         matcher.next();
         assert_eq!(matcher.label(), "1/3");
 
-        matcher.update_query("IS", false);
+        matcher.update_query(
+            "IS",
+            SearchOptions {
+                case_sensitive: true,
+                ..Default::default()
+            },
+        );
         assert_eq!(matcher.label(), "0/0");
     }
 
@@ -743,7 +973,7 @@ mod tests {
     fn test_update_matches_clamps_current_match_index_while_replacing() {
         let mut matcher = SearchMatcher::new();
         matcher.update(&Rope::from("foo foo foo"));
-        matcher.update_query("foo", true);
+        matcher.update_query("foo", SearchOptions::default());
         matcher.current_match_ix = 2;
         matcher.replacing = true;
 
@@ -754,4 +984,83 @@ mod tests {
         assert_eq!(matcher.label(), "2/2");
         assert!(!matcher.replacing);
     }
+
+    #[test]
+    fn test_search_whole_word() {
+        let mut matcher = SearchMatcher::new();
+        matcher.update(&Rope::from("is this island is_ok, is."));
+        matcher.update_query(
+            "is",
+            SearchOptions {
+                whole_word: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(matcher.matched_ranges.as_ref(), &vec![0..2, 22..24]);
+    }
+
+    #[test]
+    fn test_search_regex() {
+        let mut matcher = SearchMatcher::new();
+        matcher.update(&Rope::from("foo1 Foo22 bar3\nfoo"));
+        matcher.update_query(
+            r"foo(\d+)",
+            SearchOptions {
+                regex: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(matcher.error, None);
+        assert_eq!(matcher.matched_ranges.as_ref(), &vec![0..4, 5..10]);
+        assert_eq!(
+            matcher.replacement(&matcher.text.to_string(), &(5..10), "bar$1"),
+            "bar22"
+        );
+
+        matcher.update_query(
+            r"foo(\d+)",
+            SearchOptions {
+                regex: true,
+                case_sensitive: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(matcher.matched_ranges.as_ref(), &vec![0..4]);
+
+        // Multi-line anchors, and the empty matches are skipped.
+        matcher.update_query(
+            r"^\w*",
+            SearchOptions {
+                regex: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(matcher.matched_ranges.as_ref(), &vec![0..4, 16..19]);
+
+        // Plain text query is not expanded.
+        matcher.update_query("foo", SearchOptions::default());
+        assert_eq!(
+            matcher.replacement(&matcher.text.to_string(), &(0..3), "bar$1"),
+            "bar$1"
+        );
+    }
+
+    #[test]
+    fn test_search_invalid_regex() {
+        let mut matcher = SearchMatcher::new();
+        matcher.update(&Rope::from("foo (bar"));
+        matcher.update_query(
+            "(bar",
+            SearchOptions {
+                regex: true,
+                ..Default::default()
+            },
+        );
+        assert!(matcher.error.is_some());
+        assert_eq!(matcher.len(), 0);
+
+        matcher.update_query("(bar", SearchOptions::default());
+        assert_eq!(matcher.error, None);
+        assert_eq!(matcher.matched_ranges.as_ref(), &vec![4..8]);
+    }
 }
@@ -0,0 +1,564 @@
+use std::rc::Rc;
+
+use gpui::{
+    App, AppContext as _, Bounds, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement as _, IntoElement, ParentElement as _, Pixels, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Subscription, Task, Window, anchored,
+    deferred, div, prelude::FluentBuilder as _, px,
+};
+use instant::Duration;
+use rust_i18n::t;
+
+use crate::{
+    ActiveTheme, Disableable, ElementExt as _, GlobalState, Icon, IconName, Sizable, Size,
+    StyledExt as _,
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    storage::Storage,
+    tag::Tag,
+    v_flex,
+};
+
+use super::{Input, InputEvent, InputState};
+
+/// The default debounce delay of the [`SearchInputEvent::Change`] event.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+/// The default max number of the recent searches.
+const DEFAULT_HISTORY_LIMIT: usize = 10;
+
+/// A filter chip (scope selector) shown inside the [`SearchInput`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchFilter {
+    /// The id of the filter.
+    pub id: SharedString,
+    /// The label of the filter chip.
+    pub label: SharedString,
+    /// Whether the filter is selected.
+    pub selected: bool,
+}
+
+impl SearchFilter {
+    /// Create a new unselected filter.
+    pub fn new(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            selected: false,
+        }
+    }
+
+    /// Set the filter selected.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+}
+
+/// Events emitted by the [`SearchInputState`].
+#[derive(Debug, Clone)]
+pub enum SearchInputEvent {
+    /// The query has been changed, debounced by [`SearchInputState::debounce`].
+    Change(SharedString),
+    /// The query has been submitted by pressing Enter or choosing a recent search.
+    Submit(SharedString),
+    /// The selected filters have been changed.
+    FilterChange,
+}
+
+/// The state of the [`SearchInput`].
+pub struct SearchInputState {
+    input: Entity<InputState>,
+    filters: Vec<SearchFilter>,
+    history: Vec<SharedString>,
+    history_limit: usize,
+    storage: Option<(SharedString, Rc<dyn Storage>)>,
+    debounce: Duration,
+    history_open: bool,
+    bounds: Bounds<Pixels>,
+    _debounce_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl SearchInputState {
+    /// Create a new [`SearchInputState`].
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(t!("SearchInput.placeholder"))
+                .clean_on_escape()
+        });
+
+        let _subscriptions = vec![cx.subscribe_in(&input, window, Self::on_input_event)];
+
+        Self {
+            input,
+            filters: vec![],
+            history: vec![],
+            history_limit: DEFAULT_HISTORY_LIMIT,
+            storage: None,
+            debounce: DEFAULT_DEBOUNCE,
+            history_open: false,
+            bounds: Bounds::default(),
+            _debounce_task: Task::ready(()),
+            _subscriptions,
+        }
+    }
+
+    /// Set the placeholder of the search input.
+    pub fn placeholder(
+        self,
+        placeholder: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        self.input.update(cx, |input, cx| {
+            input.set_placeholder(placeholder, window, cx);
+        });
+        self
+    }
+
+    /// Set the debounce delay of the [`SearchInputEvent::Change`] event, default is 300ms.
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set the filter chips (scope selectors) inside the search input.
+    pub fn filters(mut self, filters: impl Into<Vec<SearchFilter>>) -> Self {
+        self.filters = filters.into();
+        self
+    }
+
+    /// Set the max number of the recent searches, default is 10.
+    ///
+    /// Set 0 to disable the recent searches.
+    pub fn history_limit(mut self, limit: usize) -> Self {
+        self.history_limit = limit;
+        self.history.truncate(limit);
+        self
+    }
+
+    /// Persist the recent searches in the `storage` with the `key`.
+    ///
+    /// The recent searches are loaded from the storage immediately.
+    pub fn history_storage(
+        mut self,
+        key: impl Into<SharedString>,
+        storage: Rc<dyn Storage>,
+    ) -> Self {
+        let key = key.into();
+        self.history = storage
+            .get(&key)
+            .and_then(|value| serde_json::from_str::<Vec<String>>(&value).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .take(self.history_limit)
+            .map(SharedString::from)
+            .collect();
+        self.storage = Some((key, storage));
+        self
+    }
+
+    /// Returns the inner [`InputState`].
+    pub fn input(&self) -> &Entity<InputState> {
+        &self.input
+    }
+
+    /// Returns the query of the search input.
+    pub fn value(&self, cx: &App) -> SharedString {
+        self.input.read(cx).value()
+    }
+
+    /// Set the query of the search input.
+    pub fn set_value(
+        &mut self,
+        value: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.input.update(cx, |input, cx| {
+            input.set_value(value, window, cx);
+        });
+    }
+
+    /// Set true to show the loading indicator.
+    pub fn set_loading(&mut self, loading: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.input.update(cx, |input, cx| {
+            input.set_loading(loading, window, cx);
+        });
+    }
+
+    /// Returns the ids of the selected filters.
+    pub fn selected_filters(&self) -> Vec<SharedString> {
+        self.filters
+            .iter()
+            .filter(|filter| filter.selected)
+            .map(|filter| filter.id.clone())
+            .collect()
+    }
+
+    /// Set the filter of the `id` selected or not.
+    pub fn set_filter_selected(
+        &mut self,
+        id: &str,
+        selected: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(filter) = self.filters.iter_mut().find(|filter| filter.id == id) else {
+            return;
+        };
+        if filter.selected == selected {
+            return;
+        }
+
+        filter.selected = selected;
+        cx.emit(SearchInputEvent::FilterChange);
+        cx.notify();
+    }
+
+    /// Returns the recent searches, the latest is first.
+    pub fn history(&self) -> &[SharedString] {
+        &self.history
+    }
+
+    /// Add the `query` to the recent searches.
+    pub fn push_history(&mut self, query: impl Into<SharedString>, cx: &mut Context<Self>) {
+        if push_history_item(&mut self.history, &query.into(), self.history_limit) {
+            self.save_history();
+            cx.notify();
+        }
+    }
+
+    /// Clear the recent searches.
+    pub fn clear_history(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.history.clear();
+        self.set_history_open(false, cx);
+        self.save_history();
+        cx.notify();
+    }
+
+    fn save_history(&self) {
+        let Some((key, storage)) = self.storage.as_ref() else {
+            return;
+        };
+
+        let items = self
+            .history
+            .iter()
+            .map(|item| item.as_str())
+            .collect::<Vec<_>>();
+        if let Ok(value) = serde_json::to_string(&items) {
+            storage.set(key, value);
+        }
+    }
+
+    fn set_history_open(&mut self, open: bool, cx: &mut Context<Self>) {
+        let open = open && !self.history.is_empty();
+        if self.history_open == open {
+            return;
+        }
+
+        self.history_open = open;
+        let focus_handle = self.input.read(cx).focus_handle(cx);
+        if open {
+            GlobalState::global_mut(cx).register_deferred_popover(&focus_handle);
+        } else {
+            GlobalState::global_mut(cx).unregister_deferred_popover(&focus_handle);
+        }
+        cx.notify();
+    }
+
+    fn on_input_event(
+        &mut self,
+        input: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::Change => {
+                let value = input.read(cx).value();
+                self.set_history_open(value.is_empty(), cx);
+
+                let debounce = self.debounce;
+                self._debounce_task = cx.spawn_in(window, async move |this, cx| {
+                    cx.background_executor().timer(debounce).await;
+                    _ = this.update(cx, |_, cx| {
+                        cx.emit(SearchInputEvent::Change(value));
+                    });
+                });
+            }
+            InputEvent::PressEnter { .. } => {
+                let value = input.read(cx).value();
+                self.submit(value, window, cx);
+            }
+            InputEvent::Focus => {
+                let is_empty = input.read(cx).value().is_empty();
+                self.set_history_open(is_empty, cx);
+            }
+            InputEvent::Blur | InputEvent::MaxLengthExceeded => {}
+        }
+    }
+
+    fn submit(&mut self, query: SharedString, _: &mut Window, cx: &mut Context<Self>) {
+        // Drop the pending debounced change, the query is submitted.
+        self._debounce_task = Task::ready(());
+        self.set_history_open(false, cx);
+        self.push_history(query.clone(), cx);
+        cx.emit(SearchInputEvent::Submit(query));
+    }
+
+    fn select_history(&mut self, query: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_value(query.clone(), window, cx);
+        self.submit(query, window, cx);
+    }
+}
+
+/// Move the `query` to the front of the `history`, returns false if the `query` is blank.
+pub(super) fn push_history_item(
+    history: &mut Vec<SharedString>,
+    query: &str,
+    limit: usize,
+) -> bool {
+    let query = query.trim();
+    if query.is_empty() || limit == 0 {
+        return false;
+    }
+
+    history.retain(|item| item != query);
+    history.insert(0, SharedString::from(query.to_string()));
+    history.truncate(limit);
+    true
+}
+
+impl EventEmitter<SearchInputEvent> for SearchInputState {}
+impl Focusable for SearchInputState {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.read(cx).focus_handle(cx)
+    }
+}
+
+/// A search input with a magnifier icon, clear button, loading indicator, filter chips
+/// and a dropdown of the recent searches.
+///
+/// Press `Escape` to clear the query.
+#[derive(IntoElement)]
+pub struct SearchInput {
+    state: Entity<SearchInputState>,
+    size: Size,
+    disabled: bool,
+    style: StyleRefinement,
+}
+
+impl SearchInput {
+    /// Create a new [`SearchInput`] element bind to the [`SearchInputState`].
+    pub fn new(state: &Entity<SearchInputState>) -> Self {
+        Self {
+            state: state.clone(),
+            size: Size::default(),
+            disabled: false,
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Sizable for SearchInput {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Disableable for SearchInput {
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl Styled for SearchInput {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl SearchInput {
+    fn render_filters(
+        &self,
+        state: &SearchInputState,
+        window: &mut Window,
+        cx: &App,
+    ) -> impl IntoElement {
+        h_flex()
+            .gap_1()
+            .children(state.filters.iter().enumerate().map(|(ix, filter)| {
+                let id = filter.id.clone();
+                let selected = filter.selected;
+
+                div()
+                    .id(("filter", ix))
+                    .when(!self.disabled, |this| this.cursor_pointer())
+                    .child(
+                        if selected {
+                            Tag::primary()
+                        } else {
+                            Tag::secondary().outline()
+                        }
+                        .xsmall()
+                        .child(filter.label.clone()),
+                    )
+                    .when(!self.disabled, |this| {
+                        this.on_click(window.listener_for(
+                            &self.state,
+                            move |this, _, window, cx| {
+                                cx.stop_propagation();
+                                this.set_filter_selected(&id, !selected, window, cx);
+                            },
+                        ))
+                    })
+            }))
+    }
+
+    fn render_history(
+        &self,
+        state: &SearchInputState,
+        window: &mut Window,
+        cx: &App,
+    ) -> impl IntoElement {
+        let popup_radius = cx.theme().radius.min(px(8.));
+
+        v_flex()
+            .occlude()
+            .w(state.bounds.size.width)
+            .mt_1p5()
+            .p_1()
+            .bg(cx.theme().popover)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(popup_radius)
+            .shadow_md()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .justify_between()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(t!("SearchInput.recent_searches").to_string())
+                    .child(
+                        Button::new("clear-history")
+                            .ghost()
+                            .xsmall()
+                            .label(t!("SearchInput.clear_history"))
+                            .on_click(window.listener_for(&self.state, |this, _, window, cx| {
+                                this.clear_history(window, cx);
+                            })),
+                    ),
+            )
+            .children(state.history.iter().enumerate().map(|(ix, query)| {
+                let query = query.clone();
+                h_flex()
+                    .id(("history", ix))
+                    .px_2()
+                    .py_1()
+                    .gap_2()
+                    .text_sm()
+                    .rounded(popup_radius)
+                    .cursor_pointer()
+                    .hover(|this| this.bg(cx.theme().accent))
+                    .child(
+                        Icon::new(IconName::Search)
+                            .xsmall()
+                            .text_color(cx.theme().muted_foreground),
+                    )
+                    .child(div().flex_1().truncate().child(query.clone()))
+                    .on_click(
+                        window.listener_for(&self.state, move |this, _, window, cx| {
+                            this.select_history(query.clone(), window, cx);
+                        }),
+                    )
+            }))
+            .on_mouse_down_out(window.listener_for(&self.state, |this, _, _, cx| {
+                this.set_history_open(false, cx);
+            }))
+    }
+}
+
+impl RenderOnce for SearchInput {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let history_open = state.history_open && !self.disabled;
+
+        div()
+            .relative()
+            .w_full()
+            .refine_style(&self.style)
+            .child(
+                Input::new(&state.input)
+                    .with_size(self.size)
+                    .disabled(self.disabled)
+                    .cleanable(true)
+                    .prefix(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Icon::new(IconName::Search).text_color(cx.theme().muted_foreground),
+                            )
+                            .when(!state.filters.is_empty(), |this| {
+                                this.child(self.render_filters(state, window, cx))
+                            }),
+                    ),
+            )
+            .on_prepaint({
+                let state = self.state.clone();
+                move |bounds, _, cx| state.update(cx, |state, _| state.bounds = bounds)
+            })
+            .when(history_open, |this| {
+                this.child(
+                    deferred(
+                        anchored()
+                            .snap_to_window_with_margin(px(8.))
+                            .child(self.render_history(state, window, cx)),
+                    )
+                    .with_priority(1),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::SharedString;
+
+    use super::push_history_item;
+
+    #[test]
+    fn test_push_history_item() {
+        let mut history: Vec<SharedString> = vec![];
+        assert!(push_history_item(&mut history, " foo ", 3));
+        assert!(push_history_item(&mut history, "bar", 3));
+        assert!(!push_history_item(&mut history, "  ", 3));
+        assert_eq!(
+            history.iter().map(|s| s.as_ref()).collect::<Vec<&str>>(),
+            vec!["bar", "foo"]
+        );
+
+        // Existing query is moved to the front.
+        assert!(push_history_item(&mut history, "foo", 3));
+        assert_eq!(
+            history.iter().map(|s| s.as_ref()).collect::<Vec<&str>>(),
+            vec!["foo", "bar"]
+        );
+
+        // Oldest query is dropped over the limit.
+        assert!(push_history_item(&mut history, "baz", 3));
+        assert!(push_history_item(&mut history, "qux", 3));
+        assert_eq!(
+            history.iter().map(|s| s.as_ref()).collect::<Vec<&str>>(),
+            vec!["qux", "baz", "foo"]
+        );
+
+        assert!(!push_history_item(&mut history, "foo", 0));
+    }
+}
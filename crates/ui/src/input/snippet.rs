@@ -0,0 +1,461 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
+
+use gpui::{Context, Window};
+
+use crate::input::{InputState, RopeExt as _};
+
+/// A parsed snippet, in the LSP snippet syntax.
+///
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#snippet_syntax
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Snippet {
+    /// The text to insert, without the snippet syntax.
+    pub(super) text: String,
+    /// The byte ranges (relative to the `text`) of each tab stop, ordered by the index,
+    /// the final tab stop (`$0`) is always the last.
+    pub(super) tabstops: Vec<Vec<Range<usize>>>,
+}
+
+impl Snippet {
+    /// Parse the snippet, the `indent` is inserted after each newline, to keep the
+    /// lines of the snippet aligned with the line where it is inserted.
+    pub(super) fn parse(snippet: &str, indent: &str) -> Self {
+        let mut parser = Parser {
+            chars: snippet.chars().collect(),
+            ix: 0,
+            indent,
+            text: String::new(),
+            stops: vec![],
+            placeholders: HashMap::new(),
+        };
+        parser.parse_any(false);
+
+        let mut groups: BTreeMap<usize, Vec<Range<usize>>> = BTreeMap::new();
+        for (index, range) in parser.stops {
+            groups.entry(index).or_default().push(range);
+        }
+
+        let len = parser.text.len();
+        let final_stop = groups.remove(&0).unwrap_or_else(|| vec![len..len]);
+        let mut tabstops = groups.into_values().collect::<Vec<_>>();
+        tabstops.push(final_stop);
+
+        Self {
+            text: parser.text,
+            tabstops,
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    ix: usize,
+    indent: &'a str,
+    text: String,
+    stops: Vec<(usize, Range<usize>)>,
+    /// The text of the placeholders, to mirror on the tab stops of the same index.
+    placeholders: HashMap<usize, String>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.ix).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.ix += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        self.text.push(c);
+        if c == '\n' {
+            self.text.push_str(self.indent);
+        }
+    }
+
+    /// Parse until the end, or the unescaped `}` if `nested`.
+    fn parse_any(&mut self, nested: bool) {
+        while let Some(c) = self.peek() {
+            match c {
+                '\\' => match self.chars.get(self.ix + 1).copied() {
+                    Some(next @ ('$' | '}' | '\\')) => {
+                        self.push(next);
+                        self.ix += 2;
+                    }
+                    _ => {
+                        self.push(c);
+                        self.ix += 1;
+                    }
+                },
+                '}' if nested => {
+                    self.ix += 1;
+                    return;
+                }
+                '$' => {
+                    if !self.parse_dollar() {
+                        self.push(c);
+                        self.ix += 1;
+                    }
+                }
+                _ => {
+                    self.push(c);
+                    self.ix += 1;
+                }
+            }
+        }
+    }
+
+    fn parse_int(&mut self) -> Option<usize> {
+        let start = self.ix;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.ix += 1;
+        }
+        if self.ix == start {
+            return None;
+        }
+
+        self.chars[start..self.ix]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+
+    fn parse_var(&mut self) -> Option<String> {
+        let start = self.ix;
+        if !self
+            .peek()
+            .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        {
+            return None;
+        }
+        while self
+            .peek()
+            .is_some_and(|c| c == '_' || c.is_ascii_alphanumeric())
+        {
+            self.ix += 1;
+        }
+
+        Some(self.chars[start..self.ix].iter().collect())
+    }
+
+    /// Parse the options of a choice after `${1|`, returns the first option.
+    fn parse_choice(&mut self) -> Option<String> {
+        let mut first = None;
+        let mut option = String::new();
+        while let Some(c) = self.peek() {
+            self.ix += 1;
+            match c {
+                '\\' => {
+                    if let Some(next) = self.peek() {
+                        option.push(next);
+                        self.ix += 1;
+                    }
+                }
+                ',' => {
+                    first.get_or_insert(std::mem::take(&mut option));
+                }
+                '|' if self.eat('}') => {
+                    return Some(first.unwrap_or(option));
+                }
+                _ => option.push(c),
+            }
+        }
+
+        None
+    }
+
+    fn insert_tabstop(&mut self, index: usize) {
+        let start = self.text.len();
+        if let Some(placeholder) = self.placeholders.get(&index) {
+            self.text.push_str(placeholder);
+        }
+        self.stops.push((index, start..self.text.len()));
+    }
+
+    /// Parse the tab stop, placeholder, choice or variable at `$`.
+    ///
+    /// Returns false if it is not a valid syntax, and the `$` is a plain text.
+    fn parse_dollar(&mut self) -> bool {
+        let start_ix = self.ix;
+        self.ix += 1;
+
+        if self.eat('{') {
+            if let Some(index) = self.parse_int() {
+                if self.eat('}') {
+                    self.insert_tabstop(index);
+                    return true;
+                }
+
+                if self.eat(':') {
+                    let start = self.text.len();
+                    self.parse_any(true);
+                    let placeholder = self.text[start..].to_string();
+                    self.placeholders.entry(index).or_insert(placeholder);
+                    self.stops.push((index, start..self.text.len()));
+                    return true;
+                }
+
+                if self.eat('|') {
+                    if let Some(choice) = self.parse_choice() {
+                        let start = self.text.len();
+                        self.text.push_str(&choice);
+                        self.placeholders.entry(index).or_insert(choice);
+                        self.stops.push((index, start..self.text.len()));
+                        return true;
+                    }
+                }
+            } else if self.parse_var().is_some() {
+                // The variables are not resolved, use the default text.
+                if self.eat('}') {
+                    return true;
+                }
+
+                if self.eat(':') {
+                    self.parse_any(true);
+                    return true;
+                }
+
+                if self.eat('/') {
+                    // Skip the transform.
+                    while let Some(c) = self.peek() {
+                        self.ix += 1;
+                        if c == '\\' {
+                            self.ix += 1;
+                        } else if c == '}' {
+                            return true;
+                        }
+                    }
+                    return true;
+                }
+            }
+        } else if let Some(index) = self.parse_int() {
+            self.insert_tabstop(index);
+            return true;
+        } else if self.parse_var().is_some() {
+            return true;
+        }
+
+        self.ix = start_ix;
+        false
+    }
+}
+
+/// The active snippet after inserted, to cycle the tab stops by Tab and Shift-Tab.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SnippetSession {
+    /// The byte ranges of each tab stop in the text, see [`Snippet::tabstops`].
+    tabstops: Vec<Vec<Range<usize>>>,
+    /// The index of the active tab stop.
+    active: usize,
+}
+
+impl SnippetSession {
+    /// Update the ranges of the tab stops for the edit of the `range` replaced by a text
+    /// of `new_len` bytes.
+    ///
+    /// Returns false if the edit overlaps a tab stop partially, the session should end.
+    fn adjust_for_edit(&mut self, range: &Range<usize>, new_len: usize) -> bool {
+        let delta = new_len as isize - range.len() as isize;
+        let shift = |offset: usize| offset.saturating_add_signed(delta);
+
+        for (ix, ranges) in self.tabstops.iter_mut().enumerate() {
+            let is_active = ix == self.active;
+            for tabstop in ranges.iter_mut() {
+                let within = tabstop.start <= range.start && range.end <= tabstop.end;
+                if is_active && within {
+                    tabstop.end = shift(tabstop.end);
+                } else if range.end <= tabstop.start {
+                    *tabstop = shift(tabstop.start)..shift(tabstop.end);
+                } else if range.start >= tabstop.end {
+                    continue;
+                } else if within {
+                    tabstop.end = shift(tabstop.end);
+                } else {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl InputState {
+    /// Insert the snippet (in the LSP snippet syntax) at the cursor, and select the first tab stop.
+    ///
+    /// Then use Tab and Shift-Tab to move to the next and previous tab stop, the tab stops
+    /// of the same index are edited together. Escape to exit the snippet.
+    pub fn insert_snippet(&mut self, snippet: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range.start..self.selected_range.end;
+        self.insert_snippet_in_range(range, snippet, window, cx);
+    }
+
+    /// Replace the byte `range` with the snippet, see [`Self::insert_snippet`].
+    pub(crate) fn insert_snippet_in_range(
+        &mut self,
+        range: Range<usize>,
+        snippet: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let row = self.text.offset_to_point(range.start).row;
+        let indent = self
+            .text
+            .slice_line(row)
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect::<String>();
+        let snippet = Snippet::parse(snippet, &indent);
+
+        self.snippet = None;
+        let range_utf16 = self.range_to_utf16(&range);
+        self.replace_text_in_range_silent(Some(range_utf16), &snippet.text, window, cx);
+
+        let tabstops = snippet
+            .tabstops
+            .into_iter()
+            .map(|ranges| {
+                ranges
+                    .into_iter()
+                    .map(|r| range.start + r.start..range.start + r.end)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        self.snippet = Some(SnippetSession {
+            tabstops,
+            active: 0,
+        });
+        self.select_snippet_tabstop(cx);
+    }
+
+    /// Select the ranges of the active tab stop, the session ends at the final tab stop.
+    fn select_snippet_tabstop(&mut self, cx: &mut Context<Self>) {
+        let Some(session) = self.snippet.as_ref() else {
+            return;
+        };
+
+        let ranges = session.tabstops[session.active].clone();
+        if session.active + 1 == session.tabstops.len() {
+            self.snippet = None;
+        }
+        self.set_cursors(&ranges, cx);
+    }
+
+    /// Move to the next tab stop of the snippet, returns false if there is no active snippet.
+    pub(super) fn next_snippet_tabstop(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(session) = self.snippet.as_mut() else {
+            return false;
+        };
+
+        session.active = (session.active + 1).min(session.tabstops.len() - 1);
+        self.select_snippet_tabstop(cx);
+        true
+    }
+
+    /// Move to the previous tab stop of the snippet, returns false if there is no active snippet.
+    pub(super) fn prev_snippet_tabstop(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(session) = self.snippet.as_mut() else {
+            return false;
+        };
+
+        session.active = session.active.saturating_sub(1);
+        self.select_snippet_tabstop(cx);
+        true
+    }
+
+    /// Exit the snippet, returns true if there was an active snippet.
+    pub(super) fn exit_snippet(&mut self) -> bool {
+        self.snippet.take().is_some()
+    }
+
+    /// Update the tab stops of the active snippet for the edit.
+    pub(super) fn adjust_snippet_for_edit(&mut self, range: &Range<usize>, new_len: usize) {
+        if let Some(session) = self.snippet.as_mut() {
+            if !session.adjust_for_edit(range, new_len) {
+                self.snippet = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Snippet, SnippetSession};
+
+    #[test]
+    fn test_parse_snippet() {
+        let snippet = Snippet::parse("println!(\"$1\");$0", "");
+        assert_eq!(snippet.text, "println!(\"\");");
+        assert_eq!(snippet.tabstops, vec![vec![10..10], vec![13..13]]);
+
+        // Placeholders, the final tab stop is at the end if not specified.
+        let snippet = Snippet::parse("fn ${1:name}(${2:args})", "");
+        assert_eq!(snippet.text, "fn name(args)");
+        assert_eq!(
+            snippet.tabstops,
+            vec![vec![3..7], vec![8..12], vec![13..13]]
+        );
+
+        // Linked tab stops mirror the placeholder.
+        let snippet = Snippet::parse("let ${1:a} = 1; $1 + ${2:b}", "");
+        assert_eq!(snippet.text, "let a = 1; a + b");
+        assert_eq!(
+            snippet.tabstops,
+            vec![vec![4..5, 11..12], vec![15..16], vec![16..16]]
+        );
+
+        // Nested placeholders.
+        let snippet = Snippet::parse("${1:foo(${2:bar})}", "");
+        assert_eq!(snippet.text, "foo(bar)");
+        assert_eq!(snippet.tabstops, vec![vec![0..8], vec![4..7], vec![8..8]]);
+
+        // Choices and variables.
+        let snippet = Snippet::parse("${1|one,two|} ${TM_FILENAME} ${NAME:default}", "");
+        assert_eq!(snippet.text, "one  default");
+        assert_eq!(snippet.tabstops, vec![vec![0..3], vec![12..12]]);
+
+        // Escapes and invalid syntax.
+        let snippet = Snippet::parse("\\$1 \\} $ ${", "");
+        assert_eq!(snippet.text, "$1 } $ ${");
+        assert_eq!(snippet.tabstops, vec![vec![9..9]]);
+
+        // Indent the lines.
+        let snippet = Snippet::parse("if $1 {\n\t$0\n}", "  ");
+        assert_eq!(snippet.text, "if  {\n  \t\n  }");
+        assert_eq!(snippet.tabstops, vec![vec![3..3], vec![9..9]]);
+    }
+
+    #[test]
+    fn test_adjust_for_edit() {
+        // "let a = 1; a + b"
+        let mut session = SnippetSession {
+            tabstops: vec![vec![4..5, 11..12], vec![15..16], vec![16..16]],
+            active: 0,
+        };
+
+        // Replace the second `a` with `foo`, then the first.
+        assert!(session.adjust_for_edit(&(11..12), 3));
+        assert!(session.adjust_for_edit(&(4..5), 3));
+        assert_eq!(
+            session.tabstops,
+            vec![vec![4..7, 13..16], vec![19..20], vec![20..20]]
+        );
+
+        // Type in the empty final tab stop when it is not active.
+        assert!(session.adjust_for_edit(&(20..20), 1));
+        assert_eq!(session.tabstops[2], vec![21..21]);
+
+        // An edit overlaps a tab stop partially.
+        assert!(!session.adjust_for_edit(&(3..5), 0));
+    }
+}
@@ -0,0 +1,174 @@
+use std::{borrow::Cow, cell::RefCell, ops::Range, rc::Rc};
+
+use gpui::{Context, Entity, WeakEntity, Window};
+use ropey::Rope;
+
+use crate::input::InputState;
+
+/// The group of [`InputState`]s sharing one text buffer, see [`InputState::share_buffer`].
+pub(super) type SharedBuffer = Rc<RefCell<Vec<WeakEntity<InputState>>>>;
+
+impl InputState {
+    /// Share the text buffer with the `other` input, for building the split editor views.
+    ///
+    /// The edits in any of the shared inputs are applied to all of them, while each one
+    /// keeps its own cursor, selection, scroll position and folds. The [`Rope`] is cheap
+    /// to clone, so the text is not duplicated.
+    ///
+    /// The undo history is per input, and records the edits from the other inputs too.
+    ///
+    /// ```ignore
+    /// let left = cx.new(|cx| InputState::new(window, cx).code_editor("rust"));
+    /// let right = cx.new(|cx| {
+    ///     InputState::new(window, cx)
+    ///         .code_editor("rust")
+    ///         .share_buffer(&left, window, cx)
+    /// });
+    /// ```
+    pub fn share_buffer(
+        mut self,
+        other: &Entity<InputState>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let other_weak = other.downgrade();
+        let (text, group) = other.update(cx, |other, _| {
+            let group = other
+                .shared_buffer
+                .get_or_insert_with(|| Rc::new(RefCell::new(vec![other_weak])))
+                .clone();
+            (other.text.clone(), group)
+        });
+        group.borrow_mut().push(cx.weak_entity());
+
+        self.text = text;
+        if let Some(diagnostics) = self.mode.diagnostics_mut() {
+            diagnostics.reset(&self.text)
+        }
+        self.shared_buffer = Some(group);
+        self.refresh(cx);
+        self
+    }
+
+    /// Returns true if the text buffer is shared with other inputs, see [`Self::share_buffer`].
+    pub fn is_buffer_shared(&self) -> bool {
+        self.shared_buffer
+            .as_ref()
+            .is_some_and(|group| group.borrow().len() > 1)
+    }
+
+    /// Apply the edit of the `range` replaced with `new_text` in this input to the
+    /// other inputs sharing the buffer.
+    pub(super) fn sync_shared_buffer(
+        &mut self,
+        old_text: &Rope,
+        range: &Range<usize>,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(group) = self.shared_buffer.clone() else {
+            return;
+        };
+
+        // The text may be changed by the mask after the edit, send the whole text instead.
+        let (range, new_text) = if old_text.len() - range.len() + new_text.len() == self.text.len()
+        {
+            (range.clone(), Cow::Borrowed(new_text))
+        } else {
+            (0..old_text.len(), Cow::Owned(self.text.to_string()))
+        };
+
+        let entity_id = cx.entity_id();
+        group.borrow_mut().retain(|peer| peer.upgrade().is_some());
+        let peers = group.borrow().clone();
+        for peer in peers {
+            if peer.entity_id() == entity_id {
+                continue;
+            }
+
+            _ = peer.update(cx, |peer, cx| {
+                peer.apply_shared_edit(&self.text, &range, &new_text, window, cx);
+            });
+        }
+    }
+
+    /// Apply an edit from another input sharing the buffer, the `text` is the result of the edit.
+    fn apply_shared_edit(
+        &mut self,
+        text: &Rope,
+        range: &Range<usize>,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let old_text = std::mem::replace(&mut self.text, text.clone());
+
+        self.extra_cursors.clear();
+        self.ime_marked_range = None;
+        self.snippet = None;
+        self.push_history(&old_text, range, new_text);
+        self.history.end_grouping();
+        if let Some(diagnostics) = self.mode.diagnostics_mut() {
+            diagnostics.reset(&self.text)
+        }
+        self.display_map
+            .adjust_folds_for_edit(&old_text, range, new_text);
+        self.display_map
+            .on_text_changed(&self.text, range, &Rope::from(new_text), cx);
+
+        let bg = self
+            .mode
+            .update_highlighter(range, &old_text, &self.text, new_text, true, cx);
+        if let Some(bg) = bg {
+            Self::dispatch_background_parse(bg, window, cx);
+        }
+
+        self.update_fold_candidates_incremental(range, new_text);
+        self.lsp.update(&self.text, window, cx);
+
+        let start = shift_offset(self.selected_range.start, range, new_text.len());
+        let end = shift_offset(self.selected_range.end, range, new_text.len());
+        self.selected_range = (start..end).into();
+        self.update_search(cx);
+        self.mode.update_auto_grow(&self.display_map);
+        if self.emit_events {
+            cx.emit(super::InputEvent::Change);
+        }
+        cx.notify();
+    }
+}
+
+/// Shift the `offset` after the `range` is replaced with a text of `new_len` bytes.
+///
+/// The offset inside the replaced range is moved to the end of the new text.
+fn shift_offset(offset: usize, range: &Range<usize>, new_len: usize) -> usize {
+    if offset <= range.start {
+        offset
+    } else if offset >= range.end {
+        offset - range.len() + new_len
+    } else {
+        range.start + new_len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shift_offset;
+
+    #[test]
+    fn test_shift_offset() {
+        // Insert "abc" at 5
+        assert_eq!(shift_offset(3, &(5..5), 3), 3);
+        assert_eq!(shift_offset(5, &(5..5), 3), 5);
+        assert_eq!(shift_offset(8, &(5..5), 3), 11);
+
+        // Replace 5..10 with "ab"
+        assert_eq!(shift_offset(7, &(5..10), 2), 7);
+        assert_eq!(shift_offset(10, &(5..10), 2), 7);
+        assert_eq!(shift_offset(12, &(5..10), 2), 9);
+
+        // Delete 5..10
+        assert_eq!(shift_offset(7, &(5..10), 0), 5);
+    }
+}
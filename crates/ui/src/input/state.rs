@@ -26,10 +26,20 @@ use super::{
     blink_cursor::BlinkCursor,
     change::Change,
     element::{EditorScrollbarSnapshot, TextElement},
+    gutter_marks::GutterMark,
+    language_settings::LanguageSettingsTable,
+    large_file::{DEFAULT_LARGE_FILE_THRESHOLD, ValueLoading},
     mask_pattern::{MaskPattern, normalize_number_input},
     mode::InputMode,
+    multi_cursor::{ColumnSelection, CursorState},
+    number_format::{NumberInputFormat, NumberType, NumberValue, clamp_number_text},
     number_input,
     number_input::{NumberStep, StepAction},
+    popovers,
+    snippet::SnippetSession,
+    split::SharedBuffer,
+    value_history::ValueHistory,
+    vim::{self, VimKeymap, VimState},
 };
 use crate::Size;
 use crate::actions::{SelectDown, SelectLeft, SelectRight, SelectUp};
@@ -42,7 +52,10 @@ use crate::input::{
     HoverDefinition, InlineCompletion, Lsp, Position, RopeExt as _, Selection,
     display_map::LineLayout,
     element::RIGHT_MARGIN,
-    popovers::{ContextMenu, DiagnosticPopover, HoverPopover},
+    popovers::{
+        ContextMenu, DefinitionPeek, DiagnosticPopover, HoverPopover, RenameEditor,
+        SignatureHelpPopover,
+    },
     search::SearchPanel,
 };
 use crate::native_menu::NativeMenu;
@@ -115,16 +128,41 @@ actions!(
         Escape,
         ToggleCodeActions,
         Search,
+        Replace,
         GoToDefinition,
+        PeekDefinition,
+        Rename,
+        Format,
+        FormatSelection,
+        SelectNextOccurrence,
+        ToggleColumnSelection,
+        ToggleLineComment,
+        ToggleBlockComment,
+        DuplicateLine,
+        MoveLineUp,
+        MoveLineDown,
+        JoinLines,
+        DeleteLine,
+        Transpose,
+        SortLines,
+        Fold,
+        Unfold,
+        FoldAll,
+        UnfoldAll,
     ]
 );
 
 #[derive(Clone)]
 pub enum InputEvent {
     Change,
-    PressEnter { secondary: bool, shift: bool },
+    PressEnter {
+        secondary: bool,
+        shift: bool,
+    },
     Focus,
     Blur,
+    /// The user input was cut to fit the [`InputState::max_length`].
+    MaxLengthExceeded,
 }
 
 pub(super) const CONTEXT: &str = "Input";
@@ -272,18 +310,102 @@ pub(crate) fn init(cx: &mut App) {
         KeyBinding::new("cmd-f", Search, Some(CONTEXT)),
         #[cfg(not(target_os = "macos"))]
         KeyBinding::new("ctrl-f", Search, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-f", Replace, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-h", Replace, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-d", SelectNextOccurrence, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-d", SelectNextOccurrence, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-[", Fold, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-alt-]", Unfold, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-k cmd-0", FoldAll, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-k cmd-j", UnfoldAll, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-[", Fold, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-]", Unfold, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-k ctrl-0", FoldAll, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-k ctrl-j", UnfoldAll, Some(CONTEXT)),
+        KeyBinding::new("f12", GoToDefinition, Some(CONTEXT)),
+        KeyBinding::new("alt-f12", PeekDefinition, Some(CONTEXT)),
+        KeyBinding::new("f2", Rename, Some(CONTEXT)),
+        KeyBinding::new("shift-alt-f", Format, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-k cmd-f", FormatSelection, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-k ctrl-f", FormatSelection, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("cmd-/", ToggleLineComment, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-/", ToggleLineComment, Some(CONTEXT)),
+        KeyBinding::new("shift-alt-a", ToggleBlockComment, Some(CONTEXT)),
+        KeyBinding::new("shift-alt-down", DuplicateLine, Some(CONTEXT)),
+        KeyBinding::new("alt-up", MoveLineUp, Some(CONTEXT)),
+        KeyBinding::new("alt-down", MoveLineDown, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("ctrl-j", JoinLines, Some(CONTEXT)),
+        #[cfg(not(target_os = "macos"))]
+        KeyBinding::new("ctrl-shift-j", JoinLines, Some(CONTEXT)),
+        KeyBinding::new("secondary-shift-k", DeleteLine, Some(CONTEXT)),
+        #[cfg(target_os = "macos")]
+        KeyBinding::new("ctrl-t", Transpose, Some(CONTEXT)),
+        KeyBinding::new("f9", SortLines, Some(CONTEXT)),
     ]);
 
     number_input::init(cx);
+    popovers::init(cx);
+    vim::init(cx);
 }
 
-/// Whitespace indicators for rendering spaces and tabs.
+/// Whitespace indicators for rendering spaces, tabs and line endings.
 #[derive(Clone, Default)]
 pub(crate) struct WhitespaceIndicators {
     /// Shaped line for space character indicator (•)
     pub(crate) space: ShapedLine,
     /// Shaped line for tab character indicator (→)
     pub(crate) tab: ShapedLine,
+    /// Which spaces and tabs to show the indicators.
+    pub(crate) visibility: WhitespaceVisibility,
+    /// Shaped lines for the LF (¬) and CRLF (¤¬) line ending indicators, `None` to hide.
+    pub(crate) line_endings: Option<(ShapedLine, ShapedLine)>,
+}
+
+/// Which spaces and tabs to show the [`WhitespaceIndicators`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum WhitespaceVisibility {
+    #[default]
+    None,
+    /// Only the whitespaces at the end of the lines.
+    Trailing,
+    All,
+}
+
+/// How to number the lines in the gutter of the [`InputMode::CodeEditor`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineNumberMode {
+    /// Show the line number of each line, this is the default.
+    #[default]
+    Absolute,
+    /// Show the distance of each line to the cursor line, and the line number of the cursor line.
+    Relative,
+}
+
+impl LineNumberMode {
+    /// Returns the number to show for the `line` (0-based) with the cursor at the `current_row`.
+    pub(super) fn number(self, line: usize, current_row: Option<usize>) -> usize {
+        match (self, current_row) {
+            (Self::Relative, Some(row)) if row != line => line.abs_diff(row),
+            _ => line + 1,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -377,17 +499,57 @@ pub struct InputState {
     /// See [`Self::cursor_surrounding_lines`].
     pub(super) cursor_surrounding_lines: Option<usize>,
     pub(super) show_whitespaces: bool,
+    /// See [`Self::show_trailing_whitespaces`].
+    pub(super) show_trailing_whitespaces: bool,
+    /// See [`Self::show_line_endings`].
+    pub(super) show_line_endings: bool,
+    /// See [`Self::highlight_current_line`].
+    pub(super) highlight_current_line: bool,
+    /// See [`Self::line_number_mode`].
+    pub(super) line_number_mode: LineNumberMode,
+    /// See [`Self::minimap`].
+    pub(super) minimap: bool,
+    /// See [`Self::sticky_scroll`].
+    pub(super) sticky_scroll: bool,
+    /// See [`Self::highlight_active_indent_guide`].
+    pub(super) highlight_active_indent_guide: bool,
+    /// See [`Self::highlight_occurrences`].
+    pub(super) highlight_occurrences: bool,
+    /// See [`Self::code_action_lightbulb`].
+    pub(super) code_action_lightbulb: bool,
+    /// See [`Self::vim_mode`].
+    pub(super) vim: VimState,
+    /// See [`Self::auto_close_brackets`].
+    pub(super) auto_close_brackets: bool,
+    /// See [`Self::rulers`].
+    pub(super) rulers: Vec<usize>,
+    /// See [`Self::trim_trailing_whitespace_on_save`].
+    pub(super) trim_trailing_whitespace: bool,
+    /// See [`Self::language_settings`].
+    pub(super) language_settings: Option<Rc<LanguageSettingsTable>>,
+    /// See [`Self::set_gutter_marks`].
+    pub(super) gutter_marks: Rc<Vec<GutterMark>>,
+    /// The offset of the mouse in the minimap viewport indicator while dragging it.
+    pub(super) minimap_drag_offset: Option<Pixels>,
     /// This flag tells the renderer to prefer the end of the current visual line.
     pub(crate) cursor_line_end_affinity: bool,
     pub(super) pattern: Option<regex::Regex>,
     pub(super) validate: Option<Box<dyn Fn(&str, &mut Context<Self>) -> bool + 'static>>,
+    /// See [`Self::max_length`].
+    pub(super) max_length: Option<usize>,
     /// The step strategy for [`super::NumberInput`] to increment/decrement.
     /// See [`Self::step`] and [`Self::step_by`].
     pub(super) number_step: Option<NumberStep>,
     /// The minimum value for [`super::NumberInput`]. See [`Self::min`].
-    pub(super) number_min: Option<f64>,
+    pub(super) number_min: Option<NumberValue>,
     /// The maximum value for [`super::NumberInput`]. See [`Self::max`].
-    pub(super) number_max: Option<f64>,
+    pub(super) number_max: Option<NumberValue>,
+    /// See [`Self::number_type`].
+    pub(super) number_type: NumberType,
+    /// See [`Self::number_format`].
+    pub(super) number_format: Option<NumberInputFormat>,
+    /// Whether the text is the formatted display of the [`Self::number_format`].
+    pub(super) number_formatted: bool,
     pub(crate) scroll_handle: ScrollHandle,
     /// The deferred scroll offset to apply on next layout.
     pub(crate) deferred_scroll_offset: Option<Point<Pixels>>,
@@ -425,8 +587,13 @@ pub struct InputState {
     /// A flag to indicate if we are currently inserting a completion item.
     pub(super) completion_inserting: bool,
     pub(super) hover_popover: Option<Entity<HoverPopover>>,
+    pub(super) signature_help_popover: Option<Entity<SignatureHelpPopover>>,
     /// The LSP definitions locations for "Go to Definition" feature.
     pub(super) hover_definition: HoverDefinition,
+    /// The "Peek Definition" view.
+    pub(super) definition_peek: Option<Entity<DefinitionPeek>>,
+    /// The inline rename editor.
+    pub(super) rename_editor: Option<Entity<RenameEditor>>,
 
     pub lsp: Lsp,
 
@@ -444,6 +611,25 @@ pub struct InputState {
     /// The first element is the x-coordinate (Pixels), preferred to use this.
     /// The second element is the column (usize), fallback to use this.
     pub(super) preferred_column: Option<(Pixels, usize)>,
+    /// The extra cursors for multi-cursor editing (Alt+Click, Cmd+D), the primary
+    /// cursor is `selected_range`.
+    pub(super) extra_cursors: Vec<CursorState>,
+    /// The column selection in progress, by Alt+Drag or in the column selection mode.
+    pub(super) column_selection: Option<ColumnSelection>,
+    /// See [`Self::column_selection_mode`].
+    pub(super) column_selection_mode: bool,
+    /// The active snippet to cycle the tab stops, see [`Self::insert_snippet`].
+    pub(super) snippet: Option<SnippetSession>,
+    /// The inputs sharing the text buffer with this one, see [`Self::share_buffer`].
+    pub(super) shared_buffer: Option<SharedBuffer>,
+    /// See [`Self::large_file_threshold`].
+    pub(super) large_file_threshold: usize,
+    /// The soft wrap before the text exceeds the `large_file_threshold`, `None` if the text is not large.
+    pub(super) large_file: Option<bool>,
+    /// The loading of the text started by [`Self::load_value`].
+    pub(super) value_loading: Option<ValueLoading>,
+    /// See [`Self::value_history`].
+    pub(super) value_history: Option<ValueHistory>,
     _subscriptions: Vec<Subscription>,
 
     pub(super) _context_menu_task: Task<Result<()>>,
@@ -506,12 +692,32 @@ impl InputState {
             scroll_beyond_last_line: None,
             cursor_surrounding_lines: None,
             show_whitespaces: false,
+            show_trailing_whitespaces: false,
+            show_line_endings: false,
+            highlight_current_line: true,
+            line_number_mode: LineNumberMode::default(),
+            minimap: false,
+            sticky_scroll: false,
+            highlight_active_indent_guide: false,
+            minimap_drag_offset: None,
+            highlight_occurrences: true,
+            code_action_lightbulb: true,
+            vim: VimState::default(),
+            auto_close_brackets: true,
+            rulers: vec![],
+            trim_trailing_whitespace: false,
+            language_settings: None,
+            gutter_marks: Rc::new(vec![]),
             loading: false,
             pattern: None,
             validate: None,
+            max_length: None,
             number_step: Some(NumberStep::Fixed(1.)),
             number_min: None,
             number_max: None,
+            number_type: NumberType::default(),
+            number_format: None,
+            number_formatted: false,
             mode: InputMode::default(),
             last_layout: None,
             last_bounds: None,
@@ -540,7 +746,10 @@ impl InputState {
             enable_context_menu: true,
             completion_inserting: false,
             hover_popover: None,
+            signature_help_popover: None,
             hover_definition: HoverDefinition::default(),
+            definition_peek: None,
+            rename_editor: None,
             silent_replace_text: false,
             emit_events: true,
             size: Size::default(),
@@ -550,6 +759,15 @@ impl InputState {
             inline_completion: InlineCompletion::default(),
             cursor_line_end_affinity: false,
             auto_scroll: AutoScroll::default(),
+            extra_cursors: vec![],
+            column_selection: None,
+            column_selection_mode: false,
+            snippet: None,
+            shared_buffer: None,
+            large_file_threshold: DEFAULT_LARGE_FILE_THRESHOLD,
+            large_file: None,
+            value_loading: None,
+            value_history: None,
         }
     }
 
@@ -605,8 +823,12 @@ impl InputState {
     }
 
     /// Set this input is searchable, default is false (Default true for Code Editor).
+    ///
+    /// When enabled, `Cmd+F` (`Ctrl+F` on Windows/Linux) opens the search bar, and
+    /// `Cmd+Alt+F` (`Ctrl+H` on Windows/Linux) opens it in replace mode.
+    ///
+    /// In single line mode, the search bar is shown as an overlay below the input.
     pub fn searchable(mut self, searchable: bool) -> Self {
-        debug_assert!(self.mode.is_multi_line());
         self.searchable = searchable;
         self
     }
@@ -666,6 +888,48 @@ impl InputState {
         cx.notify();
     }
 
+    /// Set how to number the lines in the gutter, only for [`InputMode::CodeEditor`] mode,
+    /// default is [`LineNumberMode::Absolute`].
+    pub fn line_number_mode(mut self, mode: LineNumberMode) -> Self {
+        debug_assert!(self.mode.is_code_editor());
+        self.line_number_mode = mode;
+        self
+    }
+
+    /// Update how to number the lines in the gutter, only for [`InputMode::CodeEditor`] mode.
+    pub fn set_line_number_mode(
+        &mut self,
+        mode: LineNumberMode,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        debug_assert!(self.mode.is_code_editor());
+        self.line_number_mode = mode;
+        cx.notify();
+    }
+
+    /// Set whether to highlight the background of the cursor line, only for
+    /// [`InputMode::CodeEditor`] mode, default is true.
+    ///
+    /// The color is the `editor.active_line.background` of the highlight theme.
+    pub fn highlight_current_line(mut self, highlight: bool) -> Self {
+        debug_assert!(self.mode.is_code_editor());
+        self.highlight_current_line = highlight;
+        self
+    }
+
+    /// Update whether to highlight the background of the cursor line.
+    pub fn set_highlight_current_line(
+        &mut self,
+        highlight: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        debug_assert!(self.mode.is_code_editor());
+        self.highlight_current_line = highlight;
+        cx.notify();
+    }
+
     /// Set the number of rows for the multi-line Textarea.
     ///
     /// This is only used when `multi_line` is set to true.
@@ -707,10 +971,11 @@ impl InputState {
             }
             _ => {}
         }
+        self.update_language_settings(cx);
         cx.notify();
     }
 
-    fn reset_highlighter(&mut self, cx: &mut Context<Self>) {
+    pub(super) fn reset_highlighter(&mut self, cx: &mut Context<Self>) {
         match &mut self.mode {
             InputMode::CodeEditor {
                 highlighter,
@@ -783,15 +1048,23 @@ impl InputState {
     /// the view is scrolled back to the start, so a long value shows its
     /// beginning instead of its tail (matching HTML `<input>`). Multi-line
     /// inputs reset the selection to `0..0`.
+    ///
+    /// To open a very large file without blocking the UI, use [`Self::load_value`].
     pub fn set_value(
         &mut self,
         value: impl Into<SharedString>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        let value: SharedString = value.into();
+        self.cancel_value_loading();
+        self.number_formatted = false;
+        self.update_large_file(value.len(), cx);
         self.history.ignore = true;
         self.emit_events = false;
+        let max_length = self.max_length.take();
         self.replace_text(value, window, cx);
+        self.max_length = max_length;
         self.history.ignore = false;
         self.emit_events = true;
 
@@ -876,7 +1149,8 @@ impl InputState {
         self.disabled = was_disabled;
     }
 
-    fn reset_selection(&mut self) {
+    pub(super) fn reset_selection(&mut self) {
+        self.extra_cursors.clear();
         // For single-line inputs the caret is placed at the end of the text
         // (matching HTML `<input>`); multi-line inputs reset the selection to
         // `0..0`.
@@ -888,14 +1162,14 @@ impl InputState {
         }
     }
 
-    fn reset_lsp_state(&mut self) {
+    pub(super) fn reset_lsp_state(&mut self) {
         if self.mode.is_code_editor() {
             self._pending_update = true;
             self.lsp.reset();
         }
     }
 
-    fn reset_scroll_to_start(&mut self) {
+    pub(super) fn reset_scroll_to_start(&mut self) {
         // Move scroll to the start. For single-line the caret is at the end, so
         // override the cursor-follow scroll for the next painted frame to keep
         // the start visible; the deferred offset is consumed during that paint.
@@ -960,9 +1234,30 @@ impl InputState {
         self
     }
 
+    /// Set whether to show the whitespace characters at the end of the lines, default is false.
+    ///
+    /// This is useful to show only the trailing whitespaces without [`Self::show_whitespaces`],
+    /// e.g. in a code review.
+    pub fn show_trailing_whitespaces(mut self, show: bool) -> Self {
+        self.show_trailing_whitespaces = show;
+        self
+    }
+
+    /// Set whether to show a marker at the end of the lines, `¬` for LF and `¤¬` for CRLF,
+    /// default is false.
+    pub fn show_line_endings(mut self, show: bool) -> Self {
+        debug_assert!(self.mode.is_multi_line());
+        self.show_line_endings = show;
+        self
+    }
+
     /// Update the soft wrap mode for multi-line input, default is true.
     pub fn set_soft_wrap(&mut self, wrap: bool, _: &mut Window, cx: &mut Context<Self>) {
         debug_assert!(self.mode.is_multi_line());
+        self.update_soft_wrap(wrap, cx);
+    }
+
+    pub(super) fn update_soft_wrap(&mut self, wrap: bool, cx: &mut Context<Self>) {
         self.soft_wrap = wrap;
         if wrap {
             let wrap_width = self
@@ -989,6 +1284,111 @@ impl InputState {
         cx.notify();
     }
 
+    /// Update whether to show the whitespace characters at the end of the lines.
+    pub fn set_show_trailing_whitespaces(
+        &mut self,
+        show: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_trailing_whitespaces = show;
+        cx.notify();
+    }
+
+    /// Update whether to show a marker at the end of the lines.
+    pub fn set_show_line_endings(&mut self, show: bool, _: &mut Window, cx: &mut Context<Self>) {
+        debug_assert!(self.mode.is_multi_line());
+        self.show_line_endings = show;
+        cx.notify();
+    }
+
+    /// Returns which spaces and tabs to show the indicators.
+    pub(super) fn whitespace_visibility(&self) -> WhitespaceVisibility {
+        if self.show_whitespaces {
+            WhitespaceVisibility::All
+        } else if self.show_trailing_whitespaces {
+            WhitespaceVisibility::Trailing
+        } else {
+            WhitespaceVisibility::None
+        }
+    }
+
+    /// Set whether to show the minimap, only for [`InputMode::CodeEditor`] mode, default is false.
+    ///
+    /// The minimap is a scaled-down preview of the text on the right side of the editor,
+    /// with the visible area and the diagnostics marked. Click or drag on it to scroll.
+    pub fn minimap(mut self, minimap: bool) -> Self {
+        debug_assert!(self.mode.is_code_editor());
+        self.minimap = minimap;
+        self
+    }
+
+    /// Update whether to show the minimap, only for [`InputMode::CodeEditor`] mode.
+    pub fn set_minimap(&mut self, minimap: bool, _: &mut Window, cx: &mut Context<Self>) {
+        debug_assert!(self.mode.is_code_editor());
+        self.minimap = minimap;
+        cx.notify();
+    }
+
+    /// Set whether to highlight the occurrences of the word under the cursor, only for
+    /// [`InputMode::CodeEditor`] mode, default is true.
+    ///
+    /// The occurrences come from the [`DocumentHighlightProvider`] if present,
+    /// otherwise the same whole words in the visible text are highlighted.
+    ///
+    /// [`DocumentHighlightProvider`]: crate::input::DocumentHighlightProvider
+    pub fn highlight_occurrences(mut self, highlight: bool) -> Self {
+        self.highlight_occurrences = highlight;
+        self
+    }
+
+    /// Update whether to highlight the occurrences of the word under the cursor.
+    pub fn set_highlight_occurrences(
+        &mut self,
+        highlight: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.highlight_occurrences = highlight;
+        self.update_document_highlights(window, cx);
+        cx.notify();
+    }
+
+    /// Set true to enable the Vim keybinding mode, default is false.
+    ///
+    /// The input starts in [`VimMode::Normal`] mode, press `i` to insert text and
+    /// `Escape` to go back to Normal mode. Use [`Self::current_vim_mode`] to get
+    /// the current mode.
+    ///
+    /// [`VimMode::Normal`]: crate::input::VimMode::Normal
+    pub fn vim_mode(mut self, enabled: bool) -> Self {
+        self.vim.set_enabled(enabled);
+        self
+    }
+
+    /// Set whether to enable the Vim keybinding mode.
+    pub fn set_vim_mode(&mut self, enabled: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.vim.set_enabled(enabled);
+        cx.notify();
+    }
+
+    /// Set the key remapping layer of the Vim keybinding mode, see [`VimKeymap`].
+    pub fn vim_keymap(mut self, keymap: VimKeymap) -> Self {
+        self.vim.keymap = Rc::new(keymap);
+        self
+    }
+
+    /// Returns true if the minimap is visible.
+    pub(super) fn is_minimap_visible(&self) -> bool {
+        self.minimap && self.mode.is_code_editor() && self.mode.is_multi_line()
+    }
+
+    /// Scroll the editor vertically to `scroll_top`, used by the minimap.
+    pub(super) fn set_minimap_scroll_top(&mut self, scroll_top: Pixels, cx: &mut Context<Self>) {
+        let offset = point(self.scroll_handle.offset().x, -scroll_top);
+        self.update_scroll_offset(Some(offset), cx);
+    }
+
     /// Empty rows reserved below the last line of content ("scroll
     /// beyond last line"), code-editor mode only. Mirrors VSCode's
     /// `editor.scrollBeyondLastLine` / Zed's `scroll_beyond_last_line`.
@@ -1077,6 +1477,26 @@ impl InputState {
         self
     }
 
+    /// Set the maximum number of characters the user can enter, default is `None` for no limit.
+    ///
+    /// The typed or pasted text is cut to fit, and [`InputEvent::MaxLengthExceeded`] is emitted.
+    /// Like the HTML `maxlength`, the value set by [`Self::set_value`] is not limited.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Update the maximum number of characters the user can enter, `None` for no limit.
+    pub fn set_max_length(
+        &mut self,
+        max_length: impl Into<Option<usize>>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.max_length = max_length.into();
+        cx.notify();
+    }
+
     /// Set the step value of the [`super::NumberInput`] for increment/decrement.
     ///
     /// Only for [`InputMode::SingleLine`] mode with [`super::NumberInput`].
@@ -1133,9 +1553,9 @@ impl InputState {
     /// The value will be clamped to the minimum value on stepping and on
     /// blur (only if the clamped value passes the `pattern`/`validate` check).
     /// See also [`Self::step`].
-    pub fn min(mut self, min: f64) -> Self {
+    pub fn min(mut self, min: impl Into<NumberValue>) -> Self {
         debug_assert!(self.mode.is_single_line());
-        self.number_min = Some(min);
+        self.number_min = Some(min.into());
         self
     }
 
@@ -1146,9 +1566,9 @@ impl InputState {
     /// The value will be clamped to the maximum value on stepping and on
     /// blur (only if the clamped value passes the `pattern`/`validate` check).
     /// See also [`Self::step`].
-    pub fn max(mut self, max: f64) -> Self {
+    pub fn max(mut self, max: impl Into<NumberValue>) -> Self {
         debug_assert!(self.mode.is_single_line());
-        self.number_max = Some(max);
+        self.number_max = Some(max.into());
         self
     }
 
@@ -1167,13 +1587,13 @@ impl InputState {
     }
 
     /// Update the minimum value after construction. See [`Self::min`].
-    pub fn set_min(&mut self, min: Option<f64>, _: &mut Window, _: &mut Context<Self>) {
+    pub fn set_min(&mut self, min: Option<NumberValue>, _: &mut Window, _: &mut Context<Self>) {
         debug_assert!(self.mode.is_single_line());
         self.number_min = min;
     }
 
     /// Update the maximum value after construction. See [`Self::max`].
-    pub fn set_max(&mut self, max: Option<f64>, _: &mut Window, _: &mut Context<Self>) {
+    pub fn set_max(&mut self, max: Option<NumberValue>, _: &mut Window, _: &mut Context<Self>) {
         debug_assert!(self.mode.is_single_line());
         self.number_max = max;
     }
@@ -1205,6 +1625,16 @@ impl InputState {
         SharedString::new(self.text.to_string())
     }
 
+    /// Return the number of characters of the value.
+    pub fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Return the number of words of the value, split by the Unicode word boundaries.
+    pub fn word_count(&self) -> usize {
+        self.text.to_string().unicode_words().count()
+    }
+
     /// Return the portion of the value within the input field that
     /// is selected by the user
     pub fn selected_value(&self) -> SharedString {
@@ -1212,8 +1642,17 @@ impl InputState {
     }
 
     /// Return the value without mask.
+    ///
+    /// For the [`super::NumberInput`] with [`Self::number_format`], this is the raw value
+    /// without the group separators.
     pub fn unmask_value(&self) -> SharedString {
-        self.mask_pattern.unmask(&self.text.to_string()).into()
+        let text = self.text.to_string();
+        match &self.number_format {
+            Some(format) if self.number_formatted => {
+                self.mask_pattern.unmask(&format.unformat(&text)).into()
+            }
+            _ => self.mask_pattern.unmask(&text).into(),
+        }
     }
 
     /// Return the text [`Rope`] of the input field.
@@ -1270,15 +1709,48 @@ impl InputState {
         cx.notify();
     }
 
-    pub(super) fn select_left(&mut self, _: &SelectLeft, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn select_left(
+        &mut self,
+        action: &SelectLeft,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.select_left(action, window, cx)
+            });
+            return;
+        }
         self.select_to(self.previous_boundary(self.cursor()), cx);
     }
 
-    pub(super) fn select_right(&mut self, _: &SelectRight, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn select_right(
+        &mut self,
+        action: &SelectRight,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.select_right(action, window, cx)
+            });
+            return;
+        }
         self.select_to(self.next_boundary(self.cursor()), cx);
     }
 
-    pub(super) fn select_up(&mut self, _: &SelectUp, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn select_up(
+        &mut self,
+        action: &SelectUp,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.select_up(action, window, cx)
+            });
+            return;
+        }
         if self.mode.is_single_line() {
             return;
         }
@@ -1286,7 +1758,18 @@ impl InputState {
         self.select_to(self.previous_boundary(offset), cx);
     }
 
-    pub(super) fn select_down(&mut self, _: &SelectDown, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn select_down(
+        &mut self,
+        action: &SelectDown,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.select_down(action, window, cx)
+            });
+            return;
+        }
         if self.mode.is_single_line() {
             return;
         }
@@ -1294,7 +1777,8 @@ impl InputState {
         self.select_to(self.next_boundary(offset), cx);
     }
 
-    pub(super) fn select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
+    pub(crate) fn select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
+        self.extra_cursors.clear();
         self.selected_range = (0..self.text.len()).into();
         cx.notify();
     }
@@ -1320,40 +1804,64 @@ impl InputState {
 
     pub(super) fn select_to_start_of_line(
         &mut self,
-        _: &SelectToStartOfLine,
-        _: &mut Window,
+        action: &SelectToStartOfLine,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.select_to_start_of_line(action, window, cx)
+            });
+            return;
+        }
         let offset = self.start_of_line();
         self.select_to(offset, cx);
     }
 
     pub(super) fn select_to_end_of_line(
         &mut self,
-        _: &SelectToEndOfLine,
-        _: &mut Window,
+        action: &SelectToEndOfLine,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.select_to_end_of_line(action, window, cx)
+            });
+            return;
+        }
         let offset = self.end_of_line();
         self.select_to(offset, cx);
     }
 
     pub(super) fn select_to_previous_word(
         &mut self,
-        _: &SelectToPreviousWordStart,
-        _: &mut Window,
+        action: &SelectToPreviousWordStart,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.select_to_previous_word(action, window, cx)
+            });
+            return;
+        }
         let offset = self.previous_start_of_word();
         self.select_to(offset, cx);
     }
 
     pub(super) fn select_to_next_word(
         &mut self,
-        _: &SelectToNextWordEnd,
-        _: &mut Window,
+        action: &SelectToNextWordEnd,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.select_to_next_word(action, window, cx)
+            });
+            return;
+        }
         let offset = self.next_end_of_word();
         self.select_to(offset, cx);
     }
@@ -1504,15 +2012,36 @@ impl InputState {
         }
     }
 
-    pub(super) fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn backspace(
+        &mut self,
+        action: &Backspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.backspace(action, window, cx)
+            });
+            return;
+        }
         if self.selected_range.is_empty() {
-            self.select_to(self.previous_boundary(self.cursor()), cx)
+            if let Some(range) = self.empty_bracket_pair_range() {
+                self.selected_range = range.into();
+            } else {
+                self.select_to(self.previous_boundary(self.cursor()), cx)
+            }
         }
         self.replace_text_in_range(None, "", window, cx);
         self.pause_blink_cursor(cx);
     }
 
-    pub(super) fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn delete(&mut self, action: &Delete, window: &mut Window, cx: &mut Context<Self>) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.delete(action, window, cx)
+            });
+            return;
+        }
         if self.selected_range.is_empty() {
             self.select_to(self.next_boundary(self.cursor()), cx)
         }
@@ -1522,10 +2051,16 @@ impl InputState {
 
     pub(super) fn delete_to_beginning_of_line(
         &mut self,
-        _: &DeleteToBeginningOfLine,
+        action: &DeleteToBeginningOfLine,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.delete_to_beginning_of_line(action, window, cx)
+            });
+            return;
+        }
         if !self.selected_range.is_empty() {
             self.replace_text_in_range(None, "", window, cx);
             self.pause_blink_cursor(cx);
@@ -1547,10 +2082,16 @@ impl InputState {
 
     pub(super) fn delete_to_end_of_line(
         &mut self,
-        _: &DeleteToEndOfLine,
+        action: &DeleteToEndOfLine,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.delete_to_end_of_line(action, window, cx)
+            });
+            return;
+        }
         if !self.selected_range.is_empty() {
             self.replace_text_in_range(None, "", window, cx);
             self.pause_blink_cursor(cx);
@@ -1572,10 +2113,16 @@ impl InputState {
 
     pub(super) fn delete_previous_word(
         &mut self,
-        _: &DeleteToPreviousWordStart,
+        action: &DeleteToPreviousWordStart,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.delete_previous_word(action, window, cx)
+            });
+            return;
+        }
         if !self.selected_range.is_empty() {
             self.replace_text_in_range(None, "", window, cx);
             self.pause_blink_cursor(cx);
@@ -1594,10 +2141,16 @@ impl InputState {
 
     pub(super) fn delete_next_word(
         &mut self,
-        _: &DeleteToNextWordEnd,
+        action: &DeleteToNextWordEnd,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.has_extra_cursors() {
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                this.delete_next_word(action, window, cx)
+            });
+            return;
+        }
         if !self.selected_range.is_empty() {
             self.replace_text_in_range(None, "", window, cx);
             self.pause_blink_cursor(cx);
@@ -1631,20 +2184,37 @@ impl InputState {
         let insert_newline = self.mode.is_multi_line() && (!self.submit_on_enter || action.shift);
 
         if insert_newline {
-            // Get current line indent
-            let indent = if self.mode.is_code_editor() {
-                self.indent_of_next_line()
-            } else {
-                "".to_string()
-            };
+            // Insert at every cursor when multi-cursor editing.
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                // Indent by the indents query of the language, or keep the current line indent.
+                let auto_indent = if this.mode.is_code_editor() {
+                    this.auto_indent_new_line()
+                } else {
+                    None
+                };
+                let (new_line_text, cursor) = auto_indent.unwrap_or_else(|| {
+                    let indent = if this.mode.is_code_editor() {
+                        this.indent_of_next_line()
+                    } else {
+                        "".to_string()
+                    };
+                    let text = format!("\n{}", indent);
+                    let cursor = text.len();
+                    (text, cursor)
+                });
 
-            // Add newline and indent
-            let new_line_text = format!("\n{}", indent);
-            self.replace_text_in_range_silent(None, &new_line_text, window, cx);
+                // Add newline and indent
+                let start = this.selected_range.start;
+                this.replace_text_in_range_silent(None, &new_line_text, window, cx);
+                if cursor < new_line_text.len() {
+                    this.selected_range = (start + cursor..start + cursor).into();
+                }
+            });
             self.pause_blink_cursor(cx);
         } else {
             // Single line input or submit-on-enter: just emit the event
             // (e.g.: in a dialog to confirm, or a chat textarea to send).
+            self.commit_history_value(cx);
             cx.propagate();
         }
 
@@ -1675,6 +2245,30 @@ impl InputState {
             self.unmark_text(window, cx);
         }
 
+        if self.vim_escape(cx) {
+            return;
+        }
+
+        if self.exit_snippet() {
+            self.clear_extra_cursors();
+            cx.notify();
+            return;
+        }
+
+        if self.clear_extra_cursors() {
+            cx.notify();
+            return;
+        }
+
+        if self.definition_peek.is_some() {
+            self.close_definition_peek(window, cx);
+            return;
+        }
+
+        if self.hide_signature_help(cx) {
+            return;
+        }
+
         if self.clean_on_escape {
             return self.clean(window, cx);
         }
@@ -1709,6 +2303,8 @@ impl InputState {
 
             let is_enable = !self.disabled;
             let has_goto_definition = is_enable && self.lsp.definition_provider.is_some();
+            let has_rename = is_enable && self.lsp.rename_provider.is_some();
+            let has_format = is_enable && self.lsp.formatting_provider.is_some();
             let has_code_action = is_enable && !self.lsp.code_action_providers.is_empty();
             let is_selected = !self.selected_range.is_empty();
             let has_paste = is_enable && cx.read_from_clipboard().is_some();
@@ -1721,11 +2317,32 @@ impl InputState {
                         !has_goto_definition,
                         Box::new(crate::input::GoToDefinition),
                     )
+                    .menu_with_disabled(
+                        rust_i18n::t!("Input.Peek Definition"),
+                        !has_goto_definition,
+                        Box::new(crate::input::PeekDefinition),
+                    )
+                    .menu_with_disabled(
+                        rust_i18n::t!("Input.Rename Symbol"),
+                        !has_rename,
+                        Box::new(crate::input::Rename),
+                    )
                     .menu_with_disabled(
                         rust_i18n::t!("Input.Show Code Actions"),
                         !has_code_action,
                         Box::new(crate::input::ToggleCodeActions),
                     )
+                    .separator()
+                    .menu_with_disabled(
+                        rust_i18n::t!("Input.Format Document"),
+                        !has_format,
+                        Box::new(crate::input::Format),
+                    )
+                    .menu_with_disabled(
+                        rust_i18n::t!("Input.Format Selection"),
+                        !(has_format && is_selected),
+                        Box::new(crate::input::FormatSelection),
+                    )
                     .separator();
             }
 
@@ -1775,9 +2392,36 @@ impl InputState {
             }
         }
 
-        self.selecting = true;
         let offset = self.index_for_mouse_position(event.position);
 
+        // Alt+Click to add (or remove) a cursor, and Alt+Drag to select a column.
+        if event.button == MouseButton::Left
+            && event.modifiers.alt
+            && event.click_count == 1
+            && self.mode.is_multi_line()
+        {
+            self.add_cursor(offset, cx);
+            self.start_column_selection(offset);
+            self.selecting = true;
+            return;
+        }
+
+        self.selecting = true;
+        if self.clear_extra_cursors() {
+            cx.notify();
+        }
+
+        if event.button == MouseButton::Left
+            && event.click_count == 1
+            && !event.modifiers.shift
+            && self.column_selection_mode
+            && self.mode.is_multi_line()
+        {
+            self.move_to(offset, None, cx);
+            self.start_column_selection(offset);
+            return;
+        }
+
         if self.handle_click_hover_definition(event, offset, window, cx) {
             return;
         }
@@ -1827,6 +2471,7 @@ impl InputState {
             self.selection_reversed = false;
         }
         self.selecting = false;
+        self.column_selection = None;
         self.selected_word_range = None;
         self.auto_scroll.stop();
     }
@@ -2035,6 +2680,11 @@ impl InputState {
     }
 
     pub(super) fn copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
+        if self.has_extra_cursors() {
+            self.copy_cursors(cx);
+            return;
+        }
+
         if self.selected_range.is_empty() {
             return;
         }
@@ -2044,6 +2694,16 @@ impl InputState {
     }
 
     pub(super) fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
+        if self.has_extra_cursors() {
+            self.copy_cursors(cx);
+            self.for_each_cursor(window, cx, |this, window, cx| {
+                if !this.selected_range.is_empty() {
+                    this.replace_text_in_range_silent(None, "", window, cx);
+                }
+            });
+            return;
+        }
+
         if self.selected_range.is_empty() {
             return;
         }
@@ -2061,12 +2721,17 @@ impl InputState {
                 new_text = new_text.replace('\n', "");
             }
 
+            if self.has_extra_cursors() {
+                self.paste_cursors(&new_text, window, cx);
+                return;
+            }
+
             self.replace_text_in_range_silent(None, &new_text, window, cx);
             self.scroll_to(self.cursor(), None, cx);
         }
     }
 
-    fn push_history(&mut self, text: &Rope, range: &Range<usize>, new_text: &str) {
+    pub(super) fn push_history(&mut self, text: &Rope, range: &Range<usize>, new_text: &str) {
         if self.history.ignore {
             return;
         }
@@ -2081,6 +2746,8 @@ impl InputState {
     }
 
     pub(super) fn undo(&mut self, _: &Undo, window: &mut Window, cx: &mut Context<Self>) {
+        self.extra_cursors.clear();
+        self.snippet = None;
         self.history.ignore = true;
         if let Some(changes) = self.history.undo() {
             for change in changes {
@@ -2092,6 +2759,8 @@ impl InputState {
     }
 
     pub(super) fn redo(&mut self, _: &Redo, window: &mut Window, cx: &mut Context<Self>) {
+        self.extra_cursors.clear();
+        self.snippet = None;
         self.history.ignore = true;
         if let Some(changes) = self.history.redo() {
             for change in changes {
@@ -2247,6 +2916,7 @@ impl InputState {
     /// Ensure the offset use self.next_boundary or self.previous_boundary to get the correct offset.
     pub(crate) fn select_to(&mut self, offset: usize, cx: &mut Context<Self>) {
         self.clear_inline_completion(cx);
+        self.extra_cursors.clear();
 
         let offset = offset.clamp(0, self.text.len());
         if self.selection_reversed {
@@ -2304,7 +2974,7 @@ impl InputState {
 
     /// If offset falls on a hidden (folded) line, clamp backward to the end of
     /// the fold header line (last visible position before the fold).
-    fn clamp_offset_to_visible_backward(&self, offset: usize) -> usize {
+    pub(super) fn clamp_offset_to_visible_backward(&self, offset: usize) -> usize {
         let line = self.text.offset_to_point(offset).row;
         if self.display_map.is_buffer_line_hidden(line) {
             for fold in self.display_map.folded_ranges() {
@@ -2318,7 +2988,7 @@ impl InputState {
 
     /// If offset falls on a hidden (folded) line, clamp forward to the start of
     /// the fold end line (first visible position after the fold).
-    fn clamp_offset_to_visible_forward(&self, offset: usize) -> usize {
+    pub(super) fn clamp_offset_to_visible_forward(&self, offset: usize) -> usize {
         let line = self.text.offset_to_point(offset).row;
         if self.display_map.is_buffer_line_hidden(line) {
             for fold in self.display_map.folded_ranges() {
@@ -2360,10 +3030,11 @@ impl InputState {
             && window.is_window_active()
     }
 
-    fn on_focus(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+    fn on_focus(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.blink_cursor.update(cx, |cursor, cx| {
             cursor.start(cx);
         });
+        self.update_number_display(false, window, cx);
         cx.emit(InputEvent::Focus);
     }
 
@@ -2378,6 +3049,7 @@ impl InputState {
         self.hover_popover = None;
         self.diagnostic_popover = None;
         self.context_menu_content = None;
+        self.hide_signature_help(cx);
         self.clear_inline_completion(cx);
         self.blink_cursor.update(cx, |cursor, cx| {
             cursor.stop(cx);
@@ -2386,6 +3058,7 @@ impl InputState {
             root.focused_input = None;
         });
         self.clamp_number_value(window, cx);
+        self.update_number_display(true, window, cx);
         cx.emit(InputEvent::Blur);
         cx.notify();
     }
@@ -2405,19 +3078,17 @@ impl InputState {
             return;
         }
 
-        let Ok(value) = self.unmask_value().parse::<f64>() else {
+        let Some(new_text) = clamp_number_text(
+            &self.unmask_value(),
+            self.number_type,
+            self.number_min,
+            self.number_max,
+        ) else {
             return;
         };
 
-        let clamped = match (self.number_min, self.number_max) {
-            (Some(min), _) if value < min => min,
-            (_, Some(max)) if value > max => max,
-            _ => return,
-        };
-
         // The clamped value must pass the `pattern`/`validate` check,
         // otherwise keep the value as is.
-        let new_text = clamped.to_string();
         if !self.is_valid_input(&new_text, cx) {
             return;
         }
@@ -2432,8 +3103,16 @@ impl InputState {
         });
     }
 
-    pub(super) fn on_key_down(&mut self, _: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn on_key_down(
+        &mut self,
+        event: &KeyDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         self.pause_blink_cursor(cx);
+        if self.vim_handle_key_down(event, window, cx) {
+            cx.stop_propagation();
+        }
     }
 
     pub(super) fn on_drag_move(
@@ -2460,7 +3139,9 @@ impl InputState {
 
         self.auto_scroll.last_drag_position = Some(event.position);
         let offset = self.index_for_mouse_position(event.position);
-        self.select_to(offset, cx);
+        if !self.select_column_to(offset, cx) {
+            self.select_to(offset, cx);
+        }
 
         if !self.mode.is_single_line() {
             // Expand input_bounds by the CSS padding so the bounds reflect the full
@@ -2485,7 +3166,9 @@ impl InputState {
                 state.update_scroll_offset(Some(point(current.x, current.y + delta)), cx);
                 if let Some(pos) = state.auto_scroll.last_drag_position {
                     let offset = state.index_for_mouse_position(pos);
-                    state.select_to(offset, cx);
+                    if !state.select_column_to(offset, cx) {
+                        state.select_to(offset, cx);
+                    }
                 }
             });
         }
@@ -2519,6 +3202,10 @@ impl InputState {
             return false;
         }
 
+        if !self.is_valid_number_type(new_text) {
+            return false;
+        }
+
         let Some(pattern) = &self.pattern else {
             return true;
         };
@@ -2535,6 +3222,8 @@ impl InputState {
     /// - Other characters will be treated as literal mask characters
     ///
     /// Example: "(999)999-999" for phone numbers
+    ///
+    /// Use [`MaskPattern::custom`] with a [`super::MaskProvider`] for the custom formats.
     pub fn mask_pattern(mut self, pattern: impl Into<MaskPattern>) -> Self {
         self.mask_pattern = pattern.into();
         self.mask_pattern_set = true;
@@ -2654,7 +3343,11 @@ impl InputState {
 
     /// Incrementally update fold candidates after a text edit.
     /// Only traverses the edited region of the syntax tree instead of the full tree.
-    fn update_fold_candidates_incremental(&mut self, edit_range: &Range<usize>, new_text: &str) {
+    pub(super) fn update_fold_candidates_incremental(
+        &mut self,
+        edit_range: &Range<usize>,
+        new_text: &str,
+    ) {
         if !self.mode.is_folding() {
             return;
         }
@@ -2686,7 +3379,7 @@ impl InputState {
     /// Dropping the returned `Task` (stored in `parse_task`) cancels the
     /// parse, which naturally debounces rapid edits.
     #[cfg(feature = "tree-sitter")]
-    fn dispatch_background_parse(
+    pub(super) fn dispatch_background_parse(
         pending: super::mode::PendingBackgroundParse,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -2814,7 +3507,7 @@ impl InputState {
     }
 
     #[cfg(not(feature = "tree-sitter"))]
-    fn dispatch_background_parse(
+    pub(super) fn dispatch_background_parse(
         _pending: super::mode::PendingBackgroundParse,
         _window: &mut Window,
         _cx: &mut Context<Self>,
@@ -2876,10 +3569,30 @@ impl EntityInputHandler for InputState {
             return;
         }
 
+        if self.has_extra_cursors() {
+            // Typing (no explicit range) applies to every cursor, an edit of an
+            // explicit range would leave the extra cursors out of date.
+            if range_utf16.is_none() && self.ime_marked_range.is_none() {
+                self.for_each_cursor(window, cx, |this, window, cx| {
+                    this.replace_text_in_range(None, new_text, window, cx);
+                });
+                return;
+            }
+            self.extra_cursors.clear();
+        }
+
         if self.blink_cursor.read(cx).visible() {
             self.pause_blink_cursor(cx);
         }
 
+        if range_utf16.is_none()
+            && self.ime_marked_range.is_none()
+            && !self.silent_replace_text
+            && self.handle_bracket_input(new_text, window, cx)
+        {
+            return;
+        }
+
         // NOTE: The normalization keeps the UTF-16 length, but may change the
         // UTF-8 byte length, so all the byte-offset calculations below must
         // use the normalized text.
@@ -2895,6 +3608,20 @@ impl EntityInputHandler for InputState {
             }))
             .unwrap_or(self.selected_range.into());
 
+        let new_text = if let Some(max_length) = self.max_length {
+            let kept = self.char_count() - self.text.slice(range.clone()).chars().count();
+            let fitted = truncate_chars(new_text, max_length.saturating_sub(kept));
+            if fitted.len() < new_text.len() {
+                cx.emit(InputEvent::MaxLengthExceeded);
+                if fitted.is_empty() && range.is_empty() {
+                    return;
+                }
+            }
+            fitted
+        } else {
+            new_text
+        };
+
         let old_text = self.text.clone();
         self.text.replace(range.clone(), new_text);
 
@@ -2904,7 +3631,8 @@ impl EntityInputHandler for InputState {
         // separators or completing a leading dot.
         let mut mask_changed = false;
 
-        if self.mode.is_single_line() {
+        // The formatted number is not checked and masked, see `update_number_display`.
+        if self.mode.is_single_line() && !self.number_formatted {
             let pending_text = self.text.to_string();
             // Check if the new text is valid.
             //
@@ -2954,6 +3682,7 @@ impl EntityInputHandler for InputState {
         }
 
         self.update_fold_candidates_incremental(&range, new_text);
+        self.adjust_snippet_for_edit(&range, new_text.len());
         self.lsp.update(&self.text, window, cx);
         self.selected_range = (new_offset..new_offset).into();
         self.ime_marked_range.take();
@@ -2962,7 +3691,9 @@ impl EntityInputHandler for InputState {
         self.mode.update_auto_grow(&self.display_map);
         if !self.silent_replace_text {
             self.handle_completion_trigger(&range, &new_text, window, cx);
+            self.handle_signature_help_trigger(&new_text, window, cx);
         }
+        self.sync_shared_buffer(&old_text, &range, new_text, window, cx);
         if self.emit_events {
             cx.emit(InputEvent::Change);
         }
@@ -2982,6 +3713,7 @@ impl EntityInputHandler for InputState {
             return;
         }
 
+        self.extra_cursors.clear();
         self.lsp.reset();
 
         // See the same NOTE in `replace_text_in_range`.
@@ -3000,7 +3732,7 @@ impl EntityInputHandler for InputState {
         let old_text = self.text.clone();
         self.text.replace(range.clone(), new_text);
 
-        if self.mode.is_single_line() {
+        if self.mode.is_single_line() && !self.number_formatted {
             let pending_text = self.text.to_string();
             // See the same NOTE in `replace_text_in_range`.
             if !self.is_valid_input(&pending_text, cx)
@@ -3028,6 +3760,7 @@ impl EntityInputHandler for InputState {
         }
 
         self.update_fold_candidates_incremental(&range, new_text);
+        self.adjust_snippet_for_edit(&range, new_text.len());
         self.lsp.update(&self.text, window, cx);
         if new_text.is_empty() {
             // Cancel selection, when cancel IME input.
@@ -3045,6 +3778,7 @@ impl EntityInputHandler for InputState {
         self.mode.update_auto_grow(&self.display_map);
         self.history.start_grouping();
         self.push_history(&old_text, &range, new_text);
+        self.sync_shared_buffer(&old_text, &range, new_text, window, cx);
         cx.notify();
     }
 
@@ -3159,6 +3893,17 @@ impl Render for InputState {
             .children(self.diagnostic_popover.clone())
             .children(self.context_menu_content.as_ref().map(|menu| menu.render()))
             .children(self.hover_popover.clone())
+            .children(self.signature_help_popover.clone())
+            .children(self.definition_peek.clone())
+            .children(self.rename_editor.clone())
+    }
+}
+
+/// Returns the longest prefix of the `text` with at most `max_chars` characters.
+fn truncate_chars(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((ix, _)) => &text[..ix],
+        None => text,
     }
 }
 
@@ -3533,6 +4278,45 @@ ORDER BY id
         });
     }
 
+    #[gpui::test]
+    fn test_number_input_format(cx: &mut TestAppContext) {
+        let input_view = InputView::build(cx, |state| {
+            state
+                .mask_pattern(MaskPattern::Number {
+                    separator: None,
+                    fraction: None,
+                })
+                .number_type(NumberType::Integer)
+                .number_format(NumberInputFormat::locale("de"))
+                .max(i128::MAX)
+        });
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                // The fraction is not allowed for the integer type.
+                state.replace_text_in_range(None, "1", window, cx);
+                state.replace_text_in_range(None, ".", window, cx);
+                assert_eq!(state.value(), "1");
+
+                let range = state.range_to_utf16(&(0..state.text.len()));
+                state.replace_text_in_range(Some(range), "1234567", window, cx);
+                state.update_number_display(true, window, cx);
+                assert_eq!(state.value(), "1.234.567");
+                assert_eq!(state.unmask_value(), "1234567");
+                assert_eq!(state.number_value(), Some(NumberValue::Integer(1234567)));
+
+                // The raw value is shown to edit.
+                state.update_number_display(false, window, cx);
+                assert_eq!(state.value(), "1234567");
+
+                state.set_value("170141183460469231731687303715884105727", window, cx);
+                assert_eq!(state.number_value(), Some(NumberValue::Integer(i128::MAX)));
+            });
+        });
+    }
+
     #[gpui::test]
     fn test_number_input_undo_with_mask(cx: &mut TestAppContext) {
         let input_view = InputView::build(cx, |state| {
@@ -3849,4 +4633,527 @@ ORDER BY id
             });
         });
     }
+
+    #[gpui::test]
+    fn test_multi_cursor_edit(cx: &mut TestAppContext) {
+        let input_view = InputView::build(cx, |state| state.multi_line(true));
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |s, cx| {
+                s.set_value("foo bar foo baz foo", window, cx);
+                s.set_selected_range(0..3, cx);
+                s.select_next_occurrence(&SelectNextOccurrence, window, cx);
+                s.select_next_occurrence(&SelectNextOccurrence, window, cx);
+                assert_eq!(s.selected_ranges(), vec![0..3, 8..11, 16..19]);
+                // No more occurrences, keep the cursors.
+                s.select_next_occurrence(&SelectNextOccurrence, window, cx);
+                assert_eq!(s.selected_ranges(), vec![0..3, 8..11, 16..19]);
+
+                // Typing replaces every selection.
+                s.replace_text_in_range(None, "x", window, cx);
+                assert_eq!(s.value(), "x bar x baz x");
+                assert_eq!(s.selected_ranges(), vec![1..1, 7..7, 13..13]);
+
+                // A single undo step reverts the edits of all cursors.
+                s.undo(&Undo, window, cx);
+                assert_eq!(s.value(), "foo bar foo baz foo");
+                assert_eq!(s.selected_ranges().len(), 1);
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_multi_cursor_add_cursor(cx: &mut TestAppContext) {
+        let input_view = InputView::build(cx, |state| state.multi_line(true));
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |s, cx| {
+                s.set_value("ab\ncd\nef", window, cx);
+                s.set_selected_range(1..1, cx);
+                s.add_cursor(4, cx);
+                s.add_cursor(7, cx);
+                assert_eq!(s.selected_ranges(), vec![1..1, 4..4, 7..7]);
+                assert_eq!(s.cursor(), 7);
+
+                // Click on an extra cursor again to remove it.
+                s.add_cursor(4, cx);
+                assert_eq!(s.selected_ranges(), vec![1..1, 7..7]);
+                s.add_cursor(4, cx);
+
+                s.backspace(&Backspace, window, cx);
+                assert_eq!(s.value(), "b\nd\nf");
+                assert_eq!(s.selected_ranges(), vec![0..0, 2..2, 4..4]);
+
+                s.right(&MoveRight, window, cx);
+                assert_eq!(s.selected_ranges(), vec![1..1, 3..3, 5..5]);
+
+                s.enter(
+                    &Enter {
+                        secondary: false,
+                        shift: false,
+                    },
+                    window,
+                    cx,
+                );
+                assert_eq!(s.value(), "b\n\nd\n\nf\n");
+
+                // Other movements collapse to the primary cursor.
+                s.move_to_start(&MoveToStart, window, cx);
+                assert_eq!(s.selected_ranges(), vec![0..0]);
+
+                s.add_cursor(2, cx);
+                assert!(s.has_extra_cursors());
+                s.escape(&Escape, window, cx);
+                assert!(!s.has_extra_cursors());
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_fold_actions(cx: &mut TestAppContext) {
+        use crate::input::display_map::FoldRange;
+
+        let input_view = InputView::new(cx);
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                state.set_value("a\nb\nc\nd\ne\nf\ng", window, cx);
+            });
+        });
+        cx.run_until_parked();
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                state
+                    .display_map
+                    .set_fold_candidates(vec![FoldRange::new(0, 6), FoldRange::new(1, 4)]);
+
+                // Fold the innermost range at the cursor, the cursor moves out of the hidden lines.
+                let offset = state.text.line_start_offset(2);
+                state.set_selected_range(offset..offset, cx);
+                state.on_action_fold(&Fold, window, cx);
+                assert_eq!(state.folded_ranges(), &[FoldRange::new(1, 4)]);
+                assert_eq!(state.cursor(), state.text.line_end_offset(1));
+
+                state.on_action_fold(&Fold, window, cx);
+                assert_eq!(
+                    state.folded_ranges(),
+                    &[FoldRange::new(0, 6), FoldRange::new(1, 4)]
+                );
+                assert_eq!(state.cursor(), state.text.line_end_offset(0));
+
+                state.on_action_unfold(&Unfold, window, cx);
+                assert_eq!(state.folded_ranges(), &[FoldRange::new(1, 4)]);
+
+                state.on_action_unfold_all(&UnfoldAll, window, cx);
+                assert!(state.folded_ranges().is_empty());
+
+                state.on_action_fold_all(&FoldAll, window, cx);
+                assert_eq!(state.folded_ranges(), state.fold_candidates());
+
+                // Not a fold candidate.
+                state.unfold_all(cx);
+                state.toggle_fold(2, cx);
+                assert!(state.folded_ranges().is_empty());
+                state.toggle_fold(1, cx);
+                assert!(state.is_folded(1));
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_occurrences_for_range(cx: &mut TestAppContext) {
+        let input_view = InputView::new(cx);
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                state.set_value(
+                    "foo bar foo_bar
+foo(foo)",
+                    window,
+                    cx,
+                );
+            });
+        });
+
+        cx.update(|_, cx| {
+            input.update(cx, |state, cx| {
+                let all = 0..state.text.len();
+
+                // Cursor at the end of the word, only the whole words are matched.
+                state.set_selected_range(3..3, cx);
+                assert_eq!(
+                    state.occurrences_for_range(&all),
+                    vec![(0..3, false), (16..19, false), (20..23, false)]
+                );
+                assert_eq!(
+                    state.occurrences_for_range(&(10..20)),
+                    vec![(16..19, false)]
+                );
+
+                state.set_selected_range(7..7, cx);
+                assert_eq!(state.occurrences_for_range(&all), vec![(4..7, false)]);
+
+                // No highlights when has a selection.
+                state.set_selected_range(0..3, cx);
+                assert!(state.occurrences_for_range(&all).is_empty());
+
+                state.highlight_occurrences = false;
+                state.set_selected_range(1..1, cx);
+                assert!(state.occurrences_for_range(&all).is_empty());
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_vim_mode(cx: &mut TestAppContext) {
+        use crate::input::{VimMode, VimRegister};
+
+        let input_view = InputView::build(cx, |state| state.code_editor("sql").vim_mode(true));
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                state.set_value("foo bar\n  baz qux\nend", window, cx);
+                state.set_selected_range(0..0, cx);
+                assert_eq!(state.current_vim_mode(), Some(VimMode::Normal));
+
+                state.vim_input("w", window, cx);
+                assert_eq!(state.cursor(), 4);
+
+                // `dw` stops at the end of line, the cursor is kept on the last char.
+                state.vim_input("dw", window, cx);
+                assert_eq!(state.text.to_string(), "foo \n  baz qux\nend");
+                assert_eq!(state.cursor(), 3);
+                assert_eq!(
+                    state.vim_register('"'),
+                    Some(&VimRegister {
+                        text: "bar".into(),
+                        linewise: false,
+                    })
+                );
+
+                state.vim_input("j", window, cx);
+                assert_eq!(state.cursor(), 8);
+
+                state.vim_input("dd", window, cx);
+                assert_eq!(state.text.to_string(), "foo \nend");
+                assert_eq!(state.cursor(), 5);
+                assert_eq!(state.vim_register('"').unwrap().text, "  baz qux\n");
+
+                state.vim_input("P", window, cx);
+                assert_eq!(state.text.to_string(), "foo \n  baz qux\nend");
+                assert_eq!(state.cursor(), 7);
+
+                state.set_selected_range(0..0, cx);
+                state.vim_input("iX", window, cx);
+                assert_eq!(state.current_vim_mode(), Some(VimMode::Insert));
+                assert_eq!(state.text.to_string(), "Xfoo \n  baz qux\nend");
+
+                state.escape(&Escape, window, cx);
+                assert_eq!(state.current_vim_mode(), Some(VimMode::Normal));
+                assert_eq!(state.cursor(), 0);
+
+                state.vim_input("ve", window, cx);
+                assert_eq!(state.current_vim_mode(), Some(VimMode::Visual));
+                assert_eq!(state.selected_range(), 0..4);
+                state.vim_input("\"ay", window, cx);
+                assert_eq!(state.current_vim_mode(), Some(VimMode::Normal));
+                assert_eq!(state.vim_register('a').unwrap().text, "Xfoo");
+
+                state.vim_input("G", window, cx);
+                assert_eq!(state.cursor(), state.text.line_start_offset(2));
+                state.vim_input("cwEND", window, cx);
+                assert_eq!(state.text.to_string(), "Xfoo \n  baz qux\nEND");
+
+                state.set_vim_mode(false, window, cx);
+                assert_eq!(state.current_vim_mode(), None);
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_language_settings(cx: &mut TestAppContext) {
+        use crate::input::{LanguageSettings, LanguageSettingsTable};
+
+        let input_view = InputView::build(cx, |state| {
+            state.code_editor("sql").language_settings(
+                LanguageSettingsTable::new()
+                    .default_settings(LanguageSettings::new().tab_size(2).rulers([100]))
+                    .language(
+                        "python",
+                        LanguageSettings::new()
+                            .tab_size(4)
+                            .rulers([79])
+                            .soft_wrap(false)
+                            .trim_trailing_whitespace(true),
+                    ),
+            )
+        });
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                assert_eq!(state.mode.tab_size().tab_size, 2);
+                assert_eq!(state.rulers, vec![100]);
+                assert!(state.soft_wrap);
+                assert!(!state.trim_trailing_whitespace);
+
+                state.set_highlighter("python", cx);
+                assert_eq!(state.mode.tab_size().tab_size, 4);
+                assert_eq!(state.rulers, vec![79]);
+                assert!(!state.soft_wrap);
+                assert!(state.trim_trailing_whitespace);
+
+                state.set_value("def foo():  \n    pass\t\n\nend ", window, cx);
+                state.set_selected_range(16..16, cx);
+                state.prepare_for_save(window, cx);
+                assert_eq!(state.text.to_string(), "def foo():\n    pass\n\nend");
+                assert_eq!(state.cursor(), 14);
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_auto_close_brackets(cx: &mut TestAppContext) {
+        let input_view = InputView::new(cx);
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                state.replace_text_in_range(None, "(", window, cx);
+                assert_eq!(state.text.to_string(), "()");
+                assert_eq!(state.cursor(), 1);
+
+                state.replace_text_in_range(None, "a", window, cx);
+                state.replace_text_in_range(None, ")", window, cx);
+                assert_eq!(state.text.to_string(), "(a)");
+                assert_eq!(state.cursor(), 3);
+
+                state.replace_text_in_range(None, "[", window, cx);
+                assert_eq!(state.text.to_string(), "(a)[]");
+                state.backspace(&Backspace, window, cx);
+                assert_eq!(state.text.to_string(), "(a)");
+                assert_eq!(state.cursor(), 3);
+
+                // Surround the selection.
+                state.set_selected_range(1..2, cx);
+                state.replace_text_in_range(None, "\"", window, cx);
+                assert_eq!(state.text.to_string(), "(\"a\")");
+                assert_eq!(state.selected_range(), 2..3);
+
+                // Not closed before a word.
+                state.set_selected_range(0..0, cx);
+                state.replace_text_in_range(None, "{", window, cx);
+                assert_eq!(state.text.to_string(), "{(\"a\")");
+
+                // Not closed the quote after a word.
+                state.set_value("don", window, cx);
+                state.set_selected_range(3..3, cx);
+                state.replace_text_in_range(None, "'", window, cx);
+                assert_eq!(state.text.to_string(), "don'");
+
+                state.set_auto_close_brackets(false, window, cx);
+                state.set_value("", window, cx);
+                state.replace_text_in_range(None, "(", window, cx);
+                assert_eq!(state.text.to_string(), "(");
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_matching_brackets(cx: &mut TestAppContext) {
+        let input_view = InputView::new(cx);
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                state.set_value("fn(a[0], b)", window, cx);
+
+                state.set_selected_range(2..2, cx);
+                assert_eq!(state.matching_bracket_ranges(), Some([2..3, 10..11]));
+                // The bracket before the cursor.
+                state.set_selected_range(11..11, cx);
+                assert_eq!(state.matching_bracket_ranges(), Some([10..11, 2..3]));
+                state.set_selected_range(5..5, cx);
+                assert_eq!(state.matching_bracket_ranges(), Some([4..5, 6..7]));
+                state.set_selected_range(1..1, cx);
+                assert_eq!(state.matching_bracket_ranges(), None);
+            });
+        });
+    }
+
+    #[test]
+    fn test_line_number_mode() {
+        assert_eq!(LineNumberMode::Absolute.number(4, Some(2)), 5);
+        assert_eq!(LineNumberMode::Relative.number(4, Some(2)), 2);
+        assert_eq!(LineNumberMode::Relative.number(0, Some(2)), 2);
+        // The cursor line keeps the line number.
+        assert_eq!(LineNumberMode::Relative.number(2, Some(2)), 3);
+        assert_eq!(LineNumberMode::Relative.number(4, None), 5);
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("hello", 3), "hel");
+        assert_eq!(truncate_chars("hello", 10), "hello");
+        assert_eq!(truncate_chars("你好世界", 2), "你好");
+        assert_eq!(truncate_chars("abc", 0), "");
+    }
+
+    #[gpui::test]
+    fn test_max_length(cx: &mut TestAppContext) {
+        let input_view = InputView::build(cx, |state| state.max_length(5));
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                state.replace_text_in_range(None, "abc", window, cx);
+                state.replace_text_in_range(None, "defg", window, cx);
+                assert_eq!(state.value(), "abcde");
+                assert_eq!(state.char_count(), 5);
+
+                // Replace the selected text within the limit.
+                state.set_selected_range(0..2, cx);
+                state.replace_text_in_range(None, "你好世", window, cx);
+                assert_eq!(state.value(), "你好cde");
+
+                // The value set by code is not limited.
+                state.set_value("hello world", window, cx);
+                assert_eq!(state.value(), "hello world");
+                assert_eq!(state.word_count(), 2);
+            });
+        });
+    }
+
+    #[gpui::test]
+    fn test_share_buffer(cx: &mut TestAppContext) {
+        let input_view = InputView::build(cx, |state| {
+            state.multi_line(true).default_value("Hello\nWorld")
+        });
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let left = input_view.input;
+
+        let right = cx.update(|window, cx| {
+            cx.new(|cx| {
+                InputState::new(window, cx)
+                    .multi_line(true)
+                    .share_buffer(&left, window, cx)
+            })
+        });
+
+        cx.update(|window, cx| {
+            assert!(left.read(cx).is_buffer_shared());
+            assert_eq!(right.read(cx).value(), "Hello\nWorld");
+
+            // The cursor of the other input is shifted by the edit.
+            right.update(cx, |state, cx| state.set_selected_range(6..6, cx));
+            left.update(cx, |state, cx| {
+                state.set_selected_range(0..0, cx);
+                state.replace_text_in_range(None, "Hi, ", window, cx);
+            });
+            assert_eq!(right.read(cx).value(), "Hi, Hello\nWorld");
+            assert_eq!(right.read(cx).selected_range.start, 10);
+
+            right.update(cx, |state, cx| {
+                state.replace_text_in_range(None, "big ", window, cx);
+            });
+            assert_eq!(left.read(cx).value(), "Hi, Hello\nbig World");
+            assert_eq!(left.read(cx).selected_range.start, 4);
+
+            // Undo in the one input is applied to the other.
+            left.update(cx, |state, cx| state.undo(&Undo, window, cx));
+            assert_ne!(left.read(cx).value(), "Hi, Hello\nbig World");
+            assert_eq!(right.read(cx).value(), left.read(cx).value());
+        });
+    }
+
+    #[gpui::test]
+    fn test_load_value(cx: &mut TestAppContext) {
+        let input_view = InputView::build(cx, |state| {
+            state
+                .code_editor("rust")
+                .soft_wrap(true)
+                .large_file_threshold(100)
+        });
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        let text = "fn main() {}\n".repeat(100);
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| state.load_value(text.clone(), window, cx));
+            assert!(input.read(cx).is_loading_value());
+            assert!(input.read(cx).disabled);
+        });
+        cx.run_until_parked();
+
+        cx.update(|window, cx| {
+            let state = input.read(cx);
+            assert!(!state.is_loading_value());
+            assert!(!state.disabled);
+            assert_eq!(state.value(), text);
+            assert!(state.is_large_file());
+            assert!(!state.soft_wrap);
+            assert_eq!(state.display_map.buffer_line_count(), 101);
+
+            // Restore the soft wrap for the small text.
+            input.update(cx, |state, cx| state.set_value("fn main() {}", window, cx));
+            let state = input.read(cx);
+            assert!(!state.is_large_file());
+            assert!(state.soft_wrap);
+        });
+    }
+
+    #[gpui::test]
+    fn test_auto_indent(cx: &mut TestAppContext) {
+        const ENTER: Enter = Enter {
+            secondary: false,
+            shift: false,
+        };
+        let input_view = InputView::build(cx, |state| state.code_editor("json"));
+        let mut cx = VisualTestContext::from_window(input_view.window_handle.into(), cx);
+        let input = input_view.input;
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                state.set_value("{}", window, cx);
+            });
+        });
+        cx.run_until_parked();
+
+        cx.update(|window, cx| {
+            input.update(cx, |state, cx| {
+                // Indent the new line, and move the closing bracket to another line.
+                state.set_selected_range(1..1, cx);
+                state.enter(&ENTER, window, cx);
+                assert_eq!(state.text.to_string(), "{\n  \n}");
+                assert_eq!(state.cursor(), 4);
+
+                // Indent in the nested block.
+                state.replace_text_in_range(None, "\"a\": [1]", window, cx);
+                state.set_selected_range(10..10, cx);
+                state.enter(&ENTER, window, cx);
+                assert_eq!(state.text.to_string(), "{\n  \"a\": [\n    1]\n}");
+                assert_eq!(state.cursor(), 15);
+
+                // Keep the indent of the current line.
+                state.set_selected_range(16..16, cx);
+                state.enter(&ENTER, window, cx);
+                assert_eq!(state.text.to_string(), "{\n  \"a\": [\n    1\n    ]\n}");
+            });
+        });
+    }
 }
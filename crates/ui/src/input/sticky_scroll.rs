@@ -0,0 +1,450 @@
+use gpui::{
+    App, Bounds, Context, CursorStyle, Hitbox, HitboxBehavior, IsZero as _, MouseButton,
+    MouseDownEvent, Pixels, ShapedLine, SharedString, TextAlign, TextRun, TextStyle, Window, fill,
+    point, px, size,
+};
+use ropey::Rope;
+
+use crate::{
+    ActiveTheme as _,
+    input::{
+        InputState, LastLayout, RopeExt as _,
+        display_map::Tree,
+        element::{LINE_NUMBER_RIGHT_MARGIN, TextElement},
+    },
+};
+
+/// The max number of the sticky headers.
+const MAX_STICKY_LINES: usize = 5;
+
+/// The enclosing scope header pinned at the top of the viewport.
+struct StickyHeader {
+    /// The buffer row of the header.
+    row: usize,
+    line_number: Option<ShapedLine>,
+    line: ShapedLine,
+}
+
+pub(super) struct StickyHeadersLayout {
+    headers: Vec<StickyHeader>,
+    /// The bounds of the headers, at the top of the unscrolled input bounds.
+    bounds: Bounds<Pixels>,
+    /// The x of the text, follows the horizontal scroll.
+    text_x: Pixels,
+    line_number_width: Pixels,
+    line_height: Pixels,
+    hitbox: Hitbox,
+}
+
+/// Returns true if the syntax node kind is a scope to pin as a sticky header,
+/// e.g. functions, classes, structs, impls and modules.
+fn is_sticky_scope_kind(kind: &str) -> bool {
+    const SCOPES: [&str; 11] = [
+        "function",
+        "method",
+        "class",
+        "struct",
+        "impl",
+        "trait",
+        "enum",
+        "interface",
+        "module",
+        "mod_item",
+        "namespace",
+    ];
+    const EXCLUDES: [&str; 5] = ["expression", "call", "argument", "parameter", "literal"];
+
+    SCOPES.iter().any(|scope| kind.contains(scope))
+        && !EXCLUDES.iter().any(|exclude| kind.contains(exclude))
+}
+
+/// Returns the rows of the sticky headers when the `top_row` is at the top of the viewport.
+///
+/// The `scopes_at` returns the `(start_row, end_row)` of the scopes containing the row,
+/// from the outermost. The header at the `ix` is the scope of the `ix` depth containing
+/// the row it covers, so a scope is unpinned when its last row is reached.
+fn sticky_rows(
+    top_row: usize,
+    max_lines: usize,
+    scopes_at: impl Fn(usize) -> Vec<(usize, usize)>,
+) -> Vec<usize> {
+    let mut rows: Vec<usize> = vec![];
+    for ix in 0..max_lines {
+        let probe = top_row + ix;
+        let scopes = scopes_at(probe)
+            .into_iter()
+            .filter(|(start, end)| *start < probe && *end > probe)
+            .map(|(start, _)| start)
+            .collect::<Vec<_>>();
+
+        // The outer scopes must be the same as the headers above.
+        if scopes.len() <= ix || scopes[..ix] != rows[..] {
+            break;
+        }
+        rows.push(scopes[ix]);
+    }
+
+    rows
+}
+
+/// Returns the `(start_row, end_row)` of the scopes containing the row, from the outermost.
+#[cfg(feature = "tree-sitter")]
+fn scopes_at(tree: &Tree, text: &Rope, row: usize) -> Vec<(usize, usize)> {
+    if row >= text.lines_len() {
+        return vec![];
+    }
+
+    // Use the first non-whitespace character, to skip the scope ends at the line start.
+    let indent = text
+        .slice_line(row)
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .count();
+    let offset = text.line_start_offset(row) + indent;
+    let Some(mut node) = tree
+        .root_node()
+        .named_descendant_for_byte_range(offset, offset)
+    else {
+        return vec![];
+    };
+
+    let mut scopes: Vec<(usize, usize)> = vec![];
+    loop {
+        let start = node.start_position().row;
+        let end = node.end_position().row;
+        if end > start
+            && is_sticky_scope_kind(node.kind())
+            && scopes
+                .last()
+                .is_none_or(|(last_start, _)| *last_start != start)
+        {
+            scopes.push((start, end));
+        }
+
+        let Some(parent) = node.parent() else {
+            break;
+        };
+        node = parent;
+    }
+
+    scopes.reverse();
+    scopes
+}
+
+#[cfg(not(feature = "tree-sitter"))]
+fn scopes_at(_: &Tree, _: &Rope, _: usize) -> Vec<(usize, usize)> {
+    vec![]
+}
+
+impl TextElement {
+    /// Layout the headers of the enclosing scopes of the top row, by the syntax tree.
+    ///
+    /// The `bounds` is the scrolled text bounds, and `input_bounds` is the unscrolled one.
+    pub(super) fn layout_sticky_headers(
+        &self,
+        bounds: &Bounds<Pixels>,
+        input_bounds: &Bounds<Pixels>,
+        last_layout: &LastLayout,
+        line_number_len: usize,
+        text_style: &TextStyle,
+        window: &mut Window,
+        cx: &App,
+    ) -> Option<StickyHeadersLayout> {
+        let state = self.state.read(cx);
+        if !state.sticky_scroll || !state.mode.is_code_editor() || !state.mode.is_multi_line() {
+            return None;
+        }
+
+        let highlighter = state.mode.highlighter()?.borrow();
+        let highlighter = highlighter.as_ref()?;
+        let tree = highlighter.tree()?;
+
+        let line_height = last_layout.line_height;
+        let rows = sticky_rows(last_layout.visible_range.start, MAX_STICKY_LINES, |row| {
+            scopes_at(tree, &state.text, row)
+        });
+        if rows.is_empty() {
+            return None;
+        }
+
+        let font_size = text_style.font_size.to_pixels(window.rem_size());
+        let theme = &cx.theme().highlight_theme;
+        let headers = rows
+            .into_iter()
+            .map(|row| {
+                let line_start = state.text.line_start_offset(row);
+                let line: SharedString = state
+                    .text
+                    .slice_line(row)
+                    .to_string()
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string()
+                    .into();
+
+                let mut runs = highlighter
+                    .styles(&(line_start..line_start + line.len()), theme)
+                    .into_iter()
+                    .map(|(range, style)| text_style.clone().highlight(style).to_run(range.len()))
+                    .filter(|run| run.len > 0)
+                    .collect::<Vec<_>>();
+                if runs.iter().map(|run| run.len).sum::<usize>() != line.len() {
+                    runs = vec![text_style.to_run(line.len())];
+                }
+                let line = window
+                    .text_system()
+                    .shape_line(line, font_size, &runs, None);
+
+                let line_number = state.mode.line_number().then(|| {
+                    let line_no: SharedString =
+                        format!("{:>width$}", row + 1, width = line_number_len).into();
+                    let run = TextRun {
+                        len: line_no.len(),
+                        font: text_style.font(),
+                        color: cx.theme().muted_foreground,
+                        background_color: None,
+                        underline: None,
+                        strikethrough: None,
+                    };
+                    window
+                        .text_system()
+                        .shape_line(line_no, font_size, &[run], None)
+                });
+
+                StickyHeader {
+                    row,
+                    line_number,
+                    line,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let headers_bounds = Bounds::new(
+            input_bounds.origin,
+            size(input_bounds.size.width, line_height * headers.len()),
+        );
+        let hitbox = window.insert_hitbox(headers_bounds, HitboxBehavior::BlockMouse);
+
+        Some(StickyHeadersLayout {
+            headers,
+            bounds: headers_bounds,
+            text_x: bounds.origin.x + last_layout.line_number_width,
+            line_number_width: last_layout.line_number_width,
+            line_height,
+            hitbox,
+        })
+    }
+
+    /// Paint the sticky headers over the text, click a header to jump to it.
+    pub(super) fn paint_sticky_headers(
+        &self,
+        layout: &StickyHeadersLayout,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let bounds = layout.bounds;
+        let line_height = layout.line_height;
+        let editor_background = cx.theme().editor_background();
+        let gutter_background = cx
+            .theme()
+            .highlight_theme
+            .style
+            .editor_gutter_background
+            .unwrap_or(editor_background);
+        let hovered_ix = layout
+            .hitbox
+            .is_hovered(window)
+            .then(|| window.mouse_position())
+            .map(|position| ((position.y - bounds.top()) / line_height).floor() as usize);
+
+        window.paint_quad(fill(bounds, editor_background));
+        for (ix, header) in layout.headers.iter().enumerate() {
+            let y = bounds.top() + line_height * ix;
+            if hovered_ix == Some(ix) {
+                if let Some(color) = cx.theme().highlight_theme.style.editor_active_line {
+                    window.paint_quad(fill(
+                        Bounds::new(
+                            point(bounds.left(), y),
+                            size(bounds.size.width, line_height),
+                        ),
+                        color,
+                    ));
+                }
+            }
+
+            _ = header.line.paint(
+                point(layout.text_x, y),
+                line_height,
+                TextAlign::Left,
+                None,
+                window,
+                cx,
+            );
+        }
+
+        // Cover the text scrolled horizontally under the gutter.
+        if !layout.line_number_width.is_zero() {
+            window.paint_quad(fill(
+                Bounds::new(
+                    bounds.origin,
+                    size(
+                        layout.line_number_width - LINE_NUMBER_RIGHT_MARGIN,
+                        bounds.size.height,
+                    ),
+                ),
+                gutter_background,
+            ));
+        }
+        for (ix, header) in layout.headers.iter().enumerate() {
+            if let Some(line_number) = header.line_number.as_ref() {
+                let y = bounds.top() + line_height * ix;
+                _ = line_number.paint(
+                    point(bounds.left(), y),
+                    line_height,
+                    TextAlign::Left,
+                    None,
+                    window,
+                    cx,
+                );
+            }
+        }
+
+        window.paint_quad(fill(
+            Bounds::new(
+                point(bounds.left(), bounds.bottom() - px(1.)),
+                size(bounds.size.width, px(1.)),
+            ),
+            cx.theme().border,
+        ));
+        window.set_cursor_style(CursorStyle::PointingHand, &layout.hitbox);
+
+        window.on_mouse_event({
+            let state = self.state.clone();
+            let hitbox = layout.hitbox.clone();
+            let rows = layout
+                .headers
+                .iter()
+                .map(|header| header.row)
+                .collect::<Vec<_>>();
+            move |event: &MouseDownEvent, phase, window, cx| {
+                if !phase.bubble()
+                    || event.button != MouseButton::Left
+                    || !hitbox.is_hovered(window)
+                {
+                    return;
+                }
+
+                let ix = ((event.position.y - bounds.top()) / line_height).floor() as usize;
+                let Some(&row) = rows.get(ix) else {
+                    return;
+                };
+
+                cx.stop_propagation();
+                state.update(cx, |state, cx| {
+                    state.jump_to_sticky_header(row, ix, window, cx);
+                });
+            }
+        });
+    }
+}
+
+impl InputState {
+    /// Set true to pin the headers of the enclosing scopes (e.g. functions, structs) at
+    /// the top of the viewport while scrolling, only for [`InputMode::CodeEditor`] mode,
+    /// default is false.
+    ///
+    /// The scopes are computed from the syntax tree of the highlighter, click a header to
+    /// jump to it.
+    ///
+    /// [`InputMode::CodeEditor`]: crate::input::InputMode::CodeEditor
+    pub fn sticky_scroll(mut self, sticky_scroll: bool) -> Self {
+        debug_assert!(self.mode.is_code_editor());
+        self.sticky_scroll = sticky_scroll;
+        self
+    }
+
+    /// Update whether to pin the headers of the enclosing scopes while scrolling.
+    pub fn set_sticky_scroll(
+        &mut self,
+        sticky_scroll: bool,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        debug_assert!(self.mode.is_code_editor());
+        self.sticky_scroll = sticky_scroll;
+        cx.notify();
+    }
+
+    /// Move the cursor to the header `row`, and scroll it to the `ix` of the sticky headers.
+    fn jump_to_sticky_header(
+        &mut self,
+        row: usize,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let indent = self
+            .text
+            .slice_line(row)
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .count();
+        self.move_to(self.text.line_start_offset(row) + indent, None, cx);
+        self.focus(window, cx);
+
+        if let Some(last_layout) = self.last_layout.as_ref() {
+            let display_row = self.display_map.buffer_line_to_display_row(row);
+            let scroll_top = last_layout.line_height * display_row.saturating_sub(ix);
+            let offset = point(self.scroll_handle.offset().x, -scroll_top);
+            self.update_scroll_offset(Some(offset), cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_sticky_scope_kind, sticky_rows};
+
+    #[test]
+    fn test_is_sticky_scope_kind() {
+        assert!(is_sticky_scope_kind("function_item"));
+        assert!(is_sticky_scope_kind("impl_item"));
+        assert!(is_sticky_scope_kind("class_declaration"));
+        assert!(is_sticky_scope_kind("method_definition"));
+        assert!(is_sticky_scope_kind("function_definition"));
+        assert!(!is_sticky_scope_kind("block"));
+        assert!(!is_sticky_scope_kind("call_expression"));
+        assert!(!is_sticky_scope_kind("struct_expression"));
+        assert!(!is_sticky_scope_kind("parameters"));
+    }
+
+    #[test]
+    fn test_sticky_rows() {
+        // 0: impl Foo {
+        // 1:     fn bar() {
+        // 2:         ...
+        // 8:     }
+        // 9:     fn baz() {
+        // 10:        ...
+        // 12:    }
+        // 13: }
+        let scopes_at = |row: usize| {
+            let mut scopes = vec![(0, 13)];
+            if (1..=8).contains(&row) {
+                scopes.push((1, 8));
+            }
+            if (9..=12).contains(&row) {
+                scopes.push((9, 12));
+            }
+            scopes
+        };
+
+        assert_eq!(sticky_rows(0, 5, scopes_at), Vec::<usize>::new());
+        assert_eq!(sticky_rows(1, 5, scopes_at), vec![0, 1]);
+        assert_eq!(sticky_rows(3, 5, scopes_at), vec![0, 1]);
+        // The end of `bar` is under the headers.
+        assert_eq!(sticky_rows(7, 5, scopes_at), vec![0]);
+        assert_eq!(sticky_rows(10, 5, scopes_at), vec![0, 9]);
+        assert_eq!(sticky_rows(10, 1, scopes_at), vec![0]);
+        assert_eq!(sticky_rows(13, 5, scopes_at), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,208 @@
+use std::rc::Rc;
+
+use gpui::{Context, SharedString, Window};
+
+use super::search_input::push_history_item;
+use crate::{input::InputState, storage::Storage};
+
+/// The max number of the committed values to keep.
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// The committed values of a single-line input, see [`InputState::value_history`].
+pub(super) struct ValueHistory {
+    key: SharedString,
+    storage: Rc<dyn Storage>,
+    /// The committed values, the latest is first.
+    values: Vec<SharedString>,
+    recall: Option<Recall>,
+}
+
+/// The state of recalling the values by `Up` and `Down`.
+struct Recall {
+    /// The text before recalling, used as the prefix to filter the values and restored at the end.
+    draft: SharedString,
+    /// The index of the recalled value.
+    ix: usize,
+    /// The recalled value, to restart the recalling once the text is edited.
+    value: SharedString,
+}
+
+impl ValueHistory {
+    fn save(&self) {
+        let items = self
+            .values
+            .iter()
+            .map(|item| item.as_str())
+            .collect::<Vec<_>>();
+        if let Ok(value) = serde_json::to_string(&items) {
+            self.storage.set(&self.key, value);
+        }
+    }
+}
+
+impl InputState {
+    /// Enable the shell-style history of the committed values, only for [`super::InputMode::SingleLine`] mode.
+    ///
+    /// The value is committed by pressing `Enter`, and persisted in the `storage` with the `key`,
+    /// e.g. the id of the input. Press `Up` and `Down` to cycle the values starting with the text
+    /// typed before, the text is restored after the latest value.
+    ///
+    /// The values are loaded from the storage immediately, the masked input does not commit values.
+    pub fn value_history(mut self, key: impl Into<SharedString>, storage: Rc<dyn Storage>) -> Self {
+        debug_assert!(self.mode.is_single_line());
+        let key = key.into();
+        let values = storage
+            .get(&key)
+            .and_then(|value| serde_json::from_str::<Vec<String>>(&value).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .take(DEFAULT_HISTORY_LIMIT)
+            .map(SharedString::from)
+            .collect();
+        self.value_history = Some(ValueHistory {
+            key,
+            storage,
+            values,
+            recall: None,
+        });
+        self
+    }
+
+    /// Returns the committed values of the [`Self::value_history`], the latest is first.
+    pub fn history_values(&self) -> &[SharedString] {
+        self.value_history
+            .as_ref()
+            .map(|history| history.values.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Add the `value` to the committed values of the [`Self::value_history`].
+    pub fn push_history_value(&mut self, value: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let Some(history) = self.value_history.as_mut() else {
+            return;
+        };
+
+        history.recall = None;
+        if push_history_item(&mut history.values, &value.into(), DEFAULT_HISTORY_LIMIT) {
+            history.save();
+            cx.notify();
+        }
+    }
+
+    /// Clear the committed values of the [`Self::value_history`].
+    pub fn clear_history_values(&mut self, cx: &mut Context<Self>) {
+        let Some(history) = self.value_history.as_mut() else {
+            return;
+        };
+
+        history.values.clear();
+        history.recall = None;
+        history.storage.remove(&history.key);
+        cx.notify();
+    }
+
+    /// Commit the value to the history on `Enter`.
+    pub(super) fn commit_history_value(&mut self, cx: &mut Context<Self>) {
+        if self.masked || !self.mode.is_single_line() {
+            return;
+        }
+
+        let value = self.value();
+        self.push_history_value(value, cx);
+    }
+
+    /// Recall the older (`direction < 0`) or newer value of the [`Self::value_history`].
+    pub(super) fn recall_history_value(
+        &mut self,
+        direction: isize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let text = self.value();
+        let Some(history) = self.value_history.as_mut() else {
+            return;
+        };
+
+        if history
+            .recall
+            .as_ref()
+            .is_some_and(|recall| recall.value != text)
+        {
+            history.recall = None;
+        }
+
+        let (draft, current) = match history.recall.as_ref() {
+            Some(recall) => (recall.draft.clone(), Some(recall.ix)),
+            None => (text, None),
+        };
+
+        let value = match next_history_ix(&history.values, &draft, current, direction) {
+            Some(ix) => {
+                let value = history.values[ix].clone();
+                history.recall = Some(Recall {
+                    draft,
+                    ix,
+                    value: value.clone(),
+                });
+                value
+            }
+            // Restore the draft after the latest value.
+            None if direction > 0 && current.is_some() => {
+                history.recall = None;
+                draft
+            }
+            None => return,
+        };
+
+        self.replace_all(value, window, cx);
+    }
+}
+
+/// Returns the index of the older (`direction < 0`) or newer value than the `current`
+/// that starts with the `prefix`.
+fn next_history_ix(
+    values: &[SharedString],
+    prefix: &str,
+    current: Option<usize>,
+    direction: isize,
+) -> Option<usize> {
+    let matches = |ix: &usize| {
+        let value = &values[*ix];
+        value.starts_with(prefix) && value.as_str() != prefix
+    };
+
+    if direction < 0 {
+        let start = current.map_or(0, |ix| ix + 1);
+        (start..values.len()).find(matches)
+    } else {
+        (0..current?).rev().find(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::SharedString;
+
+    use super::next_history_ix;
+
+    #[test]
+    fn test_next_history_ix() {
+        let values: Vec<SharedString> = vec!["git push".into(), "ls".into(), "git pull".into()];
+
+        assert_eq!(next_history_ix(&values, "", None, -1), Some(0));
+        assert_eq!(next_history_ix(&values, "", Some(0), -1), Some(1));
+        assert_eq!(next_history_ix(&values, "", Some(2), -1), None);
+        assert_eq!(next_history_ix(&values, "", Some(2), 1), Some(1));
+        assert_eq!(next_history_ix(&values, "", Some(0), 1), None);
+        assert_eq!(next_history_ix(&values, "", None, 1), None);
+
+        // Filter by the prefix.
+        assert_eq!(next_history_ix(&values, "git", None, -1), Some(0));
+        assert_eq!(next_history_ix(&values, "git", Some(0), -1), Some(2));
+        assert_eq!(next_history_ix(&values, "git", Some(2), 1), Some(0));
+        assert_eq!(next_history_ix(&values, "cargo", None, -1), None);
+
+        // Skip the value same as the prefix.
+        assert_eq!(next_history_ix(&values, "ls", None, -1), None);
+    }
+}
@@ -0,0 +1,1465 @@
+use std::{collections::HashMap, ops::Range, rc::Rc};
+
+use gpui::{Action, App, ClipboardItem, Context, KeyBinding, KeyDownEvent, Window};
+use ropey::Rope;
+use serde::Deserialize;
+
+use crate::{
+    highlighter::BracketPair,
+    input::{Escape, InputState, MovePageDown, MovePageUp, Redo, RopeExt as _, Undo},
+};
+
+const NORMAL_CONTEXT: &str = "Input && vim_mode == normal";
+const INSERT_CONTEXT: &str = "Input && vim_mode == insert";
+const VISUAL_CONTEXT: &str = "Input && vim_mode == visual";
+const VISUAL_LINE_CONTEXT: &str = "Input && vim_mode == visual_line";
+
+/// Run the keys as Vim commands, as if they were typed in Normal or Visual mode.
+///
+/// This can be bound in the `vim_mode` key contexts to map a key to Vim commands:
+///
+/// ```ignore
+/// cx.bind_keys([KeyBinding::new(
+///     "ctrl-e",
+///     VimKeys { keys: "$".into() },
+///     Some("Input && vim_mode == normal"),
+/// )]);
+/// ```
+#[derive(Action, Clone, PartialEq, Eq, Deserialize)]
+#[action(namespace = input, no_json)]
+pub struct VimKeys {
+    pub keys: String,
+}
+
+pub(crate) fn init(cx: &mut App) {
+    let mut bindings = vec![
+        KeyBinding::new("ctrl-[", Escape, Some(INSERT_CONTEXT)),
+        KeyBinding::new("ctrl-r", Redo, Some(NORMAL_CONTEXT)),
+        KeyBinding::new("ctrl-d", MovePageDown, Some(NORMAL_CONTEXT)),
+        KeyBinding::new("ctrl-u", MovePageUp, Some(NORMAL_CONTEXT)),
+    ];
+    for context in [NORMAL_CONTEXT, VISUAL_CONTEXT, VISUAL_LINE_CONTEXT] {
+        bindings.extend([
+            KeyBinding::new("ctrl-[", Escape, Some(context)),
+            KeyBinding::new("enter", VimKeys { keys: "+".into() }, Some(context)),
+            KeyBinding::new("backspace", VimKeys { keys: "h".into() }, Some(context)),
+            KeyBinding::new("delete", VimKeys { keys: "x".into() }, Some(context)),
+            // Disable the indent by Tab, the text can only be changed by commands.
+            KeyBinding::new("tab", VimKeys { keys: "".into() }, Some(context)),
+            KeyBinding::new("shift-tab", VimKeys { keys: "".into() }, Some(context)),
+        ]);
+    }
+
+    cx.bind_keys(bindings);
+}
+
+/// The mode of the Vim keybinding mode, see [`InputState::vim_mode`].
+///
+/// The mode is also set to the `vim_mode` key context of the input, e.g.
+/// `Input && vim_mode == normal`, to bind keys for a mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum VimMode {
+    /// Keys are commands, e.g. `dd` to delete a line.
+    #[default]
+    Normal,
+    /// Keys insert text, like a normal input.
+    Insert,
+    /// Motions extend the selection by characters.
+    Visual,
+    /// Motions extend the selection by whole lines.
+    VisualLine,
+}
+
+impl VimMode {
+    /// Returns the value of the `vim_mode` key context.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Insert => "insert",
+            Self::Visual => "visual",
+            Self::VisualLine => "visual_line",
+        }
+    }
+
+    /// Returns true if the mode is [`VimMode::Visual`] or [`VimMode::VisualLine`].
+    pub fn is_visual(&self) -> bool {
+        matches!(self, Self::Visual | Self::VisualLine)
+    }
+}
+
+/// The content of a Vim register, see [`InputState::vim_register`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VimRegister {
+    /// The yanked or deleted text.
+    pub text: String,
+    /// Whether the text is whole lines, it will be pasted as new lines.
+    pub linewise: bool,
+}
+
+/// A key remapping layer for the Vim mode.
+///
+/// The mapped keys are only matched at the start of a command, and the target keys
+/// are not remapped again.
+///
+/// ```
+/// use gpui_component::input::{VimKeymap, VimMode};
+///
+/// let keymap = VimKeymap::new()
+///     .map(VimMode::Normal, "H", "^")
+///     .map(VimMode::Normal, "L", "$")
+///     .map(VimMode::Normal, "Y", "y$")
+///     .map(VimMode::Visual, "H", "^");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VimKeymap {
+    mappings: HashMap<VimMode, HashMap<String, String>>,
+}
+
+impl VimKeymap {
+    /// Create an empty keymap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map the `keys` to the `target` keys in the `mode`.
+    ///
+    /// The mappings of [`VimMode::Visual`] are also used in [`VimMode::VisualLine`],
+    /// and [`VimMode::Insert`] is not supported, use a key binding in the
+    /// `Input && vim_mode == insert` context instead.
+    pub fn map(
+        mut self,
+        mode: VimMode,
+        keys: impl Into<String>,
+        target: impl Into<String>,
+    ) -> Self {
+        self.mappings
+            .entry(Self::mode_key(mode))
+            .or_default()
+            .insert(keys.into(), target.into());
+        self
+    }
+
+    fn mode_key(mode: VimMode) -> VimMode {
+        if mode.is_visual() {
+            VimMode::Visual
+        } else {
+            mode
+        }
+    }
+
+    fn get(&self, mode: VimMode, keys: &str) -> Option<&str> {
+        self.mappings
+            .get(&Self::mode_key(mode))
+            .and_then(|mappings| mappings.get(keys))
+            .map(|target| target.as_str())
+    }
+
+    /// Returns true if the `keys` is the start of a longer mapping.
+    fn is_prefix(&self, mode: VimMode, keys: &str) -> bool {
+        self.mappings
+            .get(&Self::mode_key(mode))
+            .is_some_and(|mappings| {
+                mappings
+                    .keys()
+                    .any(|mapped| mapped.len() > keys.len() && mapped.starts_with(keys))
+            })
+    }
+}
+
+/// The state of the Vim keybinding mode.
+#[derive(Default)]
+pub(super) struct VimState {
+    pub(super) enabled: bool,
+    pub(super) mode: VimMode,
+    /// The typed keys of an incomplete command, e.g. `d` of `dw`.
+    pending: String,
+    registers: HashMap<char, VimRegister>,
+    pub(super) keymap: Rc<VimKeymap>,
+    /// The fixed end of the selection in Visual mode.
+    visual_anchor: usize,
+    /// The moving end of the selection in Visual mode.
+    visual_head: usize,
+    /// The character column to keep when moving up and down, `usize::MAX` is the line end.
+    column: Option<usize>,
+}
+
+impl VimState {
+    pub(super) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.mode = VimMode::Normal;
+        self.pending.clear();
+        self.column = None;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    /// `w` or `W` (true).
+    NextWordStart(bool),
+    /// `e` or `E` (true).
+    NextWordEnd(bool),
+    /// `b` or `B` (true).
+    PreviousWordStart(bool),
+    LineStart,
+    FirstNonBlank,
+    LineEnd,
+    NextLineStart,
+    PreviousLineStart,
+    FirstLine,
+    LastLine,
+    /// `f` or `t` (till).
+    FindForward {
+        ch: char,
+        till: bool,
+    },
+    /// `F` or `T` (till).
+    FindBackward {
+        ch: char,
+        till: bool,
+    },
+    MatchingBracket,
+}
+
+impl Motion {
+    fn is_linewise(&self) -> bool {
+        matches!(
+            self,
+            Self::Up
+                | Self::Down
+                | Self::NextLineStart
+                | Self::PreviousLineStart
+                | Self::FirstLine
+                | Self::LastLine
+        )
+    }
+
+    /// Returns true if the character at the target is included by an operator.
+    fn is_inclusive(&self) -> bool {
+        matches!(
+            self,
+            Self::NextWordEnd(_) | Self::LineEnd | Self::FindForward { .. } | Self::MatchingBracket
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+    Indent,
+    Outdent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InsertAt {
+    Cursor,
+    AfterCursor,
+    FirstNonBlank,
+    LineEnd,
+    LineBelow,
+    LineAbove,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommandKind {
+    Move(Motion),
+    /// Apply the operator to the text of the motion, or the lines if None (e.g. `dd`).
+    ///
+    /// In Visual mode, the operator is applied to the selection.
+    Operate(Operator, Option<Motion>),
+    Insert(InsertAt),
+    Paste {
+        before: bool,
+    },
+    ReplaceChar(char),
+    Undo,
+    ToggleVisual(VimMode),
+    SwapAnchor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Command {
+    register: Option<char>,
+    count: Option<usize>,
+    kind: CommandKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Parsed {
+    /// More keys are needed.
+    Pending,
+    Invalid,
+    Command(Command),
+}
+
+fn parse_count(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<usize> {
+    if !chars.peek().is_some_and(|c| matches!(c, '1'..='9')) {
+        return None;
+    }
+
+    let mut count = 0usize;
+    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+        chars.next();
+        count = count.saturating_mul(10).saturating_add(digit as usize);
+    }
+    Some(count)
+}
+
+fn parse_motion(
+    c: char,
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+) -> Result<Motion, Parsed> {
+    let motion = match c {
+        'h' => Motion::Left,
+        'l' | ' ' => Motion::Right,
+        'j' => Motion::Down,
+        'k' => Motion::Up,
+        'w' => Motion::NextWordStart(false),
+        'W' => Motion::NextWordStart(true),
+        'e' => Motion::NextWordEnd(false),
+        'E' => Motion::NextWordEnd(true),
+        'b' => Motion::PreviousWordStart(false),
+        'B' => Motion::PreviousWordStart(true),
+        '0' => Motion::LineStart,
+        '^' => Motion::FirstNonBlank,
+        '$' => Motion::LineEnd,
+        '+' => Motion::NextLineStart,
+        '-' => Motion::PreviousLineStart,
+        'G' => Motion::LastLine,
+        '%' => Motion::MatchingBracket,
+        'g' => match chars.next() {
+            None => return Err(Parsed::Pending),
+            Some('g') => Motion::FirstLine,
+            Some(_) => return Err(Parsed::Invalid),
+        },
+        'f' | 't' | 'F' | 'T' => {
+            let Some(ch) = chars.next() else {
+                return Err(Parsed::Pending);
+            };
+            let till = matches!(c, 't' | 'T');
+            if matches!(c, 'f' | 't') {
+                Motion::FindForward { ch, till }
+            } else {
+                Motion::FindBackward { ch, till }
+            }
+        }
+        _ => return Err(Parsed::Invalid),
+    };
+
+    Ok(motion)
+}
+
+/// Parse the typed keys into a command, e.g. `"a2dw`.
+fn parse_command(keys: &str, visual: bool) -> Parsed {
+    let mut chars = keys.chars().peekable();
+
+    let mut register = None;
+    if chars.peek() == Some(&'"') {
+        chars.next();
+        match chars.next() {
+            None => return Parsed::Pending,
+            Some(c) if c.is_ascii_alphanumeric() || matches!(c, '"' | '+' | '*' | '_' | '-') => {
+                register = Some(c)
+            }
+            Some(_) => return Parsed::Invalid,
+        }
+    }
+
+    let mut count = parse_count(&mut chars);
+    let Some(c) = chars.next() else {
+        return Parsed::Pending;
+    };
+
+    let operator = match c {
+        'd' => Some(Operator::Delete),
+        'c' => Some(Operator::Change),
+        'y' => Some(Operator::Yank),
+        '>' => Some(Operator::Indent),
+        '<' => Some(Operator::Outdent),
+        _ => None,
+    };
+
+    let kind = match (c, operator) {
+        (_, Some(operator)) if visual => CommandKind::Operate(operator, None),
+        (_, Some(operator)) => {
+            let motion_count = parse_count(&mut chars);
+            if motion_count.is_some() {
+                count = Some(count.unwrap_or(1) * motion_count.unwrap_or(1));
+            }
+
+            match chars.next() {
+                None => return Parsed::Pending,
+                Some(m) if m == c => CommandKind::Operate(operator, None),
+                Some(m) => match parse_motion(m, &mut chars) {
+                    Ok(motion) => CommandKind::Operate(operator, Some(motion)),
+                    Err(parsed) => return parsed,
+                },
+            }
+        }
+        ('x', _) if !visual => CommandKind::Operate(Operator::Delete, Some(Motion::Right)),
+        ('X', _) if !visual => CommandKind::Operate(Operator::Delete, Some(Motion::Left)),
+        ('s', _) if !visual => CommandKind::Operate(Operator::Change, Some(Motion::Right)),
+        ('x' | 'X', _) => CommandKind::Operate(Operator::Delete, None),
+        ('s', _) => CommandKind::Operate(Operator::Change, None),
+        ('D', _) => CommandKind::Operate(Operator::Delete, Some(Motion::LineEnd)),
+        ('C', _) => CommandKind::Operate(Operator::Change, Some(Motion::LineEnd)),
+        ('S', _) => CommandKind::Operate(Operator::Change, None),
+        ('Y', _) => CommandKind::Operate(Operator::Yank, None),
+        ('o', _) if visual => CommandKind::SwapAnchor,
+        ('i', _) if !visual => CommandKind::Insert(InsertAt::Cursor),
+        ('a', _) if !visual => CommandKind::Insert(InsertAt::AfterCursor),
+        ('I', _) if !visual => CommandKind::Insert(InsertAt::FirstNonBlank),
+        ('A', _) if !visual => CommandKind::Insert(InsertAt::LineEnd),
+        ('o', _) => CommandKind::Insert(InsertAt::LineBelow),
+        ('O', _) if !visual => CommandKind::Insert(InsertAt::LineAbove),
+        ('p' | 'P', _) => CommandKind::Paste { before: c == 'P' },
+        ('u', _) if !visual => CommandKind::Undo,
+        ('r', _) if !visual => match chars.next() {
+            None => return Parsed::Pending,
+            Some(ch) => CommandKind::ReplaceChar(ch),
+        },
+        ('v', _) => CommandKind::ToggleVisual(VimMode::Visual),
+        ('V', _) => CommandKind::ToggleVisual(VimMode::VisualLine),
+        _ => match parse_motion(c, &mut chars) {
+            Ok(motion) => CommandKind::Move(motion),
+            Err(parsed) => return parsed,
+        },
+    };
+
+    if chars.next().is_some() {
+        return Parsed::Invalid;
+    }
+
+    Parsed::Command(Command {
+        register,
+        count,
+        kind,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharKind {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+/// Returns the kind of the char, all non-whitespace chars are `Word` in a WORD (`big`).
+fn char_kind(c: char, big: bool) -> CharKind {
+    if c.is_whitespace() {
+        CharKind::Whitespace
+    } else if big || c.is_alphanumeric() || c == '_' {
+        CharKind::Word
+    } else {
+        CharKind::Punctuation
+    }
+}
+
+/// Returns the start offset of the next word (`w`), an empty line is also a word.
+fn next_word_start(text: &Rope, offset: usize, big: bool) -> usize {
+    let mut chars = text.chars_at(offset);
+    let Some(first) = chars.next() else {
+        return text.len();
+    };
+
+    let kind = char_kind(first, big);
+    let mut in_word = kind != CharKind::Whitespace;
+    let mut prev = first;
+    let mut pos = offset + first.len_utf8();
+    for c in chars {
+        let c_kind = char_kind(c, big);
+        if !(in_word && c_kind == kind) {
+            in_word = false;
+            if c_kind != CharKind::Whitespace || (c == '\n' && prev == '\n') {
+                return pos;
+            }
+        }
+
+        if c != '\r' {
+            prev = c;
+        }
+        pos += c.len_utf8();
+    }
+
+    text.len()
+}
+
+/// Returns the offset of the last char of the next word end (`e`).
+fn next_word_end(text: &Rope, offset: usize, big: bool) -> usize {
+    let mut chars = text.chars_at(offset);
+    let Some(first) = chars.next() else {
+        return offset;
+    };
+
+    let mut kind = None;
+    let mut last = offset;
+    let mut pos = offset + first.len_utf8();
+    for c in chars {
+        let c_kind = char_kind(c, big);
+        match kind {
+            None if c_kind != CharKind::Whitespace => {
+                kind = Some(c_kind);
+                last = pos;
+            }
+            Some(kind) if c_kind == kind => last = pos,
+            Some(_) => return last,
+            None => {}
+        }
+        pos += c.len_utf8();
+    }
+
+    last
+}
+
+/// Returns the start offset of the previous word (`b`).
+fn previous_word_start(text: &Rope, offset: usize, big: bool) -> usize {
+    let mut kind = None;
+    let mut pos = offset;
+    for c in text.chars_at(offset).reversed() {
+        let c_kind = char_kind(c, big);
+        match kind {
+            None if c_kind != CharKind::Whitespace => kind = Some(c_kind),
+            Some(kind) if c_kind != kind => return pos,
+            _ => {}
+        }
+        pos -= c.len_utf8();
+    }
+
+    pos
+}
+
+/// Returns the end offset (exclusive) of the word or whitespace at the offset.
+fn word_run_end(text: &Rope, offset: usize, big: bool) -> usize {
+    let mut chars = text.chars_at(offset);
+    let Some(first) = chars.next() else {
+        return offset;
+    };
+
+    let kind = char_kind(first, big);
+    let mut pos = offset + first.len_utf8();
+    for c in chars {
+        if c == '\n' || char_kind(c, big) != kind {
+            break;
+        }
+        pos += c.len_utf8();
+    }
+    pos
+}
+
+/// The text range of an operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum VimRange {
+    Chars(Range<usize>),
+    /// The rows (0-based) of the first and the last line.
+    Lines(usize, usize),
+}
+
+impl InputState {
+    /// Returns the current mode of the Vim keybinding mode, None if it is disabled.
+    ///
+    /// See also [`InputState::vim_mode`].
+    pub fn current_vim_mode(&self) -> Option<VimMode> {
+        self.vim.enabled.then_some(self.vim.mode)
+    }
+
+    /// Returns the content of the Vim register by the name, e.g. `"` (the unnamed register),
+    /// `0` (the last yank) or `a`.
+    pub fn vim_register(&self, name: char) -> Option<&VimRegister> {
+        self.vim.registers.get(&name.to_ascii_lowercase())
+    }
+
+    /// Returns true to paint a block cursor.
+    pub(super) fn is_vim_block_cursor(&self) -> bool {
+        self.vim.enabled && self.vim.mode == VimMode::Normal
+    }
+
+    /// Set the Vim mode, and update the selection and cursor for the mode.
+    pub(super) fn set_vim_mode_state(&mut self, mode: VimMode, cx: &mut Context<Self>) {
+        let old_mode = self.vim.mode;
+        self.vim.mode = mode;
+        self.vim.pending.clear();
+        self.vim.column = None;
+
+        if mode.is_visual() {
+            if !old_mode.is_visual() {
+                let cursor = self.cursor();
+                self.vim.visual_anchor = cursor;
+                self.vim.visual_head = cursor;
+            }
+            self.vim_update_visual_selection(cx);
+        } else if old_mode.is_visual() {
+            let offset = self.vim_clamp_offset(self.vim.visual_head);
+            self.move_to(offset, None, cx);
+        }
+        cx.notify();
+    }
+
+    /// Handle the key down in Normal or Visual mode.
+    ///
+    /// Returns true if the key is consumed as a part of a command, the key will not
+    /// insert text.
+    pub(super) fn vim_handle_key_down(
+        &mut self,
+        event: &KeyDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.vim.enabled || self.vim.mode == VimMode::Insert || self.disabled {
+            return false;
+        }
+
+        let modifiers = &event.keystroke.modifiers;
+        if modifiers.control || modifiers.platform || modifiers.function {
+            return false;
+        }
+        let Some(key_char) = event.keystroke.key_char.clone() else {
+            return false;
+        };
+
+        self.vim_input(&key_char, window, cx);
+        true
+    }
+
+    /// Handle the Escape, returns true if it is consumed by the Vim mode.
+    pub(super) fn vim_escape(&mut self, cx: &mut Context<Self>) -> bool {
+        if !self.vim.enabled {
+            return false;
+        }
+
+        match self.vim.mode {
+            VimMode::Insert => {
+                // Like Vim, the cursor moves back onto the last inserted char.
+                let cursor = self.cursor();
+                let row = self.text.offset_to_point(cursor).row;
+                let offset = if cursor > self.text.line_start_offset(row) {
+                    self.previous_boundary(cursor)
+                } else {
+                    cursor
+                };
+                self.set_vim_mode_state(VimMode::Normal, cx);
+                self.move_to(self.vim_clamp_offset(offset), None, cx);
+                true
+            }
+            VimMode::Visual | VimMode::VisualLine => {
+                self.set_vim_mode_state(VimMode::Normal, cx);
+                true
+            }
+            VimMode::Normal => {
+                if self.vim.pending.is_empty() {
+                    return false;
+                }
+                self.vim.pending.clear();
+                true
+            }
+        }
+    }
+
+    pub(super) fn on_action_vim_keys(
+        &mut self,
+        action: &VimKeys,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.vim.enabled || self.vim.mode == VimMode::Insert {
+            cx.propagate();
+            return;
+        }
+
+        self.vim_input(&action.keys, window, cx);
+    }
+
+    /// Run the keys as typed in Normal or Visual mode.
+    pub(super) fn vim_input(&mut self, keys: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.vim_feed(keys, true, window, cx);
+        cx.notify();
+    }
+
+    fn vim_feed(&mut self, keys: &str, remap: bool, window: &mut Window, cx: &mut Context<Self>) {
+        for (ix, key) in keys.char_indices() {
+            if !self.vim.enabled {
+                return;
+            }
+
+            if self.vim.mode == VimMode::Insert {
+                // The rest keys are inserted as text, e.g. `ihello`.
+                self.replace_text_in_range_silent(None, &keys[ix..], window, cx);
+                return;
+            }
+
+            self.vim.pending.push(key);
+            if remap && self.vim_feed_mapping(window, cx) {
+                continue;
+            }
+
+            match parse_command(&self.vim.pending, self.vim.mode.is_visual()) {
+                Parsed::Pending => {}
+                Parsed::Invalid => self.vim.pending.clear(),
+                Parsed::Command(command) => {
+                    self.vim.pending.clear();
+                    self.vim_execute(command, window, cx);
+                }
+            }
+        }
+    }
+
+    /// Apply the [`VimKeymap`] to the pending keys, returns true if they are mapped
+    /// or may be mapped with more keys.
+    fn vim_feed_mapping(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let keymap = self.vim.keymap.clone();
+        if let Some(target) = keymap.get(self.vim.mode, &self.vim.pending) {
+            self.vim.pending.clear();
+            self.vim_feed(target, false, window, cx);
+            return true;
+        }
+
+        keymap.is_prefix(self.vim.mode, &self.vim.pending)
+    }
+
+    fn vim_execute(&mut self, command: Command, window: &mut Window, cx: &mut Context<Self>) {
+        let count = command.count.unwrap_or(1);
+
+        match command.kind {
+            CommandKind::Move(motion) => {
+                let from = self.vim_head();
+                if matches!(motion, Motion::Up | Motion::Down) && self.vim.column.is_none() {
+                    self.vim.column = Some(self.vim_column(from));
+                }
+                let Some(offset) = self.vim_motion_target(from, motion, command.count) else {
+                    return;
+                };
+
+                self.vim.column = match motion {
+                    Motion::Up | Motion::Down => self.vim.column,
+                    Motion::LineEnd => Some(usize::MAX),
+                    _ => None,
+                };
+                self.vim_move_head(offset, cx);
+            }
+            CommandKind::Operate(operator, motion) => {
+                let range = if self.vim.mode.is_visual() {
+                    self.vim_visual_range()
+                } else {
+                    let Some(range) = self.vim_operator_range(operator, motion, command.count)
+                    else {
+                        return;
+                    };
+                    range
+                };
+
+                self.vim_operate(operator, range, command.register, window, cx);
+            }
+            CommandKind::Insert(at) => self.vim_insert(at, window, cx),
+            CommandKind::Paste { before } => {
+                self.vim_paste(before, count, command.register, window, cx)
+            }
+            CommandKind::ReplaceChar(ch) => self.vim_replace_char(ch, count, window, cx),
+            CommandKind::Undo => {
+                for _ in 0..count {
+                    self.undo(&Undo, window, cx);
+                }
+                self.move_to(self.vim_clamp_offset(self.cursor()), None, cx);
+            }
+            CommandKind::ToggleVisual(mode) => {
+                if self.vim.mode == mode {
+                    self.set_vim_mode_state(VimMode::Normal, cx);
+                } else {
+                    self.set_vim_mode_state(mode, cx);
+                }
+            }
+            CommandKind::SwapAnchor => {
+                std::mem::swap(&mut self.vim.visual_anchor, &mut self.vim.visual_head);
+                self.vim_update_visual_selection(cx);
+            }
+        }
+    }
+
+    /// The moving end of the selection in Visual mode, or the cursor.
+    fn vim_head(&self) -> usize {
+        if self.vim.mode.is_visual() {
+            self.vim.visual_head
+        } else {
+            self.cursor()
+        }
+    }
+
+    fn vim_move_head(&mut self, offset: usize, cx: &mut Context<Self>) {
+        if self.vim.mode.is_visual() {
+            self.vim.visual_head = offset;
+            self.vim_update_visual_selection(cx);
+        } else {
+            self.move_to(self.vim_clamp_offset(offset), None, cx);
+        }
+    }
+
+    fn vim_update_visual_selection(&mut self, cx: &mut Context<Self>) {
+        let anchor = self.vim.visual_anchor.min(self.text.len());
+        let head = self.vim.visual_head.min(self.text.len());
+        let (start, end) = (anchor.min(head), anchor.max(head));
+
+        let range = if self.vim.mode == VimMode::VisualLine {
+            let start_row = self.text.offset_to_point(start).row;
+            let end_row = self.text.offset_to_point(end).row;
+            self.text.line_start_offset(start_row)..self.vim_line_end(end_row)
+        } else {
+            start..self.vim_next_char(end)
+        };
+
+        self.selected_range = range.into();
+        self.selection_reversed = head < anchor;
+        self.scroll_to(head, None, cx);
+        cx.notify();
+    }
+
+    fn vim_visual_range(&self) -> VimRange {
+        let anchor = self.vim.visual_anchor.min(self.text.len());
+        let head = self.vim.visual_head.min(self.text.len());
+        if self.vim.mode == VimMode::VisualLine {
+            let anchor_row = self.text.offset_to_point(anchor).row;
+            let head_row = self.text.offset_to_point(head).row;
+            VimRange::Lines(anchor_row.min(head_row), anchor_row.max(head_row))
+        } else {
+            VimRange::Chars(self.selected_range.into())
+        }
+    }
+
+    /// Returns the end offset of the row, excluding the line break.
+    fn vim_line_end(&self, row: usize) -> usize {
+        let start = self.text.line_start_offset(row);
+        let end = self.text.line_end_offset(row);
+        if end > start && self.text.char_at(end - 1) == Some('\r') {
+            end - 1
+        } else {
+            end
+        }
+    }
+
+    /// Returns the offset after the char at the offset, in the same line.
+    fn vim_next_char(&self, offset: usize) -> usize {
+        match self.text.char_at(offset) {
+            Some(c) if c != '\n' && c != '\r' => offset + c.len_utf8(),
+            _ => offset,
+        }
+    }
+
+    fn vim_first_non_blank(&self, row: usize) -> usize {
+        let start = self.text.line_start_offset(row);
+        let end = self.vim_line_end(row);
+        start
+            + self
+                .text
+                .slice(start..end)
+                .chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .map(|c| c.len_utf8())
+                .sum::<usize>()
+    }
+
+    fn vim_last_row(&self) -> usize {
+        self.text.lines_len().saturating_sub(1)
+    }
+
+    /// Returns the char column of the offset in its line.
+    fn vim_column(&self, offset: usize) -> usize {
+        let row = self.text.offset_to_point(offset).row;
+        self.text
+            .slice(self.text.line_start_offset(row)..offset)
+            .chars()
+            .count()
+    }
+
+    /// Returns the offset of the char column in the row, clamped to the last char.
+    fn vim_offset_for_column(&self, row: usize, column: usize) -> usize {
+        let start = self.text.line_start_offset(row);
+        let end = self.vim_line_end(row);
+        let mut offset = start;
+        for (ix, c) in self.text.slice(start..end).chars().enumerate() {
+            if ix == column || offset + c.len_utf8() >= end {
+                break;
+            }
+            offset += c.len_utf8();
+        }
+        offset
+    }
+
+    /// In Normal mode, the cursor is on a char, it can not be after the last char of a line.
+    fn vim_clamp_offset(&self, offset: usize) -> usize {
+        let offset = offset.min(self.text.len());
+        if self.vim.mode != VimMode::Normal {
+            return offset;
+        }
+
+        let row = self.text.offset_to_point(offset).row;
+        let start = self.text.line_start_offset(row);
+        let end = self.vim_line_end(row);
+        if offset >= end && end > start {
+            self.previous_boundary(end)
+        } else {
+            offset
+        }
+    }
+
+    fn vim_motion_target(
+        &self,
+        from: usize,
+        motion: Motion,
+        count: Option<usize>,
+    ) -> Option<usize> {
+        let times = count.unwrap_or(1);
+        let row = self.text.offset_to_point(from).row;
+        let line_start = self.text.line_start_offset(row);
+        let line_end = self.vim_line_end(row);
+        let last_row = self.vim_last_row();
+
+        let offset = match motion {
+            Motion::Left => {
+                let mut offset = from;
+                for _ in 0..times {
+                    if offset <= line_start {
+                        break;
+                    }
+                    offset = self.previous_boundary(offset);
+                }
+                offset
+            }
+            Motion::Right => {
+                let mut offset = from;
+                for _ in 0..times {
+                    if offset >= line_end {
+                        break;
+                    }
+                    offset = self.next_boundary(offset);
+                }
+                offset.min(line_end)
+            }
+            Motion::Up | Motion::Down => {
+                let column = self.vim.column.unwrap_or_else(|| self.vim_column(from));
+                let target_row = if motion == Motion::Down {
+                    (row + times).min(last_row)
+                } else {
+                    row.saturating_sub(times)
+                };
+                let offset = self.vim_offset_for_column(target_row, column);
+                if motion == Motion::Down {
+                    self.clamp_offset_to_visible_forward(offset)
+                } else {
+                    self.clamp_offset_to_visible_backward(offset)
+                }
+            }
+            Motion::NextWordStart(big) => {
+                (0..times).fold(from, |offset, _| next_word_start(&self.text, offset, big))
+            }
+            Motion::NextWordEnd(big) => {
+                (0..times).fold(from, |offset, _| next_word_end(&self.text, offset, big))
+            }
+            Motion::PreviousWordStart(big) => (0..times).fold(from, |offset, _| {
+                previous_word_start(&self.text, offset, big)
+            }),
+            Motion::LineStart => line_start,
+            Motion::FirstNonBlank => self.vim_first_non_blank(row),
+            Motion::LineEnd => self.vim_line_end((row + times - 1).min(last_row)),
+            Motion::NextLineStart => self.vim_first_non_blank((row + times).min(last_row)),
+            Motion::PreviousLineStart => self.vim_first_non_blank(row.saturating_sub(times)),
+            Motion::FirstLine => {
+                self.vim_first_non_blank(count.unwrap_or(1).saturating_sub(1).min(last_row))
+            }
+            Motion::LastLine => {
+                let target_row = count.map(|n| n.saturating_sub(1)).unwrap_or(last_row);
+                self.vim_first_non_blank(target_row.min(last_row))
+            }
+            Motion::FindForward { ch, till } => {
+                let line = self.text.slice(from..line_end).to_string();
+                let (ix, _) = line
+                    .char_indices()
+                    .skip(1)
+                    .filter(|(_, c)| *c == ch)
+                    .nth(times - 1)?;
+                let offset = from + ix;
+                if till {
+                    self.previous_boundary(offset)
+                } else {
+                    offset
+                }
+            }
+            Motion::FindBackward { ch, till } => {
+                let line = self.text.slice(line_start..from).to_string();
+                let (ix, c) = line
+                    .char_indices()
+                    .rev()
+                    .filter(|(_, c)| *c == ch)
+                    .nth(times - 1)?;
+                let offset = line_start + ix;
+                if till { offset + c.len_utf8() } else { offset }
+            }
+            Motion::MatchingBracket => self.vim_matching_bracket(from, line_end)?,
+        };
+
+        Some(offset)
+    }
+
+    /// Returns the offset of the bracket matching the first bracket after the offset in the line.
+    fn vim_matching_bracket(&self, from: usize, line_end: usize) -> Option<usize> {
+        let line = self.text.slice(from..line_end).to_string();
+        let (ix, _) = line
+            .char_indices()
+            .find(|(_, c)| matches!(c, '(' | ')' | '[' | ']' | '{' | '}'))?;
+
+        self.matching_bracket(from + ix, &BracketPair::defaults(), usize::MAX)
+    }
+
+    /// Returns the text range of the operator in Normal mode.
+    fn vim_operator_range(
+        &self,
+        operator: Operator,
+        motion: Option<Motion>,
+        count: Option<usize>,
+    ) -> Option<VimRange> {
+        let from = self.cursor();
+        let row = self.text.offset_to_point(from).row;
+
+        let Some(motion) = motion else {
+            let end_row = (row + count.unwrap_or(1) - 1).min(self.vim_last_row());
+            return Some(VimRange::Lines(row, end_row));
+        };
+
+        // Like Vim, `cw` changes to the end of the word, without the trailing whitespace.
+        if let (Operator::Change, Motion::NextWordStart(big)) = (operator, motion)
+            && self.text.char_at(from).is_some_and(|c| !c.is_whitespace())
+        {
+            let mut end = word_run_end(&self.text, from, big);
+            for _ in 1..count.unwrap_or(1) {
+                let last = self.previous_boundary(end);
+                end = self.vim_next_char(next_word_end(&self.text, last, big));
+            }
+            return Some(VimRange::Chars(from..end));
+        }
+
+        let to = self.vim_motion_target(from, motion, count)?;
+        if motion.is_linewise() {
+            let to_row = self.text.offset_to_point(to).row;
+            return Some(VimRange::Lines(row.min(to_row), row.max(to_row)));
+        }
+
+        let start = from.min(to);
+        let mut end = from.max(to);
+        if motion.is_inclusive() {
+            end = self.vim_next_char(end);
+        }
+        // The `w` motion of an operator stops at the end of line.
+        if let Motion::NextWordStart(_) = motion {
+            end = end.min(self.vim_line_end(row)).max(start);
+        }
+
+        Some(VimRange::Chars(start..end))
+    }
+
+    /// Returns the byte range of the rows to delete, including a line break.
+    fn vim_lines_delete_range(&self, start_row: usize, end_row: usize) -> Range<usize> {
+        let last_row = self.vim_last_row();
+        if end_row < last_row {
+            self.text.line_start_offset(start_row)..self.text.line_start_offset(end_row + 1)
+        } else if start_row > 0 {
+            self.vim_line_end(start_row - 1)..self.text.len()
+        } else {
+            0..self.text.len()
+        }
+    }
+
+    fn vim_range_text(&self, range: &VimRange) -> VimRegister {
+        match range {
+            VimRange::Chars(range) => VimRegister {
+                text: self.text.slice(range.clone()).to_string(),
+                linewise: false,
+            },
+            VimRange::Lines(start_row, end_row) => {
+                let start = self.text.line_start_offset(*start_row);
+                let end = self.vim_line_end(*end_row);
+                let text = self
+                    .text
+                    .slice(start..end)
+                    .to_string()
+                    .replace("\r\n", "\n");
+                VimRegister {
+                    text: format!("{}\n", text),
+                    linewise: true,
+                }
+            }
+        }
+    }
+
+    fn vim_set_register(
+        &mut self,
+        name: Option<char>,
+        register: VimRegister,
+        yank: bool,
+        cx: &mut Context<Self>,
+    ) {
+        match name {
+            Some('_') => return,
+            Some('+' | '*') => {
+                cx.write_to_clipboard(ClipboardItem::new_string(register.text.clone()))
+            }
+            Some(name) if name.is_ascii_uppercase() => {
+                let entry = self
+                    .vim
+                    .registers
+                    .entry(name.to_ascii_lowercase())
+                    .or_default();
+                entry.text.push_str(&register.text);
+                entry.linewise |= register.linewise;
+            }
+            Some(name) if name != '"' => {
+                self.vim.registers.insert(name, register.clone());
+            }
+            _ => {
+                self.vim
+                    .registers
+                    .insert(if yank { '0' } else { '-' }, register.clone());
+            }
+        }
+
+        self.vim.registers.insert('"', register);
+    }
+
+    fn vim_get_register(&self, name: Option<char>, cx: &App) -> Option<VimRegister> {
+        match name.unwrap_or('"') {
+            '+' | '*' => {
+                let text = cx.read_from_clipboard()?.text()?;
+                Some(VimRegister {
+                    linewise: text.ends_with('\n'),
+                    text,
+                })
+            }
+            name => self.vim_register(name).cloned(),
+        }
+    }
+
+    fn vim_replace(
+        &mut self,
+        range: Range<usize>,
+        new_text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if range.is_empty() && new_text.is_empty() {
+            return;
+        }
+
+        let range_utf16 = self.range_to_utf16(&range);
+        self.replace_text_in_range_silent(Some(range_utf16), new_text, window, cx);
+    }
+
+    fn vim_operate(
+        &mut self,
+        operator: Operator,
+        range: VimRange,
+        register: Option<char>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let was_visual = self.vim.mode.is_visual();
+        if was_visual {
+            self.set_vim_mode_state(VimMode::Normal, cx);
+        }
+
+        match operator {
+            Operator::Yank | Operator::Delete | Operator::Change => {
+                let content = self.vim_range_text(&range);
+                self.vim_set_register(register, content, operator == Operator::Yank, cx);
+            }
+            _ => {}
+        }
+
+        match (operator, range) {
+            (Operator::Yank, VimRange::Chars(range)) => {
+                self.move_to(self.vim_clamp_offset(range.start), None, cx);
+            }
+            (Operator::Yank, VimRange::Lines(start_row, _)) => {
+                if was_visual {
+                    let offset = self.text.line_start_offset(start_row);
+                    self.move_to(offset, None, cx);
+                }
+            }
+            (Operator::Delete, VimRange::Chars(range)) => {
+                self.vim_replace(range.clone(), "", window, cx);
+                self.move_to(self.vim_clamp_offset(range.start), None, cx);
+            }
+            (Operator::Delete, VimRange::Lines(start_row, end_row)) => {
+                let range = self.vim_lines_delete_range(start_row, end_row);
+                self.vim_replace(range, "", window, cx);
+                let row = start_row.min(self.vim_last_row());
+                self.move_to(self.vim_first_non_blank(row), None, cx);
+            }
+            (Operator::Change, VimRange::Chars(range)) => {
+                self.vim_replace(range.clone(), "", window, cx);
+                self.set_vim_mode_state(VimMode::Insert, cx);
+                self.move_to(range.start, None, cx);
+            }
+            (Operator::Change, VimRange::Lines(start_row, end_row)) => {
+                // Keep the indent of the first line.
+                let indent_end = self.vim_first_non_blank(start_row);
+                let end = self.vim_line_end(end_row);
+                self.vim_replace(indent_end..end, "", window, cx);
+                self.set_vim_mode_state(VimMode::Insert, cx);
+                self.move_to(indent_end, None, cx);
+            }
+            (Operator::Indent | Operator::Outdent, range) => {
+                let (start_row, end_row) = match range {
+                    VimRange::Lines(start_row, end_row) => (start_row, end_row),
+                    VimRange::Chars(range) => (
+                        self.text.offset_to_point(range.start).row,
+                        self.text.offset_to_point(range.end).row,
+                    ),
+                };
+
+                self.selected_range =
+                    (self.text.line_start_offset(start_row)..self.vim_line_end(end_row)).into();
+                self.selection_reversed = false;
+                if operator == Operator::Indent {
+                    self.indent(true, window, cx);
+                } else {
+                    self.outdent(true, window, cx);
+                }
+                self.move_to(self.vim_first_non_blank(start_row), None, cx);
+            }
+        }
+    }
+
+    fn vim_insert(&mut self, at: InsertAt, window: &mut Window, cx: &mut Context<Self>) {
+        let cursor = self.cursor();
+        let row = self.text.offset_to_point(cursor).row;
+
+        let offset = match at {
+            InsertAt::Cursor => cursor,
+            InsertAt::AfterCursor => self.vim_next_char(cursor),
+            InsertAt::FirstNonBlank => self.vim_first_non_blank(row),
+            InsertAt::LineEnd => self.vim_line_end(row),
+            InsertAt::LineBelow | InsertAt::LineAbove => {
+                let line_start = self.text.line_start_offset(row);
+                let indent = self
+                    .text
+                    .slice(line_start..self.vim_first_non_blank(row))
+                    .to_string();
+
+                if at == InsertAt::LineBelow {
+                    let line_end = self.vim_line_end(row);
+                    self.vim_replace(line_end..line_end, &format!("\n{}", indent), window, cx);
+                    line_end + 1 + indent.len()
+                } else {
+                    self.vim_replace(line_start..line_start, &format!("{}\n", indent), window, cx);
+                    line_start + indent.len()
+                }
+            }
+        };
+
+        self.set_vim_mode_state(VimMode::Insert, cx);
+        self.move_to(offset, None, cx);
+    }
+
+    fn vim_paste(
+        &mut self,
+        before: bool,
+        count: usize,
+        register: Option<char>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(content) = self.vim_get_register(register, cx) else {
+            return;
+        };
+        let text = content.text.repeat(count);
+
+        if self.vim.mode.is_visual() {
+            let range = match self.vim_visual_range() {
+                VimRange::Chars(range) => range,
+                VimRange::Lines(start_row, end_row) => {
+                    self.text.line_start_offset(start_row)..self.vim_line_end(end_row)
+                }
+            };
+            let text = text.strip_suffix('\n').unwrap_or(&text).to_string();
+            self.set_vim_mode_state(VimMode::Normal, cx);
+            self.vim_replace(range.clone(), &text, window, cx);
+            self.move_to(self.vim_clamp_offset(range.start), None, cx);
+            return;
+        }
+
+        let cursor = self.cursor();
+        let row = self.text.offset_to_point(cursor).row;
+        if content.linewise {
+            if before {
+                let line_start = self.text.line_start_offset(row);
+                self.vim_replace(line_start..line_start, &text, window, cx);
+                self.move_to(self.vim_first_non_blank(row), None, cx);
+            } else {
+                let line_end = self.vim_line_end(row);
+                let text = format!("\n{}", text.strip_suffix('\n').unwrap_or(&text));
+                self.vim_replace(line_end..line_end, &text, window, cx);
+                self.move_to(self.vim_first_non_blank(row + 1), None, cx);
+            }
+        } else {
+            let offset = if before {
+                cursor
+            } else {
+                self.vim_next_char(cursor)
+            };
+            self.vim_replace(offset..offset, &text, window, cx);
+            let end = offset + text.len();
+            self.move_to(self.vim_clamp_offset(self.previous_boundary(end)), None, cx);
+        }
+    }
+
+    fn vim_replace_char(
+        &mut self,
+        ch: char,
+        count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let cursor = self.cursor();
+        let row = self.text.offset_to_point(cursor).row;
+        let line = self.text.slice(cursor..self.vim_line_end(row)).to_string();
+        if line.chars().count() < count {
+            return;
+        }
+
+        let len = line
+            .chars()
+            .take(count)
+            .map(|c| c.len_utf8())
+            .sum::<usize>();
+        let new_text = ch.to_string().repeat(count);
+        self.vim_replace(cursor..cursor + len, &new_text, window, cx);
+        let end = cursor + new_text.len();
+        self.move_to(self.previous_boundary(end), None, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(count: Option<usize>, kind: CommandKind) -> Parsed {
+        Parsed::Command(Command {
+            register: None,
+            count,
+            kind,
+        })
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("d", false), Parsed::Pending);
+        assert_eq!(parse_command("2", false), Parsed::Pending);
+        assert_eq!(parse_command("g", false), Parsed::Pending);
+        assert_eq!(parse_command("f", false), Parsed::Pending);
+        assert_eq!(parse_command("\"", false), Parsed::Pending);
+        assert_eq!(parse_command("Z", false), Parsed::Invalid);
+        assert_eq!(parse_command("gx", false), Parsed::Invalid);
+
+        assert_eq!(
+            parse_command("j", false),
+            command(None, CommandKind::Move(Motion::Down))
+        );
+        assert_eq!(
+            parse_command("0", false),
+            command(None, CommandKind::Move(Motion::LineStart))
+        );
+        assert_eq!(
+            parse_command("10G", false),
+            command(Some(10), CommandKind::Move(Motion::LastLine))
+        );
+        assert_eq!(
+            parse_command("gg", false),
+            command(None, CommandKind::Move(Motion::FirstLine))
+        );
+        assert_eq!(
+            parse_command("dd", false),
+            command(None, CommandKind::Operate(Operator::Delete, None))
+        );
+        assert_eq!(
+            parse_command("2d3w", false),
+            command(
+                Some(6),
+                CommandKind::Operate(Operator::Delete, Some(Motion::NextWordStart(false)))
+            )
+        );
+        assert_eq!(
+            parse_command("ct)", false),
+            command(
+                None,
+                CommandKind::Operate(
+                    Operator::Change,
+                    Some(Motion::FindForward {
+                        ch: ')',
+                        till: true
+                    })
+                )
+            )
+        );
+        assert_eq!(
+            parse_command("\"ayy", false),
+            Parsed::Command(Command {
+                register: Some('a'),
+                count: None,
+                kind: CommandKind::Operate(Operator::Yank, None),
+            })
+        );
+
+        // Visual mode
+        assert_eq!(
+            parse_command("d", true),
+            command(None, CommandKind::Operate(Operator::Delete, None))
+        );
+        assert_eq!(
+            parse_command("o", true),
+            command(None, CommandKind::SwapAnchor)
+        );
+        assert_eq!(parse_command("i", true), Parsed::Invalid);
+    }
+
+    #[test]
+    fn test_word_motions() {
+        let text = Rope::from("foo.bar  baz\n\nqux");
+
+        assert_eq!(next_word_start(&text, 0, false), 3);
+        assert_eq!(next_word_start(&text, 3, false), 4);
+        assert_eq!(next_word_start(&text, 4, false), 9);
+        assert_eq!(next_word_start(&text, 0, true), 9);
+        // Stop at the empty line.
+        assert_eq!(next_word_start(&text, 9, false), 13);
+        assert_eq!(next_word_start(&text, 13, false), 14);
+        assert_eq!(next_word_start(&text, 14, false), text.len());
+
+        assert_eq!(next_word_end(&text, 0, false), 2);
+        assert_eq!(next_word_end(&text, 2, false), 3);
+        assert_eq!(next_word_end(&text, 0, true), 6);
+        assert_eq!(next_word_end(&text, 6, false), 11);
+
+        assert_eq!(previous_word_start(&text, 11, false), 9);
+        assert_eq!(previous_word_start(&text, 9, false), 4);
+        assert_eq!(previous_word_start(&text, 9, true), 0);
+        assert_eq!(previous_word_start(&text, 14, false), 9);
+
+        assert_eq!(word_run_end(&text, 1, false), 3);
+        assert_eq!(word_run_end(&text, 7, false), 9);
+    }
+
+    #[test]
+    fn test_keymap() {
+        let keymap = VimKeymap::new()
+            .map(VimMode::Normal, "H", "^")
+            .map(VimMode::Normal, "gh", "0")
+            .map(VimMode::Visual, "L", "$");
+
+        assert_eq!(keymap.get(VimMode::Normal, "H"), Some("^"));
+        assert_eq!(keymap.get(VimMode::Visual, "H"), None);
+        assert_eq!(keymap.get(VimMode::VisualLine, "L"), Some("$"));
+        assert!(keymap.is_prefix(VimMode::Normal, "g"));
+        assert!(!keymap.is_prefix(VimMode::Normal, "gh"));
+        assert!(!keymap.is_prefix(VimMode::Visual, "g"));
+    }
+}
@@ -5,7 +5,7 @@ use gpui::{
     Styled, StyledText, Window, div, prelude::FluentBuilder, rems,
 };
 
-use crate::{ActiveTheme, StyledExt};
+use crate::{ActiveTheme, ElementExt as _, MiddleEllipsis, StyledExt};
 
 const MASKED: &'static str = "•";
 
@@ -56,6 +56,7 @@ pub struct Label {
     secondary: Option<SharedString>,
     masked: bool,
     highlights_text: Option<HighlightsMatch>,
+    middle_ellipsis: bool,
 }
 
 impl Label {
@@ -68,6 +69,7 @@ impl Label {
             secondary: None,
             masked: false,
             highlights_text: None,
+            middle_ellipsis: false,
         }
     }
 
@@ -90,6 +92,17 @@ impl Label {
         self
     }
 
+    /// Set to truncate the overflowing text in the middle with an ellipsis,
+    /// the label will be displayed in a single line.
+    ///
+    /// This is useful for the file paths, e.g.: `/Users/jason/…/project/src/main.rs`.
+    ///
+    /// The highlights and the secondary text color are not supported in this mode.
+    pub fn middle_ellipsis(mut self) -> Self {
+        self.middle_ellipsis = true;
+        self
+    }
+
     fn full_text(&self) -> SharedString {
         match &self.secondary {
             Some(secondary) => format!("{} {}", self.label, secondary).into(),
@@ -200,12 +213,24 @@ impl RenderOnce for Label {
             text = SharedString::from(MASKED.repeat(chars_count))
         };
 
+        if self.middle_ellipsis {
+            return div()
+                .line_height(rems(1.25))
+                .text_color(cx.theme().foreground)
+                .min_w_0()
+                .overflow_hidden()
+                .refine_style(&self.style)
+                .test_record(None, Some(text.clone()))
+                .child(MiddleEllipsis::new(text));
+        }
+
         let highlights = self.measure_highlights(text.len(), cx);
 
         div()
             .line_height(rems(1.25))
             .text_color(cx.theme().foreground)
             .refine_style(&self.style)
+            .test_record(None, Some(text.clone()))
             .child(
                 StyledText::new(&text).when_some(highlights, |this, hl| this.with_highlights(hl)),
             )
@@ -13,10 +13,14 @@ mod index_path;
 mod inspector;
 #[cfg(all(target_os = "macos", not(test)))]
 mod macos_accessibility;
+mod notifier;
 mod root;
 mod styled;
 mod time;
 mod title_bar;
+mod truncate;
+mod type_ahead;
+mod virtual_grid;
 mod virtual_list;
 mod window_border;
 mod window_ext;
@@ -38,8 +42,12 @@ pub mod color_picker;
 pub mod combobox;
 pub mod description_list;
 pub mod dialog;
+pub mod diff;
+pub mod dnd;
 pub mod dock;
 pub mod form;
+pub mod gantt;
+pub mod graph;
 pub mod group_box;
 pub mod highlighter;
 pub mod history;
@@ -68,13 +76,18 @@ pub mod sheet;
 pub mod sidebar;
 pub mod skeleton;
 pub mod slider;
+#[cfg(any(test, feature = "image-export"))]
+pub mod snapshot;
 pub mod spinner;
 pub mod status_bar;
 pub mod stepper;
+pub mod storage;
 pub mod switch;
 pub mod tab;
 pub mod table;
 pub mod tag;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test;
 pub mod text;
 pub mod theme;
 pub mod tooltip;
@@ -89,15 +102,21 @@ pub use global_state::GlobalState;
 pub use gpui_component_macros::icon_named;
 pub use icon::*;
 pub use index_path::IndexPath;
-pub use input::{Rope, RopeExt, RopeLines};
+pub use input::{LineEnding, Rope, RopeEdits, RopeExt, RopeLines};
 #[cfg(any(feature = "inspector", debug_assertions))]
 pub use inspector::*;
+pub use notifier::Notifier;
 pub use root::Root;
 pub use styled::*;
 pub use theme::*;
 pub use time::{calendar, date_picker};
 pub use title_bar::*;
-pub use virtual_list::{VirtualList, VirtualListScrollHandle, h_virtual_list, v_virtual_list};
+pub use truncate::{LineClamp, MiddleEllipsis};
+pub use virtual_grid::{VirtualGrid, virtual_grid};
+pub use virtual_list::{
+    VirtualList, VirtualListAnchor, VirtualListMeasureCache, VirtualListScrollHandle,
+    h_virtual_list, v_virtual_list, v_virtual_list_measured,
+};
 pub use window_border::{WindowBorder, window_border, window_paddings};
 pub use window_ext::WindowExt;
 
@@ -112,6 +131,7 @@ pub fn init(cx: &mut App) {
     #[cfg(any(feature = "inspector", debug_assertions))]
     inspector::init(cx);
     root::init(cx);
+    notifier::init(cx);
     focus_trap::init(cx);
     color_picker::init(cx);
     date_picker::init(cx);
@@ -95,6 +95,31 @@ impl RowsCache {
             .position(|p| p.is_entry() && p.eq_index_path(path))
     }
 
+    /// Returns the index paths of all the items.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = IndexPath> + '_ {
+        self.entities.iter().filter_map(|entry| match entry {
+            RowEntry::Entry(ix) => Some(*ix),
+            _ => None,
+        })
+    }
+
+    /// Returns the index paths of the items between `a` and `b` (inclusive) in display order.
+    ///
+    /// Returns empty if either of them is not in the cache.
+    pub(crate) fn entries_between(&self, a: &IndexPath, b: &IndexPath) -> Vec<IndexPath> {
+        let (Some(a), Some(b)) = (self.position_of(a), self.position_of(b)) else {
+            return vec![];
+        };
+
+        self.entities[a.min(b)..=a.max(b)]
+            .iter()
+            .filter_map(|entry| match entry {
+                RowEntry::Entry(ix) => Some(*ix),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Return prev row, if the row is the first in the first section, goes to the last row.
     ///
     /// Empty rows section are skipped.
@@ -381,4 +406,29 @@ mod tests {
             IndexPath::new(1).section(2)
         );
     }
+
+    #[test]
+    fn test_entries_between() {
+        let mut row_cache = RowsCache::default();
+        row_cache.sections = Rc::new(vec![2, 0, 3]);
+        row_cache.entities = Rc::new(build_entities(&[2, 0, 3]));
+
+        assert_eq!(row_cache.entries().count(), 5);
+        assert_eq!(
+            row_cache.entries_between(&IndexPath::new(1).section(2), &IndexPath::new(1)),
+            vec![
+                IndexPath::new(1),
+                IndexPath::new(0).section(2),
+                IndexPath::new(1).section(2),
+            ]
+        );
+        assert_eq!(
+            row_cache.entries_between(&IndexPath::new(1), &IndexPath::new(1)),
+            vec![IndexPath::new(1)]
+        );
+        assert_eq!(
+            row_cache.entries_between(&IndexPath::new(0), &IndexPath::new(5)),
+            vec![]
+        );
+    }
 }
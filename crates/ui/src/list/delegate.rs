@@ -1,8 +1,14 @@
-use gpui::{AnyElement, App, Context, IntoElement, ParentElement as _, Styled as _, Task, Window};
+use gpui::{
+    AnyElement, App, Context, IntoElement, ParentElement as _, SharedString, Styled as _, Task,
+    Window,
+};
+use rust_i18n::t;
 
 use crate::{
-    ActiveTheme as _, Icon, IconName, IndexPath, Selectable, h_flex,
+    ActiveTheme as _, Icon, IconName, IndexPath, Selectable, Sizable as _,
+    button::{Button, ButtonVariants as _},
     list::{ListState, loading::Loading},
+    v_flex,
 };
 
 /// A delegate for the List.
@@ -71,19 +77,77 @@ pub trait ListDelegate: Sized + 'static {
     }
 
     /// Return a Element to show when list is empty.
+    ///
+    /// Default is an icon with a "No data" message.
     fn render_empty(
         &mut self,
         window: &mut Window,
         cx: &mut Context<ListState<Self>>,
     ) -> impl IntoElement {
-        h_flex()
+        v_flex()
+            .size_full()
+            .py_6()
+            .gap_2()
+            .items_center()
+            .justify_center()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(
+                Icon::new(IconName::Inbox)
+                    .size_12()
+                    .text_color(cx.theme().muted_foreground.opacity(0.6)),
+            )
+            .child(t!("List.empty").to_string())
+            .into_any_element()
+    }
+
+    /// Returns the error message to show the error view, default is None.
+    ///
+    /// The error view is shown instead of the items, e.g. when failed to load the data.
+    fn error(&self, cx: &App) -> Option<SharedString> {
+        None
+    }
+
+    /// Return a Element to show when [`ListDelegate::error`] returns an error.
+    ///
+    /// Default is an icon with the error message and a retry button to call
+    /// [`ListDelegate::on_retry`].
+    fn render_error(
+        &mut self,
+        error: SharedString,
+        window: &mut Window,
+        cx: &mut Context<ListState<Self>>,
+    ) -> impl IntoElement {
+        v_flex()
             .size_full()
+            .py_6()
+            .gap_2()
+            .items_center()
             .justify_center()
-            .text_color(cx.theme().muted_foreground.opacity(0.6))
-            .child(Icon::new(IconName::Inbox).size_12())
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(
+                Icon::new(IconName::CircleX)
+                    .size_12()
+                    .text_color(cx.theme().danger.opacity(0.8)),
+            )
+            .child(error)
+            .child(
+                Button::new("retry")
+                    .small()
+                    .outline()
+                    .label(t!("List.retry"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.delegate_mut().on_retry(window, cx);
+                        cx.notify();
+                    })),
+            )
             .into_any_element()
     }
 
+    /// Called when the retry button of the error view is clicked, e.g. to load the data again.
+    fn on_retry(&mut self, window: &mut Window, cx: &mut Context<ListState<Self>>) {}
+
     /// Returns Some(AnyElement) to render the initial state of the list.
     ///
     /// This can be used to show a view for the list before the user has
@@ -112,7 +176,7 @@ pub trait ListDelegate: Sized + 'static {
         window: &mut Window,
         cx: &mut Context<ListState<Self>>,
     ) -> impl IntoElement {
-        Loading
+        Loading::new()
     }
 
     /// Set the selected index, just store the ix, don't confirm.
@@ -123,6 +187,13 @@ pub trait ListDelegate: Sized + 'static {
         cx: &mut Context<ListState<Self>>,
     );
 
+    /// Returns the label of the item for the type-ahead search, default is `None`.
+    ///
+    /// When the list has focus, typing jumps to the next item whose label starts with the typed prefix.
+    fn item_label(&self, ix: IndexPath, cx: &App) -> Option<SharedString> {
+        None
+    }
+
     /// Set the index of the item that has been right clicked.
     fn set_right_clicked_index(
         &mut self,
@@ -142,6 +213,19 @@ pub trait ListDelegate: Sized + 'static {
     /// Cancel the selection, e.g.: Pressed ESC.
     fn cancel(&mut self, window: &mut Window, cx: &mut Context<ListState<Self>>) {}
 
+    /// Move the item at `from` to `to` in the same section, default is no-op.
+    ///
+    /// Called when an item is dropped in a [`ListState::reorderable`] list,
+    /// update the data order here.
+    fn move_item(
+        &mut self,
+        from: IndexPath,
+        to: IndexPath,
+        window: &mut Window,
+        cx: &mut Context<ListState<Self>>,
+    ) {
+    }
+
     /// Return true to enable load more data when scrolling to the bottom.
     ///
     /// Default: false
@@ -0,0 +1,240 @@
+use std::ops::Range;
+
+/// The score of each matched character.
+const SCORE_MATCH: i32 = 16;
+/// The bonus of a match at the start of a word, e.g. after a space or the `B` in `camelBack`.
+const BONUS_BOUNDARY: i32 = 8;
+/// The extra bonus of a match at the first character of the text.
+const BONUS_FIRST_CHAR: i32 = 8;
+/// The bonus of a match right after the previous match.
+const BONUS_CONSECUTIVE: i32 = 12;
+/// The penalty of each skipped character between the matches.
+const PENALTY_GAP: i32 = 1;
+/// The max penalty of the skipped characters before the first match.
+const MAX_PENALTY_LEADING: i32 = 3;
+
+/// The result of [`FuzzyMatcher::match_text`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// The score of the match, the higher is the better.
+    pub score: i32,
+    /// The byte indices of the matched characters in the text, in ascending order.
+    ///
+    /// Use it with [`ListItem::highlight_indices`](super::ListItem::highlight_indices)
+    /// to highlight the matched characters.
+    pub indices: Vec<usize>,
+}
+
+/// A case-insensitive fuzzy matcher, the characters of the query must appear in the text in order.
+///
+/// ```
+/// use gpui_component::list::FuzzyMatcher;
+///
+/// let matcher = FuzzyMatcher::new("gc");
+/// let matched = matcher.match_text("gpui-component").unwrap();
+/// assert_eq!(matched.indices, vec![0, 5]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FuzzyMatcher {
+    query: Vec<char>,
+}
+
+impl FuzzyMatcher {
+    /// Create a new matcher with the `query`, the whitespaces in the query are ignored.
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(to_lower)
+                .collect(),
+        }
+    }
+
+    /// Returns true if the query is empty, it matches all the texts.
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
+    /// Match the `text`, returns `None` if not matched.
+    pub fn match_text(&self, text: &str) -> Option<FuzzyMatch> {
+        if self.query.is_empty() {
+            return Some(FuzzyMatch::default());
+        }
+
+        let chars = text.char_indices().collect::<Vec<_>>();
+
+        // Find the first end of the match, then search backward for the shortest window.
+        let mut qi = 0;
+        let mut end = None;
+        for (i, (_, c)) in chars.iter().enumerate() {
+            if to_lower(*c) == self.query[qi] {
+                qi += 1;
+                if qi == self.query.len() {
+                    end = Some(i);
+                    break;
+                }
+            }
+        }
+        let end = end?;
+
+        let mut qi = self.query.len();
+        let mut start = end;
+        for i in (0..=end).rev() {
+            if to_lower(chars[i].1) == self.query[qi - 1] {
+                qi -= 1;
+                if qi == 0 {
+                    start = i;
+                    break;
+                }
+            }
+        }
+
+        let mut score = -(start as i32 * PENALTY_GAP).min(MAX_PENALTY_LEADING);
+        let mut indices = Vec::with_capacity(self.query.len());
+        let mut last_match: Option<usize> = None;
+        let mut qi = 0;
+        for i in start..=end {
+            if qi == self.query.len() {
+                break;
+            }
+
+            let (byte_ix, c) = chars[i];
+            if to_lower(c) != self.query[qi] {
+                continue;
+            }
+
+            score += SCORE_MATCH;
+            if i == 0 {
+                score += BONUS_BOUNDARY + BONUS_FIRST_CHAR;
+            } else if is_boundary(chars[i - 1].1, c) {
+                score += BONUS_BOUNDARY;
+            }
+            match last_match {
+                Some(last) if last + 1 == i => score += BONUS_CONSECUTIVE,
+                Some(last) => score -= (i - last - 1) as i32 * PENALTY_GAP,
+                None => {}
+            }
+
+            indices.push(byte_ix);
+            last_match = Some(i);
+            qi += 1;
+        }
+
+        Some(FuzzyMatch { score, indices })
+    }
+
+    /// Match the `items` by the text of each item, returns the matched items sorted by the score.
+    ///
+    /// The items with the same score keep the original order.
+    pub fn filter<T>(
+        &self,
+        items: impl IntoIterator<Item = T>,
+        text: impl Fn(&T) -> &str,
+    ) -> Vec<(T, FuzzyMatch)> {
+        let mut matches = items
+            .into_iter()
+            .filter_map(|item| {
+                let matched = self.match_text(text(&item))?;
+                Some((item, matched))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|(_, a), (_, b)| b.score.cmp(&a.score));
+        matches
+    }
+}
+
+#[inline]
+fn to_lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Returns true if the `c` is the start of a word after the `prev` character.
+fn is_boundary(prev: char, c: char) -> bool {
+    if !prev.is_alphanumeric() {
+        return c.is_alphanumeric();
+    }
+
+    (prev.is_lowercase() && c.is_uppercase()) || (!prev.is_numeric() && c.is_numeric())
+}
+
+/// Returns the byte ranges of the characters at the `indices` in the `text`,
+/// the adjacent characters are merged into one range.
+pub(crate) fn highlight_ranges(text: &str, indices: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for ix in indices {
+        let Some(c) = text.get(*ix..).and_then(|s| s.chars().next()) else {
+            continue;
+        };
+
+        let range = *ix..*ix + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => ranges.push(range),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FuzzyMatcher, highlight_ranges};
+
+    #[test]
+    fn test_match_text() {
+        let matcher = FuzzyMatcher::new("gc");
+        assert_eq!(
+            matcher.match_text("gpui-component").unwrap().indices,
+            vec![0, 5]
+        );
+        assert_eq!(
+            matcher.match_text("GPUI Component").unwrap().indices,
+            vec![0, 5]
+        );
+        assert!(matcher.match_text("component").is_none());
+
+        // The shortest window is used.
+        let matcher = FuzzyMatcher::new("ab");
+        assert_eq!(matcher.match_text("a-x-ab").unwrap().indices, vec![4, 5]);
+
+        // Multi-byte characters use the byte indices.
+        let matcher = FuzzyMatcher::new("中文");
+        assert_eq!(matcher.match_text("中的文字").unwrap().indices, vec![0, 6]);
+
+        let matcher = FuzzyMatcher::new("  ");
+        assert!(matcher.is_empty());
+        assert_eq!(
+            matcher.match_text("any").unwrap().indices,
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_match_score() {
+        let matcher = FuzzyMatcher::new("list");
+        let score = |text: &str| matcher.match_text(text).unwrap().score;
+
+        assert!(score("List") > score("SelectList"));
+        assert!(score("SelectList") > score("l_i_s_t"));
+        assert!(score("l_i_s_t") > score("callist"));
+        assert!(score("callist") > score("lxixsxt"));
+    }
+
+    #[test]
+    fn test_filter() {
+        let matcher = FuzzyMatcher::new("tb");
+        let items = vec!["Button", "Table", "ToolBar", "Tab"];
+        let matches = matcher.filter(items, |item| item);
+        assert_eq!(
+            matches.iter().map(|(item, _)| *item).collect::<Vec<_>>(),
+            vec!["ToolBar", "Table", "Tab"]
+        );
+    }
+
+    #[test]
+    fn test_highlight_ranges() {
+        assert_eq!(highlight_ranges("Table", &[0, 1, 3]), vec![0..2, 3..4]);
+        assert_eq!(highlight_ranges("中的文字", &[0, 6, 9]), vec![0..3, 6..12]);
+        assert_eq!(highlight_ranges("Tab", &[1, 10]), vec![1..2]);
+    }
+}
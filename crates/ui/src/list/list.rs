@@ -1,28 +1,39 @@
-use instant::Duration;
+use instant::{Duration, Instant};
+use std::collections::BTreeSet;
 use std::ops::Range;
 
 use crate::actions::{Cancel, Confirm, SelectDown, SelectUp};
 use crate::input::InputState;
 use crate::list::cache::{MeasuredEntrySize, RowEntry, RowsCache};
+use crate::list::loading::Loading;
 use crate::{
     ActiveTheme, IconName, Size,
     input::{Input, InputEvent},
-    scroll::Scrollbar,
+    scroll::{AutoScroll, Scrollbar},
     v_flex,
 };
-use crate::{Icon, IndexPath, Selectable, Sizable, StyledExt};
+use crate::{
+    Icon, IndexPath, Selectable, Sizable, StyledExt, checkbox::Checkbox, type_ahead::TypeAhead,
+};
 use crate::{VirtualListScrollHandle, list::ListDelegate, v_virtual_list};
 use gpui::{
-    App, AvailableSpace, ClickEvent, Context, DefiniteLength, EdgesRefinement, EventEmitter,
-    ListSizingBehavior, RenderOnce, Role, ScrollStrategy, SharedString, StatefulInteractiveElement,
-    StyleRefinement, Subscription, px, size,
+    Animation, AnimationExt as _, App, AvailableSpace, Bounds, ClickEvent, Context, DefiniteLength,
+    DragMoveEvent, EdgesRefinement, EntityId, EventEmitter, ListSizingBehavior, Pixels, RenderOnce,
+    Role, ScrollStrategy, SharedString, StatefulInteractiveElement, StyleRefinement, Subscription,
+    px, size,
 };
 use gpui::{
-    AppContext, Entity, FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding,
-    Length, MouseButton, ParentElement, Render, Styled, Task, Window, div, prelude::FluentBuilder,
+    AppContext, Div, Entity, FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding,
+    KeyDownEvent, Length, MouseButton, ParentElement, Render, Styled, Task, Window, actions, div,
+    prelude::FluentBuilder,
 };
 use rust_i18n::t;
 
+actions!(list, [SelectAllItems]);
+
+/// The duration to fade the loading placeholder to the items.
+const PLACEHOLDER_FADE_DURATION: Duration = Duration::from_millis(250);
+
 pub(crate) fn init(cx: &mut App) {
     let context: Option<&str> = Some("List");
     cx.bind_keys([
@@ -31,6 +42,7 @@ pub(crate) fn init(cx: &mut App) {
         KeyBinding::new("secondary-enter", Confirm { secondary: true }, context),
         KeyBinding::new("up", SelectUp, context),
         KeyBinding::new("down", SelectDown, context),
+        KeyBinding::new("secondary-a", SelectAllItems, context),
     ]);
 }
 
@@ -42,6 +54,35 @@ pub enum ListEvent {
     Confirm(IndexPath),
     /// Pressed ESC to deselect the item.
     Cancel,
+    /// The item has been moved from the first index to the second index by dragging.
+    Move(IndexPath, IndexPath),
+    /// The multi-selection of the items has been changed.
+    ///
+    /// Contains the selected items in ascending order,
+    /// emitted when [`ListState::multi_selectable`] is enabled.
+    SelectionChanged(Vec<IndexPath>),
+}
+
+/// The dragging item of a reorderable [`ListState`].
+#[derive(Clone)]
+pub(crate) struct DragListItem {
+    entity_id: EntityId,
+    ix: IndexPath,
+    size: gpui::Size<Pixels>,
+}
+
+impl Render for DragListItem {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .w(self.size.width)
+            .h(self.size.height)
+            .bg(cx.theme().background)
+            .opacity(0.9)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .shadow_md()
+    }
 }
 
 struct ListOptions {
@@ -50,6 +91,7 @@ struct ListOptions {
     search_placeholder: Option<SharedString>,
     max_height: Option<Length>,
     paddings: EdgesRefinement<DefiniteLength>,
+    loading_placeholder: Option<usize>,
 }
 
 impl Default for ListOptions {
@@ -60,6 +102,7 @@ impl Default for ListOptions {
             max_height: None,
             search_placeholder: None,
             paddings: EdgesRefinement::default(),
+            loading_placeholder: None,
         }
     }
 }
@@ -82,6 +125,19 @@ pub struct ListState<D: ListDelegate> {
     reset_on_cancel: bool,
     searchable: bool,
     selectable: bool,
+    reorderable: bool,
+    multi_selectable: bool,
+    checkbox: bool,
+    selected_indexes: BTreeSet<IndexPath>,
+    /// The item to start the range selection by Shift-click.
+    selection_anchor: Option<IndexPath>,
+    type_ahead: TypeAhead,
+    /// The insertion gap of the dragging item, the item is inserted before the index.
+    drag_gap: Option<IndexPath>,
+    bounds: Bounds<Pixels>,
+    was_loading: bool,
+    /// The times and the start time of fading the loading placeholder to the items.
+    placeholder_fade: Option<(usize, Instant)>,
     _search_task: Task<()>,
     _load_more_task: Task<()>,
     _query_input_subscription: Subscription,
@@ -108,6 +164,16 @@ where
             selected_index: None,
             selectable: true,
             searchable: false,
+            reorderable: false,
+            multi_selectable: false,
+            checkbox: false,
+            selected_indexes: BTreeSet::new(),
+            selection_anchor: None,
+            type_ahead: TypeAhead::default(),
+            drag_gap: None,
+            bounds: Bounds::default(),
+            was_loading: false,
+            placeholder_fade: None,
             item_to_measure_index: IndexPath::default(),
             deferred_scroll_to_index: None,
             mouse_right_clicked_index: None,
@@ -144,6 +210,107 @@ where
         cx.notify();
     }
 
+    /// Sets whether the items can be reordered by dragging, default is false.
+    ///
+    /// The items can be only moved within the same section,
+    /// see [`ListDelegate::move_item`].
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    /// Sets whether the items can be reordered by dragging, default is false.
+    pub fn set_reorderable(&mut self, reorderable: bool, cx: &mut Context<Self>) {
+        self.reorderable = reorderable;
+        self.drag_gap = None;
+        cx.notify();
+    }
+
+    /// Sets whether the list is multi-selectable, default is false.
+    ///
+    /// When `true`, the items can be selected by Cmd/Ctrl-click, Shift-click ranges and the
+    /// `secondary-a` to select all, then [`ListEvent::SelectionChanged`] is emitted.
+    pub fn multi_selectable(mut self, multi_selectable: bool) -> Self {
+        self.multi_selectable = multi_selectable;
+        self
+    }
+
+    /// Sets whether the list is multi-selectable, the selection is cleared when disabled.
+    pub fn set_multi_selectable(&mut self, multi_selectable: bool, cx: &mut Context<Self>) {
+        self.multi_selectable = multi_selectable;
+        if !multi_selectable {
+            self.selection_anchor = None;
+            self.update_selected_indexes(BTreeSet::new(), cx);
+        }
+        cx.notify();
+    }
+
+    /// Sets whether to show a leading checkbox on each item
+    /// when [`Self::multi_selectable`] is enabled, default is false.
+    pub fn checkbox(mut self, checkbox: bool) -> Self {
+        self.checkbox = checkbox;
+        self
+    }
+
+    /// Sets whether to show a leading checkbox on each item.
+    pub fn set_checkbox(&mut self, checkbox: bool, cx: &mut Context<Self>) {
+        self.checkbox = checkbox;
+        cx.notify();
+    }
+
+    /// Returns the selected items in ascending order, when [`Self::multi_selectable`] is enabled.
+    pub fn selected_indexes(&self) -> Vec<IndexPath> {
+        self.selected_indexes.iter().copied().collect()
+    }
+
+    /// Returns true if the item at the given index is in the multi-selection.
+    pub fn is_index_selected(&self, ix: IndexPath) -> bool {
+        self.selected_indexes.contains(&ix)
+    }
+
+    /// Set the selected items, and emit [`ListEvent::SelectionChanged`] if changed.
+    pub fn set_selected_indexes(
+        &mut self,
+        indexes: impl IntoIterator<Item = IndexPath>,
+        cx: &mut Context<Self>,
+    ) {
+        let indexes = indexes
+            .into_iter()
+            .filter(|ix| self.rows_cache.position_of(ix).is_some())
+            .collect();
+        self.update_selected_indexes(indexes, cx);
+    }
+
+    /// Select all the items, when [`Self::multi_selectable`] is enabled.
+    pub fn select_all(&mut self, cx: &mut Context<Self>) {
+        if !self.multi_selectable {
+            return;
+        }
+
+        let indexes = self.rows_cache.entries().collect();
+        self.update_selected_indexes(indexes, cx);
+    }
+
+    /// Add or remove the item at the given index to the multi-selection.
+    pub fn toggle_index_selected(&mut self, ix: IndexPath, cx: &mut Context<Self>) {
+        let mut indexes = self.selected_indexes.clone();
+        if !indexes.remove(&ix) {
+            indexes.insert(ix);
+        }
+        self.selection_anchor = Some(ix);
+        self.update_selected_indexes(indexes, cx);
+    }
+
+    fn update_selected_indexes(&mut self, indexes: BTreeSet<IndexPath>, cx: &mut Context<Self>) {
+        if self.selected_indexes == indexes {
+            return;
+        }
+
+        self.selected_indexes = indexes;
+        cx.emit(ListEvent::SelectionChanged(self.selected_indexes()));
+        cx.notify();
+    }
+
     pub fn delegate(&self) -> &D {
         &self.delegate
     }
@@ -280,6 +447,10 @@ where
                 self.set_searching(true, window, cx);
                 let search = self.delegate.perform_search(&text, window, cx);
 
+                // The indexes are changed by the search results.
+                self.selection_anchor = None;
+                self.update_selected_indexes(BTreeSet::new(), cx);
+
                 if self.rows_cache.len() > 0 {
                     self._set_selected_index(Some(IndexPath::default()), window, cx);
                 } else {
@@ -377,6 +548,55 @@ where
         cx.notify();
     }
 
+    fn on_action_select_all(&mut self, _: &SelectAllItems, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.multi_selectable {
+            cx.propagate();
+            return;
+        }
+
+        self.select_all(cx);
+    }
+
+    fn on_item_click(
+        &mut self,
+        e: &ClickEvent,
+        ix: IndexPath,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_right_clicked_index(None, window, cx);
+
+        let modifiers = e.modifiers();
+        if self.multi_selectable && modifiers.shift {
+            // Select the range from the anchor item to the clicked item.
+            let anchor = self.selection_anchor.unwrap_or(ix);
+            let indexes = self.rows_cache.entries_between(&anchor, &ix);
+            self.update_selected_indexes(indexes.into_iter().collect(), cx);
+            self.set_selected_index(Some(ix), window, cx);
+            cx.notify();
+            return;
+        } else if self.multi_selectable && modifiers.secondary() {
+            self.toggle_index_selected(ix, cx);
+            self.set_selected_index(Some(ix), window, cx);
+            cx.notify();
+            return;
+        }
+
+        if self.multi_selectable {
+            self.selection_anchor = Some(ix);
+            self.update_selected_indexes(BTreeSet::from([ix]), cx);
+        }
+
+        self.selected_index = Some(ix);
+        self.on_action_confirm(
+            &Confirm {
+                secondary: modifiers.secondary(),
+            },
+            window,
+            cx,
+        );
+    }
+
     fn select_item(&mut self, ix: IndexPath, window: &mut Window, cx: &mut Context<Self>) {
         if !self.selectable {
             return;
@@ -417,6 +637,144 @@ where
         self.select_item(next_ix, window, cx);
     }
 
+    /// Select the next item whose label starts with the typed prefix,
+    /// see [`ListDelegate::item_label`].
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        // Skip the typing in the search input.
+        if !self.focus_handle.is_focused(window) {
+            return;
+        }
+        let Some(text) = TypeAhead::key_char(event) else {
+            return;
+        };
+
+        let entries = self.rows_cache.entries().collect::<Vec<_>>();
+        let Some(first) = entries.first() else {
+            return;
+        };
+        if self.delegate.item_label(*first, cx).is_none() {
+            return;
+        }
+        if !self.type_ahead.push(text, Instant::now()) {
+            return;
+        }
+
+        let labels = entries
+            .iter()
+            .map(|ix| self.delegate.item_label(*ix, cx).unwrap_or_default())
+            .collect::<Vec<_>>();
+        let current = self
+            .selected_index
+            .and_then(|selected| entries.iter().position(|ix| *ix == selected));
+        if let Some(pos) = self.type_ahead.find(&labels, current) {
+            cx.stop_propagation();
+            self.select_item(entries[pos], window, cx);
+        }
+    }
+
+    /// Move the item at `from` to `to` in the same section, and keep the selection on the moved items.
+    ///
+    /// This calls [`ListDelegate::move_item`] to move the data, then emits [`ListEvent::Move`].
+    pub fn move_item(
+        &mut self,
+        from: IndexPath,
+        to: IndexPath,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if from.section != to.section || from.row == to.row {
+            return;
+        }
+
+        self.delegate.move_item(from, to, window, cx);
+
+        let remap = |ix: IndexPath| {
+            if ix.section == from.section {
+                ix.row(move_ix(ix.row, from.row, to.row))
+            } else {
+                ix
+            }
+        };
+        self.selected_index = self.selected_index.map(remap);
+        self.mouse_right_clicked_index = self.mouse_right_clicked_index.map(remap);
+        self.selection_anchor = self.selection_anchor.map(remap);
+        let indexes = self.selected_indexes.iter().copied().map(remap).collect();
+        self.update_selected_indexes(indexes, cx);
+
+        cx.emit(ListEvent::Move(from, to));
+        cx.notify();
+    }
+
+    /// Auto scroll the list when dragging an item near the top or bottom edges.
+    fn on_item_drag_move(
+        &mut self,
+        e: &DragMoveEvent<DragListItem>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if e.drag(cx).entity_id != cx.entity_id() {
+            return;
+        }
+
+        let position = e.event.position;
+        if !e.bounds.contains(&position) {
+            if self.drag_gap.take().is_some() {
+                cx.notify();
+            }
+            return;
+        }
+
+        let Some(delta) = AutoScroll::compute_delta(position.y, e.bounds) else {
+            return;
+        };
+
+        let scroll_handle = self.scroll_handle.base_handle();
+        let mut offset = scroll_handle.offset();
+        offset.y = (offset.y - delta)
+            .max(-scroll_handle.max_offset().y)
+            .min(px(0.));
+        if offset != scroll_handle.offset() {
+            scroll_handle.set_offset(offset);
+            cx.notify();
+        }
+    }
+
+    /// Update the insertion gap when dragging an item over the item at the `ix`.
+    fn on_item_drag_move_over(
+        &mut self,
+        e: &DragMoveEvent<DragListItem>,
+        ix: IndexPath,
+        cx: &mut Context<Self>,
+    ) {
+        let drag = e.drag(cx);
+        if drag.entity_id != cx.entity_id() || !e.bounds.contains(&e.event.position) {
+            return;
+        }
+
+        let gap = item_drag_gap(ix, drag.ix, e.event.position.y, e.bounds);
+        if self.drag_gap != gap {
+            self.drag_gap = gap;
+            cx.notify();
+        }
+    }
+
+    fn on_item_drop(&mut self, drag: &DragListItem, window: &mut Window, cx: &mut Context<Self>) {
+        if drag.entity_id != cx.entity_id() {
+            return;
+        }
+
+        // Insert the dragged item into the indicated gap.
+        let Some(gap) = self.drag_gap.take() else {
+            return;
+        };
+        let to = if drag.ix.row < gap.row {
+            gap.row(gap.row - 1)
+        } else {
+            gap
+        };
+        self.move_item(drag.ix, to, window, cx);
+    }
+
     fn prepare_items_if_needed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let sections_count = self.delegate.sections_count(cx).max(1);
         let mut measured_size = MeasuredEntrySize::default();
@@ -464,31 +822,87 @@ where
         let id = SharedString::from(format!("list-item-{}", ix));
 
         let total_items = self.rows_cache.items_count();
+        let entity_id = cx.entity_id();
+        let drag_size = gpui::Size {
+            width: self.bounds.size.width,
+            height: self
+                .rows_cache
+                .position_of(&ix)
+                .and_then(|pos| self.rows_cache.entries_sizes.get(pos))
+                .map(|size| size.height)
+                .unwrap_or_default(),
+        };
+        let is_last_item = ix.row + 1 == self.delegate.items_count(ix.section, cx);
+        let multi_selected = self.multi_selectable && self.selected_indexes.contains(&ix);
+        let show_checkbox = self.multi_selectable && self.checkbox;
+        let item = self.delegate.render_item(ix, window, cx).map(|item| {
+            item.selected(selected || multi_selected)
+                .secondary_selected(mouse_right_clicked)
+        });
 
         div()
             .id(id)
             .role(Role::ListItem)
             .aria_position_in_set(ix.row + 1)
             .aria_size_of_set(total_items)
-            .aria_selected(selected)
+            .aria_selected(selected || multi_selected)
             .w_full()
             .relative()
             .overflow_hidden()
-            .children(self.delegate.render_item(ix, window, cx).map(|item| {
-                item.selected(selected)
-                    .secondary_selected(mouse_right_clicked)
-            }))
+            .map(|this| {
+                if show_checkbox {
+                    this.flex()
+                        .items_center()
+                        .child(self.render_item_checkbox(ix, multi_selected, cx))
+                        .child(div().flex_1().min_w_0().children(item))
+                } else {
+                    this.children(item)
+                }
+            })
+            .when(self.reorderable, |this| {
+                this.on_drag(
+                    DragListItem {
+                        entity_id,
+                        ix,
+                        size: drag_size,
+                    },
+                    |drag, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| drag.clone())
+                    },
+                )
+                .on_drag_move(cx.listener(
+                    move |this, e: &DragMoveEvent<DragListItem>, _, cx| {
+                        this.on_item_drag_move_over(e, ix, cx)
+                    },
+                ))
+            })
+            .map(|this| {
+                // Draw the insertion indicator on the top edge of the gap item,
+                // or on the bottom edge of the last item for the trailing gap.
+                match self.drag_gap {
+                    Some(gap)
+                        if cx.has_active_drag()
+                            && gap.section == ix.section
+                            && (gap.row == ix.row || (is_last_item && gap.row == ix.row + 1)) =>
+                    {
+                        let bottom_side = gap.row == ix.row + 1;
+                        this.child(
+                            div()
+                                .absolute()
+                                .left_0()
+                                .right_0()
+                                .h(px(2.))
+                                .map(|d| if bottom_side { d.bottom_0() } else { d.top_0() })
+                                .bg(cx.theme().drag_border),
+                        )
+                    }
+                    _ => this,
+                }
+            })
             .when(selectable, |this| {
                 this.on_click(cx.listener(move |this, e: &ClickEvent, window, cx| {
-                    this.set_right_clicked_index(None, window, cx);
-                    this.selected_index = Some(ix);
-                    this.on_action_confirm(
-                        &Confirm {
-                            secondary: e.modifiers().secondary(),
-                        },
-                        window,
-                        cx,
-                    );
+                    this.on_item_click(e, ix, window, cx);
                 }))
                 .on_mouse_down(
                     MouseButton::Right,
@@ -500,13 +914,71 @@ where
             })
     }
 
+    fn render_item_checkbox(
+        &self,
+        ix: IndexPath,
+        checked: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .flex_shrink_0()
+            .map(|this| match self.options.size {
+                Size::Small => this.pl_2(),
+                _ => this.pl_3(),
+            })
+            .child(
+                Checkbox::new(SharedString::from(format!("list-checkbox-{}", ix)))
+                    .checked(checked)
+                    .tab_stop(false)
+                    .on_click(cx.listener(move |this, _: &bool, _, cx| {
+                        cx.stop_propagation();
+                        this.toggle_index_selected(ix, cx);
+                    })),
+            )
+    }
+
+    /// Cross-fade the placeholder with `rows` skeleton rows to the `items` after loaded.
+    fn render_placeholder_fade(&self, items: Div, rows: usize) -> impl IntoElement {
+        let fading = self
+            .placeholder_fade
+            .filter(|(_, started_at)| started_at.elapsed() < PLACEHOLDER_FADE_DURATION);
+
+        v_flex()
+            .flex_grow_1()
+            .relative()
+            .size_full()
+            .map(|this| match self.placeholder_fade {
+                Some((times, _)) => this.child(items.with_animation(
+                    ("list-fade-in", times),
+                    Animation::new(PLACEHOLDER_FADE_DURATION),
+                    |this, delta| this.opacity(delta),
+                )),
+                None => this.child(items),
+            })
+            .when_some(fading, |this, (times, _)| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .right_0()
+                        .child(Loading::new().rows(rows))
+                        .with_animation(
+                            ("list-fade-out", times),
+                            Animation::new(PLACEHOLDER_FADE_DURATION),
+                            |this, delta| this.opacity(1. - delta),
+                        ),
+                )
+            })
+    }
+
     fn render_items(
         &mut self,
         items_count: usize,
         entities_count: usize,
         window: &mut Window,
         cx: &mut Context<Self>,
-    ) -> impl IntoElement {
+    ) -> Div {
         let rows_cache = self.rows_cache.clone();
         let scrollbar_visible = self.options.scrollbar_visible;
         let scroll_handle = self.scroll_handle.clone();
@@ -517,6 +989,14 @@ where
             .size_full()
             .when_some(self.options.max_height, |this, h| this.max_h(h))
             .overflow_hidden()
+            .when(self.reorderable, |this| {
+                this.on_drag_move(cx.listener(Self::on_item_drag_move))
+                    .on_drop(cx.listener(Self::on_item_drop))
+                    .on_prepaint({
+                        let state = cx.entity();
+                        move |bounds, _, cx| state.update(cx, |state, _| state.bounds = bounds)
+                    })
+            })
             .when(items_count == 0, |this| {
                 this.child(self.delegate.render_empty(window, cx))
             })
@@ -618,11 +1098,31 @@ where
             None
         };
 
+        let loading_placeholder = self.options.loading_placeholder;
+        if self.was_loading && !loading && loading_placeholder.is_some() {
+            let times = self.placeholder_fade.map_or(0, |(times, _)| times) + 1;
+            self.placeholder_fade = Some((times, Instant::now()));
+        }
+        self.was_loading = loading;
+
         let loading_view = if loading {
-            Some(self.delegate.render_loading(window, cx).into_any_element())
+            Some(match loading_placeholder {
+                Some(rows) => Loading::new().rows(rows).into_any_element(),
+                None => self.delegate.render_loading(window, cx).into_any_element(),
+            })
         } else {
             None
         };
+
+        let error_view = if loading {
+            None
+        } else {
+            self.delegate().error(cx).map(|error| {
+                self.delegate
+                    .render_error(error, window, cx)
+                    .into_any_element()
+            })
+        };
         let initial_view = if let Some(input) = &query_input {
             if input.read(cx).value().is_empty() {
                 self.delegate.render_initial(window, cx)
@@ -670,11 +1170,19 @@ where
                     .on_action(cx.listener(Self::on_action_confirm))
                     .on_action(cx.listener(Self::on_action_select_next))
                     .on_action(cx.listener(Self::on_action_select_prev))
+                    .on_action(cx.listener(Self::on_action_select_all))
+                    .on_key_down(cx.listener(Self::on_key_down))
                     .map(|this| {
-                        if let Some(view) = initial_view {
+                        if let Some(view) = error_view {
+                            this.child(view)
+                        } else if let Some(view) = initial_view {
                             this.child(view)
                         } else {
-                            this.child(self.render_items(items_count, entities_count, window, cx))
+                            let items = self.render_items(items_count, entities_count, window, cx);
+                            match loading_placeholder {
+                                Some(rows) => this.child(self.render_placeholder_fade(items, rows)),
+                                None => this.child(items),
+                            }
                         }
                     })
                     // Click out to cancel right clicked row
@@ -721,6 +1229,13 @@ where
         self.options.search_placeholder = Some(placeholder.into());
         self
     }
+
+    /// Show `count` skeleton rows while [`ListDelegate::loading`] is true,
+    /// instead of the [`ListDelegate::render_loading`], then fade to the items when loaded.
+    pub fn loading_placeholder(mut self, count: usize) -> Self {
+        self.options.loading_placeholder = Some(count);
+        self
+    }
 }
 
 impl<D> Styled for List<D>
@@ -766,3 +1281,84 @@ where
             .child(self.state.clone())
     }
 }
+
+/// Returns the insertion gap when dragging the item at `drag_ix`
+/// over the item at `ix` with the `bounds`.
+///
+/// Returns `None` if dropping there would not move the dragged item,
+/// or the items are in the different sections.
+fn item_drag_gap(
+    ix: IndexPath,
+    drag_ix: IndexPath,
+    y: Pixels,
+    bounds: Bounds<Pixels>,
+) -> Option<IndexPath> {
+    if ix.section != drag_ix.section {
+        return None;
+    }
+
+    let gap = if y < bounds.center().y {
+        ix.row
+    } else {
+        ix.row + 1
+    };
+
+    if gap == drag_ix.row || gap == drag_ix.row + 1 {
+        None
+    } else {
+        Some(ix.row(gap))
+    }
+}
+
+/// Returns the new index of the item `ix` after the item at `from` is moved to `to`.
+fn move_ix(ix: usize, from: usize, to: usize) -> usize {
+    if ix == from {
+        to
+    } else if from < ix && ix <= to {
+        ix - 1
+    } else if to <= ix && ix < from {
+        ix + 1
+    } else {
+        ix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Bounds, point, px, size};
+
+    use super::{item_drag_gap, move_ix};
+    use crate::IndexPath;
+
+    #[test]
+    fn test_item_drag_gap() {
+        let bounds = Bounds::new(point(px(0.), px(100.)), size(px(200.), px(30.)));
+        let ix = |row| IndexPath::new(row);
+
+        assert_eq!(item_drag_gap(ix(5), ix(1), px(105.), bounds), Some(ix(5)));
+        assert_eq!(item_drag_gap(ix(5), ix(1), px(125.), bounds), Some(ix(6)));
+        assert_eq!(item_drag_gap(ix(0), ix(3), px(101.), bounds), Some(ix(0)));
+
+        // Dropping around the dragged item would not move it.
+        assert_eq!(item_drag_gap(ix(5), ix(5), px(105.), bounds), None);
+        assert_eq!(item_drag_gap(ix(4), ix(5), px(125.), bounds), None);
+        assert_eq!(item_drag_gap(ix(6), ix(5), px(105.), bounds), None);
+
+        // Only move within the same section.
+        assert_eq!(
+            item_drag_gap(ix(5).section(1), ix(1), px(105.), bounds),
+            None
+        );
+    }
+
+    #[test]
+    fn test_move_ix() {
+        assert_eq!(move_ix(1, 1, 3), 3);
+        assert_eq!(move_ix(2, 1, 3), 1);
+        assert_eq!(move_ix(3, 1, 3), 2);
+        assert_eq!(move_ix(4, 1, 3), 4);
+        assert_eq!(move_ix(3, 3, 1), 1);
+        assert_eq!(move_ix(1, 3, 1), 2);
+        assert_eq!(move_ix(0, 3, 1), 0);
+    }
+}
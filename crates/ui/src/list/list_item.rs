@@ -1,9 +1,14 @@
-use crate::{ActiveTheme, Disableable, Icon, Selectable, Sizable as _, StyledExt, h_flex};
+use crate::{
+    ActiveTheme, Disableable, Icon, Selectable, Sizable as _, StyledExt,
+    dnd::{DragDropExt as _, DragItem, DropTarget},
+    h_flex,
+    list::fuzzy::highlight_ranges,
+};
 use gpui::{
-    AnyElement, App, ClickEvent, Div, ElementId, InteractiveElement, IntoElement, MouseButton,
-    MouseDownEvent, MouseMoveEvent, ParentElement, RenderOnce, Stateful,
-    StatefulInteractiveElement as _, StyleRefinement, Styled, Window, div,
-    prelude::FluentBuilder as _,
+    AnyElement, App, ClickEvent, Div, ElementId, FontWeight, HighlightStyle, InteractiveElement,
+    IntoElement, MouseButton, MouseDownEvent, MouseMoveEvent, ParentElement, RenderOnce,
+    SharedString, Stateful, StatefulInteractiveElement as _, StyleRefinement, Styled, StyledText,
+    Window, div, prelude::FluentBuilder as _,
 };
 use smallvec::SmallVec;
 use std::collections::HashMap;
@@ -32,6 +37,8 @@ pub struct ListItem {
     secondary_selected: bool,
     confirmed: bool,
     check_icon: Option<Icon>,
+    label: Option<SharedString>,
+    highlight_indices: Vec<usize>,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     on_mouse_down:
         HashMap<MouseButton, Box<dyn Fn(&MouseDownEvent, &mut Window, &mut App) + 'static>>,
@@ -55,6 +62,8 @@ impl ListItem {
             on_mouse_down: HashMap::new(),
             on_mouse_enter: None,
             check_icon: None,
+            label: None,
+            highlight_indices: vec![],
             suffix: None,
             children: SmallVec::new(),
         }
@@ -72,6 +81,19 @@ impl ListItem {
         self
     }
 
+    /// Set the label text of the list item, it is rendered before the children.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the byte indices of the characters to highlight in the [`ListItem::label`],
+    /// e.g. the [`FuzzyMatch::indices`](super::FuzzyMatch::indices) of the search query.
+    pub fn highlight_indices(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.highlight_indices = indices.into_iter().collect();
+        self
+    }
+
     /// Set ListItem as the selected item style.
     pub fn selected(mut self, selected: bool) -> Self {
         self.selected = selected;
@@ -125,6 +147,18 @@ impl ListItem {
         self.on_mouse_enter = Some(Box::new(handler));
         self
     }
+
+    /// Make the list item draggable with the `item` as the payload.
+    pub fn draggable<T: Clone + 'static>(mut self, item: DragItem<T>) -> Self {
+        self.base = self.base.draggable(item);
+        self
+    }
+
+    /// Make the list item a drop target of the [`DragItem<T>`] payloads.
+    pub fn drop_target<T: Clone + 'static>(mut self, target: DropTarget<T>) -> Self {
+        self.base = self.base.drop_target(target);
+        self
+    }
 }
 
 impl Disableable for ListItem {
@@ -211,7 +245,41 @@ impl RenderOnce for ListItem {
                     .items_center()
                     .justify_between()
                     .gap_x_1()
-                    .child(div().w_full().children(self.children))
+                    .map(|this| match self.label {
+                        Some(label) => {
+                            let highlights = highlight_ranges(&label, &self.highlight_indices)
+                                .into_iter()
+                                .map(|range| {
+                                    (
+                                        range,
+                                        HighlightStyle {
+                                            color: Some(cx.theme().blue),
+                                            font_weight: Some(FontWeight::BOLD),
+                                            ..Default::default()
+                                        },
+                                    )
+                                });
+
+                            this.child(
+                                h_flex()
+                                    .w_full()
+                                    .gap_2()
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .min_w_0()
+                                            .overflow_hidden()
+                                            .whitespace_nowrap()
+                                            .text_ellipsis()
+                                            .child(
+                                                StyledText::new(label).with_highlights(highlights),
+                                            ),
+                                    )
+                                    .children(self.children),
+                            )
+                        }
+                        None => this.child(div().w_full().children(self.children)),
+                    })
                     .when_some(self.check_icon, |this, icon| {
                         this.child(
                             div().w_5().items_center().justify_center().when(
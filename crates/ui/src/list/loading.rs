@@ -2,8 +2,24 @@ use super::ListItem;
 use crate::{skeleton::Skeleton, v_flex};
 use gpui::{IntoElement, ParentElement as _, RenderOnce, Styled};
 
+/// The skeleton rows to show while the list is loading.
 #[derive(IntoElement)]
-pub struct Loading;
+pub struct Loading {
+    rows: usize,
+}
+
+impl Loading {
+    /// Create a loading view with 3 skeleton rows.
+    pub fn new() -> Self {
+        Self { rows: 3 }
+    }
+
+    /// Set the number of the skeleton rows.
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
+    }
+}
 
 #[derive(IntoElement)]
 struct LoadingItem;
@@ -25,8 +41,7 @@ impl RenderOnce for Loading {
         v_flex()
             .py_2p5()
             .gap_3()
-            .child(LoadingItem)
-            .child(LoadingItem)
-            .child(LoadingItem)
+            .overflow_hidden()
+            .children((0..self.rows).map(|_| LoadingItem))
     }
 }
@@ -1,11 +1,13 @@
 pub(crate) mod cache;
 mod delegate;
+mod fuzzy;
 mod list;
 mod list_item;
 mod loading;
 mod separator_item;
 
 pub use delegate::*;
+pub use fuzzy::{FuzzyMatch, FuzzyMatcher};
 pub use list::*;
 pub use list_item::*;
 use schemars::JsonSchema;
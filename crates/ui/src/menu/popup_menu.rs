@@ -2,19 +2,33 @@ use crate::actions::{Cancel, Confirm, SelectDown, SelectUp};
 use crate::actions::{SelectLeft, SelectRight};
 use crate::menu::menu_item::MenuItemElement;
 use crate::scroll::ScrollableElement;
+use crate::virtual_list::{VirtualListScrollHandle, v_virtual_list};
 use crate::{ActiveTheme, ElementExt, Icon, IconName, Sizable as _, h_flex, v_flex};
 use crate::{Side, Size, StyledExt, kbd::Kbd};
 use gpui::{
     Action, Anchor, AnyElement, App, AppContext, Bounds, Context, DismissEvent, Edges, Entity,
     EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding,
-    ParentElement, Pixels, Render, Role, ScrollHandle, SharedString, StatefulInteractiveElement,
-    Styled, WeakEntity, Window, anchored, deferred, div, prelude::FluentBuilder, px, rems,
+    ParentElement, Pixels, Render, Role, ScrollStrategy, SharedString, StatefulInteractiveElement,
+    Styled, WeakEntity, Window, anchored, deferred, div, prelude::FluentBuilder, px, rems, size,
 };
-use gpui::{ClickEvent, Half, MouseDownEvent, OwnedMenuItem, Point, Subscription};
+use gpui::{
+    ClickEvent, Half, HighlightStyle, KeyDownEvent, Keystroke, MouseDownEvent, OwnedMenuItem,
+    Point, StyledText, Subscription, UnderlineStyle,
+};
+use instant::{Duration, Instant};
 
+use std::ops::Range;
 use std::rc::Rc;
 
 const CONTEXT: &str = "PopupMenu";
+/// The timeout to reset the typed prefix for jumping to the matching items.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+/// The menu with more items than this only renders the visible items.
+const VIRTUAL_ITEMS_THRESHOLD: usize = 100;
+/// The gap between the items, same as the `gap_y_0p5`.
+const ITEM_GAP: Pixels = px(2.);
+/// The height of the separator, including the margins.
+const SEPARATOR_HEIGHT: Pixels = px(6.);
 
 pub fn init(cx: &mut App) {
     cx.bind_keys([
@@ -271,12 +285,60 @@ impl PopupMenuItem {
 
     fn a11y_label(&self) -> Option<SharedString> {
         match self {
-            PopupMenuItem::Item { label, .. }
-            | PopupMenuItem::Label(label)
-            | PopupMenuItem::Submenu { label, .. } => Some(label.clone()),
+            PopupMenuItem::Item { label, .. } | PopupMenuItem::Submenu { label, .. } => {
+                Some(parse_accelerator(label).0)
+            }
+            PopupMenuItem::Label(label) => Some(label.clone()),
             PopupMenuItem::Separator | PopupMenuItem::ElementItem { .. } => None,
         }
     }
+
+    /// Returns the label (without the `&` marker) and the lowercase accelerator character.
+    fn accelerator_label(&self) -> Option<(SharedString, Option<char>)> {
+        match self {
+            PopupMenuItem::Item { label, .. } | PopupMenuItem::Submenu { label, .. } => {
+                let (label, range) = parse_accelerator(label);
+                let accelerator = range
+                    .and_then(|range| label[range].chars().next())
+                    .map(|c| c.to_ascii_lowercase());
+                Some((label, accelerator))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse the accelerator of a menu label, the character after `&` is the accelerator,
+/// and `&&` is a literal `&`, e.g. `"&Open"`, `"Save &As..."`.
+///
+/// Returns the label to display and the byte range of the accelerator in it.
+fn parse_accelerator(label: &str) -> (SharedString, Option<Range<usize>>) {
+    if !label.contains('&') {
+        return (label.to_string().into(), None);
+    }
+
+    let mut text = String::with_capacity(label.len());
+    let mut accelerator = None;
+    let mut chars = label.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            match chars.peek() {
+                Some('&') => {
+                    chars.next();
+                }
+                Some(next) if next.is_alphanumeric() => {
+                    if accelerator.is_none() {
+                        accelerator = Some(text.len()..text.len() + next.len_utf8());
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        text.push(c);
+    }
+
+    (text.into(), accelerator)
 }
 
 pub struct PopupMenu {
@@ -296,7 +358,9 @@ pub struct PopupMenu {
     parent_menu: Option<WeakEntity<Self>>,
     scrollable: bool,
     external_link_icon: bool,
-    scroll_handle: ScrollHandle,
+    scroll_handle: VirtualListScrollHandle,
+    /// The row sizes and the width of the virtual list, with the items count it was measured for.
+    virtual_layout: Option<(usize, Rc<Vec<gpui::Size<Pixels>>>, Pixels)>,
     // This will update on render
     submenu_anchor: (Anchor, Pixels),
 
@@ -312,6 +376,9 @@ pub struct PopupMenu {
     /// per level matters because GPUI caps nested deferred depth (see
     /// `prepaint_deferred_draws`).
     priority: usize,
+    /// The typed prefix for jumping to the matching items.
+    typeahead: String,
+    typeahead_at: Instant,
 
     _subscriptions: Vec<Subscription>,
 }
@@ -330,11 +397,14 @@ impl PopupMenu {
             check_side: Side::Left,
             bounds: Bounds::default(),
             scrollable: false,
-            scroll_handle: ScrollHandle::default(),
+            scroll_handle: VirtualListScrollHandle::new(),
+            virtual_layout: None,
             external_link_icon: true,
             size: Size::default(),
             submenu_anchor: (Anchor::TopLeft, Pixels::ZERO),
             priority: 1,
+            typeahead: String::new(),
+            typeahead_at: Instant::now(),
             _subscriptions: vec![],
         }
     }
@@ -393,6 +463,9 @@ impl PopupMenu {
 
     /// Set the menu to be scrollable to show vertical scrollbar.
     ///
+    /// The menu with more than 100 items is always scrollable, and only renders the visible items,
+    /// so a menu built from a large dynamic set (e.g. thousands of folders) stays fast.
+    ///
     /// NOTE: If this is true, the sub-menus will cannot be support.
     pub fn scrollable(mut self, scrollable: bool) -> Self {
         self.scrollable = scrollable;
@@ -823,11 +896,84 @@ impl PopupMenu {
     fn set_selected_index(&mut self, ix: usize, cx: &mut Context<Self>) {
         if self.selected_index != Some(ix) {
             self.selected_index = Some(ix);
-            self.scroll_handle.scroll_to_item(ix);
+            if self.is_virtual() {
+                // Scroll to the nearest edge to keep the item visible.
+                self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+            } else {
+                self.scroll_handle.base_handle().scroll_to_item(ix);
+            }
             cx.notify();
         }
     }
 
+    /// Whether to render the items by a virtual list, see [`VIRTUAL_ITEMS_THRESHOLD`].
+    #[inline]
+    fn is_virtual(&self) -> bool {
+        self.menu_items.len() > VIRTUAL_ITEMS_THRESHOLD
+    }
+
+    fn item_height(&self) -> Pixels {
+        match self.size {
+            Size::Small => px(20.),
+            _ => px(26.),
+        }
+    }
+
+    /// Returns the row sizes and the width for the virtual list.
+    ///
+    /// The items are not measured one by one, so the width is estimated by the longest label,
+    /// and the custom element items are laid out in the item height.
+    fn virtual_layout(&mut self, window: &mut Window) -> (Rc<Vec<gpui::Size<Pixels>>>, Pixels) {
+        let items_count = self.menu_items.len();
+        if let Some((count, sizes, width)) = &self.virtual_layout {
+            if *count == items_count {
+                return (sizes.clone(), *width);
+            }
+        }
+
+        let item_height = self.item_height();
+        let sizes = self
+            .menu_items
+            .iter()
+            .enumerate()
+            .map(|(ix, item)| {
+                let height = match item {
+                    // Ignore last separator
+                    PopupMenuItem::Separator if ix + 1 == items_count => px(0.),
+                    PopupMenuItem::Separator => SEPARATOR_HEIGHT + ITEM_GAP,
+                    _ => item_height + ITEM_GAP,
+                };
+                size(px(0.), height)
+            })
+            .collect::<Vec<_>>();
+
+        let longest_label = self
+            .menu_items
+            .iter()
+            .filter_map(|item| item.accelerator_label().map(|(label, _)| label))
+            .max_by_key(|label| label.chars().count());
+        let label_width = longest_label.map_or(px(0.), |label| {
+            let text_style = window.text_style();
+            let font_size = rems(0.875).to_pixels(window.rem_size());
+            let runs = [text_style.to_run(label.len())];
+            window
+                .text_system()
+                .shape_line(label, font_size, &runs, None)
+                .width
+        });
+        // The room for the paddings, the icons and the submenu arrow.
+        let width = (label_width + px(64.))
+            .max(
+                self.min_width
+                    .unwrap_or(rems(8.).to_pixels(window.rem_size())),
+            )
+            .min(self.max_width());
+
+        let sizes = Rc::new(sizes);
+        self.virtual_layout = Some((items_count, sizes.clone(), width));
+        (sizes, width)
+    }
+
     fn select_up(&mut self, _: &SelectUp, _: &mut Window, cx: &mut Context<Self>) {
         cx.stop_propagation();
         let ix = self.selected_index.unwrap_or(0);
@@ -867,6 +1013,160 @@ impl PopupMenu {
         self.set_selected_index(0, cx);
     }
 
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        if self.activate_by_keystroke(keystroke, window, cx) {
+            cx.stop_propagation();
+            return;
+        }
+
+        let modifiers = keystroke.modifiers;
+        if modifiers.control || modifiers.platform || modifiers.function {
+            return;
+        }
+
+        // Use the key without the modifiers for the accelerator, e.g. `alt-o` on macOS.
+        let mut key_chars = keystroke.key.chars();
+        if let (Some(c), None) = (key_chars.next(), key_chars.next()) {
+            if c.is_alphanumeric() && self.activate_by_accelerator(c, window, cx) {
+                cx.stop_propagation();
+                return;
+            }
+        }
+
+        if modifiers.alt {
+            return;
+        }
+        let Some(key_char) = keystroke.key_char.as_ref() else {
+            return;
+        };
+        if key_char.chars().any(|c| c.is_control()) || key_char.trim().is_empty() {
+            return;
+        }
+
+        self.select_by_typeahead(key_char, cx);
+        cx.stop_propagation();
+    }
+
+    /// Activate the item whose displayed key binding matches the `keystroke`.
+    fn activate_by_keystroke(
+        &mut self,
+        keystroke: &Keystroke,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let matched_ix = self.clickable_menu_items().find_map(|(ix, item)| {
+            let action = match item {
+                PopupMenuItem::Item { action, .. } | PopupMenuItem::ElementItem { action, .. } => {
+                    action.as_ref()?
+                }
+                _ => return None,
+            };
+
+            let binding = self
+                .action_context
+                .as_ref()
+                .and_then(|handle| {
+                    window.highest_precedence_binding_for_action_in(action.as_ref(), handle)
+                })
+                .or_else(|| window.highest_precedence_binding_for_action(action.as_ref()))?;
+            let [binding_keystroke] = binding.keystrokes() else {
+                return None;
+            };
+
+            let binding_keystroke = binding_keystroke.as_keystroke();
+            (binding_keystroke.key == keystroke.key
+                && binding_keystroke.modifiers == keystroke.modifiers)
+                .then_some(ix)
+        });
+
+        let Some(ix) = matched_ix else {
+            return false;
+        };
+        self.selected_index = Some(ix);
+        self.confirm(&Confirm { secondary: false }, window, cx);
+        true
+    }
+
+    /// Activate the item with the accelerator `c`, or select the next one if there
+    /// are multiple items with the same accelerator.
+    fn activate_by_accelerator(
+        &mut self,
+        c: char,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let c = c.to_ascii_lowercase();
+        let matched = self
+            .clickable_menu_items()
+            .filter(|(_, item)| {
+                item.accelerator_label()
+                    .is_some_and(|(_, accelerator)| accelerator == Some(c))
+            })
+            .map(|(ix, _)| ix)
+            .collect::<Vec<_>>();
+
+        match matched.as_slice() {
+            [] => false,
+            [ix] => {
+                let ix = *ix;
+                if matches!(self.menu_items[ix], PopupMenuItem::Submenu { .. }) {
+                    self.set_selected_index(ix, cx);
+                    self._select_submenu(window, cx);
+                } else {
+                    self.selected_index = Some(ix);
+                    self.confirm(&Confirm { secondary: false }, window, cx);
+                }
+                true
+            }
+            _ => {
+                let current = self.selected_index;
+                let next_ix = matched
+                    .iter()
+                    .find(|ix| Some(**ix) > current)
+                    .unwrap_or(&matched[0]);
+                self.set_selected_index(*next_ix, cx);
+                true
+            }
+        }
+    }
+
+    /// Select the next item whose label starts with the typed prefix.
+    fn select_by_typeahead(&mut self, text: &str, cx: &mut Context<Self>) {
+        let now = Instant::now();
+        if now.duration_since(self.typeahead_at) > TYPEAHEAD_TIMEOUT {
+            self.typeahead.clear();
+        }
+        self.typeahead_at = now;
+        self.typeahead.push_str(&text.to_lowercase());
+
+        // Typing the same character repeatedly cycles the items starting with it.
+        let mut prefix_chars = self.typeahead.chars();
+        let first_char = prefix_chars.next();
+        let is_repeated = prefix_chars.all(|c| Some(c) == first_char);
+        let (prefix, start) = if is_repeated {
+            (
+                first_char.map(String::from).unwrap_or_default(),
+                self.selected_index.map_or(0, |ix| ix + 1),
+            )
+        } else {
+            (self.typeahead.clone(), self.selected_index.unwrap_or(0))
+        };
+
+        let len = self.menu_items.len();
+        let matched_ix = (0..len).map(|i| (start + i) % len).find(|&ix| {
+            let item = &self.menu_items[ix];
+            item.is_clickable()
+                && item
+                    .accelerator_label()
+                    .is_some_and(|(label, _)| label.to_lowercase().starts_with(&prefix))
+        });
+
+        if let Some(ix) = matched_ix {
+            self.set_selected_index(ix, cx);
+        }
+    }
+
     fn select_left(&mut self, _: &SelectLeft, window: &mut Window, cx: &mut Context<Self>) {
         let handled = if matches!(self.submenu_anchor.0, Anchor::TopLeft | Anchor::BottomLeft) {
             self._unselect_submenu(window, cx)
@@ -1087,6 +1387,25 @@ impl PopupMenu {
         };
     }
 
+    /// Render the label with the accelerator underlined.
+    fn render_label(label: &SharedString) -> StyledText {
+        let (label, accelerator) = parse_accelerator(label);
+        let highlights = accelerator.map(|range| {
+            (
+                range,
+                HighlightStyle {
+                    underline: Some(UnderlineStyle {
+                        thickness: px(1.),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
+        });
+
+        StyledText::new(label).with_highlights(highlights)
+    }
+
     fn render_item(
         &self,
         ix: usize,
@@ -1110,9 +1429,10 @@ impl PopupMenu {
         let is_submenu = matches!(item, PopupMenuItem::Submenu { .. });
         let group_name = format!("{}:item-{}", cx.entity().entity_id(), ix);
 
-        let (item_height, radius) = match self.size {
-            Size::Small => (px(20.), options.radius.half()),
-            _ => (px(26.), options.radius),
+        let item_height = self.item_height();
+        let radius = match self.size {
+            Size::Small => options.radius.half(),
+            _ => options.radius,
         };
 
         let this = MenuItemElement::new(ix, &group_name)
@@ -1213,7 +1533,9 @@ impl PopupMenu {
                         .gap_3()
                         .items_center()
                         .justify_between()
-                        .when(!show_link_icon, |this| this.child(label.clone()))
+                        .when(!show_link_icon, |this| {
+                            this.child(Self::render_label(label))
+                        })
                         .children(right_check_icon)
                         .when(show_link_icon, |this| {
                             this.child(
@@ -1221,7 +1543,7 @@ impl PopupMenu {
                                     .w_full()
                                     .justify_between()
                                     .gap_1p5()
-                                    .child(label.clone())
+                                    .child(Self::render_label(label))
                                     .child(
                                         Icon::new(IconName::ExternalLink)
                                             .xsmall()
@@ -1260,7 +1582,7 @@ impl PopupMenu {
                                 .gap_2()
                                 .items_center()
                                 .justify_between()
-                                .child(label.clone())
+                                .child(Self::render_label(label))
                                 .child(
                                     Icon::new(IconName::ChevronRight)
                                         .xsmall()
@@ -1332,6 +1654,9 @@ impl Render for PopupMenu {
             check_side: self.check_side,
             radius: cx.theme().radius.min(px(8.)),
         };
+        let is_virtual = self.is_virtual();
+        let scrollable = self.scrollable || is_virtual;
+        let virtual_layout = is_virtual.then(|| self.virtual_layout(window));
 
         v_flex()
             .id("popup-menu")
@@ -1344,35 +1669,79 @@ impl Render for PopupMenu {
             .on_action(cx.listener(Self::select_right))
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::dismiss))
+            .on_key_down(cx.listener(Self::on_key_down))
             .on_mouse_down_out(cx.listener(Self::on_mouse_down_out))
             .popover_style(cx)
             .text_color(cx.theme().popover_foreground)
             .relative()
             .occlude()
-            .child(
-                v_flex()
-                    .id("items")
-                    .p_1()
-                    .gap_y_0p5()
-                    .min_w(rems(8.))
-                    .when_some(self.min_width, |this, min_width| this.min_w(min_width))
-                    .max_w(max_width)
-                    .when(self.scrollable, |this| {
-                        this.max_h(max_height)
-                            .overflow_y_scroll()
+            .map(|this| match virtual_layout {
+                Some((item_sizes, width)) => this.child(
+                    v_flex()
+                        .id("items")
+                        .p_1()
+                        .w(width)
+                        .child(
+                            v_virtual_list(
+                                view.clone(),
+                                "virtual-items",
+                                item_sizes,
+                                move |menu, visible_range, window, cx| {
+                                    let item_height = menu.item_height();
+                                    visible_range
+                                        .map(|ix| {
+                                            let item = &menu.menu_items[ix];
+                                            if ix + 1 == items_count && item.is_separator() {
+                                                return div();
+                                            }
+
+                                            div().pb(ITEM_GAP).child(
+                                                menu.render_item(ix, item, options, window, cx)
+                                                    .when(!item.is_separator(), |this| {
+                                                        this.h(item_height)
+                                                    }),
+                                            )
+                                        })
+                                        .collect()
+                                },
+                            )
                             .track_scroll(&self.scroll_handle)
-                    })
-                    .children(
-                        self.menu_items
-                            .iter()
-                            .enumerate()
-                            // Ignore last separator
-                            .filter(|(ix, item)| !(*ix + 1 == items_count && item.is_separator()))
-                            .map(|(ix, item)| self.render_item(ix, item, options, window, cx)),
-                    )
-                    .on_prepaint(move |bounds, _, cx| view.update(cx, |r, _| r.bounds = bounds)),
-            )
-            .when(self.scrollable, |this| {
+                            .w_full()
+                            .max_h(max_height),
+                        )
+                        .on_prepaint(move |bounds, _, cx| {
+                            view.update(cx, |r, _| r.bounds = bounds)
+                        }),
+                ),
+                None => this.child(
+                    v_flex()
+                        .id("items")
+                        .p_1()
+                        .gap_y_0p5()
+                        .min_w(rems(8.))
+                        .when_some(self.min_width, |this, min_width| this.min_w(min_width))
+                        .max_w(max_width)
+                        .when(scrollable, |this| {
+                            this.max_h(max_height)
+                                .overflow_y_scroll()
+                                .track_scroll(&self.scroll_handle)
+                        })
+                        .children(
+                            self.menu_items
+                                .iter()
+                                .enumerate()
+                                // Ignore last separator
+                                .filter(|(ix, item)| {
+                                    !(*ix + 1 == items_count && item.is_separator())
+                                })
+                                .map(|(ix, item)| self.render_item(ix, item, options, window, cx)),
+                        )
+                        .on_prepaint(move |bounds, _, cx| {
+                            view.update(cx, |r, _| r.bounds = bounds)
+                        }),
+                ),
+            })
+            .when(scrollable, |this| {
                 // TODO: When the menu is limited by `overflow_y_scroll`, the sub-menu will cannot be displayed.
                 this.vertical_scrollbar(&self.scroll_handle)
             })
@@ -1400,7 +1769,37 @@ mod tests {
             PopupMenuItem::submenu("More", submenu).a11y_label(),
             Some("More".into())
         );
+        assert_eq!(
+            PopupMenuItem::new("Save &As...").a11y_label(),
+            Some("Save As...".into())
+        );
         assert_eq!(PopupMenuItem::separator().a11y_label(), None);
         assert_eq!(PopupMenuItem::element(|_, _| div()).a11y_label(), None);
     }
+
+    #[test]
+    fn test_parse_accelerator() {
+        assert_eq!(parse_accelerator("Open"), ("Open".into(), None));
+        assert_eq!(parse_accelerator("&Open"), ("Open".into(), Some(0..1)));
+        assert_eq!(
+            parse_accelerator("Save &As..."),
+            ("Save As...".into(), Some(5..6))
+        );
+        // `&&` is a literal `&`, and only the first marker is the accelerator.
+        assert_eq!(
+            parse_accelerator("Find && &Replace &Next"),
+            ("Find & Replace Next".into(), Some(7..8))
+        );
+        // `&` before a non-alphanumeric character is kept.
+        assert_eq!(
+            parse_accelerator("Cut & Paste"),
+            ("Cut & Paste".into(), None)
+        );
+
+        assert_eq!(
+            PopupMenuItem::new("E&xit").accelerator_label(),
+            Some(("Exit".into(), Some('x')))
+        );
+        assert_eq!(PopupMenuItem::separator().accelerator_label(), None);
+    }
 }
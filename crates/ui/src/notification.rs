@@ -14,7 +14,7 @@ use gpui::{
 };
 
 use crate::{
-    ActiveTheme as _, Edges, Icon, IconName, Sizable as _, StyledExt, TITLE_BAR_HEIGHT,
+    ActiveTheme as _, Edges, Icon, IconName, LineClamp, Sizable as _, StyledExt, TITLE_BAR_HEIGHT,
     animation::cubic_bezier,
     button::{Button, ButtonVariants as _},
     h_flex, v_flex,
@@ -69,6 +69,7 @@ pub struct Notification {
     type_: Option<NotificationType>,
     title: Option<SharedString>,
     message: Option<SharedString>,
+    message_lines: Option<usize>,
     icon: Option<Icon>,
     autohide: bool,
     action_builder: Option<Rc<dyn Fn(&mut Self, &mut Window, &mut Context<Self>) -> Button>>,
@@ -126,6 +127,7 @@ impl Notification {
             style: StyleRefinement::default(),
             title: None,
             message: None,
+            message_lines: None,
             type_: None,
             icon: None,
             autohide: true,
@@ -143,6 +145,14 @@ impl Notification {
         self
     }
 
+    /// Set the max number of lines of the message, default is None (no limit).
+    ///
+    /// The long message will be clamped, with a "Show more" button to expand it.
+    pub fn message_lines(mut self, lines: usize) -> Self {
+        self.message_lines = Some(lines);
+        self
+    }
+
     /// Create an info notification with the given message.
     pub fn info(message: impl Into<SharedString>) -> Self {
         Self::new()
@@ -336,7 +346,16 @@ impl Render for Notification {
                         this.child(div().text_sm().font_semibold().child(title))
                     })
                     .when_some(self.message.clone(), |this, message| {
-                        this.child(div().text_sm().child(message))
+                        this.child(match self.message_lines {
+                            Some(lines) => {
+                                LineClamp::new(("message", cx.entity_id().as_u64()), message)
+                                    .lines(lines)
+                                    .expandable(true)
+                                    .text_sm()
+                                    .into_any_element()
+                            }
+                            None => div().text_sm().child(message).into_any_element(),
+                        })
                     })
                     .when_some(content, |this, content| this.child(content)),
             )
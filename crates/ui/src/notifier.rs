@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+
+use gpui::{AnyWindowHandle, App, Context, Global, SharedString, Window};
+
+use crate::{
+    Root,
+    async_util::{Sender, unbounded},
+    notification::{Notification, NotificationType},
+};
+
+pub(crate) fn init(cx: &mut App) {
+    let (tx, rx) = unbounded::<NotifierMessage>();
+    cx.set_global(GlobalNotifier {
+        notifier: Notifier { tx },
+        pending: VecDeque::new(),
+    });
+
+    cx.spawn(async move |cx| {
+        while let Ok(message) = rx.recv().await {
+            _ = cx.update(|cx| GlobalNotifier::deliver(message, cx));
+        }
+    })
+    .detach();
+}
+
+#[derive(Debug, Clone)]
+struct NotifierMessage {
+    type_: NotificationType,
+    message: SharedString,
+    /// Deliver to all Root windows instead of only the focused one.
+    broadcast: bool,
+}
+
+impl NotifierMessage {
+    fn notification(&self) -> Notification {
+        (self.type_, self.message.clone()).into()
+    }
+}
+
+/// An app-level handle to push notifications without a [`Window`].
+///
+/// The handle is `Clone + Send`, so it can be moved into background tasks or non-UI code.
+/// Messages are delivered on the main thread to the Root of the focused window, and are
+/// queued until the first [`Root`] is created when no window exists yet.
+///
+/// ```ignore
+/// let notifier = Notifier::global(cx);
+/// cx.background_spawn(async move {
+///     notifier.notify(NotificationType::Success, "Export finished.");
+/// })
+/// .detach();
+/// ```
+#[derive(Clone)]
+pub struct Notifier {
+    tx: Sender<NotifierMessage>,
+}
+
+impl Notifier {
+    /// Returns the global notifier handle.
+    pub fn global(cx: &App) -> Self {
+        cx.global::<GlobalNotifier>().notifier.clone()
+    }
+
+    /// Show a notification in the focused window.
+    ///
+    /// Falls back to the first Root window when no window is focused.
+    pub fn notify(&self, type_: NotificationType, message: impl Into<SharedString>) {
+        self.send(type_, message.into(), false);
+    }
+
+    /// Show a notification in every Root window.
+    pub fn notify_all(&self, type_: NotificationType, message: impl Into<SharedString>) {
+        self.send(type_, message.into(), true);
+    }
+
+    fn send(&self, type_: NotificationType, message: SharedString, broadcast: bool) {
+        _ = self.tx.try_send(NotifierMessage {
+            type_,
+            message,
+            broadcast,
+        });
+    }
+}
+
+pub(crate) struct GlobalNotifier {
+    notifier: Notifier,
+    /// Messages received before any Root window exists.
+    pending: VecDeque<NotifierMessage>,
+}
+
+impl Global for GlobalNotifier {}
+
+impl GlobalNotifier {
+    fn deliver(message: NotifierMessage, cx: &mut App) {
+        let windows = cx.windows();
+        let targets: Vec<AnyWindowHandle> = if message.broadcast {
+            windows
+        } else {
+            cx.active_window().into_iter().chain(windows).collect()
+        };
+
+        let mut delivered = false;
+        for handle in targets {
+            let pushed = handle
+                .update(cx, |_, window, cx| {
+                    let Some(Some(root)) = window.root::<Root>() else {
+                        return false;
+                    };
+                    root.update(cx, |root, cx| {
+                        root.push_notification(message.notification(), window, cx)
+                    });
+                    true
+                })
+                .unwrap_or(false);
+
+            delivered |= pushed;
+            if delivered && !message.broadcast {
+                break;
+            }
+        }
+
+        if !delivered {
+            cx.global_mut::<GlobalNotifier>().pending.push_back(message);
+        }
+    }
+
+    /// Flush the pending messages into a newly created Root.
+    pub(crate) fn flush(window: &mut Window, cx: &mut Context<Root>) {
+        let Some(this) = cx.try_global::<GlobalNotifier>() else {
+            return;
+        };
+        if this.pending.is_empty() {
+            return;
+        }
+
+        let pending = std::mem::take(&mut cx.global_mut::<GlobalNotifier>().pending);
+        cx.defer_in(window, move |root, window, cx| {
+            for message in pending {
+                root.push_notification(message.notification(), window, cx);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, Render, TestAppContext, div};
+
+    struct EmptyView;
+
+    impl Render for EmptyView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl gpui::IntoElement {
+            div()
+        }
+    }
+
+    #[gpui::test]
+    fn test_notify_queues_until_root_exists(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+
+        cx.update(|cx| {
+            let notifier = Notifier::global(cx);
+            notifier.notify(NotificationType::Info, "Hello");
+            notifier.notify(NotificationType::Error, "World");
+        });
+        cx.run_until_parked();
+        cx.update(|cx| {
+            assert_eq!(cx.global::<GlobalNotifier>().pending.len(), 2);
+        });
+
+        let (root, cx) = cx.add_window_view(|window, cx| {
+            let view = cx.new(|_| EmptyView);
+            Root::new(view, window, cx)
+        });
+        cx.run_until_parked();
+
+        let count = root.read_with(cx, |root, cx| {
+            root.notification.read(cx).notifications.len()
+        });
+        assert_eq!(count, 2);
+
+        cx.update(|_, cx| {
+            assert!(cx.global::<GlobalNotifier>().pending.is_empty());
+            Notifier::global(cx).notify(NotificationType::Success, "Done");
+        });
+        cx.run_until_parked();
+
+        let count = root.read_with(cx, |root, cx| {
+            root.notification.read(cx).notifications.len()
+        });
+        assert_eq!(count, 3);
+    }
+}
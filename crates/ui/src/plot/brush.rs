@@ -0,0 +1,304 @@
+use std::ops::Range;
+
+use gpui::{
+    App, Bounds, Context, Entity, EventEmitter, InteractiveElement as _, IntoElement, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement as _, Pixels, Point, RenderOnce,
+    Size, StyleRefinement, Styled, Window, div, prelude::FluentBuilder as _, px,
+};
+
+use crate::{ActiveTheme as _, ElementExt as _, StyledExt as _};
+
+/// The min size of the brush to select, a smaller drag clears the selection.
+const MIN_BRUSH_SIZE: Pixels = px(3.);
+
+/// The axes to select by the [`Brush`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BrushAxis {
+    /// Select a range of the x axis, the full height is covered.
+    #[default]
+    X,
+    /// Select a box of both the x and y axes.
+    XY,
+}
+
+/// The selected area of a [`Brush`], relative to the top-left of the brush.
+///
+/// The values are in the same coordinates as the [`Scale::tick`](super::scale::Scale::tick),
+/// map them to the data by the scales, e.g. [`ScaleLinear::invert`](super::scale::ScaleLinear::invert).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrushSelection {
+    /// The selected range of the x axis.
+    pub x: Range<f32>,
+    /// The selected range of the y axis, `None` for [`BrushAxis::X`].
+    pub y: Option<Range<f32>>,
+}
+
+/// Events emitted by the [`BrushState`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrushEvent {
+    /// The selection has been changed at the end of a drag, `None` if it is cleared.
+    ///
+    /// Use this to filter the linked views, e.g. a table or another chart.
+    BrushChanged(Option<BrushSelection>),
+}
+
+/// The state of the [`Brush`].
+pub struct BrushState {
+    axis: BrushAxis,
+    selection: Option<BrushSelection>,
+    /// The start position and the selection before the dragging.
+    dragging: Option<(Point<Pixels>, Option<BrushSelection>)>,
+    bounds: Bounds<Pixels>,
+}
+
+impl BrushState {
+    /// Create a new [`BrushState`] to select a range of the x axis.
+    pub fn new() -> Self {
+        Self {
+            axis: BrushAxis::default(),
+            selection: None,
+            dragging: None,
+            bounds: Bounds::default(),
+        }
+    }
+
+    /// Set the axes to select, default is [`BrushAxis::X`].
+    pub fn axis(mut self, axis: BrushAxis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Returns the selection of the brush.
+    pub fn selection(&self) -> Option<&BrushSelection> {
+        self.selection.as_ref()
+    }
+
+    /// Returns the bounds of the brush in the last frame, to build the scales to map the selection.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        self.bounds
+    }
+
+    /// Set the selection of the brush, e.g. to sync the brushes of the linked charts.
+    ///
+    /// Emits [`BrushEvent::BrushChanged`] if changed.
+    pub fn set_selection(&mut self, selection: Option<BrushSelection>, cx: &mut Context<Self>) {
+        self.dragging = None;
+        if self.selection == selection {
+            return;
+        }
+
+        self.selection = selection.clone();
+        cx.emit(BrushEvent::BrushChanged(selection));
+        cx.notify();
+    }
+
+    /// Clear the selection of the brush.
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.set_selection(None, cx);
+    }
+
+    fn on_mouse_down(&mut self, e: &MouseDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let position = e.position - self.bounds.origin;
+        self.dragging = Some((position, self.selection.clone()));
+        cx.stop_propagation();
+    }
+
+    fn on_mouse_move(&mut self, e: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some((start, _)) = self.dragging.as_ref() else {
+            return;
+        };
+        if !e.dragging() {
+            return;
+        }
+
+        let end = e.position - self.bounds.origin;
+        self.selection = brush_selection(*start, end, self.bounds.size, self.axis);
+        cx.notify();
+    }
+
+    fn on_mouse_up(&mut self, e: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some((start, old_selection)) = self.dragging.take() else {
+            return;
+        };
+
+        let end = e.position - self.bounds.origin;
+        self.selection = brush_selection(start, end, self.bounds.size, self.axis);
+        if self.selection != old_selection {
+            cx.emit(BrushEvent::BrushChanged(self.selection.clone()));
+        }
+        cx.notify();
+    }
+}
+
+impl Default for BrushState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventEmitter<BrushEvent> for BrushState {}
+
+/// Returns the selection between the `start` and `end` positions, clamped to the `size`.
+///
+/// Returns `None` if the selection is smaller than the [`MIN_BRUSH_SIZE`].
+fn brush_selection(
+    start: Point<Pixels>,
+    end: Point<Pixels>,
+    size: Size<Pixels>,
+    axis: BrushAxis,
+) -> Option<BrushSelection> {
+    let range = |a: Pixels, b: Pixels, max: Pixels| -> Option<Range<f32>> {
+        let (a, b) = (a.clamp(px(0.), max), b.clamp(px(0.), max));
+        let (start, end) = if a <= b { (a, b) } else { (b, a) };
+        if end - start < MIN_BRUSH_SIZE {
+            return None;
+        }
+        Some(start.as_f32()..end.as_f32())
+    };
+
+    let x = range(start.x, end.x, size.width)?;
+    let y = match axis {
+        BrushAxis::X => None,
+        BrushAxis::XY => Some(range(start.y, end.y, size.height)?),
+    };
+    Some(BrushSelection { x, y })
+}
+
+/// An overlay to select a range of a plot by dragging, render it above the plot.
+///
+/// ```ignore
+/// let brush = cx.new(|_| BrushState::new());
+/// cx.subscribe(&brush, |this, _, event: &BrushEvent, cx| {
+///     let BrushEvent::BrushChanged(selection) = event;
+///     // Filter the linked views by the selection.
+/// })
+/// .detach();
+///
+/// div()
+///     .relative()
+///     .size_full()
+///     .child(chart)
+///     .child(Brush::new(&brush).absolute().inset_0())
+/// ```
+#[derive(IntoElement)]
+pub struct Brush {
+    state: Entity<BrushState>,
+    style: StyleRefinement,
+}
+
+impl Brush {
+    /// Create a new [`Brush`] element bind to the [`BrushState`].
+    pub fn new(state: &Entity<BrushState>) -> Self {
+        Self {
+            state: state.clone(),
+            style: StyleRefinement::default(),
+        }
+    }
+}
+
+impl Styled for Brush {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for Brush {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let selection = self.state.read(cx).selection.clone();
+
+        div()
+            .relative()
+            .size_full()
+            .cursor_crosshair()
+            .refine_style(&self.style)
+            .on_mouse_down(
+                MouseButton::Left,
+                window.listener_for(&self.state, BrushState::on_mouse_down),
+            )
+            .on_mouse_move(window.listener_for(&self.state, BrushState::on_mouse_move))
+            .on_mouse_up(
+                MouseButton::Left,
+                window.listener_for(&self.state, BrushState::on_mouse_up),
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                window.listener_for(&self.state, BrushState::on_mouse_up),
+            )
+            .when_some(selection, |this, selection| {
+                this.child(
+                    div()
+                        .absolute()
+                        .left(px(selection.x.start))
+                        .w(px(selection.x.end - selection.x.start))
+                        .map(|this| match selection.y {
+                            Some(y) => this.top(px(y.start)).h(px(y.end - y.start)),
+                            None => this.top_0().bottom_0(),
+                        })
+                        .bg(cx.theme().primary.opacity(0.1))
+                        .border_1()
+                        .border_color(cx.theme().primary.opacity(0.5)),
+                )
+            })
+            .on_prepaint({
+                let state = self.state.clone();
+                move |bounds, _, cx| state.update(cx, |state, _| state.bounds = bounds)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size};
+
+    use super::{BrushAxis, BrushSelection, brush_selection};
+
+    #[test]
+    fn test_brush_selection() {
+        let size = size(px(100.), px(50.));
+
+        assert_eq!(
+            brush_selection(
+                point(px(60.), px(10.)),
+                point(px(20.), px(40.)),
+                size,
+                BrushAxis::X
+            ),
+            Some(BrushSelection {
+                x: 20.0..60.0,
+                y: None
+            })
+        );
+        assert_eq!(
+            brush_selection(
+                point(px(20.), px(40.)),
+                point(px(150.), px(-10.)),
+                size,
+                BrushAxis::XY
+            ),
+            Some(BrushSelection {
+                x: 20.0..100.0,
+                y: Some(0.0..40.0)
+            })
+        );
+
+        // Too small to select.
+        assert_eq!(
+            brush_selection(
+                point(px(20.), px(10.)),
+                point(px(21.), px(40.)),
+                size,
+                BrushAxis::X
+            ),
+            None
+        );
+        assert_eq!(
+            brush_selection(
+                point(px(20.), px(10.)),
+                point(px(60.), px(11.)),
+                size,
+                BrushAxis::XY
+            ),
+            None
+        );
+    }
+}
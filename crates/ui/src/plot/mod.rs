@@ -1,4 +1,5 @@
 mod axis;
+pub mod brush;
 mod grid;
 pub mod label;
 pub mod scale;
@@ -3,7 +3,7 @@
 use itertools::Itertools;
 use num_traits::{Num, ToPrimitive};
 
-use super::{sealed::Sealed, Scale};
+use super::{Scale, sealed::Sealed};
 
 #[derive(Clone)]
 pub struct ScaleLinear<T> {
@@ -49,6 +49,18 @@ where
             range_diff: range_end - range_start,
         }
     }
+
+    /// Get the domain value of the tick, the inverse of [`Scale::tick`].
+    ///
+    /// e.g.: map the position of a [`Brush`](crate::plot::brush::Brush) selection to the data.
+    pub fn invert(&self, tick: f32) -> Option<f64> {
+        if self.range_diff == 0. {
+            return None;
+        }
+
+        let ratio = ((tick - self.range_start) / self.range_diff) as f64;
+        Some(self.domain_start.to_f64()? + ratio * self.domain_diff.to_f64()?)
+    }
 }
 
 impl<T> Scale<T> for ScaleLinear<T>
@@ -101,6 +113,20 @@ mod tests {
         assert_eq!(scale.tick(&3.), Some(0.));
     }
 
+    #[test]
+    fn test_scale_linear_invert() {
+        let scale = ScaleLinear::new(vec![10., 20.], vec![0., 100.]);
+        assert_eq!(scale.invert(0.), Some(10.));
+        assert_eq!(scale.invert(50.), Some(15.));
+        assert_eq!(scale.invert(100.), Some(20.));
+
+        let scale = ScaleLinear::new(vec![10., 20.], vec![100., 0.]);
+        assert_eq!(scale.invert(25.), Some(17.5));
+
+        let scale = ScaleLinear::new(vec![10., 20.], vec![]);
+        assert_eq!(scale.invert(0.), None);
+    }
+
     #[test]
     fn test_scale_linear_multiple_range() {
         let scale = ScaleLinear::new(vec![1., 2., 3.], vec![0., 50., 100.]);
@@ -412,6 +412,8 @@ impl RenderOnce for Tooltip {
                 this.absolute()
                     .min_w(px(150.))
                     .popover_style(cx)
+                    .bg(cx.theme().tokens.chart_tooltip)
+                    .text_color(cx.theme().chart_tooltip_foreground)
                     .p_2()
                     .map(|c| {
                         if cursor.x < within.width * 0.5 {
@@ -1,9 +1,11 @@
 use gpui::{
-    Anchor, AnyElement, App, Bounds, Context, Deferred, DismissEvent, Div, ElementId,
-    EventEmitter, FocusHandle, Focusable, InteractiveElement as _, IntoElement, KeyBinding,
-    MouseButton, ParentElement, Pixels, Point, Render, RenderOnce, Stateful, StyleRefinement,
-    Styled, Subscription, Window, anchored, deferred, div, prelude::FluentBuilder as _, px,
+    Anchor, AnyElement, App, Bounds, Context, Deferred, DismissEvent, Div, ElementId, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement as _, IntoElement, KeyBinding, MouseButton,
+    ParentElement, Pixels, Point, Render, RenderOnce, Stateful, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Subscription, Task, Window, anchored, deferred, div,
+    prelude::FluentBuilder as _, px,
 };
+use instant::Duration;
 use std::{cell::Cell, rc::Rc};
 
 use crate::{
@@ -15,6 +17,27 @@ pub(crate) fn init(cx: &mut App) {
     cx.bind_keys([KeyBinding::new("escape", Cancel, Some(CONTEXT))])
 }
 
+/// The interaction that opens a [`Popover`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PopoverTriggerMode {
+    /// Toggle the popover by clicking the trigger, default.
+    #[default]
+    Click,
+    /// Open the popover when hovering the trigger.
+    ///
+    /// Moving the cursor from the trigger towards the content keeps the popover
+    /// open, even if the cursor passes over the gap between them.
+    Hover,
+}
+
+impl PopoverTriggerMode {
+    /// Returns true if the popover is opened by hovering the trigger.
+    #[inline]
+    pub fn is_hover(&self) -> bool {
+        matches!(self, Self::Hover)
+    }
+}
+
 /// A popover element that can be triggered by a button or any other element.
 #[derive(IntoElement)]
 pub struct Popover {
@@ -36,6 +59,9 @@ pub struct Popover {
     /// This is used for hotfix the trigger element style to support w_full.
     trigger_style: Option<StyleRefinement>,
     mouse_button: MouseButton,
+    trigger_mode: PopoverTriggerMode,
+    open_delay: Duration,
+    close_delay: Duration,
     appearance: bool,
     overlay_closable: bool,
     on_open_change: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
@@ -54,6 +80,9 @@ impl Popover {
             tracked_focus_handle: None,
             children: vec![],
             mouse_button: MouseButton::Left,
+            trigger_mode: PopoverTriggerMode::Click,
+            open_delay: Duration::from_millis(300),
+            close_delay: Duration::from_millis(200),
             appearance: true,
             overlay_closable: true,
             default_open: false,
@@ -79,6 +108,25 @@ impl Popover {
         self
     }
 
+    /// Set the interaction that opens the popover, default is [`PopoverTriggerMode::Click`].
+    pub fn trigger_mode(mut self, mode: PopoverTriggerMode) -> Self {
+        self.trigger_mode = mode;
+        self
+    }
+
+    /// Set the delay before opening the popover in hover mode, default is 300ms.
+    pub fn open_delay(mut self, duration: Duration) -> Self {
+        self.open_delay = duration;
+        self
+    }
+
+    /// Set the delay before closing the popover after the cursor leaves it in hover mode,
+    /// default is 200ms.
+    pub fn close_delay(mut self, duration: Duration) -> Self {
+        self.close_delay = duration;
+        self
+    }
+
     /// Set the trigger element of the popover.
     pub fn trigger<T>(mut self, trigger: T) -> Self
     where
@@ -212,9 +260,22 @@ pub struct PopoverState {
     previous_focus_handle: Option<FocusHandle>,
     trigger_bounds: Bounds<Pixels>,
     trigger_bounds_captured: bool,
+    content_bounds: Bounds<Pixels>,
     open: bool,
     on_open_change: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
 
+    // Hover mode
+    trigger_mode: PopoverTriggerMode,
+    open_delay: Duration,
+    close_delay: Duration,
+    hovering_trigger: bool,
+    hovering_content: bool,
+    /// The cursor position where it left the trigger, the apex of the safe triangle.
+    exit_point: Option<Point<Pixels>>,
+    last_mouse_position: Option<Point<Pixels>>,
+    epoch: usize,
+    _hover_task: Option<Task<()>>,
+
     _dismiss_subscription: Option<Subscription>,
 }
 
@@ -226,8 +287,18 @@ impl PopoverState {
             previous_focus_handle: None,
             trigger_bounds: Bounds::default(),
             trigger_bounds_captured: false,
+            content_bounds: Bounds::default(),
             open: default_open,
             on_open_change: None,
+            trigger_mode: PopoverTriggerMode::Click,
+            open_delay: Duration::ZERO,
+            close_delay: Duration::ZERO,
+            hovering_trigger: false,
+            hovering_content: false,
+            exit_point: None,
+            last_mouse_position: None,
+            epoch: 0,
+            _hover_task: None,
             _dismiss_subscription: None,
         }
     }
@@ -260,22 +331,41 @@ impl PopoverState {
         }
     }
 
+    /// Toggle the open state of the popover.
+    pub fn toggle(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.toggle_open(window, cx);
+    }
+
     fn toggle_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let opening = !self.open;
-        if opening {
+        self.update_open(!self.open, window, cx);
+
+        if let Some(callback) = self.on_open_change.as_ref() {
+            callback(&self.open, window, cx);
+        }
+        cx.notify();
+    }
+
+    /// Apply the open state with focus management, without emitting `on_open_change`.
+    fn update_open(&mut self, open: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.cancel_hover_task();
+        // Hover popovers are transient, they should not steal the focus from the trigger.
+        let move_focus = !self.trigger_mode.is_hover();
+        if open && move_focus {
             // Save the focused element before opening, so we can restore it on close.
             self.previous_focus_handle = window.focused(cx);
         }
-        self.set_open(opening, cx);
+        self.set_open(open, cx);
         if self.open {
             let state = cx.entity();
-            let focus_handle = if let Some(tracked_focus_handle) = self.tracked_focus_handle.clone()
-            {
-                tracked_focus_handle
-            } else {
-                self.focus_handle.clone()
-            };
-            focus_handle.focus(window, cx);
+            if move_focus {
+                let focus_handle =
+                    if let Some(tracked_focus_handle) = self.tracked_focus_handle.clone() {
+                        tracked_focus_handle
+                    } else {
+                        self.focus_handle.clone()
+                    };
+                focus_handle.focus(window, cx);
+            }
 
             self._dismiss_subscription =
                 Some(
@@ -288,6 +378,9 @@ impl PopoverState {
                 );
         } else {
             self._dismiss_subscription = None;
+            self.hovering_trigger = false;
+            self.hovering_content = false;
+            self.exit_point = None;
             // Restore focus to the element that was focused before the popover opened.
             if let Some(prev) = self.previous_focus_handle.take() {
                 if self.focus_handle.contains_focused(window, cx) {
@@ -295,16 +388,144 @@ impl PopoverState {
                 }
             }
         }
-
-        if let Some(callback) = self.on_open_change.as_ref() {
-            callback(&self.open, window, cx);
-        }
-        cx.notify();
     }
 
     fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
         self.dismiss(window, cx);
     }
+
+    fn on_mouse_down_out(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Let the nested popover (e.g. a Select opened inside the content) dismiss first,
+        // the click may be inside it, which is outside of this popover's content.
+        if self.open && !GlobalState::global(cx).is_topmost_deferred_popover(&self.focus_handle) {
+            return;
+        }
+
+        self.dismiss(window, cx);
+    }
+
+    fn cancel_hover_task(&mut self) {
+        self.epoch += 1;
+        self._hover_task = None;
+    }
+
+    fn on_trigger_hover(&mut self, hovered: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.hovering_trigger = hovered;
+        if hovered {
+            self.exit_point = None;
+            if self.open {
+                self.cancel_hover_task();
+            } else {
+                self.schedule_hover_open(window, cx);
+            }
+        } else if self.open {
+            self.exit_point = Some(window.mouse_position());
+            self.last_mouse_position = self.exit_point;
+            self.schedule_hover_close(window, cx);
+        } else {
+            self.cancel_hover_task();
+        }
+    }
+
+    fn on_content_hover(&mut self, hovered: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.hovering_content = hovered;
+        if hovered {
+            self.exit_point = None;
+            self.cancel_hover_task();
+        } else if !self.hovering_trigger {
+            self.schedule_hover_close(window, cx);
+        }
+    }
+
+    fn schedule_hover_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.cancel_hover_task();
+        let epoch = self.epoch;
+        let delay = self.open_delay;
+
+        self._hover_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            let _ = this.update_in(cx, |state, window, cx| {
+                if state.epoch != epoch || !state.hovering_trigger || state.open {
+                    return;
+                }
+
+                state.toggle_open(window, cx);
+            });
+        }));
+    }
+
+    fn schedule_hover_close(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.cancel_hover_task();
+        let epoch = self.epoch;
+        let delay = self.close_delay;
+
+        self._hover_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            let _ = this.update_in(cx, |state, window, cx| {
+                if state.epoch != epoch
+                    || !state.open
+                    || state.hovering_trigger
+                    || state.hovering_content
+                {
+                    return;
+                }
+
+                // Keep the popover open while the cursor is still moving inside the
+                // safe triangle from the exit point towards the content.
+                let mouse_position = window.mouse_position();
+                if let Some(exit_point) = state.exit_point {
+                    let moving = state.last_mouse_position != Some(mouse_position);
+                    state.last_mouse_position = Some(mouse_position);
+                    if moving
+                        && is_in_safe_triangle(exit_point, state.content_bounds, mouse_position)
+                    {
+                        state.schedule_hover_close(window, cx);
+                        return;
+                    }
+                }
+
+                state.toggle_open(window, cx);
+            });
+        }));
+    }
+}
+
+/// Returns true if the `point` is inside the triangle formed by the `apex` (where the
+/// cursor left the trigger) and the nearest edge of the `target` bounds.
+///
+/// This is the "safe polygon" used to keep a hover popover open while the cursor is
+/// travelling diagonally from the trigger to the content.
+pub(crate) fn is_in_safe_triangle(
+    apex: Point<Pixels>,
+    target: Bounds<Pixels>,
+    point: Point<Pixels>,
+) -> bool {
+    if target.contains(&point) {
+        return true;
+    }
+
+    let (a, b) = if apex.y <= target.top() {
+        (target.origin, target.top_right())
+    } else if apex.y >= target.bottom() {
+        (target.bottom_left(), target.bottom_right())
+    } else if apex.x <= target.left() {
+        (target.origin, target.bottom_left())
+    } else {
+        (target.top_right(), target.bottom_right())
+    };
+
+    fn sign(p1: Point<Pixels>, p2: Point<Pixels>, p3: Point<Pixels>) -> f32 {
+        f32::from(p1.x - p3.x) * f32::from(p2.y - p3.y)
+            - f32::from(p2.x - p3.x) * f32::from(p1.y - p3.y)
+    }
+
+    let d1 = sign(point, apex, a);
+    let d2 = sign(point, a, b);
+    let d3 = sign(point, b, apex);
+    let has_neg = d1 < 0. || d2 < 0. || d3 < 0.;
+    let has_pos = d1 > 0. || d2 > 0. || d3 > 0.;
+
+    !(has_neg && has_pos)
 }
 
 impl Focusable for PopoverState {
@@ -375,8 +596,16 @@ impl RenderOnce for Popover {
                 state.tracked_focus_handle = Some(tracked_focus_handle);
             }
             state.on_open_change = self.on_open_change.clone();
+            state.trigger_mode = self.trigger_mode;
+            state.open_delay = self.open_delay;
+            state.close_delay = self.close_delay;
             if let Some(force_open) = force_open {
-                state.set_open(force_open, cx);
+                if state.open != force_open {
+                    // Opened or closed programmatically by the controlled `open` value.
+                    state.update_open(force_open, window, cx);
+                } else {
+                    state.set_open(force_open, cx);
+                }
             }
         });
 
@@ -398,21 +627,29 @@ impl RenderOnce for Popover {
             trigger_bounds,
         )));
 
+        let hover_mode = self.trigger_mode.is_hover();
         let el = div()
             .id(self.id)
             .child((trigger)(open, window, cx))
-            .on_mouse_down(self.mouse_button, {
-                let state = state.clone();
-                move |_, window, cx| {
-                    cx.stop_propagation();
-                    state.update(cx, |state, cx| {
-                        // We force set open to false to toggle it correctly.
-                        // Because if the mouse down out will toggle open first.
-                        state.set_open(open, cx);
-                        state.toggle_open(window, cx);
-                    });
-                    cx.notify(parent_view_id);
-                }
+            .when(!hover_mode, |this| {
+                this.on_mouse_down(self.mouse_button, {
+                    let state = state.clone();
+                    move |_, window, cx| {
+                        cx.stop_propagation();
+                        state.update(cx, |state, cx| {
+                            // We force set open to false to toggle it correctly.
+                            // Because if the mouse down out will toggle open first.
+                            state.set_open(open, cx);
+                            state.toggle_open(window, cx);
+                        });
+                        cx.notify(parent_view_id);
+                    }
+                })
+            })
+            .when(hover_mode, |this| {
+                this.on_hover(window.listener_for(&state, |state, hovered, window, cx| {
+                    state.on_trigger_hover(*hovered, window, cx);
+                }))
             })
             .on_prepaint({
                 let state = state.clone();
@@ -452,12 +689,23 @@ impl RenderOnce for Popover {
                         let state = state.clone();
                         move |_, window, cx| {
                             state.update(cx, |state, cx| {
-                                state.dismiss(window, cx);
+                                state.on_mouse_down_out(window, cx);
                             });
                             cx.notify(parent_view_id);
                         }
                     })
                 })
+                .when(hover_mode, |this| {
+                    this.on_hover(window.listener_for(&state, |state, hovered, window, cx| {
+                        state.on_content_hover(*hovered, window, cx);
+                    }))
+                })
+                .on_prepaint({
+                    let state = state.clone();
+                    move |bounds, _, cx| {
+                        state.update(cx, |state, _| state.content_bounds = bounds);
+                    }
+                })
                 .refine_style(&self.style);
 
         el.child(Self::render_popover(
@@ -482,13 +730,59 @@ mod tests {
             .mouse_button(MouseButton::Right)
             .default_open(true)
             .appearance(false)
-            .overlay_closable(false);
+            .overlay_closable(false)
+            .trigger_mode(PopoverTriggerMode::Hover)
+            .open_delay(Duration::from_millis(100))
+            .close_delay(Duration::from_millis(50));
 
         assert_eq!(popover.anchor, Anchor::BottomCenter);
         assert_eq!(popover.mouse_button, MouseButton::Right);
         assert!(popover.default_open);
         assert!(!popover.appearance);
         assert!(!popover.overlay_closable);
+        assert_eq!(popover.trigger_mode, PopoverTriggerMode::Hover);
+        assert_eq!(popover.open_delay, Duration::from_millis(100));
+        assert_eq!(popover.close_delay, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_is_in_safe_triangle() {
+        use gpui::{point, px, size};
+
+        // Content below the trigger, cursor left the trigger at (100, 100).
+        let content = Bounds::new(point(px(50.), px(120.)), size(px(200.), px(100.)));
+        let apex = point(px(100.), px(100.));
+
+        // Inside the content.
+        assert!(is_in_safe_triangle(apex, content, point(px(60.), px(150.))));
+        // In the gap, moving diagonally towards the content.
+        assert!(is_in_safe_triangle(
+            apex,
+            content,
+            point(px(120.), px(110.))
+        ));
+        assert!(is_in_safe_triangle(apex, content, point(px(70.), px(115.))));
+        // Moving away from the content.
+        assert!(!is_in_safe_triangle(
+            apex,
+            content,
+            point(px(100.), px(90.))
+        ));
+        assert!(!is_in_safe_triangle(
+            apex,
+            content,
+            point(px(10.), px(110.))
+        ));
+
+        // Content on the right side of the trigger.
+        let content = Bounds::new(point(px(200.), px(0.)), size(px(100.), px(200.)));
+        let apex = point(px(180.), px(100.));
+        assert!(is_in_safe_triangle(apex, content, point(px(190.), px(60.))));
+        assert!(!is_in_safe_triangle(
+            apex,
+            content,
+            point(px(170.), px(100.))
+        ));
     }
 
     #[test]
@@ -1,8 +1,10 @@
 use std::ops::Range;
 
+use anyhow::Result;
 use gpui::{
     Along, App, Axis, Bounds, Context, ElementId, EventEmitter, IsZero, Pixels, Window, px,
 };
+use serde::{Deserialize, Serialize};
 
 mod panel;
 mod resize_handle;
@@ -35,6 +37,15 @@ pub struct ResizableState {
     sizes: Vec<Pixels>,
     pub(crate) resizing_panel_ix: Option<usize>,
     bounds: Bounds<Pixels>,
+    /// The fractions to apply once the panels and the container size are known.
+    pending_fractions: Option<Vec<f32>>,
+}
+
+/// The serialized [`ResizableState`], see [`ResizableState::to_json`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ResizableStateJson {
+    /// The panel sizes as fractions of the container size.
+    fractions: Vec<f32>,
 }
 
 impl Default for ResizableState {
@@ -45,6 +56,7 @@ impl Default for ResizableState {
             sizes: vec![],
             resizing_panel_ix: None,
             bounds: Bounds::default(),
+            pending_fractions: None,
         }
     }
 }
@@ -55,6 +67,48 @@ impl ResizableState {
         &self.sizes
     }
 
+    /// Get the size of the panels as fractions of the container size, the sum is `1.0`.
+    pub fn fractions(&self) -> Vec<f32> {
+        if let Some(fractions) = &self.pending_fractions {
+            return fractions.clone();
+        }
+
+        normalize_fractions(self.sizes.iter().map(|size| size.as_f32()).collect())
+    }
+
+    /// Set the size of the panels as fractions of the container size, e.g. `[0.25, 0.75]`.
+    ///
+    /// The fractions are normalized to sum up to `1.0`. If the panels are not rendered yet,
+    /// they are applied on the first layout, so the panels keep the same proportions
+    /// whatever the container size is.
+    pub fn set_fractions(&mut self, fractions: Vec<f32>, cx: &mut Context<Self>) {
+        self.pending_fractions = Some(normalize_fractions(fractions));
+        self.adjust_to_container_size(cx);
+    }
+
+    /// Serialize the panel sizes as fractions of the container, to restore by [`Self::from_json`].
+    ///
+    /// This can be persisted alongside the [`crate::dock::DockAreaState`].
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(ResizableStateJson {
+            fractions: self.fractions(),
+        })
+        .unwrap_or_default()
+    }
+
+    /// Create a state from the JSON by [`Self::to_json`].
+    ///
+    /// ```ignore
+    /// let state = cx.new(|_| ResizableState::from_json(json).unwrap_or_default());
+    /// ```
+    pub fn from_json(json: serde_json::Value) -> Result<Self> {
+        let json: ResizableStateJson = serde_json::from_value(json)?;
+        Ok(Self {
+            pending_fractions: Some(normalize_fractions(json.fractions)),
+            ..Default::default()
+        })
+    }
+
     /// Programmatically resize the panel at `ix` to `size`, redistributing
     /// space among siblings using the same logic as a drag.
     ///
@@ -100,11 +154,15 @@ impl ResizableState {
         let size = size.unwrap_or(PANEL_MIN_SIZE);
 
         // We make sure that the size always sums up to the container size
-        // by reducing the size of all other panels first.
-        let container_size = self.container_size().max(px(1.));
+        // by reducing the size of all other unlocked panels first.
+        let container_size = (self.container_size() - self.locked_size()).max(px(1.));
         let total_leftover_size = (container_size - size).max(px(1.));
 
         for (i, panel) in self.panels.iter_mut().enumerate() {
+            if panel.locked {
+                continue;
+            }
+
             let ratio = self.sizes[i] / container_size;
             self.sizes[i] = total_leftover_size * ratio;
             panel.size = Some(self.sizes[i]);
@@ -188,12 +246,38 @@ impl ResizableState {
         cx: &mut Context<Self>,
     ) {
         let old_size = self.sizes[panel_ix];
+        let locked = self.panels[panel_ix].locked;
 
-        self.panels[panel_ix] = panel;
+        self.panels[panel_ix] = ResizablePanelState { locked, ..panel };
         self.sizes[panel_ix] = old_size;
         self.adjust_to_container_size(cx);
     }
 
+    /// Lock the size of the panel at `ix`, the locked panel keeps its size when the panels
+    /// are added, removed or the container is resized, and it can't be resized by dragging.
+    pub(crate) fn set_locked(&mut self, ix: usize, locked: bool, cx: &mut Context<Self>) {
+        if let Some(panel) = self.panels.get_mut(ix) {
+            panel.locked = locked;
+            cx.notify();
+        }
+    }
+
+    /// Returns true if the size of the panel at `ix` is locked.
+    pub(crate) fn is_locked(&self, ix: usize) -> bool {
+        self.panels.get(ix).is_some_and(|panel| panel.locked)
+    }
+
+    /// The total size of the locked panels.
+    fn locked_size(&self) -> Pixels {
+        self.panels
+            .iter()
+            .zip(self.sizes.iter())
+            .filter(|(panel, _)| panel.locked)
+            .map(|(_, size)| size.as_f32())
+            .sum::<f32>()
+            .into()
+    }
+
     pub(crate) fn clear(&mut self) {
         self.panels.clear();
         self.sizes.clear();
@@ -213,6 +297,9 @@ impl ResizableState {
         let Some(panel) = self.panels.get(ix) else {
             return PANEL_MIN_SIZE..Pixels::MAX;
         };
+        if panel.locked {
+            return self.sizes[ix]..self.sizes[ix];
+        }
 
         panel.size_range.clone()
     }
@@ -243,6 +330,10 @@ impl ResizableState {
         if ix >= old_sizes.len() - 1 {
             return;
         }
+        // The locked panels beside the handle can't be resized.
+        if self.is_locked(ix) || self.is_locked(ix + 1) {
+            return;
+        }
         let container_size = self.container_size();
         self.sync_real_panel_sizes(cx);
 
@@ -310,13 +401,40 @@ impl ResizableState {
         }
 
         let container_size = self.container_size();
-        let total = self.sizes.iter().map(|s| s.as_f32()).sum::<f32>();
+
+        // Apply the fractions by `set_fractions` or `from_json`, once the panels are synced.
+        if let Some(fractions) = self
+            .pending_fractions
+            .take_if(|fractions| fractions.len() == self.panels.len())
+        {
+            for (i, fraction) in fractions.into_iter().enumerate() {
+                let new_size = container_size * fraction;
+                self.sizes[i] = new_size;
+                self.panels[i].size = Some(new_size);
+            }
+            cx.notify();
+            return;
+        }
+
+        // The locked panels keep their sizes, the others share the rest of the container.
+        let total = self
+            .panels
+            .iter()
+            .zip(self.sizes.iter())
+            .filter(|(panel, _)| !panel.locked)
+            .map(|(_, size)| size.as_f32())
+            .sum::<f32>();
         if !total.is_finite() || total <= 0. {
             return;
         }
         let total_size = px(total);
+        let container_size = (container_size - self.locked_size()).max(px(0.));
 
         for i in 0..self.panels.len() {
+            if self.panels[i].locked {
+                continue;
+            }
+
             let size = self.sizes[i];
             let ratio = size / total_size;
             let new_size = container_size * ratio;
@@ -330,9 +448,87 @@ impl ResizableState {
 
 impl EventEmitter<ResizablePanelEvent> for ResizableState {}
 
+/// Normalize the fractions to sum up to `1.0`, the invalid values are treated as `0.0`.
+fn normalize_fractions(fractions: Vec<f32>) -> Vec<f32> {
+    let fractions = fractions
+        .into_iter()
+        .map(|f| if f.is_finite() { f.max(0.) } else { 0. })
+        .collect::<Vec<_>>();
+    let total = fractions.iter().sum::<f32>();
+    if total <= 0. {
+        let len = fractions.len();
+        return vec![1. / len as f32; len];
+    }
+
+    fractions.into_iter().map(|f| f / total).collect()
+}
+
 #[derive(Debug, Clone, Default)]
 pub(crate) struct ResizablePanelState {
     pub size: Option<Pixels>,
     pub size_range: Range<Pixels>,
+    /// Whether the size is locked, see [`ResizableState::set_locked`].
+    pub locked: bool,
     bounds: Bounds<Pixels>,
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{AppContext as _, Bounds, TestAppContext, point, px, size};
+
+    use super::{ResizablePanelState, ResizableState, normalize_fractions};
+
+    #[test]
+    fn test_normalize_fractions() {
+        assert_eq!(normalize_fractions(vec![]), Vec::<f32>::new());
+        assert_eq!(normalize_fractions(vec![0.25, 0.75]), vec![0.25, 0.75]);
+        assert_eq!(normalize_fractions(vec![1., 3.]), vec![0.25, 0.75]);
+        assert_eq!(normalize_fractions(vec![0., 0.]), vec![0.5, 0.5]);
+        assert_eq!(
+            normalize_fractions(vec![f32::NAN, -1., 2.]),
+            vec![0., 0., 1.]
+        );
+    }
+
+    #[gpui::test]
+    fn test_locked_size(cx: &mut TestAppContext) {
+        let state = cx.new(|_| {
+            let mut state = ResizableState {
+                bounds: Bounds::new(point(px(0.), px(0.)), size(px(300.), px(100.))),
+                ..Default::default()
+            };
+            state.panels = vec![ResizablePanelState::default(); 3];
+            state.sizes = vec![px(100.); 3];
+            state
+        });
+
+        state.update(cx, |state, cx| {
+            state.set_locked(0, true, cx);
+            assert!(state.is_locked(0));
+            assert!(!state.is_locked(1));
+
+            state.remove_panel(2, cx);
+            assert_eq!(state.sizes(), &vec![px(100.), px(200.)]);
+
+            state.insert_panel(Some(px(100.)), None, cx);
+            assert_eq!(state.sizes(), &vec![px(100.), px(100.), px(100.)]);
+
+            state.set_locked(0, false, cx);
+            state.remove_panel(2, cx);
+            assert_eq!(state.sizes(), &vec![px(150.), px(150.)]);
+        });
+    }
+
+    #[test]
+    fn test_json() {
+        let json = serde_json::json!({ "fractions": [1., 2., 1.] });
+        let state = ResizableState::from_json(json).unwrap();
+        assert_eq!(state.fractions(), vec![0.25, 0.5, 0.25]);
+        assert_eq!(
+            state.to_json(),
+            serde_json::json!({ "fractions": [0.25, 0.5, 0.25] })
+        );
+
+        assert!(ResizableState::from_json(serde_json::json!({ "sizes": [] })).is_err());
+    }
+}
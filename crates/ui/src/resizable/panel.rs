@@ -7,7 +7,7 @@ use gpui::{
     Along, AnyElement, App, AppContext, Axis, Bounds, Context, Element, ElementId, Empty, Entity,
     EventEmitter, InteractiveElement as _, IntoElement, IsZero as _, MouseMoveEvent, MouseUpEvent,
     ParentElement, Pixels, Render, RenderOnce, Style, StyleRefinement, Styled, Window, div,
-    prelude::FluentBuilder,
+    prelude::FluentBuilder, relative,
 };
 
 use crate::{
@@ -213,6 +213,8 @@ pub struct ResizablePanel {
     state: Option<Entity<ResizableState>>,
     /// Initial size is the size that the panel has when it is created.
     initial_size: Option<Pixels>,
+    /// Initial size as a fraction of the group size, exclusive with `initial_size`.
+    initial_fraction: Option<f32>,
     /// size range limit of this panel.
     size_range: Range<Pixels>,
     children: Vec<AnyElement>,
@@ -226,6 +228,7 @@ impl ResizablePanel {
         Self {
             panel_ix: 0,
             initial_size: None,
+            initial_fraction: None,
             state: None,
             size_range: (PANEL_MIN_SIZE..Pixels::MAX),
             axis: Axis::Horizontal,
@@ -244,6 +247,17 @@ impl ResizablePanel {
     /// Set the initial size of the panel.
     pub fn size(mut self, size: impl Into<Pixels>) -> Self {
         self.initial_size = Some(size.into());
+        self.initial_fraction = None;
+        self
+    }
+
+    /// Set the initial size of the panel as a fraction of the group size, e.g. `0.25` for 25%.
+    ///
+    /// Unlike [`Self::size`], the panel keeps the same proportion when the group is
+    /// rendered in a different size.
+    pub fn size_fraction(mut self, fraction: f32) -> Self {
+        self.initial_fraction = Some(fraction.clamp(0., 1.));
+        self.initial_size = None;
         self
     }
 
@@ -307,7 +321,15 @@ impl RenderOnce for ResizablePanel {
             // 1. initial_size is None, to use auto size.
             // 2. initial_size is Some and size is none, to use the initial size of the panel for first time render.
             // 3. initial_size is Some and size is Some, use `size`.
-            .when(self.initial_size.is_none(), |this| this.flex_shrink_1())
+            // 4. initial_fraction is Some, same as initial_size but relative to the group size.
+            .when(
+                self.initial_size.is_none() && self.initial_fraction.is_none(),
+                |this| this.flex_shrink_1(),
+            )
+            .when_some(self.initial_fraction, |this, fraction| {
+                this.when(panel_state.size.is_none(), |this| this.flex_none())
+                    .flex_basis(relative(fraction))
+            })
             .when_some(self.initial_size, |this, initial_size| {
                 // The `self.size` is None, that mean the initial size for the panel,
                 // so we need set `flex_shrink_0` To let it keep the initial size.
@@ -1,10 +1,11 @@
 use crate::{
-    ActiveTheme, ElementExt, Placement, StyledExt,
+    ActiveTheme, ElementExt, Placement, ResetZoom, StyledExt, Theme, ZoomIn, ZoomOut,
     dialog::{ANIMATION_DURATION, Dialog},
     focus_trap::FocusTrapManager,
     input::{Copy, InputState},
     native_menu::FallbackMenuOverlay,
     notification::{Notification, NotificationList},
+    notifier::GlobalNotifier,
     sheet::Sheet,
     text::{SelectionScope, TextSelectionController, TextViewState, WindowTextSelection},
     tooltip::TooltipOverlay,
@@ -20,7 +21,7 @@ use std::{any::TypeId, collections::HashMap, rc::Rc};
 
 actions!(root, [Tab, TabPrev]);
 
-const CONTEXT: &str = "Root";
+pub(crate) const CONTEXT: &str = "Root";
 pub(crate) fn init(cx: &mut App) {
     cx.bind_keys([
         KeyBinding::new("tab", Tab, Some(CONTEXT)),
@@ -96,6 +97,7 @@ impl Root {
     pub fn new(view: impl Into<AnyView>, window: &mut Window, cx: &mut Context<Self>) -> Self {
         #[cfg(all(target_os = "macos", not(test)))]
         crate::macos_accessibility::install_window_hit_test_forwarder(window);
+        GlobalNotifier::flush(window, cx);
 
         Self {
             style: StyleRefinement::default(),
@@ -534,6 +536,19 @@ impl Root {
         window.focus_prev(cx);
     }
 
+    fn on_action_zoom_in(&mut self, _: &ZoomIn, _: &mut Window, cx: &mut Context<Self>) {
+        // The zoom level is global, refresh all the windows.
+        Theme::zoom_in(None, cx);
+    }
+
+    fn on_action_zoom_out(&mut self, _: &ZoomOut, _: &mut Window, cx: &mut Context<Self>) {
+        Theme::zoom_out(None, cx);
+    }
+
+    fn on_action_reset_zoom(&mut self, _: &ResetZoom, _: &mut Window, cx: &mut Context<Self>) {
+        Theme::reset_zoom(None, cx);
+    }
+
     fn on_action_copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
         let text = self.window_selected_text(cx).trim().to_string();
         if text.is_empty() {
@@ -560,6 +575,9 @@ impl Render for Root {
             .on_action(cx.listener(Self::on_action_tab))
             .on_action(cx.listener(Self::on_action_tab_prev))
             .on_action(cx.listener(Self::on_action_copy))
+            .on_action(cx.listener(Self::on_action_zoom_in))
+            .on_action(cx.listener(Self::on_action_zoom_out))
+            .on_action(cx.listener(Self::on_action_reset_zoom))
             .relative()
             .size_full()
             .font_family(cx.theme().font_family.clone())
@@ -2,7 +2,7 @@ use std::{panic::Location, rc::Rc};
 
 use crate::StyledExt;
 
-use super::{Scrollbar, ScrollbarAxis, ScrollbarHandle};
+use super::{Scrollbar, ScrollbarAxis, ScrollbarHandle, ScrollbarShow};
 use gpui::{
     App, Div, Element, ElementId, InteractiveElement, IntoElement, ParentElement, RenderOnce,
     ScrollHandle, Stateful, StatefulInteractiveElement, StyleRefinement, Styled, Window, div,
@@ -68,6 +68,7 @@ pub struct Scrollable<E: InteractiveElement + Styled + ParentElement + Element>
     id: ElementId,
     element: E,
     axis: ScrollbarAxis,
+    scrollbar_show: Option<ScrollbarShow>,
 }
 
 impl<E> Scrollable<E>
@@ -80,8 +81,17 @@ where
             id: caller_id(),
             element,
             axis: axis.into(),
+            scrollbar_show: None,
         }
     }
+
+    /// Set the scrollbar show mode of this scroll area, default is `cx.theme().scrollbar_show`.
+    ///
+    /// For example, always show the scrollbar of a long list, but auto-hide elsewhere.
+    pub fn scrollbar_show(mut self, scrollbar_show: ScrollbarShow) -> Self {
+        self.scrollbar_show = Some(scrollbar_show);
+        self
+    }
 }
 
 impl<E> Styled for Scrollable<E>
@@ -163,6 +173,7 @@ where
                 scrollbar_id,
                 &scroll_handle,
                 self.axis,
+                self.scrollbar_show,
                 window,
                 cx,
             ))
@@ -189,7 +200,14 @@ where
     H: ScrollbarHandle + Clone + 'static,
 {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
-        render_scrollbar(self.id, self.scroll_handle.as_ref(), self.axis, window, cx)
+        render_scrollbar(
+            self.id,
+            self.scroll_handle.as_ref(),
+            self.axis,
+            None,
+            window,
+            cx,
+        )
     }
 }
 
@@ -232,6 +250,7 @@ fn render_scrollbar<H: ScrollbarHandle + Clone>(
     id: impl Into<ElementId>,
     scroll_handle: &H,
     axis: ScrollbarAxis,
+    scrollbar_show: Option<ScrollbarShow>,
     window: &mut Window,
     cx: &mut App,
 ) -> Div {
@@ -248,7 +267,14 @@ fn render_scrollbar<H: ScrollbarHandle + Clone>(
         .left_0()
         .right_0()
         .bottom_0()
-        .child(Scrollbar::new(scroll_handle).id(id).axis(axis))
+        .child(
+            Scrollbar::new(scroll_handle)
+                .id(id)
+                .axis(axis)
+                .when_some(scrollbar_show, |this, scrollbar_show| {
+                    this.scrollbar_show(scrollbar_show)
+                }),
+        )
 }
 
 #[cfg(test)]
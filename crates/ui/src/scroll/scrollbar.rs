@@ -309,6 +309,8 @@ pub struct Scrollbar {
     scrollbar_show: Option<ScrollbarShow>,
     scroll_handle: Rc<dyn ScrollbarHandle>,
     scroll_size: Option<Size<Pixels>>,
+    /// The minimum length of the thumb, default is 48px.
+    min_thumb_size: Pixels,
     /// Maximum frames per second for scrolling by drag. Default is 120 FPS.
     ///
     /// This is used to limit the update rate of the scrollbar when it is
@@ -330,6 +332,7 @@ impl Scrollbar {
             scroll_handle: Rc::new(scroll_handle.clone()),
             max_fps: 120,
             scroll_size: None,
+            min_thumb_size: px(MIN_THUMB_SIZE),
         }
     }
 
@@ -367,6 +370,15 @@ impl Scrollbar {
         self
     }
 
+    /// Set the minimum length of the thumb, default is 48px.
+    ///
+    /// The thumb gets shorter as the content gets longer, this keeps it easy to grab
+    /// for very long content. The thumb is never longer than the track.
+    pub fn min_thumb_size(mut self, min_thumb_size: impl Into<Pixels>) -> Self {
+        self.min_thumb_size = min_thumb_size.into();
+        self
+    }
+
     /// Set scrollbar axis.
     pub fn axis(mut self, axis: impl Into<ScrollbarAxis>) -> Self {
         self.axis = axis.into();
@@ -571,8 +583,12 @@ impl Element for Scrollbar {
                 continue;
             }
 
-            let thumb_length =
-                (container_size / scroll_area_size * container_size).max(px(MIN_THUMB_SIZE));
+            let thumb_length = thumb_length(
+                container_size,
+                scroll_area_size,
+                self.min_thumb_size,
+                container_size - margin_end,
+            );
             let thumb_start = -(scroll_position / (scroll_area_size - container_size)
                 * (container_size - margin_end - thumb_length));
             let thumb_end = (thumb_start + thumb_length).min(container_size - margin_end);
@@ -842,8 +858,34 @@ impl Element for Scrollbar {
                                         state.set(state.get().with_drag_pos(axis, pos));
 
                                         cx.notify(view_id);
+                                    } else if !event.modifiers.shift && !event.modifiers.alt {
+                                        // click on the track, page toward the click position
+                                        let offset = scroll_handle.offset();
+                                        if is_vertical {
+                                            let forward = event.position.y > thumb_bounds.bottom();
+                                            scroll_handle.set_offset(point(
+                                                offset.x,
+                                                page_offset(
+                                                    offset.y,
+                                                    container_size,
+                                                    scroll_area_size,
+                                                    forward,
+                                                ),
+                                            ));
+                                        } else {
+                                            let forward = event.position.x > thumb_bounds.right();
+                                            scroll_handle.set_offset(point(
+                                                page_offset(
+                                                    offset.x,
+                                                    container_size,
+                                                    scroll_area_size,
+                                                    forward,
+                                                ),
+                                                offset.y,
+                                            ));
+                                        }
                                     } else {
-                                        // click on the scrollbar, jump to the position
+                                        // click on the track with `Shift` or `Alt`, jump to the position
                                         // Set the thumb bar center to the click position
                                         let offset = scroll_handle.offset();
                                         let percentage = if is_vertical {
@@ -980,3 +1022,59 @@ impl Element for Scrollbar {
         );
     }
 }
+
+/// Returns the length of the thumb, at least `min_size` but not longer than `max_size`.
+fn thumb_length(
+    container_size: Pixels,
+    scroll_size: Pixels,
+    min_size: Pixels,
+    max_size: Pixels,
+) -> Pixels {
+    (container_size / scroll_size * container_size)
+        .max(min_size)
+        .min(max_size)
+}
+
+/// Returns the scroll offset after paging by the container size,
+/// `forward` to scroll toward the end of the content.
+fn page_offset(
+    offset: Pixels,
+    container_size: Pixels,
+    scroll_size: Pixels,
+    forward: bool,
+) -> Pixels {
+    let max_offset = (scroll_size - container_size).max(px(0.));
+    let offset = if forward {
+        offset - container_size
+    } else {
+        offset + container_size
+    };
+    offset.clamp(-max_offset, px(0.))
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::{page_offset, thumb_length};
+
+    #[test]
+    fn test_thumb_length() {
+        assert_eq!(thumb_length(px(100.), px(200.), px(48.), px(100.)), px(50.));
+        // Very long content
+        assert_eq!(
+            thumb_length(px(100.), px(100000.), px(48.), px(100.)),
+            px(48.)
+        );
+        // The container is smaller than the minimum size
+        assert_eq!(thumb_length(px(30.), px(1000.), px(48.), px(30.)), px(30.));
+    }
+
+    #[test]
+    fn test_page_offset() {
+        assert_eq!(page_offset(px(0.), px(100.), px(250.), true), px(-100.));
+        assert_eq!(page_offset(px(-100.), px(100.), px(250.), true), px(-150.));
+        assert_eq!(page_offset(px(-150.), px(100.), px(250.), false), px(-50.));
+        assert_eq!(page_offset(px(-50.), px(100.), px(250.), false), px(0.));
+    }
+}
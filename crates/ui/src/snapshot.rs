@@ -0,0 +1,219 @@
+//! Render a view into an image, for the golden-image tests, or exporting a chart,
+//! generating the theme previews in the apps.
+//!
+//! Enable the `image-export` feature to use it, the `test-support` feature enables it too.
+//!
+//! ```ignore
+//! use gpui::{px, size};
+//! use gpui_component::{Theme, snapshot};
+//!
+//! let view = cx.new(|cx| ChartView::new(cx));
+//! let image = snapshot::render_to_image(view, size(px(400.), px(300.)), Theme::global(cx), cx)?;
+//! image.save("chart.png")?;
+//! ```
+use std::{borrow::Cow, path::Path};
+
+use anyhow::Result;
+use gpui::{
+    AnyView, App, AppContext as _, Bounds, Global, Pixels, SharedString, Size, WindowBounds,
+    WindowKind, WindowOptions, point, px,
+};
+pub use image::RgbaImage;
+
+use crate::{Root, Theme};
+
+/// The environment variable to write the rendered images to the golden files in [`assert_golden`].
+pub const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+
+/// The family of the font bundled to render the text in [`render_to_image`].
+pub const BUNDLED_FONT_FAMILY: &str = "JetBrains Mono";
+const BUNDLED_FONT: &[u8] = include_bytes!("../fonts/JetBrainsMono-Regular.ttf");
+
+/// The font family to render the images, set by [`set_font`].
+struct SnapshotFont(SharedString);
+
+impl Global for SnapshotFont {}
+
+/// Register the font to render the text in [`render_to_image`], instead of the bundled font.
+///
+/// The font is used for both the UI and monospace text.
+pub fn set_font(
+    family: impl Into<SharedString>,
+    fonts: Vec<Cow<'static, [u8]>>,
+    cx: &mut App,
+) -> Result<()> {
+    cx.text_system().add_fonts(fonts)?;
+    cx.set_global(SnapshotFont(family.into()));
+    Ok(())
+}
+
+/// Returns the font family to render the images, the bundled font is loaded at the first time.
+///
+/// The system fonts are different between the machines, so the text renders with a different
+/// fallback font, pin the font to make the images the same on all machines.
+fn snapshot_font(cx: &mut App) -> Result<SharedString> {
+    if let Some(font) = cx.try_global::<SnapshotFont>() {
+        return Ok(font.0.clone());
+    }
+
+    set_font(BUNDLED_FONT_FAMILY, vec![Cow::Borrowed(BUNDLED_FONT)], cx)?;
+    Ok(BUNDLED_FONT_FAMILY.into())
+}
+
+/// Render the view with the `theme` in an offscreen window of the `size`,
+/// and returns the pixels of the window.
+///
+/// The text uses the bundled font, or the font of [`set_font`] if registered,
+/// instead of the font of the `theme`.
+///
+/// The global [`Theme`] is replaced by the `theme` only while drawing the offscreen window,
+/// and restored before return, so the other windows never draw with it.
+pub fn render_to_image(
+    view: impl Into<AnyView>,
+    size: Size<Pixels>,
+    theme: &Theme,
+    cx: &mut App,
+) -> Result<RgbaImage> {
+    let view = view.into();
+    let family = snapshot_font(cx)?;
+
+    let mut theme = theme.clone();
+    theme.font_family = family.clone();
+    theme.mono_font_family = family;
+    let saved_theme = Theme::global(cx).clone();
+    cx.set_global(theme);
+
+    let result = (|| {
+        let window = cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(Bounds {
+                    origin: point(px(0.), px(0.)),
+                    size,
+                })),
+                kind: WindowKind::PopUp,
+                show: false,
+                focus: false,
+                ..Default::default()
+            },
+            |window, cx| cx.new(|cx| Root::new(view, window, cx)),
+        )?;
+
+        let image = window.update(cx, |_, window, cx| {
+            let _ = window.draw(cx);
+            window.render_to_image()
+        })?;
+        _ = window.update(cx, |_, window, _| window.remove_window());
+        image
+    })();
+
+    cx.set_global(saved_theme);
+    result
+}
+
+/// Assert the `image` is the same as the golden image at the `path`.
+///
+/// If the golden image does not exist, or the [`UPDATE_GOLDEN_ENV`] environment variable is set,
+/// the `image` is saved as the golden image instead.
+///
+/// Panics with the number of different pixels if not the same.
+pub fn assert_golden(image: &RgbaImage, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    if !path.exists() || std::env::var(UPDATE_GOLDEN_ENV).is_ok() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).expect("failed to create the golden image dir");
+        }
+        image.save(path).expect("failed to save the golden image");
+        return;
+    }
+
+    let golden = image::open(path)
+        .unwrap_or_else(|err| panic!("failed to open golden image {}: {}", path.display(), err))
+        .into_rgba8();
+    assert_eq!(
+        image.dimensions(),
+        golden.dimensions(),
+        "the size of the image is different from the golden image {}",
+        path.display()
+    );
+
+    let diff = diff_pixels(image, &golden);
+    assert!(
+        diff == 0,
+        "{} pixels are different from the golden image {}, set `{}=1` to update it",
+        diff,
+        path.display(),
+        UPDATE_GOLDEN_ENV
+    );
+}
+
+/// Returns the number of the pixels that are different, the images must be the same size.
+///
+/// Allow a small difference in each channel for the rounding of the GPU renderers.
+fn diff_pixels(a: &RgbaImage, b: &RgbaImage) -> usize {
+    const TOLERANCE: u8 = 2;
+
+    a.pixels()
+        .zip(b.pixels())
+        .filter(|(a, b)| {
+            a.0.iter()
+                .zip(b.0.iter())
+                .any(|(a, b)| a.abs_diff(*b) > TOLERANCE)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{
+        AppContext as _, Context, IntoElement, ParentElement as _, Render, Styled as _,
+        TestAppContext, Window, blue, div, px, red, size,
+    };
+    use image::{Rgba, RgbaImage};
+
+    use super::{assert_golden, diff_pixels, render_to_image};
+    use crate::{Theme, h_flex};
+
+    struct QuadsView;
+
+    impl Render for QuadsView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            h_flex()
+                .size_full()
+                .child(div().flex_1().h_full().bg(red()))
+                .child(div().flex_1().h_full().bg(blue()))
+        }
+    }
+
+    #[test]
+    fn test_diff_pixels() {
+        let a = RgbaImage::from_pixel(2, 2, Rgba([10, 20, 30, 255]));
+        let mut b = a.clone();
+        b.put_pixel(0, 0, Rgba([12, 20, 30, 255]));
+        assert_eq!(diff_pixels(&a, &b), 0);
+        b.put_pixel(1, 1, Rgba([10, 20, 40, 255]));
+        assert_eq!(diff_pixels(&a, &b), 1);
+    }
+
+    #[gpui::test]
+    fn test_render_to_image(cx: &mut TestAppContext) {
+        let image = cx
+            .update(|cx| {
+                crate::init(cx);
+                let saved_theme = Theme::global(cx).clone();
+                let image = render_to_image(
+                    cx.new(|_| QuadsView),
+                    size(px(8.), px(4.)),
+                    &Theme::default(),
+                    cx,
+                );
+                // The global theme is restored after rendering.
+                assert_eq!(Theme::global(cx).font_family, saved_theme.font_family);
+                image
+            })
+            .expect("failed to render the view");
+        assert_golden(
+            &image,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/snapshot_quads.png"),
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! A simple key-value storage to persist the component states, e.g. the search history.
+use std::{cell::RefCell, collections::HashMap};
+
+/// A key-value storage to persist the component states.
+///
+/// Implement this to save the states to a file, database or the browser local storage.
+/// The [`MemoryStorage`] only keeps the states in memory, for testing or the states
+/// that do not need to be persisted across app launches.
+pub trait Storage: 'static {
+    /// Read the value of the `key`, returns `None` if not exists.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Write the `value` of the `key`.
+    fn set(&self, key: &str, value: String);
+
+    /// Remove the value of the `key`.
+    fn remove(&self, key: &str);
+}
+
+/// A [`Storage`] that keeps the values in memory.
+#[derive(Default)]
+pub struct MemoryStorage {
+    values: RefCell<HashMap<String, String>>,
+}
+
+impl MemoryStorage {
+    /// Create a new empty [`MemoryStorage`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: String) {
+        self.values.borrow_mut().insert(key.to_string(), value);
+    }
+
+    fn remove(&self, key: &str) {
+        self.values.borrow_mut().remove(key);
+    }
+}
@@ -1,11 +1,13 @@
-use std::f32;
+use std::{f32, fmt, rc::Rc};
 
 use gpui::{
-    Bounds, Context, Edges, Empty, EntityId, IntoElement, ParentElement as _, Pixels, Render,
-    SharedString, Styled as _, TextAlign, Window, div, prelude::FluentBuilder, px,
+    AnyElement, App, Bounds, Context, Edges, Empty, EntityId, IntoElement, ParentElement as _,
+    Pixels, Render, SharedString, Styled as _, TextAlign, Window, div, prelude::FluentBuilder, px,
 };
 
-use crate::ActiveTheme as _;
+use serde::{Deserialize, Serialize};
+
+use crate::{ActiveTheme as _, input::group_digits};
 
 /// Represents a column in a table, used for initializing table columns.
 #[derive(Debug, Clone)]
@@ -28,10 +30,14 @@ pub struct Column {
     pub paddings: Option<Edges<Pixels>>,
     /// The width of the column.
     pub width: Pixels,
-    /// Whether the column is fixed, the fixed column will pin at the left side when scrolling horizontally.
+    /// Whether the column is fixed, the fixed column will pin at the left or right side when scrolling horizontally.
     pub fixed: Option<ColumnFixed>,
     /// Whether the column is resizable.
     pub resizable: bool,
+    /// Whether to fit the width to the content by double-clicking the resize handle.
+    ///
+    /// See [`Column::auto_fit`].
+    pub auto_fit: bool,
     /// Whether the column is movable.
     pub movable: bool,
     /// Whether the column is selectable.
@@ -48,16 +54,154 @@ pub struct Column {
     pub min_width: Pixels,
     /// The maximum width of the column.
     pub max_width: Pixels,
+    /// The number format to display the cell text, if any.
+    ///
+    /// See [`Column::number_format`].
+    pub number_format: Option<NumberFormat>,
+    /// Whether to truncate the overflowing cell text with an ellipsis.
+    ///
+    /// See [`Column::ellipsis`].
+    pub ellipsis: bool,
+    /// The custom cell renderer of the column, if any.
+    ///
+    /// See [`Column::renderer`].
+    pub renderer: Option<ColumnRenderer>,
+    /// The filter kind of the column, if any.
+    ///
+    /// See [`Column::filter`].
+    pub filter: Option<ColumnFilter>,
+}
+
+/// A cell renderer of a [`Column`], receives the `row_ix` to render the cell.
+#[derive(Clone)]
+pub struct ColumnRenderer(Rc<dyn Fn(usize, &mut Window, &mut App) -> AnyElement>);
+
+impl ColumnRenderer {
+    pub(crate) fn render(&self, row_ix: usize, window: &mut Window, cx: &mut App) -> AnyElement {
+        (self.0)(row_ix, window, cx)
+    }
+}
+
+impl fmt::Debug for ColumnRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnRenderer").finish_non_exhaustive()
+    }
+}
+
+/// The number format of a [`Column`].
+///
+/// ```rust,ignore
+/// // 1234.5 -> "$1,234.50"
+/// NumberFormat::new().precision(2).thousands_separator(true).prefix("$")
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberFormat {
+    /// The number of decimal places, `None` to keep the value as is.
+    pub precision: Option<usize>,
+    /// Whether to group the integer digits by thousands with `,`.
+    pub thousands_separator: bool,
+    /// The text before the number, e.g. `$`.
+    pub prefix: SharedString,
+    /// The text after the number, e.g. `%`.
+    pub suffix: SharedString,
+}
+
+impl NumberFormat {
+    /// Create a new number format.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of decimal places, default is None (keep the value as is).
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Set whether to group the integer digits by thousands, default is false.
+    pub fn thousands_separator(mut self, thousands_separator: bool) -> Self {
+        self.thousands_separator = thousands_separator;
+        self
+    }
+
+    /// Set the text before the number, default is empty.
+    pub fn prefix(mut self, prefix: impl Into<SharedString>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set the text after the number, default is empty.
+    pub fn suffix(mut self, suffix: impl Into<SharedString>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Format the number.
+    pub fn format(&self, value: f64) -> String {
+        let number = match self.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => value.to_string(),
+        };
+        // The sign goes before the prefix, e.g. `-$1,234.50`.
+        let (sign, number) = match number.strip_prefix('-') {
+            Some(number) => ("-", number),
+            None => ("", number.as_str()),
+        };
+
+        let number = if self.thousands_separator {
+            let (integer, fraction) = match number.find('.') {
+                Some(ix) => number.split_at(ix),
+                None => (number, ""),
+            };
+
+            format!("{}{}", group_digits(integer, ',', 3, None), fraction)
+        } else {
+            number.to_string()
+        };
+
+        format!("{}{}{}{}", sign, self.prefix, number, self.suffix)
+    }
+
+    /// Parse the text formatted by this format back to the number, e.g. `-$1,234.50` to `-1234.5`.
+    ///
+    /// The text without the prefix or suffix is parsed too, returns `None` if it is not a number.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text),
+        };
+        let text = text.strip_prefix(self.prefix.as_ref()).unwrap_or(text);
+        let text = text.strip_suffix(self.suffix.as_ref()).unwrap_or(text);
+        let value = text.trim().replace(',', "").parse::<f64>().ok()?;
+        if !value.is_finite() {
+            return None;
+        }
+
+        Some(if negative { -value } else { value })
+    }
+
+    /// Format the text if it can be parsed as a number, otherwise return it as is.
+    pub fn format_str(&self, text: &str) -> String {
+        match text.trim().parse::<f64>() {
+            Ok(value) if value.is_finite() => self.format(value),
+            _ => text.to_string(),
+        }
+    }
 }
 
 /// A column group can be used to group multiple columns under a single header.
+///
+/// See [`TableDelegate::group_headers`](crate::table::TableDelegate::group_headers).
 #[derive(Debug, Clone)]
 pub struct ColumnGroup {
     pub label: SharedString,
+    /// The number of the leaf columns spanned by the group.
     pub span: usize,
 }
 
 impl ColumnGroup {
+    /// Create a new column group spans the `span` leaf columns.
     pub fn new(label: impl Into<SharedString>, span: usize) -> Self {
         Self {
             label: label.into(),
@@ -77,10 +221,15 @@ impl Default for Column {
             width: px(100.),
             fixed: None,
             resizable: true,
+            auto_fit: false,
             movable: true,
             selectable: true,
             min_width: px(20.0),
             max_width: px(f32::MAX),
+            number_format: None,
+            ellipsis: false,
+            renderer: None,
+            filter: None,
         }
     }
 }
@@ -123,20 +272,84 @@ impl Column {
         self
     }
 
+    /// Set the text alignment of the header and cells, default is left.
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the text alignment of the column to left.
+    pub fn text_left(mut self) -> Self {
+        self.align = TextAlign::Left;
+        self
+    }
+
     /// Set the text alignment of the column to center.
     pub fn text_center(mut self) -> Self {
         self.align = TextAlign::Center;
         self
     }
 
-    /// Set the alignment of the column text, default is left.
-    ///
-    /// Only `text_left`, `text_right` is supported.
+    /// Set the text alignment of the column to right.
     pub fn text_right(mut self) -> Self {
         self.align = TextAlign::Right;
         self
     }
 
+    /// Set the number format of the column, default is None.
+    ///
+    /// When set, the cells display [`TableDelegate::cell_text`](crate::table::TableDelegate::cell_text)
+    /// formatted as a number instead of calling `render_td`.
+    /// The text that can't be parsed as a number is displayed as is.
+    pub fn number_format(mut self, format: NumberFormat) -> Self {
+        self.number_format = Some(format);
+        self
+    }
+
+    /// Set whether to truncate the overflowing cell text with an ellipsis, default is false.
+    ///
+    /// When the [`TableDelegate::cell_text`](crate::table::TableDelegate::cell_text)
+    /// is wider than the column, a tooltip shows the full text on hover.
+    pub fn ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Set a custom renderer for the cells of this column, default is None.
+    ///
+    /// The renderer receives the `row_ix` and takes precedence over `render_td`,
+    /// so simple tables don't need to match the `col_ix` in the delegate.
+    ///
+    /// ```rust,ignore
+    /// let stocks = Rc::new(stocks);
+    /// Column::new("name", "Name").renderer({
+    ///     let stocks = stocks.clone();
+    ///     move |row_ix, _, _| stocks[row_ix].name.clone()
+    /// })
+    /// ```
+    pub fn renderer<E>(
+        mut self,
+        renderer: impl Fn(usize, &mut Window, &mut App) -> E + 'static,
+    ) -> Self
+    where
+        E: IntoElement,
+    {
+        self.renderer = Some(ColumnRenderer(Rc::new(move |row_ix, window, cx| {
+            renderer(row_ix, window, cx).into_any_element()
+        })));
+        self
+    }
+
+    /// Set the filter kind of the column, default is None (not filterable).
+    ///
+    /// A filter button is shown in the header to edit the filter, and the table emits
+    /// [`TableEvent::FilterChanged`](crate::table::TableEvent::FilterChanged) with the
+    /// filters of all the columns.
+    pub fn filter(mut self, filter: ColumnFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     /// Set the padding of the column, default is None.
     pub fn paddings(mut self, paddings: impl Into<Edges<Pixels>>) -> Self {
         self.paddings = Some(paddings.into());
@@ -167,12 +380,29 @@ impl Column {
         self
     }
 
+    /// Set whether the column is fixed on right side, default is false.
+    ///
+    /// The right fixed columns must be placed at the end of the columns.
+    pub fn fixed_right(mut self) -> Self {
+        self.fixed = Some(ColumnFixed::Right);
+        self
+    }
+
     /// Set whether the column is resizable, default is true.
     pub fn resizable(mut self, resizable: bool) -> Self {
         self.resizable = resizable;
         self
     }
 
+    /// Fit the width of the column to the content by double-clicking the resize handle,
+    /// default is false.
+    ///
+    /// See also [`TableState::autofit_column`](crate::table::TableState::autofit_column).
+    pub fn auto_fit(mut self) -> Self {
+        self.auto_fit = true;
+        self
+    }
+
     /// Set whether the column is movable, default is true.
     pub fn movable(mut self, movable: bool) -> Self {
         self.movable = movable;
@@ -229,9 +459,10 @@ impl Column {
 
 impl FluentBuilder for Column {}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ColumnFixed {
     Left,
+    Right,
 }
 
 /// Used to sort the column runtime info in Table internal.
@@ -261,7 +492,7 @@ pub(crate) struct DragColumn {
 }
 
 /// The sorting behavior of a column.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ColumnSort {
     /// No sorting.
     #[default]
@@ -272,6 +503,87 @@ pub enum ColumnSort {
     Descending,
 }
 
+/// The filter kind of a [`Column`], see [`Column::filter`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnFilter {
+    /// Filter the cells that contain the text, edited by [`FilterValue::Contains`].
+    Text,
+    /// Filter the cells by a number range, edited by [`FilterValue::Range`].
+    Number,
+    /// Filter the cells by a set of the options, edited by [`FilterValue::In`].
+    Enum(Vec<SharedString>),
+}
+
+/// The value of a column filter, see [`FilterDescriptor`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FilterValue {
+    /// The text contains the query, case-insensitive.
+    Contains(SharedString),
+    /// The number is in the range, both bounds are inclusive.
+    Range { min: Option<f64>, max: Option<f64> },
+    /// The text is one of the options.
+    In(Vec<SharedString>),
+}
+
+impl FilterValue {
+    /// Returns true if the filter matches all the values, e.g. the query is empty.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Contains(query) => query.trim().is_empty(),
+            Self::Range { min, max } => min.is_none() && max.is_none(),
+            Self::In(options) => options.is_empty(),
+        }
+    }
+
+    /// Returns true if the cell text matches the filter.
+    ///
+    /// The text that can't be parsed as a number never matches a [`FilterValue::Range`].
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Contains(query) => text.to_lowercase().contains(&query.trim().to_lowercase()),
+            Self::Range { min, max } => match text.trim().parse::<f64>() {
+                Ok(value) => {
+                    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+                }
+                Err(_) => self.is_empty(),
+            },
+            Self::In(options) => options.is_empty() || options.iter().any(|option| option == text),
+        }
+    }
+}
+
+/// The filter of a column, emitted by [`TableEvent::FilterChanged`](crate::table::TableEvent::FilterChanged).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterDescriptor {
+    /// The [`Column::key`] of the column.
+    pub key: SharedString,
+    pub value: FilterValue,
+}
+
+/// Used to serialize and deserialize the layout of the columns of a table,
+/// see [`TableState::dump`](super::TableState::dump).
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TableLayoutState {
+    /// The columns in the display order.
+    pub columns: Vec<ColumnState>,
+}
+
+/// Used to serialize and deserialize the layout of a column.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ColumnState {
+    /// The [`Column::key`] of the column.
+    pub key: SharedString,
+    /// The width of the column, the width before hiding if the column is hidden.
+    pub width: Pixels,
+    #[serde(default)]
+    pub hidden: bool,
+    /// The sort of the column, `None` if the column is not sortable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<ColumnSort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<ColumnFixed>,
+}
+
 impl Render for DragColumn {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         div()
@@ -290,6 +602,34 @@ impl Render for DragColumn {
     }
 }
 
+#[derive(Clone)]
+pub(crate) struct DragRow {
+    pub(crate) entity_id: EntityId,
+    /// The text of the first visible cell, to show in the drag preview.
+    pub(crate) label: SharedString,
+    pub(crate) width: Pixels,
+    pub(crate) row_ix: usize,
+}
+
+impl Render for DragRow {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_4()
+            .py_1()
+            .bg(cx.theme().background)
+            .text_color(cx.theme().foreground)
+            .opacity(0.9)
+            .border_1()
+            .border_color(cx.theme().border)
+            .shadow_md()
+            .w(self.width)
+            .min_w(px(100.))
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .child(self.label.clone())
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct ResizeColumn(pub (EntityId, usize));
 impl Render for ResizeColumn {
@@ -297,3 +637,65 @@ impl Render for ResizeColumn {
         Empty
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FilterValue, NumberFormat};
+
+    #[test]
+    fn test_filter_value_matches() {
+        let filter = FilterValue::Contains("App".into());
+        assert!(filter.matches("Apple Inc."));
+        assert!(filter.matches("SNAPPY"));
+        assert!(!filter.matches("Google"));
+        assert!(FilterValue::Contains(" ".into()).is_empty());
+
+        let filter = FilterValue::Range {
+            min: Some(10.),
+            max: None,
+        };
+        assert!(filter.matches("10"));
+        assert!(filter.matches(" 12.5 "));
+        assert!(!filter.matches("9.99"));
+        assert!(!filter.matches("N/A"));
+        let filter = FilterValue::Range {
+            min: Some(-1.),
+            max: Some(1.),
+        };
+        assert!(filter.matches("0"));
+        assert!(!filter.matches("1.5"));
+
+        let filter = FilterValue::In(vec!["Buy".into(), "Hold".into()]);
+        assert!(filter.matches("Buy"));
+        assert!(!filter.matches("Sell"));
+        assert!(FilterValue::In(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_number_format() {
+        assert_eq!(NumberFormat::new().format(1234.5), "1234.5");
+        assert_eq!(NumberFormat::new().precision(2).format(1234.5), "1234.50");
+        assert_eq!(NumberFormat::new().precision(0).format(1234.5), "1234");
+
+        let format = NumberFormat::new()
+            .precision(2)
+            .thousands_separator(true)
+            .prefix("$");
+        assert_eq!(format.format(1234567.891), "$1,234,567.89");
+        assert_eq!(format.format(-1234.5), "-$1,234.50");
+        assert_eq!(format.format_str("-1234.5"), "-$1,234.50");
+        assert_eq!(format.format(123.0), "$123.00");
+        assert_eq!(format.format_str(" 1000 "), "$1,000.00");
+        assert_eq!(format.format_str("N/A"), "N/A");
+        assert_eq!(format.parse("$1,200.00"), Some(1200.));
+        assert_eq!(format.parse("-$1,234.50"), Some(-1234.5));
+        assert_eq!(format.parse(" 1,000 "), Some(1000.));
+        assert_eq!(format.parse("N/A"), None);
+        assert_eq!(format.parse(""), None);
+
+        let format = NumberFormat::new().precision(1).suffix("%");
+        assert_eq!(format.format(12.34), "12.3%");
+        assert_eq!(format.format(-12.34), "-12.3%");
+        assert_eq!(format.parse("12%"), Some(12.));
+    }
+}
@@ -4,13 +4,27 @@ use crate::{
         Cancel, SelectDown, SelectFirst, SelectLast, SelectNextColumn, SelectPageDown,
         SelectPageUp, SelectPrevColumn, SelectUp,
     },
+    input,
+    scroll::ScrollbarShow,
     table::{TableDelegate, TableState},
 };
 use gpui::{
     App, Edges, Entity, Focusable, InteractiveElement, IntoElement, KeyBinding, ParentElement,
-    RenderOnce, Styled, Window, div, prelude::FluentBuilder,
+    RenderOnce, Styled, Window, actions, div, prelude::FluentBuilder,
 };
 
+actions!(
+    table,
+    [
+        EditCell,
+        SelectAllRows,
+        ExtendSelectionUp,
+        ExtendSelectionDown,
+        ExtendSelectionLeft,
+        ExtendSelectionRight
+    ]
+);
+
 const CONTEXT: &'static str = "DataTable";
 pub(super) fn init(cx: &mut App) {
     cx.bind_keys([
@@ -25,11 +39,20 @@ pub(super) fn init(cx: &mut App) {
         KeyBinding::new("pagedown", SelectPageDown, Some(CONTEXT)),
         KeyBinding::new("tab", SelectNextColumn, Some(CONTEXT)),
         KeyBinding::new("shift-tab", SelectPrevColumn, Some(CONTEXT)),
+        KeyBinding::new("f2", EditCell, Some(CONTEXT)),
+        KeyBinding::new("secondary-a", SelectAllRows, Some(CONTEXT)),
+        KeyBinding::new("shift-up", ExtendSelectionUp, Some(CONTEXT)),
+        KeyBinding::new("shift-down", ExtendSelectionDown, Some(CONTEXT)),
+        KeyBinding::new("shift-left", ExtendSelectionLeft, Some(CONTEXT)),
+        KeyBinding::new("shift-right", ExtendSelectionRight, Some(CONTEXT)),
+        KeyBinding::new("secondary-c", input::Copy, Some(CONTEXT)),
     ]);
 }
 
 pub(super) struct TableOptions {
     pub(super) scrollbar_visible: Edges<bool>,
+    /// The scrollbar show mode of the table, `None` to use the theme setting.
+    pub(super) scrollbar_show: Option<ScrollbarShow>,
     /// Set stripe style of the table.
     pub(super) stripe: bool,
     /// Set to use border style of the table.
@@ -42,6 +65,7 @@ impl Default for TableOptions {
     fn default() -> Self {
         Self {
             scrollbar_visible: Edges::all(true),
+            scrollbar_show: None,
             stripe: false,
             bordered: true,
             size: Size::default(),
@@ -70,6 +94,7 @@ impl Default for TableOptions {
 ///   (use [`TableState::row_header()`] to hide it)
 /// - Keyboard navigation (arrow keys, Tab, Home, End, PageUp, PageDown) works at cell level
 /// - Right-click and double-click events are supported
+/// - Double-click or press `F2` to edit the cell, if [`TableDelegate::is_cell_editable`]
 ///
 /// See [`TableState`] for more details on cell selection.
 ///
@@ -125,6 +150,12 @@ where
         };
         self
     }
+
+    /// Set the scrollbar show mode of the table, default is `cx.theme().scrollbar_show`.
+    pub fn scrollbar_show(mut self, scrollbar_show: ScrollbarShow) -> Self {
+        self.options.scrollbar_show = Some(scrollbar_show);
+        self
+    }
 }
 
 impl<D> Sizable for DataTable<D>
@@ -162,6 +193,13 @@ where
             .on_action(window.listener_for(&self.state, TableState::action_select_last_column))
             .on_action(window.listener_for(&self.state, TableState::action_select_page_up))
             .on_action(window.listener_for(&self.state, TableState::action_select_page_down))
+            .on_action(window.listener_for(&self.state, TableState::action_edit_cell))
+            .on_action(window.listener_for(&self.state, TableState::action_select_all_rows))
+            .on_action(window.listener_for(&self.state, TableState::action_extend_selection_up))
+            .on_action(window.listener_for(&self.state, TableState::action_extend_selection_down))
+            .on_action(window.listener_for(&self.state, TableState::action_extend_selection_left))
+            .on_action(window.listener_for(&self.state, TableState::action_extend_selection_right))
+            .on_action(window.listener_for(&self.state, TableState::action_copy))
             .bg(cx.theme().tokens.table)
             .when(bordered, |this| {
                 this.rounded(cx.theme().radius)
@@ -1,12 +1,13 @@
 use std::ops::Range;
 
 use gpui::{
-    App, Context, Div, InteractiveElement as _, IntoElement, ParentElement as _, Pixels,
-    SharedString, Stateful, Styled as _, Window, div,
+    App, Context, Div, Entity, InteractiveElement as _, IntoElement, ParentElement as _, Pixels,
+    SharedString, Stateful, Styled as _, Window, div, px,
 };
 
 use crate::{
-    ActiveTheme as _, Icon, IconName, Size, h_flex,
+    ActiveTheme as _, Icon, IconName, Sizable as _, Size, h_flex,
+    input::{Input, InputState},
     menu::PopupMenu,
     table::{Column, ColumnGroup, ColumnSort, TableState, loading::Loading},
 };
@@ -85,6 +86,18 @@ pub trait TableDelegate: Sized + 'static {
             .child(self.column(col_ix, cx).name.clone())
     }
 
+    /// Render the footer cell at the given column index, default to empty.
+    ///
+    /// Only used when [`TableState::footer`] is enabled, e.g. to show the totals or averages.
+    fn render_footer_cell(
+        &mut self,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        div()
+    }
+
     /// Render the row at the given row and column.
     ///
     /// Not include the table head row.
@@ -108,14 +121,114 @@ pub trait TableDelegate: Sized + 'static {
         menu
     }
 
-    /// Render cell at the given row and column.
+    /// Render cell at the given row and column, default to the [`TableDelegate::cell_text`].
+    ///
+    /// This is not called for the columns with [`Column::renderer`] or [`Column::number_format`].
     fn render_td(
         &mut self,
         row_ix: usize,
         col_ix: usize,
         window: &mut Window,
         cx: &mut Context<TableState<Self>>,
-    ) -> impl IntoElement;
+    ) -> impl IntoElement {
+        self.cell_text(row_ix, col_ix, cx)
+    }
+
+    /// Return true if the cell at the given row and column can be edited, default is false.
+    ///
+    /// Editable cells can be edited inline by double-click or `F2` in cell selection mode,
+    /// see [`TableState::edit_cell`].
+    fn is_cell_editable(&self, row_ix: usize, col_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Render the editor of the cell that is being edited, default is an [`Input`] without appearance.
+    ///
+    /// The `input` is initialized with the [`TableDelegate::cell_text`], and its value is
+    /// emitted by [`TableEvent::CellEdited`](crate::table::TableEvent::CellEdited) on commit.
+    fn cell_editor(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        input: &Entity<InputState>,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        Input::new(input).appearance(false).small()
+    }
+
+    /// Return the height of the row at the given index, default is `None`.
+    ///
+    /// Only used when [`TableState::variable_row_height`] is enabled, return `None` to measure
+    /// the height of the row after it renders.
+    fn row_height(&self, row_ix: usize, cx: &App) -> Option<Pixels> {
+        None
+    }
+
+    /// Return true if the row at the given index can be expanded, default is true.
+    ///
+    /// Only used when [`TableState::expandable`] is enabled.
+    fn is_row_expandable(&self, row_ix: usize, cx: &App) -> bool {
+        true
+    }
+
+    /// Return the height of the detail area of the expanded row, default is 120px.
+    ///
+    /// The table uses this to account the height of the rows, the content of
+    /// [`TableDelegate::render_expanded_row`] is clipped to this height.
+    fn expanded_row_height(&self, row_ix: usize, cx: &App) -> Pixels {
+        px(120.)
+    }
+
+    /// Render the full-width detail area under the expanded row at the given row index.
+    ///
+    /// Only used when [`TableState::expandable`] is enabled.
+    fn render_expanded_row(
+        &mut self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        div()
+    }
+
+    /// Return the depth of the row at the given index in the tree mode, default is 0.
+    ///
+    /// The first column is indented by the depth, see [`TableState::tree`].
+    fn row_depth(&self, row_ix: usize, cx: &App) -> usize {
+        0
+    }
+
+    /// Return true if the row at the given index has children in the tree mode, default is false.
+    ///
+    /// Return true for the rows whose children have not been loaded yet to load them lazily
+    /// in [`TableDelegate::set_children_expanded`].
+    fn has_children(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Return true if the children of the row at the given index are expanded in the tree mode.
+    fn is_children_expanded(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Return true to show a spinner instead of the toggle while loading the children.
+    fn is_children_loading(&self, row_ix: usize, cx: &App) -> bool {
+        false
+    }
+
+    /// Expand or collapse the children of the row at the given index in the tree mode.
+    ///
+    /// Insert the child rows after the row when expanded (load them in a background task
+    /// if needed), and remove all its descendant rows when collapsed.
+    fn set_children_expanded(
+        &mut self,
+        row_ix: usize,
+        expanded: bool,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
 
     /// Move the column at the given `col_ix` so that it ends up at the index `to_ix`.
     ///
@@ -129,6 +242,19 @@ pub trait TableDelegate: Sized + 'static {
     ) {
     }
 
+    /// Move the row at the given `row_ix` so that it ends up at the index `to_ix`,
+    /// when [`TableState::row_movable`] is enabled.
+    ///
+    /// e.g.: `let row = self.rows.remove(row_ix); self.rows.insert(to_ix, row);`
+    fn move_row(
+        &mut self,
+        row_ix: usize,
+        to_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<TableState<Self>>,
+    ) {
+    }
+
     /// Return a Element to show when table is empty.
     fn render_empty(
         &mut self,
@@ -148,8 +274,18 @@ pub trait TableDelegate: Sized + 'static {
         false
     }
 
+    /// Return false if the data of the row has not arrived yet, default is true.
+    ///
+    /// The row is rendered as a skeleton placeholder, useful for the rows loaded asynchronously.
+    fn is_row_loaded(&self, row_ix: usize, cx: &App) -> bool {
+        true
+    }
+
     /// Return a Element to show when table is loading, default is built-in Skeleton loading view.
     ///
+    /// Only used when [`TableState::loading_skeleton`] is disabled, otherwise the
+    /// rows are rendered as skeleton placeholders.
+    ///
     /// The size is the size of the Table.
     fn render_loading(
         &mut self,
@@ -225,7 +361,18 @@ pub trait TableDelegate: Sized + 'static {
     ///
     /// Returns an empty string by default. Implement this method to support export.
     /// The text should be formatted as it should appear in the exported data.
+    ///
+    /// This is also used by the default `render_td`, the [`Column::number_format`]
+    /// and the tooltip of [`Column::ellipsis`].
     fn cell_text(&self, row_ix: usize, col_ix: usize, cx: &App) -> String {
         String::new()
     }
+
+    /// Get the raw number of a cell, used to sum the columns with a [`Column::number_format`]
+    /// in the group header of [`TableState::group_by`].
+    ///
+    /// Returns `None` by default to parse the [`Self::cell_text`] by the number format.
+    fn cell_number(&self, row_ix: usize, col_ix: usize, cx: &App) -> Option<f64> {
+        None
+    }
 }
@@ -0,0 +1,193 @@
+use std::{borrow::Cow, io::Write, path::Path, rc::Rc};
+
+use gpui::{App, Context, Window};
+
+use super::{TableDelegate, TableState};
+
+/// The options of [`TableState::export_csv`].
+#[derive(Clone)]
+pub struct CsvExportOptions {
+    delimiter: char,
+    header: bool,
+    visible_only: bool,
+    cell_text: Option<Rc<dyn Fn(usize, usize, &App) -> String>>,
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            header: true,
+            visible_only: false,
+            cell_text: None,
+        }
+    }
+}
+
+impl CsvExportOptions {
+    /// Create the options to export CSV, separated by `,` with the header row.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the options to export TSV, separated by `\t` with the header row.
+    pub fn tsv() -> Self {
+        Self::default().delimiter('\t')
+    }
+
+    /// Set the delimiter of the fields, default is `,`.
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set whether to write the names of the columns as the first row, default is true.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Set whether to export the visible columns only, default is false.
+    pub fn visible_only(mut self, visible_only: bool) -> Self {
+        self.visible_only = visible_only;
+        self
+    }
+
+    /// Set the callback to extract the text of the cell by `(row_ix, col_ix)`,
+    /// default is the [`TableDelegate::cell_text`].
+    pub fn cell_text(mut self, f: impl Fn(usize, usize, &App) -> String + 'static) -> Self {
+        self.cell_text = Some(Rc::new(f));
+        self
+    }
+}
+
+impl<D> TableState<D>
+where
+    D: TableDelegate,
+{
+    /// Write the rows to the `writer` as CSV, the columns are in the display order.
+    ///
+    /// The fields containing the delimiter, quotes or line breaks are quoted.
+    pub fn export_csv(
+        &self,
+        writer: &mut impl Write,
+        options: &CsvExportOptions,
+        cx: &App,
+    ) -> std::io::Result<()> {
+        let cols = (0..self.col_groups.len())
+            .filter(|col_ix| !options.visible_only || self.is_column_visible(*col_ix))
+            .collect::<Vec<_>>();
+
+        if options.header {
+            let names = cols
+                .iter()
+                .map(|col_ix| Cow::Borrowed(self.col_groups[*col_ix].column.name.as_str()));
+            write_record(writer, names, options.delimiter)?;
+        }
+
+        let delegate = self.delegate();
+        for row_ix in 0..delegate.rows_count(cx) {
+            let cells = cols.iter().map(|col_ix| {
+                Cow::Owned(match &options.cell_text {
+                    Some(cell_text) => cell_text(row_ix, *col_ix, cx),
+                    None => delegate.cell_text(row_ix, *col_ix, cx),
+                })
+            });
+            write_record(writer, cells, options.delimiter)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Prompt for a file path in the `directory`, then export the rows to the file as CSV.
+    ///
+    /// The rows are exported before prompting, the changes after that are not included.
+    pub fn prompt_export_csv(
+        &self,
+        directory: &Path,
+        options: &CsvExportOptions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut content = vec![];
+        if let Err(err) = self.export_csv(&mut content, options, cx) {
+            tracing::error!("failed to export csv: {:?}", err);
+            return;
+        }
+
+        let file_name = if options.delimiter == '\t' {
+            "export.tsv"
+        } else {
+            "export.csv"
+        };
+        let receiver = cx.prompt_for_new_path(directory, Some(file_name));
+        cx.spawn_in(window, async move |_, _| {
+            let Ok(Ok(Some(path))) = receiver.await else {
+                return;
+            };
+
+            if let Err(err) = std::fs::write(&path, content) {
+                tracing::error!("failed to write csv to {:?}: {:?}", path, err);
+            }
+        })
+        .detach();
+    }
+}
+
+/// Write the `fields` as a record of CSV, ends with `\n`.
+fn write_record<'a>(
+    writer: &mut impl Write,
+    fields: impl Iterator<Item = Cow<'a, str>>,
+    delimiter: char,
+) -> std::io::Result<()> {
+    let mut delimiter_buf = [0; 4];
+    let delimiter_str = delimiter.encode_utf8(&mut delimiter_buf);
+
+    for (ix, field) in fields.enumerate() {
+        if ix > 0 {
+            writer.write_all(delimiter_str.as_bytes())?;
+        }
+        writer.write_all(quote_field(&field, delimiter).as_bytes())?;
+    }
+    writer.write_all(b"\n")
+}
+
+/// Quote the field if it contains the delimiter, quotes or line breaks.
+pub(super) fn quote_field(field: &str, delimiter: char) -> Cow<'_, str> {
+    if field.contains(|c| c == delimiter || matches!(c, '"' | '\n' | '\r')) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{quote_field, write_record};
+
+    #[test]
+    fn test_quote_field() {
+        assert_eq!(quote_field("AAPL", ','), "AAPL");
+        assert_eq!(quote_field("1,234.5", ','), "\"1,234.5\"");
+        assert_eq!(quote_field("1,234.5", '\t'), "1,234.5");
+        assert_eq!(quote_field("a;b", ';'), "\"a;b\"");
+        assert_eq!(quote_field("Say \"Hi\"", ','), "\"Say \"\"Hi\"\"\"");
+        assert_eq!(quote_field("Line 1\r\nLine 2", ','), "\"Line 1\r\nLine 2\"");
+        assert_eq!(quote_field("", ','), "");
+    }
+
+    #[test]
+    fn test_write_record() {
+        let mut buf = vec![];
+        write_record(
+            &mut buf,
+            ["a", "b,c", ""].into_iter().map(Cow::Borrowed),
+            ',',
+        )
+        .unwrap();
+        write_record(&mut buf, ["a", "b,c"].into_iter().map(Cow::Borrowed), '\t').unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "a,\"b,c\",\na\tb,c\n");
+    }
+}
@@ -1,26 +1,47 @@
-use std::{ops::Range, rc::Rc, time::Duration};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    ops::Range,
+    rc::Rc,
+    time::Duration,
+};
 
 use crate::{
-    ActiveTheme, ElementExt, Icon, IconName, StyleSized as _, StyledExt, VirtualListScrollHandle,
+    ActiveTheme, Disableable as _, ElementExt, Icon, IconName, Sizable as _, Size, StyleSized as _,
+    StyledExt, VirtualListScrollHandle,
     actions::{
         Cancel, SelectDown, SelectFirst, SelectLast, SelectNextColumn, SelectPageDown,
         SelectPageUp, SelectPrevColumn, SelectUp,
     },
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
     h_flex,
-    menu::{ContextMenuExt, PopupMenu},
+    input::{Input, InputEvent, InputState, SelectAll},
+    menu::{ContextMenuExt, DropdownMenu as _, PopupMenu, PopupMenuItem},
+    plot::label::measure_text_width,
+    popover::Popover,
     scroll::{ScrollableMask, Scrollbar},
-    v_flex,
+    skeleton::Skeleton,
+    spinner::Spinner,
+    tooltip::Tooltip,
+    v_flex, v_virtual_list,
 };
 use gpui::{
-    AppContext, Axis, Bounds, ClickEvent, Context, Div, DragMoveEvent, EventEmitter, FocusHandle,
-    Focusable, InteractiveElement, IntoElement, ListSizingBehavior, MouseButton, MouseDownEvent,
-    ParentElement, Pixels, Point, Render, ScrollStrategy, SharedString, Stateful,
-    StatefulInteractiveElement as _, Styled, Task, UniformListScrollHandle, Window, div,
-    prelude::FluentBuilder, px, uniform_list,
+    Anchor, AnyElement, AppContext, Axis, Bounds, ClickEvent, ClipboardItem, Context, Div,
+    DragMoveEvent, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement,
+    ListSizingBehavior, MouseButton, MouseDownEvent, MouseMoveEvent, ParentElement, Pixels, Point,
+    Render, ScrollHandle, ScrollStrategy, SharedString, Stateful, StatefulInteractiveElement as _,
+    Styled, Subscription, Task, TextAlign, UniformListScrollHandle, Window, div,
+    prelude::FluentBuilder, px, relative, rems, uniform_list,
 };
+use rust_i18n::t;
 
 use super::*;
 
+/// The number of skeleton rows to render when loading an empty table before the first layout.
+const DEFAULT_SKELETON_ROWS: usize = 10;
+/// The indentation of each depth level in the tree mode.
+const TREE_INDENT: Pixels = px(16.);
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum SelectionMode {
     Column,
@@ -61,6 +82,10 @@ pub enum TableEvent {
     ///
     /// This event is also emitted when navigating between cells using keyboard shortcuts.
     SelectCell(usize, usize),
+    /// A range of the cells has been selected, by `Shift` + arrows, `Shift` + click or mouse drag.
+    ///
+    /// The first `Range<usize>` is the rows, and the second `Range<usize>` is the columns.
+    SelectCellRange(Range<usize>, Range<usize>),
     /// A cell has been double-clicked.
     ///
     /// Emitted when a cell is double-clicked in cell selection mode.
@@ -68,15 +93,58 @@ pub enum TableEvent {
     ///
     /// Use this event to trigger actions like opening a detail view or editing the cell content.
     DoubleClickedCell(usize, usize),
+    /// A cell has been edited.
+    ///
+    /// Emitted when the editing of a cell is committed by `Enter`, `Tab` or blur,
+    /// and the value has been changed.
+    /// The first `usize` is the row index, the second `usize` is the column index,
+    /// and the `SharedString` is the new value.
+    ///
+    /// The table does not change the data, apply the value to your delegate.
+    CellEdited(usize, usize, SharedString),
+    /// A row has been expanded or collapsed.
+    ///
+    /// The `usize` is the row index, and the `bool` is true if the row is expanded.
+    ExpandRow(usize, bool),
+    /// The children of a row have been expanded or collapsed in the tree mode.
+    ///
+    /// The `usize` is the row index, and the `bool` is true if the children are expanded.
+    ExpandChildren(usize, bool),
+    /// A group of the rows has been expanded or collapsed, when grouping by a column.
+    ///
+    /// The `SharedString` is the group key, and the `bool` is true if the group is expanded.
+    ExpandGroup(SharedString, bool),
+    /// The multi-selection of the rows has been changed.
+    ///
+    /// Contains the selected row indices in ascending order,
+    /// emitted when [`TableState::multi_selectable`] is enabled.
+    SelectionChanged(Vec<usize>),
     /// The column widths have changed.
     ///
     /// The `Vec<Pixels>` contains the new widths of all columns.
     ColumnWidthsChanged(Vec<Pixels>),
+    /// A column has been shown or hidden.
+    ///
+    /// The `usize` is the column index, and the `bool` is true if the column is visible.
+    /// Use this event to persist the visible columns.
+    ColumnVisibilityChanged(usize, bool),
     /// A column has been moved.
     ///
     /// The first `usize` is the original index of the column,
     /// and the second `usize` is the new index of the column.
     MoveColumn(usize, usize),
+    /// A row has been moved by dragging the handle, see [`TableState::row_movable`].
+    ///
+    /// The first `usize` is the original index of the row,
+    /// and the second `usize` is the new index of the row.
+    MoveRow(usize, usize),
+    /// The filters of the columns have been changed.
+    ///
+    /// Contains the filters of all the filtered columns, in the order they were added.
+    ///
+    /// The table does not filter the rows, apply the filters to your delegate,
+    /// e.g. by [`FilterValue::matches`].
+    FilterChanged(Vec<FilterDescriptor>),
     /// A row has been right-clicked.
     ///
     /// Contains the row index, or `None` if right-clicked on an empty area.
@@ -117,6 +185,23 @@ impl TableVisibleRange {
     }
 }
 
+/// A group of the rows with the same text in the grouping column.
+struct RowGroup {
+    key: SharedString,
+    rows: Vec<usize>,
+    /// The sums of the number columns, `(col_ix, formatted sum)`.
+    aggregates: Vec<(usize, SharedString)>,
+}
+
+/// An item of the table body when the rows are grouped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DisplayRow {
+    /// The header of the group at the index.
+    Group(usize),
+    /// The row at the index.
+    Row(usize),
+}
+
 /// The state for [`DataTable`].
 ///
 /// # Selection Modes
@@ -143,6 +228,13 @@ impl TableVisibleRange {
 /// - [`TableEvent::SelectCell`]: Emitted when a cell is selected
 /// - [`TableEvent::DoubleClickedCell`]: Emitted when a cell is double-clicked
 /// - [`TableEvent::RightClickedCell`]: Emitted when a cell is right-clicked
+/// - [`TableEvent::CellEdited`]: Emitted when a cell has been edited
+///
+/// ## Cell Editing
+///
+/// When [`TableDelegate::is_cell_editable`] returns true, double-click or press `F2`
+/// on the selected cell to edit it inline. Press `Enter` to commit, `Escape` to cancel,
+/// and `Tab` / `Shift-Tab` to commit and move to the next / previous editable cell.
 ///
 /// # Example
 ///
@@ -176,6 +268,24 @@ pub(crate) struct HeaderCell {
     start_leaf_col_ix: usize,
 }
 
+/// The state of the cell that is being edited.
+struct CellEditing {
+    row_ix: usize,
+    col_ix: usize,
+    /// The value before editing, used to check if the value has been changed.
+    old_value: SharedString,
+    input: Entity<InputState>,
+    _subscription: Subscription,
+}
+
+/// The inputs of the column filter that is being edited in the header.
+struct FilterEditing {
+    key: SharedString,
+    /// The query input of [`ColumnFilter::Text`], or the min and max inputs of [`ColumnFilter::Number`].
+    inputs: Vec<Entity<InputState>>,
+    _subscriptions: Vec<Subscription>,
+}
+
 pub struct TableState<D: TableDelegate> {
     focus_handle: FocusHandle,
     delegate: D,
@@ -184,8 +294,10 @@ pub struct TableState<D: TableDelegate> {
     bounds: Bounds<Pixels>,
     /// The bounds of the fixed head cols.
     fixed_head_cols_bounds: Bounds<Pixels>,
+    /// The bounds of the right fixed head cols.
+    fixed_right_head_cols_bounds: Bounds<Pixels>,
 
-    col_groups: Vec<ColGroup>,
+    pub(super) col_groups: Vec<ColGroup>,
     header_layout: Vec<Vec<HeaderCell>>,
 
     /// Whether the table can loop selection, default is true.
@@ -220,11 +332,64 @@ pub struct TableState<D: TableDelegate> {
     pub col_resizable: bool,
     /// Whether the table can move columns.
     pub col_movable: bool,
+    /// Whether the rows can be moved by dragging, default is false.
+    ///
+    /// See [`Self::row_movable`].
+    pub row_movable: bool,
     /// Enable/disable fixed columns feature.
     pub col_fixed: bool,
+    /// Whether to render skeleton rows when loading, default is `true`.
+    ///
+    /// When disabled, the whole table is replaced by [`TableDelegate::render_loading`].
+    pub loading_skeleton: bool,
+    /// Whether the rows can be expanded to show a detail area, default is false.
+    ///
+    /// See [`Self::expandable`].
+    pub expandable: bool,
+    /// Whether the rows are hierarchical, default is false.
+    ///
+    /// See [`Self::tree`].
+    pub tree: bool,
+    /// Whether multiple rows can be selected, default is false.
+    ///
+    /// See [`Self::multi_selectable`].
+    pub multi_selectable: bool,
+    /// Whether the rows can have different heights, default is false.
+    ///
+    /// See [`Self::variable_row_height`].
+    pub variable_row_height: bool,
+    /// Whether to show the column chooser button in the header, default is false.
+    ///
+    /// See [`Self::column_picker`].
+    pub column_picker: bool,
+    /// See [`Self::footer`].
+    pub footer: bool,
+    /// The column index to group the rows by, see [`Self::group_by`].
+    group_by: Option<usize>,
+    row_groups: Vec<RowGroup>,
+    /// The group headers and the rows of the expanded groups in the display order.
+    display_rows: Vec<DisplayRow>,
+    collapsed_groups: HashSet<SharedString>,
+    /// The rows count when the groups were built, `None` to build the groups again.
+    grouped_rows_count: Option<usize>,
+    /// The first visible index of the display rows, to find the sticky group header.
+    first_visible_display_ix: usize,
 
     pub vertical_scroll_handle: UniformListScrollHandle,
     pub horizontal_scroll_handle: VirtualListScrollHandle,
+    /// The vertical scroll handle of the rows when [`Self::expandable`] or
+    /// [`Self::variable_row_height`] is enabled, the rows have different heights.
+    expandable_scroll_handle: VirtualListScrollHandle,
+    /// The measured heights of the rows, when `variable_row_height` is enabled.
+    measured_row_heights: HashMap<usize, Pixels>,
+    /// The measured text widths of the [`Column::ellipsis`] cells by `(row_ix, col_ix)`,
+    /// with the text and the font size they were measured for.
+    cell_text_widths: HashMap<(usize, usize), (SharedString, Pixels, f32)>,
+    /// The keys of the hidden columns.
+    hidden_cols: HashSet<SharedString>,
+    /// The widths of the hidden columns before hiding, to restore when shown again.
+    hidden_col_widths: HashMap<SharedString, Pixels>,
+    expanded_rows: HashSet<usize>,
 
     selected_row: Option<usize>,
     selection_mode: SelectionMode,
@@ -232,6 +397,20 @@ pub struct TableState<D: TableDelegate> {
     right_clicked_cell: Option<(usize, usize)>,
     selected_col: Option<usize>,
     selected_cell: Option<(usize, usize)>,
+    /// The selected rows of the multi-selection.
+    selected_rows: BTreeSet<usize>,
+    /// The row to start the range selection by `Shift` + click.
+    selection_anchor: Option<usize>,
+    /// The cell to start the range selection of the cells, the other corner is the `selected_cell`.
+    cell_range_anchor: Option<(usize, usize)>,
+    /// The cell where the mouse is pressed down, to select the cells by dragging.
+    cell_drag_start: Option<(usize, usize)>,
+    /// The cell that is being edited.
+    editing_cell: Option<CellEditing>,
+    /// The filters of the columns, see [`Self::set_filter`].
+    filters: Vec<FilterDescriptor>,
+    /// The column filter that is being edited.
+    filter_editing: Option<FilterEditing>,
 
     /// The column index that is being resized.
     resizing_col: Option<usize>,
@@ -240,6 +419,9 @@ pub struct TableState<D: TableDelegate> {
     /// header: the dragged column will be inserted between the columns
     /// `gap - 1` and `gap` on drop.
     col_drag_gap: Option<usize>,
+    /// The insertion gap index (`0..=rows_count`) while dragging a row,
+    /// the dragged row will be inserted between the rows `gap - 1` and `gap` on drop.
+    row_drag_gap: Option<usize>,
 
     /// The visible range of the rows and columns.
     visible_range: TableVisibleRange,
@@ -262,16 +444,27 @@ where
             header_layout: Vec::new(),
             horizontal_scroll_handle: VirtualListScrollHandle::new(),
             vertical_scroll_handle: UniformListScrollHandle::new(),
+            expandable_scroll_handle: VirtualListScrollHandle::new(),
+            measured_row_heights: HashMap::new(),
+            cell_text_widths: HashMap::new(),
+            hidden_cols: HashSet::new(),
+            hidden_col_widths: HashMap::new(),
+            expanded_rows: HashSet::new(),
             selection_mode: SelectionMode::Row,
             selected_row: None,
             right_clicked_row: None,
             right_clicked_cell: None,
             selected_col: None,
             selected_cell: None,
+            editing_cell: None,
+            filters: Vec::new(),
+            filter_editing: None,
             resizing_col: None,
             col_drag_gap: None,
+            row_drag_gap: None,
             bounds: Bounds::default(),
             fixed_head_cols_bounds: Bounds::default(),
+            fixed_right_head_cols_bounds: Bounds::default(),
             visible_range: TableVisibleRange::default(),
             loop_selection: true,
             col_selectable: true,
@@ -280,8 +473,26 @@ where
             row_header: true,
             sortable: true,
             col_movable: true,
+            row_movable: false,
             col_resizable: true,
             col_fixed: true,
+            loading_skeleton: true,
+            expandable: false,
+            tree: false,
+            multi_selectable: false,
+            variable_row_height: false,
+            column_picker: false,
+            footer: false,
+            group_by: None,
+            row_groups: Vec::new(),
+            display_rows: Vec::new(),
+            collapsed_groups: HashSet::new(),
+            grouped_rows_count: None,
+            first_visible_display_ix: 0,
+            selected_rows: BTreeSet::new(),
+            selection_anchor: None,
+            cell_range_anchor: None,
+            cell_drag_start: None,
             _load_more_task: Task::ready(()),
             _measure: Vec::new(),
         };
@@ -312,6 +523,19 @@ where
         self
     }
 
+    /// Set to enable/disable row movable, default to false.
+    ///
+    /// When enabled, a drag handle cell is shown at the start of each row,
+    /// drag it to move the row, the table is scrolled when dragging near the top or bottom edge.
+    /// The rows can not be moved when grouped by a column.
+    ///
+    /// Implement [`TableDelegate::move_row`] to apply the move to your data,
+    /// and [`TableEvent::MoveRow`] is emitted.
+    pub fn row_movable(mut self, row_movable: bool) -> Self {
+        self.row_movable = row_movable;
+        self
+    }
+
     /// Set to enable/disable column resizable, default to true.
     pub fn col_resizable(mut self, col_resizable: bool) -> Self {
         self.col_resizable = col_resizable;
@@ -373,199 +597,1217 @@ where
         self
     }
 
-    /// When we update columns or rows, we need to refresh the table.
-    pub fn refresh(&mut self, cx: &mut Context<Self>) {
-        self.prepare_col_groups(cx);
+    /// Set whether to render skeleton rows when loading, default is `true`.
+    ///
+    /// When enabled, [`TableDelegate::loading`] keeps the header and the scroll position,
+    /// and renders the rows (or a page of rows when the table is empty) as skeleton
+    /// placeholders matching the column widths. The rows that
+    /// [`TableDelegate::is_row_loaded`] returns false are always rendered as skeleton.
+    ///
+    /// Set to `false` to replace the whole table by [`TableDelegate::render_loading`].
+    pub fn loading_skeleton(mut self, loading_skeleton: bool) -> Self {
+        self.loading_skeleton = loading_skeleton;
+        self
     }
 
-    /// Scroll to the row at the given index.
-    pub fn scroll_to_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
-        self.vertical_scroll_handle
-            .scroll_to_item(row_ix, ScrollStrategy::Top);
-        cx.notify();
+    /// Set whether the rows can be expanded, default is false.
+    ///
+    /// When enabled, a chevron toggle is rendered at the start of each row
+    /// ([`TableDelegate::is_row_expandable`]) to show a full-width detail area under the row,
+    /// which is rendered by [`TableDelegate::render_expanded_row`] with the height of
+    /// [`TableDelegate::expanded_row_height`].
+    pub fn expandable(mut self, expandable: bool) -> Self {
+        self.expandable = expandable;
+        self
     }
 
-    // Scroll to the column at the given index.
-    pub fn scroll_to_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
-        let col_ix = col_ix.saturating_sub(self.fixed_left_cols_count());
+    /// Set whether the rows can have different heights, default is false.
+    ///
+    /// By default the table uses a uniform list, so every row has the same height.
+    /// When enabled, the rows are rendered by a virtual list, the row height is
+    /// [`TableDelegate::row_height`], or measured after the row renders and cached,
+    /// so cells with wrapped text can grow the row.
+    ///
+    /// The measured heights are cleared by [`Self::refresh`] and [`Self::reset_row_heights`].
+    pub fn variable_row_height(mut self, variable_row_height: bool) -> Self {
+        self.variable_row_height = variable_row_height;
+        self
+    }
 
-        self.horizontal_scroll_handle
-            .scroll_to_item(col_ix, ScrollStrategy::Top);
-        cx.notify();
+    /// Set whether to show the column chooser in the header, default is false.
+    ///
+    /// When enabled, a gear button is rendered at the right of the header, it opens
+    /// a menu with a checkbox for each column to show or hide it.
+    ///
+    /// See also [`Self::set_column_visible`].
+    pub fn column_picker(mut self, column_picker: bool) -> Self {
+        self.column_picker = column_picker;
+        self
     }
 
-    /// Returns the selected row index.
-    pub fn selected_row(&self) -> Option<usize> {
-        self.selected_row
+    /// Set whether to show the footer row, default is false.
+    ///
+    /// The footer is pinned below the rows, e.g. to show the totals or averages, each cell
+    /// is rendered by [`TableDelegate::render_footer_cell`] with the width of the column,
+    /// and scrolls horizontally with the header.
+    pub fn footer(mut self, footer: bool) -> Self {
+        self.footer = footer;
+        self
     }
 
-    /// Sets the selected row to the given index.
-    pub fn set_selected_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
-        let is_down = match self.selected_row {
-            Some(selected_row) => row_ix > selected_row,
-            None => true,
+    /// Returns true if the column at the given index is visible.
+    pub fn is_column_visible(&self, col_ix: usize) -> bool {
+        self.col_groups
+            .get(col_ix)
+            .map_or(false, |g| !self.hidden_cols.contains(&g.column.key))
+    }
+
+    /// Show or hide the column at the given index.
+    ///
+    /// The hidden column keeps its index, so the delegate does not need to change,
+    /// and the width is restored when shown again.
+    ///
+    /// Emits [`TableEvent::ColumnVisibilityChanged`] if changed.
+    pub fn set_column_visible(&mut self, col_ix: usize, visible: bool, cx: &mut Context<Self>) {
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
         };
 
-        cx.stop_propagation();
-        self.selection_mode = SelectionMode::Row;
-        self.right_clicked_row = None;
-        self.selected_row = Some(row_ix);
-        if let Some(row_ix) = self.selected_row {
-            self.vertical_scroll_handle.scroll_to_item(
-                row_ix,
-                if is_down {
-                    ScrollStrategy::Bottom
-                } else {
-                    ScrollStrategy::Top
-                },
-            );
+        let key = col_group.column.key.clone();
+        if visible {
+            if !self.hidden_cols.remove(&key) {
+                return;
+            }
+            col_group.width = self
+                .hidden_col_widths
+                .remove(&key)
+                .unwrap_or(col_group.column.width);
+        } else {
+            if !self.hidden_cols.insert(key.clone()) {
+                return;
+            }
+            self.hidden_col_widths.insert(key, col_group.width);
+            col_group.width = px(0.);
         }
-        cx.emit(TableEvent::SelectRow(row_ix));
-        cx.emit(TableEvent::RightClickedRow(None));
+
+        self.measured_row_heights.clear();
+        self.update_header_layout(cx);
+        cx.emit(TableEvent::ColumnVisibilityChanged(col_ix, visible));
         cx.notify();
     }
 
-    /// Returns the row that has been right clicked.
-    pub fn right_clicked_row(&self) -> Option<usize> {
-        self.right_clicked_row
+    /// Returns the filters of the columns, in the order they were added.
+    pub fn filters(&self) -> &[FilterDescriptor] {
+        &self.filters
     }
 
-    /// Set or clear the right-clicked row state.
+    /// Set the filter of the column with the given key, `None` or an empty value to remove it.
     ///
-    /// Pass `None` to clear — useful when opening a header context menu
-    /// to prevent the row context menu from appearing simultaneously.
-    pub fn set_right_clicked_row(&mut self, row: Option<usize>, cx: &mut Context<Self>) {
-        self.right_clicked_row = row;
-        cx.notify();
-    }
+    /// Emits [`TableEvent::FilterChanged`] if changed.
+    pub fn set_filter(
+        &mut self,
+        key: impl Into<SharedString>,
+        value: Option<FilterValue>,
+        cx: &mut Context<Self>,
+    ) {
+        let key: SharedString = key.into();
+        let value = value.filter(|value| !value.is_empty());
+        let ix = self.filters.iter().position(|filter| filter.key == key);
+        match (ix, value) {
+            (Some(ix), Some(value)) => {
+                if self.filters[ix].value == value {
+                    return;
+                }
+                self.filters[ix].value = value;
+            }
+            (Some(ix), None) => {
+                self.filters.remove(ix);
+            }
+            (None, Some(value)) => self.filters.push(FilterDescriptor { key, value }),
+            (None, None) => return,
+        }
 
-    /// Returns the selected column index.
-    pub fn selected_col(&self) -> Option<usize> {
-        self.selected_col
+        cx.emit(TableEvent::FilterChanged(self.filters.clone()));
+        cx.notify();
     }
 
-    /// Sets the selected col to the given index.
-    pub fn set_selected_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
-        self.selection_mode = SelectionMode::Column;
-        self.selected_col = Some(col_ix);
-        if let Some(col_ix) = self.selected_col {
-            self.scroll_to_col(col_ix, cx);
+    /// Remove the filters of all the columns.
+    ///
+    /// Emits [`TableEvent::FilterChanged`] if there are any filters.
+    pub fn clear_filters(&mut self, cx: &mut Context<Self>) {
+        if self.filters.is_empty() {
+            return;
         }
-        cx.emit(TableEvent::SelectColumn(col_ix));
+
+        self.filters.clear();
+        cx.emit(TableEvent::FilterChanged(Vec::new()));
         cx.notify();
     }
 
-    /// Returns the selected cell as `(row_ix, col_ix)`.
-    ///
-    /// Returns `None` if no cell is currently selected or if the table is in row/column selection mode.
-    ///
-    /// # Example
-    ///
-    /// ```rust,ignore
-    /// if let Some((row_ix, col_ix)) = table_state.read(cx).selected_cell() {
-    ///     println!("Selected cell: ({}, {})", row_ix, col_ix);
-    /// }
-    /// ```
-    pub fn selected_cell(&self) -> Option<(usize, usize)> {
-        self.selected_cell
+    fn column_filter_value(&self, key: &SharedString) -> Option<&FilterValue> {
+        self.filters
+            .iter()
+            .find(|filter| &filter.key == key)
+            .map(|filter| &filter.value)
     }
 
-    /// Sets the selected cell to the given row and column indices.
-    ///
-    /// This method:
-    /// - Switches the table to cell selection mode
-    /// - Scrolls to make the cell visible (centered vertically)
-    /// - Emits a [`TableEvent::SelectCell`] event
-    ///
-    /// # Example
-    ///
-    /// ```rust,ignore
-    /// // Select the cell at row 5, column 3
-    /// table_state.update(cx, |state, cx| {
-    ///     state.set_selected_cell(5, 3, cx);
-    /// });
-    /// ```
-    pub fn set_selected_cell(&mut self, row_ix: usize, col_ix: usize, cx: &mut Context<Self>) {
-        self.selection_mode = SelectionMode::Cell;
-        self.selected_cell = Some((row_ix, col_ix));
+    /// Create the inputs to edit the filter of the column, when the filter popover is opened.
+    fn edit_filter(&mut self, col_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(column) = self.col_groups.get(col_ix).map(|g| g.column.clone()) else {
+            return;
+        };
 
-        // Scroll to the cell
-        self.vertical_scroll_handle
-            .scroll_to_item(row_ix, ScrollStrategy::Center);
-        self.scroll_to_col(col_ix, cx);
+        let values: Vec<String> = match (&column.filter, self.column_filter_value(&column.key)) {
+            (Some(ColumnFilter::Text), Some(FilterValue::Contains(query))) => {
+                vec![query.to_string()]
+            }
+            (Some(ColumnFilter::Text), _) => vec![String::new()],
+            (Some(ColumnFilter::Number), Some(FilterValue::Range { min, max })) => [min, max]
+                .iter()
+                .map(|bound| bound.map(|bound| bound.to_string()).unwrap_or_default())
+                .collect(),
+            (Some(ColumnFilter::Number), _) => vec![String::new(), String::new()],
+            _ => vec![],
+        };
+        let placeholders = match column.filter {
+            Some(ColumnFilter::Text) => vec![t!("Table.filter_contains")],
+            _ => vec![t!("Table.filter_min"), t!("Table.filter_max")],
+        };
 
-        cx.emit(TableEvent::SelectCell(row_ix, col_ix));
+        let mut inputs = Vec::with_capacity(values.len());
+        let mut _subscriptions = Vec::with_capacity(values.len());
+        for (value, placeholder) in values.into_iter().zip(placeholders) {
+            let input = cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder(placeholder)
+                    .default_value(value)
+            });
+            _subscriptions.push(cx.subscribe(&input, |this, _, event, cx| {
+                if let InputEvent::Change = event {
+                    this.apply_filter_inputs(cx);
+                }
+            }));
+            inputs.push(input);
+        }
+
+        self.filter_editing = Some(FilterEditing {
+            key: column.key,
+            inputs,
+            _subscriptions,
+        });
         cx.notify();
     }
 
-    /// Clear the selection of the table.
-    pub fn clear_selection(&mut self, cx: &mut Context<Self>) {
-        self.selection_mode = SelectionMode::Row;
-        self.selected_row = None;
-        self.selected_col = None;
-        self.selected_cell = None;
-        cx.emit(TableEvent::ClearSelection);
-        cx.notify();
+    /// Apply the values of the filter inputs to the filter of the editing column.
+    fn apply_filter_inputs(&mut self, cx: &mut Context<Self>) {
+        let Some(editing) = self.filter_editing.as_ref() else {
+            return;
+        };
+
+        let values = editing
+            .inputs
+            .iter()
+            .map(|input| input.read(cx).value())
+            .collect::<Vec<_>>();
+        let value = match values.as_slice() {
+            [query] => FilterValue::Contains(query.trim().to_string().into()),
+            [min, max] => FilterValue::Range {
+                min: min.trim().parse().ok(),
+                max: max.trim().parse().ok(),
+            },
+            _ => return,
+        };
+
+        let key = editing.key.clone();
+        self.set_filter(key, Some(value), cx);
     }
 
-    /// Returns the visible range of the rows and columns.
+    /// Dump the layout of the columns: the order, widths, visibility, sort and fixed,
+    /// to persist the layout changed by the user.
     ///
-    /// See [`TableVisibleRange`].
-    pub fn visible_range(&self) -> &TableVisibleRange {
-        &self.visible_range
+    /// See also [`Self::restore`].
+    pub fn dump(&self) -> TableLayoutState {
+        let columns = self
+            .col_groups
+            .iter()
+            .map(|col_group| {
+                let key = col_group.column.key.clone();
+                let hidden = self.hidden_cols.contains(&key);
+                let width = if hidden {
+                    self.hidden_col_widths
+                        .get(&key)
+                        .copied()
+                        .unwrap_or(col_group.column.width)
+                } else {
+                    col_group.width
+                };
+
+                ColumnState {
+                    key,
+                    width,
+                    hidden,
+                    sort: col_group.column.sort,
+                    fixed: col_group.column.fixed,
+                }
+            })
+            .collect();
+
+        TableLayoutState { columns }
     }
 
-    /// Dump table data.
+    /// Restore the layout of the columns from the [`TableLayoutState`] of [`Self::dump`].
     ///
-    /// Returns a tuple of (headers, rows) where each row is a vector of cell values.
-    pub fn dump(&self, cx: &App) -> (Vec<String>, Vec<Vec<String>>) {
-        // Get header row
-        let columns_count = self.delegate.columns_count(cx);
-        let mut headers = Vec::with_capacity(columns_count);
-        for col_ix in 0..columns_count {
-            let column = self.delegate.column(col_ix, cx);
-            headers.push(column.name.to_string());
+    /// The columns are matched by the [`Column::key`], the unknown columns in the state are
+    /// ignored, and the columns not in the state are kept after the restored columns.
+    /// The columns are moved by [`TableDelegate::move_column`], and the sorted column is
+    /// sorted by [`TableDelegate::perform_sort`].
+    pub fn restore(
+        &mut self,
+        state: TableLayoutState,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut to_ix = 0;
+        for column in &state.columns {
+            let Some(col_ix) = self
+                .col_groups
+                .iter()
+                .position(|g| g.column.key == column.key)
+            else {
+                continue;
+            };
+
+            self.move_column(col_ix, to_ix, window, cx);
+            to_ix += 1;
         }
 
-        // Get data rows
-        let rows_count = self.delegate.rows_count(cx);
-        let mut rows = Vec::with_capacity(rows_count);
-        for row_ix in 0..rows_count {
-            let mut row = Vec::with_capacity(columns_count);
-            for col_ix in 0..columns_count {
-                row.push(self.delegate.cell_text(row_ix, col_ix, cx));
+        let mut sorted_col = None;
+        for column in state.columns {
+            let Some(col_ix) = self
+                .col_groups
+                .iter()
+                .position(|g| g.column.key == column.key)
+            else {
+                continue;
+            };
+
+            let col_group = &mut self.col_groups[col_ix];
+            let width = column
+                .width
+                .clamp(col_group.column.min_width, col_group.column.max_width);
+            col_group.column.fixed = column.fixed;
+            if let (Some(sort), Some(_)) = (column.sort, col_group.column.sort) {
+                col_group.column.sort = Some(sort);
+                if sort != ColumnSort::Default {
+                    sorted_col = Some((col_ix, sort));
+                }
+            }
+
+            if column.hidden {
+                self.hidden_cols.insert(column.key.clone());
+                self.hidden_col_widths.insert(column.key, width);
+                col_group.width = px(0.);
+            } else {
+                self.hidden_cols.remove(&column.key);
+                self.hidden_col_widths.remove(&column.key);
+                col_group.width = width;
             }
-            rows.push(row);
         }
 
-        (headers, rows)
-    }
+        if let Some((col_ix, sort)) = sorted_col {
+            self.delegate_mut().perform_sort(col_ix, sort, window, cx);
+        }
 
-    /// Re-compute the header layout from the current delegate.
-    ///
-    /// Call this after changing delegate state that affects `group_headers`.
-    pub fn refresh_header_layout(&mut self, cx: &mut Context<Self>) {
+        self.grouped_rows_count = None;
+        self.measured_row_heights.clear();
         self.update_header_layout(cx);
         cx.notify();
     }
 
-    fn prepare_col_groups(&mut self, cx: &mut Context<Self>) {
-        self.col_groups = (0..self.delegate.columns_count(cx))
-            .map(|col_ix| {
-                let column = self.delegate().column(col_ix, cx);
-                ColGroup {
-                    width: column.width,
-                    bounds: Bounds::default(),
-                    column,
-                }
-            })
-            .collect();
+    /// Clear the measured row heights, to measure them again in next render.
+    ///
+    /// Call this when the contents of the rows changed,
+    /// only used when [`Self::variable_row_height`] is enabled.
+    pub fn reset_row_heights(&mut self, cx: &mut Context<Self>) {
+        self.measured_row_heights.clear();
+        cx.notify();
+    }
 
-        self.update_header_layout(cx);
+    /// Whether the rows are rendered by the virtual list rather than the uniform list.
+    fn is_virtual_list(&self) -> bool {
+        self.expandable || self.variable_row_height
     }
 
-    fn update_header_layout(&mut self, cx: &mut Context<Self>) {
-        let group_rows = self.delegate.group_headers(cx);
+    /// Returns the height of the row at the given index, not including the expanded area.
+    fn row_height(&self, row_ix: usize, rows_count: usize, cx: &App) -> Pixels {
+        let row_height = self.options.size.table_row_height();
+        if !self.variable_row_height || row_ix >= rows_count {
+            return row_height;
+        }
+
+        self.delegate
+            .row_height(row_ix, cx)
+            .or_else(|| self.measured_row_heights.get(&row_ix).copied())
+            .unwrap_or(row_height)
+            .max(row_height)
+    }
+
+    fn update_measured_row_height(
+        &mut self,
+        row_ix: usize,
+        height: Pixels,
+        cx: &mut Context<Self>,
+    ) {
+        if self.measured_row_heights.get(&row_ix) == Some(&height) {
+            return;
+        }
+
+        self.measured_row_heights.insert(row_ix, height);
+        cx.notify();
+    }
+
+    /// Returns true if the row at the given index is expanded.
+    pub fn is_row_expanded(&self, row_ix: usize) -> bool {
+        self.expanded_rows.contains(&row_ix)
+    }
+
+    /// Expand or collapse the row at the given index, and emit [`TableEvent::ExpandRow`].
+    pub fn set_row_expanded(&mut self, row_ix: usize, expanded: bool, cx: &mut Context<Self>) {
+        let changed = if expanded {
+            self.expanded_rows.insert(row_ix)
+        } else {
+            self.expanded_rows.remove(&row_ix)
+        };
+
+        if changed {
+            cx.emit(TableEvent::ExpandRow(row_ix, expanded));
+            cx.notify();
+        }
+    }
+
+    /// Toggle the expanded state of the row at the given index.
+    pub fn toggle_row_expanded(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        self.set_row_expanded(row_ix, !self.is_row_expanded(row_ix), cx);
+    }
+
+    /// Collapse all the expanded rows.
+    ///
+    /// Call this when the rows have been reordered or replaced, the expanded state is kept by row index.
+    pub fn collapse_all_rows(&mut self, cx: &mut Context<Self>) {
+        self.expanded_rows.clear();
+        cx.notify();
+    }
+
+    /// Set whether multiple rows can be selected, default is false.
+    ///
+    /// When enabled, a checkbox column is rendered at the start of each row, and in the
+    /// header to select all. Click selects a single row, `Shift` + click selects the range
+    /// from the last clicked row, and `Ctrl` (`Cmd` on macOS) + click toggles the row.
+    ///
+    /// The changes are emitted by [`TableEvent::SelectionChanged`].
+    pub fn multi_selectable(mut self, multi_selectable: bool) -> Self {
+        self.multi_selectable = multi_selectable;
+        self
+    }
+
+    /// Set whether the rows are hierarchical (tree-table mode), default is false.
+    ///
+    /// The delegate provides the flattened visible rows in the tree order, with the
+    /// depth of each row from [`TableDelegate::row_depth`]. The first column is indented
+    /// by the depth, with a chevron toggle for the rows that [`TableDelegate::has_children`].
+    ///
+    /// Toggling a row calls [`TableDelegate::set_children_expanded`], where the delegate
+    /// inserts (or loads lazily) and removes the child rows.
+    ///
+    /// In row selection mode, `Right` expands the selected row or moves to its first child,
+    /// and `Left` collapses the selected row or moves to its parent.
+    pub fn tree(mut self, tree: bool) -> Self {
+        self.tree = tree;
+        self
+    }
+
+    /// Expand or collapse the children of the row at the given index in the tree mode,
+    /// and emit [`TableEvent::ExpandChildren`].
+    pub fn set_children_expanded(
+        &mut self,
+        row_ix: usize,
+        expanded: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.delegate.has_children(row_ix, cx)
+            || self.delegate.is_children_expanded(row_ix, cx) == expanded
+        {
+            return;
+        }
+
+        self.commit_cell_editing(window, cx);
+        let old_rows_count = self.delegate.rows_count(cx);
+        self.delegate
+            .set_children_expanded(row_ix, expanded, window, cx);
+        let new_rows_count = self.delegate.rows_count(cx);
+
+        // Keep the selection on the same row after the child rows are inserted or removed.
+        let shift = |ix: usize| shift_row(ix, row_ix, old_rows_count, new_rows_count);
+        self.selected_row = self.selected_row.map(shift);
+        self.selected_cell = self.selected_cell.map(|(ix, col_ix)| (shift(ix), col_ix));
+        self.cell_range_anchor = self
+            .cell_range_anchor
+            .map(|(ix, col_ix)| (shift(ix), col_ix));
+
+        cx.emit(TableEvent::ExpandChildren(row_ix, expanded));
+        cx.notify();
+    }
+
+    /// Toggle the children of the row at the given index in the tree mode.
+    pub fn toggle_children(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let expanded = self.delegate.is_children_expanded(row_ix, cx);
+        self.set_children_expanded(row_ix, !expanded, window, cx);
+    }
+
+    /// Returns the parent row index of the row at the given index in the tree mode.
+    pub fn parent_row(&self, row_ix: usize, cx: &App) -> Option<usize> {
+        parent_row(row_ix, |ix| self.delegate.row_depth(ix, cx))
+    }
+
+    /// Group the rows by the text of the column at the given index, default is None.
+    ///
+    /// Each group renders a collapsible header row with the group key, the rows count,
+    /// and the sums of the columns with a [`Column::number_format`]. The header of the
+    /// current group sticks to the top of the table while scrolling.
+    ///
+    /// The groups are in the order of their first rows. They are built again when the
+    /// rows count changes, call [`Self::refresh`] after changing the data in place.
+    pub fn group_by(mut self, col_ix: Option<usize>) -> Self {
+        self.group_by = col_ix;
+        self.grouped_rows_count = None;
+        self
+    }
+
+    /// Set the column index to group the rows by, `None` to ungroup the rows.
+    ///
+    /// See [`Self::group_by`].
+    pub fn set_group_by(&mut self, col_ix: Option<usize>, cx: &mut Context<Self>) {
+        self.group_by = col_ix;
+        self.grouped_rows_count = None;
+        cx.notify();
+    }
+
+    /// Returns the column index that the rows are grouped by.
+    pub fn grouped_col(&self) -> Option<usize> {
+        self.group_by
+    }
+
+    /// Returns true if the group with the given key is collapsed.
+    pub fn is_group_collapsed(&self, key: &SharedString) -> bool {
+        self.collapsed_groups.contains(key)
+    }
+
+    /// Expand or collapse the group with the given key, and emit [`TableEvent::ExpandGroup`].
+    pub fn set_group_collapsed(
+        &mut self,
+        key: impl Into<SharedString>,
+        collapsed: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let key = key.into();
+        let changed = if collapsed {
+            self.collapsed_groups.insert(key.clone())
+        } else {
+            self.collapsed_groups.remove(&key)
+        };
+        if !changed {
+            return;
+        }
+
+        self.update_display_rows();
+        cx.emit(TableEvent::ExpandGroup(key, !collapsed));
+        cx.notify();
+    }
+
+    /// Toggle the group with the given key.
+    pub fn toggle_group(&mut self, key: impl Into<SharedString>, cx: &mut Context<Self>) {
+        let key = key.into();
+        let collapsed = self.is_group_collapsed(&key);
+        self.set_group_collapsed(key, !collapsed, cx);
+    }
+
+    fn is_grouped(&self) -> bool {
+        self.group_by.is_some() && !self.row_groups.is_empty()
+    }
+
+    /// Build the groups if the grouping column or the rows count has been changed.
+    fn update_groups_if_need(&mut self, cx: &App) {
+        let Some(group_col) = self.group_by else {
+            self.row_groups.clear();
+            self.display_rows.clear();
+            return;
+        };
+
+        let rows_count = self.delegate.rows_count(cx);
+        if self.grouped_rows_count == Some(rows_count) {
+            return;
+        }
+        self.grouped_rows_count = Some(rows_count);
+
+        let sum_cols = self
+            .col_groups
+            .iter()
+            .enumerate()
+            .filter(|(col_ix, _)| *col_ix != group_col)
+            .filter_map(|(col_ix, g)| Some((col_ix, g.column.number_format.clone()?)))
+            .collect::<Vec<_>>();
+
+        self.row_groups = group_rows(
+            (0..rows_count).map(|row_ix| self.delegate.cell_text(row_ix, group_col, cx)),
+        )
+        .into_iter()
+        .map(|(key, rows)| {
+            let aggregates = sum_cols
+                .iter()
+                .map(|(col_ix, number_format)| {
+                    let sum: f64 = rows
+                        .iter()
+                        .filter_map(|row_ix| {
+                            self.delegate.cell_number(*row_ix, *col_ix, cx).or_else(|| {
+                                number_format.parse(&self.delegate.cell_text(*row_ix, *col_ix, cx))
+                            })
+                        })
+                        .sum();
+                    (*col_ix, SharedString::from(number_format.format(sum)))
+                })
+                .collect();
+            RowGroup {
+                key,
+                rows,
+                aggregates,
+            }
+        })
+        .collect();
+        self.update_display_rows();
+    }
+
+    fn update_display_rows(&mut self) {
+        self.display_rows.clear();
+        for (group_ix, group) in self.row_groups.iter().enumerate() {
+            self.display_rows.push(DisplayRow::Group(group_ix));
+            if !self.collapsed_groups.contains(&group.key) {
+                self.display_rows
+                    .extend(group.rows.iter().map(|row_ix| DisplayRow::Row(*row_ix)));
+            }
+        }
+    }
+
+    /// Returns the count of the items in the table body, including the group headers.
+    fn display_rows_count(&self, rows_count: usize) -> usize {
+        if self.is_grouped() {
+            self.display_rows.len()
+        } else {
+            rows_count
+        }
+    }
+
+    /// Returns the group header or the row at the given index of the table body,
+    /// the fake rows to fill the table are after the display rows.
+    fn display_row(&self, ix: usize, rows_count: usize) -> DisplayRow {
+        if !self.is_grouped() {
+            return DisplayRow::Row(ix);
+        }
+
+        match self.display_rows.get(ix) {
+            Some(row) => *row,
+            None => DisplayRow::Row(rows_count + ix - self.display_rows.len()),
+        }
+    }
+
+    /// Returns the index in the table body of the row, or the header of its collapsed group.
+    fn display_ix(&self, row_ix: usize) -> usize {
+        if !self.is_grouped() {
+            return row_ix;
+        }
+
+        self.display_rows
+            .iter()
+            .position(|row| *row == DisplayRow::Row(row_ix))
+            .or_else(|| {
+                let group_ix = self
+                    .row_groups
+                    .iter()
+                    .position(|group| group.rows.contains(&row_ix))?;
+                self.display_rows
+                    .iter()
+                    .position(|row| *row == DisplayRow::Group(group_ix))
+            })
+            .unwrap_or(row_ix)
+    }
+
+    /// Returns the height of the item at the given index of the table body.
+    fn display_row_height(&self, ix: usize, rows_count: usize, cx: &App) -> Pixels {
+        match self.display_row(ix, rows_count) {
+            DisplayRow::Group(_) => self.options.size.table_row_height(),
+            DisplayRow::Row(row_ix) => {
+                self.row_height(row_ix, rows_count, cx)
+                    + self.expanded_height(row_ix, rows_count, cx)
+            }
+        }
+    }
+
+    /// Returns the row before or after the given row in the display order of the groups,
+    /// skipping the group headers and the rows of the collapsed groups.
+    fn step_grouped_row(&self, row_ix: Option<usize>, backward: bool) -> Option<usize> {
+        let rows = self
+            .display_rows
+            .iter()
+            .filter_map(|row| match row {
+                DisplayRow::Row(row_ix) => Some(*row_ix),
+                DisplayRow::Group(_) => None,
+            })
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let pos = row_ix.and_then(|row_ix| rows.iter().position(|ix| *ix == row_ix));
+        let new_pos = match pos {
+            None => 0,
+            Some(pos) if backward => match pos.checked_sub(1) {
+                Some(pos) => pos,
+                None if self.loop_selection => rows.len() - 1,
+                None => pos,
+            },
+            Some(pos) if pos + 1 < rows.len() => pos + 1,
+            Some(_) if self.loop_selection => 0,
+            Some(pos) => pos,
+        };
+
+        Some(rows[new_pos])
+    }
+
+    /// Returns the group to stick at the top, when the table is scrolled.
+    fn sticky_group_ix(&self) -> Option<usize> {
+        if !self.is_grouped() {
+            return None;
+        }
+
+        let offset_y = if self.is_virtual_list() {
+            self.expandable_scroll_handle.offset().y
+        } else {
+            self.vertical_scroll_handle
+                .0
+                .borrow()
+                .base_handle
+                .offset()
+                .y
+        };
+        if offset_y >= px(0.) {
+            return None;
+        }
+
+        let first_ix = self
+            .first_visible_display_ix
+            .min(self.display_rows.len().saturating_sub(1));
+        self.display_rows[..=first_ix]
+            .iter()
+            .rev()
+            .find_map(|row| match row {
+                DisplayRow::Group(group_ix) => Some(*group_ix),
+                DisplayRow::Row(_) => None,
+            })
+    }
+
+    /// When we update columns or rows, we need to refresh the table.
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        self.measured_row_heights.clear();
+        self.cell_text_widths.clear();
+        self.grouped_rows_count = None;
+        self.prepare_col_groups(cx);
+    }
+
+    /// Scroll to the row at the given index.
+    pub fn scroll_to_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        self.scroll_to_row_with_strategy(row_ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn scroll_to_row_with_strategy(&self, row_ix: usize, strategy: ScrollStrategy) {
+        self.scroll_to_display_ix(self.display_ix(row_ix), strategy);
+    }
+
+    /// Scroll to the item at the given index of the table body.
+    fn scroll_to_display_ix(&self, ix: usize, strategy: ScrollStrategy) {
+        if self.is_virtual_list() {
+            self.expandable_scroll_handle.scroll_to_item(ix, strategy);
+        } else {
+            self.vertical_scroll_handle.scroll_to_item(ix, strategy);
+        }
+    }
+
+    // Scroll to the column at the given index.
+    pub fn scroll_to_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
+        // The right fixed columns are always visible.
+        if col_ix >= self.fixed_right_cols_start() {
+            return;
+        }
+
+        let col_ix = col_ix.saturating_sub(self.fixed_left_cols_count());
+
+        self.horizontal_scroll_handle
+            .scroll_to_item(col_ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    /// Returns the selected row index.
+    pub fn selected_row(&self) -> Option<usize> {
+        self.selected_row
+    }
+
+    /// Sets the selected row to the given index.
+    ///
+    /// When [`Self::multi_selectable`] is enabled, this also selects only this row.
+    pub fn set_selected_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        if self.multi_selectable {
+            self.selection_anchor = Some(row_ix);
+            self.update_selected_rows(BTreeSet::from([row_ix]), cx);
+        }
+
+        self.set_active_row(row_ix, cx);
+    }
+
+    /// Sets the active (focused) row without changing the multi-selection.
+    fn set_active_row(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let is_down = match self.selected_row {
+            Some(selected_row) => row_ix > selected_row,
+            None => true,
+        };
+
+        cx.stop_propagation();
+        self.selection_mode = SelectionMode::Row;
+        self.right_clicked_row = None;
+        self.selected_row = Some(row_ix);
+        if let Some(row_ix) = self.selected_row {
+            self.scroll_to_row_with_strategy(
+                row_ix,
+                if is_down {
+                    ScrollStrategy::Bottom
+                } else {
+                    ScrollStrategy::Top
+                },
+            );
+        }
+        cx.emit(TableEvent::SelectRow(row_ix));
+        cx.emit(TableEvent::RightClickedRow(None));
+        cx.notify();
+    }
+
+    /// Returns the row that has been right clicked.
+    pub fn right_clicked_row(&self) -> Option<usize> {
+        self.right_clicked_row
+    }
+
+    /// Set or clear the right-clicked row state.
+    ///
+    /// Pass `None` to clear — useful when opening a header context menu
+    /// to prevent the row context menu from appearing simultaneously.
+    pub fn set_right_clicked_row(&mut self, row: Option<usize>, cx: &mut Context<Self>) {
+        self.right_clicked_row = row;
+        cx.notify();
+    }
+
+    /// Returns the selected column index.
+    pub fn selected_col(&self) -> Option<usize> {
+        self.selected_col
+    }
+
+    /// Sets the selected col to the given index.
+    pub fn set_selected_col(&mut self, col_ix: usize, cx: &mut Context<Self>) {
+        self.selection_mode = SelectionMode::Column;
+        self.selected_col = Some(col_ix);
+        if let Some(col_ix) = self.selected_col {
+            self.scroll_to_col(col_ix, cx);
+        }
+        cx.emit(TableEvent::SelectColumn(col_ix));
+        cx.notify();
+    }
+
+    /// Returns the selected cell as `(row_ix, col_ix)`.
+    ///
+    /// Returns `None` if no cell is currently selected or if the table is in row/column selection mode.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if let Some((row_ix, col_ix)) = table_state.read(cx).selected_cell() {
+    ///     println!("Selected cell: ({}, {})", row_ix, col_ix);
+    /// }
+    /// ```
+    pub fn selected_cell(&self) -> Option<(usize, usize)> {
+        self.selected_cell
+    }
+
+    /// Sets the selected cell to the given row and column indices.
+    ///
+    /// This method:
+    /// - Switches the table to cell selection mode
+    /// - Scrolls to make the cell visible (centered vertically)
+    /// - Emits a [`TableEvent::SelectCell`] event
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Select the cell at row 5, column 3
+    /// table_state.update(cx, |state, cx| {
+    ///     state.set_selected_cell(5, 3, cx);
+    /// });
+    /// ```
+    pub fn set_selected_cell(&mut self, row_ix: usize, col_ix: usize, cx: &mut Context<Self>) {
+        self.selection_mode = SelectionMode::Cell;
+        self.selected_cell = Some((row_ix, col_ix));
+        self.cell_range_anchor = None;
+
+        // Scroll to the cell
+        self.scroll_to_row_with_strategy(row_ix, ScrollStrategy::Center);
+        self.scroll_to_col(col_ix, cx);
+
+        cx.emit(TableEvent::SelectCell(row_ix, col_ix));
+        cx.notify();
+    }
+
+    /// Clear the selection of the table.
+    pub fn clear_selection(&mut self, cx: &mut Context<Self>) {
+        self.selection_mode = SelectionMode::Row;
+        self.selected_row = None;
+        self.selected_col = None;
+        self.selected_cell = None;
+        self.selection_anchor = None;
+        self.cell_range_anchor = None;
+        self.update_selected_rows(BTreeSet::new(), cx);
+        cx.emit(TableEvent::ClearSelection);
+        cx.notify();
+    }
+
+    /// Returns the selected range of the cells as `(rows, cols)` in cell selection mode.
+    ///
+    /// The range is from the anchor cell to the [`Self::selected_cell`],
+    /// it's the single selected cell if no range is selected.
+    pub fn selected_cell_range(&self) -> Option<(Range<usize>, Range<usize>)> {
+        if !self.selection_mode.is_cell() {
+            return None;
+        }
+
+        let active = self.selected_cell?;
+        Some(cell_range(self.cell_range_anchor.unwrap_or(active), active))
+    }
+
+    /// Select the rectangular range of the cells from the `anchor` cell to the `active` cell,
+    /// the cells are `(row_ix, col_ix)`.
+    ///
+    /// The `active` cell becomes the [`Self::selected_cell`], and scrolls into view.
+    ///
+    /// Emits a [`TableEvent::SelectCellRange`] event.
+    pub fn set_selected_cell_range(
+        &mut self,
+        anchor: (usize, usize),
+        active: (usize, usize),
+        cx: &mut Context<Self>,
+    ) {
+        self.selection_mode = SelectionMode::Cell;
+        self.selected_cell = Some(active);
+        self.cell_range_anchor = Some(anchor);
+
+        self.scroll_to_row_with_strategy(active.0, ScrollStrategy::Top);
+        self.scroll_to_col(active.1, cx);
+
+        let (rows, cols) = cell_range(anchor, active);
+        cx.emit(TableEvent::SelectCellRange(rows, cols));
+        cx.notify();
+    }
+
+    /// Returns true if the cell is in the selected range, but not the selected cell.
+    fn is_cell_in_range(&self, row_ix: usize, col_ix: usize) -> bool {
+        if self.selected_cell == Some((row_ix, col_ix)) || self.cell_range_anchor.is_none() {
+            return false;
+        }
+
+        self.selected_cell_range().map_or(false, |(rows, cols)| {
+            rows.contains(&row_ix) && cols.contains(&col_ix)
+        })
+    }
+
+    /// Extend the selected range of the cells by moving the selected cell with the offset,
+    /// keeping the anchor cell.
+    fn extend_cell_range(&mut self, row_offset: isize, col_offset: isize, cx: &mut Context<Self>) {
+        let rows_count = self.delegate.rows_count(cx);
+        let columns_count = self.delegate.columns_count(cx);
+        if rows_count == 0 || columns_count == 0 {
+            return;
+        }
+
+        let Some((row_ix, col_ix)) = self.selected_cell else {
+            self.set_selected_cell(0, 0, cx);
+            return;
+        };
+
+        let new_row = if self.is_grouped() && row_offset != 0 {
+            self.step_grouped_row(Some(row_ix), row_offset < 0)
+                .unwrap_or(row_ix)
+        } else {
+            row_ix.saturating_add_signed(row_offset).min(rows_count - 1)
+        };
+        let new_col = col_ix
+            .saturating_add_signed(col_offset)
+            .min(columns_count - 1);
+
+        let anchor = self.cell_range_anchor.unwrap_or((row_ix, col_ix));
+        self.set_selected_cell_range(anchor, (new_row, new_col), cx);
+    }
+
+    /// Returns the text of the selected cells as TSV, the hidden columns are skipped.
+    fn selected_cells_text(&self, cx: &App) -> Option<String> {
+        let (rows, cols) = self.selected_cell_range()?;
+        let cols = cols
+            .filter(|col_ix| self.is_column_visible(*col_ix))
+            .collect::<Vec<_>>();
+
+        Some(cells_to_tsv(rows.map(|row_ix| {
+            cols.iter()
+                .map(|col_ix| self.delegate.cell_text(row_ix, *col_ix, cx))
+                .collect()
+        })))
+    }
+
+    /// Returns the selected rows in ascending order, when [`Self::multi_selectable`] is enabled.
+    pub fn selected_rows(&self) -> Vec<usize> {
+        self.selected_rows.iter().copied().collect()
+    }
+
+    /// Returns true if the row at the given index is in the multi-selection.
+    pub fn is_row_selected(&self, row_ix: usize) -> bool {
+        self.selected_rows.contains(&row_ix)
+    }
+
+    /// Set the selected rows, and emit [`TableEvent::SelectionChanged`] if changed.
+    pub fn set_selected_rows(
+        &mut self,
+        rows: impl IntoIterator<Item = usize>,
+        cx: &mut Context<Self>,
+    ) {
+        let rows_count = self.delegate.rows_count(cx);
+        let rows = rows.into_iter().filter(|ix| *ix < rows_count).collect();
+        self.update_selected_rows(rows, cx);
+    }
+
+    /// Select all the rows, when [`Self::multi_selectable`] is enabled.
+    pub fn select_all(&mut self, cx: &mut Context<Self>) {
+        if !self.multi_selectable {
+            return;
+        }
+
+        let rows_count = self.delegate.rows_count(cx);
+        self.update_selected_rows((0..rows_count).collect(), cx);
+    }
+
+    /// Add or remove the row at the given index to the multi-selection.
+    pub fn toggle_row_selected(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let mut rows = self.selected_rows.clone();
+        if !rows.remove(&row_ix) {
+            rows.insert(row_ix);
+        }
+        self.selection_anchor = Some(row_ix);
+        self.update_selected_rows(rows, cx);
+    }
+
+    fn update_selected_rows(&mut self, rows: BTreeSet<usize>, cx: &mut Context<Self>) {
+        if self.selected_rows == rows {
+            return;
+        }
+
+        self.selected_rows = rows;
+        cx.emit(TableEvent::SelectionChanged(self.selected_rows()));
+        cx.notify();
+    }
+
+    /// Returns the cell that is being edited as `(row_ix, col_ix)`.
+    pub fn editing_cell(&self) -> Option<(usize, usize)> {
+        self.editing_cell
+            .as_ref()
+            .map(|editing| (editing.row_ix, editing.col_ix))
+    }
+
+    /// Start editing the cell at the given row and column indices.
+    ///
+    /// The cell is selected and turned into an inline editor, see [`TableDelegate::cell_editor`].
+    /// The editor is initialized with [`TableDelegate::cell_text`].
+    ///
+    /// Does nothing if [`TableDelegate::is_cell_editable`] returns false for the cell.
+    pub fn edit_cell(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.delegate.is_cell_editable(row_ix, col_ix, cx) {
+            return;
+        }
+
+        self.commit_cell_editing(window, cx);
+        if !self.selection_mode.is_cell() || self.selected_cell != Some((row_ix, col_ix)) {
+            self.set_selected_cell(row_ix, col_ix, cx);
+        }
+
+        let old_value: SharedString = self.delegate.cell_text(row_ix, col_ix, cx).into();
+        let input = cx.new(|cx| {
+            let mut input = InputState::new(window, cx).default_value(old_value.clone());
+            input.select_all(&SelectAll, window, cx);
+            input
+        });
+        input.update(cx, |input, cx| input.focus(window, cx));
+
+        let _subscription =
+            cx.subscribe_in(&input, window, |this, _, event, window, cx| match event {
+                InputEvent::PressEnter { .. } | InputEvent::Blur => {
+                    this.commit_cell_editing(window, cx)
+                }
+                _ => {}
+            });
+
+        self.editing_cell = Some(CellEditing {
+            row_ix,
+            col_ix,
+            old_value,
+            input,
+            _subscription,
+        });
+        cx.notify();
+    }
+
+    /// Commit the editing cell, and emit [`TableEvent::CellEdited`] if the value has been changed.
+    pub fn commit_cell_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editing) = self.editing_cell.take() else {
+            return;
+        };
+
+        let value = editing.input.read(cx).value();
+        if editing.input.focus_handle(cx).is_focused(window) {
+            self.focus_handle.focus(window, cx);
+        }
+        if value != editing.old_value {
+            cx.emit(TableEvent::CellEdited(
+                editing.row_ix,
+                editing.col_ix,
+                value,
+            ));
+        }
+        cx.notify();
+    }
+
+    /// Cancel the editing cell without emitting any event.
+    pub fn cancel_cell_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(editing) = self.editing_cell.take() else {
+            return;
+        };
+
+        if editing.input.focus_handle(cx).is_focused(window) {
+            self.focus_handle.focus(window, cx);
+        }
+        cx.notify();
+    }
+
+    /// Returns the next (or previous if `backward`) editable cell after the given cell,
+    /// in the reading order of the rows.
+    fn next_editable_cell(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        backward: bool,
+        cx: &App,
+    ) -> Option<(usize, usize)> {
+        let rows_count = self.delegate.rows_count(cx);
+        let columns_count = self.delegate.columns_count(cx);
+
+        let mut cell = (row_ix, col_ix);
+        for _ in 0..rows_count * columns_count {
+            cell = step_cell(
+                cell,
+                rows_count,
+                columns_count,
+                backward,
+                self.loop_selection,
+            )?;
+            if cell == (row_ix, col_ix) {
+                return None;
+            }
+            if self.delegate.is_cell_editable(cell.0, cell.1, cx) {
+                return Some(cell);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the visible range of the rows and columns.
+    ///
+    /// See [`TableVisibleRange`].
+    pub fn visible_range(&self) -> &TableVisibleRange {
+        &self.visible_range
+    }
+
+    /// Dump table data.
+    ///
+    /// Returns a tuple of (headers, rows) where each row is a vector of cell values.
+    pub fn dump(&self, cx: &App) -> (Vec<String>, Vec<Vec<String>>) {
+        // Get header row
+        let columns_count = self.delegate.columns_count(cx);
+        let mut headers = Vec::with_capacity(columns_count);
+        for col_ix in 0..columns_count {
+            let column = self.delegate.column(col_ix, cx);
+            headers.push(column.name.to_string());
+        }
+
+        // Get data rows
+        let rows_count = self.delegate.rows_count(cx);
+        let mut rows = Vec::with_capacity(rows_count);
+        for row_ix in 0..rows_count {
+            let mut row = Vec::with_capacity(columns_count);
+            for col_ix in 0..columns_count {
+                row.push(self.delegate.cell_text(row_ix, col_ix, cx));
+            }
+            rows.push(row);
+        }
+
+        (headers, rows)
+    }
+
+    /// Re-compute the header layout from the current delegate.
+    ///
+    /// Call this after changing delegate state that affects `group_headers`.
+    pub fn refresh_header_layout(&mut self, cx: &mut Context<Self>) {
+        self.update_header_layout(cx);
+        cx.notify();
+    }
+
+    fn prepare_col_groups(&mut self, cx: &mut Context<Self>) {
+        self.col_groups = (0..self.delegate.columns_count(cx))
+            .map(|col_ix| {
+                let column = self.delegate().column(col_ix, cx);
+                let width = if self.hidden_cols.contains(&column.key) {
+                    px(0.)
+                } else {
+                    column.width
+                };
+                ColGroup {
+                    width,
+                    bounds: Bounds::default(),
+                    column,
+                }
+            })
+            .collect();
+
+        self.update_header_layout(cx);
+    }
+
+    fn update_header_layout(&mut self, cx: &mut Context<Self>) {
+        let group_rows = self.delegate.group_headers(cx);
 
         let mut layout = match group_rows.as_ref() {
             Some(rows) => Vec::with_capacity(rows.len() + 1),
@@ -625,6 +1867,27 @@ where
             .count()
     }
 
+    fn fixed_right_cols_count(&self) -> usize {
+        if !self.col_fixed {
+            return 0;
+        }
+
+        // Only the columns at the end of the table can be fixed on the right side.
+        self.col_groups
+            .iter()
+            .rev()
+            .take_while(|col| col.column.fixed == Some(ColumnFixed::Right))
+            .count()
+    }
+
+    /// Returns the index of the first right fixed column, that is also the end of the
+    /// scrollable columns.
+    fn fixed_right_cols_start(&self) -> usize {
+        self.col_groups
+            .len()
+            .saturating_sub(self.fixed_right_cols_count())
+    }
+
     fn page_item_count(&self) -> usize {
         let row_height = self.options.size.table_row_height();
         let height = self.bounds.size.height;
@@ -673,6 +1936,20 @@ where
             return;
         }
 
+        let modifiers = e.modifiers();
+        if self.multi_selectable && modifiers.shift {
+            // Select the range from the anchor row to the clicked row.
+            let anchor = self.selection_anchor.unwrap_or(row_ix);
+            let range = anchor.min(row_ix)..=anchor.max(row_ix);
+            self.update_selected_rows(range.collect(), cx);
+            self.set_active_row(row_ix, cx);
+            return;
+        } else if self.multi_selectable && modifiers.secondary() {
+            self.toggle_row_selected(row_ix, cx);
+            self.set_active_row(row_ix, cx);
+            return;
+        }
+
         self.set_selected_row(row_ix, cx);
 
         if e.click_count() == 2 {
@@ -701,7 +1978,7 @@ where
         e: &ClickEvent,
         row_ix: usize,
         col_ix: usize,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         if !self.cell_selectable {
@@ -710,8 +1987,30 @@ where
 
         cx.stop_propagation();
 
+        // Clicks inside the editor are handled by the editor itself.
+        if self.editing_cell() == Some((row_ix, col_ix)) {
+            return;
+        }
+        self.commit_cell_editing(window, cx);
+
         let is_double_click = e.click_count() == 2;
 
+        // The mouse is released on the cell where the dragging started.
+        let drag_start = self.cell_drag_start.take();
+        if drag_start.is_some() && drag_start == self.cell_range_anchor {
+            self.set_selected_cell(row_ix, col_ix, cx);
+            return;
+        }
+
+        // Shift + click to select the range from the anchor cell to the clicked cell.
+        if e.modifiers().shift && self.selection_mode.is_cell() {
+            if let Some(active) = self.selected_cell {
+                let anchor = self.cell_range_anchor.unwrap_or(active);
+                self.set_selected_cell_range(anchor, (row_ix, col_ix), cx);
+                return;
+            }
+        }
+
         // When the row header column is hidden, a single click on the
         // already-selected cell escalates the selection to the entire row —
         // giving users a way to pick rows without the dedicated header column.
@@ -730,14 +2029,69 @@ where
 
         if is_double_click {
             cx.emit(TableEvent::DoubleClickedCell(row_ix, col_ix));
+            self.edit_cell(row_ix, col_ix, window, cx);
+        }
+    }
+
+    fn on_cell_mouse_down(
+        &mut self,
+        e: &MouseDownEvent,
+        row_ix: usize,
+        col_ix: usize,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+        if e.modifiers.shift || self.editing_cell() == Some((row_ix, col_ix)) {
+            return;
         }
+
+        self.cell_drag_start = Some((row_ix, col_ix));
+    }
+
+    /// Select the range from the cell where the mouse is pressed down to the hovered cell.
+    fn on_cell_mouse_move(
+        &mut self,
+        e: &MouseMoveEvent,
+        row_ix: usize,
+        col_ix: usize,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(start) = self.cell_drag_start else {
+            return;
+        };
+
+        if e.pressed_button != Some(MouseButton::Left) {
+            self.cell_drag_start = None;
+            return;
+        }
+
+        let is_dragging = self.selection_mode.is_cell() && self.cell_range_anchor == Some(start);
+        if !is_dragging && start == (row_ix, col_ix) {
+            return;
+        }
+        if is_dragging && self.selected_cell == Some((row_ix, col_ix)) {
+            return;
+        }
+
+        self.set_selected_cell_range(start, (row_ix, col_ix), cx);
     }
 
     fn has_selection(&self) -> bool {
         self.selected_row.is_some() || self.selected_col.is_some() || self.selected_cell.is_some()
     }
 
-    pub(super) fn action_cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+    pub(super) fn action_cancel(
+        &mut self,
+        _: &Cancel,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.editing_cell.is_some() {
+            self.cancel_cell_editing(window, cx);
+            return;
+        }
+
         if self.has_selection() {
             self.clear_selection(cx);
             return;
@@ -748,9 +2102,11 @@ where
     pub(super) fn action_select_prev(
         &mut self,
         _: &SelectUp,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.commit_cell_editing(window, cx);
+
         let rows_count = self.delegate.rows_count(cx);
         if rows_count < 1 {
             return;
@@ -759,7 +2115,9 @@ where
         // Cell selection mode: move up within the same column
         if self.selection_mode.is_cell() {
             if let Some((row_ix, col_ix)) = self.selected_cell {
-                let new_row = if row_ix > 0 {
+                let new_row = if self.is_grouped() {
+                    self.step_grouped_row(Some(row_ix), true).unwrap_or(row_ix)
+                } else if row_ix > 0 {
                     row_ix.saturating_sub(1)
                 } else if self.loop_selection {
                     rows_count.saturating_sub(1)
@@ -775,6 +2133,13 @@ where
         }
 
         // Row selection mode
+        if self.is_grouped() {
+            if let Some(row_ix) = self.step_grouped_row(self.selected_row, true) {
+                self.set_selected_row(row_ix, cx);
+            }
+            return;
+        }
+
         let mut selected_row = self.selected_row.unwrap_or(0);
         if selected_row > 0 {
             selected_row = selected_row.saturating_sub(1);
@@ -790,9 +2155,11 @@ where
     pub(super) fn action_select_next(
         &mut self,
         _: &SelectDown,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.commit_cell_editing(window, cx);
+
         let rows_count = self.delegate.rows_count(cx);
         if rows_count < 1 {
             return;
@@ -801,7 +2168,9 @@ where
         // Cell selection mode: move down within the same column
         if self.selection_mode.is_cell() {
             if let Some((row_ix, col_ix)) = self.selected_cell {
-                let new_row = if row_ix < rows_count.saturating_sub(1) {
+                let new_row = if self.is_grouped() {
+                    self.step_grouped_row(Some(row_ix), false).unwrap_or(row_ix)
+                } else if row_ix < rows_count.saturating_sub(1) {
                     row_ix + 1
                 } else if self.loop_selection {
                     0
@@ -817,6 +2186,13 @@ where
         }
 
         // Row selection mode
+        if self.is_grouped() {
+            if let Some(row_ix) = self.step_grouped_row(self.selected_row, false) {
+                self.set_selected_row(row_ix, cx);
+            }
+            return;
+        }
+
         let selected_row = match self.selected_row {
             Some(selected_row) if selected_row < rows_count.saturating_sub(1) => selected_row + 1,
             Some(selected_row) => {
@@ -879,9 +2255,11 @@ where
     pub(super) fn action_select_page_up(
         &mut self,
         _: &SelectPageUp,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.commit_cell_editing(window, cx);
+
         let step = self.page_item_count();
 
         // Cell selection mode: move up by page within the same column
@@ -905,9 +2283,11 @@ where
     pub(super) fn action_select_page_down(
         &mut self,
         _: &SelectPageDown,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.commit_cell_editing(window, cx);
+
         let rows_count = self.delegate.rows_count(cx);
         if rows_count == 0 {
             return;
@@ -938,9 +2318,18 @@ where
     pub(super) fn action_select_prev_col(
         &mut self,
         _: &SelectPrevColumn,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        // Tab while editing: commit and edit the previous editable cell.
+        if let Some((row_ix, col_ix)) = self.editing_cell() {
+            self.commit_cell_editing(window, cx);
+            if let Some((row_ix, col_ix)) = self.next_editable_cell(row_ix, col_ix, true, cx) {
+                self.edit_cell(row_ix, col_ix, window, cx);
+            }
+            return;
+        }
+
         let columns_count = self.delegate.columns_count(cx);
 
         // Cell selection mode: move left within the same row
@@ -961,6 +2350,18 @@ where
             return;
         }
 
+        // Tree mode: collapse the selected row, or move to the parent row
+        if self.tree && self.selection_mode.is_row() {
+            if let Some(row_ix) = self.selected_row {
+                if self.delegate.is_children_expanded(row_ix, cx) {
+                    self.set_children_expanded(row_ix, false, window, cx);
+                } else if let Some(parent_ix) = self.parent_row(row_ix, cx) {
+                    self.set_selected_row(parent_ix, cx);
+                }
+                return;
+            }
+        }
+
         // Column selection mode
         let mut selected_col = self.selected_col.unwrap_or(0);
         if selected_col > 0 {
@@ -976,9 +2377,18 @@ where
     pub(super) fn action_select_next_col(
         &mut self,
         _: &SelectNextColumn,
-        _: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        // Tab while editing: commit and edit the next editable cell.
+        if let Some((row_ix, col_ix)) = self.editing_cell() {
+            self.commit_cell_editing(window, cx);
+            if let Some((row_ix, col_ix)) = self.next_editable_cell(row_ix, col_ix, false, cx) {
+                self.edit_cell(row_ix, col_ix, window, cx);
+            }
+            return;
+        }
+
         let columns_count = self.delegate.columns_count(cx);
 
         // Cell selection mode: move right within the same row
@@ -999,17 +2409,136 @@ where
             return;
         }
 
-        // Column selection mode
-        let mut selected_col = self.selected_col.unwrap_or(0);
-        if selected_col < columns_count.saturating_sub(1) {
-            selected_col += 1;
-        } else {
-            if self.loop_selection {
-                selected_col = 0;
-            }
+        // Tree mode: expand the selected row, or move to the first child row
+        if self.tree && self.selection_mode.is_row() {
+            if let Some(row_ix) = self.selected_row {
+                if self.delegate.has_children(row_ix, cx) {
+                    if !self.delegate.is_children_expanded(row_ix, cx) {
+                        self.set_children_expanded(row_ix, true, window, cx);
+                    } else if row_ix + 1 < self.delegate.rows_count(cx)
+                        && self.delegate.row_depth(row_ix + 1, cx)
+                            > self.delegate.row_depth(row_ix, cx)
+                    {
+                        self.set_selected_row(row_ix + 1, cx);
+                    }
+                }
+                return;
+            }
+        }
+
+        // Column selection mode
+        let mut selected_col = self.selected_col.unwrap_or(0);
+        if selected_col < columns_count.saturating_sub(1) {
+            selected_col += 1;
+        } else {
+            if self.loop_selection {
+                selected_col = 0;
+            }
+        }
+
+        self.set_selected_col(selected_col, cx);
+    }
+
+    pub(super) fn action_select_all_rows(
+        &mut self,
+        _: &SelectAllRows,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.multi_selectable {
+            cx.propagate();
+            return;
+        }
+
+        self.select_all(cx);
+    }
+
+    pub(super) fn action_edit_cell(
+        &mut self,
+        _: &EditCell,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.selection_mode.is_cell() || self.editing_cell.is_some() {
+            cx.propagate();
+            return;
+        }
+
+        let Some((row_ix, col_ix)) = self.selected_cell else {
+            cx.propagate();
+            return;
+        };
+
+        self.edit_cell(row_ix, col_ix, window, cx);
+    }
+
+    pub(super) fn action_extend_selection_up(
+        &mut self,
+        _: &ExtendSelectionUp,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.extend_cell_range_or_propagate(-1, 0, cx);
+    }
+
+    pub(super) fn action_extend_selection_down(
+        &mut self,
+        _: &ExtendSelectionDown,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.extend_cell_range_or_propagate(1, 0, cx);
+    }
+
+    pub(super) fn action_extend_selection_left(
+        &mut self,
+        _: &ExtendSelectionLeft,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.extend_cell_range_or_propagate(0, -1, cx);
+    }
+
+    pub(super) fn action_extend_selection_right(
+        &mut self,
+        _: &ExtendSelectionRight,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.extend_cell_range_or_propagate(0, 1, cx);
+    }
+
+    fn extend_cell_range_or_propagate(
+        &mut self,
+        row_offset: isize,
+        col_offset: isize,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.selection_mode.is_cell() || self.editing_cell.is_some() {
+            cx.propagate();
+            return;
+        }
+
+        self.extend_cell_range(row_offset, col_offset, cx);
+    }
+
+    pub(super) fn action_copy(
+        &mut self,
+        _: &crate::input::Copy,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.editing_cell.is_some() {
+            cx.propagate();
+            return;
         }
 
-        self.set_selected_col(selected_col, cx);
+        let Some(text) = self.selected_cells_text(cx) else {
+            cx.propagate();
+            return;
+        };
+
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
     }
 
     /// Scroll table when mouse position is near the edge of the table bounds.
@@ -1041,6 +2570,96 @@ where
 
     /// The `ix`` is the index of the col to resize,
     /// and the `size` is the new size for the col.
+    /// Resize the column at the given index to fit the content of the header and the visible cells.
+    ///
+    /// The content width is measured by the text of the cells, the [`TableDelegate::cell_text`]
+    /// formatted by the [`Column::number_format`], and clamped by the min and max width of the column.
+    ///
+    /// Emits [`TableEvent::ColumnWidthsChanged`] if changed.
+    pub fn autofit_column(&mut self, col_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.is_column_visible(col_ix) {
+            return;
+        }
+        let Some(width) = self.measure_column_content_width(col_ix, window, cx) else {
+            return;
+        };
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
+        };
+
+        let width = width.clamp(col_group.column.min_width, col_group.column.max_width);
+        if col_group.width == width {
+            return;
+        }
+
+        col_group.width = width;
+        self.measured_row_heights.clear();
+        self.update_header_layout(cx);
+        cx.emit(TableEvent::ColumnWidthsChanged(self.column_widths()));
+        cx.notify();
+    }
+
+    /// Returns the content width of the column at the given index, including the paddings.
+    fn measure_column_content_width(
+        &self,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &App,
+    ) -> Option<Pixels> {
+        let col_group = self.col_groups.get(col_ix)?;
+        let column = &col_group.column;
+        let font_size = self.cell_font_size(window);
+
+        // Leave the space for the sort icon and the filter button in the header.
+        let mut header_width = px(measure_text_width(&column.name, font_size, window));
+        if self.sortable && column.sort.is_some() {
+            header_width += px(20.);
+        }
+        if column.filter.is_some() {
+            header_width += px(24.);
+        }
+
+        let rows_count = self.delegate.rows_count(cx);
+        let rows = self.visible_range.rows.start.min(rows_count)
+            ..self.visible_range.rows.end.min(rows_count);
+        let cells_width = rows
+            .map(|row_ix| {
+                let text = self.delegate.cell_text(row_ix, col_ix, cx);
+                let text: SharedString = match &column.number_format {
+                    Some(number_format) => number_format.format_str(&text).into(),
+                    None => text.into(),
+                };
+                px(measure_text_width(&text, font_size, window))
+            })
+            .fold(px(0.), |width, cell_width| width.max(cell_width));
+
+        let padding = column
+            .paddings
+            .unwrap_or(self.options.size.table_cell_padding());
+        Some((header_width.max(cells_width) + padding.left + padding.right).ceil())
+    }
+
+    /// Returns the widths of all the columns, the width before hiding for the hidden columns.
+    fn column_widths(&self) -> Vec<Pixels> {
+        self.col_groups
+            .iter()
+            .map(|g| {
+                self.hidden_col_widths
+                    .get(&g.column.key)
+                    .copied()
+                    .unwrap_or(g.width)
+            })
+            .collect()
+    }
+
+    /// Returns the font size of the cell text.
+    fn cell_font_size(&self, window: &Window) -> Pixels {
+        match self.options.size {
+            Size::XSmall | Size::Small => rems(0.875).to_pixels(window.rem_size()),
+            _ => window.text_style().font_size.to_pixels(window.rem_size()),
+        }
+    }
+
     fn resize_cols(&mut self, ix: usize, size: Pixels, _: &mut Window, cx: &mut Context<Self>) {
         if !self.col_resizable {
             return;
@@ -1109,11 +2728,134 @@ where
         self.delegate.move_column(col_ix, to_ix, window, cx);
         let col_group = self.col_groups.remove(col_ix);
         self.col_groups.insert(to_ix, col_group);
+        if let Some(group_col) = self.group_by {
+            self.group_by = Some(move_ix(group_col, col_ix, to_ix));
+            self.grouped_rows_count = None;
+        }
+        // The widths of the column groups are changed.
+        self.update_header_layout(cx);
 
         cx.emit(TableEvent::MoveColumn(col_ix, to_ix));
         cx.notify();
     }
 
+    /// Move the row at the `row_ix` so that it ends up at the index `to_ix`.
+    ///
+    /// The selection and the expanded rows follow the moved rows.
+    pub fn move_row(
+        &mut self,
+        row_ix: usize,
+        to_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if row_ix == to_ix {
+            return;
+        }
+
+        self.delegate.move_row(row_ix, to_ix, window, cx);
+        let move_row_ix = |ix: usize| move_ix(ix, row_ix, to_ix);
+        self.selected_row = self.selected_row.map(move_row_ix);
+        self.selection_anchor = self.selection_anchor.map(move_row_ix);
+        self.right_clicked_row = self.right_clicked_row.map(move_row_ix);
+        self.selected_cell = self
+            .selected_cell
+            .map(|(row_ix, col_ix)| (move_row_ix(row_ix), col_ix));
+        self.cell_range_anchor = None;
+        self.expanded_rows = self
+            .expanded_rows
+            .iter()
+            .copied()
+            .map(move_row_ix)
+            .collect();
+        self.measured_row_heights.clear();
+        let selected_rows = self
+            .selected_rows
+            .iter()
+            .copied()
+            .map(move_row_ix)
+            .collect();
+        self.update_selected_rows(selected_rows, cx);
+
+        cx.emit(TableEvent::MoveRow(row_ix, to_ix));
+        cx.notify();
+    }
+
+    /// Returns the scroll handle of the table body.
+    fn body_scroll_handle(&self) -> ScrollHandle {
+        if self.is_virtual_list() {
+            self.expandable_scroll_handle.base_handle().clone()
+        } else {
+            self.vertical_scroll_handle.0.borrow().base_handle.clone()
+        }
+    }
+
+    /// Update the insertion gap when dragging a row over the table body,
+    /// and scroll the body when the mouse is near the top or bottom edge.
+    fn on_row_drag_move(
+        &mut self,
+        e: &DragMoveEvent<DragRow>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if e.drag(cx).entity_id != cx.entity_id() {
+            return;
+        }
+
+        let position = e.event.position;
+        if !e.bounds.contains(&position) {
+            if self.row_drag_gap.take().is_some() {
+                cx.notify();
+            }
+            return;
+        }
+
+        let edge = self.options.size.table_row_height();
+        let scroll_handle = self.body_scroll_handle();
+        let mut offset = scroll_handle.offset();
+        if position.y < e.bounds.top() + edge {
+            offset.y = (offset.y + px(4.)).min(px(0.));
+        } else if position.y > e.bounds.bottom() - edge {
+            offset.y = (offset.y - px(4.)).max(-scroll_handle.max_offset().y);
+        }
+        if offset != scroll_handle.offset() {
+            scroll_handle.set_offset(offset);
+            cx.notify();
+        }
+    }
+
+    /// Update the insertion gap when dragging a row over the row at the `row_ix`.
+    fn on_row_drag_move_over(
+        &mut self,
+        e: &DragMoveEvent<DragRow>,
+        row_ix: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let drag = e.drag(cx);
+        if drag.entity_id != cx.entity_id() || !e.bounds.contains(&e.event.position) {
+            return;
+        }
+
+        let gap = row_drag_gap(row_ix, drag.row_ix, e.event.position.y, e.bounds);
+        if self.row_drag_gap != gap {
+            self.row_drag_gap = gap;
+            cx.notify();
+        }
+    }
+
+    fn on_row_drop(&mut self, drag: &DragRow, window: &mut Window, cx: &mut Context<Self>) {
+        if drag.entity_id != cx.entity_id() {
+            return;
+        }
+
+        // Insert the dragged row into the indicated gap.
+        let Some(gap) = self.row_drag_gap.take() else {
+            return;
+        };
+        let to_ix = if drag.row_ix < gap { gap - 1 } else { gap };
+        self.move_row(drag.row_ix, to_ix, window, cx);
+    }
+
     /// Resolve the insertion gap for a column-header drag at the window
     /// coordinate `x`, or `None` when dropping there would not move the
     /// dragged column at `drag_col_ix`.
@@ -1123,8 +2865,13 @@ where
         // Columns scrolled beneath the fixed region keep stale bounds, so
         // resolve `x` against the fixed columns alone when it falls in that
         // region, and against the visible scrollable columns otherwise.
+        let right_start = self.fixed_right_cols_start();
         let candidates = if fixed_count > 0 && x < self.fixed_head_cols_bounds.right() {
             0..fixed_count
+        } else if right_start < self.col_groups.len()
+            && x >= self.fixed_right_head_cols_bounds.left()
+        {
+            right_start..self.col_groups.len()
         } else {
             self.calculate_visible_leaf_col_range(fixed_count).0
         };
@@ -1139,14 +2886,27 @@ where
         }
 
         // No gap if dropping there would put the dragged column back to
-        // where it already is.
-        if gap == drag_col_ix || gap == drag_col_ix + 1 {
+        // where it already is, or move it out of its column groups.
+        if gap == drag_col_ix
+            || gap == drag_col_ix + 1
+            || !is_gap_in_groups(&self.column_group_ranges(), drag_col_ix, gap)
+        {
             None
         } else {
             Some(gap)
         }
     }
 
+    /// Returns the ranges of the leaf columns of all the column groups in the header.
+    fn column_group_ranges(&self) -> Vec<Range<usize>> {
+        self.header_layout
+            .iter()
+            .flatten()
+            .filter(|cell| !cell.is_leaf)
+            .map(|cell| cell.start_leaf_col_ix..cell.start_leaf_col_ix + cell.col_span)
+            .collect()
+    }
+
     /// Dispatch delegate's `load_more` method when the visible range is near the end.
     fn load_more_if_need(
         &mut self,
@@ -1211,8 +2971,15 @@ where
             return div();
         };
 
+        // The hidden column keeps an empty cell, to keep the column indices.
+        if self.hidden_cols.contains(&col_group.column.key) {
+            return div().w_0().h_full().flex_shrink_0().overflow_hidden();
+        }
+
         let col_width = col_group.width;
         let col_padding = col_group.column.paddings;
+        let align = col_group.column.align;
+        let ellipsis = col_group.column.ellipsis;
 
         div()
             .w(col_width)
@@ -1229,6 +2996,12 @@ where
                     .pb(padding.bottom),
                 None => this,
             })
+            .map(|this| match align {
+                TextAlign::Center => this.text_center(),
+                TextAlign::Right => this.text_right(),
+                TextAlign::Left => this,
+            })
+            .when(ellipsis, |this| this.text_ellipsis())
     }
 
     /// Show Column selection style, when the column is selected and the selection state is Column.
@@ -1273,10 +3046,12 @@ where
                 .col_groups
                 .get(ix)
                 .map(|col| col.is_resizable())
-                .unwrap_or(false);
+                .unwrap_or(false)
+            && self.is_column_visible(ix);
         if !resizable {
             return div().into_any_element();
         }
+        let auto_fit = self.col_groups[ix].column.auto_fit;
 
         let group_id = SharedString::from(format!("resizable-handle:{}", ix));
 
@@ -1338,6 +3113,13 @@ where
                 cx.stop_propagation();
                 cx.new(|_| drag.clone())
             })
+            .when(auto_fit, |this| {
+                this.on_click(cx.listener(move |view, e: &ClickEvent, window, cx| {
+                    if e.click_count() == 2 {
+                        view.autofit_column(ix, window, cx);
+                    }
+                }))
+            })
             .on_mouse_up_out(
                 MouseButton::Left,
                 cx.listener(|view, _, _, cx| {
@@ -1346,9 +3128,10 @@ where
                     }
 
                     view.resizing_col = None;
+                    // The text may wrap differently with the new widths.
+                    view.measured_row_heights.clear();
 
-                    let new_widths = view.col_groups.iter().map(|g| g.width).collect();
-                    cx.emit(TableEvent::ColumnWidthsChanged(new_widths));
+                    cx.emit(TableEvent::ColumnWidthsChanged(view.column_widths()));
                     cx.notify();
                 }),
             )
@@ -1423,6 +3206,126 @@ where
         )
     }
 
+    /// Render the filter button of the column, to edit the filter in a popover.
+    fn render_filter_button(
+        &self,
+        col_ix: usize,
+        col_group: &ColGroup,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let filter = col_group.column.filter.as_ref()?;
+        let key = col_group.column.key.clone();
+        let value = self.column_filter_value(&key).cloned();
+        let view = cx.entity();
+
+        let editing = self
+            .filter_editing
+            .as_ref()
+            .filter(|editing| editing.key == key);
+        let content = match (filter, editing) {
+            (ColumnFilter::Text, Some(editing)) => editing
+                .inputs
+                .first()
+                .map(|input| Input::new(input).small().cleanable(true).into_any_element()),
+            (ColumnFilter::Number, Some(editing)) => Some(
+                h_flex()
+                    .gap_1()
+                    .children(
+                        editing
+                            .inputs
+                            .iter()
+                            .map(|input| Input::new(input).small().into_any_element()),
+                    )
+                    .into_any_element(),
+            ),
+            (ColumnFilter::Enum(options), Some(_)) => {
+                let checked = match &value {
+                    Some(FilterValue::In(checked)) => checked.clone(),
+                    _ => vec![],
+                };
+                Some(
+                    v_flex()
+                        .gap_2()
+                        .children(options.iter().enumerate().map(|(ix, option)| {
+                            let is_checked = checked.contains(option);
+                            let mut new_checked = checked.clone();
+                            if is_checked {
+                                new_checked.retain(|checked| checked != option);
+                            } else {
+                                new_checked.push(option.clone());
+                            }
+                            let view = view.clone();
+                            let key = key.clone();
+                            Checkbox::new(("filter-option", ix))
+                                .small()
+                                .label(option.clone())
+                                .checked(is_checked)
+                                .on_click(move |_, _, cx| {
+                                    let value = FilterValue::In(new_checked.clone());
+                                    view.update(cx, |table, cx| {
+                                        table.set_filter(key.clone(), Some(value), cx)
+                                    });
+                                })
+                        }))
+                        .into_any_element(),
+                )
+            }
+            _ => None,
+        };
+
+        Some(
+            Popover::new(("filter", col_ix))
+                .anchor(Anchor::TopRight)
+                .trigger(
+                    Button::new(("filter-button", col_ix))
+                        .icon(
+                            Icon::new(IconName::Funnel)
+                                .size_3()
+                                .text_color(cx.theme().secondary_foreground),
+                        )
+                        .ghost()
+                        .xsmall()
+                        .when(value.is_none(), |this| this.opacity(0.5))
+                        .tooltip(t!("Table.filter")),
+                )
+                .on_open_change({
+                    let view = view.clone();
+                    move |open, window, cx| {
+                        view.update(cx, |table, cx| {
+                            if *open {
+                                table.edit_filter(col_ix, window, cx);
+                            } else {
+                                table.filter_editing = None;
+                                cx.notify();
+                            }
+                        })
+                    }
+                })
+                .child(
+                    v_flex().w(px(200.)).gap_2().children(content).child(
+                        Button::new("clear-filter")
+                            .label(t!("Table.clear_filter"))
+                            .ghost()
+                            .xsmall()
+                            .disabled(value.is_none())
+                            .on_click(move |_, window, cx| {
+                                view.update(cx, |table, cx| {
+                                    if let Some(editing) = table.filter_editing.as_ref() {
+                                        for input in editing.inputs.clone() {
+                                            input.update(cx, |input, cx| {
+                                                input.set_value("", window, cx)
+                                            });
+                                        }
+                                    }
+                                    table.set_filter(key.clone(), None, cx);
+                                })
+                            }),
+                    ),
+                ),
+        )
+    }
+
     /// Render the column header.
     /// The children must be one by one items.
     /// Because the horizontal scroll handle will use the child_item_bounds to
@@ -1455,7 +3358,8 @@ where
                                     self.options.size.table_cell_padding().right - paddings.right;
                                 this.pr(offset_pr.max(px(0.)))
                             })
-                            .children(self.render_sort_icon(col_ix, &col_group, window, cx)),
+                            .children(self.render_sort_icon(col_ix, &col_group, window, cx))
+                            .children(self.render_filter_button(col_ix, &col_group, window, cx)),
                     )
                     .when(movable, |this| {
                         this.on_drag(
@@ -1508,6 +3412,8 @@ where
 
     /// Compute the visible non-fixed leaf-column range for header rendering.
     ///
+    /// The right fixed columns are excluded from the range.
+    ///
     /// Returns `(visible_range, left_spacer_width)` where:
     /// - `visible_range` is the column-index range that should be rendered.
     /// - `left_spacer_width` is the total width of the off-screen left columns,
@@ -1519,13 +3425,14 @@ where
         &self,
         left_columns_count: usize,
     ) -> (Range<usize>, Pixels) {
-        let total_cols = self.col_groups.len();
+        let total_cols = self.fixed_right_cols_start();
 
         if self.bounds.size.width == px(0.) {
             return (left_columns_count..total_cols, px(0.));
         }
 
-        let fixed_width = self.fixed_head_cols_bounds.size.width;
+        let fixed_width =
+            self.fixed_head_cols_bounds.size.width + self.fixed_right_head_cols_bounds.size.width;
         let available_width = (self.bounds.size.width - fixed_width).max(px(0.));
         // The scroll handle offset is negative when scrolled right; negate it
         // to obtain a positive distance from the left edge of the scroll area.
@@ -1570,7 +3477,6 @@ where
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
-        let view = cx.entity().clone();
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
 
         // Header leaf-column virtualization.
@@ -1586,7 +3492,8 @@ where
         //
         // The spacers preserve the flex container's total content width so that
         // the scrollbar range stays correct.
-        let total_cols = self.col_groups.len();
+        let total_cols = self.fixed_right_cols_start();
+        let right_columns_count = self.col_groups.len() - total_cols;
         let (visible_col_range, left_spacer) =
             self.calculate_visible_leaf_col_range(left_columns_count);
 
@@ -1596,6 +3503,9 @@ where
         if left_columns_count == 0 {
             self.fixed_head_cols_bounds = Bounds::default();
         }
+        if right_columns_count == 0 {
+            self.fixed_right_head_cols_bounds = Bounds::default();
+        }
 
         let mut header = self.delegate_mut().render_header(window, cx);
         let style = header.style().clone();
@@ -1636,67 +3546,21 @@ where
                 let to_ix = if drag.col_ix < gap { gap - 1 } else { gap };
                 table.move_column(drag.col_ix, to_ix, window, cx);
             }))
+            .when(self.row_movable, |this| {
+                this.child(self.render_row_drag_handle_cell(None, cx))
+            })
+            .when(self.multi_selectable, |this| {
+                this.child(self.render_checkbox_cell(None, cx))
+            })
+            .when(self.expandable, |this| {
+                this.child(self.render_expand_toggle_cell(None, cx))
+            })
             .when(self.cell_selectable && self.row_header, |this| {
                 this.child(self.render_row_header_cell(0, true, cx))
             })
             .when(left_columns_count > 0, |this| {
-                let view = view.clone();
                 // Render left fixed columns
-                this.child(
-                    h_flex()
-                        .relative()
-                        .h_full()
-                        .bg(cx.theme().tokens.table_head)
-                        .child(v_flex().min_w_full().flex_shrink_0().children(
-                            layout.iter().enumerate().map(|(_row_ix, row_cells)| {
-                                h_flex()
-                                    .min_w_full()
-                                    .h(self.options.size.table_row_height())
-                                    .border_b_1()
-                                    .border_color(cx.theme().border)
-                                    .children(row_cells.iter().filter_map(|cell| {
-                                        if cell.start_leaf_col_ix < left_columns_count {
-                                            if cell.is_leaf {
-                                                if let Some(ix) = cell.leaf_col_ix {
-                                                    return Some(
-                                                        self.render_th(ix, window, cx)
-                                                            .into_any_element(),
-                                                    );
-                                                }
-                                            } else {
-                                                return Some(
-                                                    self.delegate_mut()
-                                                        .render_group_th(
-                                                            &cell.label,
-                                                            cell.col_span,
-                                                            cell.width,
-                                                            window,
-                                                            cx,
-                                                        )
-                                                        .into_any_element(),
-                                                );
-                                            }
-                                        }
-                                        None
-                                    }))
-                            }),
-                        ))
-                        .child(
-                            // Fixed columns border
-                            div()
-                                .absolute()
-                                .top_0()
-                                .right_0()
-                                .bottom_0()
-                                .w_0()
-                                .flex_shrink_0()
-                                .border_r_1()
-                                .border_color(cx.theme().border),
-                        )
-                        .on_prepaint(move |bounds, _, cx| {
-                            view.update(cx, |r, _| r.fixed_head_cols_bounds = bounds)
-                        }),
-                )
+                this.child(self.render_fixed_head_cols(0..left_columns_count, false, window, cx))
             })
             .child(
                 // Columns
@@ -1751,7 +3615,9 @@ where
                                         // Group header rows have far fewer cells (one per group),
                                         // so the cost of rendering all of them is negligible.
                                         this.children(row_cells.iter().filter_map(|cell| {
-                                            if cell.start_leaf_col_ix >= left_columns_count {
+                                            if cell.start_leaf_col_ix >= left_columns_count
+                                                && cell.start_leaf_col_ix < total_cols
+                                            {
                                                 if cell.is_leaf {
                                                     if let Some(ix) = cell.leaf_col_ix {
                                                         return Some(
@@ -1781,6 +3647,261 @@ where
                         }),
                     )),
             )
+            .when(right_columns_count > 0, |this| {
+                // Render right fixed columns
+                this.child(self.render_fixed_head_cols(
+                    total_cols..self.col_groups.len(),
+                    true,
+                    window,
+                    cx,
+                ))
+            })
+            .when(self.column_picker, |this| {
+                this.relative().child(self.render_column_picker(cx))
+            })
+    }
+
+    /// Render the gear button to show or hide the columns, at the right of the header.
+    fn render_column_picker(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity().downgrade();
+
+        h_flex()
+            .absolute()
+            .top_0()
+            .right_0()
+            .h(self.options.size.table_row_height())
+            .px_1()
+            .items_center()
+            .bg(cx.theme().tokens.table_head)
+            .border_l_1()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                Button::new("column-picker")
+                    .icon(IconName::Settings2)
+                    .ghost()
+                    .xsmall()
+                    .tooltip(t!("Table.columns"))
+                    .dropdown_menu_with_anchor(Anchor::TopRight, move |mut menu, _, cx| {
+                        let Some(view) = view.upgrade() else {
+                            return menu;
+                        };
+
+                        let table = view.read(cx);
+                        for (col_ix, col_group) in table.col_groups.iter().enumerate() {
+                            let visible = table.is_column_visible(col_ix);
+                            let view = view.clone();
+                            menu = menu.item(
+                                PopupMenuItem::new(col_group.column.name.clone())
+                                    .checked(visible)
+                                    .on_click(move |_, _, cx| {
+                                        view.update(cx, |table, cx| {
+                                            table.set_column_visible(col_ix, !visible, cx);
+                                        })
+                                    }),
+                            );
+                        }
+                        let view = view.clone();
+                        menu.separator()
+                            .item(PopupMenuItem::new(t!("Table.export_csv")).on_click(
+                                move |_, window, cx| {
+                                    view.update(cx, |table, cx| {
+                                        table.prompt_export_csv(
+                                            &std::env::home_dir().unwrap_or_default(),
+                                            &CsvExportOptions::new().visible_only(true),
+                                            window,
+                                            cx,
+                                        );
+                                    })
+                                },
+                            ))
+                            .scrollable(true)
+                    }),
+            )
+    }
+
+    /// Render the head of the fixed columns in the given range, at the left or right side.
+    fn render_fixed_head_cols(
+        &mut self,
+        cols: Range<usize>,
+        is_right: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let view = cx.entity().clone();
+        let layout = self.header_layout.clone();
+
+        h_flex()
+            .relative()
+            .h_full()
+            .flex_shrink_0()
+            .bg(cx.theme().tokens.table_head)
+            .child(
+                v_flex()
+                    .min_w_full()
+                    .flex_shrink_0()
+                    .children(layout.iter().map(|row_cells| {
+                        h_flex()
+                            .min_w_full()
+                            .h(self.options.size.table_row_height())
+                            .border_b_1()
+                            .border_color(cx.theme().border)
+                            .children(row_cells.iter().filter_map(|cell| {
+                                if !cols.contains(&cell.start_leaf_col_ix) {
+                                    return None;
+                                }
+
+                                if cell.is_leaf {
+                                    let ix = cell.leaf_col_ix?;
+                                    Some(self.render_th(ix, window, cx).into_any_element())
+                                } else {
+                                    Some(
+                                        self.delegate_mut()
+                                            .render_group_th(
+                                                &cell.label,
+                                                cell.col_span,
+                                                cell.width,
+                                                window,
+                                                cx,
+                                            )
+                                            .into_any_element(),
+                                    )
+                                }
+                            }))
+                    })),
+            )
+            .child(self.render_fixed_cols_border(is_right, cx))
+            .on_prepaint(move |bounds, _, cx| {
+                view.update(cx, |r, _| {
+                    if is_right {
+                        r.fixed_right_head_cols_bounds = bounds;
+                    } else {
+                        r.fixed_head_cols_bounds = bounds;
+                    }
+                })
+            })
+    }
+
+    /// Render the footer row, pinned below the rows.
+    fn render_table_footer(
+        &mut self,
+        left_columns_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
+        let right_columns_start = self.fixed_right_cols_start();
+        let columns_count = self.col_groups.len();
+
+        h_flex()
+            .id("table-footer")
+            .w_full()
+            .h(self.options.size.table_row_height())
+            .flex_shrink_0()
+            .overflow_hidden()
+            .bg(cx.theme().tokens.table_head)
+            .text_color(cx.theme().table_head_foreground)
+            .border_t_1()
+            .border_color(cx.theme().border)
+            .when(self.row_movable, |this| {
+                this.child(self.render_row_drag_handle_cell(None, cx))
+            })
+            .when(self.multi_selectable, |this| {
+                this.child(self.render_empty_checkbox_cell())
+            })
+            .when(self.expandable, |this| {
+                this.child(self.render_expand_toggle_cell(None, cx))
+            })
+            .when(self.cell_selectable && self.row_header, |this| {
+                this.child(
+                    div()
+                        .w(px(40.))
+                        .h_full()
+                        .flex_shrink_0()
+                        .table_cell_size(self.options.size),
+                )
+            })
+            .when(left_columns_count > 0, |this| {
+                this.child(self.render_fixed_footer_cells(0..left_columns_count, false, window, cx))
+            })
+            .child(
+                h_flex()
+                    .flex_1()
+                    .h_full()
+                    .overflow_hidden()
+                    .children((left_columns_count..right_columns_start).map(|col_ix| {
+                        h_flex()
+                            .h_full()
+                            .left(horizontal_scroll_handle.offset().x)
+                            .child(self.render_footer_cell(col_ix, window, cx))
+                    }))
+                    .child(
+                        h_flex()
+                            .h_full()
+                            .left(horizontal_scroll_handle.offset().x)
+                            .child(self.delegate.render_last_empty_col(window, cx)),
+                    ),
+            )
+            .when(right_columns_start < columns_count, |this| {
+                this.child(self.render_fixed_footer_cells(
+                    right_columns_start..columns_count,
+                    true,
+                    window,
+                    cx,
+                ))
+            })
+    }
+
+    /// Render the footer cells of the fixed columns in the given range, at the left or right side.
+    fn render_fixed_footer_cells(
+        &mut self,
+        cols: Range<usize>,
+        is_right: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        h_flex()
+            .relative()
+            .h_full()
+            .flex_shrink_0()
+            .children(cols.map(|col_ix| self.render_footer_cell(col_ix, window, cx)))
+            .child(self.render_fixed_cols_border(is_right, cx))
+    }
+
+    fn render_footer_cell(
+        &mut self,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let cell = self.render_cell(None, col_ix, window, cx);
+        if !self.is_column_visible(col_ix) {
+            return cell;
+        }
+
+        cell.child(
+            self.delegate_mut()
+                .render_footer_cell(col_ix, window, cx)
+                .into_any_element(),
+        )
+    }
+
+    /// Render the border between the fixed columns and the scrollable columns.
+    fn render_fixed_cols_border(&self, is_right: bool, cx: &App) -> Div {
+        div()
+            .absolute()
+            .top_0()
+            .bottom_0()
+            .w_0()
+            .flex_shrink_0()
+            .border_color(cx.theme().border)
+            .map(|this| {
+                if is_right {
+                    this.left_0().border_l_1()
+                } else {
+                    this.right_0().border_r_1()
+                }
+            })
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1797,20 +3918,40 @@ where
     ) -> Stateful<Div> {
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
         let is_stripe_row = self.options.stripe && row_ix % 2 != 0;
-        let is_selected = self.selected_row == Some(row_ix);
+        let is_selected = self.selected_row == Some(row_ix) || self.selected_rows.contains(&row_ix);
         let view = cx.entity().clone();
         let row_height = self.options.size.table_row_height();
+        let right_columns_count = self.fixed_right_cols_count();
 
-        if row_ix < rows_count {
+        if row_ix < rows_count && self.is_skeleton_row(row_ix, cx) {
+            self.render_skeleton_row(row_ix, columns_count, window, cx)
+        } else if row_ix < rows_count {
             let is_last_row = row_ix + 1 == rows_count;
             let need_render_border = is_selected || !is_last_row || !is_filled;
 
             let mut tr = self.delegate.render_tr(row_ix, window, cx);
             let style = tr.style().clone();
 
+            let measure_row_height =
+                self.variable_row_height && self.delegate.row_height(row_ix, cx).is_none();
+
             tr.h_flex()
                 .w_full()
-                .h(row_height)
+                .map(|this| {
+                    if self.variable_row_height {
+                        this.min_h(row_height)
+                    } else {
+                        this.h(row_height)
+                    }
+                })
+                .when(measure_row_height, |this| {
+                    let view = view.clone();
+                    this.on_prepaint(move |bounds, _, cx| {
+                        view.update(cx, |table, cx| {
+                            table.update_measured_row_height(row_ix, bounds.size.height, cx)
+                        })
+                    })
+                })
                 .when(need_render_border, |this| {
                     this.border_b_1().border_color(cx.theme().table_row_border)
                 })
@@ -1823,101 +3964,32 @@ where
                         this.bg(cx.theme().tokens.table_hover)
                     }
                 })
+                .when(self.row_movable, |this| {
+                    this.child(self.render_row_drag_handle_cell(Some(row_ix), cx))
+                        .on_drag_move(cx.listener(
+                            move |table, e: &DragMoveEvent<DragRow>, _, cx| {
+                                table.on_row_drag_move_over(e, row_ix, cx)
+                            },
+                        ))
+                })
+                .when(self.multi_selectable, |this| {
+                    this.child(self.render_checkbox_cell(Some(row_ix), cx))
+                })
+                .when(self.expandable, |this| {
+                    this.child(self.render_expand_toggle_cell(Some(row_ix), cx))
+                })
                 .when(self.cell_selectable && self.row_header, |this| {
                     this.child(self.render_row_header_cell(row_ix, false, cx))
                 })
                 .when(left_columns_count > 0, |this| {
                     // Left fixed columns
-                    this.child(
-                        h_flex()
-                            .relative()
-                            .h_full()
-                            .children({
-                                let mut items = Vec::with_capacity(left_columns_count);
-
-                                (0..left_columns_count).for_each(|col_ix| {
-                                    let is_cell_selected = self.selected_cell
-                                        == Some((row_ix, col_ix))
-                                        && self.selection_mode.is_cell();
-                                    let is_cell_right_clicked =
-                                        self.right_clicked_cell == Some((row_ix, col_ix));
-
-                                    items.push(
-                                        self.render_col_wrap(Some(row_ix), col_ix, window, cx)
-                                            .child(
-                                                self.render_cell(Some(row_ix), col_ix, window, cx)
-                                                    .id(format!("table-cell:{}:{}", row_ix, col_ix))
-                                                    .relative()
-                                                    .child(self.measure_render_td(
-                                                        row_ix, col_ix, window, cx,
-                                                    ))
-                                                    .when(is_cell_selected, |this| {
-                                                        this.child(
-                                                            div()
-                                                                .absolute()
-                                                                .inset_0()
-                                                                .bg(cx.theme().tokens.table_active)
-                                                                .border_1()
-                                                                .border_color(
-                                                                    cx.theme().table_active_border,
-                                                                ),
-                                                        )
-                                                    })
-                                                    .when(
-                                                        is_cell_right_clicked && !is_cell_selected,
-                                                        |this| {
-                                                            this.child(
-                                                                div()
-                                                                    .absolute()
-                                                                    .inset_0()
-                                                                    .border_1()
-                                                                    .border_color(
-                                                                        cx.theme()
-                                                                            .table_active_border
-                                                                            .opacity(0.5),
-                                                                    ),
-                                                            )
-                                                        },
-                                                    )
-                                                    .when(self.cell_selectable, |this| {
-                                                        this.on_click(cx.listener(
-                                                            move |table, e, window, cx| {
-                                                                table.on_cell_click(
-                                                                    e, row_ix, col_ix, window, cx,
-                                                                );
-                                                            },
-                                                        ))
-                                                        .on_mouse_down(
-                                                            MouseButton::Right,
-                                                            cx.listener(
-                                                                move |table, e, window, cx| {
-                                                                    table.on_cell_right_click(
-                                                                        e, row_ix, col_ix, window,
-                                                                        cx,
-                                                                    );
-                                                                },
-                                                            ),
-                                                        )
-                                                    }),
-                                            ),
-                                    );
-                                });
-
-                                items
-                            })
-                            .child(
-                                // Fixed columns border
-                                div()
-                                    .absolute()
-                                    .top_0()
-                                    .right_0()
-                                    .bottom_0()
-                                    .w_0()
-                                    .flex_shrink_0()
-                                    .border_r_1()
-                                    .border_color(cx.theme().border),
-                            ),
-                    )
+                    this.child(self.render_fixed_row_cells(
+                        row_ix,
+                        0..left_columns_count,
+                        false,
+                        window,
+                        cx,
+                    ))
                 })
                 .child(
                     h_flex()
@@ -1949,6 +4021,8 @@ where
                                             let is_cell_selected = table.selected_cell
                                                 == Some((row_ix, col_ix))
                                                 && table.selection_mode.is_cell();
+                                            let is_cell_in_range =
+                                                table.is_cell_in_range(row_ix, col_ix);
                                             let is_cell_right_clicked =
                                                 table.right_clicked_cell == Some((row_ix, col_ix));
 
@@ -1986,6 +4060,14 @@ where
                                                                     ),
                                                             )
                                                         })
+                                                        .when(is_cell_in_range, |this| {
+                                                            this.child(
+                                                                div().absolute().inset_0().bg(cx
+                                                                    .theme()
+                                                                    .tokens
+                                                                    .table_active),
+                                                            )
+                                                        })
                                                         .when(
                                                             is_cell_right_clicked
                                                                 && !is_cell_selected,
@@ -2013,6 +4095,25 @@ where
                                                                     );
                                                                 },
                                                             ))
+                                                            .on_mouse_down(
+                                                                MouseButton::Left,
+                                                                cx.listener(
+                                                                    move |table, e, window, cx| {
+                                                                        table.on_cell_mouse_down(
+                                                                            e, row_ix, col_ix,
+                                                                            window, cx,
+                                                                        );
+                                                                    },
+                                                                ),
+                                                            )
+                                                            .on_mouse_move(cx.listener(
+                                                                move |table, e, window, cx| {
+                                                                    table.on_cell_mouse_move(
+                                                                        e, row_ix, col_ix, window,
+                                                                        cx,
+                                                                    );
+                                                                },
+                                                            ))
                                                             .on_mouse_down(
                                                                 MouseButton::Right,
                                                                 cx.listener(
@@ -2038,6 +4139,16 @@ where
                         )
                         .child(self.delegate.render_last_empty_col(window, cx)),
                 )
+                .when(right_columns_count > 0, |this| {
+                    // Right fixed columns
+                    this.child(self.render_fixed_row_cells(
+                        row_ix,
+                        self.col_groups.len() - right_columns_count..self.col_groups.len(),
+                        true,
+                        window,
+                        cx,
+                    ))
+                })
                 // Row selected style
                 // Note: Don't show row selection if a cell is selected
                 .when_some(self.selected_row, |this, _| {
@@ -2074,6 +4185,28 @@ where
                             .border_color(cx.theme().selection),
                     )
                 })
+                .map(|this| {
+                    // Draw the insertion indicator on the top edge of the gap row,
+                    // or on the bottom edge of the last row for the trailing gap.
+                    match self.row_drag_gap {
+                        Some(gap)
+                            if cx.has_active_drag()
+                                && (gap == row_ix || (is_last_row && gap == row_ix + 1)) =>
+                        {
+                            let bottom_side = gap == row_ix + 1;
+                            this.relative().child(
+                                div()
+                                    .absolute()
+                                    .left_0()
+                                    .right_0()
+                                    .h(px(2.))
+                                    .map(|d| if bottom_side { d.bottom_0() } else { d.top_0() })
+                                    .bg(cx.theme().drag_border),
+                            )
+                        }
+                        _ => this,
+                    }
+                })
                 .on_mouse_down(
                     MouseButton::Right,
                     cx.listener(move |this, e, window, cx| {
@@ -2093,6 +4226,15 @@ where
                 .border_b_1()
                 .border_color(cx.theme().table_row_border)
                 .when(is_stripe_row, |this| this.bg(cx.theme().tokens.table_even))
+                .when(self.row_movable, |this| {
+                    this.child(self.render_row_drag_handle_cell(None, cx))
+                })
+                .when(self.multi_selectable, |this| {
+                    this.child(self.render_empty_checkbox_cell())
+                })
+                .when(self.expandable, |this| {
+                    this.child(self.render_expand_toggle_cell(None, cx))
+                })
                 .when(self.cell_selectable && self.row_header, |this| {
                     // Render empty row header cell for fake rows
                     this.child(
@@ -2102,14 +4244,445 @@ where
                             .flex_shrink_0()
                             .table_cell_size(self.options.size),
                     )
-                })
-                .children((0..columns_count).map(|col_ix| {
-                    h_flex()
-                        .left(horizontal_scroll_handle.offset().x)
-                        .child(self.render_cell(None, col_ix, window, cx))
-                }))
-                .child(self.delegate.render_last_empty_col(window, cx))
-        }
+                })
+                .children((0..columns_count).map(|col_ix| {
+                    h_flex()
+                        .left(horizontal_scroll_handle.offset().x)
+                        .child(self.render_cell(None, col_ix, window, cx))
+                }))
+                .child(self.delegate.render_last_empty_col(window, cx))
+        }
+    }
+
+    /// Render the cells of the fixed columns in the given range, at the left or right side.
+    fn render_fixed_row_cells(
+        &mut self,
+        row_ix: usize,
+        cols: Range<usize>,
+        is_right: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        h_flex()
+            .relative()
+            .h_full()
+            .flex_shrink_0()
+            .children(cols.map(|col_ix| {
+                let is_cell_selected =
+                    self.selected_cell == Some((row_ix, col_ix)) && self.selection_mode.is_cell();
+                let is_cell_in_range = self.is_cell_in_range(row_ix, col_ix);
+                let is_cell_right_clicked = self.right_clicked_cell == Some((row_ix, col_ix));
+
+                self.render_col_wrap(Some(row_ix), col_ix, window, cx)
+                    .child(
+                        self.render_cell(Some(row_ix), col_ix, window, cx)
+                            .id(format!("table-cell:{}:{}", row_ix, col_ix))
+                            .relative()
+                            .child(self.measure_render_td(row_ix, col_ix, window, cx))
+                            .when(is_cell_selected, |this| {
+                                this.child(
+                                    div()
+                                        .absolute()
+                                        .inset_0()
+                                        .bg(cx.theme().tokens.table_active)
+                                        .border_1()
+                                        .border_color(cx.theme().table_active_border),
+                                )
+                            })
+                            .when(is_cell_in_range, |this| {
+                                this.child(
+                                    div()
+                                        .absolute()
+                                        .inset_0()
+                                        .bg(cx.theme().tokens.table_active),
+                                )
+                            })
+                            .when(is_cell_right_clicked && !is_cell_selected, |this| {
+                                this.child(
+                                    div()
+                                        .absolute()
+                                        .inset_0()
+                                        .border_1()
+                                        .border_color(cx.theme().table_active_border.opacity(0.5)),
+                                )
+                            })
+                            .when(self.cell_selectable, |this| {
+                                this.on_click(cx.listener(move |table, e, window, cx| {
+                                    table.on_cell_click(e, row_ix, col_ix, window, cx);
+                                }))
+                                .on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(move |table, e, window, cx| {
+                                        table.on_cell_mouse_down(e, row_ix, col_ix, window, cx);
+                                    }),
+                                )
+                                .on_mouse_move(cx.listener(move |table, e, window, cx| {
+                                    table.on_cell_mouse_move(e, row_ix, col_ix, window, cx);
+                                }))
+                                .on_mouse_down(
+                                    MouseButton::Right,
+                                    cx.listener(move |table, e, window, cx| {
+                                        table.on_cell_right_click(e, row_ix, col_ix, window, cx);
+                                    }),
+                                )
+                            }),
+                    )
+            }))
+            .child(self.render_fixed_cols_border(is_right, cx))
+    }
+
+    /// Render the rows in the visible range of the table body.
+    #[allow(clippy::too_many_arguments)]
+    fn render_visible_rows(
+        &mut self,
+        visible_range: Range<usize>,
+        rows_count: usize,
+        left_columns_count: usize,
+        columns_count: usize,
+        is_filled: bool,
+        loading: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        // Use `col.width` (always up-to-date) rather than
+        // `col.bounds.size.width`, which is only set after
+        // prepaint and is therefore zero on the first frame.
+        let col_sizes: Rc<Vec<gpui::Size<Pixels>>> = Rc::new(
+            self.col_groups[..self.fixed_right_cols_start()]
+                .iter()
+                .skip(left_columns_count)
+                .map(|col| gpui::Size {
+                    width: col.width,
+                    height: px(0.),
+                })
+                .collect(),
+        );
+
+        let display_rows_count = self.display_rows_count(rows_count);
+        if !loading {
+            self.load_more_if_need(display_rows_count, visible_range.end, window, cx);
+        }
+        let visible_rows = self.rows_in_display_range(visible_range.clone(), rows_count);
+        self.update_visible_range_if_need(visible_rows, Axis::Vertical, window, cx);
+        self.first_visible_display_ix = visible_range.start;
+
+        if visible_range.end > display_rows_count {
+            self.scroll_to_display_ix(
+                std::cmp::min(visible_range.start, display_rows_count.saturating_sub(1)),
+                ScrollStrategy::Top,
+            );
+            cx.notify();
+        }
+
+        let mut items = Vec::with_capacity(visible_range.end.saturating_sub(visible_range.start));
+
+        // Render fake rows to fill the table
+        visible_range.for_each(|ix| {
+            let row_ix = match self.display_row(ix, rows_count) {
+                DisplayRow::Group(group_ix) => {
+                    items.push(self.render_group_row(group_ix, cx).into_any_element());
+                    return;
+                }
+                DisplayRow::Row(row_ix) => row_ix,
+            };
+
+            // Render real rows for available data
+            let tr = self.render_table_row(
+                row_ix,
+                rows_count,
+                left_columns_count,
+                col_sizes.clone(),
+                columns_count,
+                is_filled,
+                window,
+                cx,
+            );
+
+            let expanded_height = self.expanded_height(row_ix, rows_count, cx);
+            if expanded_height > px(0.) {
+                items.push(
+                    v_flex()
+                        .w_full()
+                        .child(tr)
+                        .child(self.render_expanded_row(row_ix, expanded_height, window, cx))
+                        .into_any_element(),
+                );
+            } else {
+                items.push(tr.into_any_element());
+            }
+        });
+
+        items
+    }
+
+    /// Returns the range of the rows in the given range of the table body.
+    fn rows_in_display_range(&self, range: Range<usize>, rows_count: usize) -> Range<usize> {
+        if !self.is_grouped() {
+            return range;
+        }
+
+        range
+            .filter_map(|ix| match self.display_row(ix, rows_count) {
+                DisplayRow::Row(row_ix) if row_ix < rows_count => Some(row_ix),
+                _ => None,
+            })
+            .fold(None, |range: Option<Range<usize>>, row_ix| match range {
+                Some(range) => Some(range.start.min(row_ix)..range.end.max(row_ix + 1)),
+                None => Some(row_ix..row_ix + 1),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Render the header row of the group, click to expand or collapse the group.
+    fn render_group_row(&self, group_ix: usize, cx: &mut Context<Self>) -> Stateful<Div> {
+        let group = &self.row_groups[group_ix];
+        let key = group.key.clone();
+        let col_name = |col_ix: usize| {
+            self.col_groups
+                .get(col_ix)
+                .map(|g| g.column.name.clone())
+                .unwrap_or_default()
+        };
+        let icon = if self.collapsed_groups.contains(&key) {
+            IconName::ChevronRight
+        } else {
+            IconName::ChevronDown
+        };
+
+        h_flex()
+            .id(("table-group", group_ix))
+            .w_full()
+            .h(self.options.size.table_row_height())
+            .flex_shrink_0()
+            .gap_2()
+            .px_2()
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .cursor_pointer()
+            .bg(cx.theme().tokens.table_head)
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .child(
+                Icon::new(icon)
+                    .small()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .child(div().font_medium().child(format!(
+                "{}: {}",
+                col_name(self.group_by.unwrap_or(0)),
+                key
+            )))
+            .child(
+                div()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!("({})", group.rows.len())),
+            )
+            .children(group.aggregates.iter().map(|(col_ix, sum)| {
+                div().text_color(cx.theme().muted_foreground).child(format!(
+                    "{}: {}",
+                    col_name(*col_ix),
+                    sum
+                ))
+            }))
+            .on_click(cx.listener(move |table, _, _, cx| {
+                table.toggle_group(key.clone(), cx);
+            }))
+    }
+
+    /// Returns the height of the detail area of the row, zero if the row is not expanded.
+    fn expanded_height(&self, row_ix: usize, rows_count: usize, cx: &App) -> Pixels {
+        if self.expandable
+            && row_ix < rows_count
+            && self.expanded_rows.contains(&row_ix)
+            && self.delegate.is_row_expandable(row_ix, cx)
+        {
+            self.delegate.expanded_row_height(row_ix, cx)
+        } else {
+            px(0.)
+        }
+    }
+
+    /// Render the full-width detail area under the expanded row.
+    fn render_expanded_row(
+        &mut self,
+        row_ix: usize,
+        height: Pixels,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(("expanded-row", row_ix))
+            .w_full()
+            .h(height)
+            .overflow_hidden()
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .bg(cx.theme().tokens.table_head)
+            .child(self.delegate.render_expanded_row(row_ix, window, cx))
+    }
+
+    /// Render the checkbox cell of the multi-selection, `None` for the header to select all.
+    fn render_checkbox_cell(
+        &self,
+        row_ix: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let checked = match row_ix {
+            Some(row_ix) => self.selected_rows.contains(&row_ix),
+            None => {
+                let rows_count = self.delegate.rows_count(cx);
+                rows_count > 0 && self.selected_rows.len() == rows_count
+            }
+        };
+
+        self.render_empty_checkbox_cell().child(
+            Checkbox::new(("row-checkbox", row_ix.unwrap_or(usize::MAX)))
+                .checked(checked)
+                .tab_stop(false)
+                .on_click(cx.listener(move |table, checked: &bool, _, cx| {
+                    cx.stop_propagation();
+                    match row_ix {
+                        Some(row_ix) => table.toggle_row_selected(row_ix, cx),
+                        None if *checked => table.select_all(cx),
+                        None => table.set_selected_rows([], cx),
+                    }
+                })),
+        )
+    }
+
+    /// Render the drag handle cell of the movable row, `None` for the header and fake rows.
+    fn render_row_drag_handle_cell(
+        &self,
+        row_ix: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let entity_id = cx.entity_id();
+
+        h_flex()
+            .id(("row-drag-handle", row_ix.unwrap_or(usize::MAX)))
+            .w_6()
+            .h_full()
+            .flex_shrink_0()
+            .justify_center()
+            .text_color(cx.theme().muted_foreground)
+            .when_some(row_ix.filter(|_| !self.is_grouped()), |this, row_ix| {
+                let label = (0..self.col_groups.len())
+                    .find(|col_ix| self.is_column_visible(*col_ix))
+                    .map(|col_ix| self.delegate.cell_text(row_ix, col_ix, cx))
+                    .unwrap_or_default();
+
+                this.cursor_grab()
+                    .hover(|this| this.text_color(cx.theme().foreground))
+                    .child(Icon::new(IconName::EllipsisVertical).xsmall())
+                    .on_drag(
+                        DragRow {
+                            entity_id,
+                            row_ix,
+                            label: label.into(),
+                            width: self.bounds.size.width.min(px(450.)),
+                        },
+                        |drag, _, _, cx| {
+                            cx.stop_propagation();
+                            cx.new(|_| drag.clone())
+                        },
+                    )
+            })
+    }
+
+    fn render_empty_checkbox_cell(&self) -> Div {
+        h_flex().w_8().h_full().flex_shrink_0().justify_center()
+    }
+
+    /// Render the chevron toggle cell of the expandable row, `None` for the header and fake rows.
+    fn render_expand_toggle_cell(
+        &self,
+        row_ix: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let expandable = row_ix.map_or(false, |row_ix| self.delegate.is_row_expandable(row_ix, cx));
+
+        h_flex()
+            .id(("expand-toggle", row_ix.unwrap_or(usize::MAX)))
+            .w_7()
+            .h_full()
+            .flex_shrink_0()
+            .justify_center()
+            .text_color(cx.theme().muted_foreground)
+            .when_some(row_ix.filter(|_| expandable), |this, row_ix| {
+                let icon = if self.is_row_expanded(row_ix) {
+                    IconName::ChevronDown
+                } else {
+                    IconName::ChevronRight
+                };
+
+                this.cursor_pointer()
+                    .hover(|this| this.text_color(cx.theme().foreground))
+                    .child(Icon::new(icon).small())
+                    .on_click(cx.listener(move |table, _, _, cx| {
+                        cx.stop_propagation();
+                        table.toggle_row_expanded(row_ix, cx);
+                    }))
+            })
+    }
+
+    /// Returns true if the row is rendered as a skeleton placeholder.
+    fn is_skeleton_row(&self, row_ix: usize, cx: &App) -> bool {
+        (self.loading_skeleton && self.delegate.loading(cx))
+            || !self.delegate.is_row_loaded(row_ix, cx)
+    }
+
+    /// Render a skeleton row, the cells have the same widths as the columns.
+    fn render_skeleton_row(
+        &mut self,
+        row_ix: usize,
+        columns_count: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Stateful<Div> {
+        let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
+        let is_stripe_row = self.options.stripe && row_ix % 2 != 0;
+        let row_height = self.options.size.table_row_height();
+
+        self.delegate
+            .render_tr(row_ix, window, cx)
+            .h_flex()
+            .w_full()
+            .h(row_height)
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .when(is_stripe_row, |this| this.bg(cx.theme().tokens.table_even))
+            .when(self.row_movable, |this| {
+                this.child(self.render_row_drag_handle_cell(None, cx))
+            })
+            .when(self.multi_selectable, |this| {
+                this.child(self.render_empty_checkbox_cell())
+            })
+            .when(self.expandable, |this| {
+                this.child(self.render_expand_toggle_cell(None, cx))
+            })
+            .when(self.cell_selectable && self.row_header, |this| {
+                this.child(
+                    div()
+                        .w(px(40.))
+                        .h_full()
+                        .flex_shrink_0()
+                        .table_cell_size(self.options.size),
+                )
+            })
+            .children((0..columns_count).map(|col_ix| {
+                // Vary the widths a little to look like the real content.
+                let width = match (row_ix + col_ix) % 3 {
+                    0 => relative(0.8),
+                    1 => relative(0.6),
+                    _ => relative(0.4),
+                };
+
+                h_flex().left(horizontal_scroll_handle.offset().x).child(
+                    self.render_cell(None, col_ix, window, cx)
+                        .child(Skeleton::new().h(row_height * 0.4).w(width)),
+                )
+            }))
+            .child(self.delegate.render_last_empty_col(window, cx))
     }
 
     /// Calculate the extra rows needed to fill the table empty space when `stripe` is true.
@@ -2138,16 +4711,154 @@ where
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
         if !crate::measure_enable() {
+            return self.render_td_content(row_ix, col_ix, window, cx);
+        }
+
+        let start = std::time::Instant::now();
+        let el = self.render_td_content(row_ix, col_ix, window, cx);
+        self._measure.push(start.elapsed());
+        el
+    }
+
+    /// Render the cell content, with the indentation and the children toggle
+    /// in the first column of the tree mode.
+    fn render_td_content(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let el = self.render_td_value(row_ix, col_ix, window, cx);
+        if !self.tree || col_ix != 0 {
+            return el;
+        }
+
+        let depth = self.delegate.row_depth(row_ix, cx);
+        let has_children = self.delegate.has_children(row_ix, cx);
+        let is_loading = self.delegate.is_children_loading(row_ix, cx);
+        let is_expanded = self.delegate.is_children_expanded(row_ix, cx);
+
+        h_flex()
+            .w_full()
+            .gap_1()
+            .pl(TREE_INDENT * depth as f32)
+            .child(
+                h_flex()
+                    .id(("tree-toggle", row_ix))
+                    .size_4()
+                    .flex_shrink_0()
+                    .justify_center()
+                    .text_color(cx.theme().muted_foreground)
+                    .map(|this| {
+                        if is_loading {
+                            this.child(Spinner::new().xsmall())
+                        } else if has_children {
+                            this.cursor_pointer()
+                                .hover(|this| this.text_color(cx.theme().foreground))
+                                .child(
+                                    Icon::new(if is_expanded {
+                                        IconName::ChevronDown
+                                    } else {
+                                        IconName::ChevronRight
+                                    })
+                                    .small(),
+                                )
+                                .on_click(cx.listener(move |table, _, window, cx| {
+                                    cx.stop_propagation();
+                                    table.toggle_children(row_ix, window, cx);
+                                }))
+                        } else {
+                            this
+                        }
+                    }),
+            )
+            .child(div().flex_1().overflow_hidden().child(el))
+            .into_any_element()
+    }
+
+    /// Render the cell value by the column renderer, number format or [`TableDelegate::render_td`].
+    fn render_td_value(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        if let Some(editing) = self
+            .editing_cell
+            .as_ref()
+            .filter(|editing| editing.row_ix == row_ix && editing.col_ix == col_ix)
+        {
+            let input = editing.input.clone();
             return self
                 .delegate
-                .render_td(row_ix, col_ix, window, cx)
+                .cell_editor(row_ix, col_ix, &input, window, cx)
                 .into_any_element();
         }
 
-        let start = std::time::Instant::now();
-        let el = self.delegate.render_td(row_ix, col_ix, window, cx);
-        self._measure.push(start.elapsed());
-        el.into_any_element()
+        let Some(col_group) = self.col_groups.get(col_ix) else {
+            return div().into_any_element();
+        };
+
+        let renderer = col_group.column.renderer.clone();
+        let number_format = col_group.column.number_format.clone();
+        let ellipsis = col_group.column.ellipsis;
+        let padding = col_group
+            .column
+            .paddings
+            .unwrap_or(self.options.size.table_cell_padding());
+        let text_width = col_group.width - padding.left - padding.right;
+
+        let (el, text) = if let Some(renderer) = renderer {
+            (renderer.render(row_ix, window, cx), None)
+        } else if let Some(number_format) = number_format {
+            let text = SharedString::from(
+                number_format.format_str(&self.delegate.cell_text(row_ix, col_ix, cx)),
+            );
+            (text.clone().into_any_element(), Some(text))
+        } else {
+            (
+                self.delegate
+                    .render_td(row_ix, col_ix, window, cx)
+                    .into_any_element(),
+                None,
+            )
+        };
+
+        if !ellipsis {
+            return el;
+        }
+
+        let text = text.unwrap_or_else(|| self.delegate.cell_text(row_ix, col_ix, cx).into());
+        if text.is_empty() {
+            return el;
+        }
+        let font_size = self.cell_font_size(window);
+        let width = match self.cell_text_widths.get(&(row_ix, col_ix)) {
+            Some((measured_text, measured_font_size, width))
+                if *measured_text == text && *measured_font_size == font_size =>
+            {
+                *width
+            }
+            _ => {
+                let width = measure_text_width(&text, font_size, window);
+                self.cell_text_widths
+                    .insert((row_ix, col_ix), (text.clone(), font_size, width));
+                width
+            }
+        };
+        if width <= text_width.as_f32() {
+            return el;
+        }
+
+        div()
+            .id(("cell-ellipsis", col_ix))
+            .w_full()
+            .overflow_hidden()
+            .child(el)
+            .tooltip(move |window, cx| Tooltip::new(text.clone()).build(window, cx))
+            .into_any_element()
     }
 
     fn measure(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
@@ -2179,14 +4890,29 @@ where
         _: &mut Context<Self>,
     ) -> Option<impl IntoElement> {
         let header_rows = self.header_layout.len().max(1);
+        let footer_height = if self.footer {
+            self.options.size.table_row_height()
+        } else {
+            px(0.)
+        };
         Some(
             div()
                 .absolute()
                 .top(self.options.size.table_row_height() * header_rows as f32)
                 .right_0()
-                .bottom_0()
+                .bottom(footer_height)
                 .w(Scrollbar::width())
-                .child(Scrollbar::vertical(&self.vertical_scroll_handle).max_fps(60)),
+                .map(|this| {
+                    if self.is_virtual_list() {
+                        this.child(self.with_scrollbar_show(
+                            Scrollbar::vertical(&self.expandable_scroll_handle).max_fps(60),
+                        ))
+                    } else {
+                        this.child(self.with_scrollbar_show(
+                            Scrollbar::vertical(&self.vertical_scroll_handle).max_fps(60),
+                        ))
+                    }
+                }),
         )
     }
 
@@ -2198,10 +4924,17 @@ where
         div()
             .absolute()
             .left(self.fixed_head_cols_bounds.size.width)
-            .right_0()
+            .right(self.fixed_right_head_cols_bounds.size.width)
             .bottom_0()
             .h(Scrollbar::width())
-            .child(Scrollbar::horizontal(&self.horizontal_scroll_handle))
+            .child(self.with_scrollbar_show(Scrollbar::horizontal(&self.horizontal_scroll_handle)))
+    }
+
+    fn with_scrollbar_show(&self, scrollbar: Scrollbar) -> Scrollbar {
+        match self.options.scrollbar_show {
+            Some(scrollbar_show) => scrollbar.scrollbar_show(scrollbar_show),
+            None => scrollbar,
+        }
     }
 }
 
@@ -2221,6 +4954,7 @@ where
 {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.measure(window, cx);
+        self.update_groups_if_need(cx);
 
         let columns_count = self.delegate.columns_count(cx);
         let left_columns_count = self
@@ -2228,30 +4962,51 @@ where
             .iter()
             .filter(|col| self.col_fixed && col.column.fixed == Some(ColumnFixed::Left))
             .count();
-        let rows_count = self.delegate.rows_count(cx);
         let loading = self.delegate.loading(cx);
-
         let row_height = self.options.size.table_row_height();
-        let total_height = self
-            .vertical_scroll_handle
-            .0
-            .borrow()
-            .base_handle
-            .bounds()
-            .size
-            .height;
-        let actual_height = row_height * rows_count as f32;
+        let total_height = if self.is_virtual_list() {
+            self.expandable_scroll_handle.bounds().size.height
+        } else {
+            self.vertical_scroll_handle
+                .0
+                .borrow()
+                .base_handle
+                .bounds()
+                .size
+                .height
+        };
+
+        // Render a page of skeleton rows when loading an empty table.
+        let rows_count = match self.delegate.rows_count(cx) {
+            0 if loading && self.loading_skeleton => {
+                if total_height > Pixels::ZERO {
+                    (total_height / row_height).ceil() as usize
+                } else {
+                    DEFAULT_SKELETON_ROWS
+                }
+            }
+            rows_count => rows_count,
+        };
+        let show_loading_view = loading && !self.loading_skeleton;
+        let display_rows_count = self.display_rows_count(rows_count);
+        let actual_height = if self.is_virtual_list() || self.is_grouped() {
+            (0..display_rows_count)
+                .map(|ix| self.display_row_height(ix, rows_count, cx))
+                .fold(px(0.), |sum, height| sum + height)
+        } else {
+            row_height * rows_count as f32
+        };
         let extra_rows_count =
             self.calculate_extra_rows_needed(total_height, actual_height, row_height);
         let render_rows_count = if self.options.stripe {
-            rows_count + extra_rows_count
+            display_rows_count + extra_rows_count
         } else {
-            rows_count
+            display_rows_count
         };
         let right_clicked_row = self.right_clicked_row;
         let is_filled = total_height > Pixels::ZERO && total_height <= actual_height;
 
-        let loading_view = if loading {
+        let loading_view = if show_loading_view {
             Some(
                 self.delegate
                     .render_loading(self.options.size, window, cx)
@@ -2293,88 +5048,87 @@ where
                 if rows_count == 0 {
                     this.children(empty_view)
                 } else {
-                    this.child(
-                        h_flex().id("table-body").flex_grow_1().size_full().child(
-                            uniform_list(
-                                "table-uniform-list",
-                                render_rows_count,
-                                cx.processor(
-                                    move |table, visible_range: Range<usize>, window, cx| {
-                                        // Use `col.width` (always up-to-date) rather than
-                                        // `col.bounds.size.width`, which is only set after
-                                        // prepaint and is therefore zero on the first frame.
-                                        let col_sizes: Rc<Vec<gpui::Size<Pixels>>> = Rc::new(
-                                            table
-                                                .col_groups
-                                                .iter()
-                                                .skip(left_columns_count)
-                                                .map(|col| gpui::Size {
-                                                    width: col.width,
-                                                    height: px(0.),
-                                                })
-                                                .collect(),
-                                        );
-
-                                        table.load_more_if_need(
-                                            rows_count,
-                                            visible_range.end,
-                                            window,
-                                            cx,
-                                        );
-                                        table.update_visible_range_if_need(
-                                            visible_range.clone(),
-                                            Axis::Vertical,
-                                            window,
-                                            cx,
-                                        );
-
-                                        if visible_range.end > rows_count {
-                                            table.scroll_to_row(
-                                                std::cmp::min(
-                                                    visible_range.start,
-                                                    rows_count.saturating_sub(1),
-                                                ),
-                                                cx,
-                                            );
-                                        }
-
-                                        let mut items = Vec::with_capacity(
-                                            visible_range.end.saturating_sub(visible_range.start),
-                                        );
-
-                                        // Render fake rows to fill the table
-                                        visible_range.for_each(|row_ix| {
-                                            // Render real rows for available data
-                                            items.push(table.render_table_row(
-                                                row_ix,
-                                                rows_count,
-                                                left_columns_count,
-                                                col_sizes.clone(),
-                                                columns_count,
-                                                is_filled,
-                                                window,
-                                                cx,
-                                            ));
-                                        });
-
-                                        items
-                                    },
-                                ),
+                    let render_rows =
+                        move |table: &mut Self,
+                              visible_range: Range<usize>,
+                              window: &mut Window,
+                              cx: &mut Context<Self>| {
+                            table.render_visible_rows(
+                                visible_range,
+                                rows_count,
+                                left_columns_count,
+                                columns_count,
+                                is_filled,
+                                loading,
+                                window,
+                                cx,
                             )
+                        };
+
+                    let body = if self.is_virtual_list() {
+                        // The expanded rows are taller or the rows have variable heights,
+                        // so use the virtual list to account the height of each row.
+                        let row_sizes = Rc::new(
+                            (0..render_rows_count)
+                                .map(|ix| gpui::Size {
+                                    width: px(0.),
+                                    height: self.display_row_height(ix, rows_count, cx),
+                                })
+                                .collect::<Vec<_>>(),
+                        );
+
+                        v_virtual_list(cx.entity(), "table-virtual-list", row_sizes, render_rows)
                             .flex_grow_1()
                             .size_full()
                             .with_sizing_behavior(ListSizingBehavior::Auto)
-                            .track_scroll(&self.vertical_scroll_handle)
-                            .into_any_element(),
-                        ),
+                            .track_scroll(&self.expandable_scroll_handle)
+                            .into_any_element()
+                    } else {
+                        uniform_list(
+                            "table-uniform-list",
+                            render_rows_count,
+                            cx.processor(render_rows),
+                        )
+                        .flex_grow_1()
+                        .size_full()
+                        .with_sizing_behavior(ListSizingBehavior::Auto)
+                        .track_scroll(&self.vertical_scroll_handle)
+                        .into_any_element()
+                    };
+
+                    this.child(
+                        h_flex()
+                            .id("table-body")
+                            .relative()
+                            .flex_grow_1()
+                            .size_full()
+                            .when(self.row_movable, |this| {
+                                this.on_drag_move(cx.listener(Self::on_row_drag_move))
+                                    .on_drop(cx.listener(Self::on_row_drop))
+                            })
+                            .child(body)
+                            .when_some(self.sticky_group_ix(), |this, group_ix| {
+                                this.child(
+                                    div()
+                                        .id("table-sticky-group")
+                                        .absolute()
+                                        .top_0()
+                                        .left_0()
+                                        .right_0()
+                                        .child(self.render_group_row(group_ix, cx)),
+                                )
+                            }),
                     )
                 }
+            })
+            .when(self.footer, |this| {
+                this.child(self.render_table_footer(left_columns_count, window, cx))
             });
 
         div()
             .size_full()
             .children(loading_view)
-            .when(!loading, |this| {
+            .when(!show_loading_view, |this| {
                 this.child(inner_table)
                     .child(ScrollableMask::new(
                         Axis::Horizontal,
@@ -2408,3 +5162,306 @@ where
             })
     }
 }
+
+/// Returns the cell after (or before if `backward`) the given cell in the reading order,
+/// wrapping to the next (or previous) row.
+///
+/// Returns `None` at the end of the table, unless `loop_selection` is enabled.
+fn step_cell(
+    (row_ix, col_ix): (usize, usize),
+    rows_count: usize,
+    columns_count: usize,
+    backward: bool,
+    loop_selection: bool,
+) -> Option<(usize, usize)> {
+    if rows_count == 0 || columns_count == 0 {
+        return None;
+    }
+
+    let last_ix = rows_count * columns_count - 1;
+    let ix = row_ix * columns_count + col_ix;
+    let ix = if backward {
+        match ix.checked_sub(1) {
+            Some(ix) => ix,
+            None if loop_selection => last_ix,
+            None => return None,
+        }
+    } else if ix < last_ix {
+        ix + 1
+    } else if loop_selection {
+        0
+    } else {
+        return None;
+    };
+
+    Some((ix / columns_count, ix % columns_count))
+}
+
+/// Returns the rectangular range of the cells between the two corner cells, as `(rows, cols)`.
+fn cell_range(
+    (anchor_row, anchor_col): (usize, usize),
+    (row_ix, col_ix): (usize, usize),
+) -> (Range<usize>, Range<usize>) {
+    (
+        anchor_row.min(row_ix)..anchor_row.max(row_ix) + 1,
+        anchor_col.min(col_ix)..anchor_col.max(col_ix) + 1,
+    )
+}
+
+/// Join the rows of the cells as TSV, to paste into the spreadsheet apps.
+///
+/// The cells that contain a tab, newline or quote are quoted, and the quotes are doubled.
+fn cells_to_tsv(rows: impl Iterator<Item = Vec<String>>) -> String {
+    rows.map(|cells| {
+        cells
+            .iter()
+            .map(|cell| super::export::quote_field(cell, '\t'))
+            .collect::<Vec<_>>()
+            .join("\t")
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Groups the row indices by the keys, in the order of the first row of each group.
+fn group_rows(keys: impl Iterator<Item = String>) -> Vec<(SharedString, Vec<usize>)> {
+    let mut groups: Vec<(SharedString, Vec<usize>)> = vec![];
+    let mut group_ixs: HashMap<String, usize> = HashMap::new();
+    for (row_ix, key) in keys.enumerate() {
+        match group_ixs.get(&key) {
+            Some(group_ix) => groups[*group_ix].1.push(row_ix),
+            None => {
+                group_ixs.insert(key.clone(), groups.len());
+                groups.push((key.into(), vec![row_ix]));
+            }
+        }
+    }
+    groups
+}
+
+/// Returns true if moving the column `col_ix` to the `gap` keeps it in its column groups,
+/// and does not split the other groups.
+///
+/// The `gap` is the index between the columns, `0..=columns_count`.
+fn is_gap_in_groups(groups: &[Range<usize>], col_ix: usize, gap: usize) -> bool {
+    groups.iter().all(|group| {
+        if group.contains(&col_ix) {
+            group.start <= gap && gap <= group.end
+        } else {
+            gap <= group.start || gap >= group.end
+        }
+    })
+}
+
+/// Returns the insertion gap when dragging the row at `drag_row_ix` to the `y` position
+/// over the row at `row_ix` with the `bounds`.
+///
+/// Returns `None` if dropping there would not move the dragged row.
+fn row_drag_gap(
+    row_ix: usize,
+    drag_row_ix: usize,
+    y: Pixels,
+    bounds: Bounds<Pixels>,
+) -> Option<usize> {
+    let gap = if y < bounds.center().y {
+        row_ix
+    } else {
+        row_ix + 1
+    };
+
+    if gap == drag_row_ix || gap == drag_row_ix + 1 {
+        None
+    } else {
+        Some(gap)
+    }
+}
+
+/// Returns the new index of the item `ix` after the item at `from` is moved to `to`.
+fn move_ix(ix: usize, from: usize, to: usize) -> usize {
+    if ix == from {
+        to
+    } else if from < ix && ix <= to {
+        ix - 1
+    } else if to <= ix && ix < from {
+        ix + 1
+    } else {
+        ix
+    }
+}
+
+/// Returns the nearest row before the given row with a smaller depth.
+fn parent_row(row_ix: usize, depth: impl Fn(usize) -> usize) -> Option<usize> {
+    let row_depth = depth(row_ix);
+    if row_depth == 0 {
+        return None;
+    }
+
+    (0..row_ix).rev().find(|ix| depth(*ix) < row_depth)
+}
+
+/// Returns the new index of the row `ix` after the child rows of `row_ix` have been
+/// inserted or removed, moves to `row_ix` if the row has been removed.
+fn shift_row(ix: usize, row_ix: usize, old_rows_count: usize, new_rows_count: usize) -> usize {
+    if ix <= row_ix {
+        return ix;
+    }
+
+    if new_rows_count >= old_rows_count {
+        ix + (new_rows_count - old_rows_count)
+    } else {
+        let removed = old_rows_count - new_rows_count;
+        if ix <= row_ix + removed {
+            row_ix
+        } else {
+            ix - removed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Bounds, point, px, size};
+
+    use super::{
+        cell_range, cells_to_tsv, group_rows, is_gap_in_groups, move_ix, parent_row, row_drag_gap,
+        shift_row, step_cell,
+    };
+
+    #[test]
+    fn test_cell_range() {
+        assert_eq!(cell_range((1, 2), (1, 2)), (1..2, 2..3));
+        assert_eq!(cell_range((1, 2), (3, 4)), (1..4, 2..5));
+        assert_eq!(cell_range((3, 4), (1, 2)), (1..4, 2..5));
+        assert_eq!(cell_range((3, 0), (1, 2)), (1..4, 0..3));
+    }
+
+    #[test]
+    fn test_cells_to_tsv() {
+        let tsv = cells_to_tsv(
+            vec![
+                vec!["AAPL".to_string(), "1,234.5".to_string()],
+                vec!["Say \"Hi\"".to_string(), "a\tb".to_string()],
+                vec!["Line 1\nLine 2".to_string(), "".to_string()],
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            tsv,
+            "AAPL\t1,234.5\n\"Say \"\"Hi\"\"\"\t\"a\tb\"\n\"Line 1\nLine 2\"\t"
+        );
+        assert_eq!(cells_to_tsv(std::iter::empty()), "");
+    }
+
+    #[test]
+    fn test_shift_row() {
+        // Expand the row 2 with 3 children.
+        assert_eq!(shift_row(1, 2, 10, 13), 1);
+        assert_eq!(shift_row(2, 2, 10, 13), 2);
+        assert_eq!(shift_row(3, 2, 10, 13), 6);
+
+        // Collapse the row 2 with 3 children.
+        assert_eq!(shift_row(2, 2, 13, 10), 2);
+        assert_eq!(shift_row(4, 2, 13, 10), 2);
+        assert_eq!(shift_row(5, 2, 13, 10), 2);
+        assert_eq!(shift_row(6, 2, 13, 10), 3);
+    }
+
+    #[test]
+    fn test_parent_row() {
+        // 0
+        //   1
+        //     2
+        //   3
+        // 4
+        let depths = [0, 1, 2, 1, 0];
+        let depth = |ix: usize| depths[ix];
+
+        assert_eq!(parent_row(0, depth), None);
+        assert_eq!(parent_row(1, depth), Some(0));
+        assert_eq!(parent_row(2, depth), Some(1));
+        assert_eq!(parent_row(3, depth), Some(0));
+        assert_eq!(parent_row(4, depth), None);
+    }
+
+    #[test]
+    fn test_step_cell() {
+        assert_eq!(step_cell((0, 0), 2, 3, false, false), Some((0, 1)));
+        assert_eq!(step_cell((0, 2), 2, 3, false, false), Some((1, 0)));
+        assert_eq!(step_cell((1, 2), 2, 3, false, false), None);
+        assert_eq!(step_cell((1, 2), 2, 3, false, true), Some((0, 0)));
+
+        assert_eq!(step_cell((1, 0), 2, 3, true, false), Some((0, 2)));
+        assert_eq!(step_cell((0, 0), 2, 3, true, false), None);
+        assert_eq!(step_cell((0, 0), 2, 3, true, true), Some((1, 2)));
+
+        assert_eq!(step_cell((0, 0), 0, 3, false, true), None);
+    }
+
+    #[test]
+    fn test_group_rows() {
+        let keys = ["a", "b", "a", "c", "b"].map(String::from);
+        let groups = group_rows(keys.into_iter());
+        assert_eq!(
+            groups,
+            vec![
+                ("a".into(), vec![0, 2]),
+                ("b".into(), vec![1, 4]),
+                ("c".into(), vec![3]),
+            ]
+        );
+        assert!(group_rows(std::iter::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_move_ix() {
+        // Move 1 to 3: [0, 2, 3, 1, 4]
+        assert_eq!(move_ix(1, 1, 3), 3);
+        assert_eq!(move_ix(2, 1, 3), 1);
+        assert_eq!(move_ix(3, 1, 3), 2);
+        assert_eq!(move_ix(4, 1, 3), 4);
+
+        // Move 3 to 1: [0, 3, 1, 2, 4]
+        assert_eq!(move_ix(3, 3, 1), 1);
+        assert_eq!(move_ix(1, 3, 1), 2);
+        assert_eq!(move_ix(0, 3, 1), 0);
+    }
+
+    #[test]
+    fn test_row_drag_gap() {
+        let bounds = Bounds::new(point(px(0.), px(100.)), size(px(200.), px(30.)));
+
+        // Upper half is the gap before the row, lower half is the gap after the row.
+        assert_eq!(row_drag_gap(5, 1, px(105.), bounds), Some(5));
+        assert_eq!(row_drag_gap(5, 1, px(125.), bounds), Some(6));
+        assert_eq!(row_drag_gap(0, 3, px(101.), bounds), Some(0));
+
+        // No gap around the dragged row itself.
+        assert_eq!(row_drag_gap(5, 5, px(105.), bounds), None);
+        assert_eq!(row_drag_gap(5, 5, px(125.), bounds), None);
+        assert_eq!(row_drag_gap(4, 5, px(125.), bounds), None);
+        assert_eq!(row_drag_gap(6, 5, px(105.), bounds), None);
+    }
+
+    #[test]
+    fn test_is_gap_in_groups() {
+        // Groups: [0, 1, 2] [3, 4], nested group [1, 2], and the column 5 is not grouped.
+        let groups = vec![0..3, 3..5, 1..3];
+
+        assert!(is_gap_in_groups(&groups, 1, 3));
+        assert!(is_gap_in_groups(&groups, 2, 1));
+        // Out of the nested group.
+        assert!(!is_gap_in_groups(&groups, 1, 0));
+        // Out of the group.
+        assert!(!is_gap_in_groups(&groups, 3, 2));
+        assert!(is_gap_in_groups(&groups, 4, 3));
+        // Split the other groups.
+        assert!(!is_gap_in_groups(&groups, 5, 4));
+        assert!(!is_gap_in_groups(&groups, 5, 2));
+        assert!(is_gap_in_groups(&groups, 5, 3));
+        assert!(is_gap_in_groups(&groups, 0, 3));
+        // Column 0 is out of the nested group.
+        assert!(!is_gap_in_groups(&groups, 0, 2));
+
+        assert!(is_gap_in_groups(&[], 0, 3));
+    }
+}
@@ -1,7 +1,13 @@
-use crate::{ColorName, Sizable, Size, StyledExt, theme::ActiveTheme as _};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    ColorName, ElementExt as _, Icon, IconName, Selectable, Sizable, Size, StyledExt, h_flex,
+    popover::Popover, theme::ActiveTheme as _,
+};
 use gpui::{
-    AbsoluteLength, AnyElement, App, Hsla, InteractiveElement as _, IntoElement, ParentElement,
-    RenderOnce, StyleRefinement, Styled, Window, div, prelude::FluentBuilder as _, relative, rems,
+    AbsoluteLength, AnyElement, App, ElementId, Hsla, InteractiveElement as _, IntoElement,
+    ParentElement, Pixels, RenderOnce, SharedString, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Window, div, prelude::FluentBuilder as _, px, relative, rems,
     transparent_white,
 };
 
@@ -126,6 +132,7 @@ pub struct Tag {
     style: StyleRefinement,
     variant: TagVariant,
     outline: bool,
+    selected: bool,
     size: Size,
     rounded: Option<AbsoluteLength>,
     children: Vec<AnyElement>,
@@ -137,6 +144,7 @@ impl Tag {
             style: StyleRefinement::default(),
             variant: TagVariant::default(),
             outline: false,
+            selected: false,
             size: Size::default(),
             rounded: None,
             children: Vec::new(),
@@ -219,6 +227,19 @@ impl Sizable for Tag {
     }
 }
 
+impl Selectable for Tag {
+    /// Set the selected state of the filter chip, the selected tag is filled with a check icon,
+    /// and the [`TagVariant::Secondary`] tag is emphasized as [`TagVariant::Primary`].
+    fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    fn is_selected(&self) -> bool {
+        self.selected
+    }
+}
+
 impl ParentElement for Tag {
     fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
         self.children.extend(elements);
@@ -233,13 +254,18 @@ impl Styled for Tag {
 
 impl RenderOnce for Tag {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let bg = if self.outline {
+        let (variant, outline) = match (self.selected, self.variant) {
+            (true, TagVariant::Secondary) => (TagVariant::Primary, false),
+            (true, variant) => (variant, false),
+            (false, variant) => (variant, self.outline),
+        };
+        let bg = if outline {
             transparent_white()
         } else {
-            self.variant.bg(cx)
+            variant.bg(cx)
         };
-        let fg = self.variant.fg(self.outline, cx);
-        let border = self.variant.border(cx);
+        let fg = variant.fg(outline, cx);
+        let border = variant.border(cx);
         let rounded = self.rounded.unwrap_or(
             match self.size {
                 Size::XSmall | Size::Small => cx.theme().radius / 2.,
@@ -264,6 +290,289 @@ impl RenderOnce for Tag {
             .rounded(rounded)
             .hover(|this| this.opacity(0.9))
             .refine_style(&self.style)
+            .when(self.selected, |this| {
+                this.gap_1()
+                    .child(Icon::new(IconName::Check).size_3().flex_shrink_0())
+            })
             .children(self.children)
     }
 }
+
+/// The measured widths of the [`TagGroup`] in the last frame, to decide how many tags fit.
+#[derive(Default)]
+struct TagGroupLayout {
+    container: Pixels,
+    items: Vec<Pixels>,
+    counter: Pixels,
+}
+
+/// Returns the number of the items that fit in the `container` width on a single line,
+/// leaving the space for the `counter` of the hidden items if not all the items fit.
+fn visible_count(container: Pixels, items: &[Pixels], gap: Pixels, counter: Pixels) -> usize {
+    let total = items.iter().fold(px(0.), |total, width| total + *width)
+        + gap * items.len().saturating_sub(1) as f32;
+    if total <= container {
+        return items.len();
+    }
+
+    let available = container - counter - gap;
+    let mut width = px(0.);
+    for (ix, item) in items.iter().enumerate() {
+        width += *item;
+        if width > available {
+            return ix;
+        }
+        width += gap;
+    }
+    items.len()
+}
+
+/// A group of [`Tag`]s, wraps to multiple lines by default.
+///
+/// - On a single line by [`TagGroup::single_line`], the tags that overflow are hidden
+///   in a "+N" popover.
+/// - In the filter mode by [`TagGroup::filter`], the tags are filter chips that toggle
+///   the selected state by click, e.g. the facets above a table or list.
+#[derive(IntoElement)]
+pub struct TagGroup {
+    id: ElementId,
+    style: StyleRefinement,
+    size: Size,
+    single_line: bool,
+    filter: bool,
+    items: Vec<Tag>,
+    on_change: Option<Rc<dyn Fn(&Vec<usize>, &mut Window, &mut App) + 'static>>,
+}
+
+impl TagGroup {
+    /// Create a new TagGroup.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            size: Size::default(),
+            single_line: false,
+            filter: false,
+            items: Vec::new(),
+            on_change: None,
+        }
+    }
+
+    /// Add a child [`Tag`] to the group.
+    pub fn child(mut self, tag: Tag) -> Self {
+        self.items.push(tag);
+        self
+    }
+
+    /// Add multiple [`Tag`]s to the group.
+    pub fn children(mut self, tags: impl IntoIterator<Item = Tag>) -> Self {
+        self.items.extend(tags);
+        self
+    }
+
+    /// Lay out the tags on a single line, and show the overflowing tags in a "+N" popover,
+    /// default: false
+    pub fn single_line(mut self) -> Self {
+        self.single_line = true;
+        self
+    }
+
+    /// Set the filter chip mode, the tags toggle the selected state by click, default: false
+    ///
+    /// Use [`Selectable::selected`] of the [`Tag`] to set the selected state.
+    pub fn filter(mut self, filter: bool) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Set the callback to be called when a tag is toggled in the filter mode.
+    ///
+    /// The `&Vec<usize>` parameter is the new selected indices of the tags in ascending order.
+    pub fn on_change(
+        mut self,
+        on_change: impl Fn(&Vec<usize>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Rc::new(on_change));
+        self
+    }
+}
+
+impl Sizable for TagGroup {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for TagGroup {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for TagGroup {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let gap = match self.size {
+            Size::XSmall | Size::Small => px(4.),
+            _ => px(8.),
+        };
+        let items_len = self.items.len();
+        let layout = window
+            .use_keyed_state(
+                SharedString::from(format!("{}-layout", self.id)),
+                cx,
+                |_, _| Rc::new(RefCell::new(TagGroupLayout::default())),
+            )
+            .read(cx)
+            .clone();
+
+        // Show all the tags before they are measured.
+        let visible = {
+            let layout = layout.borrow();
+            if !self.single_line || layout.container <= px(0.) || layout.items.len() != items_len {
+                items_len
+            } else {
+                visible_count(layout.container, &layout.items, gap, layout.counter)
+            }
+        };
+        layout.borrow_mut().items.resize(items_len, px(0.));
+
+        let selected = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.selected)
+            .map(|(ix, _)| ix)
+            .collect::<Vec<_>>();
+
+        let mut visible_items = vec![];
+        let mut hidden_items = vec![];
+        for (ix, item) in self.items.into_iter().enumerate() {
+            let item = div()
+                .id(ix)
+                .flex_shrink_0()
+                .child(item.with_size(self.size))
+                .when(self.filter, |this| {
+                    let selected = selected.clone();
+                    let on_change = self.on_change.clone();
+                    this.cursor_pointer().on_click(move |_, window, cx| {
+                        let mut selected = selected.clone();
+                        match selected.iter().position(|selected_ix| *selected_ix == ix) {
+                            Some(pos) => {
+                                selected.remove(pos);
+                            }
+                            None => {
+                                selected.push(ix);
+                                selected.sort();
+                            }
+                        }
+                        if let Some(on_change) = &on_change {
+                            on_change(&selected, window, cx);
+                        }
+                    })
+                });
+
+            if !self.single_line {
+                visible_items.push(item.into_any_element());
+                continue;
+            }
+
+            // Measure all the tags, the hidden tags are measured out of the flow.
+            let measured = div()
+                .flex_shrink_0()
+                .when(ix >= visible, |this| this.absolute().invisible())
+                .on_prepaint({
+                    let layout = layout.clone();
+                    move |bounds, window, _| {
+                        let mut layout = layout.borrow_mut();
+                        if let Some(width) = layout.items.get_mut(ix) {
+                            if *width != bounds.size.width {
+                                *width = bounds.size.width;
+                                window.refresh();
+                            }
+                        }
+                    }
+                });
+            if ix < visible {
+                visible_items.push(measured.child(item).into_any_element());
+            } else {
+                visible_items.push(measured.into_any_element());
+                hidden_items.push(item);
+            }
+        }
+
+        let hidden_count = hidden_items.len();
+        h_flex()
+            .id(self.id)
+            .gap(gap)
+            .map(|this| {
+                if self.single_line {
+                    this.relative().overflow_hidden().on_prepaint({
+                        let layout = layout.clone();
+                        move |bounds, window, _| {
+                            let mut layout = layout.borrow_mut();
+                            if layout.container != bounds.size.width {
+                                layout.container = bounds.size.width;
+                                window.refresh();
+                            }
+                        }
+                    })
+                } else {
+                    this.flex_wrap()
+                }
+            })
+            .refine_style(&self.style)
+            .children(visible_items)
+            .when(hidden_count > 0, |this| {
+                this.child(
+                    div()
+                        .flex_shrink_0()
+                        .on_prepaint({
+                            let layout = layout.clone();
+                            move |bounds, window, _| {
+                                let mut layout = layout.borrow_mut();
+                                if layout.counter != bounds.size.width {
+                                    layout.counter = bounds.size.width;
+                                    window.refresh();
+                                }
+                            }
+                        })
+                        .child(
+                            Popover::new("more")
+                                .trigger(
+                                    Tag::secondary()
+                                        .with_size(self.size)
+                                        .child(format!("+{}", hidden_count)),
+                                )
+                                .child(
+                                    h_flex()
+                                        .max_w(px(320.))
+                                        .flex_wrap()
+                                        .gap(gap)
+                                        .children(hidden_items),
+                                ),
+                        ),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::visible_count;
+
+    #[test]
+    fn test_visible_count() {
+        let items = [px(40.), px(60.), px(50.)];
+        // All fit, 40 + 4 + 60 + 4 + 50 = 158
+        assert_eq!(visible_count(px(158.), &items, px(4.), px(30.)), 3);
+        // Leave 30 + 4 for the counter.
+        assert_eq!(visible_count(px(157.), &items, px(4.), px(30.)), 2);
+        assert_eq!(visible_count(px(138.), &items, px(4.), px(30.)), 2);
+        assert_eq!(visible_count(px(137.), &items, px(4.), px(30.)), 1);
+        assert_eq!(visible_count(px(60.), &items, px(4.), px(30.)), 0);
+        assert_eq!(visible_count(px(0.), &[], px(4.), px(30.)), 0);
+    }
+}
@@ -0,0 +1,821 @@
+//! Utilities to write the headless tests of the components.
+//!
+//! Enable the `test-support` feature to use them in the tests of downstream apps:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! gpui-component = { version = "*", features = ["test-support"] }
+//! ```
+//!
+//! ```ignore
+//! use gpui_component::test::TestHarness;
+//!
+//! #[gpui::test]
+//! fn test_click(cx: &mut gpui::TestAppContext) {
+//!     let mut harness = TestHarness::new(cx, |_, cx| MyView::new(cx));
+//!     harness.click_on("submit");
+//!     assert!(harness.read(|view, _| view.submitted));
+//! }
+//! ```
+//!
+//! The elements are queried by the [debug selector], so add `.debug_selector(|| "submit".into())`
+//! to the element to query. The [`Label`] and [`Button`] of the components can also be queried by
+//! the id or the text, use [`ElementExt::test_record`] to make the other elements queryable.
+//!
+//! [debug selector]: gpui::InteractiveElement::debug_selector
+//! [`Label`]: crate::label::Label
+//! [`Button`]: crate::button::Button
+//! [`ElementExt::test_record`]: crate::ElementExt::test_record
+use std::time::Duration;
+
+use gpui::{
+    App, AppContext as _, Bounds, Context, ElementId, Entity, Global, Modifiers, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, Render, ScrollDelta,
+    ScrollWheelEvent, SharedString, Size, TestAppContext, VisualTestContext, Window,
+};
+
+use crate::Root;
+
+/// An element recorded in the frame drawn by the [`TestHarness`],
+/// see [`crate::ElementExt::test_record`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedElement {
+    /// The id of the element.
+    pub id: Option<ElementId>,
+    /// The text rendered by the element.
+    pub text: Option<SharedString>,
+    /// The bounds of the element in the window.
+    pub bounds: Bounds<Pixels>,
+}
+
+/// The elements recorded in the frame, only set while the [`TestHarness`] is drawing.
+#[derive(Default)]
+struct RecordedElements(Vec<RenderedElement>);
+
+impl Global for RecordedElements {}
+
+/// Record the element in the frame drawn by the [`TestHarness`], does nothing out of it.
+pub(crate) fn record_element(
+    id: Option<ElementId>,
+    text: Option<SharedString>,
+    bounds: Bounds<Pixels>,
+    cx: &mut App,
+) {
+    if cx.has_global::<RecordedElements>() {
+        cx.global_mut::<RecordedElements>()
+            .0
+            .push(RenderedElement { id, text, bounds });
+    }
+}
+
+/// A view mounted in an offscreen window with the [`Root`], to drive the synthetic
+/// events and assert the result.
+pub struct TestHarness<V: 'static> {
+    view: Entity<V>,
+    cx: VisualTestContext,
+    elements: Vec<RenderedElement>,
+}
+
+impl<V: Render> TestHarness<V> {
+    /// Initialize the components, and mount the view built by `build` in a new window.
+    pub fn new(
+        cx: &mut TestAppContext,
+        build: impl FnOnce(&mut Window, &mut Context<V>) -> V,
+    ) -> Self {
+        cx.update(crate::init);
+
+        let window = cx.add_window(|window, cx| {
+            let view = cx.new(|cx| build(window, cx));
+            Root::new(view, window, cx)
+        });
+        let view = window
+            .read_with(cx, |root, _| root.view().clone().downcast::<V>())
+            .expect("failed to read the root view")
+            .expect("the root view should be the mounted view");
+        let cx = VisualTestContext::from_window(window.into(), cx);
+
+        let mut this = Self {
+            view,
+            cx,
+            elements: vec![],
+        };
+        this.draw();
+        this
+    }
+
+    /// Returns the mounted view.
+    pub fn view(&self) -> &Entity<V> {
+        &self.view
+    }
+
+    /// Returns the test context of the window, to use the lower-level gpui test APIs.
+    pub fn cx(&mut self) -> &mut VisualTestContext {
+        &mut self.cx
+    }
+
+    /// Read the state of the mounted view.
+    pub fn read<R>(&mut self, f: impl FnOnce(&V, &gpui::App) -> R) -> R {
+        self.view.read_with(&self.cx, f)
+    }
+
+    /// Update the mounted view, and redraw the window.
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut V, &mut Window, &mut Context<V>) -> R) -> R {
+        let result = self.view.update_in(&mut self.cx, f);
+        self.draw();
+        result
+    }
+
+    /// Resize the window to the `size`, and redraw the window.
+    pub fn resize(&mut self, size: Size<Pixels>) {
+        self.cx.simulate_resize(size);
+        self.draw();
+    }
+
+    /// Returns the pixels of the window in the last frame, see [`crate::snapshot`].
+    pub fn render_to_image(&mut self) -> anyhow::Result<image::RgbaImage> {
+        self.draw();
+        self.cx.update(|window, _| window.render_to_image())
+    }
+
+    /// Run the pending tasks and draw the window, to layout and paint the elements.
+    pub fn draw(&mut self) {
+        self.cx.run_until_parked();
+        self.elements = self.cx.update(|window, cx| {
+            cx.set_global(RecordedElements::default());
+            let _ = window.draw(cx);
+            cx.remove_global::<RecordedElements>().0
+        });
+    }
+
+    /// Advance the clock of the timers by the given duration, and redraw the window.
+    pub fn advance(&mut self, duration: Duration) {
+        self.cx.background_executor.advance_clock(duration);
+        self.draw();
+    }
+
+    /// Simulate the keystrokes separated by space, e.g. `"down shift-tab cmd-a"`.
+    pub fn keystrokes(&mut self, keystrokes: &str) {
+        self.cx.simulate_keystrokes(keystrokes);
+        self.draw();
+    }
+
+    /// Simulate typing the text into the focused input.
+    pub fn type_text(&mut self, text: &str) {
+        self.cx.simulate_input(text);
+        self.draw();
+    }
+
+    /// Simulate the left mouse click at the position.
+    pub fn click(&mut self, position: Point<Pixels>) {
+        self.click_with(position, 1, Modifiers::default());
+    }
+
+    /// Simulate the left mouse double-click at the position.
+    pub fn double_click(&mut self, position: Point<Pixels>) {
+        self.click(position);
+        self.click_with(position, 2, Modifiers::default());
+    }
+
+    /// Simulate the left mouse click with the click count and modifiers at the position.
+    pub fn click_with(
+        &mut self,
+        position: Point<Pixels>,
+        click_count: usize,
+        modifiers: Modifiers,
+    ) {
+        self.cx.simulate_event(MouseDownEvent {
+            position,
+            button: MouseButton::Left,
+            modifiers,
+            click_count,
+            first_mouse: false,
+        });
+        self.cx.simulate_event(MouseUpEvent {
+            position,
+            button: MouseButton::Left,
+            modifiers,
+            click_count,
+        });
+        self.draw();
+    }
+
+    /// Simulate the right mouse click at the position.
+    pub fn right_click(&mut self, position: Point<Pixels>) {
+        self.cx
+            .simulate_mouse_down(position, MouseButton::Right, Modifiers::default());
+        self.cx
+            .simulate_mouse_up(position, MouseButton::Right, Modifiers::default());
+        self.draw();
+    }
+
+    /// Simulate moving the mouse to the position, with the pressed button if dragging.
+    pub fn mouse_move(&mut self, position: Point<Pixels>, pressed_button: Option<MouseButton>) {
+        self.cx.simulate_event(MouseMoveEvent {
+            position,
+            pressed_button,
+            modifiers: Modifiers::default(),
+        });
+        self.draw();
+    }
+
+    /// Simulate dragging with the left mouse button through the points.
+    pub fn drag(&mut self, points: &[Point<Pixels>]) {
+        let (Some(from), Some(to)) = (points.first(), points.last()) else {
+            return;
+        };
+
+        self.cx
+            .simulate_mouse_down(*from, MouseButton::Left, Modifiers::default());
+        self.draw();
+        for point in &points[1..] {
+            self.mouse_move(*point, Some(MouseButton::Left));
+        }
+        self.cx
+            .simulate_mouse_up(*to, MouseButton::Left, Modifiers::default());
+        self.draw();
+    }
+
+    /// Simulate scrolling the mouse wheel by the pixels at the position.
+    pub fn scroll(&mut self, position: Point<Pixels>, delta: Point<Pixels>) {
+        self.cx.simulate_event(ScrollWheelEvent {
+            position,
+            delta: ScrollDelta::Pixels(delta),
+            ..Default::default()
+        });
+        self.draw();
+    }
+
+    /// Returns the bounds of the element with the debug selector in the last frame.
+    pub fn bounds_of(&mut self, selector: &'static str) -> Option<Bounds<Pixels>> {
+        self.cx.debug_bounds(selector)
+    }
+
+    /// Returns true if the element with the debug selector is rendered in the last frame.
+    pub fn exists(&mut self, selector: &'static str) -> bool {
+        self.bounds_of(selector).is_some()
+    }
+
+    /// Simulate the left mouse click at the center of the element with the debug selector.
+    ///
+    /// Panics if the element is not found.
+    pub fn click_on(&mut self, selector: &'static str) {
+        let bounds = self
+            .bounds_of(selector)
+            .unwrap_or_else(|| panic!("element `{}` not found", selector));
+        self.click(bounds.center());
+    }
+
+    /// Returns the elements recorded in the last frame in the paint order,
+    /// see [`crate::ElementExt::test_record`].
+    pub fn elements(&self) -> &[RenderedElement] {
+        &self.elements
+    }
+
+    /// Returns the bounds of the first element with the `id` in the last frame.
+    pub fn find_by_id(&self, id: impl Into<ElementId>) -> Option<Bounds<Pixels>> {
+        let id = Some(id.into());
+        self.elements
+            .iter()
+            .find(|element| element.id == id)
+            .map(|element| element.bounds)
+    }
+
+    /// Returns the bounds of the first element rendered the `text` in the last frame.
+    pub fn find_by_text(&self, text: &str) -> Option<Bounds<Pixels>> {
+        self.elements
+            .iter()
+            .find(|element| element.text.as_deref() == Some(text))
+            .map(|element| element.bounds)
+    }
+
+    /// Returns a snapshot of the text rendered in the last frame, one line for each element
+    /// in the paint order, `id: text`, or `text` if the element has no id.
+    ///
+    /// Used to assert the text output in the snapshot tests.
+    pub fn text_snapshot(&self) -> String {
+        self.elements
+            .iter()
+            .filter_map(|element| {
+                let text = element.text.as_ref()?;
+                Some(match &element.id {
+                    Some(id) => format!("{}: {}", id, text),
+                    None => text.to_string(),
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns a snapshot of the layout of the elements with the debug selectors,
+    /// one line for each element, `selector: x, y, width x height`, or `selector: none`
+    /// if not rendered.
+    ///
+    /// Used to assert the layout in the snapshot tests.
+    pub fn layout_snapshot(&mut self, selectors: &[&'static str]) -> String {
+        selectors
+            .iter()
+            .map(|selector| match self.bounds_of(selector) {
+                Some(bounds) => format!(
+                    "{}: {}, {}, {} x {}",
+                    selector,
+                    f32::from(bounds.origin.x),
+                    f32::from(bounds.origin.y),
+                    f32::from(bounds.size.width),
+                    f32::from(bounds.size.height)
+                ),
+                None => format!("{}: none", selector),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gpui::{
+        App, AppContext as _, Context, FocusHandle, Focusable as _, InteractiveElement as _,
+        IntoElement, ParentElement as _, Render, SharedString, StatefulInteractiveElement as _,
+        Styled as _, Task, TestAppContext, Window, div, prelude::FluentBuilder as _, px,
+    };
+
+    use super::TestHarness;
+    use crate::table::{
+        Column, ColumnFilter, ColumnFixed, ColumnSort, ColumnState, CsvExportOptions, DataTable,
+        FilterDescriptor, FilterValue, TableDelegate, TableEvent, TableLayoutState, TableState,
+    };
+    use crate::{button::Button, label::Label};
+
+    struct CounterView {
+        count: usize,
+        elapsed: bool,
+        _task: Task<()>,
+    }
+
+    impl CounterView {
+        fn new(cx: &mut Context<Self>) -> Self {
+            let task = cx.spawn(async move |this, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(500))
+                    .await;
+                _ = this.update(cx, |this, cx| {
+                    this.elapsed = true;
+                    cx.notify();
+                });
+            });
+
+            Self {
+                count: 0,
+                elapsed: false,
+                _task: task,
+            }
+        }
+    }
+
+    impl Render for CounterView {
+        fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .child(
+                    div()
+                        .id("button")
+                        .debug_selector(|| "button".into())
+                        .w(px(100.))
+                        .h(px(30.))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.count += 1;
+                            cx.notify();
+                        })),
+                )
+                .when(self.count > 0, |this| {
+                    this.child(
+                        div()
+                            .debug_selector(|| "count".into())
+                            .w(px(50.))
+                            .h(px(20.))
+                            .child(SharedString::from(self.count.to_string())),
+                    )
+                })
+        }
+    }
+
+    struct FormView {
+        saved: bool,
+    }
+
+    impl Render for FormView {
+        fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .child(Label::new("Name").h(px(20.)))
+                .child(
+                    Button::new("save")
+                        .label("Save")
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.saved = true;
+                            cx.notify();
+                        })),
+                )
+                .when(self.saved, |this| {
+                    this.child(Label::new("Saved").h(px(20.)))
+                })
+        }
+    }
+
+    #[gpui::test]
+    fn test_click_and_query(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |_, cx| CounterView::new(cx));
+        assert!(harness.exists("button"));
+        assert!(!harness.exists("count"));
+
+        harness.click_on("button");
+        harness.click_on("button");
+        assert_eq!(harness.read(|view, _| view.count), 2);
+        assert_eq!(
+            harness.layout_snapshot(&["button", "count", "unknown"]),
+            "button: 0, 0, 100 x 30\ncount: 0, 30, 50 x 20\nunknown: none"
+        );
+    }
+
+    #[gpui::test]
+    fn test_find_by_id_and_text(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |_, _| FormView { saved: false });
+        assert_eq!(
+            harness.find_by_text("Name").map(|bounds| bounds.origin),
+            Some(gpui::point(px(0.), px(0.)))
+        );
+        assert_eq!(harness.find_by_text("Saved"), None);
+        assert_eq!(harness.find_by_id("unknown"), None);
+        assert_eq!(harness.text_snapshot(), "Name\nsave: Save");
+
+        let bounds = harness.find_by_id("save").expect("button `save` not found");
+        assert_eq!(bounds.origin.y, px(20.));
+        harness.click(bounds.center());
+        assert!(harness.read(|view, _| view.saved));
+        assert!(harness.find_by_text("Saved").is_some());
+        assert_eq!(harness.text_snapshot(), "Name\nsave: Save\nSaved");
+    }
+
+    #[gpui::test]
+    fn test_advance_timers(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |_, cx| CounterView::new(cx));
+        assert!(!harness.read(|view, _| view.elapsed));
+
+        harness.advance(Duration::from_millis(600));
+        assert!(harness.read(|view, _| view.elapsed));
+    }
+
+    struct Delegate {
+        columns: Vec<Column>,
+    }
+
+    impl TableDelegate for Delegate {
+        fn columns_count(&self, _: &App) -> usize {
+            self.columns.len()
+        }
+
+        fn rows_count(&self, _: &App) -> usize {
+            10
+        }
+
+        fn column(&self, col_ix: usize, _: &App) -> Column {
+            self.columns[col_ix].clone()
+        }
+
+        fn cell_text(&self, row_ix: usize, col_ix: usize, _: &App) -> String {
+            format!("{}:{}", row_ix, col_ix)
+        }
+    }
+
+    struct TableView {
+        focus_handle: FocusHandle,
+        table: gpui::Entity<TableState<Delegate>>,
+    }
+
+    impl Render for TableView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .track_focus(&self.focus_handle)
+                .size_full()
+                .child(DataTable::new(&self.table))
+        }
+    }
+
+    #[gpui::test]
+    fn test_table_keyboard_selection(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |window, cx| {
+            let delegate = Delegate {
+                columns: vec![Column::new("a", "A"), Column::new("b", "B")],
+            };
+            TableView {
+                focus_handle: cx.focus_handle(),
+                table: cx.new(|cx| TableState::new(delegate, window, cx)),
+            }
+        });
+
+        harness.update(|view, window, cx| {
+            view.table.update(cx, |table, cx| {
+                table.set_selected_row(0, cx);
+                table.focus_handle(cx).focus(window, cx);
+            })
+        });
+        harness.keystrokes("down down up");
+
+        let selected_row = harness.read(|view, cx| view.table.read(cx).selected_row());
+        assert_eq!(selected_row, Some(1));
+    }
+
+    #[gpui::test]
+    fn test_table_column_visible(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |window, cx| {
+            let delegate = Delegate {
+                columns: vec![Column::new("a", "A"), Column::new("b", "B").width(120.)],
+            };
+            TableView {
+                focus_handle: cx.focus_handle(),
+                table: cx.new(|cx| TableState::new(delegate, window, cx)),
+            }
+        });
+
+        harness.update(|view, _, cx| {
+            view.table
+                .update(cx, |table, cx| table.set_column_visible(1, false, cx))
+        });
+        assert!(harness.read(|view, cx| !view.table.read(cx).is_column_visible(1)));
+
+        harness.update(|view, _, cx| {
+            view.table
+                .update(cx, |table, cx| table.set_column_visible(1, true, cx))
+        });
+        assert!(harness.read(|view, cx| view.table.read(cx).is_column_visible(1)));
+    }
+
+    #[gpui::test]
+    fn test_table_dump_and_restore(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |window, cx| {
+            let delegate = Delegate {
+                columns: vec![
+                    Column::new("a", "A").width(100.),
+                    Column::new("b", "B").width(120.).sortable(),
+                    Column::new("c", "C").width(140.),
+                ],
+            };
+            TableView {
+                focus_handle: cx.focus_handle(),
+                table: cx.new(|cx| TableState::new(delegate, window, cx)),
+            }
+        });
+
+        let state = TableLayoutState {
+            columns: vec![
+                ColumnState {
+                    key: "b".into(),
+                    width: px(80.),
+                    hidden: false,
+                    sort: Some(ColumnSort::Ascending),
+                    fixed: Some(ColumnFixed::Left),
+                },
+                ColumnState {
+                    key: "unknown".into(),
+                    width: px(50.),
+                    hidden: false,
+                    sort: None,
+                    fixed: None,
+                },
+                ColumnState {
+                    key: "c".into(),
+                    width: px(200.),
+                    hidden: true,
+                    sort: None,
+                    fixed: None,
+                },
+            ],
+        };
+        harness.update(|view, window, cx| {
+            view.table
+                .update(cx, |table, cx| table.restore(state, window, cx))
+        });
+
+        let dumped = harness.read(|view, cx| view.table.read(cx).dump());
+        let keys = dumped
+            .columns
+            .iter()
+            .map(|c| c.key.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["b", "c", "a"]);
+        assert_eq!(dumped.columns[0].width, px(80.));
+        assert_eq!(dumped.columns[0].sort, Some(ColumnSort::Ascending));
+        assert_eq!(dumped.columns[0].fixed, Some(ColumnFixed::Left));
+        assert_eq!(dumped.columns[1].width, px(200.));
+        assert!(dumped.columns[1].hidden);
+        assert!(harness.read(|view, cx| !view.table.read(cx).is_column_visible(1)));
+        assert_eq!(dumped.columns[2].width, px(100.));
+
+        let json = serde_json::to_string(&dumped).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TableLayoutState>(&json).unwrap(),
+            dumped
+        );
+    }
+
+    #[gpui::test]
+    fn test_table_autofit_column(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |window, cx| {
+            let delegate = Delegate {
+                columns: vec![
+                    Column::new("a", "A").width(500.).auto_fit(),
+                    Column::new("b", "B").width(500.).min_width(200.),
+                ],
+            };
+            TableView {
+                focus_handle: cx.focus_handle(),
+                table: cx.new(|cx| TableState::new(delegate, window, cx)),
+            }
+        });
+
+        let events = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        harness.update(|view, _, cx| {
+            let events = events.clone();
+            cx.subscribe(&view.table, move |_, _, event: &TableEvent, _| {
+                if let TableEvent::ColumnWidthsChanged(widths) = event {
+                    events.borrow_mut().push(widths.clone());
+                }
+            })
+            .detach();
+        });
+
+        harness.update(|view, window, cx| {
+            view.table.update(cx, |table, cx| {
+                table.autofit_column(0, window, cx);
+                table.autofit_column(1, window, cx);
+            })
+        });
+
+        let widths = harness.read(|view, cx| {
+            let dumped = view.table.read(cx).dump();
+            dumped.columns.iter().map(|c| c.width).collect::<Vec<_>>()
+        });
+        assert!(widths[0] > px(0.) && widths[0] < px(500.));
+        // Clamped by the min width.
+        assert_eq!(widths[1], px(200.));
+        assert_eq!(events.borrow().len(), 2);
+        assert_eq!(events.borrow()[1], widths);
+    }
+
+    #[gpui::test]
+    fn test_table_move_row(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |window, cx| {
+            let delegate = Delegate {
+                columns: vec![Column::new("a", "A")],
+            };
+            TableView {
+                focus_handle: cx.focus_handle(),
+                table: cx.new(|cx| {
+                    TableState::new(delegate, window, cx)
+                        .row_movable(true)
+                        .multi_selectable(true)
+                }),
+            }
+        });
+
+        let events = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        harness.update(|view, _, cx| {
+            let events = events.clone();
+            cx.subscribe(&view.table, move |_, _, event: &TableEvent, _| {
+                if let TableEvent::MoveRow(from, to) = event {
+                    events.borrow_mut().push((*from, *to));
+                }
+            })
+            .detach();
+        });
+
+        harness.update(|view, window, cx| {
+            view.table.update(cx, |table, cx| {
+                table.set_selected_row(2, cx);
+                table.set_selected_rows([0, 3], cx);
+                table.move_row(2, 5, window, cx);
+                // Not moved.
+                table.move_row(4, 4, window, cx);
+            })
+        });
+
+        let (selected_row, selected_rows) = harness.read(|view, cx| {
+            let table = view.table.read(cx);
+            (table.selected_row(), table.selected_rows())
+        });
+        assert_eq!(selected_row, Some(5));
+        assert_eq!(selected_rows, vec![0, 2]);
+        assert_eq!(*events.borrow(), vec![(2, 5)]);
+    }
+
+    #[gpui::test]
+    fn test_table_export_csv(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |window, cx| {
+            let delegate = Delegate {
+                columns: vec![
+                    Column::new("a", "A"),
+                    Column::new("b", "B, Name"),
+                    Column::new("c", "C"),
+                ],
+            };
+            TableView {
+                focus_handle: cx.focus_handle(),
+                table: cx.new(|cx| TableState::new(delegate, window, cx)),
+            }
+        });
+
+        harness.update(|view, _, cx| {
+            view.table
+                .update(cx, |table, cx| table.set_column_visible(2, false, cx))
+        });
+
+        let mut export = |options: CsvExportOptions| {
+            harness.read(|view, cx| {
+                let mut buf = vec![];
+                view.table
+                    .read(cx)
+                    .export_csv(&mut buf, &options, cx)
+                    .unwrap();
+                String::from_utf8(buf).unwrap()
+            })
+        };
+
+        let csv = export(CsvExportOptions::new());
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "A,\"B, Name\",C");
+        assert_eq!(lines[1], "0:0,0:1,0:2");
+
+        let tsv = export(
+            CsvExportOptions::tsv()
+                .header(false)
+                .visible_only(true)
+                .cell_text(|row_ix, col_ix, _| format!("{}\t{}", row_ix, col_ix)),
+        );
+        let lines = tsv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[9], "\"9\t0\"\t\"9\t1\"");
+    }
+
+    #[gpui::test]
+    fn test_table_filters(cx: &mut TestAppContext) {
+        let mut harness = TestHarness::new(cx, |window, cx| {
+            let delegate = Delegate {
+                columns: vec![
+                    Column::new("a", "A").filter(ColumnFilter::Text),
+                    Column::new("b", "B").filter(ColumnFilter::Number),
+                ],
+            };
+            TableView {
+                focus_handle: cx.focus_handle(),
+                table: cx.new(|cx| TableState::new(delegate, window, cx)),
+            }
+        });
+
+        let events = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        harness.update(|view, _, cx| {
+            let events = events.clone();
+            cx.subscribe(&view.table, move |_, _, event: &TableEvent, _| {
+                if let TableEvent::FilterChanged(filters) = event {
+                    events.borrow_mut().push(filters.clone());
+                }
+            })
+            .detach();
+        });
+
+        let range = FilterValue::Range {
+            min: Some(1.),
+            max: None,
+        };
+        harness.update(|view, _, cx| {
+            view.table.update(cx, |table, cx| {
+                table.set_filter("b", Some(range.clone()), cx);
+                table.set_filter("a", Some(FilterValue::Contains("foo".into())), cx);
+                // Unchanged and empty values are ignored.
+                table.set_filter("b", Some(range.clone()), cx);
+                table.set_filter("a", Some(FilterValue::Contains(" ".into())), cx);
+            })
+        });
+        let filters = harness.read(|view, cx| view.table.read(cx).filters().to_vec());
+        assert_eq!(
+            filters,
+            vec![FilterDescriptor {
+                key: "b".into(),
+                value: range,
+            }]
+        );
+        assert_eq!(events.borrow().len(), 3);
+        assert_eq!(events.borrow().last(), Some(&filters));
+
+        harness.update(|view, _, cx| {
+            view.table.update(cx, |table, cx| {
+                table.clear_filters(cx);
+                table.clear_filters(cx);
+            })
+        });
+        assert!(harness.read(|view, cx| view.table.read(cx).filters().is_empty()));
+        assert_eq!(events.borrow().len(), 4);
+        assert_eq!(events.borrow().last(), Some(&vec![]));
+    }
+}
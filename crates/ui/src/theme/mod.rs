@@ -15,11 +15,17 @@ mod color;
 mod registry;
 mod schema;
 mod theme_color;
+mod zoom;
 
 pub use color::*;
 pub use registry::*;
 pub use schema::*;
 pub use theme_color::*;
+pub use zoom::{MAX_ZOOM, MIN_ZOOM, ResetZoom, ZoomIn, ZoomOut};
+
+/// The delay to reload the theme after the file changed, to skip the duplicate events.
+#[cfg(not(target_family = "wasm"))]
+const RELOAD_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
 
 pub fn init(cx: &mut App) {
     registry::init(cx);
@@ -86,6 +92,9 @@ pub struct Theme {
     pub list: ListSettings,
     /// The sheet settings.
     pub sheet: SheetSettings,
+    /// The zoom level of the UI, see [`Theme::set_zoom`].
+    #[serde(skip, default = "zoom::default_zoom")]
+    zoom: f32,
 }
 
 impl Default for Theme {
@@ -182,6 +191,90 @@ impl Theme {
         }
     }
 
+    /// Load the themes from a JSON file in the [`ThemeSet`] format, and apply the first light
+    /// and dark themes of it.
+    ///
+    /// The themes are also added to the [`ThemeRegistry`], replacing the themes with the same name.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn load_from_file(path: impl AsRef<std::path::Path>, cx: &mut App) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let theme_set = serde_json::from_str::<ThemeSet>(&content)?;
+        if theme_set.themes.is_empty() {
+            anyhow::bail!("no themes found in {}", path.display());
+        }
+
+        let themes = ThemeRegistry::global_mut(cx).insert_themes(theme_set.themes);
+        let theme = Theme::global_mut(cx);
+        for config in themes.into_iter().rev() {
+            if config.mode.is_dark() {
+                theme.dark_theme = config;
+            } else {
+                theme.light_theme = config;
+            }
+        }
+
+        let mode = theme.mode;
+        Self::change(mode, None, cx);
+        cx.refresh_windows();
+        Ok(())
+    }
+
+    /// Load the theme by [`Theme::load_from_file`], and watch the file to reload the theme
+    /// when the file changes, so the theme can be tweaked without recompiling.
+    ///
+    /// The invalid JSON is logged and ignored, the theme keeps the last loaded one.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn watch_file(path: impl Into<std::path::PathBuf>, cx: &mut App) -> anyhow::Result<()> {
+        use notify::Watcher as _;
+
+        let path = std::fs::canonicalize(path.into())?;
+        Self::load_from_file(&path, cx)?;
+        let Some(dir) = path.parent() else {
+            anyhow::bail!("invalid theme file: {}", path.display());
+        };
+
+        let (tx, rx) = smol::channel::bounded(100);
+        let mut watcher = notify::recommended_watcher({
+            let path = path.clone();
+            move |res: notify::Result<notify::Event>| {
+                let Ok(event) = res else {
+                    return;
+                };
+
+                if matches!(
+                    event.kind,
+                    notify::EventKind::Create(_) | notify::EventKind::Modify(_)
+                ) && event.paths.contains(&path)
+                {
+                    _ = tx.try_send(());
+                }
+            }
+        })?;
+        // Watch the parent directory, because some editors replace the file when saving.
+        watcher.watch(dir, notify::RecursiveMode::NonRecursive)?;
+
+        cx.spawn(async move |cx| {
+            // Keep the watcher alive.
+            let _watcher = watcher;
+
+            while rx.recv().await.is_ok() {
+                cx.background_executor().timer(RELOAD_DELAY).await;
+                while rx.try_recv().is_ok() {}
+
+                tracing::info!("Reloading theme from {}...", path.display());
+                _ = cx.update(|cx| {
+                    if let Err(err) = Self::load_from_file(&path, cx) {
+                        tracing::error!("Failed to reload theme: {:?}", err);
+                    }
+                });
+            }
+        })
+        .detach();
+
+        Ok(())
+    }
+
     /// Get the input background color.
     ///
     /// For dark, use a transparent color mixed with the input border: `cx.theme().input`,
@@ -236,6 +329,7 @@ impl From<&ThemeColor> for Theme {
             dark_theme: Rc::new(ThemeConfig::default()),
             highlight_theme: HighlightTheme::default_light(),
             sheet: SheetSettings::default(),
+            zoom: zoom::default_zoom(),
         }
     }
 }
@@ -284,3 +378,42 @@ impl From<WindowAppearance> for ThemeMode {
         }
     }
 }
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use gpui::{SharedString, TestAppContext};
+
+    use super::{Theme, ThemeRegistry};
+
+    #[gpui::test]
+    fn test_load_from_file(cx: &mut TestAppContext) {
+        let path = std::env::temp_dir().join("gpui-component-test-load-theme.json");
+        let json = serde_json::json!({
+            "name": "Test",
+            "themes": [
+                { "name": "Test Light", "mode": "light" },
+                { "name": "Test Dark", "mode": "dark" },
+            ]
+        });
+        std::fs::write(&path, json.to_string()).unwrap();
+
+        cx.update(|cx| {
+            super::init(cx);
+            Theme::load_from_file(&path, cx).unwrap();
+
+            let theme = Theme::global(cx);
+            assert_eq!(theme.light_theme.name.as_ref(), "Test Light");
+            assert_eq!(theme.dark_theme.name.as_ref(), "Test Dark");
+            assert_eq!(theme.theme_name().as_ref(), "Test Light");
+            assert!(
+                ThemeRegistry::global(cx)
+                    .themes()
+                    .contains_key(&SharedString::from("Test Dark"))
+            );
+
+            assert!(Theme::load_from_file(path.with_extension("missing"), cx).is_err());
+        });
+
+        std::fs::remove_file(path).ok();
+    }
+}
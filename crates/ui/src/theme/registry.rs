@@ -160,6 +160,20 @@ impl ThemeRegistry {
         Ok(())
     }
 
+    /// Insert the themes, replace the themes with the same name, returns the inserted themes.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) fn insert_themes(&mut self, themes: Vec<ThemeConfig>) -> Vec<Rc<ThemeConfig>> {
+        themes
+            .into_iter()
+            .map(|theme| {
+                let theme = Rc::new(theme);
+                self.themes.insert(theme.name.clone(), theme.clone());
+                self.has_custom_themes = true;
+                theme
+            })
+            .collect()
+    }
+
     fn init_default_themes(&mut self) {
         let default_themes: Vec<ThemeConfig> = serde_json::from_str::<ThemeSet>(DEFAULT_THEME)
             .expect("failed to parse default theme.")
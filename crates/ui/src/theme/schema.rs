@@ -214,6 +214,51 @@ pub struct ThemeConfigColors {
     /// Bearish color for candlestick charts (downward price movement).
     #[serde(rename = "chart_bearish")]
     pub chart_bearish: Option<SharedString>,
+    /// Chart categorical palette color 1, used for the 1st series.
+    #[serde(rename = "chart.series.1")]
+    pub chart_series_1: Option<SharedString>,
+    /// Chart categorical palette color 2, used for the 2nd series.
+    #[serde(rename = "chart.series.2")]
+    pub chart_series_2: Option<SharedString>,
+    /// Chart categorical palette color 3, used for the 3rd series.
+    #[serde(rename = "chart.series.3")]
+    pub chart_series_3: Option<SharedString>,
+    /// Chart categorical palette color 4, used for the 4th series.
+    #[serde(rename = "chart.series.4")]
+    pub chart_series_4: Option<SharedString>,
+    /// Chart categorical palette color 5, used for the 5th series.
+    #[serde(rename = "chart.series.5")]
+    pub chart_series_5: Option<SharedString>,
+    /// Chart categorical palette color 6, used for the 6th series.
+    #[serde(rename = "chart.series.6")]
+    pub chart_series_6: Option<SharedString>,
+    /// Chart categorical palette color 7, used for the 7th series.
+    #[serde(rename = "chart.series.7")]
+    pub chart_series_7: Option<SharedString>,
+    /// Chart categorical palette color 8, used for the 8th series.
+    #[serde(rename = "chart.series.8")]
+    pub chart_series_8: Option<SharedString>,
+    /// Chart categorical palette color 9, used for the 9th series.
+    #[serde(rename = "chart.series.9")]
+    pub chart_series_9: Option<SharedString>,
+    /// Chart categorical palette color 10, used for the 10th series.
+    #[serde(rename = "chart.series.10")]
+    pub chart_series_10: Option<SharedString>,
+    /// Chart grid line color.
+    #[serde(rename = "chart.grid")]
+    pub chart_grid: Option<SharedString>,
+    /// Chart axis line color.
+    #[serde(rename = "chart.axis")]
+    pub chart_axis: Option<SharedString>,
+    /// Chart axis label text color.
+    #[serde(rename = "chart.axis.foreground")]
+    pub chart_axis_foreground: Option<SharedString>,
+    /// Chart tooltip background color.
+    #[serde(rename = "chart.tooltip.background")]
+    pub chart_tooltip: Option<SharedString>,
+    /// Chart tooltip text color.
+    #[serde(rename = "chart.tooltip.foreground")]
+    pub chart_tooltip_foreground: Option<SharedString>,
     /// Danger background color.
     #[serde(rename = "danger.background")]
     pub danger: Option<SharedString>,
@@ -757,6 +802,19 @@ impl ThemeColor {
         apply_color!(chart_5, fallback = self.blue.darken(0.4));
         apply_color!(chart_bullish, fallback = self.green);
         apply_color!(chart_bearish, fallback = self.red);
+        apply_color!(chart_series_1, fallback = self.chart_2);
+        apply_color!(chart_series_2, fallback = self.green);
+        apply_color!(chart_series_3, fallback = self.yellow);
+        apply_color!(chart_series_4, fallback = self.magenta);
+        apply_color!(chart_series_5, fallback = self.cyan);
+        apply_color!(chart_series_6, fallback = self.red);
+        apply_color!(chart_series_7, fallback = self.blue_light);
+        apply_color!(chart_series_8, fallback = self.green_light);
+        apply_color!(chart_series_9, fallback = self.yellow_light);
+        apply_color!(chart_series_10, fallback = self.magenta_light);
+        apply_color!(chart_grid, fallback = self.border);
+        apply_color!(chart_axis, fallback = self.border);
+        apply_color!(chart_axis_foreground, fallback = self.muted_foreground);
         apply_background_color!(danger, fallback = self.red);
         apply_background_color!(danger_active, fallback = self.danger.darken(active_darken));
         apply_color!(danger_foreground, fallback = self.primary_foreground);
@@ -804,6 +862,8 @@ impl ThemeColor {
         apply_background_color!(list_hover, fallback = self.accent.opacity(0.6));
         apply_background_color!(popover, fallback = tokens.background);
         apply_color!(popover_foreground, fallback = self.foreground);
+        apply_background_color!(chart_tooltip, fallback = tokens.popover);
+        apply_color!(chart_tooltip_foreground, fallback = self.popover_foreground);
         apply_background_color!(progress_bar, fallback = tokens.primary);
         apply_color!(ring, fallback = self.blue);
         apply_background_color!(scrollbar, fallback = tokens.background);
@@ -915,7 +975,7 @@ impl Theme {
         };
 
         if let Some(font_size) = config.font_size {
-            self.font_size = px(font_size);
+            self.font_size = px(font_size) * self.zoom();
         }
         if let Some(font_family) = &config.font_family {
             self.font_family = font_family.clone();
@@ -924,13 +984,13 @@ impl Theme {
             self.mono_font_family = mono_font_family.clone();
         }
         if let Some(mono_font_size) = config.mono_font_size {
-            self.mono_font_size = px(mono_font_size);
+            self.mono_font_size = px(mono_font_size) * self.zoom();
         }
         if let Some(radius) = config.radius {
-            self.radius = px(radius as f32);
+            self.radius = px(radius as f32) * self.zoom();
         }
         if let Some(radius_lg) = config.radius_lg {
-            self.radius_lg = px(radius_lg as f32);
+            self.radius_lg = px(radius_lg as f32) * self.zoom();
         }
         if let Some(shadow) = config.shadow {
             self.shadow = shadow;
@@ -1029,6 +1089,39 @@ mod tests {
         assert_ne!(theme.tokens.status_bar.background, theme.status_bar.into());
     }
 
+    #[test]
+    fn test_apply_config_chart_palette() {
+        let config = serde_json::from_value::<ThemeConfig>(serde_json::json!({
+            "name": "Chart",
+            "mode": "light",
+            "colors": {
+                "chart.2": "#3b82f6",
+                "chart.series.3": "#f59e0b",
+                "chart.grid": "#e5e5e5",
+                "chart.tooltip.background": "#171717",
+            }
+        }))
+        .unwrap();
+
+        let mut theme = Theme::default();
+        theme.apply_config(&std::rc::Rc::new(config));
+
+        // `chart.series.1` falls back to `chart.2`, the single-series default.
+        assert_eq!(theme.chart_series_1, try_parse_color("#3b82f6").unwrap());
+        assert_eq!(theme.chart_series_2, theme.green);
+        assert_eq!(theme.chart_series(2), try_parse_color("#f59e0b").unwrap());
+        assert_eq!(theme.chart_palette().len(), 10);
+        // The palette cycles once every color has been used.
+        assert_eq!(theme.chart_series(10), theme.chart_series_1);
+        assert_eq!(theme.chart_series(12), theme.chart_series(2));
+
+        assert_eq!(theme.chart_grid, try_parse_color("#e5e5e5").unwrap());
+        assert_eq!(theme.chart_axis, theme.border);
+        assert_eq!(theme.chart_axis_foreground, theme.muted_foreground);
+        assert_eq!(theme.chart_tooltip, try_parse_color("#171717").unwrap());
+        assert_eq!(theme.chart_tooltip_foreground, theme.popover_foreground);
+    }
+
     #[test]
     fn test_apply_config_clamps_highlight_alpha_per_gradient_stop() {
         let config = serde_json::from_value::<ThemeConfig>(serde_json::json!({
@@ -145,6 +145,36 @@ pub struct ThemeColor {
     pub chart_bullish: Hsla,
     /// Bearish color for candlestick charts (downward price movement).
     pub chart_bearish: Hsla,
+    /// Chart categorical palette color 1, used for the 1st series.
+    pub chart_series_1: Hsla,
+    /// Chart categorical palette color 2, used for the 2nd series.
+    pub chart_series_2: Hsla,
+    /// Chart categorical palette color 3, used for the 3rd series.
+    pub chart_series_3: Hsla,
+    /// Chart categorical palette color 4, used for the 4th series.
+    pub chart_series_4: Hsla,
+    /// Chart categorical palette color 5, used for the 5th series.
+    pub chart_series_5: Hsla,
+    /// Chart categorical palette color 6, used for the 6th series.
+    pub chart_series_6: Hsla,
+    /// Chart categorical palette color 7, used for the 7th series.
+    pub chart_series_7: Hsla,
+    /// Chart categorical palette color 8, used for the 8th series.
+    pub chart_series_8: Hsla,
+    /// Chart categorical palette color 9, used for the 9th series.
+    pub chart_series_9: Hsla,
+    /// Chart categorical palette color 10, used for the 10th series.
+    pub chart_series_10: Hsla,
+    /// Chart grid line color.
+    pub chart_grid: Hsla,
+    /// Chart axis line color.
+    pub chart_axis: Hsla,
+    /// Chart axis label text color.
+    pub chart_axis_foreground: Hsla,
+    /// Chart tooltip background color.
+    pub chart_tooltip: Hsla,
+    /// Chart tooltip text color.
+    pub chart_tooltip_foreground: Hsla,
     /// Danger background color.
     pub danger: Hsla,
     /// Danger active background color.
@@ -414,6 +444,21 @@ define_theme_tokens! {
     chart_5,
     chart_bullish,
     chart_bearish,
+    chart_series_1,
+    chart_series_2,
+    chart_series_3,
+    chart_series_4,
+    chart_series_5,
+    chart_series_6,
+    chart_series_7,
+    chart_series_8,
+    chart_series_9,
+    chart_series_10,
+    chart_grid,
+    chart_axis,
+    chart_axis_foreground,
+    chart_tooltip,
+    chart_tooltip_foreground,
     danger,
     danger_active,
     danger_foreground,
@@ -522,4 +567,27 @@ impl ThemeColor {
     pub fn dark() -> Arc<Self> {
         DEFAULT_THEME_COLORS[&ThemeMode::Dark].0.clone()
     }
+
+    /// The ordered categorical chart palette, `chart_series_1` through `chart_series_10`.
+    pub fn chart_palette(&self) -> [Hsla; 10] {
+        [
+            self.chart_series_1,
+            self.chart_series_2,
+            self.chart_series_3,
+            self.chart_series_4,
+            self.chart_series_5,
+            self.chart_series_6,
+            self.chart_series_7,
+            self.chart_series_8,
+            self.chart_series_9,
+            self.chart_series_10,
+        ]
+    }
+
+    /// Get the chart palette color for the series at `ix`, cycling when there are
+    /// more series than palette colors.
+    pub fn chart_series(&self, ix: usize) -> Hsla {
+        let palette = self.chart_palette();
+        palette[ix % palette.len()]
+    }
 }
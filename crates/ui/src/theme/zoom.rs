@@ -0,0 +1,169 @@
+use std::rc::Rc;
+
+use gpui::{App, Global, KeyBinding, Pixels, SharedString, Window, actions};
+
+use crate::{storage::Storage, theme::Theme};
+
+actions!(theme, [ZoomIn, ZoomOut, ResetZoom]);
+
+/// The min zoom level of the UI.
+pub const MIN_ZOOM: f32 = 0.5;
+/// The max zoom level of the UI.
+pub const MAX_ZOOM: f32 = 3.0;
+/// The zoom level changed by each [`Theme::zoom_in`] and [`Theme::zoom_out`].
+const ZOOM_STEP: f32 = 0.1;
+
+pub(super) fn default_zoom() -> f32 {
+    1.0
+}
+
+/// The storage to persist the zoom level, see [`Theme::persist_zoom`].
+struct ZoomStorage {
+    key: SharedString,
+    storage: Rc<dyn Storage>,
+}
+
+impl Global for ZoomStorage {}
+
+impl Theme {
+    /// Returns the zoom level of the UI, default is `1.0`.
+    #[inline]
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Returns the `value` scaled by the zoom level.
+    ///
+    /// Use this for the fixed sizes in your views to follow the zoom level,
+    /// the `rems` based sizes are scaled by the [`Theme::font_size`] already.
+    #[inline]
+    pub fn scaled(&self, value: Pixels) -> Pixels {
+        value * self.zoom
+    }
+
+    /// Returns the `value` scaled by the zoom level and rounded to the device pixels
+    /// of the monitor that the window is on, to keep the thin lines sharp on any DPI.
+    pub fn scaled_to_device(&self, value: Pixels, window: &Window) -> Pixels {
+        round_to_device_pixels(self.scaled(value), window.scale_factor())
+    }
+
+    /// Set the zoom level of the UI, clamped to [`MIN_ZOOM`]..=[`MAX_ZOOM`].
+    ///
+    /// The font sizes and radii of the theme are scaled, the spacing in `rems` follows the font size.
+    pub fn set_zoom(zoom: f32, window: Option<&mut Window>, cx: &mut App) {
+        let zoom = clamp_zoom(zoom);
+        let theme = Theme::global_mut(cx);
+        if theme.zoom == zoom {
+            return;
+        }
+
+        let ratio = zoom / theme.zoom;
+        theme.zoom = zoom;
+        theme.font_size = theme.font_size * ratio;
+        theme.mono_font_size = theme.mono_font_size * ratio;
+        theme.radius = theme.radius * ratio;
+        theme.radius_lg = theme.radius_lg * ratio;
+        theme.tile_radius = theme.tile_radius * ratio;
+
+        if let Some(zoom_storage) = cx.try_global::<ZoomStorage>() {
+            zoom_storage
+                .storage
+                .set(&zoom_storage.key, zoom.to_string());
+        }
+
+        match window {
+            Some(window) => window.refresh(),
+            None => cx.refresh_windows(),
+        }
+    }
+
+    /// Increase the zoom level by a step.
+    pub fn zoom_in(window: Option<&mut Window>, cx: &mut App) {
+        let zoom = Theme::global(cx).zoom + ZOOM_STEP;
+        Self::set_zoom(zoom, window, cx);
+    }
+
+    /// Decrease the zoom level by a step.
+    pub fn zoom_out(window: Option<&mut Window>, cx: &mut App) {
+        let zoom = Theme::global(cx).zoom - ZOOM_STEP;
+        Self::set_zoom(zoom, window, cx);
+    }
+
+    /// Reset the zoom level to `1.0`.
+    pub fn reset_zoom(window: Option<&mut Window>, cx: &mut App) {
+        Self::set_zoom(default_zoom(), window, cx);
+    }
+
+    /// Bind the default zoom keys in the [`crate::Root`] view, it's opt-in to not override the app shortcuts.
+    ///
+    /// - `secondary-=` / `secondary-+` to [`ZoomIn`]
+    /// - `secondary--` to [`ZoomOut`]
+    /// - `secondary-0` to [`ResetZoom`]
+    pub fn bind_zoom_keys(cx: &mut App) {
+        let context = Some(crate::root::CONTEXT);
+        cx.bind_keys([
+            KeyBinding::new("secondary-=", ZoomIn, context),
+            KeyBinding::new("secondary-+", ZoomIn, context),
+            KeyBinding::new("secondary--", ZoomOut, context),
+            KeyBinding::new("secondary-0", ResetZoom, context),
+        ]);
+    }
+
+    /// Persist the zoom level in the `storage` with the `key`.
+    ///
+    /// The saved zoom level is restored immediately, and saved when changed.
+    pub fn persist_zoom(key: impl Into<SharedString>, storage: Rc<dyn Storage>, cx: &mut App) {
+        let key = key.into();
+        let zoom = storage
+            .get(&key)
+            .and_then(|value| value.parse::<f32>().ok());
+        cx.set_global(ZoomStorage { key, storage });
+
+        if let Some(zoom) = zoom {
+            Self::set_zoom(zoom, None, cx);
+        }
+    }
+}
+
+/// Clamp the zoom level and round it to 2 decimals, to avoid the float errors of the steps.
+fn clamp_zoom(zoom: f32) -> f32 {
+    if !zoom.is_finite() {
+        return default_zoom();
+    }
+
+    ((zoom * 100.).round() / 100.).clamp(MIN_ZOOM, MAX_ZOOM)
+}
+
+/// Round the `value` to the whole device pixels of the `scale_factor`.
+fn round_to_device_pixels(value: Pixels, scale_factor: f32) -> Pixels {
+    if scale_factor <= 0. {
+        return value;
+    }
+
+    (value * scale_factor).round() / scale_factor
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::{clamp_zoom, round_to_device_pixels};
+
+    #[test]
+    fn test_clamp_zoom() {
+        assert_eq!(clamp_zoom(1.0), 1.0);
+        assert_eq!(clamp_zoom(1.0 + 0.1 + 0.1), 1.2);
+        assert_eq!(clamp_zoom(0.1), 0.5);
+        assert_eq!(clamp_zoom(5.), 3.0);
+        assert_eq!(clamp_zoom(f32::NAN), 1.0);
+    }
+
+    #[test]
+    fn test_round_to_device_pixels() {
+        assert_eq!(round_to_device_pixels(px(1.2), 1.), px(1.));
+        assert_eq!(round_to_device_pixels(px(1.2), 2.), px(1.));
+        assert_eq!(round_to_device_pixels(px(1.3), 2.), px(1.5));
+        assert_eq!(round_to_device_pixels(px(1.4), 1.5), px(4. / 3.));
+        assert_eq!(round_to_device_pixels(px(1.4), 0.), px(1.4));
+    }
+}
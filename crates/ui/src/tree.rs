@@ -1,21 +1,30 @@
-use std::{cell::RefCell, ops::Range, rc::Rc};
+use std::{cell::RefCell, collections::HashSet, ops::Range, rc::Rc};
+
+use instant::{Duration, Instant};
 
 use gpui::{
-    App, Context, ElementId, Entity, EventEmitter, FocusHandle, InteractiveElement as _,
-    IntoElement, KeyBinding, ListSizingBehavior, MouseButton, ParentElement, Render, RenderOnce,
-    SharedString, StyleRefinement, Styled, UniformListScrollHandle, Window, div,
-    prelude::FluentBuilder as _, uniform_list,
+    App, Bounds, Context, DragMoveEvent, ElementId, Entity, EntityId, EventEmitter, FocusHandle,
+    InteractiveElement as _, IntoElement, KeyBinding, KeyDownEvent, ListSizingBehavior,
+    MouseButton, ParentElement, Pixels, Render, RenderOnce, SharedString, StyleRefinement, Styled,
+    Task, UniformListScrollHandle, Window, div, prelude::FluentBuilder as _, px, uniform_list,
 };
 
 use crate::{
-    Selectable as _, StyledExt,
+    ActiveTheme as _, Disableable as _, Selectable as _, Sizable as _, StyledExt,
     actions::{Confirm, SelectDown, SelectLeft, SelectRight, SelectUp},
+    checkbox::Checkbox,
+    dnd::{DragDropExt as _, DragItem},
     list::ListItem,
     menu::{ContextMenuExt as _, PopupMenu},
     scroll::ScrollableElement,
+    spinner::Spinner,
+    type_ahead::TypeAhead,
 };
 
 const CONTEXT: &str = "Tree";
+/// The delay to expand a collapsed folder when dragging a node over it.
+const AUTO_EXPAND_DELAY: Duration = Duration::from_millis(600);
+
 pub(crate) fn init(cx: &mut App) {
     cx.bind_keys([
         KeyBinding::new("up", SelectUp, Some(CONTEXT)),
@@ -57,6 +66,34 @@ where
 struct TreeItemState {
     expanded: bool,
     disabled: bool,
+    /// Whether the item has children to be loaded by [`TreeState::load_children`].
+    has_children: bool,
+    loading: bool,
+    /// The children loaded by [`TreeState::load_children`], `None` if not loaded yet.
+    loaded_children: Option<Vec<TreeItem>>,
+}
+
+type LoadChildren = Rc<dyn Fn(&TreeItem, &mut Context<TreeState>) -> Task<Vec<TreeItem>>>;
+type CanDrop = Rc<dyn Fn(&TreeItem, &TreeItem, TreeDropPosition, &App) -> bool>;
+
+/// The position to drop a dragging node relative to the target node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeDropPosition {
+    /// Insert before the target node, as a sibling.
+    Before,
+    /// Insert as the last child of the target folder.
+    Inside,
+    /// Insert after the target node, as a sibling.
+    After,
+}
+
+type DragTreeNodeMoveEvent = DragMoveEvent<DragItem<DragTreeNode>>;
+
+/// The payload of a dragging node of a draggable [`TreeState`].
+#[derive(Clone)]
+pub(crate) struct DragTreeNode {
+    entity_id: EntityId,
+    id: SharedString,
 }
 
 /// A tree item with a label, children, and an expanded state.
@@ -109,6 +146,12 @@ impl TreeEntry {
     pub fn is_disabled(&self) -> bool {
         self.item.is_disabled()
     }
+
+    /// Return true if the children of this item are loading.
+    #[inline]
+    pub fn is_loading(&self) -> bool {
+        self.item.is_loading()
+    }
 }
 
 /// Event emitted by [`TreeState`] when user-visible state changes.
@@ -118,6 +161,14 @@ pub enum TreeEvent {
     Expanded(SharedString),
     /// A tree node was collapsed.
     Collapsed(SharedString),
+    /// A tree node was moved to the `position` of the `target` node by dragging.
+    NodeMoved {
+        id: SharedString,
+        target: SharedString,
+        position: TreeDropPosition,
+    },
+    /// The checked nodes were changed by the checkboxes, with the [`TreeState::checked_paths`].
+    CheckedChanged(Vec<SharedString>),
 }
 
 impl TreeItem {
@@ -139,6 +190,9 @@ impl TreeItem {
             state: Rc::new(RefCell::new(TreeItemState {
                 expanded: false,
                 disabled: false,
+                has_children: false,
+                loading: false,
+                loaded_children: None,
             })),
         }
     }
@@ -167,10 +221,56 @@ impl TreeItem {
         self
     }
 
+    /// Mark this item has children without materializing them, default is false.
+    ///
+    /// The children are loaded by [`TreeState::load_children`] on the first expand.
+    pub fn has_children(self, has_children: bool) -> Self {
+        self.state.borrow_mut().has_children = has_children;
+        self
+    }
+
     /// Whether this item is a folder (has children).
     #[inline]
     pub fn is_folder(&self) -> bool {
-        self.children.len() > 0
+        self.children.len() > 0 || self.state.borrow().has_children
+    }
+
+    /// Return true if the children of this item are loading.
+    #[inline]
+    pub fn is_loading(&self) -> bool {
+        self.state.borrow().loading
+    }
+
+    /// Return true if the children need to be loaded by [`TreeState::load_children`].
+    fn needs_load(&self) -> bool {
+        let state = self.state.borrow();
+        state.has_children && !state.loading && state.loaded_children.is_none()
+    }
+
+    /// Find the item with the `id` in this item and its descendants.
+    fn find(&self, id: &SharedString) -> Option<TreeItem> {
+        if self.id == *id {
+            return Some(self.clone());
+        }
+
+        self.all_children().iter().find_map(|child| child.find(id))
+    }
+
+    /// Collect the ids of this item and its descendants.
+    fn collect_ids(&self, ids: &mut Vec<SharedString>) {
+        ids.push(self.id.clone());
+        for child in self.all_children() {
+            child.collect_ids(ids);
+        }
+    }
+
+    /// Returns the children, including the loaded children.
+    fn all_children(&self) -> Vec<TreeItem> {
+        let mut children = self.children.clone();
+        if let Some(loaded_children) = self.state.borrow().loaded_children.as_ref() {
+            children.extend(loaded_children.iter().cloned());
+        }
+        children
     }
 
     /// Return true if the item is disabled.
@@ -189,7 +289,7 @@ impl TreeItem {
             return Some(vec![]);
         }
 
-        for child in &self.children {
+        for child in &self.all_children() {
             if let Some(mut path) = child.find_ancestors(target_id) {
                 path.push(self.clone());
                 return Some(path);
@@ -211,6 +311,15 @@ pub struct TreeState {
     context_menu_builder: Option<
         Rc<dyn Fn(usize, &TreeEntry, PopupMenu, &mut Window, &mut Context<TreeState>) -> PopupMenu>,
     >,
+    load_children: Option<LoadChildren>,
+    draggable: bool,
+    can_drop: Option<CanDrop>,
+    /// The entry index and the position to drop the dragging node.
+    drop_target: Option<(usize, TreeDropPosition)>,
+    _auto_expand_task: Option<Task<()>>,
+    checkable: bool,
+    checked_ids: HashSet<SharedString>,
+    type_ahead: TypeAhead,
 }
 
 impl EventEmitter<TreeEvent> for TreeState {}
@@ -226,7 +335,145 @@ impl TreeState {
             entries: Vec::new(),
             render_item: Rc::new(|_, _, _, _, _| ListItem::new(0)),
             context_menu_builder: None,
+            load_children: None,
+            draggable: false,
+            can_drop: None,
+            drop_target: None,
+            _auto_expand_task: None,
+            checkable: false,
+            checked_ids: HashSet::new(),
+            type_ahead: TypeAhead::default(),
+        }
+    }
+
+    /// Sets whether to show the checkboxes before the items, default is false.
+    ///
+    /// Checking a parent checks all its descendants, and the parent shows
+    /// the indeterminate state when only some of its descendants are checked.
+    pub fn checkable(mut self, checkable: bool) -> Self {
+        self.checkable = checkable;
+        self
+    }
+
+    /// Sets whether to show the checkboxes before the items, default is false.
+    pub fn set_checkable(&mut self, checkable: bool, cx: &mut Context<Self>) {
+        self.checkable = checkable;
+        cx.notify();
+    }
+
+    /// Returns the ids of the checked nodes in the tree order,
+    /// a parent is included only if all its descendants are checked.
+    pub fn checked_paths(&self) -> Vec<SharedString> {
+        let mut ids = vec![];
+        for item in self.root_items() {
+            item.collect_ids(&mut ids);
+        }
+        ids.retain(|id| self.checked_ids.contains(id));
+        ids
+    }
+
+    /// Returns true if the node with the `id` is checked.
+    pub fn is_checked(&self, id: &SharedString) -> bool {
+        self.checked_ids.contains(id)
+    }
+
+    /// Returns true if the node with the `id` is not checked, but some of its descendants are.
+    pub fn is_indeterminate(&self, id: &SharedString) -> bool {
+        self.find_item(id)
+            .is_some_and(|item| self.is_item_indeterminate(&item))
+    }
+
+    fn is_item_indeterminate(&self, item: &TreeItem) -> bool {
+        !self.checked_ids.contains(&item.id)
+            && item.all_children().iter().any(|child| {
+                self.checked_ids.contains(&child.id) || self.is_item_indeterminate(child)
+            })
+    }
+
+    /// Check or uncheck the node with the `id` and all its descendants,
+    /// then update the checked state of its ancestors.
+    pub fn set_checked(&mut self, id: &SharedString, checked: bool, cx: &mut Context<Self>) {
+        let Some(item) = self.find_item(id) else {
+            return;
+        };
+
+        let mut ids = vec![];
+        item.collect_ids(&mut ids);
+        for id in ids {
+            if checked {
+                self.checked_ids.insert(id);
+            } else {
+                self.checked_ids.remove(&id);
+            }
         }
+        self.update_checked_ancestors(id);
+
+        cx.emit(TreeEvent::CheckedChanged(self.checked_paths()));
+        cx.notify();
+    }
+
+    /// Update the ancestors of the node with the `id` to be checked if all their children are.
+    fn update_checked_ancestors(&mut self, id: &SharedString) {
+        let ancestors = self
+            .root_items()
+            .iter()
+            .find_map(|item| item.find_ancestors(id))
+            .unwrap_or_default();
+
+        // The ancestors are ordered from the parent to the root.
+        for ancestor in ancestors {
+            let children = ancestor.all_children();
+            if children.is_empty() {
+                continue;
+            }
+
+            if children
+                .iter()
+                .all(|child| self.checked_ids.contains(&child.id))
+            {
+                self.checked_ids.insert(ancestor.id.clone());
+            } else {
+                self.checked_ids.remove(&ancestor.id);
+            }
+        }
+    }
+
+    /// Sets whether the nodes can be moved by dragging, default is false.
+    pub fn draggable(mut self, draggable: bool) -> Self {
+        self.draggable = draggable;
+        self
+    }
+
+    /// Sets whether the nodes can be moved by dragging, default is false.
+    pub fn set_draggable(&mut self, draggable: bool, cx: &mut Context<Self>) {
+        self.draggable = draggable;
+        self.drop_target = None;
+        cx.notify();
+    }
+
+    /// Set the callback to validate dropping the dragging node (first argument)
+    /// to the position of the target node (second argument).
+    ///
+    /// A node can never be dropped into itself or its descendants,
+    /// and only the folders accept [`TreeDropPosition::Inside`].
+    pub fn can_drop<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&TreeItem, &TreeItem, TreeDropPosition, &App) -> bool + 'static,
+    {
+        self.can_drop = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the loader of the children for the items marked by [`TreeItem::has_children`].
+    ///
+    /// The loader is called on the first expand of the item, and a spinner is shown
+    /// on the item until the returned task is finished.
+    pub fn load_children<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&TreeItem, &mut Context<TreeState>) -> Task<Vec<TreeItem>> + 'static,
+    {
+        self.load_children = Some(Rc::new(f));
+        self
     }
 
     /// Set the tree items.
@@ -251,6 +498,11 @@ impl TreeState {
         cx.notify();
     }
 
+    /// Get the scroll handle of the tree.
+    pub fn scroll_handle(&self) -> &UniformListScrollHandle {
+        &self.scroll_handle
+    }
+
     /// Get the currently selected index, if any.
     pub fn selected_index(&self) -> Option<usize> {
         self.selected_ix
@@ -318,6 +570,90 @@ impl TreeState {
         self.selected_ix.and_then(|ix| self.entries.get(ix))
     }
 
+    /// Move the node with the `id` to the `position` of the `target` node.
+    ///
+    /// No-op if the move is rejected, see [`TreeState::can_drop`].
+    pub fn move_node(
+        &mut self,
+        id: &SharedString,
+        target: &SharedString,
+        position: TreeDropPosition,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_move_node(id, target, position, cx) {
+            return;
+        }
+
+        let selected_id = self.selected_item().map(|item| item.id.clone());
+        let old_parent = self
+            .root_items()
+            .iter()
+            .find_map(|item| item.find_ancestors(id))
+            .and_then(|ancestors| ancestors.into_iter().next());
+        let mut items = self.root_items();
+        let Some(item) = take_item(&mut items, id) else {
+            return;
+        };
+        if insert_item(&mut items, item, target, position).is_some() {
+            return;
+        }
+
+        self.entries.clear();
+        for item in items.into_iter() {
+            self.add_entry(item, 0);
+        }
+        if position == TreeDropPosition::Inside {
+            if let Some(target) = self.find_item(target).filter(|item| item.needs_load()) {
+                self.load_item_children(target, cx);
+            }
+        }
+
+        if let Some(old_parent) = old_parent {
+            // Update by the first child of the parent, the parent itself may have no children.
+            if let Some(child) = old_parent.all_children().first() {
+                self.update_checked_ancestors(&child.id);
+            }
+        }
+        self.update_checked_ancestors(id);
+
+        self.selected_ix = selected_id.and_then(|id| self.index_of(&id));
+        self.right_clicked_ix = None;
+        cx.emit(TreeEvent::NodeMoved {
+            id: id.clone(),
+            target: target.clone(),
+            position,
+        });
+        cx.notify();
+    }
+
+    fn can_move_node(
+        &self,
+        id: &SharedString,
+        target: &SharedString,
+        position: TreeDropPosition,
+        cx: &App,
+    ) -> bool {
+        let (Some(item), Some(target_item)) = (self.find_item(id), self.find_item(target)) else {
+            return false;
+        };
+
+        // Can't move into itself or its descendants.
+        if item.find_ancestors(target).is_some() {
+            return false;
+        }
+        if position == TreeDropPosition::Inside && !target_item.is_folder() {
+            return false;
+        }
+
+        self.can_drop
+            .as_ref()
+            .map_or(true, |can_drop| can_drop(&item, &target_item, position, cx))
+    }
+
+    fn find_item(&self, id: &SharedString) -> Option<TreeItem> {
+        self.root_items().iter().find_map(|item| item.find(id))
+    }
+
     fn expand_ancestors(&mut self, target_id: SharedString, cx: &mut Context<Self>) {
         let mut ancestors = Vec::new();
 
@@ -348,8 +684,8 @@ impl TreeState {
             depth,
         });
         if item.is_expanded() {
-            for child in &item.children {
-                self.add_entry(child.clone(), depth + 1);
+            for child in item.all_children() {
+                self.add_entry(child, depth + 1);
             }
         }
     }
@@ -363,8 +699,13 @@ impl TreeState {
         }
 
         let expanded = !entry.is_expanded();
-        let id = entry.item.id.clone();
-        entry.item.state.borrow_mut().expanded = expanded;
+        let item = entry.item.clone();
+        let id = item.id.clone();
+        item.state.borrow_mut().expanded = expanded;
+
+        if expanded && item.needs_load() {
+            self.load_item_children(item, cx);
+        }
 
         if expanded {
             cx.emit(TreeEvent::Expanded(id));
@@ -376,13 +717,47 @@ impl TreeState {
         self.rebuild_entries();
     }
 
-    fn rebuild_entries(&mut self) {
-        let root_items: Vec<TreeItem> = self
-            .entries
+    fn load_item_children(&mut self, item: TreeItem, cx: &mut Context<Self>) {
+        let Some(load_children) = self.load_children.clone() else {
+            return;
+        };
+
+        item.state.borrow_mut().loading = true;
+        let task = load_children(&item, cx);
+        cx.spawn(async move |this, cx| {
+            let children = task.await;
+            _ = this.update(cx, |this, cx| {
+                // The loaded children of a checked node are checked.
+                if this.checked_ids.contains(&item.id) {
+                    let mut ids = vec![];
+                    for child in &children {
+                        child.collect_ids(&mut ids);
+                    }
+                    this.checked_ids.extend(ids);
+                }
+
+                let mut state = item.state.borrow_mut();
+                state.loading = false;
+                state.loaded_children = Some(children);
+                drop(state);
+
+                this.rebuild_entries();
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn root_items(&self) -> Vec<TreeItem> {
+        self.entries
             .iter()
             .filter(|e| e.is_root())
             .map(|e| e.item.clone())
-            .collect();
+            .collect()
+    }
+
+    fn rebuild_entries(&mut self) {
+        let root_items = self.root_items();
         self.entries.clear();
         for item in root_items.into_iter() {
             self.add_entry(item, 0);
@@ -455,6 +830,122 @@ impl TreeState {
         cx.notify();
     }
 
+    /// Update the drop target when dragging a node over the entry at the `ix`.
+    fn on_entry_drag_move(&mut self, e: &DragTreeNodeMoveEvent, ix: usize, cx: &mut Context<Self>) {
+        let drag = e.drag(cx).value().clone();
+        if drag.entity_id != cx.entity_id() || !e.bounds.contains(&e.event.position) {
+            return;
+        }
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+
+        let position = drop_position(e.event.position.y, e.bounds, entry.is_folder());
+        let target = self
+            .can_move_node(&drag.id, &entry.item.id, position, cx)
+            .then_some((ix, position));
+        if self.drop_target != target {
+            self.drop_target = target;
+            self.schedule_auto_expand(cx);
+            cx.notify();
+        }
+    }
+
+    /// Expand the collapsed folder after hovering inside it for a while.
+    fn schedule_auto_expand(&mut self, cx: &mut Context<Self>) {
+        self._auto_expand_task = None;
+        let Some((ix, TreeDropPosition::Inside)) = self.drop_target else {
+            return;
+        };
+        if self
+            .entries
+            .get(ix)
+            .map_or(true, |entry| entry.is_expanded())
+        {
+            return;
+        }
+
+        self._auto_expand_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(AUTO_EXPAND_DELAY).await;
+            _ = this.update(cx, |this, cx| {
+                if this.drop_target == Some((ix, TreeDropPosition::Inside)) && cx.has_active_drag()
+                {
+                    this.toggle_expand(ix, cx);
+                    cx.notify();
+                }
+            });
+        }));
+    }
+
+    fn on_entry_drop(
+        &mut self,
+        drag: &DragItem<DragTreeNode>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let drag = drag.value();
+        if drag.entity_id != cx.entity_id() {
+            return;
+        }
+
+        self._auto_expand_task = None;
+        let Some((ix, position)) = self.drop_target.take() else {
+            return;
+        };
+        let Some(target) = self.entries.get(ix).map(|entry| entry.item.id.clone()) else {
+            return;
+        };
+        self.move_node(&drag.id, &target, position, cx);
+    }
+
+    fn render_entry_checkbox(&self, entry: &TreeEntry, cx: &mut Context<Self>) -> impl IntoElement {
+        let id = entry.item.id.clone();
+        let checked = self.checked_ids.contains(&id);
+
+        div()
+            .flex_shrink_0()
+            .pl_2()
+            // Avoid toggling the expanded state by the mouse down on the row.
+            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+            .child(
+                Checkbox::new(SharedString::from(format!("tree-checkbox-{}", id)))
+                    .checked(checked)
+                    .indeterminate(self.is_item_indeterminate(&entry.item))
+                    .disabled(entry.is_disabled())
+                    .tab_stop(false)
+                    .on_click(cx.listener(move |this, checked: &bool, _, cx| {
+                        cx.stop_propagation();
+                        this.set_checked(&id, *checked, cx);
+                    })),
+            )
+    }
+
+    /// Select the next item whose label starts with the typed prefix.
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.focus_handle.is_focused(window) {
+            return;
+        }
+        let Some(text) = TypeAhead::key_char(event) else {
+            return;
+        };
+        if !self.type_ahead.push(text, Instant::now()) {
+            return;
+        }
+
+        let labels = self
+            .entries
+            .iter()
+            .map(|entry| entry.item.label.clone())
+            .collect::<Vec<_>>();
+        if let Some(ix) = self.type_ahead.find(&labels, self.selected_ix) {
+            self.selected_ix = Some(ix);
+            self.scroll_handle
+                .scroll_to_item(ix, gpui::ScrollStrategy::Top);
+            cx.stop_propagation();
+            cx.notify();
+        }
+    }
+
     fn on_entry_click(&mut self, ix: usize, _: &mut Window, cx: &mut Context<Self>) {
         self.selected_ix = Some(ix);
         self.toggle_expand(ix, cx);
@@ -466,6 +957,9 @@ impl Render for TreeState {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let render_item = self.render_item.clone();
         let state = cx.entity().clone();
+        let entity_id = cx.entity_id();
+        let draggable = self.draggable;
+        let checkable = self.checkable;
 
         div()
             .id("tree-state")
@@ -499,6 +993,17 @@ impl Render for TreeState {
                     }
                 }
             })
+            .when(draggable, |this| {
+                this.auto_scroll_on_drag::<DragTreeNode>(&self.scroll_handle)
+                    .on_drag_move(cx.listener(|this, e: &DragTreeNodeMoveEvent, _, cx| {
+                        if !e.bounds.contains(&e.event.position)
+                            && this.drop_target.take().is_some()
+                        {
+                            cx.notify();
+                        }
+                    }))
+                    .on_drop(cx.listener(Self::on_entry_drop))
+            })
             .child(
                 uniform_list("entries", self.entries.len(), {
                     cx.processor(move |state, visible_range: Range<usize>, window, cx| {
@@ -507,15 +1012,49 @@ impl Render for TreeState {
                             let entry = &state.entries[ix];
                             let selected = Some(ix) == state.selected_ix;
                             let right_clicked = Some(ix) == state.right_clicked_ix;
-                            let item = (render_item)(ix, entry, selected, window, cx);
+                            let drop_target_position = state
+                                .drop_target
+                                .filter(|(drop_ix, _)| *drop_ix == ix && cx.has_active_drag())
+                                .map(|(_, position)| position);
+                            let item = (render_item)(ix, entry, selected, window, cx)
+                                .when(entry.is_loading(), |this| {
+                                    this.suffix(|_, _| Spinner::new().xsmall())
+                                });
 
                             let el = div()
                                 .id(ix)
-                                .child(
-                                    item.disabled(entry.item().is_disabled())
+                                .relative()
+                                .when(draggable && !entry.item().is_disabled(), |this| {
+                                    this.draggable(DragItem::new(
+                                        DragTreeNode {
+                                            entity_id,
+                                            id: entry.item().id.clone(),
+                                        },
+                                        entry.item().label.clone(),
+                                    ))
+                                    .on_drag_move(
+                                        cx.listener(
+                                            move |this, e: &DragTreeNodeMoveEvent, _, cx| {
+                                                this.on_entry_drag_move(e, ix, cx)
+                                            },
+                                        ),
+                                    )
+                                })
+                                .map(|this| {
+                                    let item = item
+                                        .disabled(entry.item().is_disabled())
                                         .selected(selected)
-                                        .secondary_selected(right_clicked),
-                                )
+                                        .secondary_selected(right_clicked);
+
+                                    if checkable {
+                                        this.flex()
+                                            .items_center()
+                                            .child(state.render_entry_checkbox(entry, cx))
+                                            .child(div().flex_1().min_w_0().child(item))
+                                    } else {
+                                        this.child(item)
+                                    }
+                                })
                                 .when(!entry.item().is_disabled(), |this| {
                                     this.on_mouse_down(
                                         MouseButton::Left,
@@ -532,6 +1071,9 @@ impl Render for TreeState {
                                             cx.notify();
                                         }),
                                     )
+                                })
+                                .when_some(drop_target_position, |this, position| {
+                                    this.child(render_drop_indicator(position, cx))
                                 });
 
                             items.push(el)
@@ -618,6 +1160,7 @@ impl RenderOnce for Tree {
             .on_action(window.listener_for(&self.state, TreeState::on_action_right))
             .on_action(window.listener_for(&self.state, TreeState::on_action_up))
             .on_action(window.listener_for(&self.state, TreeState::on_action_down))
+            .on_key_down(window.listener_for(&self.state, TreeState::on_key_down))
             .size_full()
             .child(self.state)
             .refine_style(&self.style)
@@ -625,6 +1168,93 @@ impl RenderOnce for Tree {
     }
 }
 
+fn render_drop_indicator(position: TreeDropPosition, cx: &App) -> impl IntoElement {
+    let indicator = div().absolute().left_0().right_0();
+    match position {
+        TreeDropPosition::Inside => indicator
+            .top_0()
+            .bottom_0()
+            .border_1()
+            .border_color(cx.theme().drag_border)
+            .bg(cx.theme().tokens.drop_target),
+        TreeDropPosition::Before => indicator.top_0().h(px(2.)).bg(cx.theme().drag_border),
+        TreeDropPosition::After => indicator.bottom_0().h(px(2.)).bg(cx.theme().drag_border),
+    }
+}
+
+/// Returns the drop position by the mouse `y` in the `bounds` of the target entry.
+///
+/// The folders accept [`TreeDropPosition::Inside`] in the middle half.
+fn drop_position(y: Pixels, bounds: Bounds<Pixels>, is_folder: bool) -> TreeDropPosition {
+    let ratio = ((y - bounds.top()) / bounds.size.height).clamp(0., 1.);
+    if is_folder {
+        if ratio < 0.25 {
+            TreeDropPosition::Before
+        } else if ratio > 0.75 {
+            TreeDropPosition::After
+        } else {
+            TreeDropPosition::Inside
+        }
+    } else if ratio < 0.5 {
+        TreeDropPosition::Before
+    } else {
+        TreeDropPosition::After
+    }
+}
+
+/// Remove the item with the `id` from the `items` or their descendants.
+fn take_item(items: &mut Vec<TreeItem>, id: &SharedString) -> Option<TreeItem> {
+    if let Some(ix) = items.iter().position(|item| item.id == *id) {
+        return Some(items.remove(ix));
+    }
+
+    for parent in items.iter_mut() {
+        if let Some(item) = take_item(&mut parent.children, id) {
+            return Some(item);
+        }
+        if let Some(loaded_children) = parent.state.borrow_mut().loaded_children.as_mut() {
+            if let Some(item) = take_item(loaded_children, id) {
+                return Some(item);
+            }
+        }
+    }
+
+    None
+}
+
+/// Insert the `item` to the `position` of the `target` in the `items` or their descendants.
+///
+/// Returns the `item` back if the `target` is not found.
+fn insert_item(
+    items: &mut Vec<TreeItem>,
+    item: TreeItem,
+    target: &SharedString,
+    position: TreeDropPosition,
+) -> Option<TreeItem> {
+    if let Some(ix) = items.iter().position(|item| item.id == *target) {
+        match position {
+            TreeDropPosition::Before => items.insert(ix, item),
+            TreeDropPosition::After => items.insert(ix + 1, item),
+            TreeDropPosition::Inside => {
+                let target = &mut items[ix];
+                target.state.borrow_mut().expanded = true;
+                target.children.push(item);
+            }
+        }
+        return None;
+    }
+
+    let mut item = item;
+    for parent in items.iter_mut() {
+        item = insert_item(&mut parent.children, item, target, position)?;
+        if let Some(loaded_children) = parent.state.borrow_mut().loaded_children.as_mut() {
+            item = insert_item(loaded_children, item, target, position)?;
+        }
+    }
+
+    Some(item)
+}
+
 #[cfg(test)]
 mod tests {
     use std::cell::RefCell;
@@ -632,8 +1262,8 @@ mod tests {
 
     use indoc::indoc;
 
-    use super::{TreeEvent, TreeState};
-    use gpui::{AppContext as _, Render, Subscription};
+    use super::{TreeDropPosition, TreeEvent, TreeState, drop_position};
+    use gpui::{AppContext as _, Bounds, Render, Subscription, point, px, size};
 
     struct TestCollector {
         _state: gpui::Entity<TreeState>,
@@ -751,6 +1381,272 @@ mod tests {
         })
     }
 
+    #[gpui::test]
+    fn test_load_children(cx: &mut gpui::TestAppContext) {
+        use super::TreeItem;
+        use gpui::Task;
+
+        let items = vec![
+            TreeItem::new("src", "src").has_children(true),
+            TreeItem::new("README.md", "README.md"),
+        ];
+        let state = cx.new(|cx| {
+            TreeState::new(cx).items(items).load_children(|item, _| {
+                Task::ready(vec![
+                    TreeItem::new(format!("{}/lib.rs", item.id), "lib.rs"),
+                    TreeItem::new(format!("{}/ui", item.id), "ui").has_children(true),
+                ])
+            })
+        });
+
+        state.update(cx, |state, cx| {
+            let entry = state.entries.get(0).unwrap();
+            assert!(entry.is_folder());
+            assert!(!entry.is_loading());
+
+            state.toggle_expand(0, cx);
+            assert!(state.entries[0].is_expanded());
+        });
+        cx.run_until_parked();
+
+        state.update(cx, |state, cx| {
+            assert!(!state.entries[0].is_loading());
+            assert_entries(
+                &state.entries,
+                indoc! {
+                    r#"
+                src
+                    lib.rs
+                    ui
+                README.md
+                "#
+                },
+            );
+
+            // The loaded children are kept after collapse.
+            state.toggle_expand(0, cx);
+            state.toggle_expand(0, cx);
+            assert_entries(
+                &state.entries,
+                indoc! {
+                    r#"
+                src
+                    lib.rs
+                    ui
+                README.md
+                "#
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn test_drop_position() {
+        let bounds = Bounds::new(point(px(0.), px(100.)), size(px(100.), px(20.)));
+
+        assert_eq!(
+            drop_position(px(102.), bounds, true),
+            TreeDropPosition::Before
+        );
+        assert_eq!(
+            drop_position(px(110.), bounds, true),
+            TreeDropPosition::Inside
+        );
+        assert_eq!(
+            drop_position(px(118.), bounds, true),
+            TreeDropPosition::After
+        );
+        assert_eq!(
+            drop_position(px(108.), bounds, false),
+            TreeDropPosition::Before
+        );
+        assert_eq!(
+            drop_position(px(112.), bounds, false),
+            TreeDropPosition::After
+        );
+    }
+
+    #[gpui::test]
+    fn test_move_node(cx: &mut gpui::TestAppContext) {
+        use super::TreeItem;
+
+        let items = vec![
+            TreeItem::new("src", "src")
+                .expanded(true)
+                .child(TreeItem::new("src/lib.rs", "lib.rs"))
+                .child(
+                    TreeItem::new("src/ui", "ui").child(TreeItem::new("src/ui/mod.rs", "mod.rs")),
+                ),
+            TreeItem::new("Cargo.toml", "Cargo.toml"),
+            TreeItem::new("README.md", "README.md"),
+        ];
+        let state = cx.new(|cx| {
+            TreeState::new(cx)
+                .items(items)
+                .can_drop(|item, _, _, _| item.id != "README.md")
+        });
+        let collector = cx.new(|cx| TestCollector::new(&state, cx));
+
+        state.update(cx, |state, cx| {
+            state.set_selected_index(Some(3), cx);
+            state.move_node(
+                &"Cargo.toml".into(),
+                &"src/lib.rs".into(),
+                TreeDropPosition::Before,
+                cx,
+            );
+            assert_entries(
+                &state.entries,
+                indoc! {
+                    r#"
+                src
+                    Cargo.toml
+                    lib.rs
+                    ui
+                README.md
+                "#
+                },
+            );
+            // The selection follows the item.
+            assert_eq!(state.selected_item().unwrap().id, "Cargo.toml");
+
+            state.move_node(
+                &"src/lib.rs".into(),
+                &"src/ui".into(),
+                TreeDropPosition::Inside,
+                cx,
+            );
+            state.move_node(
+                &"src/ui".into(),
+                &"README.md".into(),
+                TreeDropPosition::After,
+                cx,
+            );
+            assert_entries(
+                &state.entries,
+                indoc! {
+                    r#"
+                src
+                    Cargo.toml
+                README.md
+                ui
+                    mod.rs
+                    lib.rs
+                "#
+                },
+            );
+
+            // Rejected moves
+            state.move_node(
+                &"src".into(),
+                &"Cargo.toml".into(),
+                TreeDropPosition::After,
+                cx,
+            );
+            state.move_node(
+                &"src/ui".into(),
+                &"Cargo.toml".into(),
+                TreeDropPosition::Inside,
+                cx,
+            );
+            state.move_node(
+                &"README.md".into(),
+                &"src".into(),
+                TreeDropPosition::Inside,
+                cx,
+            );
+            state.move_node(
+                &"src/ui".into(),
+                &"src/ui/mod.rs".into(),
+                TreeDropPosition::Before,
+                cx,
+            );
+            assert_eq!(state.entries.len(), 6);
+        });
+
+        let events = collector.read_with(cx, |c, _| c.events.borrow().clone());
+        assert_eq!(
+            events,
+            vec![
+                TreeEvent::NodeMoved {
+                    id: "Cargo.toml".into(),
+                    target: "src/lib.rs".into(),
+                    position: TreeDropPosition::Before,
+                },
+                TreeEvent::NodeMoved {
+                    id: "src/lib.rs".into(),
+                    target: "src/ui".into(),
+                    position: TreeDropPosition::Inside,
+                },
+                TreeEvent::NodeMoved {
+                    id: "src/ui".into(),
+                    target: "README.md".into(),
+                    position: TreeDropPosition::After,
+                },
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_checked(cx: &mut gpui::TestAppContext) {
+        use super::TreeItem;
+
+        let items = vec![
+            TreeItem::new("src", "src")
+                .child(TreeItem::new("src/lib.rs", "lib.rs"))
+                .child(
+                    TreeItem::new("src/ui", "ui")
+                        .child(TreeItem::new("src/ui/button.rs", "button.rs"))
+                        .child(TreeItem::new("src/ui/mod.rs", "mod.rs")),
+                ),
+            TreeItem::new("README.md", "README.md"),
+        ];
+        let state = cx.new(|cx| TreeState::new(cx).items(items).checkable(true));
+        let collector = cx.new(|cx| TestCollector::new(&state, cx));
+
+        state.update(cx, |state, cx| {
+            // Checking a parent checks all descendants.
+            state.set_checked(&"src/ui".into(), true, cx);
+            assert_eq!(
+                state.checked_paths(),
+                vec!["src/ui", "src/ui/button.rs", "src/ui/mod.rs"]
+            );
+            assert!(state.is_indeterminate(&"src".into()));
+            assert!(!state.is_indeterminate(&"src/ui".into()));
+
+            // All children checked makes the parent checked.
+            state.set_checked(&"src/lib.rs".into(), true, cx);
+            assert!(state.is_checked(&"src".into()));
+            assert!(!state.is_indeterminate(&"src".into()));
+
+            // Unchecking a child makes the ancestors indeterminate.
+            state.set_checked(&"src/ui/mod.rs".into(), false, cx);
+            assert_eq!(
+                state.checked_paths(),
+                vec!["src/lib.rs", "src/ui/button.rs"]
+            );
+            assert!(state.is_indeterminate(&"src".into()));
+            assert!(state.is_indeterminate(&"src/ui".into()));
+
+            state.set_checked(&"src".into(), false, cx);
+            assert!(state.checked_paths().is_empty());
+            assert!(!state.is_indeterminate(&"src".into()));
+        });
+
+        let events = collector.read_with(cx, |c, _| c.events.borrow().clone());
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[1],
+            TreeEvent::CheckedChanged(vec![
+                "src".into(),
+                "src/lib.rs".into(),
+                "src/ui".into(),
+                "src/ui/button.rs".into(),
+                "src/ui/mod.rs".into(),
+            ])
+        );
+    }
+
     #[gpui::test]
     fn test_emits_expanded_event(cx: &mut gpui::TestAppContext) {
         let items = vec![
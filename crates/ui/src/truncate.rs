@@ -0,0 +1,361 @@
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use gpui::{
+    App, AvailableSpace, Bounds, Element, ElementId, GlobalElementId, InspectorElementId,
+    IntoElement, LayoutId, LineFragment, ParentElement as _, Pixels, RenderOnce, ShapedLine,
+    SharedString, Style, StyleRefinement, Styled, TextAlign, TextStyle, Window, div,
+    prelude::FluentBuilder as _, px, size,
+};
+use rust_i18n::t;
+
+use crate::{
+    ElementExt as _, Sizable as _, StyledExt as _,
+    button::{Button, ButtonVariants as _},
+    v_flex,
+};
+
+const ELLIPSIS: &str = "…";
+
+/// A single line text element that truncates the overflowing text in the middle.
+///
+/// Unlike the `text_ellipsis`, both the start and the end of the text are kept, which
+/// is useful for the file paths, e.g.: `/Users/jason/…/project/src/main.rs`.
+///
+/// The text style is inherited from the parent element.
+///
+/// ```ignore
+/// div().w_48().child(MiddleEllipsis::new("/Users/jason/workspace/project/src/main.rs"))
+/// ```
+pub struct MiddleEllipsis {
+    text: SharedString,
+}
+
+impl MiddleEllipsis {
+    /// Create a new middle ellipsis text.
+    pub fn new(text: impl Into<SharedString>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+impl IntoElement for MiddleEllipsis {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for MiddleEllipsis {
+    type RequestLayoutState = Rc<RefCell<Option<ShapedLine>>>;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        _cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let text = self.text.clone();
+        let text_style = window.text_style();
+        let font_size = text_style.font_size.to_pixels(window.rem_size());
+        let line_height = window.line_height();
+        let shaped = Rc::new(RefCell::new(None));
+
+        // Allow to be shrunk by the flex parent, the text will be truncated to fit.
+        let mut style = Style::default();
+        style.min_size.width = px(0.).into();
+
+        let layout_id = window.request_measured_layout(style, {
+            let shaped = shaped.clone();
+            move |known_dimensions, available_space, window, _| {
+                let max_width = known_dimensions.width.or(match available_space.width {
+                    AvailableSpace::Definite(width) => Some(width),
+                    _ => None,
+                });
+                let line = shape_middle_ellipsis(&text, max_width, &text_style, font_size, window);
+                let width = known_dimensions.width.unwrap_or(line.width);
+                *shaped.borrow_mut() = Some(line);
+                size(width, line_height)
+            }
+        });
+
+        (layout_id, shaped)
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Self::PrepaintState {
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        shaped: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if let Some(line) = shaped.borrow().as_ref() {
+            _ = line.paint(
+                bounds.origin,
+                bounds.size.height,
+                TextAlign::Left,
+                None,
+                window,
+                cx,
+            );
+        }
+    }
+}
+
+/// Shape the `text` into a line, truncated in the middle if it is wider than the `max_width`.
+fn shape_middle_ellipsis(
+    text: &SharedString,
+    max_width: Option<Pixels>,
+    text_style: &TextStyle,
+    font_size: Pixels,
+    window: &mut Window,
+) -> ShapedLine {
+    let text_system = window.text_system();
+    let line = text_system.shape_line(
+        text.clone(),
+        font_size,
+        &[text_style.to_run(text.len())],
+        None,
+    );
+    let Some(max_width) = max_width.filter(|max_width| line.width > *max_width) else {
+        return line;
+    };
+
+    let ellipsis_width = text_system
+        .shape_line(
+            ELLIPSIS.into(),
+            font_size,
+            &[text_style.to_run(ELLIPSIS.len())],
+            None,
+        )
+        .width;
+    let range = middle_ellipsis_range(text, line.width, max_width - ellipsis_width, |ix| {
+        line.x_for_index(ix)
+    });
+
+    let truncated: SharedString =
+        format!("{}{}{}", &text[..range.start], ELLIPSIS, &text[range.end..]).into();
+    text_system.shape_line(
+        truncated.clone(),
+        font_size,
+        &[text_style.to_run(truncated.len())],
+        None,
+    )
+}
+
+/// Returns the byte range of the `text` to replace with the ellipsis, to fit the rest
+/// of the text into the `available` width.
+///
+/// The `width` is the width of the whole text, and the `x_for_index` returns the x
+/// position of a byte offset in the text. The head and the tail take a half of the
+/// `available` width each, and the tail takes the space left by the head.
+fn middle_ellipsis_range(
+    text: &str,
+    width: Pixels,
+    available: Pixels,
+    x_for_index: impl Fn(usize) -> Pixels,
+) -> Range<usize> {
+    let half = available / 2.;
+
+    let mut start = 0;
+    for (ix, ch) in text.char_indices() {
+        let end = ix + ch.len_utf8();
+        if x_for_index(end) > half {
+            break;
+        }
+        start = end;
+    }
+
+    let remaining = available - x_for_index(start);
+    let mut end = text.len();
+    for (ix, _) in text[start..].char_indices().rev() {
+        let ix = start + ix;
+        if width - x_for_index(ix) > remaining {
+            break;
+        }
+        end = ix;
+    }
+
+    start..end
+}
+
+#[derive(Default, Clone, Copy)]
+struct LineClampState {
+    expanded: bool,
+    /// Whether the text has more lines than the limit, measured in the last frame.
+    overflow: bool,
+}
+
+/// A multi-line text clamped to the max number of lines, with an optional
+/// "Show more" button to expand it.
+///
+/// The "Show more" button is only displayed when the text is actually longer than
+/// the limit, measured with the wrap width and the text style of the element.
+///
+/// ```ignore
+/// LineClamp::new("description", description).lines(3).expandable(true)
+/// ```
+#[derive(IntoElement)]
+pub struct LineClamp {
+    id: ElementId,
+    style: StyleRefinement,
+    text: SharedString,
+    lines: usize,
+    expandable: bool,
+}
+
+impl LineClamp {
+    /// Create a new line clamp text, default clamped to 2 lines.
+    pub fn new(id: impl Into<ElementId>, text: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            text: text.into(),
+            lines: 2,
+            expandable: false,
+        }
+    }
+
+    /// Set the max number of lines to display, default is 2.
+    pub fn lines(mut self, lines: usize) -> Self {
+        self.lines = lines.max(1);
+        self
+    }
+
+    /// Set whether to show the "Show more" button to expand the text, default is false.
+    pub fn expandable(mut self, expandable: bool) -> Self {
+        self.expandable = expandable;
+        self
+    }
+}
+
+impl Styled for LineClamp {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for LineClamp {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| LineClampState::default());
+        let LineClampState { expanded, overflow } = *state.read(cx);
+        let lines = self.lines;
+        let text = self.text.clone();
+
+        v_flex()
+            .items_start()
+            .refine_style(&self.style)
+            .child(
+                div()
+                    .relative()
+                    .w_full()
+                    .when(!expanded, |this| this.line_clamp(lines).text_ellipsis())
+                    .child(self.text)
+                    .on_prepaint({
+                        let state = state.clone();
+                        move |bounds, window, cx| {
+                            let overflow =
+                                wrapped_lines_count(&text, bounds.size.width, window, cx) > lines;
+                            state.update(cx, |state, cx| {
+                                if state.overflow != overflow {
+                                    state.overflow = overflow;
+                                    cx.notify();
+                                }
+                            });
+                        }
+                    }),
+            )
+            .when(self.expandable && (overflow || expanded), |this| {
+                let label = if expanded {
+                    t!("LineClamp.show_less")
+                } else {
+                    t!("LineClamp.show_more")
+                };
+
+                this.child(
+                    Button::new(self.id)
+                        .link()
+                        .xsmall()
+                        .label(label.to_string())
+                        .on_click(move |_, _, cx| {
+                            cx.stop_propagation();
+                            state.update(cx, |state, cx| {
+                                state.expanded = !state.expanded;
+                                cx.notify();
+                            });
+                        }),
+                )
+            })
+    }
+}
+
+/// Returns the number of the lines of the `text` after wrapping with the `wrap_width`.
+fn wrapped_lines_count(text: &str, wrap_width: Pixels, window: &mut Window, cx: &mut App) -> usize {
+    let text_style = window.text_style();
+    let font_size = text_style.font_size.to_pixels(window.rem_size());
+    let mut line_wrapper = cx.text_system().line_wrapper(text_style.font(), font_size);
+
+    text.split('\n')
+        .map(|line| {
+            line_wrapper
+                .wrap_line(&[LineFragment::text(line)], wrap_width)
+                .count()
+                + 1
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::middle_ellipsis_range;
+
+    #[test]
+    fn test_middle_ellipsis_range() {
+        // Each char is 10px wide.
+        let x_for_index = |text: &str, ix: usize| px(text[..ix].chars().count() as f32 * 10.);
+
+        let text = "abcdefghij";
+        let range = middle_ellipsis_range(text, px(100.), px(55.), |ix| x_for_index(text, ix));
+        assert_eq!(range, 2..7);
+        assert_eq!(format!("{}…{}", &text[..2], &text[7..]), "ab…hij");
+
+        // Fit exactly
+        let range = middle_ellipsis_range(text, px(100.), px(100.), |ix| x_for_index(text, ix));
+        assert_eq!(range, 5..5);
+
+        // No space left for the text
+        let range = middle_ellipsis_range(text, px(100.), px(-5.), |ix| x_for_index(text, ix));
+        assert_eq!(range, 0..text.len());
+
+        // Multi-byte chars
+        let text = "你好世界你好世界";
+        let range = middle_ellipsis_range(text, px(80.), px(40.), |ix| x_for_index(text, ix));
+        assert_eq!(&text[..range.start], "你好");
+        assert_eq!(&text[range.end..], "世界");
+    }
+}
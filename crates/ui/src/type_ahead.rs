@@ -0,0 +1,118 @@
+use gpui::KeyDownEvent;
+use instant::{Duration, Instant};
+
+/// The timeout to reset the typed prefix after the last key.
+const RESET_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Type-ahead search for the Tree and List, to jump to the next item
+/// whose label starts with the typed prefix.
+#[derive(Default)]
+pub(crate) struct TypeAhead {
+    query: String,
+    last_typed_at: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Returns the typed character of the key down event,
+    /// `None` if it's not a plain character key, e.g. with `ctrl` or an arrow key.
+    pub(crate) fn key_char(event: &KeyDownEvent) -> Option<&str> {
+        let modifiers = &event.keystroke.modifiers;
+        if modifiers.control || modifiers.platform || modifiers.alt || modifiers.function {
+            return None;
+        }
+
+        event
+            .keystroke
+            .key_char
+            .as_deref()
+            .filter(|c| !c.is_empty() && !c.chars().any(|c| c.is_control()))
+    }
+
+    /// Append the `text` to the query, the query is reset if typed after the timeout.
+    ///
+    /// Returns false if the `text` is ignored, e.g. a leading space.
+    pub(crate) fn push(&mut self, text: &str, now: Instant) -> bool {
+        if self
+            .last_typed_at
+            .is_some_and(|at| now.duration_since(at) > RESET_TIMEOUT)
+        {
+            self.query.clear();
+        }
+
+        // The space is used for other actions when not typing.
+        if self.query.is_empty() && text.trim().is_empty() {
+            return false;
+        }
+
+        self.query.push_str(&text.to_lowercase());
+        self.last_typed_at = Some(now);
+        true
+    }
+
+    /// Find the item to select by the typed query in the `labels`.
+    ///
+    /// The search starts from the `current` item to keep it while typing more characters,
+    /// or the next item if the query is the same character repeated to cycle through the items.
+    pub(crate) fn find<S: AsRef<str>>(
+        &self,
+        labels: &[S],
+        current: Option<usize>,
+    ) -> Option<usize> {
+        if self.query.is_empty() || labels.is_empty() {
+            return None;
+        }
+
+        let mut chars = self.query.chars();
+        let first = chars.next()?;
+        let (query, start) = if chars.all(|c| c == first) {
+            // Repeated the same character, e.g. `aaa` cycles through the items start with `a`.
+            let query = first.to_string();
+            (query, current.map_or(0, |ix| ix + 1))
+        } else {
+            (self.query.clone(), current.unwrap_or(0))
+        };
+
+        let len = labels.len();
+        (0..len)
+            .map(|offset| (start + offset) % len)
+            .find(|ix| labels[*ix].as_ref().to_lowercase().starts_with(&query))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instant::{Duration, Instant};
+
+    use super::TypeAhead;
+
+    #[test]
+    fn test_type_ahead() {
+        let labels = ["Apple", "Banana", "blueberry", "Cherry", "avocado"];
+        let now = Instant::now();
+
+        let mut type_ahead = TypeAhead::default();
+        assert_eq!(type_ahead.find(&labels, None), None);
+
+        assert!(type_ahead.push("b", now));
+        assert_eq!(type_ahead.find(&labels, None), Some(1));
+        // Typing more characters keeps the current item if matched.
+        assert!(type_ahead.push("l", now));
+        assert_eq!(type_ahead.find(&labels, Some(1)), Some(2));
+        assert!(type_ahead.push("u", now));
+        assert_eq!(type_ahead.find(&labels, Some(2)), Some(2));
+
+        // Reset after the timeout.
+        let now = now + Duration::from_millis(1500);
+        assert!(type_ahead.push("a", now));
+        assert_eq!(type_ahead.find(&labels, Some(2)), Some(4));
+        // Repeated the same character cycles through the items.
+        assert!(type_ahead.push("a", now));
+        assert_eq!(type_ahead.find(&labels, Some(4)), Some(0));
+
+        // The leading space is ignored.
+        let now = now + Duration::from_millis(1500);
+        assert!(!type_ahead.push(" ", now));
+        assert!(type_ahead.push("z", now));
+        assert_eq!(type_ahead.find(&labels, Some(0)), None);
+    }
+}
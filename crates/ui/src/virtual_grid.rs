@@ -0,0 +1,396 @@
+//! Virtual Grid for render a large number of equally sized cells in rows and columns.
+//!
+//! Only the visible cells are rendered, this is useful for photo grids, emoji pickers or spreadsheets.
+use std::ops::Range;
+
+use gpui::{
+    AnyElement, App, AvailableSpace, Bounds, ContentMask, Context, Div, Element, ElementId, Entity,
+    GlobalElementId, Hitbox, InteractiveElement, IntoElement, Pixels, Render, ScrollStrategy, Size,
+    Stateful, StatefulInteractiveElement, StyleRefinement, Styled, Window, div, point, px, size,
+};
+use smallvec::SmallVec;
+
+use crate::VirtualListScrollHandle;
+
+/// Create a [`VirtualGrid`] with `rows_count` x `columns_count` cells of the `cell_size`.
+///
+/// The `f` is called with the visible rows and columns, and must return the cells
+/// in row-major order, that is `rows.len() * columns.len()` elements.
+///
+/// Use `gap` to set the space between the cells.
+///
+/// See also [`v_virtual_list`](crate::v_virtual_list)
+pub fn virtual_grid<R, V>(
+    view: Entity<V>,
+    id: impl Into<ElementId>,
+    rows_count: usize,
+    columns_count: usize,
+    cell_size: Size<Pixels>,
+    f: impl 'static + Fn(&mut V, Range<usize>, Range<usize>, &mut Window, &mut Context<V>) -> Vec<R>,
+) -> VirtualGrid
+where
+    R: IntoElement,
+    V: Render,
+{
+    let id: ElementId = id.into();
+    let scroll_handle = VirtualListScrollHandle::new();
+    let render_cells = move |rows, columns, window: &mut Window, cx: &mut App| {
+        view.update(cx, |this, cx| {
+            f(this, rows, columns, window, cx)
+                .into_iter()
+                .map(|component| component.into_any_element())
+                .collect()
+        })
+    };
+
+    VirtualGrid {
+        id: id.clone(),
+        base: div()
+            .id(id)
+            .size_full()
+            .overflow_scroll()
+            .track_scroll(&scroll_handle),
+        scroll_handle,
+        rows_count,
+        columns_count,
+        cell_size,
+        render_cells: Box::new(render_cells),
+    }
+}
+
+/// VirtualGrid component for rendering a large number of equally sized cells.
+pub struct VirtualGrid {
+    id: ElementId,
+    base: Stateful<Div>,
+    scroll_handle: VirtualListScrollHandle,
+    rows_count: usize,
+    columns_count: usize,
+    cell_size: Size<Pixels>,
+    render_cells: Box<
+        dyn for<'a> Fn(
+            Range<usize>,
+            Range<usize>,
+            &'a mut Window,
+            &'a mut App,
+        ) -> SmallVec<[AnyElement; 64]>,
+    >,
+}
+
+impl Styled for VirtualGrid {
+    fn style(&mut self) -> &mut StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl VirtualGrid {
+    /// Set the scroll handle, use [`VirtualListScrollHandle::scroll_to_cell`] to scroll to a cell.
+    pub fn track_scroll(mut self, scroll_handle: &VirtualListScrollHandle) -> Self {
+        self.base = self.base.track_scroll(&scroll_handle);
+        self.scroll_handle = scroll_handle.clone();
+        self
+    }
+}
+
+/// Frame state used by the [`VirtualGrid`].
+pub struct VirtualGridFrameState {
+    /// Visible cells to be painted.
+    cells: SmallVec<[AnyElement; 64]>,
+}
+
+impl IntoElement for VirtualGrid {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for VirtualGrid {
+    type RequestLayoutState = VirtualGridFrameState;
+    type PrepaintState = Option<Hitbox>;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&gpui::InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (gpui::LayoutId, Self::RequestLayoutState) {
+        let layout_id = self.base.interactivity().request_layout(
+            global_id,
+            inspector_id,
+            window,
+            cx,
+            |style, window, cx| {
+                window.with_text_style(style.text_style().cloned(), |window| {
+                    window.request_layout(style, None, cx)
+                })
+            },
+        );
+
+        (
+            layout_id,
+            VirtualGridFrameState {
+                cells: SmallVec::new(),
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let style = self
+            .base
+            .interactivity()
+            .compute_style(global_id, None, window, cx);
+        let rem_size = window.rem_size();
+        let font_size = window.text_style().font_size.to_pixels(rem_size);
+        let border_widths = style.border_widths.to_pixels(rem_size);
+        let paddings = style.padding.to_pixels(bounds.size.into(), rem_size);
+        let gap = size(
+            style.gap.width.to_pixels(font_size.into(), rem_size),
+            style.gap.height.to_pixels(font_size.into(), rem_size),
+        );
+
+        let content_bounds = Bounds::from_corners(
+            bounds.origin
+                + point(
+                    border_widths.left + paddings.left,
+                    border_widths.top + paddings.top,
+                ),
+            bounds.bottom_right()
+                - point(
+                    border_widths.right + paddings.right,
+                    border_widths.bottom + paddings.bottom,
+                ),
+        );
+
+        // The distance between the start of two adjacent cells.
+        let stride = size(
+            self.cell_size.width + gap.width,
+            self.cell_size.height + gap.height,
+        );
+        let content_size = size(
+            content_length(self.columns_count, self.cell_size.width, gap.width),
+            content_length(self.rows_count, self.cell_size.height, gap.height),
+        );
+
+        let mut scroll_offset = self.scroll_handle.offset();
+        if let Some((row, column, strategy)) = self
+            .scroll_handle
+            .take_deferred_scroll_to_cell(self.rows_count)
+        {
+            scroll_offset.y = scroll_offset_for_cell(
+                scroll_offset.y,
+                stride.height * row as f32,
+                self.cell_size.height,
+                content_bounds.size.height,
+                strategy,
+            );
+            if let Some(column) = column {
+                scroll_offset.x = scroll_offset_for_cell(
+                    scroll_offset.x,
+                    stride.width * column as f32,
+                    self.cell_size.width,
+                    content_bounds.size.width,
+                    strategy,
+                );
+            }
+        }
+
+        scroll_offset = scroll_offset
+            .max(&point(
+                content_bounds.size.width - content_size.width,
+                content_bounds.size.height - content_size.height,
+            ))
+            .min(&point(px(0.), px(0.)));
+        if scroll_offset != self.scroll_handle.offset() {
+            self.scroll_handle.set_offset(scroll_offset);
+        }
+
+        self.base.interactivity().prepaint(
+            global_id,
+            inspector_id,
+            bounds,
+            content_size,
+            window,
+            cx,
+            |_style, _, hitbox, window, cx| {
+                if self.rows_count == 0 || self.columns_count == 0 {
+                    return hitbox;
+                }
+
+                let rows = visible_cells(
+                    self.rows_count,
+                    stride.height,
+                    scroll_offset.y,
+                    paddings.top,
+                    content_bounds.size.height,
+                );
+                let columns = visible_cells(
+                    self.columns_count,
+                    stride.width,
+                    scroll_offset.x,
+                    paddings.left,
+                    content_bounds.size.width,
+                );
+
+                let cells = (self.render_cells)(rows.clone(), columns.clone(), window, cx);
+                let cell_indices = rows
+                    .flat_map(|row| columns.clone().map(move |column| (row, column)))
+                    .collect::<Vec<_>>();
+
+                let available_space = size(
+                    AvailableSpace::Definite(self.cell_size.width),
+                    AvailableSpace::Definite(self.cell_size.height),
+                );
+                let content_mask = ContentMask { bounds };
+                window.with_content_mask(Some(content_mask), |window| {
+                    for (mut cell, (row, column)) in cells.into_iter().zip(cell_indices) {
+                        let cell_origin = content_bounds.origin
+                            + point(
+                                stride.width * column as f32 + scroll_offset.x,
+                                stride.height * row as f32 + scroll_offset.y,
+                            );
+
+                        cell.layout_as_root(available_space, window, cx);
+                        cell.prepaint_at(cell_origin, window, cx);
+                        layout.cells.push(cell);
+                    }
+                });
+
+                hitbox
+            },
+        )
+    }
+
+    fn paint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        hitbox: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.base.interactivity().paint(
+            global_id,
+            inspector_id,
+            bounds,
+            hitbox.as_ref(),
+            window,
+            cx,
+            |_, window, cx| {
+                for cell in &mut layout.cells {
+                    cell.paint(window, cx);
+                }
+            },
+        )
+    }
+}
+
+/// Returns the total length of `count` cells with the `gap` between them.
+fn content_length(count: usize, cell_size: Pixels, gap: Pixels) -> Pixels {
+    if count == 0 {
+        return px(0.);
+    }
+
+    cell_size * count as f32 + gap * (count - 1) as f32
+}
+
+/// Returns the range of the cells to render on one axis,
+/// the last visible cell is followed by one more cell.
+fn visible_cells(
+    count: usize,
+    stride: Pixels,
+    scroll_offset: Pixels,
+    padding_start: Pixels,
+    viewport_size: Pixels,
+) -> Range<usize> {
+    if count == 0 || stride <= px(0.) {
+        return 0..count;
+    }
+
+    let stride = stride.as_f32();
+    let start = (-(scroll_offset + padding_start)).as_f32() / stride;
+    let end = (-scroll_offset + viewport_size).as_f32() / stride;
+
+    let first = (start.floor().max(0.) as usize).min(count);
+    let last = (end.ceil().max(0.) as usize + 1).min(count);
+    first..last.max(first)
+}
+
+/// Returns the scroll offset on one axis to show the cell at `cell_start` with the `strategy`.
+fn scroll_offset_for_cell(
+    scroll_offset: Pixels,
+    cell_start: Pixels,
+    cell_size: Pixels,
+    viewport_size: Pixels,
+    strategy: ScrollStrategy,
+) -> Pixels {
+    match strategy {
+        ScrollStrategy::Center => viewport_size / 2. - cell_start - cell_size / 2.,
+        _ => {
+            if cell_start + scroll_offset < px(0.) {
+                -cell_start
+            } else if cell_start + cell_size + scroll_offset > viewport_size {
+                viewport_size - cell_start - cell_size
+            } else {
+                scroll_offset
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{ScrollStrategy, px};
+
+    use super::{content_length, scroll_offset_for_cell, visible_cells};
+
+    #[test]
+    fn test_content_length() {
+        assert_eq!(content_length(0, px(10.), px(2.)), px(0.));
+        assert_eq!(content_length(1, px(10.), px(2.)), px(10.));
+        assert_eq!(content_length(3, px(10.), px(2.)), px(34.));
+    }
+
+    #[test]
+    fn test_visible_cells() {
+        assert_eq!(visible_cells(100, px(10.), px(0.), px(0.), px(25.)), 0..4);
+        assert_eq!(visible_cells(100, px(10.), px(-35.), px(0.), px(25.)), 3..7);
+        assert_eq!(visible_cells(10, px(10.), px(-80.), px(0.), px(25.)), 8..10);
+        assert_eq!(visible_cells(5, px(10.), px(0.), px(0.), px(200.)), 0..5);
+        assert_eq!(visible_cells(0, px(10.), px(0.), px(0.), px(25.)), 0..0);
+    }
+
+    #[test]
+    fn test_scroll_offset_for_cell() {
+        let offset = |offset: f32, start: f32, strategy| {
+            scroll_offset_for_cell(px(offset), px(start), px(10.), px(50.), strategy)
+        };
+
+        // Already visible
+        assert_eq!(offset(0., 20., ScrollStrategy::Top), px(0.));
+        // Before the viewport
+        assert_eq!(offset(-100., 20., ScrollStrategy::Top), px(-20.));
+        // After the viewport
+        assert_eq!(offset(0., 100., ScrollStrategy::Top), px(-60.));
+        assert_eq!(offset(0., 100., ScrollStrategy::Center), px(-80.));
+    }
+}
@@ -1,6 +1,7 @@
 //! Virtual List for render a large number of differently sized rows/columns.
 //!
-//! > NOTE: This must ensure each column width or row height.
+//! > NOTE: This must ensure each column width or row height,
+//! > or use [`v_virtual_list_measured`] to measure the row heights on the first render.
 //!
 //! Only visible range are rendered for performance reasons.
 //!
@@ -28,10 +29,26 @@ use smallvec::SmallVec;
 
 use crate::{AxisExt, scroll::ScrollbarHandle};
 
+/// A stable scroll position of a [`VirtualList`], see [`VirtualListScrollHandle::anchor`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct VirtualListAnchor {
+    /// The index of the first visible item.
+    ///
+    /// Update it to the new index of the same item after the data is changed,
+    /// e.g. find the index by the item id.
+    pub item_index: usize,
+    /// The distance that the item is scrolled over the start of the viewport.
+    pub offset: Pixels,
+}
+
 struct VirtualListScrollHandleState {
     axis: Axis,
     items_count: usize,
     pub deferred_scroll_to_item: Option<DeferredScrollToItem>,
+    /// The `(row, column)` of the cell to scroll to for [`VirtualGrid`](crate::VirtualGrid).
+    deferred_scroll_to_cell: Option<(usize, Option<usize>, ScrollStrategy)>,
+    anchor: VirtualListAnchor,
+    deferred_anchor: Option<VirtualListAnchor>,
 }
 
 /// A scroll handle for [`VirtualList`].
@@ -87,6 +104,9 @@ impl VirtualListScrollHandle {
                 axis: Axis::Vertical,
                 items_count: 0,
                 deferred_scroll_to_item: None,
+                deferred_scroll_to_cell: None,
+                anchor: VirtualListAnchor::default(),
+                deferred_anchor: None,
             })),
             base_handle: ScrollHandle::default(),
         }
@@ -118,6 +138,121 @@ impl VirtualListScrollHandle {
         let items_count = self.state.borrow().items_count;
         self.scroll_to_item(items_count.saturating_sub(1), ScrollStrategy::Top);
     }
+
+    /// Returns the anchor of the current scroll position, that is the first visible item
+    /// and the offset of the item, updated on each render.
+    ///
+    /// Unlike the scroll offset, the anchor keeps the visual position when the items
+    /// before it change their sizes, restore it by [`Self::restore_anchor`].
+    pub fn anchor(&self) -> VirtualListAnchor {
+        self.state.borrow().anchor
+    }
+
+    /// Restore the scroll position to the `anchor` on the next render,
+    /// e.g. after the data is refreshed or the tab is switched back.
+    pub fn restore_anchor(&self, anchor: VirtualListAnchor) {
+        self.state.borrow_mut().deferred_anchor = Some(anchor);
+    }
+
+    /// Scroll to the cell at the given `row` and `column` of a [`VirtualGrid`](crate::VirtualGrid).
+    pub fn scroll_to_cell(&self, row: usize, column: usize, strategy: ScrollStrategy) {
+        self.state.borrow_mut().deferred_scroll_to_cell = Some((row, Some(column), strategy));
+    }
+
+    /// Take the deferred scroll to cell, the `scroll_to_item` is treated as a row without column.
+    pub(crate) fn take_deferred_scroll_to_cell(
+        &self,
+        rows_count: usize,
+    ) -> Option<(usize, Option<usize>, ScrollStrategy)> {
+        let mut state = self.state.borrow_mut();
+        state.axis = Axis::Vertical;
+        state.items_count = rows_count;
+
+        let scroll_to_item = state
+            .deferred_scroll_to_item
+            .take()
+            .map(|item| (item.item_index + item.offset, None, item.strategy));
+        state.deferred_scroll_to_cell.take().or(scroll_to_item)
+    }
+}
+
+struct MeasureCacheState {
+    estimated_size: Size<Pixels>,
+    sizes: Vec<Option<Size<Pixels>>>,
+    /// The size of the list on the cross axis that the items are measured with.
+    cross_size: Option<Pixels>,
+}
+
+/// The measured sizes of the items for [`v_virtual_list_measured`].
+///
+/// The items are measured on the first render and cached, until they are invalidated
+/// or the width of the list is changed.
+#[derive(Clone)]
+pub struct VirtualListMeasureCache {
+    state: Rc<RefCell<MeasureCacheState>>,
+}
+
+impl VirtualListMeasureCache {
+    /// Create a new cache, the `estimated_size` is used for the items that are not measured yet.
+    pub fn new(estimated_size: Pixels) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(MeasureCacheState {
+                estimated_size: size(estimated_size, estimated_size),
+                sizes: vec![],
+                cross_size: None,
+            })),
+        }
+    }
+
+    /// Invalidate the measured size of the item at the given index, e.g. the content is changed.
+    pub fn invalidate(&self, ix: usize) {
+        if let Some(size) = self.state.borrow_mut().sizes.get_mut(ix) {
+            *size = None;
+        }
+    }
+
+    /// Invalidate the measured sizes of all the items.
+    pub fn invalidate_all(&self) {
+        self.state.borrow_mut().sizes.fill(None);
+    }
+
+    /// Returns true if the item at the given index has been measured.
+    pub fn is_measured(&self, ix: usize) -> bool {
+        self.state
+            .borrow()
+            .sizes
+            .get(ix)
+            .is_some_and(|size| size.is_some())
+    }
+
+    /// Set the cross axis size of the list, all the items are invalidated if changed.
+    fn set_cross_size(&self, cross_size: Pixels) {
+        let mut state = self.state.borrow_mut();
+        if state.cross_size != Some(cross_size) {
+            state.cross_size = Some(cross_size);
+            state.sizes.fill(None);
+        }
+    }
+
+    fn set_size(&self, ix: usize, size: Size<Pixels>) {
+        if let Some(item_size) = self.state.borrow_mut().sizes.get_mut(ix) {
+            *item_size = Some(size);
+        }
+    }
+
+    /// Returns the sizes of the `items_count` items, the estimated size for the unmeasured items.
+    fn item_sizes(&self, items_count: usize) -> Rc<Vec<Size<Pixels>>> {
+        let mut state = self.state.borrow_mut();
+        state.sizes.resize(items_count, None);
+        let estimated_size = state.estimated_size;
+        Rc::new(
+            state
+                .sizes
+                .iter()
+                .map(|size| size.unwrap_or(estimated_size))
+                .collect(),
+        )
+    }
 }
 
 /// Create a [`VirtualList`] in vertical direction.
@@ -142,6 +277,29 @@ where
     virtual_list(view, id, Axis::Vertical, item_sizes, f)
 }
 
+/// Create a [`VirtualList`] in vertical direction, the items can have different heights
+/// that are measured on the first render.
+///
+/// The measured heights are cached in the `cache`, call [`VirtualListMeasureCache::invalidate`]
+/// to measure an item again, e.g. a chat message is edited.
+///
+/// See also [`v_virtual_list`]
+pub fn v_virtual_list_measured<R, V>(
+    view: Entity<V>,
+    id: impl Into<ElementId>,
+    items_count: usize,
+    cache: &VirtualListMeasureCache,
+    f: impl 'static + Fn(&mut V, Range<usize>, &mut Window, &mut Context<V>) -> Vec<R>,
+) -> VirtualList
+where
+    R: IntoElement,
+    V: Render,
+{
+    let mut list = virtual_list(view, id, Axis::Vertical, cache.item_sizes(items_count), f);
+    list.measure_cache = Some(cache.clone());
+    list
+}
+
 /// Create a [`VirtualList`] in horizontal direction.
 ///
 /// The `item_sizes` is the size of each column,
@@ -197,6 +355,8 @@ where
         item_sizes,
         render_items: Box::new(render_range),
         sizing_behavior: ListSizingBehavior::default(),
+        measure_cache: None,
+        sticky_indices: vec![],
     }
 }
 
@@ -212,6 +372,8 @@ pub struct VirtualList {
         dyn for<'a> Fn(Range<usize>, &'a mut Window, &'a mut App) -> SmallVec<[AnyElement; 64]>,
     >,
     sizing_behavior: ListSizingBehavior,
+    measure_cache: Option<VirtualListMeasureCache>,
+    sticky_indices: Vec<usize>,
 }
 
 impl Styled for VirtualList {
@@ -233,6 +395,19 @@ impl VirtualList {
         self
     }
 
+    /// Set the indices of the sticky items, default is empty.
+    ///
+    /// The sticky item pins to the start of the viewport after scrolled over,
+    /// until pushed out by the next sticky item, e.g. the date separators of a timeline.
+    ///
+    /// The sticky item is painted above the other items, so it should have a background.
+    pub fn sticky_items(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.sticky_indices = indices.into_iter().collect();
+        self.sticky_indices.sort_unstable();
+        self.sticky_indices.dedup();
+        self
+    }
+
     /// Specify for table.
     ///
     /// Table is special, because the `scroll_handle` is based on Table head (That is not a virtual list).
@@ -290,6 +465,64 @@ impl VirtualList {
         scroll_offset
     }
 
+    /// Measure the visible items that are not measured yet by the `cache`,
+    /// then update the `size_layout` with the measured sizes.
+    fn measure_visible_items(
+        &self,
+        cache: &VirtualListMeasureCache,
+        size_layout: &mut ItemSizeLayout,
+        content_bounds: &Bounds<Pixels>,
+        padding_start: Pixels,
+        gap: Pixels,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        // The measured items may be smaller than the estimated size,
+        // so measure again for the items that become visible.
+        const MAX_MEASURE_PASSES: usize = 3;
+
+        let cross_size = match self.axis {
+            Axis::Horizontal => content_bounds.size.height,
+            Axis::Vertical => content_bounds.size.width,
+        };
+        cache.set_cross_size(cross_size);
+
+        for _ in 0..MAX_MEASURE_PASSES {
+            let unmeasured = visible_range(
+                &size_layout.sizes,
+                self.scroll_handle.offset().along(self.axis),
+                padding_start,
+                content_bounds.size.along(self.axis),
+            )
+            .filter(|ix| !cache.is_measured(*ix))
+            .collect::<Vec<_>>();
+            if unmeasured.is_empty() {
+                break;
+            }
+
+            let available_space = match self.axis {
+                Axis::Horizontal => size(
+                    AvailableSpace::MinContent,
+                    AvailableSpace::Definite(cross_size),
+                ),
+                Axis::Vertical => size(
+                    AvailableSpace::Definite(cross_size),
+                    AvailableSpace::MinContent,
+                ),
+            };
+            for ix in unmeasured {
+                let Some(mut item) = (self.render_items)(ix..ix + 1, window, cx).pop() else {
+                    continue;
+                };
+                cache.set_size(ix, item.layout_as_root(available_space, window, cx));
+            }
+
+            size_layout.items_sizes = cache.item_sizes(self.items_count);
+            let longest_item_size = size_layout.content_size;
+            size_layout.update_sizes(self.axis, gap, longest_item_size);
+        }
+    }
+
     /// Ref from: https://github.com/zed-industries/zed/blob/83f9f9d9e3f5914392cab9a09e3472711a1d7b38/crates/gpui/src/elements/uniform_list.rs#L660
     fn measure_item(
         &self,
@@ -332,6 +565,53 @@ pub struct ItemSizeLayout {
     last_layout_bounds: Bounds<Pixels>,
 }
 
+impl ItemSizeLayout {
+    /// Update the sizes and origins of the items by the `items_sizes`,
+    /// the `gap` is included in the size of each item except the last one.
+    fn update_sizes(&mut self, axis: Axis, gap: Pixels, longest_item_size: Size<Pixels>) {
+        let items_count = self.items_sizes.len();
+
+        // Prepare each item's size by axis
+        self.sizes = self
+            .items_sizes
+            .iter()
+            .enumerate()
+            .map(|(i, size)| {
+                let size = size.along(axis);
+                if i + 1 == items_count {
+                    size
+                } else {
+                    size + gap
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Prepare each item's origin by axis
+        self.origins = self
+            .sizes
+            .iter()
+            .scan(px(0.), |cumulative, size| {
+                let origin = *cumulative;
+                *cumulative += *size;
+                Some(origin)
+            })
+            .collect::<Vec<_>>();
+
+        let total = px(self.sizes.iter().map(|size| size.as_f32()).sum::<f32>());
+        self.content_size = if axis.is_horizontal() {
+            Size {
+                width: total,
+                height: longest_item_size.height,
+            }
+        } else {
+            Size {
+                width: longest_item_size.width,
+                height: total,
+            }
+        };
+    }
+}
+
 impl IntoElement for VirtualList {
     type Element = Self;
 
@@ -383,58 +663,7 @@ impl Element for VirtualList {
 
                         if state.items_sizes != self.item_sizes {
                             state.items_sizes = self.item_sizes.clone();
-                            // Prepare each item's size by axis
-                            state.sizes = self
-                                .item_sizes
-                                .iter()
-                                .enumerate()
-                                .map(|(i, size)| {
-                                    let size = size.along(self.axis);
-                                    if i + 1 == self.items_count {
-                                        size
-                                    } else {
-                                        size + gap
-                                    }
-                                })
-                                .collect::<Vec<_>>();
-
-                            // Prepare each item's origin by axis
-                            state.origins = state
-                                .sizes
-                                .iter()
-                                .scan(px(0.), |cumulative, size| match self.axis {
-                                    Axis::Horizontal => {
-                                        let x = *cumulative;
-                                        *cumulative += *size;
-                                        Some(x)
-                                    }
-                                    Axis::Vertical => {
-                                        let y = *cumulative;
-                                        *cumulative += *size;
-                                        Some(y)
-                                    }
-                                })
-                                .collect::<Vec<_>>();
-
-                            state.content_size = if self.axis.is_horizontal() {
-                                Size {
-                                    width: px(state
-                                        .sizes
-                                        .iter()
-                                        .map(|size| size.as_f32())
-                                        .sum::<f32>()),
-                                    height: longest_item_size.height,
-                                }
-                            } else {
-                                Size {
-                                    width: longest_item_size.width,
-                                    height: px(state
-                                        .sizes
-                                        .iter()
-                                        .map(|size| size.as_f32())
-                                        .sum::<f32>()),
-                                }
-                            };
+                            state.update_sizes(self.axis, gap, longest_item_size);
                         }
 
                         (state.clone(), state)
@@ -535,9 +764,6 @@ impl Element for VirtualList {
             .padding
             .to_pixels(bounds.size.into(), window.rem_size());
 
-        let item_sizes = &layout.size_layout.sizes;
-        let item_origins = &layout.size_layout.origins;
-
         let content_bounds = Bounds::from_corners(
             bounds.origin
                 + point(
@@ -550,6 +776,31 @@ impl Element for VirtualList {
                     border_widths.bottom + paddings.bottom,
                 ),
         );
+        let padding_start = match self.axis {
+            Axis::Horizontal => paddings.left,
+            Axis::Vertical => paddings.top,
+        };
+
+        if let Some(cache) = self.measure_cache.as_ref() {
+            let rem_size = window.rem_size();
+            let font_size = window.text_style().font_size.to_pixels(rem_size);
+            let gap = style
+                .gap
+                .along(self.axis)
+                .to_pixels(font_size.into(), rem_size);
+            self.measure_visible_items(
+                cache,
+                &mut layout.size_layout,
+                &content_bounds,
+                padding_start,
+                gap,
+                window,
+                cx,
+            );
+        }
+
+        let item_sizes = &layout.size_layout.sizes;
+        let item_origins = &layout.size_layout.origins;
 
         // Update scroll_handle with the item bounds
         let items_bounds = item_origins
@@ -578,6 +829,13 @@ impl Element for VirtualList {
         scroll_state.items_count = self.items_count;
 
         let mut scroll_offset = self.scroll_handle.offset();
+        if let Some(anchor) = scroll_state.deferred_anchor.take() {
+            let offset = anchor_scroll_offset(item_origins, anchor);
+            match axis {
+                Axis::Horizontal => scroll_offset.x = offset,
+                Axis::Vertical => scroll_offset.y = offset,
+            }
+        }
         if let Some(scroll_to_item) = scroll_state.deferred_scroll_to_item.take() {
             scroll_offset = self.scroll_to_deferred_item(
                 scroll_offset,
@@ -596,6 +854,8 @@ impl Element for VirtualList {
         if scroll_offset != self.scroll_handle.offset() {
             self.scroll_handle.set_offset(scroll_offset);
         }
+        scroll_state.anchor = anchor_at(item_origins, scroll_offset.along(axis));
+        drop(scroll_state);
 
         self.base.interactivity().prepaint(
             global_id,
@@ -624,70 +884,23 @@ impl Element for VirtualList {
                         }
                     }
 
-                    let (first_visible_element_ix, last_visible_element_ix) = match self.axis {
-                        Axis::Horizontal => {
-                            let mut cumulative_size = px(0.);
-                            let mut first_visible_element_ix = 0;
-                            for (i, &size) in item_sizes.iter().enumerate() {
-                                cumulative_size += size;
-                                if cumulative_size > -(scroll_offset.x + paddings.left) {
-                                    first_visible_element_ix = i;
-                                    break;
-                                }
-                            }
-
-                            cumulative_size = px(0.);
-                            let mut last_visible_element_ix = 0;
-                            for (i, &size) in item_sizes.iter().enumerate() {
-                                cumulative_size += size;
-                                if cumulative_size > (-scroll_offset.x + content_bounds.size.width)
-                                {
-                                    last_visible_element_ix = i + 1;
-                                    break;
-                                }
-                            }
-                            if last_visible_element_ix == 0 {
-                                last_visible_element_ix = self.items_count;
-                            } else {
-                                last_visible_element_ix += 1;
-                            }
-                            (first_visible_element_ix, last_visible_element_ix)
-                        }
-                        Axis::Vertical => {
-                            let mut cumulative_size = px(0.);
-                            let mut first_visible_element_ix = 0;
-                            for (i, &size) in item_sizes.iter().enumerate() {
-                                cumulative_size += size;
-                                if cumulative_size > -(scroll_offset.y + paddings.top) {
-                                    first_visible_element_ix = i;
-                                    break;
-                                }
-                            }
-
-                            cumulative_size = px(0.);
-                            let mut last_visible_element_ix = 0;
-                            for (i, &size) in item_sizes.iter().enumerate() {
-                                cumulative_size += size;
-                                if cumulative_size > (-scroll_offset.y + content_bounds.size.height)
-                                {
-                                    last_visible_element_ix = i + 1;
-                                    break;
-                                }
-                            }
-                            if last_visible_element_ix == 0 {
-                                last_visible_element_ix = self.items_count;
-                            } else {
-                                last_visible_element_ix += 1;
-                            }
-                            (first_visible_element_ix, last_visible_element_ix)
-                        }
-                    };
-
-                    let visible_range = first_visible_element_ix
-                        ..cmp::min(last_visible_element_ix, self.items_count);
+                    let visible_range = visible_range(
+                        item_sizes,
+                        scroll_offset.along(self.axis),
+                        padding_start,
+                        content_bounds.size.along(self.axis),
+                    );
 
                     let items = (self.render_items)(visible_range.clone(), window, cx);
 
+                    let item_sizes_along = &layout.size_layout.items_sizes;
+                    let sticky_item = sticky_item(
+                        &self.sticky_indices,
+                        item_origins,
+                        scroll_offset.along(self.axis) + padding_start,
+                        |ix| item_sizes_along[ix].along(self.axis),
+                    );
+
                     let content_mask = ContentMask { bounds };
                     window.with_content_mask(Some(content_mask), |window| {
                         for (mut item, ix) in items.into_iter().zip(visible_range.clone()) {
@@ -717,6 +930,37 @@ impl Element for VirtualList {
                             item.prepaint_at(item_origin, window, cx);
                             layout.items.push(item);
                         }
+
+                        // Paint the sticky item at last to be above the other items.
+                        if let Some((ix, position)) = sticky_item {
+                            let Some(mut item) = (self.render_items)(ix..ix + 1, window, cx).pop()
+                            else {
+                                return;
+                            };
+
+                            let (item_origin, available_space) = match self.axis {
+                                Axis::Horizontal => (
+                                    content_bounds.origin
+                                        + point(position - padding_start, scroll_offset.y),
+                                    size(
+                                        AvailableSpace::Definite(item_sizes[ix]),
+                                        AvailableSpace::Definite(content_bounds.size.height),
+                                    ),
+                                ),
+                                Axis::Vertical => (
+                                    content_bounds.origin
+                                        + point(scroll_offset.x, position - padding_start),
+                                    size(
+                                        AvailableSpace::Definite(content_bounds.size.width),
+                                        AvailableSpace::Definite(item_sizes[ix]),
+                                    ),
+                                ),
+                            };
+
+                            item.layout_as_root(available_space, window, cx);
+                            item.prepaint_at(item_origin, window, cx);
+                            layout.items.push(item);
+                        }
                     });
                 }
 
@@ -750,3 +994,186 @@ impl Element for VirtualList {
         )
     }
 }
+
+/// Returns the range of the items to render with the `sizes`,
+/// the last visible item is followed by one more item.
+fn visible_range(
+    sizes: &[Pixels],
+    scroll_offset: Pixels,
+    padding_start: Pixels,
+    viewport_size: Pixels,
+) -> Range<usize> {
+    let items_count = sizes.len();
+
+    let mut cumulative_size = px(0.);
+    let mut first_visible_element_ix = 0;
+    for (i, &size) in sizes.iter().enumerate() {
+        cumulative_size += size;
+        if cumulative_size > -(scroll_offset + padding_start) {
+            first_visible_element_ix = i;
+            break;
+        }
+    }
+
+    cumulative_size = px(0.);
+    let mut last_visible_element_ix = 0;
+    for (i, &size) in sizes.iter().enumerate() {
+        cumulative_size += size;
+        if cumulative_size > (-scroll_offset + viewport_size) {
+            last_visible_element_ix = i + 1;
+            break;
+        }
+    }
+    if last_visible_element_ix == 0 {
+        last_visible_element_ix = items_count;
+    } else {
+        last_visible_element_ix += 1;
+    }
+
+    first_visible_element_ix..cmp::min(last_visible_element_ix, items_count)
+}
+
+/// Returns the anchor of the item at the start of the viewport with the `scroll_offset`.
+fn anchor_at(origins: &[Pixels], scroll_offset: Pixels) -> VirtualListAnchor {
+    let item_index = origins
+        .partition_point(|origin| *origin <= -scroll_offset)
+        .saturating_sub(1);
+    let offset = origins
+        .get(item_index)
+        .map_or(px(0.), |origin| -scroll_offset - *origin);
+
+    VirtualListAnchor { item_index, offset }
+}
+
+/// Returns the scroll offset to restore the `anchor`, the index is clamped to the last item.
+fn anchor_scroll_offset(origins: &[Pixels], anchor: VirtualListAnchor) -> Pixels {
+    let Some(origin) = origins.get(anchor.item_index).or(origins.last()).copied() else {
+        return px(0.);
+    };
+
+    -(origin + anchor.offset)
+}
+
+/// Returns the sticky item to pin and its position relative to the start of the viewport.
+///
+/// The `sticky_indices` must be sorted, and the `scroll_offset` includes the start padding.
+fn sticky_item(
+    sticky_indices: &[usize],
+    origins: &[Pixels],
+    scroll_offset: Pixels,
+    item_size: impl Fn(usize) -> Pixels,
+) -> Option<(usize, Pixels)> {
+    let mut sticky_indices = sticky_indices
+        .iter()
+        .copied()
+        .filter(|ix| *ix < origins.len())
+        .peekable();
+
+    let mut current = None;
+    while let Some(ix) = sticky_indices.next_if(|ix| origins[*ix] + scroll_offset <= px(0.)) {
+        current = Some(ix);
+    }
+    let ix = current?;
+
+    // Push out by the next sticky item.
+    let position = match sticky_indices.peek() {
+        Some(next_ix) => (origins[*next_ix] + scroll_offset - item_size(ix)).min(px(0.)),
+        None => px(0.),
+    };
+
+    Some((ix, position))
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, size};
+
+    use super::{
+        VirtualListAnchor, VirtualListMeasureCache, anchor_at, anchor_scroll_offset, sticky_item,
+        visible_range,
+    };
+
+    #[test]
+    fn test_anchor() {
+        let origins = (0..10).map(|i| px(i as f32 * 10.)).collect::<Vec<_>>();
+        let anchor = |ix: usize, offset: f32| VirtualListAnchor {
+            item_index: ix,
+            offset: px(offset),
+        };
+
+        assert_eq!(anchor_at(&origins, px(0.)), anchor(0, 0.));
+        assert_eq!(anchor_at(&origins, px(-25.)), anchor(2, 5.));
+        assert_eq!(anchor_at(&origins, px(-30.)), anchor(3, 0.));
+        assert_eq!(anchor_at(&[], px(-30.)), anchor(0, 0.));
+
+        assert_eq!(anchor_scroll_offset(&origins, anchor(2, 5.)), px(-25.));
+        // Clamp to the last item
+        assert_eq!(anchor_scroll_offset(&origins, anchor(20, 5.)), px(-95.));
+        assert_eq!(anchor_scroll_offset(&[], anchor(2, 5.)), px(0.));
+
+        // The anchor keeps the position after the items before it are resized.
+        let anchor = anchor_at(&origins, px(-25.));
+        let origins = (0..10).map(|i| px(i as f32 * 20.)).collect::<Vec<_>>();
+        assert_eq!(anchor_scroll_offset(&origins, anchor), px(-45.));
+    }
+
+    #[test]
+    fn test_sticky_item() {
+        // 10 items of 10px, the sticky items are 2 and 5.
+        let origins = (0..10).map(|i| px(i as f32 * 10.)).collect::<Vec<_>>();
+        let sticky = |offset: f32| sticky_item(&[2, 5, 20], &origins, px(offset), |_| px(10.));
+
+        assert_eq!(sticky(0.), None);
+        assert_eq!(sticky(-15.), None);
+        assert_eq!(sticky(-20.), Some((2, px(0.))));
+        assert_eq!(sticky(-35.), Some((2, px(0.))));
+        // Pushed by the item 5
+        assert_eq!(sticky(-45.), Some((2, px(-5.))));
+        assert_eq!(sticky(-50.), Some((5, px(0.))));
+        assert_eq!(sticky(-90.), Some((5, px(0.))));
+        assert_eq!(sticky_item(&[], &origins, px(-50.), |_| px(10.)), None);
+    }
+
+    #[test]
+    fn test_visible_range() {
+        let sizes = vec![px(10.); 10];
+        assert_eq!(visible_range(&sizes, px(0.), px(0.), px(25.)), 0..4);
+        assert_eq!(visible_range(&sizes, px(-35.), px(0.), px(25.)), 3..8);
+        assert_eq!(visible_range(&sizes, px(-80.), px(0.), px(25.)), 8..10);
+        assert_eq!(visible_range(&sizes, px(0.), px(0.), px(200.)), 0..10);
+        assert_eq!(visible_range(&[], px(0.), px(0.), px(25.)), 0..0);
+    }
+
+    #[test]
+    fn test_measure_cache() {
+        let cache = VirtualListMeasureCache::new(px(20.));
+        assert_eq!(*cache.item_sizes(2), vec![size(px(20.), px(20.)); 2]);
+
+        cache.set_size(1, size(px(100.), px(50.)));
+        assert!(cache.is_measured(1));
+        assert!(!cache.is_measured(0));
+        assert_eq!(
+            *cache.item_sizes(3),
+            vec![
+                size(px(20.), px(20.)),
+                size(px(100.), px(50.)),
+                size(px(20.), px(20.))
+            ]
+        );
+
+        cache.invalidate(1);
+        assert!(!cache.is_measured(1));
+
+        // Changing the width invalidates all the items.
+        cache.set_cross_size(px(100.));
+        cache.set_size(0, size(px(100.), px(30.)));
+        cache.set_cross_size(px(100.));
+        assert!(cache.is_measured(0));
+        cache.set_cross_size(px(200.));
+        assert!(!cache.is_measured(0));
+
+        // Out of range is ignored.
+        cache.set_size(10, size(px(100.), px(30.)));
+        assert_eq!(cache.item_sizes(1).len(), 1);
+    }
+}
@@ -1,22 +1,52 @@
+#[cfg(feature = "wry")]
 use std::{ops::Deref, rc::Rc};
 
+#[cfg(feature = "wry")]
 use wry::{
     Rect,
     dpi::{self, LogicalSize},
 };
 
 use gpui::{
-    App, Bounds, ContentMask, DismissEvent, Element, ElementId, Entity, EventEmitter, FocusHandle,
-    Focusable, GlobalElementId, Hitbox, InteractiveElement, IntoElement, LayoutId, MouseDownEvent,
-    ParentElement as _, Pixels, Render, Size, Style, Styled as _, Window, canvas, div,
+    App, Bounds, DismissEvent, EventEmitter, FocusHandle, Focusable, InteractiveElement,
+    IntoElement, ParentElement as _, Pixels, Render, SharedString, Styled as _, Window, canvas,
+    div, prelude::FluentBuilder as _,
 };
+#[cfg(feature = "wry")]
+use gpui::{
+    ContentMask, Element, ElementId, Entity, GlobalElementId, Hitbox, LayoutId, MouseDownEvent,
+    Size, Style,
+};
+use gpui_component::{
+    ActiveTheme as _, Icon, IconName, Sizable as _, StyledExt as _,
+    button::{Button, ButtonVariants as _},
+    v_flex,
+};
+use rust_i18n::t;
+
+rust_i18n::i18n!("locales", fallback = "en");
+
+/// Returns true if the native webview is supported by this build.
+///
+/// This is `false` when the `wry` feature is disabled. The wry WebView may still fail to
+/// initialize at runtime (e.g. Linux without WebKitGTK), use [`WebView::from_result`] to
+/// fall back to the placeholder in that case.
+pub fn is_supported() -> bool {
+    cfg!(feature = "wry")
+}
 
 /// A webview based on wry WebView.
 ///
+/// When the `wry` feature is disabled or the wry WebView failed to initialize,
+/// the WebView renders a placeholder with the URL and an "Open in Browser" button,
+/// so that the shared code paths can still compile and run.
+///
 /// [experimental]
 pub struct WebView {
     focus_handle: FocusHandle,
-    webview: Rc<wry::WebView>,
+    #[cfg(feature = "wry")]
+    webview: Option<Rc<wry::WebView>>,
+    url: Option<SharedString>,
     visible: bool,
     bounds: Bounds<Pixels>,
 }
@@ -29,6 +59,7 @@ impl Drop for WebView {
 
 impl WebView {
     /// Create a new WebView from a wry WebView.
+    #[cfg(feature = "wry")]
     pub fn new(webview: wry::WebView, _: &mut Window, cx: &mut App) -> Self {
         let _ = webview.set_bounds(Rect::default());
 
@@ -36,20 +67,69 @@ impl WebView {
             focus_handle: cx.focus_handle(),
             visible: true,
             bounds: Bounds::default(),
-            webview: Rc::new(webview),
+            url: None,
+            webview: Some(Rc::new(webview)),
+        }
+    }
+
+    /// Create a new WebView from the result of building a wry WebView.
+    ///
+    /// If the wry WebView failed to initialize, a [`WebView::fallback`] will be used.
+    #[cfg(feature = "wry")]
+    pub fn from_result(
+        result: Result<wry::WebView, wry::Error>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        match result {
+            Ok(webview) => Self::new(webview, window, cx),
+            Err(err) => {
+                log::warn!("failed to initialize webview, use fallback instead: {err}");
+                Self::fallback(window, cx)
+            }
+        }
+    }
+
+    /// Create a fallback WebView that renders a placeholder instead of the web content.
+    pub fn fallback(_: &mut Window, cx: &mut App) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            visible: true,
+            bounds: Bounds::default(),
+            url: None,
+            #[cfg(feature = "wry")]
+            webview: None,
         }
     }
 
+    /// Returns true if this WebView is a fallback without the native webview.
+    #[cfg(feature = "wry")]
+    pub fn is_fallback(&self) -> bool {
+        self.webview.is_none()
+    }
+
+    /// Returns true if this WebView is a fallback without the native webview.
+    #[cfg(not(feature = "wry"))]
+    pub fn is_fallback(&self) -> bool {
+        true
+    }
+
     /// Show the webview.
     pub fn show(&mut self) {
-        let _ = self.webview.set_visible(true);
+        #[cfg(feature = "wry")]
+        if let Some(webview) = self.webview.as_ref() {
+            let _ = webview.set_visible(true);
+        }
         self.visible = true;
     }
 
     /// Hide the webview.
     pub fn hide(&mut self) {
-        _ = self.webview.focus_parent();
-        _ = self.webview.set_visible(false);
+        #[cfg(feature = "wry")]
+        if let Some(webview) = self.webview.as_ref() {
+            _ = webview.focus_parent();
+            _ = webview.set_visible(false);
+        }
         self.visible = false;
     }
 
@@ -65,25 +145,91 @@ impl WebView {
 
     /// Go back in the webview history.
     pub fn back(&mut self) -> anyhow::Result<()> {
-        Ok(self.webview.evaluate_script("history.back();")?)
+        #[cfg(feature = "wry")]
+        if let Some(webview) = self.webview.as_ref() {
+            webview.evaluate_script("history.back();")?;
+        }
+        Ok(())
     }
 
     /// Load a URL in the webview.
     pub fn load_url(&mut self, url: &str) {
-        let _ = self.webview.load_url(url);
+        self.url = Some(SharedString::from(url.to_string()));
+        #[cfg(feature = "wry")]
+        if let Some(webview) = self.webview.as_ref() {
+            let _ = webview.load_url(url);
+        }
+    }
+
+    /// Get the last URL loaded by [`WebView::load_url`].
+    pub fn url(&self) -> Option<&SharedString> {
+        self.url.as_ref()
+    }
+
+    /// Get the raw wry webview, returns `None` if this is a fallback WebView.
+    #[cfg(feature = "wry")]
+    pub fn try_raw(&self) -> Option<&wry::WebView> {
+        self.webview.as_deref()
     }
 
     /// Get the raw wry webview.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a fallback WebView, use [`WebView::try_raw`] instead.
+    #[cfg(feature = "wry")]
     pub fn raw(&self) -> &wry::WebView {
-        &self.webview
+        self.try_raw()
+            .expect("the WebView is a fallback without wry WebView")
+    }
+
+    fn render_fallback(&self, _: &mut Window, cx: &mut gpui::Context<Self>) -> impl IntoElement {
+        let url = self.url.clone();
+
+        v_flex()
+            .size_full()
+            .items_center()
+            .justify_center()
+            .gap_3()
+            .p_4()
+            .bg(cx.theme().muted)
+            .text_color(cx.theme().muted_foreground)
+            .child(Icon::new(IconName::Globe).large())
+            .child(
+                div()
+                    .text_sm()
+                    .child(t!("WebView.not_available").to_string()),
+            )
+            .when_some(url, |this, url| {
+                this.child(
+                    div()
+                        .max_w_full()
+                        .text_xs()
+                        .font_family(cx.theme().mono_font_family.clone())
+                        .truncate()
+                        .child(url.clone()),
+                )
+                .child(
+                    Button::new("open-in-browser")
+                        .small()
+                        .outline()
+                        .icon(IconName::ExternalLink)
+                        .label(t!("WebView.open_in_browser").to_string())
+                        .on_click(move |_, _, cx| cx.open_url(&url)),
+                )
+            })
     }
 }
 
+#[cfg(feature = "wry")]
 impl Deref for WebView {
     type Target = wry::WebView;
 
+    /// # Panics
+    ///
+    /// Panics if this is a fallback WebView.
     fn deref(&self) -> &Self::Target {
-        &self.webview
+        self.raw()
     }
 }
 
@@ -101,8 +247,6 @@ impl Render for WebView {
         window: &mut gpui::Window,
         cx: &mut gpui::Context<Self>,
     ) -> impl IntoElement {
-        let view = cx.entity().clone();
-
         div()
             .track_focus(&self.focus_handle)
             .size_full()
@@ -115,16 +259,26 @@ impl Render for WebView {
                 .absolute()
                 .size_full()
             })
-            .child(WebViewElement::new(self.webview.clone(), view, window, cx))
+            .map(|this| {
+                #[cfg(feature = "wry")]
+                if let Some(webview) = self.webview.clone() {
+                    let view = cx.entity().clone();
+                    return this.child(WebViewElement::new(webview, view, window, cx));
+                }
+
+                this.child(self.render_fallback(window, cx))
+            })
     }
 }
 
 /// A webview element can display a wry webview.
+#[cfg(feature = "wry")]
 pub struct WebViewElement {
     parent: Entity<WebView>,
     view: Rc<wry::WebView>,
 }
 
+#[cfg(feature = "wry")]
 impl WebViewElement {
     /// Create a new webview element from a wry WebView.
     pub fn new(
@@ -137,6 +291,7 @@ impl WebViewElement {
     }
 }
 
+#[cfg(feature = "wry")]
 impl IntoElement for WebViewElement {
     type Element = WebViewElement;
 
@@ -145,6 +300,7 @@ impl IntoElement for WebViewElement {
     }
 }
 
+#[cfg(feature = "wry")]
 impl Element for WebViewElement {
     type RequestLayoutState = ();
     type PrepaintState = Option<Hitbox>;
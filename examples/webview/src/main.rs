@@ -15,6 +15,10 @@ pub struct Example {
 impl Example {
     pub fn new(window: &mut Window, cx: &mut App) -> Entity<Self> {
         let webview = cx.new(|cx| {
+            if !gpui_wry::is_supported() {
+                return WebView::fallback(window, cx);
+            }
+
             let builder = wry::WebViewBuilder::new();
             #[cfg(any(debug_assertions, feature = "inspector"))]
             let builder = builder.with_devtools(true);
@@ -33,7 +37,7 @@ impl Example {
                 // TODO: How to initialize this fixed?
                 let fixed = gtk::Fixed::builder().build();
                 fixed.show_all();
-                builder.build_gtk(&fixed).unwrap()
+                builder.build_gtk(&fixed)
             };
             #[cfg(any(
                 target_os = "windows",
@@ -45,10 +49,10 @@ impl Example {
                 use raw_window_handle::HasWindowHandle;
 
                 let window_handle = window.window_handle().expect("No window handle");
-                builder.build_as_child(&window_handle).unwrap()
+                builder.build_as_child(&window_handle)
             };
 
-            WebView::new(webview, window, cx)
+            WebView::from_result(webview, window, cx)
         });
 
         let address_input = cx.new(|cx| {